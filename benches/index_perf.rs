@@ -42,6 +42,7 @@
 
 use coding_agent_search::indexer::{IndexOptions, run_index};
 use coding_agent_search::search::tantivy::index_dir;
+use coding_agent_search::search::vector_index::Quantization;
 use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
 use std::fs;
 use std::io::Write;
@@ -95,6 +96,9 @@ fn bench_index_full(c: &mut Criterion) {
         data_dir: data_dir.clone(),
         semantic: false,
         embedder: "fastembed".to_string(),
+        ann: false,
+        quantization: Quantization::F32,
+        redact_secrets: false,
         progress: None,
     };
 
@@ -132,6 +136,9 @@ fn bench_streaming_vs_batch(c: &mut Criterion) {
             data_dir: data_dir.clone(),
             semantic: false,
             embedder: "fastembed".to_string(),
+            ann: false,
+            quantization: Quantization::F32,
+            redact_secrets: false,
             progress: None,
         };
 
@@ -197,6 +204,9 @@ fn bench_channel_overhead(c: &mut Criterion) {
         data_dir: data_dir.clone(),
         semantic: false,
         embedder: "fastembed".to_string(),
+        ann: false,
+        quantization: Quantization::F32,
+        redact_secrets: false,
         progress: None,
     };
 
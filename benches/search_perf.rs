@@ -166,6 +166,7 @@ fn make_bench_hit(id: &str, score: f32) -> SearchHit {
         source_id: "local".to_string(),
         origin_kind: "local".to_string(),
         origin_host: None,
+        parent: None,
     }
 }
 
@@ -309,6 +310,7 @@ fn bench_vector_index_search_50k_filtered(c: &mut Criterion) {
         roles: None,
         created_from: None,
         created_to: None,
+        message_ids: None,
     };
 
     c.bench_function("vector_index_search_50k_filtered", |b| {
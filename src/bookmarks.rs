@@ -6,6 +6,7 @@
 use anyhow::{Context, Result};
 use rusqlite::{Connection, OptionalExtension, params};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -135,6 +136,11 @@ impl BookmarkStore {
         Self::open(&path)
     }
 
+    /// Open the bookmark store that lives alongside an explicit data dir
+    pub fn open_in(data_dir: &Path) -> Result<Self> {
+        Self::open(&bookmarks_path_for(data_dir))
+    }
+
     /// Add a new bookmark
     pub fn add(&self, bookmark: &Bookmark) -> Result<i64> {
         self.conn.execute(
@@ -268,6 +274,34 @@ impl BookmarkStore {
         Ok(exists)
     }
 
+    /// Find the bookmark for a `source_path` + line, if any
+    pub fn find(&self, source_path: &str, line_number: Option<usize>) -> Result<Option<Bookmark>> {
+        self.conn
+            .query_row(
+                "SELECT id, title, source_path, line_number, agent, workspace, note, tags, created_at, updated_at, snippet
+                 FROM bookmarks WHERE source_path = ?1 AND line_number IS ?2
+                 ORDER BY created_at DESC LIMIT 1",
+                params![source_path, line_number.map(|n| n as i64)],
+                row_to_bookmark,
+            )
+            .optional()
+            .context("querying bookmark by location")
+    }
+
+    /// Collect the session paths of bookmarks, optionally restricted to those
+    /// carrying at least one of `tags` (case-insensitive).
+    ///
+    /// Used by `cass search --bookmarked/--tag` to build a session filter.
+    pub fn source_paths(&self, tags: &[String]) -> Result<HashSet<String>> {
+        let paths = self
+            .list(None)?
+            .into_iter()
+            .filter(|b| tags.is_empty() || tags.iter().any(|t| b.has_tag(t)))
+            .map(|b| b.source_path)
+            .collect();
+        Ok(paths)
+    }
+
     /// Export all bookmarks to JSON
     pub fn export_json(&self) -> Result<String> {
         let bookmarks = self.list(None)?;
@@ -349,6 +383,11 @@ pub fn default_bookmarks_path() -> PathBuf {
     )
 }
 
+/// Bookmarks database path for an explicit data dir (honors `--data-dir`)
+pub fn bookmarks_path_for(data_dir: &Path) -> PathBuf {
+    data_dir.join("bookmarks.db")
+}
+
 /// SQL schema for bookmarks database
 const SCHEMA: &str = r"
 CREATE TABLE IF NOT EXISTS bookmarks (
@@ -499,6 +538,54 @@ mod tests {
         assert!(!store.is_bookmarked("/other.rs", Some(10)).unwrap());
     }
 
+    #[test]
+    fn test_find_by_location() {
+        let (store, _dir) = test_store();
+
+        let id = store
+            .add(&Bookmark::new("X", "/file.rs", "a", "/w").with_line(10))
+            .unwrap();
+        store
+            .add(&Bookmark::new("Whole session", "/file.rs", "a", "/w"))
+            .unwrap();
+
+        assert_eq!(store.find("/file.rs", Some(10)).unwrap().unwrap().id, id);
+        assert_eq!(
+            store.find("/file.rs", None).unwrap().unwrap().title,
+            "Whole session"
+        );
+        assert!(store.find("/file.rs", Some(11)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_source_paths_by_tag() {
+        let (store, _dir) = test_store();
+
+        store
+            .add(&Bookmark::new("A", "/a.jsonl", "a", "/w").with_tags("good, auth"))
+            .unwrap();
+        store
+            .add(&Bookmark::new("B", "/b.jsonl", "a", "/w").with_tags("Auth"))
+            .unwrap();
+        store
+            .add(&Bookmark::new("C", "/c.jsonl", "a", "/w"))
+            .unwrap();
+
+        assert_eq!(store.source_paths(&[]).unwrap().len(), 3);
+
+        let auth = store.source_paths(&["auth".to_string()]).unwrap();
+        assert_eq!(auth.len(), 2);
+        assert!(auth.contains("/a.jsonl"));
+        assert!(auth.contains("/b.jsonl"));
+
+        assert!(
+            store
+                .source_paths(&["missing".to_string()])
+                .unwrap()
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_export_import() {
         let (store1, _dir1) = test_store();
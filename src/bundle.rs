@@ -0,0 +1,850 @@
+//! Portable session bundles (`cass bundle export` / `cass bundle import`).
+//!
+//! A bundle moves conversations between machines that cannot reach each other
+//! over SSH, such as air-gapped CI runners. It is one file: [`MAGIC`], a JSON
+//! [`BundleHeader`] line, then the normalized conversations as gzip-compressed
+//! JSON lines. With a password the compressed body is sealed with AES-256-GCM
+//! under an Argon2id key, and the header line is authenticated along with it.
+//!
+//! Importing indexes the conversations under their own source id and keeps an
+//! unencrypted copy at `<data_dir>/bundles/<source_id>/<bundle_id>.cassbundle`,
+//! which `cass index` re-reads so rebuilds keep them. Conversations are keyed by
+//! `(source_id, agent, external_id)`, so importing the same or an overlapping
+//! bundle again only adds the messages that are new.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use base64::prelude::*;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use rand::{RngCore, rngs::OsRng};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::connectors::NormalizedConversation;
+use crate::encryption::{Argon2Params, aes_gcm_decrypt, aes_gcm_encrypt, argon2id_hash};
+use crate::import::{LexicalStatus, validate_source_id};
+use crate::indexer::{ingest_with_origin, stored_secret_redactor};
+use crate::model::types::{Conversation, ConversationSelector, Message};
+use crate::search::tantivy::{TantivyIndex, index_dir, index_schema_current};
+use crate::sources::provenance::{LOCAL_SOURCE_ID, Origin, Source, SourceKind};
+use crate::storage::sqlite::SqliteStorage;
+
+/// Directory under the data dir holding the imported bundles of each source id.
+pub const BUNDLES_DIR: &str = "bundles";
+
+/// Extension of bundle files, used for the stored copies.
+pub const BUNDLE_EXTENSION: &str = "cassbundle";
+
+/// First bytes of every bundle.
+const MAGIC: &[u8] = b"CASSBDL1";
+
+/// Format version written to [`BundleHeader::version`].
+const BUNDLE_VERSION: u32 = 1;
+
+const CIPHER: &str = "aes-256-gcm";
+const KDF: &str = "argon2id";
+const TAG_LEN: usize = 16;
+
+/// Argon2id cost for password-protected bundles, the same as `cass pages`.
+const KDF_COST: KdfCost = KdfCost {
+    memory_kib: 65536,
+    iterations: 3,
+    parallelism: 4,
+};
+
+/// Most Argon2id cost a bundle header may ask for. The header is read before
+/// anything is authenticated, so a bundle must not be able to demand gigabytes
+/// of memory or minutes of hashing.
+const KDF_LIMIT: KdfCost = KdfCost {
+    memory_kib: 1024 * 1024,
+    iterations: 16,
+    parallelism: 16,
+};
+
+/// First line of a bundle, readable without the password.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleHeader {
+    pub version: u32,
+    /// Hash of the conversations (16 hex digits); bundles with the same content share it
+    pub bundle_id: String,
+    /// Name of the machine that exported the bundle
+    pub exported_from: String,
+    /// Export time, in milliseconds
+    pub created_at: i64,
+    pub conversations: usize,
+    pub messages: usize,
+    /// How the body is encrypted; `None` for a plain bundle
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<BundleEncryption>,
+}
+
+/// Parameters needed to decrypt a bundle body, besides the password.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BundleEncryption {
+    pub cipher: String,
+    pub kdf: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    /// Base64 Argon2id salt
+    pub salt: String,
+    /// Base64 AES-GCM nonce
+    pub nonce: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct KdfCost {
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+/// Options for [`export`].
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Machine name recorded in the bundle; defaults to the host name
+    pub name: Option<String>,
+    /// Encrypt the body with this password
+    pub password: Option<String>,
+}
+
+/// Outcome of [`export`], serialized as-is for `cass bundle export --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReport {
+    pub output: PathBuf,
+    pub bundle_id: String,
+    pub exported_from: String,
+    pub conversations: usize,
+    pub messages: usize,
+    pub encrypted: bool,
+    /// Size of the bundle file
+    pub bytes: usize,
+}
+
+/// Options for [`import`].
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Source id to index under; defaults to the exporting machine's name
+    pub source_id: Option<String>,
+    /// Password of an encrypted bundle
+    pub password: Option<String>,
+    pub dry_run: bool,
+}
+
+/// Outcome of [`import`], serialized as-is for `cass bundle import --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub bundle_id: String,
+    pub exported_from: String,
+    /// Export time, in milliseconds
+    pub created_at: i64,
+    pub encrypted: bool,
+    pub source_id: String,
+    /// Stored copy of the bundle that `cass index` re-reads
+    pub stored_path: PathBuf,
+    pub conversations: usize,
+    pub messages: usize,
+    /// Messages this source did not have yet; only known once the index is updated
+    pub new_messages: Option<usize>,
+    pub lexical_index: LexicalStatus,
+}
+
+/// A bundle kept under [`BUNDLES_DIR`].
+#[derive(Debug, Clone)]
+pub struct StoredBundle {
+    pub source_id: String,
+    pub path: PathBuf,
+    /// Modification time of the stored copy, in milliseconds
+    pub modified_ms: i64,
+}
+
+/// Write the conversations matching `selector` to a bundle at `output`.
+///
+/// An empty selector exports every conversation.
+pub fn export(
+    storage: &SqliteStorage,
+    selector: &ConversationSelector,
+    output: &Path,
+    opts: &ExportOptions,
+) -> Result<ExportReport> {
+    let exported_from = opts
+        .name
+        .clone()
+        .or_else(local_hostname)
+        .unwrap_or_else(|| "unknown-host".to_string());
+    let mut convs = Vec::new();
+    for selected in storage.select_conversations(selector)? {
+        let Some(conv) = storage.get_conversation(selected.conversation_id)? else {
+            continue;
+        };
+        let messages = storage.fetch_messages(selected.conversation_id)?;
+        convs.push(portable(conv, messages, &exported_from));
+    }
+
+    let (header, bytes) = encode(
+        &convs,
+        &exported_from,
+        SqliteStorage::now_millis(),
+        opts.password.as_deref(),
+        KDF_COST,
+    )?;
+    let tmp = output.with_extension("tmp");
+    fs::write(&tmp, &bytes).with_context(|| format!("writing {}", tmp.display()))?;
+    fs::rename(&tmp, output).with_context(|| format!("writing {}", output.display()))?;
+    Ok(ExportReport {
+        output: output.to_path_buf(),
+        bundle_id: header.bundle_id,
+        exported_from,
+        conversations: header.conversations,
+        messages: header.messages,
+        encrypted: header.encryption.is_some(),
+        bytes: bytes.len(),
+    })
+}
+
+/// Import the bundle at `path` into the stores under `data_dir`.
+pub fn import(
+    storage: &mut SqliteStorage,
+    data_dir: &Path,
+    path: &Path,
+    opts: &ImportOptions,
+) -> Result<ImportReport> {
+    let bytes = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    let (header, body) = open(&bytes, opts.password.as_deref())?;
+    let convs = parse_body(&header, &body)?;
+    let source_id = opts
+        .source_id
+        .clone()
+        .unwrap_or_else(|| default_source_id(&header.exported_from));
+    validate_source_id(storage, &source_id, SourceKind::Bundle)?;
+
+    let source_dir = data_dir.join(BUNDLES_DIR).join(&source_id);
+    let stored_path = source_dir.join(format!("{}.{BUNDLE_EXTENSION}", header.bundle_id));
+    let mut report = ImportReport {
+        dry_run: opts.dry_run,
+        bundle_id: header.bundle_id.clone(),
+        exported_from: header.exported_from.clone(),
+        created_at: header.created_at,
+        encrypted: header.encryption.is_some(),
+        source_id: source_id.clone(),
+        stored_path: stored_path.clone(),
+        conversations: convs.len(),
+        messages: convs.iter().map(|c| c.messages.len()).sum(),
+        new_messages: None,
+        lexical_index: LexicalStatus::Untouched,
+    };
+    if opts.dry_run {
+        return Ok(report);
+    }
+
+    // The stored copy is never encrypted; `cass vault` covers the data dir at rest.
+    let stored = BundleHeader {
+        encryption: None,
+        ..header.clone()
+    };
+    fs::create_dir_all(&source_dir)
+        .with_context(|| format!("creating {}", source_dir.display()))?;
+    let tmp = stored_path.with_extension("tmp");
+    fs::write(&tmp, assemble(&serde_json::to_vec(&stored)?, &body))
+        .with_context(|| format!("copying the bundle to {}", tmp.display()))?;
+    fs::rename(&tmp, &stored_path)
+        .with_context(|| format!("storing the bundle at {}", stored_path.display()))?;
+    storage.upsert_source(&Source {
+        id: source_id.clone(),
+        kind: SourceKind::Bundle,
+        host_label: Some(header.exported_from.clone()),
+        machine_id: None,
+        platform: None,
+        config_json: Some(json!({ "bundle_id": header.bundle_id, "imported_from": path })),
+        created_at: None,
+        updated_at: None,
+    })?;
+
+    let index_path = index_dir(data_dir)?;
+    if !index_path.join("meta.json").exists() || !index_schema_current(&index_path) {
+        // Opening would create or wipe the index; leave that to `cass index`.
+        report.lexical_index = LexicalStatus::Pending;
+        return Ok(report);
+    }
+    let mut t_index = TantivyIndex::open_or_create(&index_path)
+        .context("opening the lexical index (is `cass index --watch` running?)")?;
+    let redactor = stored_secret_redactor(storage)?;
+    let before = source_message_count(storage, &source_id)?;
+    ingest_with_origin(
+        storage,
+        &mut t_index,
+        convs,
+        &Origin::bundle(&source_id, &header.exported_from),
+        false,
+        redactor.as_ref(),
+    )?;
+    t_index.commit()?;
+    report.new_messages = Some(source_message_count(storage, &source_id)?.saturating_sub(before));
+    report.lexical_index = LexicalStatus::Updated;
+    Ok(report)
+}
+
+/// Read the header of a bundle, to find out whether it needs a password.
+pub fn read_header(path: &Path) -> Result<BundleHeader> {
+    let bytes = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    Ok(split(&bytes)?.0)
+}
+
+/// Read the header and conversations of a stored (unencrypted) bundle.
+pub fn read_stored(path: &Path) -> Result<(BundleHeader, Vec<NormalizedConversation>)> {
+    let bytes = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    let (header, body) = open(&bytes, None)?;
+    let convs = parse_body(&header, &body)?;
+    Ok((header, convs))
+}
+
+/// Drop the conversations keyed by `(agent, external_id)` in `keys` from the
+/// stored bundles of `source_id`, so `cass index` does not bring them back, and
+/// return the stored copies that held any of them. Each is re-encoded under its
+/// new bundle id; a bundle left empty is removed. With `dry_run` nothing is
+/// written.
+pub(crate) fn forget_stored_conversations(
+    data_dir: &Path,
+    source_id: &str,
+    keys: &HashSet<(String, String)>,
+    dry_run: bool,
+) -> Result<Vec<PathBuf>> {
+    let mut touched = Vec::new();
+    for stored in stored_bundles(data_dir) {
+        if stored.source_id != source_id {
+            continue;
+        }
+        let (header, convs) = read_stored(&stored.path)?;
+        let (forgotten, kept): (Vec<_>, Vec<_>) = convs.into_iter().partition(|conv| {
+            let key = (
+                conv.agent_slug.clone(),
+                conv.external_id.clone().unwrap_or_default(),
+            );
+            keys.contains(&key)
+        });
+        if forgotten.is_empty() {
+            continue;
+        }
+        if !dry_run {
+            if !kept.is_empty() {
+                let (rewritten, bytes) = encode(
+                    &kept,
+                    &header.exported_from,
+                    header.created_at,
+                    None,
+                    KDF_COST,
+                )?;
+                let path = stored
+                    .path
+                    .with_file_name(format!("{}.{BUNDLE_EXTENSION}", rewritten.bundle_id));
+                let tmp = path.with_extension("tmp");
+                fs::write(&tmp, &bytes).with_context(|| format!("writing {}", tmp.display()))?;
+                fs::rename(&tmp, &path)
+                    .with_context(|| format!("storing the bundle at {}", path.display()))?;
+            }
+            fs::remove_file(&stored.path)
+                .with_context(|| format!("removing {}", stored.path.display()))?;
+        }
+        touched.push(stored.path);
+    }
+    Ok(touched)
+}
+
+/// Bundles kept under `<data_dir>/bundles`, ordered by source id and bundle id.
+pub fn stored_bundles(data_dir: &Path) -> Vec<StoredBundle> {
+    let Ok(sources) = fs::read_dir(data_dir.join(BUNDLES_DIR)) else {
+        return Vec::new();
+    };
+    let mut stored = Vec::new();
+    for source in sources.flatten() {
+        let Some(source_id) = source.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let Ok(entries) = fs::read_dir(source.path()) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(BUNDLE_EXTENSION) {
+                continue;
+            }
+            let modified_ms = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_millis() as i64);
+            stored.push(StoredBundle {
+                source_id: source_id.clone(),
+                path,
+                modified_ms,
+            });
+        }
+    }
+    stored.sort_by(|a, b| (&a.source_id, &a.path).cmp(&(&b.source_id, &b.path)));
+    stored
+}
+
+/// The conversation as it travels. Its original provenance moves to
+/// `metadata.cass.bundle.origin`, since the importer stamps its own
+/// `cass.origin`, and it gets an external id that is the same in every export.
+fn portable(
+    conv: Conversation,
+    messages: Vec<Message>,
+    exported_from: &str,
+) -> NormalizedConversation {
+    let source_id = conv.source_id.clone();
+    let host = conv.origin_host.clone();
+    let mut conv = crate::forget::normalized(conv, messages);
+
+    let external_id = conv
+        .external_id
+        .take()
+        .unwrap_or_else(|| conv.source_path.to_string_lossy().into_owned());
+    // Conversations from different sources may share ids; one import source holds them all
+    conv.external_id = Some(if source_id == LOCAL_SOURCE_ID {
+        external_id
+    } else {
+        format!("{source_id}:{external_id}")
+    });
+
+    if !conv.metadata.is_object() {
+        conv.metadata = json!({});
+    }
+    if !conv.metadata["cass"].is_object() {
+        conv.metadata["cass"] = json!({});
+    }
+    let cass = &mut conv.metadata["cass"];
+    let origin = cass
+        .as_object_mut()
+        .and_then(|cass| cass.remove("origin"))
+        .unwrap_or_else(|| json!({ "source_id": source_id, "host": host }));
+    cass["bundle"] = json!({ "exported_from": exported_from, "origin": origin });
+    conv
+}
+
+/// Serialize `convs` into a bundle; `password` encrypts the body.
+fn encode(
+    convs: &[NormalizedConversation],
+    exported_from: &str,
+    created_at: i64,
+    password: Option<&str>,
+    cost: KdfCost,
+) -> Result<(BundleHeader, Vec<u8>)> {
+    let mut jsonl = Vec::new();
+    for conv in convs {
+        serde_json::to_writer(&mut jsonl, conv)?;
+        jsonl.push(b'\n');
+    }
+    let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+    gz.write_all(&jsonl)?;
+    let mut body = gz.finish()?;
+
+    let mut header = BundleHeader {
+        version: BUNDLE_VERSION,
+        bundle_id: bundle_id(&jsonl),
+        exported_from: exported_from.to_string(),
+        created_at,
+        conversations: convs.len(),
+        messages: convs.iter().map(|c| c.messages.len()).sum(),
+        encryption: None,
+    };
+    if let Some(password) = password {
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+        let encryption = BundleEncryption {
+            cipher: CIPHER.to_string(),
+            kdf: KDF.to_string(),
+            memory_kib: cost.memory_kib,
+            iterations: cost.iterations,
+            parallelism: cost.parallelism,
+            salt: BASE64_STANDARD.encode(salt),
+            nonce: BASE64_STANDARD.encode(nonce),
+        };
+        let key = derive_key(password, &encryption)?;
+        header.encryption = Some(encryption);
+        let aad = serde_json::to_vec(&header)?;
+        let (mut sealed, tag) =
+            aes_gcm_encrypt(&key, &nonce, &body, &aad).map_err(anyhow::Error::msg)?;
+        sealed.extend_from_slice(&tag);
+        body = sealed;
+    }
+    let bytes = assemble(&serde_json::to_vec(&header)?, &body);
+    Ok((header, bytes))
+}
+
+fn assemble(header_line: &[u8], body: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(MAGIC.len() + header_line.len() + 1 + body.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(header_line);
+    bytes.push(b'\n');
+    bytes.extend_from_slice(body);
+    bytes
+}
+
+/// Split a bundle into its header, the raw header line and the body.
+fn split(bytes: &[u8]) -> Result<(BundleHeader, &[u8], &[u8])> {
+    let Some(rest) = bytes.strip_prefix(MAGIC) else {
+        bail!("not a cass bundle");
+    };
+    let newline = rest
+        .iter()
+        .position(|b| *b == b'\n')
+        .context("truncated bundle header")?;
+    let (header_line, body) = (&rest[..newline], &rest[newline + 1..]);
+    let header: BundleHeader =
+        serde_json::from_slice(header_line).context("corrupt bundle header")?;
+    if header.version > BUNDLE_VERSION {
+        bail!(
+            "bundle format version {} is newer than this cass supports ({BUNDLE_VERSION}); upgrade cass",
+            header.version
+        );
+    }
+    Ok((header, header_line, body))
+}
+
+/// The header and the compressed body, decrypted with `password` if needed.
+fn open(bytes: &[u8], password: Option<&str>) -> Result<(BundleHeader, Vec<u8>)> {
+    let (header, header_line, body) = split(bytes)?;
+    let Some(encryption) = &header.encryption else {
+        return Ok((header, body.to_vec()));
+    };
+    if encryption.cipher != CIPHER || encryption.kdf != KDF {
+        bail!(
+            "unsupported bundle encryption {} with {}",
+            encryption.cipher,
+            encryption.kdf
+        );
+    }
+    let Some(password) = password else {
+        bail!("the bundle is encrypted; a password is required");
+    };
+    if encryption.memory_kib > KDF_LIMIT.memory_kib
+        || encryption.iterations > KDF_LIMIT.iterations
+        || encryption.parallelism > KDF_LIMIT.parallelism
+    {
+        bail!(
+            "the bundle asks for Argon2 parameters above the supported limit \
+             (memory {} KiB, {} iterations, parallelism {})",
+            encryption.memory_kib,
+            encryption.iterations,
+            encryption.parallelism
+        );
+    }
+    let nonce = BASE64_STANDARD
+        .decode(&encryption.nonce)
+        .context("corrupt bundle nonce")?;
+    if body.len() < TAG_LEN {
+        bail!("truncated bundle");
+    }
+    let (sealed, tag) = body.split_at(body.len() - TAG_LEN);
+    let key = derive_key(password, encryption)?;
+    let body = aes_gcm_decrypt(&key, &nonce, sealed, header_line, tag)
+        .map_err(|_| anyhow::anyhow!("wrong password, or the bundle is corrupted"))?;
+    Ok((header, body))
+}
+
+fn derive_key(password: &str, encryption: &BundleEncryption) -> Result<Vec<u8>> {
+    let salt = BASE64_STANDARD
+        .decode(&encryption.salt)
+        .context("corrupt bundle salt")?;
+    let params = Argon2Params::new(
+        encryption.memory_kib,
+        encryption.iterations,
+        encryption.parallelism,
+        Some(32),
+    )
+    .map_err(|e| anyhow::anyhow!("invalid Argon2 parameters: {e}"))?;
+    argon2id_hash(password.as_bytes(), &salt, &params).map_err(anyhow::Error::msg)
+}
+
+/// Content hash naming a bundle: the first 8 bytes of the SHA-256 of its JSON lines, in hex.
+fn bundle_id(jsonl: &[u8]) -> String {
+    hex::encode(&Sha256::digest(jsonl)[..8])
+}
+
+/// Decompress and parse the body, checking it against the header's bundle id.
+///
+/// The id names the stored copy, so it must be the content hash and nothing else.
+fn parse_body(header: &BundleHeader, body: &[u8]) -> Result<Vec<NormalizedConversation>> {
+    let mut jsonl = Vec::new();
+    GzDecoder::new(body)
+        .read_to_end(&mut jsonl)
+        .context("decompressing the bundle")?;
+    if header.bundle_id != bundle_id(&jsonl) {
+        bail!("the bundle id does not match its contents; the bundle is corrupted");
+    }
+    jsonl
+        .split(|b| *b == b'\n')
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(n, line)| {
+            serde_json::from_slice(line)
+                .with_context(|| format!("bundle conversation {} is corrupt", n + 1))
+        })
+        .collect()
+}
+
+/// Source id for a machine name: characters a source id cannot hold become `-`.
+fn default_source_id(exported_from: &str) -> String {
+    exported_from
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches('.')
+        .to_string()
+}
+
+fn source_message_count(storage: &SqliteStorage, source_id: &str) -> Result<usize> {
+    let count: i64 = storage.raw().query_row(
+        "SELECT COUNT(*) FROM messages m JOIN conversations c ON c.id = m.conversation_id
+         WHERE c.source_id = ?1",
+        [source_id],
+        |row| row.get(0),
+    )?;
+    Ok(count as usize)
+}
+
+/// This machine's host name, recorded in bundles exported without a name.
+fn local_hostname() -> Option<String> {
+    let nonblank = |name: String| Some(name.trim().to_string()).filter(|n| !n.is_empty());
+    std::env::var("HOSTNAME")
+        .ok()
+        .and_then(nonblank)
+        .or_else(|| fs::read_to_string("/etc/hostname").ok().and_then(nonblank))
+        .or_else(|| {
+            let output = std::process::Command::new("hostname").output().ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+                .and_then(nonblank)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::types::{Agent, AgentKind, MessageRole};
+    use tempfile::TempDir;
+
+    /// Cheap enough for tests; real bundles use [`KDF_COST`].
+    const TEST_COST: KdfCost = KdfCost {
+        memory_kib: 1024,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn conversation(external_id: &str, contents: &[&str]) -> Conversation {
+        Conversation {
+            id: None,
+            agent_slug: "codex".into(),
+            workspace: None,
+            external_id: Some(external_id.into()),
+            title: Some(external_id.into()),
+            source_path: PathBuf::from(format!("/s/{external_id}.jsonl")),
+            started_at: Some(1_700_000_000_000),
+            ended_at: None,
+            approx_tokens: None,
+            metadata_json: json!({ "cass": { "origin": { "source_id": "local", "kind": "local", "host": null } } }),
+            messages: contents
+                .iter()
+                .enumerate()
+                .map(|(idx, content)| Message {
+                    id: None,
+                    idx: idx as i64,
+                    role: MessageRole::User,
+                    author: None,
+                    created_at: Some(1_700_000_000_000 + idx as i64),
+                    content: (*content).into(),
+                    extra_json: json!({}),
+                    snippets: Vec::new(),
+                })
+                .collect(),
+            source_id: "local".into(),
+            origin_host: None,
+        }
+    }
+
+    fn storage_with(dir: &Path, convs: &[Conversation]) -> SqliteStorage {
+        let mut storage = SqliteStorage::open(&dir.join("agent_search.db")).unwrap();
+        let agent_id = storage
+            .ensure_agent(&Agent {
+                id: None,
+                slug: "codex".into(),
+                name: "Codex".into(),
+                version: None,
+                kind: AgentKind::Cli,
+            })
+            .unwrap();
+        for conv in convs {
+            storage
+                .insert_conversation_tree(agent_id, None, conv)
+                .unwrap();
+        }
+        storage
+    }
+
+    #[test]
+    fn encrypted_bundles_need_the_right_password() {
+        let conv = portable(
+            conversation("c-1", &["deploy the canary"]),
+            Vec::new(),
+            "runner-7",
+        );
+        let (plain_header, plain) =
+            encode(&[conv.clone()], "runner-7", 1, None, TEST_COST).unwrap();
+        let (header, sealed) = encode(&[conv], "runner-7", 1, Some("hunter22"), TEST_COST).unwrap();
+        assert_eq!(header.bundle_id, plain_header.bundle_id);
+        assert!(plain_header.encryption.is_none());
+
+        let (header, body) = open(&plain, None).unwrap();
+        assert_eq!(
+            parse_body(&header, &body).unwrap()[0]
+                .external_id
+                .as_deref(),
+            Some("c-1")
+        );
+        let (header, body) = open(&sealed, Some("hunter22")).unwrap();
+        assert_eq!(parse_body(&header, &body).unwrap().len(), 1);
+
+        assert!(open(&sealed, None).is_err());
+        assert!(open(&sealed, Some("wrong-password")).is_err());
+        // The header is authenticated with the body
+        let mut tampered = sealed.clone();
+        let at = tampered.windows(8).position(|w| w == b"runner-7").unwrap();
+        tampered[at + 7] = b'8';
+        assert!(open(&tampered, Some("hunter22")).is_err());
+        assert!(open(b"PK\x03\x04", None).is_err());
+    }
+
+    #[test]
+    fn untrusted_header_fields_are_checked_before_use() {
+        let conv = portable(conversation("c-1", &["hi"]), Vec::new(), "runner-7");
+        let (header, plain) = encode(&[conv.clone()], "runner-7", 1, None, TEST_COST).unwrap();
+        let traversal =
+            String::from_utf8_lossy(&plain).replacen(&header.bundle_id, "../../../../tmp/x", 1);
+        let (forged, body) = open(traversal.as_bytes(), None).unwrap();
+        let err = parse_body(&forged, &body).unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+
+        let (_, sealed) = encode(&[conv], "runner-7", 1, Some("hunter22"), TEST_COST).unwrap();
+        let costly = String::from_utf8_lossy(&sealed).replacen(
+            r#""memory_kib":1024"#,
+            r#""memory_kib":4000000000"#,
+            1,
+        );
+        let err = open(costly.as_bytes(), Some("hunter22")).unwrap_err();
+        assert!(err.to_string().contains("supported limit"));
+    }
+
+    #[test]
+    fn portable_conversations_keep_their_original_provenance() {
+        let mut remote = conversation("c-2", &["hi"]);
+        remote.source_id = "laptop".into();
+        remote.origin_host = Some("me@laptop".into());
+        remote.metadata_json = json!({});
+        let conv = portable(remote, Vec::new(), "hub");
+        assert_eq!(conv.external_id.as_deref(), Some("laptop:c-2"));
+        assert_eq!(conv.metadata["cass"]["bundle"]["exported_from"], "hub");
+        assert_eq!(
+            conv.metadata["cass"]["bundle"]["origin"]["host"],
+            "me@laptop"
+        );
+        assert!(conv.metadata["cass"].get("origin").is_none());
+
+        let mut unnamed = conversation("c-3", &["hi"]);
+        unnamed.external_id = None;
+        let conv = portable(unnamed, Vec::new(), "hub");
+        assert_eq!(conv.external_id.as_deref(), Some("/s/c-3.jsonl"));
+        assert_eq!(conv.metadata["cass"]["bundle"]["origin"]["kind"], "local");
+
+        assert_eq!(default_source_id("ci runner/7"), "ci-runner-7");
+    }
+
+    #[test]
+    fn reimporting_a_bundle_only_adds_new_messages() {
+        let ci = TempDir::new().unwrap();
+        let mut first = conversation("c-1", &["run the migrations"]);
+        let storage = storage_with(ci.path(), &[first.clone()]);
+        let bundle = ci.path().join("first.cassbundle");
+        let opts = ExportOptions {
+            name: Some("runner-7".into()),
+            password: None,
+        };
+        let exported = export(&storage, &ConversationSelector::default(), &bundle, &opts).unwrap();
+        assert_eq!((exported.conversations, exported.messages), (1, 1));
+
+        let hub = TempDir::new().unwrap();
+        let mut hub_storage = storage_with(hub.path(), &[]);
+        TantivyIndex::open_or_create(&index_dir(hub.path()).unwrap())
+            .unwrap()
+            .commit()
+            .unwrap();
+        let report = import(
+            &mut hub_storage,
+            hub.path(),
+            &bundle,
+            &ImportOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(report.source_id, "runner-7");
+        assert_eq!(report.lexical_index, LexicalStatus::Updated);
+        assert_eq!(report.new_messages, Some(1));
+        assert!(report.stored_path.is_file());
+
+        let again = import(
+            &mut hub_storage,
+            hub.path(),
+            &bundle,
+            &ImportOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(again.new_messages, Some(0));
+
+        // A later export of the same conversation with one more message
+        first.messages.push(Message {
+            idx: 1,
+            content: "and seed the fixtures".into(),
+            ..first.messages[0].clone()
+        });
+        let ci2 = TempDir::new().unwrap();
+        let storage = storage_with(ci2.path(), &[first]);
+        let later = ci2.path().join("later.cassbundle");
+        export(&storage, &ConversationSelector::default(), &later, &opts).unwrap();
+        let report = import(
+            &mut hub_storage,
+            hub.path(),
+            &later,
+            &ImportOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(report.new_messages, Some(1));
+
+        let stored = stored_bundles(hub.path());
+        assert_eq!(stored.len(), 2);
+        let (header, convs) = read_stored(&stored[0].path).unwrap();
+        assert_eq!(header.exported_from, "runner-7");
+        assert!(header.encryption.is_none());
+        assert_eq!(convs.len(), 1);
+
+        let source = hub_storage.get_source("runner-7").unwrap().unwrap();
+        assert_eq!(source.kind, SourceKind::Bundle);
+        assert_eq!(source.host_label.as_deref(), Some("runner-7"));
+    }
+}
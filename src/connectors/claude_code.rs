@@ -1,13 +1,760 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use walkdir::WalkDir;
 
 use crate::connectors::{
     Connector, DetectionResult, NormalizedConversation, NormalizedMessage, ScanContext,
 };
+use crate::model::types::TokenUsage;
+
+/// Tokens of the API response a line belongs to, the first time that response is seen.
+fn claude_usage(
+    val: &Value,
+    model: Option<&str>,
+    seen: &mut HashSet<String>,
+) -> Option<TokenUsage> {
+    let message = val.get("message")?;
+    let usage = message.get("usage")?;
+    if let Some(id) = message.get("id").and_then(|v| v.as_str())
+        && !seen.insert(id.to_string())
+    {
+        return None;
+    }
+    let field = |key: &str| usage.get(key).and_then(|v| v.as_i64()).unwrap_or(0);
+    let usage = TokenUsage {
+        model: model.map(String::from),
+        input_tokens: field("input_tokens"),
+        output_tokens: field("output_tokens"),
+        cache_read_tokens: field("cache_read_input_tokens"),
+        cache_write_tokens: field("cache_creation_input_tokens"),
+    };
+    (!usage.is_empty()).then_some(usage)
+}
+
+/// Subagent (Task tool) thread of a Claude Code session.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubagentThread {
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub tool_use_id: Option<String>,
+    /// Message that called the Task tool, and its line in the session file
+    #[serde(default)]
+    pub spawned_by: Option<i64>,
+    #[serde(default)]
+    pub spawned_at_line: Option<usize>,
+    /// Separate transcript file (`agent-<id>.jsonl`), written by newer Claude Code versions
+    #[serde(default)]
+    pub transcript: Option<PathBuf>,
+    /// Sidechain messages kept in the session file itself (older versions), and their lines
+    #[serde(default)]
+    pub messages: Vec<i64>,
+    #[serde(default)]
+    pub lines: Vec<usize>,
+    /// Task prompt, to match transcripts that carry no agent id
+    #[serde(skip)]
+    prompt: Option<String>,
+}
+
+/// Messages that continue from an earlier point than the active conversation
+/// (rewinds and edited prompts).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Branch {
+    /// Last active-path message before the fork (None: forks at the start)
+    #[serde(default)]
+    pub fork_after: Option<i64>,
+    #[serde(default)]
+    pub messages: Vec<i64>,
+}
+
+/// The session a subagent transcript belongs to.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParentLink {
+    pub source_path: PathBuf,
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub agent_id: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub spawned_by: Option<i64>,
+    #[serde(default)]
+    pub spawned_at_line: Option<usize>,
+}
+
+/// Parent/child structure of a Claude Code session, stored in the
+/// conversation metadata under `subagents`, `branches` and `parent`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionThreads {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subagents: Vec<SubagentThread>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub branches: Vec<Branch>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<ParentLink>,
+}
+
+impl SessionThreads {
+    pub fn is_empty(&self) -> bool {
+        self.subagents.is_empty() && self.branches.is_empty() && self.parent.is_none()
+    }
+
+    /// Threads recorded in a conversation's metadata.
+    pub fn from_metadata(metadata: &Value) -> Self {
+        let field = |key: &str| metadata.get(key).cloned().unwrap_or(Value::Null);
+        Self {
+            subagents: serde_json::from_value(field("subagents")).unwrap_or_default(),
+            branches: serde_json::from_value(field("branches")).unwrap_or_default(),
+            parent: serde_json::from_value(field("parent")).unwrap_or_default(),
+        }
+    }
+
+    fn write_metadata(&self, metadata: &mut Value) {
+        if let (Some(obj), Ok(Value::Object(threads))) =
+            (metadata.as_object_mut(), serde_json::to_value(self))
+        {
+            obj.extend(threads);
+        }
+    }
+
+    /// Subagent whose sidechain contains message `idx`.
+    pub fn inline_thread_of(&self, idx: i64) -> Option<&SubagentThread> {
+        self.subagents
+            .iter()
+            .find(|t| t.spawned_by.is_some() && t.messages.contains(&idx))
+    }
+}
+
+/// Threads of the Claude Code session file at `path`, including the link to
+/// the parent session when it is a subagent transcript. None for files that
+/// are not JSONL sessions.
+pub fn session_threads(path: &Path) -> Option<SessionThreads> {
+    if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+        return None;
+    }
+    let session = parse_jsonl(path).ok()?;
+    let mut threads = session.threads;
+    if let Some(transcript) = &session.transcript {
+        threads.parent = parent_link(
+            path,
+            session.session_id.as_deref(),
+            transcript,
+            &mut ParentCache::new(),
+        );
+    }
+    Some(threads)
+}
+
+/// One line of a session file, as far as threading is concerned.
+struct LineNode {
+    line: usize,
+    uuid: Option<String>,
+    parent: Option<String>,
+    sidechain: bool,
+    agent_id: Option<String>,
+    /// Index of the message this line became, if it was kept
+    msg_idx: Option<i64>,
+    /// Text of a user prompt (not a tool result)
+    prompt: Option<String>,
+}
+
+/// A Task tool call in the main conversation.
+struct TaskCall {
+    line: usize,
+    tool_use_id: Option<String>,
+    description: Option<String>,
+    prompt: Option<String>,
+}
+
+/// What identifies a subagent transcript to its parent.
+struct Transcript {
+    agent_id: Option<String>,
+    prompt: Option<String>,
+}
+
+struct JsonlSession {
+    messages: Vec<NormalizedMessage>,
+    started_at: Option<i64>,
+    ended_at: Option<i64>,
+    workspace: Option<PathBuf>,
+    session_id: Option<String>,
+    git_branch: Option<String>,
+    threads: SessionThreads,
+    /// Set when every message is on a sidechain: the file is a subagent transcript
+    transcript: Option<Transcript>,
+}
+
+fn parse_jsonl(path: &Path) -> Result<JsonlSession> {
+    let file = std::fs::File::open(path).with_context(|| format!("open {}", path.display()))?;
+    let reader = std::io::BufReader::new(file);
+
+    let mut messages = Vec::new();
+    let mut started_at = None;
+    let mut ended_at = None;
+    // Track workspace from first entry's cwd field
+    let mut workspace: Option<PathBuf> = None;
+    let mut session_id: Option<String> = None;
+    let mut git_branch: Option<String> = None;
+    // API message ids whose usage was recorded; Claude Code writes one line
+    // per content block and repeats the usage on each
+    let mut usage_seen: HashSet<String> = HashSet::new();
+    let mut nodes: Vec<LineNode> = Vec::new();
+    let mut tasks: Vec<TaskCall> = Vec::new();
+    // tool_use_id of a Task call -> agent id reported in its result
+    let mut task_agents: HashMap<String, String> = HashMap::new();
+
+    for (line_no, line_res) in std::io::BufRead::lines(reader).enumerate() {
+        let line = match line_res {
+            Ok(l) => l,
+            Err(_) => continue,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let val: Value = match serde_json::from_str(&line) {
+            Ok(v) => v,
+            Err(_) => continue, // Skip malformed lines
+        };
+
+        // Extract session metadata from first available entry
+        if workspace.is_none() {
+            workspace = val.get("cwd").and_then(|v| v.as_str()).map(PathBuf::from);
+        }
+        if session_id.is_none() {
+            session_id = val
+                .get("sessionId")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+        }
+        if git_branch.is_none() {
+            git_branch = val
+                .get("gitBranch")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+        }
+
+        let str_field = |key: &str| val.get(key).and_then(|v| v.as_str()).map(String::from);
+        let sidechain = val
+            .get("isSidechain")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let mut node = LineNode {
+            line: line_no + 1,
+            uuid: str_field("uuid"),
+            // Compaction starts a new root that points back through logicalParentUuid
+            parent: str_field("parentUuid").or_else(|| str_field("logicalParentUuid")),
+            sidechain,
+            agent_id: str_field("agentId"),
+            msg_idx: None,
+            prompt: None,
+        };
+        record_task_links(&val, &node, &mut tasks, &mut task_agents);
+
+        // Filter to user/assistant entries only (skip summary, file-history-snapshot, etc.)
+        let entry_type = val.get("type").and_then(|v| v.as_str());
+        let role_hint = val
+            .get("message")
+            .and_then(|m| m.get("role"))
+            .and_then(|v| v.as_str())
+            .or_else(|| val.get("role").and_then(|v| v.as_str()));
+        let is_user_assistant = matches!(entry_type, Some("user" | "assistant"))
+            || (entry_type == Some("message") && matches!(role_hint, Some("user" | "assistant")));
+        if !is_user_assistant {
+            nodes.push(node);
+            continue;
+        }
+
+        // Parse ISO-8601 timestamp using shared utility
+        let created = val
+            .get("timestamp")
+            .and_then(crate::connectors::parse_timestamp);
+
+        // NOTE: Do NOT filter individual messages by timestamp here!
+        // The file-level check in file_modified_since() is sufficient.
+        // Filtering messages would cause older messages to be lost when
+        // the file is re-indexed after new messages are added.
+
+        started_at = started_at.or(created);
+        ended_at = created.or(ended_at);
+
+        // Role from message.role, top-level role, or entry type
+        let role = role_hint.or(entry_type).unwrap_or("agent");
+
+        // Content from message.content or top-level content (may be string or array)
+        let content_val = val
+            .get("message")
+            .and_then(|m| m.get("content"))
+            .or_else(|| val.get("content"));
+        let content_str = content_val
+            .map(crate::connectors::flatten_content)
+            .unwrap_or_default();
+        let snippets = content_val
+            .map(crate::connectors::extract_tool_snippets)
+            .unwrap_or_default();
+
+        // Skip entries with empty content
+        if content_str.trim().is_empty() {
+            nodes.push(node);
+            continue;
+        }
+
+        let is_tool_result = val.get("toolUseResult").is_some()
+            || content_val
+                .and_then(|c| c.as_array())
+                .is_some_and(|blocks| {
+                    blocks
+                        .iter()
+                        .any(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+                });
+        if role == "user" && !is_tool_result {
+            node.prompt = Some(content_str.clone());
+        }
+        node.msg_idx = Some(messages.len() as i64);
+        nodes.push(node);
+
+        // Extract model name for author field
+        let author = val
+            .get("message")
+            .and_then(|m| m.get("model"))
+            .and_then(|v| v.as_str())
+            .map(String::from);
+
+        let usage = claude_usage(&val, author.as_deref(), &mut usage_seen);
+        let mut extra = val;
+        if let Some(usage) = usage {
+            usage.add_to_extra(&mut extra);
+        }
+
+        messages.push(NormalizedMessage {
+            idx: 0, // will be re-assigned after filtering
+            role: role.to_string(),
+            author,
+            created_at: created,
+            content: content_str,
+            extra,
+            snippets,
+        });
+    }
+    // Re-assign sequential indices after filtering
+    super::reindex_messages(&mut messages);
+
+    let kept: Vec<&LineNode> = nodes.iter().filter(|n| n.msg_idx.is_some()).collect();
+    let transcript = (!kept.is_empty() && kept.iter().all(|n| n.sidechain)).then(|| Transcript {
+        agent_id: kept.iter().find_map(|n| n.agent_id.clone()).or_else(|| {
+            path.file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.strip_prefix("agent-"))
+                .map(String::from)
+        }),
+        prompt: kept.iter().find_map(|n| n.prompt.clone()),
+    });
+    let threads = if transcript.is_some() {
+        SessionThreads::default()
+    } else {
+        SessionThreads {
+            subagents: subagent_threads(path, &nodes, &tasks, &task_agents),
+            branches: branches(&nodes),
+            parent: None,
+        }
+    };
+
+    Ok(JsonlSession {
+        messages,
+        started_at,
+        ended_at,
+        workspace,
+        session_id,
+        git_branch,
+        threads,
+        transcript,
+    })
+}
+
+/// Note Task tool calls and the agent ids their results report.
+fn record_task_links(
+    val: &Value,
+    node: &LineNode,
+    tasks: &mut Vec<TaskCall>,
+    task_agents: &mut HashMap<String, String>,
+) {
+    let Some(blocks) = val
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(|c| c.as_array())
+    else {
+        return;
+    };
+    let agent_id = val
+        .get("toolUseResult")
+        .and_then(|r| r.get("agentId"))
+        .and_then(|v| v.as_str());
+    for block in blocks {
+        let text = |key: &str| block.get(key).and_then(|v| v.as_str()).map(String::from);
+        match block.get("type").and_then(|t| t.as_str()) {
+            Some("tool_use")
+                if !node.sidechain
+                    && matches!(
+                        block.get("name").and_then(|n| n.as_str()),
+                        Some("Task" | "Agent")
+                    ) =>
+            {
+                let input = |key: &str| {
+                    block
+                        .get("input")
+                        .and_then(|i| i.get(key))
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                };
+                tasks.push(TaskCall {
+                    line: node.line,
+                    tool_use_id: text("id"),
+                    description: input("description"),
+                    prompt: input("prompt"),
+                });
+            }
+            Some("tool_result") => {
+                if let (Some(id), Some(agent_id)) = (text("tool_use_id"), agent_id) {
+                    task_agents.insert(id, agent_id.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Match Task calls to the sidechains stored in this file and to transcript
+/// files next to it.
+fn subagent_threads(
+    path: &Path,
+    nodes: &[LineNode],
+    tasks: &[TaskCall],
+    task_agents: &HashMap<String, String>,
+) -> Vec<SubagentThread> {
+    // Sidechains in this file, split at their roots
+    let mut sidechains: Vec<SubagentThread> = Vec::new();
+    let mut prompts: Vec<Option<String>> = Vec::new();
+    let mut chain_of: HashMap<&str, usize> = HashMap::new();
+    for node in nodes.iter().filter(|n| n.sidechain) {
+        let chain = match node.parent.as_deref().and_then(|p| chain_of.get(p)) {
+            Some(&chain) => chain,
+            None => {
+                sidechains.push(SubagentThread::default());
+                prompts.push(None);
+                sidechains.len() - 1
+            }
+        };
+        if let Some(uuid) = node.uuid.as_deref() {
+            chain_of.insert(uuid, chain);
+        }
+        let thread = &mut sidechains[chain];
+        if thread.agent_id.is_none() {
+            thread.agent_id.clone_from(&node.agent_id);
+        }
+        if prompts[chain].is_none() {
+            prompts[chain].clone_from(&node.prompt);
+        }
+        if let Some(idx) = node.msg_idx {
+            thread.messages.push(idx);
+            thread.lines.push(node.line);
+        }
+    }
+    let mut claimed = vec![false; sidechains.len()];
+
+    let line_msg: HashMap<usize, i64> = nodes
+        .iter()
+        .filter_map(|n| n.msg_idx.map(|idx| (n.line, idx)))
+        .collect();
+    let mut threads = Vec::new();
+    for task in tasks {
+        let agent_id = task
+            .tool_use_id
+            .as_ref()
+            .and_then(|id| task_agents.get(id))
+            .cloned();
+        let same_prompt = |i: usize| {
+            task.prompt
+                .as_deref()
+                .map(str::trim)
+                .is_some_and(|p| prompts[i].as_deref().map(str::trim) == Some(p))
+        };
+        let chain = (0..sidechains.len()).find(|&i| {
+            !claimed[i]
+                && ((agent_id.is_some() && sidechains[i].agent_id == agent_id) || same_prompt(i))
+        });
+        let mut thread = match chain {
+            Some(i) => {
+                claimed[i] = true;
+                sidechains[i].clone()
+            }
+            None => SubagentThread::default(),
+        };
+        thread.agent_id = agent_id.or(thread.agent_id);
+        thread.prompt.clone_from(&task.prompt);
+        thread.description.clone_from(&task.description);
+        thread.tool_use_id.clone_from(&task.tool_use_id);
+        thread.spawned_by = line_msg.get(&task.line).copied();
+        thread.spawned_at_line = Some(task.line);
+        thread.transcript = thread
+            .agent_id
+            .as_deref()
+            .and_then(|id| transcript_path(path, id));
+        threads.push(thread);
+    }
+    attach_unnamed_transcripts(path, &mut threads);
+    // Sidechains no Task call accounts for stay listed, unattached
+    threads.extend(
+        sidechains
+            .into_iter()
+            .zip(claimed)
+            .filter(|(t, claimed)| !claimed && !t.messages.is_empty())
+            .map(|(t, _)| t),
+    );
+    threads
+}
+
+/// Transcript of subagent `agent_id` spawned by the session at `path`.
+fn transcript_path(path: &Path, agent_id: &str) -> Option<PathBuf> {
+    let dir = path.parent()?;
+    let name = format!("agent-{agent_id}.jsonl");
+    let mut candidates = vec![dir.join(&name)];
+    if let Some(stem) = path.file_stem() {
+        candidates.insert(0, dir.join(stem).join("subagents").join(&name));
+    }
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+/// Match transcripts in `<session>/subagents/` that no agent id pointed to
+/// by their prompt; the rest are listed unattached.
+fn attach_unnamed_transcripts(path: &Path, threads: &mut Vec<SubagentThread>) {
+    let (Some(dir), Some(stem)) = (path.parent(), path.file_stem()) else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(dir.join(stem).join("subagents")) else {
+        return;
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "jsonl"))
+        .collect();
+    files.sort();
+    for file in files {
+        if threads.iter().any(|t| t.transcript.as_ref() == Some(&file)) {
+            continue;
+        }
+        let prompt = first_prompt(&file);
+        let matched = threads.iter_mut().find(|t| {
+            t.transcript.is_none()
+                && t.prompt.as_deref().map(str::trim).is_some()
+                && t.prompt.as_deref().map(str::trim) == prompt.as_deref().map(str::trim)
+        });
+        match matched {
+            Some(thread) => thread.transcript = Some(file),
+            None => threads.push(SubagentThread {
+                agent_id: file
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .and_then(|s| s.strip_prefix("agent-"))
+                    .map(String::from),
+                transcript: Some(file),
+                ..SubagentThread::default()
+            }),
+        }
+    }
+}
+
+/// Text of the first user line of a transcript (the Task prompt).
+fn first_prompt(path: &Path) -> Option<String> {
+    let file = std::fs::File::open(path).ok()?;
+    std::io::BufRead::lines(std::io::BufReader::new(file))
+        .map_while(std::result::Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .find(|val| val.get("type").and_then(|t| t.as_str()) == Some("user"))
+        .and_then(|val| {
+            val.get("message")
+                .and_then(|m| m.get("content"))
+                .map(crate::connectors::flatten_content)
+        })
+}
+
+/// Main-chain messages off the active path, grouped by where they fork.
+fn branches(nodes: &[LineNode]) -> Vec<Branch> {
+    let by_uuid: HashMap<&str, &LineNode> = nodes
+        .iter()
+        .filter_map(|n| n.uuid.as_deref().map(|u| (u, n)))
+        .collect();
+    let parent_of = |n: &LineNode| n.parent.as_deref().and_then(|p| by_uuid.get(p).copied());
+
+    // The active path runs from the last main-chain line back to its root
+    let mut on_path: HashSet<&str> = HashSet::new();
+    let mut cursor = nodes
+        .iter()
+        .rev()
+        .find(|n| !n.sidechain && n.uuid.is_some());
+    while let Some(node) = cursor {
+        let Some(uuid) = node.uuid.as_deref() else {
+            break;
+        };
+        if !on_path.insert(uuid) {
+            break;
+        }
+        cursor = parent_of(node);
+    }
+    if on_path.is_empty() {
+        return Vec::new();
+    }
+
+    // Branch root (first line off the path) -> branch
+    let mut found: Vec<(&str, Branch, bool)> = Vec::new();
+    for node in nodes.iter().filter(|n| !n.sidechain) {
+        let (Some(uuid), Some(idx)) = (node.uuid.as_deref(), node.msg_idx) else {
+            continue;
+        };
+        if on_path.contains(uuid) {
+            continue;
+        }
+        // Climb to the fork point
+        let mut root = node;
+        let mut fork = parent_of(node);
+        let mut seen = HashSet::new();
+        while let Some(parent) = fork {
+            let parent_uuid = parent.uuid.as_deref().unwrap_or_default();
+            if on_path.contains(parent_uuid) || !seen.insert(parent_uuid) {
+                break;
+            }
+            root = parent;
+            fork = parent_of(parent);
+        }
+        let root_uuid = root.uuid.as_deref().unwrap_or_default();
+        let pos = match found.iter().position(|(r, _, _)| *r == root_uuid) {
+            Some(pos) => pos,
+            None => {
+                let mut fork_after = None;
+                let mut at =
+                    fork.filter(|f| on_path.contains(f.uuid.as_deref().unwrap_or_default()));
+                while let Some(n) = at {
+                    if let Some(idx) = n.msg_idx {
+                        fork_after = Some(idx);
+                        break;
+                    }
+                    at = parent_of(n);
+                }
+                found.push((
+                    root_uuid,
+                    Branch {
+                        fork_after,
+                        messages: Vec::new(),
+                    },
+                    false,
+                ));
+                found.len() - 1
+            }
+        };
+        found[pos].1.messages.push(idx);
+        // Parallel tool calls also leave side lines; only a new prompt makes a branch
+        found[pos].2 |= node.prompt.is_some();
+    }
+    found
+        .into_iter()
+        .filter(|(_, _, prompted)| *prompted)
+        .map(|(_, branch, _)| branch)
+        .collect()
+}
+
+/// First line of the first user message, or the workspace directory name.
+fn jsonl_title(messages: &[NormalizedMessage], workspace: Option<&Path>) -> Option<String> {
+    messages
+        .iter()
+        .find(|m| m.role == "user")
+        .map(|m| {
+            m.content
+                .lines()
+                .next()
+                .unwrap_or(&m.content)
+                .chars()
+                .take(100)
+                .collect::<String>()
+        })
+        .or_else(|| {
+            // Fallback to workspace directory name
+            workspace
+                .and_then(|p| p.file_name())
+                .and_then(|n| n.to_str())
+                .map(String::from)
+        })
+}
+
+/// Session file a transcript's `sessionId` points to:
+/// `<project>/<session>/subagents/agent-<id>.jsonl` or `<project>/agent-<id>.jsonl`
+/// next to `<project>/<session>.jsonl`.
+fn parent_session_path(path: &Path, session_id: &str) -> Option<PathBuf> {
+    let dir = path.parent()?;
+    let project = if dir.file_name().is_some_and(|n| n == "subagents") {
+        dir.parent()?.parent()?
+    } else {
+        dir
+    };
+    let parent = project.join(format!("{session_id}.jsonl"));
+    (parent != path && parent.is_file()).then_some(parent)
+}
+
+/// Title and subagents of parsed parent sessions, by path.
+type ParentCache = HashMap<PathBuf, Option<(Option<String>, Vec<SubagentThread>)>>;
+
+/// Link a subagent transcript to the Task call that spawned it.
+fn parent_link(
+    path: &Path,
+    session_id: Option<&str>,
+    transcript: &Transcript,
+    cache: &mut ParentCache,
+) -> Option<ParentLink> {
+    let session_id = session_id?;
+    let parent_path = parent_session_path(path, session_id)?;
+    let parsed = cache
+        .entry(parent_path.clone())
+        .or_insert_with(|| {
+            parse_jsonl(&parent_path).ok().map(|parent| {
+                let title = jsonl_title(&parent.messages, parent.workspace.as_deref());
+                (title, parent.threads.subagents)
+            })
+        })
+        .as_ref()?;
+    let (title, subagents) = parsed;
+    let spawn = subagents
+        .iter()
+        .find(|t| transcript.agent_id.is_some() && t.agent_id == transcript.agent_id)
+        .or_else(|| {
+            subagents
+                .iter()
+                .find(|t| t.transcript.as_deref() == Some(path))
+        })
+        .or_else(|| {
+            // Without an agent id in the parent, match the prompt
+            let prompt = transcript.prompt.as_deref()?.trim();
+            subagents
+                .iter()
+                .find(|t| t.prompt.as_deref().map(str::trim) == Some(prompt))
+        });
+    Some(ParentLink {
+        source_path: parent_path,
+        session_id: Some(session_id.to_string()),
+        title: title.clone(),
+        agent_id: transcript.agent_id.clone(),
+        description: spawn.and_then(|t| t.description.clone()),
+        spawned_by: spawn.and_then(|t| t.spawned_by),
+        spawned_at_line: spawn.and_then(|t| t.spawned_at_line),
+    })
+}
 
 pub struct ClaudeCodeConnector;
 impl Default for ClaudeCodeConnector {
@@ -65,6 +812,7 @@ impl Connector for ClaudeCodeConnector {
 
         let mut convs = Vec::new();
         let mut file_count = 0;
+        let mut parent_cache = ParentCache::new();
 
         for root in roots {
             let scan_target = if root.is_file() {
@@ -97,110 +845,24 @@ impl Connector for ClaudeCodeConnector {
                 let mut messages = Vec::new();
                 let mut started_at = None;
                 let mut ended_at = None;
-                // Track workspace from first entry's cwd field
                 let mut workspace: Option<PathBuf> = None;
                 let mut session_id: Option<String> = None;
                 let mut git_branch: Option<String> = None;
                 let mut content_string = String::new();
+                let mut threads = SessionThreads::default();
+                let mut transcript = None;
 
                 if ext == Some("jsonl") {
-                    let file = std::fs::File::open(entry.path())
-                        .with_context(|| format!("open {}", entry.path().display()))?;
-                    let reader = std::io::BufReader::new(file);
-
-                    for line_res in std::io::BufRead::lines(reader) {
-                        let line = match line_res {
-                            Ok(l) => l,
-                            Err(_) => continue,
-                        };
-                        if line.trim().is_empty() {
-                            continue;
-                        }
-                        let val: Value = match serde_json::from_str(&line) {
-                            Ok(v) => v,
-                            Err(_) => continue, // Skip malformed lines
-                        };
-
-                        // Extract session metadata from first available entry
-                        if workspace.is_none() {
-                            workspace = val.get("cwd").and_then(|v| v.as_str()).map(PathBuf::from);
-                        }
-                        if session_id.is_none() {
-                            session_id = val
-                                .get("sessionId")
-                                .and_then(|v| v.as_str())
-                                .map(String::from);
-                        }
-                        if git_branch.is_none() {
-                            git_branch = val
-                                .get("gitBranch")
-                                .and_then(|v| v.as_str())
-                                .map(String::from);
-                        }
-
-                        // Filter to user/assistant entries only (skip summary, file-history-snapshot, etc.)
-                        let entry_type = val.get("type").and_then(|v| v.as_str());
-                        let role_hint = val
-                            .get("message")
-                            .and_then(|m| m.get("role"))
-                            .and_then(|v| v.as_str())
-                            .or_else(|| val.get("role").and_then(|v| v.as_str()));
-                        let is_user_assistant = matches!(entry_type, Some("user" | "assistant"))
-                            || (entry_type == Some("message")
-                                && matches!(role_hint, Some("user" | "assistant")));
-                        if !is_user_assistant {
-                            continue;
-                        }
-
-                        // Parse ISO-8601 timestamp using shared utility
-                        let created = val
-                            .get("timestamp")
-                            .and_then(crate::connectors::parse_timestamp);
-
-                        // NOTE: Do NOT filter individual messages by timestamp here!
-                        // The file-level check in file_modified_since() is sufficient.
-                        // Filtering messages would cause older messages to be lost when
-                        // the file is re-indexed after new messages are added.
-
-                        started_at = started_at.or(created);
-                        ended_at = created.or(ended_at);
-
-                        // Role from message.role, top-level role, or entry type
-                        let role = role_hint.or(entry_type).unwrap_or("agent");
-
-                        // Content from message.content or top-level content (may be string or array)
-                        let content_val = val
-                            .get("message")
-                            .and_then(|m| m.get("content"))
-                            .or_else(|| val.get("content"));
-                        let content_str = content_val
-                            .map(crate::connectors::flatten_content)
-                            .unwrap_or_default();
-
-                        // Skip entries with empty content
-                        if content_str.trim().is_empty() {
-                            continue;
-                        }
-
-                        // Extract model name for author field
-                        let author = val
-                            .get("message")
-                            .and_then(|m| m.get("model"))
-                            .and_then(|v| v.as_str())
-                            .map(String::from);
-
-                        messages.push(NormalizedMessage {
-                            idx: 0, // will be re-assigned after filtering
-                            role: role.to_string(),
-                            author,
-                            created_at: created,
-                            content: content_str,
-                            extra: val,
-                            snippets: Vec::new(),
-                        });
-                    }
-                    // Re-assign sequential indices after filtering
-                    super::reindex_messages(&mut messages);
+                    JsonlSession {
+                        messages,
+                        started_at,
+                        ended_at,
+                        workspace,
+                        session_id,
+                        git_branch,
+                        threads,
+                        transcript,
+                    } = parse_jsonl(entry.path())?;
                 } else {
                     // Safety check: Don't read files larger than 100MB to avoid OOM
                     if let Ok(metadata) = fs::metadata(entry.path())
@@ -263,7 +925,10 @@ impl Connector for ClaudeCodeConnector {
                                 created_at: created,
                                 content: content_str,
                                 extra: item.clone(),
-                                snippets: Vec::new(),
+                                snippets: item
+                                    .get("content")
+                                    .map(crate::connectors::extract_tool_snippets)
+                                    .unwrap_or_default(),
                             });
                         }
                     }
@@ -279,27 +944,8 @@ impl Connector for ClaudeCodeConnector {
                 tracing::debug!(path = %entry.path().display(), messages = messages.len(), "claude_code extracted messages");
 
                 // Extract title from first user message, truncated to reasonable length
-                let title = if ext == Some("jsonl") {
-                    messages
-                        .iter()
-                        .find(|m| m.role == "user")
-                        .map(|m| {
-                            m.content
-                                .lines()
-                                .next()
-                                .unwrap_or(&m.content)
-                                .chars()
-                                .take(100)
-                                .collect::<String>()
-                        })
-                        .or_else(|| {
-                            // Fallback to workspace directory name
-                            workspace
-                                .as_ref()
-                                .and_then(|p| p.file_name())
-                                .and_then(|n| n.to_str())
-                                .map(String::from)
-                        })
+                let mut title = if ext == Some("jsonl") {
+                    jsonl_title(&messages, workspace.as_deref())
                 } else {
                     serde_json::from_str::<Value>(&content_string)
                         .ok()
@@ -316,6 +962,29 @@ impl Connector for ClaudeCodeConnector {
                         })
                 };
 
+                // Subagent transcripts carry their parent session in the title, as breadcrumbs
+                if let Some(transcript) = &transcript {
+                    threads.parent = parent_link(
+                        entry.path(),
+                        session_id.as_deref(),
+                        transcript,
+                        &mut parent_cache,
+                    );
+                }
+                if let Some(parent) = &threads.parent {
+                    let own = parent.description.clone().or(title);
+                    title = match (&parent.title, own) {
+                        (Some(parent_title), Some(own)) => Some(format!("{parent_title} › {own}")),
+                        (parent_title, own) => own.or_else(|| parent_title.clone()),
+                    };
+                }
+                let mut metadata = serde_json::json!({
+                    "source": "claude_code",
+                    "sessionId": session_id,
+                    "gitBranch": git_branch
+                });
+                threads.write_metadata(&mut metadata);
+
                 convs.push(NormalizedConversation {
                     agent_slug: "claude_code".into(),
                     external_id: entry
@@ -328,11 +997,7 @@ impl Connector for ClaudeCodeConnector {
                     source_path: entry.path().to_path_buf(),
                     started_at,
                     ended_at,
-                    metadata: serde_json::json!({
-                        "source": "claude_code",
-                        "sessionId": session_id,
-                        "gitBranch": git_branch
-                    }),
+                    metadata,
                     messages,
                 });
             }
@@ -458,6 +1123,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scan_records_usage_once_per_api_message() {
+        let dir = TempDir::new().unwrap();
+        let claude_dir = dir.path().join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+
+        let usage = r#""usage":{"input_tokens":12,"cache_creation_input_tokens":300,"cache_read_input_tokens":4000,"output_tokens":90}"#;
+        let content = format!(
+            r#"{{"type":"assistant","message":{{"id":"msg_1","role":"assistant","model":"claude-sonnet-4-5","content":[{{"type":"text","text":"Reading the file"}}],{usage}}}}}
+{{"type":"assistant","message":{{"id":"msg_1","role":"assistant","model":"claude-sonnet-4-5","content":[{{"type":"tool_use","name":"Read","input":{{"file_path":"/src/lib.rs"}}}}],{usage}}}}}
+"#
+        );
+        fs::write(claude_dir.join("session.jsonl"), content).unwrap();
+
+        let connector = ClaudeCodeConnector::new();
+        let ctx = ScanContext::local_default(claude_dir.clone(), None);
+        let convs = connector.scan(&ctx).unwrap();
+
+        let messages = &convs[0].messages;
+        assert_eq!(messages.len(), 2);
+        let usage = TokenUsage::from_extra(&messages[0].extra).unwrap();
+        assert_eq!(usage.model.as_deref(), Some("claude-sonnet-4-5"));
+        assert_eq!(usage.cache_read_tokens, 4000);
+        assert_eq!(usage.total(), 4402);
+        assert!(TokenUsage::from_extra(&messages[1].extra).is_none());
+    }
+
     #[test]
     fn scan_parses_iso8601_timestamp() {
         let dir = TempDir::new().unwrap();
@@ -1100,4 +1792,162 @@ mod tests {
         );
         assert_eq!(convs[0].messages[0].content, "Generic root test");
     }
+
+    // =========================================================================
+    // Subagent and branch threading tests
+    // =========================================================================
+
+    fn write_jsonl(path: &Path, lines: &[Value]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let content: Vec<String> = lines.iter().map(Value::to_string).collect();
+        fs::write(path, content.join("\n")).unwrap();
+    }
+
+    fn entry(kind: &str, uuid: &str, parent: Option<&str>, content: Value) -> Value {
+        json!({
+            "type": kind,
+            "uuid": uuid,
+            "parentUuid": parent,
+            "sessionId": "sess-1",
+            "message": { "role": kind, "content": content }
+        })
+    }
+
+    fn task_call(uuid: &str, parent: &str, prompt: &str) -> Value {
+        entry(
+            "assistant",
+            uuid,
+            Some(parent),
+            json!([
+                { "type": "text", "text": "Delegating the search" },
+                {
+                    "type": "tool_use",
+                    "id": "toolu_1",
+                    "name": "Task",
+                    "input": { "description": "Find callers", "prompt": prompt }
+                }
+            ]),
+        )
+    }
+
+    #[test]
+    fn scan_links_subagent_transcript_to_parent_session() {
+        let dir = TempDir::new().unwrap();
+        let claude_dir = dir.path().join(".claude");
+        let session = claude_dir.join("sess-1.jsonl");
+        let mut result = entry(
+            "user",
+            "r1",
+            Some("a1"),
+            json!([
+                { "type": "tool_result", "tool_use_id": "toolu_1", "content": "3 callers" },
+                { "type": "text", "text": "Subagent finished" }
+            ]),
+        );
+        result["toolUseResult"] = json!({ "agentId": "abc" });
+        write_jsonl(
+            &session,
+            &[
+                entry("user", "u1", None, json!("Trace foo callers")),
+                task_call("a1", "u1", "Find all callers of foo"),
+                result,
+                entry("assistant", "a2", Some("r1"), json!("foo has 3 callers")),
+            ],
+        );
+        let subagent_line = |uuid: &str, parent: Option<&str>, kind: &str, text: &str| {
+            let mut line = entry(kind, uuid, parent, json!(text));
+            line["isSidechain"] = json!(true);
+            line["agentId"] = json!("abc");
+            line
+        };
+        let transcript = claude_dir.join("sess-1/subagents/agent-abc.jsonl");
+        write_jsonl(
+            &transcript,
+            &[
+                subagent_line("s1", None, "user", "Find all callers of foo"),
+                subagent_line("s2", Some("s1"), "assistant", "Found 3 callers"),
+            ],
+        );
+
+        let connector = ClaudeCodeConnector::new();
+        let ctx = ScanContext::local_default(claude_dir.clone(), None);
+        let convs = connector.scan(&ctx).unwrap();
+        assert_eq!(convs.len(), 2);
+
+        let parent = convs.iter().find(|c| c.source_path == session).unwrap();
+        let threads = SessionThreads::from_metadata(&parent.metadata);
+        assert_eq!(threads.subagents.len(), 1);
+        let thread = &threads.subagents[0];
+        assert_eq!(thread.agent_id.as_deref(), Some("abc"));
+        assert_eq!(thread.description.as_deref(), Some("Find callers"));
+        assert_eq!(thread.spawned_by, Some(1));
+        assert_eq!(thread.spawned_at_line, Some(2));
+        assert_eq!(thread.transcript.as_deref(), Some(transcript.as_path()));
+        assert!(threads.branches.is_empty());
+
+        let child = convs.iter().find(|c| c.source_path == transcript).unwrap();
+        assert_eq!(
+            child.title.as_deref(),
+            Some("Trace foo callers › Find callers")
+        );
+        let link = SessionThreads::from_metadata(&child.metadata)
+            .parent
+            .unwrap();
+        assert_eq!(link.source_path, session);
+        assert_eq!(link.spawned_by, Some(1));
+        assert_eq!(session_threads(&transcript).unwrap().parent, Some(link));
+    }
+
+    #[test]
+    fn scan_threads_inline_sidechains_and_branches() {
+        let dir = TempDir::new().unwrap();
+        let claude_dir = dir.path().join(".claude");
+        let session = claude_dir.join("sess-1.jsonl");
+        let sidechain = |uuid: &str, parent: Option<&str>, kind: &str, text: &str| {
+            let mut line = entry(kind, uuid, parent, json!(text));
+            line["isSidechain"] = json!(true);
+            line
+        };
+        write_jsonl(
+            &session,
+            &[
+                entry("user", "u0", None, json!("Start")),
+                entry("assistant", "a0", Some("u0"), json!("Ready")),
+                // Abandoned by a rewind to a0
+                entry("user", "x1", Some("a0"), json!("Abandoned idea")),
+                entry("assistant", "x2", Some("x1"), json!("Sure")),
+                entry("user", "u1", Some("a0"), json!("Trace foo callers")),
+                task_call("a1", "u1", "Find all callers of foo"),
+                sidechain("s1", None, "user", "Find all callers of foo"),
+                sidechain("s2", Some("s1"), "assistant", "Found 3 callers"),
+                entry("assistant", "a2", Some("a1"), json!("foo has 3 callers")),
+            ],
+        );
+
+        let connector = ClaudeCodeConnector::new();
+        let ctx = ScanContext::local_default(claude_dir.clone(), None);
+        let convs = connector.scan(&ctx).unwrap();
+        assert_eq!(convs.len(), 1);
+        // Sidechains stay in file order
+        assert_eq!(convs[0].messages.len(), 9);
+
+        let threads = SessionThreads::from_metadata(&convs[0].metadata);
+        assert_eq!(threads.subagents.len(), 1);
+        let thread = &threads.subagents[0];
+        assert_eq!(thread.spawned_by, Some(5));
+        assert_eq!(thread.messages, vec![6, 7]);
+        assert_eq!(thread.lines, vec![7, 8]);
+        assert!(thread.transcript.is_none());
+        assert_eq!(threads.inline_thread_of(7), Some(thread));
+        assert!(threads.inline_thread_of(8).is_none());
+
+        assert_eq!(
+            threads.branches,
+            vec![Branch {
+                fork_after: Some(1),
+                messages: vec![2, 3],
+            }]
+        );
+        assert!(threads.parent.is_none());
+    }
 }
@@ -5,7 +5,8 @@ use anyhow::{Context, Result};
 use serde_json::Value;
 
 use crate::connectors::{
-    Connector, DetectionResult, NormalizedConversation, NormalizedMessage, ScanContext,
+    Connector, DetectionResult, NormalizedConversation, NormalizedMessage, NormalizedSnippet,
+    ScanContext, tool_call_snippets,
 };
 
 pub struct ClineConnector;
@@ -219,7 +220,7 @@ impl Connector for ClineConnector {
                             created_at: created,
                             content: content.to_string(),
                             extra: item.clone(),
-                            snippets: Vec::new(),
+                            snippets: cline_tool_snippets(item, content),
                         });
                     }
                 }
@@ -286,6 +287,23 @@ impl Connector for ClineConnector {
     }
 }
 
+/// File snippets for a Cline tool message.
+///
+/// UI messages record tool use as `{"say": "tool", "text": "{\"tool\": \"editedExistingFile\", ...}"}`
+/// (or `"ask": "tool"` while awaiting approval), with the tool arguments JSON-encoded in `text`.
+fn cline_tool_snippets(item: &Value, text: &str) -> Vec<NormalizedSnippet> {
+    let is_tool = item.get("say").and_then(|v| v.as_str()) == Some("tool")
+        || item.get("ask").and_then(|v| v.as_str()) == Some("tool");
+    if !is_tool {
+        return Vec::new();
+    }
+    let Ok(args) = serde_json::from_str::<Value>(text) else {
+        return Vec::new();
+    };
+    let tool = args.get("tool").and_then(|v| v.as_str()).unwrap_or("");
+    tool_call_snippets(tool, &args)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -515,6 +533,30 @@ mod tests {
         assert_eq!(convs[0].messages[0].content, "Content from text field");
     }
 
+    #[test]
+    fn scan_extracts_tool_snippets() {
+        let dir = TempDir::new().unwrap();
+        let storage = create_cline_storage(&dir);
+        let task_dir = create_task_dir(&storage, "task-tool");
+
+        let tool = json!({"tool": "editedExistingFile", "path": "src/app.ts", "diff": "+x"});
+        let messages = json!([
+            {"type": "say", "say": "text", "text": "Editing the app"},
+            {"type": "say", "say": "tool", "text": tool.to_string()}
+        ]);
+        fs::write(task_dir.join("ui_messages.json"), messages.to_string()).unwrap();
+
+        let connector = ClineConnector::new();
+        let ctx = ScanContext::local_default(storage.clone(), None);
+        let convs = connector.scan(&ctx).unwrap();
+
+        assert!(convs[0].messages[0].snippets.is_empty());
+        let snippets = &convs[0].messages[1].snippets;
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].file_path, Some(PathBuf::from("src/app.ts")));
+        assert_eq!(snippets[0].snippet_text.as_deref(), Some("+x"));
+    }
+
     #[test]
     fn scan_parses_content_from_message_field() {
         let dir = TempDir::new().unwrap();
@@ -6,8 +6,61 @@ use serde_json::Value;
 use walkdir::WalkDir;
 
 use crate::connectors::{
-    Connector, DetectionResult, NormalizedConversation, NormalizedMessage, ScanContext,
+    Connector, DetectionResult, NormalizedConversation, NormalizedMessage, NormalizedSnippet,
+    ScanContext, tool_call_snippets,
 };
+use crate::model::types::TokenUsage;
+
+/// Credits `token_count` events to the agent messages of a rollout.
+#[derive(Default)]
+struct CodexUsage {
+    /// `total_token_usage` of the previous event; Codex repeats events without a new request
+    last_total: Option<Value>,
+    /// Usage reported while the turn had no agent message yet
+    pending: TokenUsage,
+}
+
+impl CodexUsage {
+    /// Add the request reported by a `token_count` payload to the latest agent message.
+    /// OpenAI counts cached input inside `input_tokens` and reasoning inside `output_tokens`.
+    fn credit(&mut self, payload: &Value, model: Option<&str>, messages: &mut [NormalizedMessage]) {
+        let Some(info) = payload.get("info").filter(|info| !info.is_null()) else {
+            return;
+        };
+        let total = info.get("total_token_usage").cloned();
+        if total.is_some() && total == self.last_total {
+            return;
+        }
+        self.last_total = total;
+        let Some(last) = info.get("last_token_usage") else {
+            return;
+        };
+        let field = |key: &str| last.get(key).and_then(|v| v.as_i64()).unwrap_or(0);
+        let cached = field("cached_input_tokens");
+        self.pending.add(&TokenUsage {
+            model: model.map(String::from),
+            input_tokens: (field("input_tokens") - cached).max(0),
+            output_tokens: field("output_tokens"),
+            cache_read_tokens: cached,
+            cache_write_tokens: 0,
+        });
+        // Requests that only ran tools wait for the turn's next agent message
+        if let Some(msg) = messages.last_mut().filter(|m| m.role != "user") {
+            std::mem::take(&mut self.pending).add_to_extra(&mut msg.extra);
+        }
+    }
+
+    /// At the end of the rollout, give usage still waiting for an agent message to the
+    /// last one there is, so requests of an unfinished turn are not dropped.
+    fn finish(&mut self, messages: &mut [NormalizedMessage]) {
+        if self.pending.is_empty() {
+            return;
+        }
+        if let Some(msg) = messages.iter_mut().rev().find(|m| m.role != "user") {
+            std::mem::take(&mut self.pending).add_to_extra(&mut msg.extra);
+        }
+    }
+}
 
 pub struct CodexConnector;
 impl Default for CodexConnector {
@@ -141,6 +194,8 @@ impl Connector for CodexConnector {
                 let mut started_at = None;
                 let mut ended_at = None;
                 let mut session_cwd: Option<PathBuf> = None;
+                let mut model: Option<String> = None;
+                let mut usage = CodexUsage::default();
 
                 if ext == Some("jsonl") {
                     let f = std::fs::File::open(&file)
@@ -190,10 +245,14 @@ impl Connector for CodexConnector {
                                         .and_then(|v| v.as_str())
                                         .unwrap_or("agent");
 
-                                    let content_str = payload
+                                    let snippets = codex_tool_snippets(payload);
+                                    let mut content_str = payload
                                         .get("content")
                                         .map(crate::connectors::flatten_content)
                                         .unwrap_or_default();
+                                    if content_str.trim().is_empty() && !snippets.is_empty() {
+                                        content_str = tool_call_summary(payload, &snippets);
+                                    }
 
                                     if content_str.trim().is_empty() {
                                         continue;
@@ -209,7 +268,7 @@ impl Connector for CodexConnector {
                                         created_at: created,
                                         content: content_str,
                                         extra: val,
-                                        snippets: Vec::new(),
+                                        snippets,
                                     });
                                 }
                             }
@@ -258,13 +317,26 @@ impl Connector for CodexConnector {
                                                 });
                                             }
                                         }
-                                        _ => {} // Skip token_count, turn_aborted, etc.
+                                        Some("token_count") => {
+                                            usage.credit(payload, model.as_deref(), &mut messages);
+                                        }
+                                        _ => {} // Skip turn_aborted, etc.
                                     }
                                 }
                             }
-                            _ => {} // Skip turn_context and unknown types
+                            "turn_context" => {
+                                if let Some(m) = val
+                                    .get("payload")
+                                    .and_then(|p| p.get("model"))
+                                    .and_then(|v| v.as_str())
+                                {
+                                    model = Some(m.to_string());
+                                }
+                            }
+                            _ => {} // Skip unknown types
                         }
                     }
+                    usage.finish(&mut messages);
                     // Re-assign sequential indices after filtering
                     super::reindex_messages(&mut messages);
                 } else if ext == Some("json") {
@@ -288,10 +360,14 @@ impl Connector for CodexConnector {
                         for item in items {
                             let role = item.get("role").and_then(|v| v.as_str()).unwrap_or("agent");
 
-                            let content_str = item
+                            let snippets = codex_tool_snippets(item);
+                            let mut content_str = item
                                 .get("content")
                                 .map(crate::connectors::flatten_content)
                                 .unwrap_or_default();
+                            if content_str.trim().is_empty() && !snippets.is_empty() {
+                                content_str = tool_call_summary(item, &snippets);
+                            }
 
                             if content_str.trim().is_empty() {
                                 continue;
@@ -314,7 +390,7 @@ impl Connector for CodexConnector {
                                 created_at: created,
                                 content: content_str,
                                 extra: item.clone(),
-                                snippets: Vec::new(),
+                                snippets,
                             });
                         }
                     }
@@ -364,6 +440,44 @@ impl Connector for CodexConnector {
     }
 }
 
+/// File snippets for a Codex tool call item (`function_call`, `custom_tool_call`
+/// or `local_shell_call`). Edits arrive as `apply_patch` envelopes.
+fn codex_tool_snippets(item: &Value) -> Vec<NormalizedSnippet> {
+    let name = item.get("name").and_then(|v| v.as_str()).unwrap_or("");
+    match item.get("type").and_then(|v| v.as_str()) {
+        Some("function_call") => {
+            let args = item
+                .get("arguments")
+                .and_then(|v| v.as_str())
+                .and_then(|s| serde_json::from_str::<Value>(s).ok())
+                .unwrap_or(Value::Null);
+            tool_call_snippets(name, &args)
+        }
+        Some("custom_tool_call") => {
+            tool_call_snippets(name, item.get("input").unwrap_or(&Value::Null))
+        }
+        Some("local_shell_call") => {
+            tool_call_snippets("local_shell", item.get("action").unwrap_or(&Value::Null))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Searchable stand-in content for a tool call that has no text of its own,
+/// in the same `[Tool: name - detail]` shape `flatten_content` uses.
+fn tool_call_summary(item: &Value, snippets: &[NormalizedSnippet]) -> String {
+    let name = item
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("apply_patch");
+    let files: Vec<String> = snippets
+        .iter()
+        .filter_map(|s| s.file_path.as_ref())
+        .map(|p| p.display().to_string())
+        .collect();
+    format!("[Tool: {name} - {}]", files.join(", "))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -707,6 +821,78 @@ not valid json at all
         assert_eq!(convs[0].messages.len(), 1);
     }
 
+    #[test]
+    fn scan_credits_token_counts_to_agent_messages() {
+        let dir = TempDir::new().unwrap();
+        let codex_dir = dir.path().join(".codex");
+        let sessions = codex_dir.join("sessions");
+        fs::create_dir_all(&sessions).unwrap();
+
+        let usage = r#"{"input_tokens":1200,"cached_input_tokens":1000,"output_tokens":80,"reasoning_output_tokens":30,"total_tokens":1280}"#;
+        let content = format!(
+            r#"{{"type":"turn_context","timestamp":"2025-12-01T10:00:00Z","payload":{{"model":"gpt-5-codex"}}}}
+{{"type":"response_item","timestamp":"2025-12-01T10:00:01Z","payload":{{"role":"user","content":"Fix the build"}}}}
+{{"type":"response_item","timestamp":"2025-12-01T10:00:02Z","payload":{{"role":"assistant","content":"Done"}}}}
+{{"type":"event_msg","timestamp":"2025-12-01T10:00:03Z","payload":{{"type":"token_count","info":{{"total_token_usage":{usage},"last_token_usage":{usage}}}}}}}
+{{"type":"event_msg","timestamp":"2025-12-01T10:00:04Z","payload":{{"type":"token_count","info":{{"total_token_usage":{usage},"last_token_usage":{usage}}}}}}}
+"#
+        );
+        fs::write(sessions.join("rollout-usage.jsonl"), content).unwrap();
+
+        let connector = CodexConnector::new();
+        let ctx = ScanContext::local_default(codex_dir.clone(), None);
+        let convs = connector.scan(&ctx).unwrap();
+
+        let messages = &convs[0].messages;
+        assert!(TokenUsage::from_extra(&messages[0].extra).is_none());
+        // The repeated event reports no new request
+        let usage = TokenUsage::from_extra(&messages[1].extra).unwrap();
+        assert_eq!(usage.model.as_deref(), Some("gpt-5-codex"));
+        assert_eq!(usage.input_tokens, 200);
+        assert_eq!(usage.cache_read_tokens, 1000);
+        assert_eq!(usage.output_tokens, 80);
+    }
+
+    #[test]
+    fn scan_keeps_usage_of_a_turn_without_a_final_agent_message() {
+        let dir = TempDir::new().unwrap();
+        let codex_dir = dir.path().join(".codex");
+        let sessions = codex_dir.join("sessions");
+        fs::create_dir_all(&sessions).unwrap();
+
+        let usage = |total: i64| {
+            format!(
+                r#"{{"input_tokens":{total},"cached_input_tokens":0,"output_tokens":10,"total_tokens":{}}}"#,
+                total + 10
+            )
+        };
+        let token_count = |total: i64, last: i64| {
+            format!(
+                r#"{{"type":"event_msg","timestamp":"2025-12-01T10:00:05Z","payload":{{"type":"token_count","info":{{"total_token_usage":{},"last_token_usage":{}}}}}}}"#,
+                usage(total),
+                usage(last)
+            )
+        };
+        let content = [
+            r#"{"type":"response_item","timestamp":"2025-12-01T10:00:01Z","payload":{"role":"user","content":"Fix the build"}}"#.to_string(),
+            r#"{"type":"response_item","timestamp":"2025-12-01T10:00:02Z","payload":{"role":"assistant","content":"Looking"}}"#.to_string(),
+            token_count(100, 100),
+            r#"{"type":"response_item","timestamp":"2025-12-01T10:00:03Z","payload":{"role":"user","content":"Also the docs"}}"#.to_string(),
+            // Tool-only request of a turn the rollout ends in
+            token_count(150, 50),
+        ]
+        .join("\n");
+        fs::write(sessions.join("rollout-open-turn.jsonl"), content).unwrap();
+
+        let connector = CodexConnector::new();
+        let ctx = ScanContext::local_default(codex_dir.clone(), None);
+        let convs = connector.scan(&ctx).unwrap();
+
+        let usage = TokenUsage::from_extra(&convs[0].messages[1].extra).unwrap();
+        assert_eq!(usage.input_tokens, 150);
+        assert_eq!(usage.output_tokens, 20);
+    }
+
     #[test]
     fn scan_assigns_sequential_indices() {
         let dir = TempDir::new().unwrap();
@@ -1117,6 +1303,44 @@ not valid json at all
         assert!(convs[0].messages[0].content.contains("Part one"));
     }
 
+    #[test]
+    fn scan_extracts_apply_patch_snippets() {
+        let dir = TempDir::new().unwrap();
+        let codex_dir = dir.path().join(".codex");
+        let sessions = codex_dir.join("sessions");
+        fs::create_dir_all(&sessions).unwrap();
+
+        let patch = "*** Begin Patch\n*** Update File: src/foo.rs\n@@\n-a\n+b\n*** End Patch";
+        let lines = [
+            json!({"type": "response_item", "payload": {"role": "user", "content": "fix foo"}}),
+            json!({
+                "type": "response_item",
+                "payload": {
+                    "type": "function_call",
+                    "name": "shell",
+                    "arguments": json!({"command": ["apply_patch", patch]}).to_string()
+                }
+            }),
+            json!({
+                "type": "response_item",
+                "payload": {"type": "custom_tool_call", "name": "apply_patch", "input": patch}
+            }),
+        ];
+        let body: String = lines.iter().map(|l| l.to_string() + "\n").collect();
+        fs::write(sessions.join("rollout-patch.jsonl"), body).unwrap();
+
+        let connector = CodexConnector::new();
+        let ctx = ScanContext::local_default(codex_dir.clone(), None);
+        let convs = connector.scan(&ctx).unwrap();
+
+        assert_eq!(convs[0].messages.len(), 3);
+        for msg in &convs[0].messages[1..] {
+            assert_eq!(msg.snippets.len(), 1);
+            assert_eq!(msg.snippets[0].file_path, Some(PathBuf::from("src/foo.rs")));
+            assert!(msg.content.contains("src/foo.rs"));
+        }
+    }
+
     #[test]
     fn scan_uses_default_role_when_missing() {
         let dir = TempDir::new().unwrap();
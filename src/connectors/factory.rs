@@ -20,7 +20,7 @@ use walkdir::WalkDir;
 
 use crate::connectors::{
     Connector, DetectionResult, NormalizedConversation, NormalizedMessage, ScanContext,
-    file_modified_since, flatten_content, parse_timestamp,
+    extract_tool_snippets, file_modified_since, flatten_content, parse_timestamp,
 };
 
 pub struct FactoryConnector;
@@ -213,6 +213,7 @@ fn parse_factory_session(path: &Path) -> Result<Option<NormalizedConversation>>
                 // Extract content from message.content
                 let content_val = val.get("message").and_then(|m| m.get("content"));
                 let content_str = content_val.map(flatten_content).unwrap_or_default();
+                let snippets = content_val.map(extract_tool_snippets).unwrap_or_default();
 
                 // Skip entries with empty content
                 if content_str.trim().is_empty() {
@@ -233,7 +234,7 @@ fn parse_factory_session(path: &Path) -> Result<Option<NormalizedConversation>>
                     created_at: created,
                     content: content_str,
                     extra: val,
-                    snippets: Vec::new(),
+                    snippets,
                 });
             }
             // Skip other types: todo_state, tool_result, etc.
@@ -0,0 +1,725 @@
+//! Config-driven connector for agents without a built-in connector.
+//!
+//! Each `*.toml` file in `$XDG_CONFIG_HOME/cass/connectors/` (or
+//! `CASS_CONNECTORS_DIR`) declares one agent:
+//!
+//! ```toml
+//! slug = "acme"                       # agent name in filters and results
+//! format = "jsonl"                    # jsonl | json | markdown
+//! roots = ["~/.acme/sessions/**/*.jsonl"]
+//!
+//! [mapping]                           # JSON pointers (RFC 6901)
+//! role = "/message/role"
+//! content = "/message/content"
+//! timestamp = "/ts"
+//! workspace = "/cwd"
+//! title = "/title"
+//! session_id = "/session"
+//!
+//! [roles]                             # optional: raw role -> cass role
+//! human = "user"
+//! ```
+//!
+//! - `jsonl`: every line is a record. A record is a message when both `role`
+//!   and `content` resolve; `workspace`, `title` and `session_id` are taken
+//!   from the first record that has them, so they can live in a header line.
+//! - `json`: one session per file. Messages are the array at
+//!   `mapping.messages` (default `/messages`, or the document itself when it is
+//!   an array); message pointers are relative to each element and session
+//!   pointers to the document.
+//! - `markdown`: a heading whose text is a role (`## User`, `### Assistant:`)
+//!   starts a message; the first `#` heading that is not a role is the title.
+//!   Mappings are not used.
+//!
+//! Content may be a string or an array of content blocks. All definitions are
+//! served by one connector, so they share watch mode and provenance with the
+//! built-in connectors. A remote source picks a definition up when one of its
+//! `paths` is the directory part of a root glob (or a parent of it).
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::connectors::{
+    Connector, DetectionResult, NormalizedConversation, NormalizedMessage, ScanContext, ScanRoot,
+    extract_tool_snippets, file_modified_since, flatten_content, parse_timestamp,
+};
+use crate::sources::sync::path_to_safe_dirname;
+
+/// Slugs of the built-in connectors and `cass import`, which a definition may not reuse
+const BUILTIN_SLUGS: &[&str] = &[
+    "codex",
+    "cline",
+    "gemini",
+    "claude_code",
+    "opencode",
+    "amp",
+    "aider",
+    "cursor",
+    "chatgpt",
+    "pi_agent",
+    "factory",
+    "claude_ai",
+];
+
+/// Characters that start the wildcard part of a root glob
+const GLOB_META: &[char] = &['*', '?', '[', '{'];
+
+/// File format of the sessions matched by a definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionFormat {
+    Jsonl,
+    Json,
+    Markdown,
+}
+
+/// JSON pointers locating each field in a record.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldMapping {
+    /// `json` only: the message array
+    #[serde(default)]
+    pub messages: Option<String>,
+    #[serde(default = "default_role_pointer")]
+    pub role: String,
+    #[serde(default = "default_content_pointer")]
+    pub content: String,
+    #[serde(default = "default_timestamp_pointer")]
+    pub timestamp: String,
+    #[serde(default)]
+    pub workspace: Option<String>,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub session_id: Option<String>,
+}
+
+fn default_role_pointer() -> String {
+    "/role".to_string()
+}
+
+fn default_content_pointer() -> String {
+    "/content".to_string()
+}
+
+fn default_timestamp_pointer() -> String {
+    "/timestamp".to_string()
+}
+
+impl Default for FieldMapping {
+    fn default() -> Self {
+        Self {
+            messages: None,
+            role: default_role_pointer(),
+            content: default_content_pointer(),
+            timestamp: default_timestamp_pointer(),
+            workspace: None,
+            title: None,
+            session_id: None,
+        }
+    }
+}
+
+/// One agent, as declared in a connector TOML file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConnectorDefinition {
+    pub slug: String,
+    pub format: SessionFormat,
+    /// Globs for session files; `~/` is the home directory
+    pub roots: Vec<String>,
+    #[serde(default)]
+    pub mapping: FieldMapping,
+    /// Raw role values mapped to cass roles; others are lowercased
+    #[serde(default)]
+    pub roles: HashMap<String, String>,
+}
+
+impl ConnectorDefinition {
+    /// Parse and validate a definition from TOML.
+    pub fn from_toml(text: &str) -> Result<Self> {
+        let def: Self = toml::from_str(text)?;
+        def.validate()?;
+        Ok(def)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.slug.is_empty()
+            || !self
+                .slug
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+        {
+            bail!(
+                "slug '{}' must be lowercase letters, digits, '_' or '-'",
+                self.slug
+            );
+        }
+        if BUILTIN_SLUGS.contains(&self.slug.as_str()) {
+            bail!("slug '{}' is used by a built-in connector", self.slug);
+        }
+        if self.roots.is_empty() {
+            bail!("at least one root glob is required");
+        }
+        for root in &self.roots {
+            glob::Pattern::new(&expand_home(root))
+                .with_context(|| format!("invalid root glob '{root}'"))?;
+        }
+        let m = &self.mapping;
+        for pointer in [Some(&m.role), Some(&m.content), Some(&m.timestamp)]
+            .into_iter()
+            .chain([&m.messages, &m.workspace, &m.title, &m.session_id].map(Option::as_ref))
+            .flatten()
+        {
+            if !pointer.is_empty() && !pointer.starts_with('/') {
+                bail!("'{pointer}' is not a JSON pointer (they start with '/')");
+            }
+        }
+        Ok(())
+    }
+
+    fn map_role(&self, raw: &str) -> String {
+        self.roles
+            .get(raw)
+            .cloned()
+            .unwrap_or_else(|| raw.to_lowercase())
+    }
+
+    fn format_name(&self) -> &'static str {
+        match self.format {
+            SessionFormat::Jsonl => "jsonl",
+            SessionFormat::Json => "json",
+            SessionFormat::Markdown => "markdown",
+        }
+    }
+
+    /// The cass role named by a markdown heading, if it names one
+    fn heading_role(&self, heading: &str) -> Option<String> {
+        let text = heading.trim().trim_end_matches(':').trim();
+        if let Some(role) = self.roles.get(text) {
+            return Some(role.clone());
+        }
+        let lower = text.to_lowercase();
+        matches!(lower.as_str(), "user" | "assistant" | "system" | "tool").then_some(lower)
+    }
+}
+
+pub struct GenericConnector {
+    definitions: Vec<ConnectorDefinition>,
+}
+
+impl Default for GenericConnector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GenericConnector {
+    /// Load every definition in [`Self::definitions_dir`]; invalid files are logged and skipped.
+    pub fn new() -> Self {
+        let definitions = match Self::definitions_dir() {
+            Some(dir) => load_definitions(&dir)
+                .into_iter()
+                .filter_map(|(path, def)| match def {
+                    Ok(def) => Some(def),
+                    Err(e) => {
+                        tracing::warn!(path = %path.display(), "invalid connector definition: {e:#}");
+                        None
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        Self::with_definitions(definitions)
+    }
+
+    pub fn with_definitions(definitions: Vec<ConnectorDefinition>) -> Self {
+        Self { definitions }
+    }
+
+    pub fn definitions(&self) -> &[ConnectorDefinition] {
+        &self.definitions
+    }
+
+    /// `CASS_CONNECTORS_DIR`, else `connectors/` next to `sources.toml`
+    pub fn definitions_dir() -> Option<PathBuf> {
+        if let Ok(dir) = dotenvy::var("CASS_CONNECTORS_DIR") {
+            return Some(PathBuf::from(dir));
+        }
+        crate::sources::config::SourcesConfig::config_path()
+            .ok()
+            .and_then(|p| p.parent().map(|dir| dir.join("connectors")))
+    }
+
+    /// Session files of `def` to scan under `ctx`.
+    fn session_files(def: &ConnectorDefinition, ctx: &ScanContext) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for root in &def.roots {
+            let pattern = expand_home(root);
+            if ctx.use_default_detection() {
+                files.extend(glob_files(&pattern));
+                continue;
+            }
+            let base = static_prefix(&pattern);
+            for scan_root in &ctx.scan_roots {
+                if scan_root.path.starts_with(&base) {
+                    // Watch mode: a changed directory under this root
+                    files.extend(
+                        glob_files(&pattern)
+                            .into_iter()
+                            .filter(|f| f.starts_with(&scan_root.path)),
+                    );
+                } else if let Some(tail) = mirrored_pattern(root, scan_root) {
+                    files.extend(glob_files(&tail));
+                }
+            }
+        }
+        files.sort();
+        files.dedup();
+        files
+    }
+}
+
+impl Connector for GenericConnector {
+    fn detect(&self) -> DetectionResult {
+        let mut result = DetectionResult::not_found();
+        for def in &self.definitions {
+            for root in &def.roots {
+                let base = static_prefix(&expand_home(root));
+                if base.exists() && !result.root_paths.contains(&base) {
+                    result
+                        .evidence
+                        .push(format!("{}: found {}", def.slug, base.display()));
+                    result.root_paths.push(base);
+                }
+            }
+        }
+        result.detected = !result.root_paths.is_empty();
+        result
+    }
+
+    fn scan(&self, ctx: &ScanContext) -> Result<Vec<NormalizedConversation>> {
+        let mut convs = Vec::new();
+        for def in &self.definitions {
+            for file in Self::session_files(def, ctx) {
+                if !file_modified_since(&file, ctx.since_ts) {
+                    continue;
+                }
+                match parse_session(def, &file) {
+                    Ok(Some(conv)) => convs.push(conv),
+                    Ok(None) => {}
+                    Err(e) => tracing::debug!(
+                        connector = %def.slug,
+                        path = %file.display(),
+                        "skipping session: {e:#}"
+                    ),
+                }
+            }
+        }
+        Ok(convs)
+    }
+}
+
+/// Read every `*.toml` in `dir`, in file name order.
+pub fn load_definitions(dir: &Path) -> Vec<(PathBuf, Result<ConnectorDefinition>)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let def = fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|text| ConnectorDefinition::from_toml(&text));
+            (path, def)
+        })
+        .collect()
+}
+
+fn expand_home(pattern: &str) -> String {
+    match pattern.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .unwrap_or_default()
+            .join(rest)
+            .to_string_lossy()
+            .into_owned(),
+        None => pattern.to_string(),
+    }
+}
+
+/// The directory part of a glob before its first wildcard.
+fn static_prefix(pattern: &str) -> PathBuf {
+    let mut base = PathBuf::new();
+    for component in Path::new(pattern).components() {
+        if component.as_os_str().to_string_lossy().contains(GLOB_META) {
+            break;
+        }
+        base.push(component);
+    }
+    if base.as_os_str() == pattern {
+        // No wildcard: the root names a file or directory itself
+        base.pop();
+    }
+    base
+}
+
+/// The glob for a remote mirror of `root`, if `scan_root` is one.
+///
+/// Mirrors are named after the synced path (see `path_to_safe_dirname`), so a
+/// root of `~/.acme/sessions/**/*.jsonl` is found in the mirror of
+/// `~/.acme/sessions` or of `~/.acme`, and re-rooted there.
+fn mirrored_pattern(root: &str, scan_root: &ScanRoot) -> Option<String> {
+    if scan_root.origin.source_id == "local" {
+        return None;
+    }
+    let mirror_name = scan_root.path.file_name()?.to_string_lossy().into_owned();
+    let base = static_prefix(root);
+    let synced = base
+        .ancestors()
+        .filter(|dir| !matches!(dir.to_str(), Some("" | "~" | "/")))
+        .find(|dir| path_to_safe_dirname(&dir.to_string_lossy()) == mirror_name)?;
+    let tail = Path::new(root).strip_prefix(synced).ok()?;
+    Some(scan_root.path.join(tail).to_string_lossy().into_owned())
+}
+
+fn glob_files(pattern: &str) -> Vec<PathBuf> {
+    let Ok(paths) = glob::glob(pattern) else {
+        return Vec::new();
+    };
+    let mut files = Vec::new();
+    for path in paths.flatten() {
+        if path.is_file() {
+            files.push(path);
+        } else if path.is_dir() {
+            // A root naming a directory means every file below it
+            files.extend(
+                walkdir::WalkDir::new(&path)
+                    .into_iter()
+                    .flatten()
+                    .filter(|e| e.file_type().is_file())
+                    .map(|e| e.into_path()),
+            );
+        }
+    }
+    files
+}
+
+/// `value` at `pointer`, ignoring JSON nulls; an empty pointer is the value itself
+fn lookup<'a>(value: &'a Value, pointer: &str) -> Option<&'a Value> {
+    value.pointer(pointer).filter(|v| !v.is_null())
+}
+
+fn lookup_string(value: &Value, pointer: Option<&String>) -> Option<String> {
+    match lookup(value, pointer?)? {
+        Value::String(s) if !s.trim().is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Turn one JSON message record into a message, if it has a role and content.
+fn json_message(def: &ConnectorDefinition, record: &Value) -> Option<NormalizedMessage> {
+    let raw_role = lookup(record, &def.mapping.role)?.as_str()?;
+    let content_value = lookup(record, &def.mapping.content)?;
+    let content = flatten_content(content_value);
+    if content.trim().is_empty() {
+        return None;
+    }
+    Some(NormalizedMessage {
+        idx: 0,
+        role: def.map_role(raw_role),
+        author: None,
+        created_at: lookup(record, &def.mapping.timestamp).and_then(parse_timestamp),
+        content,
+        extra: record.clone(),
+        snippets: extract_tool_snippets(content_value),
+    })
+}
+
+/// Session-level fields read from a record or document
+#[derive(Default)]
+struct SessionFields {
+    workspace: Option<String>,
+    title: Option<String>,
+    session_id: Option<String>,
+}
+
+impl SessionFields {
+    /// Fill fields that are still unset from `value`.
+    fn absorb(&mut self, def: &ConnectorDefinition, value: &Value) {
+        let m = &def.mapping;
+        if self.workspace.is_none() {
+            self.workspace = lookup_string(value, m.workspace.as_ref());
+        }
+        if self.title.is_none() {
+            self.title = lookup_string(value, m.title.as_ref());
+        }
+        if self.session_id.is_none() {
+            self.session_id = lookup_string(value, m.session_id.as_ref());
+        }
+    }
+}
+
+fn parse_session(def: &ConnectorDefinition, path: &Path) -> Result<Option<NormalizedConversation>> {
+    let text =
+        fs::read_to_string(path).with_context(|| format!("read session {}", path.display()))?;
+    let mut fields = SessionFields::default();
+    let mut messages = Vec::new();
+
+    match def.format {
+        SessionFormat::Jsonl => {
+            for line in text.lines().filter(|l| !l.trim().is_empty()) {
+                let Ok(record) = serde_json::from_str::<Value>(line) else {
+                    continue;
+                };
+                fields.absorb(def, &record);
+                messages.extend(json_message(def, &record));
+            }
+        }
+        SessionFormat::Json => {
+            let doc: Value = serde_json::from_str(&text)
+                .with_context(|| format!("parse JSON session {}", path.display()))?;
+            fields.absorb(def, &doc);
+            let items = match def.mapping.messages.as_deref() {
+                Some(pointer) => lookup(&doc, pointer),
+                None if doc.is_array() => Some(&doc),
+                None => lookup(&doc, "/messages"),
+            };
+            if let Some(items) = items.and_then(Value::as_array) {
+                messages.extend(items.iter().filter_map(|item| json_message(def, item)));
+            }
+        }
+        SessionFormat::Markdown => {
+            let (title, parsed) = markdown_messages(def, &text);
+            fields.title = title;
+            messages = parsed;
+        }
+    }
+
+    if messages.is_empty() {
+        return Ok(None);
+    }
+    crate::connectors::reindex_messages(&mut messages);
+
+    let mut started_at = messages.iter().filter_map(|m| m.created_at).min();
+    let mut ended_at = messages.iter().filter_map(|m| m.created_at).max();
+    if started_at.is_none() {
+        // Nothing timestamped: fall back to the file's mtime
+        let mtime = fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64);
+        started_at = mtime;
+        ended_at = mtime;
+    }
+
+    let title = fields.title.or_else(|| {
+        messages
+            .iter()
+            .find(|m| m.role == "user")
+            .or(messages.first())
+            .and_then(|m| m.content.lines().next())
+            .map(|line| line.chars().take(100).collect())
+    });
+    let external_id = fields
+        .session_id
+        .clone()
+        .or_else(|| path.file_stem().and_then(|s| s.to_str()).map(String::from));
+
+    Ok(Some(NormalizedConversation {
+        agent_slug: def.slug.clone(),
+        external_id,
+        title,
+        workspace: fields.workspace.map(PathBuf::from),
+        source_path: path.to_path_buf(),
+        started_at,
+        ended_at,
+        metadata: json!({
+            "source": "generic",
+            "format": def.format_name(),
+            "session_id": fields.session_id,
+        }),
+        messages,
+    }))
+}
+
+/// Split a markdown transcript into messages at role headings.
+fn markdown_messages(
+    def: &ConnectorDefinition,
+    text: &str,
+) -> (Option<String>, Vec<NormalizedMessage>) {
+    let mut title = None;
+    let mut messages = Vec::new();
+    let mut current: Option<(String, String)> = None;
+
+    let mut flush = |current: &mut Option<(String, String)>| {
+        if let Some((role, body)) = current.take()
+            && !body.trim().is_empty()
+        {
+            messages.push(NormalizedMessage {
+                idx: 0,
+                role,
+                author: None,
+                created_at: None,
+                content: body.trim().to_string(),
+                extra: json!({}),
+                snippets: Vec::new(),
+            });
+        }
+    };
+
+    for line in text.lines() {
+        let heading = line
+            .strip_prefix('#')
+            .map(|rest| rest.trim_start_matches('#'))
+            .filter(|rest| rest.starts_with(' '));
+        if let Some(heading) = heading {
+            if let Some(role) = def.heading_role(heading) {
+                flush(&mut current);
+                current = Some((role, String::new()));
+                continue;
+            }
+            if title.is_none() && line.starts_with("# ") {
+                title = Some(heading.trim().to_string());
+                continue;
+            }
+        }
+        if let Some((_, body)) = current.as_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    flush(&mut current);
+    (title, messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn definition(toml: &str) -> ConnectorDefinition {
+        ConnectorDefinition::from_toml(toml).expect("valid definition")
+    }
+
+    #[test]
+    fn definitions_are_validated() {
+        let base = "format = \"jsonl\"\nroots = [\"/tmp/x/*.jsonl\"]\n";
+        assert!(ConnectorDefinition::from_toml(&format!("slug = \"acme\"\n{base}")).is_ok());
+        for bad in [
+            format!("slug = \"codex\"\n{base}"),
+            format!("slug = \"Acme Agent\"\n{base}"),
+            "slug = \"acme\"\nformat = \"jsonl\"\nroots = []\n".to_string(),
+            format!("slug = \"acme\"\n{base}[mapping]\nrole = \"role\"\n"),
+            format!("slug = \"acme\"\n{base}color = \"red\"\n"),
+        ] {
+            assert!(ConnectorDefinition::from_toml(&bad).is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn static_prefix_stops_at_first_wildcard() {
+        assert_eq!(
+            static_prefix("/home/me/.acme/sessions/**/*.jsonl"),
+            PathBuf::from("/home/me/.acme/sessions")
+        );
+        assert_eq!(
+            static_prefix("/home/me/.acme/log.jsonl"),
+            PathBuf::from("/home/me/.acme")
+        );
+    }
+
+    #[test]
+    fn remote_mirrors_are_matched_by_synced_path() {
+        use crate::sources::provenance::Origin;
+        let root = "~/.acme/sessions/**/*.jsonl";
+        let mirror = |synced: &str| {
+            ScanRoot::remote(
+                PathBuf::from("/data/remotes/laptop/mirror").join(path_to_safe_dirname(synced)),
+                Origin::remote("laptop"),
+                None,
+            )
+        };
+        let direct = mirror("~/.acme/sessions");
+        assert_eq!(
+            mirrored_pattern(root, &direct),
+            Some(format!("{}/**/*.jsonl", direct.path.display()))
+        );
+        let parent = mirror("~/.acme");
+        assert_eq!(
+            mirrored_pattern(root, &parent),
+            Some(format!("{}/sessions/**/*.jsonl", parent.path.display()))
+        );
+        assert_eq!(mirrored_pattern(root, &mirror("~/.claude/projects")), None);
+        let local = ScanRoot::local(direct.path.clone());
+        assert_eq!(mirrored_pattern(root, &local), None);
+    }
+
+    #[test]
+    fn json_sessions_use_relative_pointers() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("s1.json");
+        fs::write(
+            &file,
+            r#"{"meta":{"id":"s-1","cwd":"/repo"},
+                "turns":[
+                  {"who":"human","text":"fix the flaky test","at":1700000000},
+                  {"who":"bot","text":[{"type":"text","text":"Done."}],"at":1700000060}
+                ]}"#,
+        )
+        .unwrap();
+        let def = definition(
+            r#"
+            slug = "acme"
+            format = "json"
+            roots = ["/unused/*.json"]
+            [mapping]
+            messages = "/turns"
+            role = "/who"
+            content = "/text"
+            timestamp = "/at"
+            workspace = "/meta/cwd"
+            session_id = "/meta/id"
+            [roles]
+            human = "user"
+            bot = "assistant"
+            "#,
+        );
+        let conv = parse_session(&def, &file).unwrap().unwrap();
+        assert_eq!(conv.agent_slug, "acme");
+        assert_eq!(conv.external_id.as_deref(), Some("s-1"));
+        assert_eq!(conv.workspace, Some(PathBuf::from("/repo")));
+        assert_eq!(conv.title.as_deref(), Some("fix the flaky test"));
+        assert_eq!(conv.messages.len(), 2);
+        assert_eq!(conv.messages[1].role, "assistant");
+        assert_eq!(conv.messages[1].content, "Done.");
+        assert_eq!(conv.started_at, Some(1_700_000_000_000));
+        assert_eq!(conv.ended_at, Some(1_700_000_060_000));
+    }
+
+    #[test]
+    fn markdown_sessions_split_at_role_headings() {
+        let def = definition("slug = \"notes\"\nformat = \"markdown\"\nroots = [\"/x/*.md\"]\n");
+        let (title, messages) = markdown_messages(
+            &def,
+            "# Refactor auth\n\n## User\nsplit the module\n\n## Assistant:\nSplit into two files.\n### Details\nkept\n",
+        );
+        assert_eq!(title.as_deref(), Some("Refactor auth"));
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].role, "user");
+        assert_eq!(messages[0].content, "split the module");
+        assert!(messages[1].content.contains("### Details\nkept"));
+    }
+}
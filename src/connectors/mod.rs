@@ -397,6 +397,7 @@ pub mod codex;
 pub mod cursor;
 pub mod factory;
 pub mod gemini;
+pub mod generic;
 pub mod opencode;
 pub mod pi_agent;
 
@@ -790,6 +791,281 @@ fn extract_content_part(item: &serde_json::Value) -> Option<String> {
     None
 }
 
+// -------------------------------------------------------------------------
+// File snippets from tool calls
+// -------------------------------------------------------------------------
+
+/// Extract file snippets from the `tool_use` blocks of a message's content.
+///
+/// Claude Code and Factory store tool calls inline in the content array as
+/// `{"type": "tool_use", "name": "Edit", "input": {...}}`.
+pub fn extract_tool_snippets(content: &serde_json::Value) -> Vec<NormalizedSnippet> {
+    let Some(arr) = content.as_array() else {
+        return Vec::new();
+    };
+    arr.iter()
+        .filter(|item| item.get("type").and_then(|v| v.as_str()) == Some("tool_use"))
+        .flat_map(|item| {
+            let name = item.get("name").and_then(|v| v.as_str()).unwrap_or("");
+            let input = item.get("input").unwrap_or(&serde_json::Value::Null);
+            tool_call_snippets(name, input)
+        })
+        .collect()
+}
+
+/// Build snippets for a single tool call from its name and input arguments.
+///
+/// Recognizes read/edit/write style tools across agents (`Read`, `Edit`, `MultiEdit`,
+/// `Write`, Cline's `editedExistingFile`, OpenCode's `edit`, ...) plus Codex
+/// `apply_patch`, whether called directly or through a shell command. Unknown tools
+/// and calls without a file path produce nothing.
+pub fn tool_call_snippets(name: &str, input: &serde_json::Value) -> Vec<NormalizedSnippet> {
+    let tool: String = name
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect();
+    let str_field = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|k| input.get(*k).and_then(|v| v.as_str()))
+            .map(str::to_string)
+    };
+    let path = str_field(&[
+        "file_path",
+        "filePath",
+        "path",
+        "notebook_path",
+        "target_file",
+    ]);
+
+    match tool.as_str() {
+        "read" | "readfile" | "view" => {
+            let Some(path) = path else {
+                return Vec::new();
+            };
+            let offset = input.get("offset").and_then(|v| v.as_i64());
+            let limit = input.get("limit").and_then(|v| v.as_i64());
+            let start_line = offset.map(|o| o.max(1)).or(limit.map(|_| 1));
+            let end_line = match (start_line, limit) {
+                (Some(start), Some(limit)) if limit > 0 => Some(start + limit - 1),
+                _ => None,
+            };
+            vec![file_snippet(&path, start_line, end_line, None)]
+        }
+        "edit" | "editfile" | "editedexistingfile" | "replaceinfile" | "strreplace" | "replace"
+        | "notebookedit" => {
+            let Some(path) = path else {
+                return Vec::new();
+            };
+            let text = str_field(&[
+                "new_string",
+                "newString",
+                "new_str",
+                "new_source",
+                "diff",
+                "content",
+            ]);
+            vec![file_snippet(&path, None, None, text)]
+        }
+        "multiedit" => {
+            let Some(path) = path else {
+                return Vec::new();
+            };
+            let edits = input.get("edits").and_then(|v| v.as_array());
+            match edits {
+                Some(edits) if !edits.is_empty() => edits
+                    .iter()
+                    .map(|edit| {
+                        let text = edit
+                            .get("new_string")
+                            .or_else(|| edit.get("newString"))
+                            .and_then(|v| v.as_str())
+                            .map(str::to_string);
+                        file_snippet(&path, None, None, text)
+                    })
+                    .collect(),
+                _ => vec![file_snippet(&path, None, None, None)],
+            }
+        }
+        "write" | "writefile" | "writetofile" | "create" | "createfile" | "newfilecreated" => {
+            let Some(path) = path else {
+                return Vec::new();
+            };
+            let text = str_field(&["content", "file_text", "text"]);
+            let end_line = text.as_deref().map(|t| t.lines().count().max(1) as i64);
+            vec![file_snippet(&path, end_line.map(|_| 1), end_line, text)]
+        }
+        "applypatch" | "patch" => input
+            .as_str()
+            .map(str::to_string)
+            .or_else(|| str_field(&["input", "patch"]))
+            .map(|patch| apply_patch_snippets(&patch))
+            .unwrap_or_default(),
+        "shell" | "bash" | "execcommand" | "localshell" | "containerexec" => {
+            let command = input.get("command").or_else(|| input.get("cmd"));
+            let parts: Vec<&str> = match command {
+                Some(serde_json::Value::Array(items)) => {
+                    items.iter().filter_map(|v| v.as_str()).collect()
+                }
+                Some(serde_json::Value::String(cmd)) => vec![cmd.as_str()],
+                _ => Vec::new(),
+            };
+            parts
+                .into_iter()
+                .find(|p| p.contains("*** Begin Patch"))
+                .map(apply_patch_snippets)
+                .unwrap_or_default()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Split a Codex `apply_patch` envelope into one snippet per touched file.
+///
+/// The envelope looks like `*** Begin Patch` / `*** Update File: path` / hunks /
+/// `*** End Patch`. Added files get a 1-based line range covering their content;
+/// updated files get the range from the first unified `@@ -a,b +c,d @@` header when
+/// one is present. Deleted files are recorded without text.
+pub fn apply_patch_snippets(patch: &str) -> Vec<NormalizedSnippet> {
+    struct Section<'a> {
+        path: &'a str,
+        added: bool,
+        deleted: bool,
+        body: Vec<&'a str>,
+    }
+
+    let mut sections: Vec<Section<'_>> = Vec::new();
+    for line in patch.lines() {
+        let header = |prefix: &str| line.strip_prefix(prefix).map(str::trim);
+        if let Some(path) = header("*** Update File:") {
+            sections.push(Section {
+                path,
+                added: false,
+                deleted: false,
+                body: Vec::new(),
+            });
+        } else if let Some(path) = header("*** Add File:") {
+            sections.push(Section {
+                path,
+                added: true,
+                deleted: false,
+                body: Vec::new(),
+            });
+        } else if let Some(path) = header("*** Delete File:") {
+            sections.push(Section {
+                path,
+                added: false,
+                deleted: true,
+                body: Vec::new(),
+            });
+        } else if let Some(path) = header("*** Move to:") {
+            if let Some(current) = sections.last_mut() {
+                current.path = path;
+            }
+        } else if line.starts_with("*** ") {
+            // Begin/End Patch and End of File markers
+        } else if let Some(current) = sections.last_mut() {
+            current.body.push(line);
+        }
+    }
+
+    sections
+        .into_iter()
+        .filter(|s| !s.path.is_empty())
+        .map(|section| {
+            if section.deleted {
+                return file_snippet(section.path, None, None, None);
+            }
+            if section.added {
+                let lines: Vec<&str> = section
+                    .body
+                    .iter()
+                    .map(|l| l.strip_prefix('+').unwrap_or(l))
+                    .collect();
+                let end_line = (!lines.is_empty()).then_some(lines.len() as i64);
+                return file_snippet(
+                    section.path,
+                    end_line.map(|_| 1),
+                    end_line,
+                    Some(lines.join("\n")),
+                );
+            }
+            let (start_line, end_line) = section
+                .body
+                .iter()
+                .find_map(|l| parse_hunk_new_range(l))
+                .map_or((None, None), |(start, end)| (Some(start), Some(end)));
+            let text = (!section.body.is_empty()).then(|| section.body.join("\n"));
+            file_snippet(section.path, start_line, end_line, text)
+        })
+        .collect()
+}
+
+/// Parse the new-file range out of a unified diff hunk header (`@@ -a,b +c,d @@`).
+fn parse_hunk_new_range(line: &str) -> Option<(i64, i64)> {
+    let rest = line.strip_prefix("@@")?;
+    let new = rest.split_whitespace().find(|t| t.starts_with('+'))?;
+    let mut nums = new[1..].splitn(2, ',');
+    let start: i64 = nums.next()?.parse().ok()?;
+    let count: i64 = nums.next().map_or(Some(1), |n| n.parse().ok())?;
+    Some((start, start + count.max(1) - 1))
+}
+
+fn file_snippet(
+    path: &str,
+    start_line: Option<i64>,
+    end_line: Option<i64>,
+    snippet_text: Option<String>,
+) -> NormalizedSnippet {
+    let file_path = PathBuf::from(path);
+    NormalizedSnippet {
+        language: language_for_path(&file_path),
+        file_path: Some(file_path),
+        start_line,
+        end_line,
+        snippet_text: snippet_text.filter(|t| !t.is_empty()),
+    }
+}
+
+/// Guess a language name from a file extension (used to label snippets).
+pub fn language_for_path(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let lang = match ext.as_str() {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "jsx",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "swift" => "swift",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "scala" => "scala",
+        "lua" => "lua",
+        "zig" => "zig",
+        "ex" | "exs" => "elixir",
+        "sh" | "bash" | "zsh" => "bash",
+        "sql" => "sql",
+        "html" | "htm" => "html",
+        "css" | "scss" => "css",
+        "vue" => "vue",
+        "svelte" => "svelte",
+        "md" | "markdown" => "markdown",
+        "json" => "json",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "ipynb" => "jupyter",
+        _ => return None,
+    };
+    Some(lang.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1070,6 +1346,86 @@ mod tests {
         assert_eq!(super::flatten_content(&val), "   \n\t  ");
     }
 
+    // =========================================================================
+    // Tool call snippets
+    // =========================================================================
+
+    #[test]
+    fn extract_tool_snippets_from_claude_blocks() {
+        let content = serde_json::json!([
+            {"type": "text", "text": "Fixing the parser"},
+            {"type": "tool_use", "name": "Read", "input": {"file_path": "/p/src/lib.rs", "offset": 10, "limit": 5}},
+            {"type": "tool_use", "name": "Edit", "input": {"file_path": "/p/src/lib.rs", "old_string": "a", "new_string": "b"}},
+            {"type": "tool_use", "name": "Write", "input": {"file_path": "/p/README.md", "content": "one\ntwo"}},
+            {"type": "tool_use", "name": "Bash", "input": {"command": "ls"}}
+        ]);
+        let snippets = extract_tool_snippets(&content);
+        assert_eq!(snippets.len(), 3);
+
+        assert_eq!(snippets[0].file_path, Some(PathBuf::from("/p/src/lib.rs")));
+        assert_eq!(
+            (snippets[0].start_line, snippets[0].end_line),
+            (Some(10), Some(14))
+        );
+        assert_eq!(snippets[0].language.as_deref(), Some("rust"));
+        assert!(snippets[0].snippet_text.is_none());
+
+        assert_eq!(snippets[1].snippet_text.as_deref(), Some("b"));
+
+        assert_eq!(snippets[2].language.as_deref(), Some("markdown"));
+        assert_eq!(
+            (snippets[2].start_line, snippets[2].end_line),
+            (Some(1), Some(2))
+        );
+    }
+
+    #[test]
+    fn tool_call_snippets_normalizes_tool_names() {
+        let input = serde_json::json!({"path": "src/app.ts", "diff": "+x"});
+        let snippets = tool_call_snippets("editedExistingFile", &input);
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].language.as_deref(), Some("typescript"));
+
+        let input = serde_json::json!({"filePath": "src/app.ts", "newString": "y"});
+        assert_eq!(tool_call_snippets("edit", &input).len(), 1);
+        assert!(tool_call_snippets("edit", &serde_json::json!({})).is_empty());
+        assert!(tool_call_snippets("WebFetch", &input).is_empty());
+    }
+
+    #[test]
+    fn apply_patch_snippets_splits_files() {
+        let patch = "*** Begin Patch\n\
+                     *** Update File: src/foo.rs\n\
+                     @@ -3,2 +3,3 @@\n\
+                     -old\n\
+                     +new\n\
+                     *** Add File: docs/notes.md\n\
+                     +hello\n\
+                     +world\n\
+                     *** Delete File: old.txt\n\
+                     *** End Patch";
+        let snippets = apply_patch_snippets(patch);
+        assert_eq!(snippets.len(), 3);
+        assert_eq!(snippets[0].file_path, Some(PathBuf::from("src/foo.rs")));
+        assert_eq!(
+            (snippets[0].start_line, snippets[0].end_line),
+            (Some(3), Some(5))
+        );
+        assert_eq!(snippets[1].snippet_text.as_deref(), Some("hello\nworld"));
+        assert_eq!(snippets[1].end_line, Some(2));
+        assert!(snippets[2].snippet_text.is_none());
+    }
+
+    #[test]
+    fn shell_apply_patch_is_detected() {
+        let input = serde_json::json!({
+            "command": ["apply_patch", "*** Begin Patch\n*** Update File: a.py\n+x\n*** End Patch"]
+        });
+        let snippets = tool_call_snippets("shell", &input);
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].language.as_deref(), Some("python"));
+    }
+
     // =========================================================================
     // NormalizedMessage construction (bead yln.4)
     // =========================================================================
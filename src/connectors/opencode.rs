@@ -15,7 +15,8 @@ use serde::Deserialize;
 use walkdir::WalkDir;
 
 use crate::connectors::{
-    Connector, DetectionResult, NormalizedConversation, NormalizedMessage, ScanContext,
+    Connector, DetectionResult, NormalizedConversation, NormalizedMessage, NormalizedSnippet,
+    ScanContext, tool_call_snippets,
 };
 
 pub struct OpenCodeConnector;
@@ -125,6 +126,12 @@ struct PartInfo {
     part_type: Option<String>,
     #[serde(default)]
     text: Option<String>,
+    /// Tool name for tool parts (`edit`, `write`, `read`, ...)
+    #[serde(default)]
+    tool: Option<String>,
+    /// Files touched by a patch part
+    #[serde(default)]
+    files: Vec<String>,
     // Tool state for tool parts
     #[serde(default)]
     state: Option<ToolState>,
@@ -132,6 +139,8 @@ struct PartInfo {
 
 #[derive(Debug, Clone, Deserialize)]
 struct ToolState {
+    #[serde(default)]
+    input: Option<serde_json::Value>,
     #[serde(default)]
     output: Option<String>,
 }
@@ -439,7 +448,7 @@ fn load_messages(session_msg_dir: &PathBuf, part_dir: &PathBuf) -> Result<Vec<No
                     "message_id": message_id,
                     "session_id": msg_info.session_id,
                 }),
-                snippets: Vec::new(),
+                snippets: snippets_from_parts(&parts),
             },
         ));
     }
@@ -482,6 +491,37 @@ fn sort_parts_for_message(parts: &mut [PartInfo]) {
     });
 }
 
+/// File snippets from a message's tool parts (edit/write/read calls) and patch parts
+/// (which list the files a step changed).
+fn snippets_from_parts(parts: &[PartInfo]) -> Vec<NormalizedSnippet> {
+    let mut snippets = Vec::new();
+    for part in parts {
+        match part.part_type.as_deref() {
+            Some("tool") => {
+                if let Some(tool) = &part.tool
+                    && let Some(input) = part.state.as_ref().and_then(|s| s.input.as_ref())
+                {
+                    snippets.extend(tool_call_snippets(tool, input));
+                }
+            }
+            Some("patch") => {
+                for file in &part.files {
+                    let file_path = PathBuf::from(file);
+                    snippets.push(NormalizedSnippet {
+                        language: crate::connectors::language_for_path(&file_path),
+                        file_path: Some(file_path),
+                        start_line: None,
+                        end_line: None,
+                        snippet_text: None,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    snippets
+}
+
 /// Assemble message content from parts
 fn assemble_content_from_parts(parts: &[PartInfo]) -> String {
     let mut content_pieces: Vec<String> = Vec::new();
@@ -677,6 +717,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("text".into()),
                 text: Some("Hello, world!".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
             PartInfo {
@@ -685,6 +727,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("text".into()),
                 text: Some("Second part".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
         ];
@@ -701,7 +745,10 @@ mod tests {
             message_id: Some("m1".into()),
             part_type: Some("tool".into()),
             text: None,
+            tool: None,
+            files: Vec::new(),
             state: Some(ToolState {
+                input: None,
                 output: Some("Tool executed successfully".into()),
             }),
         }];
@@ -718,6 +765,8 @@ mod tests {
             message_id: Some("m1".into()),
             part_type: Some("reasoning".into()),
             text: Some("Let me think about this...".into()),
+            tool: None,
+            files: Vec::new(),
             state: None,
         }];
         let content = assemble_content_from_parts(&parts);
@@ -733,6 +782,8 @@ mod tests {
             message_id: Some("m1".into()),
             part_type: Some("patch".into()),
             text: Some("@@ -1,3 +1,4 @@".into()),
+            tool: None,
+            files: Vec::new(),
             state: None,
         }];
         let content = assemble_content_from_parts(&parts);
@@ -749,6 +800,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("text".into()),
                 text: Some("".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
             PartInfo {
@@ -757,6 +810,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("text".into()),
                 text: Some("   ".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
             PartInfo {
@@ -765,6 +820,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("text".into()),
                 text: Some("Actual content".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
         ];
@@ -781,6 +838,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("step-start".into()),
                 text: Some("Starting...".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
             PartInfo {
@@ -789,6 +848,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("step-finish".into()),
                 text: Some("Done".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
         ];
@@ -805,6 +866,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("text".into()),
                 text: Some("Here's my analysis:".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
             PartInfo {
@@ -813,6 +876,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("reasoning".into()),
                 text: Some("Thinking...".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
             PartInfo {
@@ -821,7 +886,10 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("tool".into()),
                 text: None,
+                tool: None,
+                files: Vec::new(),
                 state: Some(ToolState {
+                    input: None,
                     output: Some("Result: 42".into()),
                 }),
             },
@@ -832,6 +900,47 @@ mod tests {
         assert!(content.contains("[Tool Output]"));
     }
 
+    #[test]
+    fn snippets_from_tool_and_patch_parts() {
+        let parts = vec![
+            PartInfo {
+                id: Some("p1".into()),
+                index: None,
+                message_id: Some("m1".into()),
+                part_type: Some("tool".into()),
+                text: None,
+                tool: Some("edit".into()),
+                files: Vec::new(),
+                state: Some(ToolState {
+                    input: Some(serde_json::json!({
+                        "filePath": "/repo/src/main.go",
+                        "oldString": "a",
+                        "newString": "b"
+                    })),
+                    output: None,
+                }),
+            },
+            PartInfo {
+                id: Some("p2".into()),
+                index: None,
+                message_id: Some("m1".into()),
+                part_type: Some("patch".into()),
+                text: None,
+                tool: None,
+                files: vec!["/repo/README.md".into()],
+                state: None,
+            },
+        ];
+        let snippets = snippets_from_parts(&parts);
+        assert_eq!(snippets.len(), 2);
+        assert_eq!(snippets[0].language.as_deref(), Some("go"));
+        assert_eq!(snippets[0].snippet_text.as_deref(), Some("b"));
+        assert_eq!(
+            snippets[1].file_path,
+            Some(PathBuf::from("/repo/README.md"))
+        );
+    }
+
     #[test]
     fn sort_parts_for_message_orders_by_index_then_id() {
         let mut parts = vec![
@@ -841,6 +950,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("text".into()),
                 text: Some("second".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
             PartInfo {
@@ -849,6 +960,8 @@ mod tests {
                 message_id: Some("m1".into()),
                 part_type: Some("text".into()),
                 text: Some("first".into()),
+                tool: None,
+                files: Vec::new(),
                 state: None,
             },
         ];
@@ -371,6 +371,7 @@ mod tests {
             source_id: "local".to_string(),
             origin_kind: "local".to_string(),
             origin_host: None,
+            parent: None,
         }
     }
 
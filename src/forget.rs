@@ -0,0 +1,368 @@
+//! Targeted removal of indexed conversations (`cass forget`).
+//!
+//! Deletes the selected conversations from SQLite (messages, snippets, FTS rows,
+//! tags and `daily_stats`), their Tantivy documents and their CVVI vectors in
+//! one pass, so pasted secrets stop being searchable without a rebuild. Stored
+//! import exports and bundles are rewritten without the forgotten conversations,
+//! since `cass index` ingests them again. Session files on disk are left alone;
+//! a full reindex would import them again.
+
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+
+use crate::connectors::{NormalizedConversation, NormalizedMessage};
+use crate::model::types::{
+    Conversation, ConversationSelector, Message, MessageRole, SelectedConversation,
+};
+use crate::search::tantivy::{TantivyIndex, index_dir, index_schema_current};
+use crate::search::vector_index::{remove_vector_messages, vector_index_bases};
+use crate::sources::provenance::SourceKind;
+use crate::storage::sqlite::SqliteStorage;
+
+/// What a forget did to the Tantivy index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LexicalStatus {
+    /// Documents of the forgotten conversations were deleted
+    Updated,
+    /// No lexical index exists yet
+    Missing,
+    /// The index predates the current schema; the next `cass index` rebuilds it
+    Stale,
+    /// Dry run or nothing selected: the index was not opened
+    Untouched,
+}
+
+/// Outcome of [`forget`], serialized as-is for `cass forget --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ForgetReport {
+    pub dry_run: bool,
+    pub conversations: Vec<SelectedConversation>,
+    pub conversation_count: usize,
+    pub message_count: usize,
+    /// Distinct session files the conversations came from
+    pub session_files: usize,
+    pub lexical_index: LexicalStatus,
+    /// Conversations re-indexed because they share a session file with a forgotten one
+    pub lexical_reindexed: usize,
+    /// Vector rows (chunks) removed across all embedders
+    pub vectors_removed: usize,
+    /// Stored import exports and bundles rewritten without the conversations
+    pub stored_copies: Vec<PathBuf>,
+}
+
+/// Remove every conversation matching `selector` from all stores under `data_dir`.
+///
+/// With `dry_run` only the selection is reported. The stores are updated in the
+/// order Tantivy, vectors, SQLite, so a failed run can simply be repeated: the
+/// selection lives in SQLite until the very end.
+pub fn forget(
+    storage: &mut SqliteStorage,
+    data_dir: &Path,
+    selector: &ConversationSelector,
+    dry_run: bool,
+) -> Result<ForgetReport> {
+    if selector.is_empty() {
+        bail!("refusing to forget every conversation: give at least one selector");
+    }
+    let conversations = storage.select_conversations(selector)?;
+    let session_paths: BTreeSet<String> = conversations
+        .iter()
+        .map(|c| c.source_path.clone())
+        .collect();
+    let mut report = ForgetReport {
+        dry_run,
+        conversation_count: conversations.len(),
+        message_count: conversations.iter().map(|c| c.message_count as usize).sum(),
+        session_files: session_paths.len(),
+        conversations,
+        lexical_index: LexicalStatus::Untouched,
+        lexical_reindexed: 0,
+        vectors_removed: 0,
+        stored_copies: Vec::new(),
+    };
+    report.stored_copies = forget_stored_copies(storage, data_dir, &report.conversations, dry_run)?;
+    if dry_run || report.conversations.is_empty() {
+        return Ok(report);
+    }
+
+    let conversation_ids: Vec<i64> = report
+        .conversations
+        .iter()
+        .map(|c| c.conversation_id)
+        .collect();
+    let forgotten: HashSet<i64> = conversation_ids.iter().copied().collect();
+
+    let (status, reindexed) = forget_lexical(storage, data_dir, &session_paths, &forgotten)?;
+    report.lexical_index = status;
+    report.lexical_reindexed = reindexed;
+
+    let message_ids: HashSet<u64> = storage
+        .message_ids_for_conversations(&conversation_ids)?
+        .into_iter()
+        .map(|id| id as u64)
+        .collect();
+    for base in vector_index_bases(data_dir) {
+        report.vectors_removed += remove_vector_messages(&base, &message_ids)
+            .with_context(|| format!("removing vectors from {}", base.display()))?;
+    }
+
+    storage.delete_conversations(&conversation_ids)?;
+    Ok(report)
+}
+
+/// Drop the selected conversations from the stored import exports and bundles
+/// that hold them, so `cass index` cannot ingest them again. Returns the stored
+/// files that were (or, on a dry run, would be) rewritten.
+fn forget_stored_copies(
+    storage: &SqliteStorage,
+    data_dir: &Path,
+    conversations: &[SelectedConversation],
+    dry_run: bool,
+) -> Result<Vec<PathBuf>> {
+    let source_ids: BTreeSet<&str> = conversations.iter().map(|c| c.source_id.as_str()).collect();
+    let mut touched = Vec::new();
+    for source_id in source_ids {
+        let selected = conversations.iter().filter(|c| c.source_id == source_id);
+        match storage.get_source(source_id)?.map(|s| s.kind) {
+            Some(SourceKind::Import) => {
+                let paths: HashSet<String> = selected.map(|c| c.source_path.clone()).collect();
+                touched.extend(crate::import::forget_stored_conversations(
+                    data_dir, source_id, &paths, dry_run,
+                )?);
+            }
+            Some(SourceKind::Bundle) => {
+                let mut keys = HashSet::new();
+                for selected in selected {
+                    if let Some(conv) = storage.get_conversation(selected.conversation_id)? {
+                        keys.insert((conv.agent_slug, conv.external_id.unwrap_or_default()));
+                    }
+                }
+                touched.extend(crate::bundle::forget_stored_conversations(
+                    data_dir, source_id, &keys, dry_run,
+                )?);
+            }
+            _ => {}
+        }
+    }
+    Ok(touched)
+}
+
+/// Delete the Tantivy documents of `session_paths`, then re-add the conversations
+/// at those paths that are not being forgotten (documents are keyed by path only).
+fn forget_lexical(
+    storage: &SqliteStorage,
+    data_dir: &Path,
+    session_paths: &BTreeSet<String>,
+    forgotten: &HashSet<i64>,
+) -> Result<(LexicalStatus, usize)> {
+    let index_path = index_dir(data_dir)?;
+    if !index_path.join("meta.json").exists() {
+        return Ok((LexicalStatus::Missing, 0));
+    }
+    if !index_schema_current(&index_path) {
+        // Opening would wipe the index; leave the rebuild to `cass index`.
+        return Ok((LexicalStatus::Stale, 0));
+    }
+
+    let mut index = TantivyIndex::open_or_create(&index_path)
+        .context("opening the lexical index (is `cass index --watch` running?)")?;
+    let mut reindexed = 0;
+    for path in session_paths {
+        index.delete_session(path);
+        for conv in storage.conversations_for_path(path)? {
+            let Some(id) = conv.id.filter(|id| !forgotten.contains(id)) else {
+                continue;
+            };
+            let messages = storage.fetch_messages(id)?;
+            index.add_conversation(&normalized(conv, messages))?;
+            reindexed += 1;
+        }
+    }
+    index.commit()?;
+    Ok((LexicalStatus::Updated, reindexed))
+}
+
+/// Rebuild the connector-level view of a stored conversation for re-indexing.
+pub(crate) fn normalized(conv: Conversation, messages: Vec<Message>) -> NormalizedConversation {
+    NormalizedConversation {
+        agent_slug: conv.agent_slug,
+        external_id: conv.external_id,
+        title: conv.title,
+        workspace: conv.workspace,
+        source_path: conv.source_path,
+        started_at: conv.started_at,
+        ended_at: conv.ended_at,
+        metadata: conv.metadata_json,
+        messages: messages
+            .into_iter()
+            .map(|m| NormalizedMessage {
+                idx: m.idx,
+                role: match m.role {
+                    MessageRole::User => "user".to_string(),
+                    MessageRole::Agent => "assistant".to_string(),
+                    MessageRole::Tool => "tool".to_string(),
+                    MessageRole::System => "system".to_string(),
+                    MessageRole::Other(other) => other,
+                },
+                author: m.author,
+                created_at: m.created_at,
+                content: m.content,
+                extra: m.extra_json,
+                snippets: Vec::new(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::types::{Agent, AgentKind};
+    use crate::search::query::{FieldMask, SearchClient, SearchFilters};
+    use crate::search::vector_index::{
+        Quantization, VECTOR_INDEX_DIR, VectorEntry, VectorIndex, vector_index_path,
+    };
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn conversation(external_id: &str, source_path: &str, content: &str) -> Conversation {
+        Conversation {
+            id: None,
+            agent_slug: "codex".into(),
+            workspace: None,
+            external_id: Some(external_id.into()),
+            title: Some(external_id.into()),
+            source_path: PathBuf::from(source_path),
+            started_at: Some(1_700_000_000_000),
+            ended_at: None,
+            approx_tokens: None,
+            metadata_json: serde_json::json!({}),
+            messages: vec![Message {
+                id: None,
+                idx: 0,
+                role: MessageRole::User,
+                author: None,
+                created_at: Some(1_700_000_000_000),
+                content: content.into(),
+                extra_json: serde_json::json!({}),
+                snippets: Vec::new(),
+            }],
+            source_id: "local".into(),
+            origin_host: None,
+        }
+    }
+
+    fn lexical_hits(data_dir: &Path, query: &str) -> Vec<String> {
+        let client = SearchClient::open(&index_dir(data_dir).unwrap(), None)
+            .unwrap()
+            .unwrap();
+        let mut titles: Vec<String> = client
+            .search(query, SearchFilters::default(), 10, 0, FieldMask::FULL)
+            .unwrap()
+            .into_iter()
+            .map(|hit| hit.title)
+            .collect();
+        titles.sort();
+        titles
+    }
+
+    #[test]
+    fn forget_scrubs_sqlite_tantivy_and_vectors() {
+        let dir = TempDir::new().unwrap();
+        let data_dir = dir.path();
+        let mut storage = SqliteStorage::open(&data_dir.join("agent_search.db")).unwrap();
+        let agent_id = storage
+            .ensure_agent(&Agent {
+                id: None,
+                slug: "codex".into(),
+                name: "Codex".into(),
+                version: None,
+                kind: AgentKind::Cli,
+            })
+            .unwrap();
+
+        // "leak" and "keep" share one session file, as in multi-session exports
+        let mut index = TantivyIndex::open_or_create(&index_dir(data_dir).unwrap()).unwrap();
+        for (external_id, path, content) in [
+            ("leak", "/s/shared.json", "sk-live-4f9a leakedsecret"),
+            ("keep", "/s/shared.json", "harmless notes"),
+            ("other", "/s/other.json", "harmless notes too"),
+        ] {
+            let conv = conversation(external_id, path, content);
+            storage
+                .insert_conversation_tree(agent_id, None, &conv)
+                .unwrap();
+            index
+                .add_conversation(&normalized(conv.clone(), conv.messages))
+                .unwrap();
+        }
+        index.commit().unwrap();
+        drop(index);
+
+        let all = ConversationSelector {
+            agents: vec!["codex".into()],
+            ..Default::default()
+        };
+        let all_ids: Vec<i64> = storage
+            .select_conversations(&all)
+            .unwrap()
+            .iter()
+            .map(|c| c.conversation_id)
+            .collect();
+        let entries = storage
+            .message_ids_for_conversations(&all_ids)
+            .unwrap()
+            .into_iter()
+            .map(|id| VectorEntry {
+                message_id: id as u64,
+                created_at_ms: 0,
+                agent_id: 1,
+                workspace_id: 0,
+                source_id: 0,
+                role: 0,
+                chunk_idx: 0,
+                content_hash: [0; 32],
+                vector: vec![1.0, 0.0],
+            });
+        std::fs::create_dir_all(data_dir.join(VECTOR_INDEX_DIR)).unwrap();
+        let vectors = vector_index_path(data_dir, "test");
+        VectorIndex::build("test", "rev", 2, Quantization::F32, entries)
+            .unwrap()
+            .save(&vectors)
+            .unwrap();
+
+        let selector = ConversationSelector {
+            query: Some("sk-live-4f9a".into()),
+            ..Default::default()
+        };
+        let preview = forget(&mut storage, data_dir, &selector, true).unwrap();
+        assert_eq!(preview.conversation_count, 1);
+        assert_eq!(preview.lexical_index, LexicalStatus::Untouched);
+        assert_eq!(lexical_hits(data_dir, "leakedsecret"), vec!["leak"]);
+
+        let report = forget(&mut storage, data_dir, &selector, false).unwrap();
+        assert_eq!(report.conversations[0].title.as_deref(), Some("leak"));
+        assert_eq!(report.lexical_index, LexicalStatus::Updated);
+        assert_eq!(report.lexical_reindexed, 1);
+        assert_eq!(report.vectors_removed, 1);
+
+        assert!(lexical_hits(data_dir, "leakedsecret").is_empty());
+        assert_eq!(lexical_hits(data_dir, "harmless"), vec!["keep", "other"]);
+        assert_eq!(VectorIndex::load(&vectors).unwrap().rows().len(), 2);
+        assert_eq!(storage.select_conversations(&all).unwrap().len(), 2);
+        assert!(
+            forget(
+                &mut storage,
+                data_dir,
+                &ConversationSelector::default(),
+                true
+            )
+            .is_err(),
+            "an empty selector must not select everything"
+        );
+    }
+}
@@ -0,0 +1,549 @@
+//! Multi-conversation export with a browsable index.
+//!
+//! [`HtmlExporter::export_batch`] renders each [`BatchConversation`] through
+//! [`HtmlExporter::export_messages`], linking every page back to
+//! [`INDEX_FILENAME`] and to its neighbours, and adds an index page with a
+//! sortable table and a client-side text filter.
+//!
+//! With encryption enabled every conversation page is encrypted under the
+//! same password. The index is encrypted too unless the caller asks for a
+//! readable table of contents; its scripts use event delegation so sorting
+//! and filtering keep working once the table is decrypted in place.
+
+use std::time::Instant;
+
+use tracing::{debug, info};
+
+use super::renderer::Message;
+use super::template::{
+    ExportOptions, HtmlExporter, HtmlTemplate, NavLink, TemplateError, TemplateMetadata,
+    html_escape,
+};
+use super::{encryption, scripts, styles};
+
+/// File name of the index page, relative to the export directory.
+pub const INDEX_FILENAME: &str = "index.html";
+
+/// Characters of the first user message shown under each index entry.
+const EXCERPT_CHARS: usize = 160;
+
+/// One conversation in a batch export.
+#[derive(Debug, Clone)]
+pub struct BatchConversation {
+    /// Page and index title
+    pub title: String,
+
+    /// Messages, in order
+    pub messages: Vec<Message>,
+
+    /// Header metadata; `agent` and `project` also fill the index columns
+    pub metadata: TemplateMetadata,
+
+    /// File name of the page, relative to the index
+    pub filename: String,
+
+    /// Start time in milliseconds since the epoch, used to sort the index
+    pub started_at: Option<i64>,
+}
+
+/// A rendered page of a batch export.
+#[derive(Debug, Clone)]
+pub struct BatchPage {
+    /// File name relative to the export directory
+    pub filename: String,
+
+    /// Complete HTML document
+    pub html: String,
+}
+
+const INDEX_CSS: &str = r#"
+/* ============================================
+   Batch Index - Conversation Table
+   ============================================ */
+.batch-index-controls {
+    display: flex;
+    align-items: center;
+    gap: var(--space-md);
+    margin-bottom: var(--space-md);
+}
+
+#index-filter {
+    flex: 1 1 auto;
+    padding: var(--space-xs) var(--space-sm);
+    background: var(--bg-surface);
+    border: 1px solid var(--border);
+    border-radius: var(--radius-sm);
+    color: var(--text-primary);
+    font: inherit;
+}
+
+.index-count {
+    font-size: 0.875rem;
+    color: var(--text-muted);
+    white-space: nowrap;
+}
+
+.index-table {
+    width: 100%;
+    border-collapse: collapse;
+    font-size: 0.875rem;
+}
+
+.index-table th,
+.index-table td {
+    padding: var(--space-xs) var(--space-sm);
+    border-bottom: 1px solid var(--border);
+    text-align: left;
+    vertical-align: top;
+}
+
+.index-table th {
+    color: var(--text-secondary);
+    font-weight: 600;
+    cursor: pointer;
+    user-select: none;
+    white-space: nowrap;
+}
+
+.index-table th[aria-sort="ascending"]::after { content: " \25B2"; }
+.index-table th[aria-sort="descending"]::after { content: " \25BC"; }
+
+.index-table tbody tr:hover {
+    background: var(--bg-elevated);
+}
+
+.index-table a {
+    color: var(--accent);
+    text-decoration: none;
+}
+
+.index-table a:hover {
+    text-decoration: underline;
+}
+
+.index-excerpt {
+    margin-top: 2px;
+    color: var(--text-muted);
+    font-size: 0.8125rem;
+}
+
+.index-table .numeric {
+    text-align: right;
+}
+"#;
+
+const INDEX_JS: &str = r#"// Batch index: sortable columns and text filter
+const BatchIndex = {
+    sortKey: null,
+    descending: false,
+
+    rows() {
+        return Array.from($$('#index-table tbody tr'));
+    },
+
+    sort(th) {
+        const key = th.dataset.sort;
+        const numeric = th.dataset.type === 'number';
+        // Numbers and dates start newest/largest first, text starts A-Z
+        this.descending = key === this.sortKey ? !this.descending : numeric;
+        this.sortKey = key;
+        const dir = this.descending ? -1 : 1;
+        const value = (row) => numeric
+            ? Number(row.dataset[key] || 0)
+            : (row.dataset[key] || '').toLowerCase();
+        const tbody = $('#index-table tbody');
+        this.rows()
+            .sort((a, b) => {
+                const x = value(a);
+                const y = value(b);
+                return (x < y ? -1 : x > y ? 1 : 0) * dir;
+            })
+            .forEach((row) => tbody.appendChild(row));
+        $$('#index-table th[data-sort]').forEach((h) => {
+            const order = this.descending ? 'descending' : 'ascending';
+            h.setAttribute('aria-sort', h === th ? order : 'none');
+        });
+    },
+
+    filter(text) {
+        const terms = text.toLowerCase().split(/\s+/).filter(Boolean);
+        const rows = this.rows();
+        let shown = 0;
+        rows.forEach((row) => {
+            const match = terms.every((t) => row.dataset.search.includes(t));
+            row.hidden = !match;
+            if (match) shown++;
+        });
+        const count = $('#index-count');
+        if (count) count.textContent = `${shown} of ${rows.length}`;
+    },
+
+    init() {
+        // Delegated so an encrypted index still works after decryption
+        document.addEventListener('click', (e) => {
+            const th = e.target.closest('#index-table th[data-sort]');
+            if (th) this.sort(th);
+        });
+        document.addEventListener('input', (e) => {
+            if (e.target.id === 'index-filter') this.filter(e.target.value);
+        });
+    }
+};
+
+BatchIndex.init();"#;
+
+impl HtmlExporter {
+    /// Export several conversations as linked pages plus an [`INDEX_FILENAME`] page.
+    ///
+    /// Pages keep the order of `conversations`; "Previous" and "Next" follow it.
+    /// When the exporter encrypts, `password` is required and applies to every
+    /// page; `encrypt_index` decides whether the index is encrypted as well.
+    pub fn export_batch(
+        &self,
+        title: &str,
+        conversations: &[BatchConversation],
+        password: Option<&str>,
+        encrypt_index: bool,
+    ) -> Result<Vec<BatchPage>, TemplateError> {
+        let started = Instant::now();
+        if conversations.is_empty() {
+            return Err(TemplateError::InvalidInput(
+                "no conversations to export".to_string(),
+            ));
+        }
+        if self.options().encrypt && password.is_none() {
+            return Err(TemplateError::EncryptionRequired);
+        }
+        info!(
+            component = "template",
+            operation = "export_batch",
+            conversations = conversations.len(),
+            encrypt = self.options().encrypt,
+            encrypt_index,
+            "Starting batch HTML export"
+        );
+
+        let mut pages = Vec::with_capacity(conversations.len() + 1);
+        for (i, conv) in conversations.iter().enumerate() {
+            let mut metadata = conv.metadata.clone();
+            metadata.nav = page_nav(conversations, i);
+            let html = self.export_messages(&conv.title, &conv.messages, metadata, password)?;
+            pages.push(BatchPage {
+                filename: conv.filename.clone(),
+                html,
+            });
+        }
+
+        let index = self.render_index(title, conversations, password, encrypt_index)?;
+        pages.push(BatchPage {
+            filename: INDEX_FILENAME.to_string(),
+            html: index,
+        });
+
+        info!(
+            component = "template",
+            operation = "export_batch_complete",
+            duration_ms = started.elapsed().as_millis(),
+            pages = pages.len(),
+            "Batch HTML export complete"
+        );
+        Ok(pages)
+    }
+
+    fn render_index(
+        &self,
+        title: &str,
+        conversations: &[BatchConversation],
+        password: Option<&str>,
+        encrypt_index: bool,
+    ) -> Result<String, TemplateError> {
+        let options = ExportOptions {
+            include_search: false,
+            show_tool_calls: false,
+            encrypt: self.options().encrypt && encrypt_index,
+            ..self.options().clone()
+        };
+
+        let table = render_index_table(conversations);
+        let content = match password.filter(|_| options.encrypt) {
+            Some(password) => {
+                let encrypted = encryption::encrypt_content(
+                    &table,
+                    password,
+                    &encryption::EncryptionParams::default(),
+                )
+                .map_err(|e| TemplateError::RenderFailed(e.to_string()))?;
+                encryption::render_encrypted_placeholder(&encrypted)
+            }
+            None => table,
+        };
+        debug!(
+            component = "template",
+            operation = "render_index",
+            rows = conversations.len(),
+            encrypted = options.encrypt,
+            "Rendered batch index"
+        );
+
+        let styles = styles::generate_styles(&options);
+        let scripts = scripts::generate_scripts(&options);
+        let template = HtmlTemplate {
+            title: title.to_string(),
+            critical_css: format!("{}\n{}", styles.critical_css, INDEX_CSS),
+            print_css: styles.print_css,
+            inline_js: format!("{}\n\n{}", scripts.inline_js, INDEX_JS),
+            content,
+            encrypted: options.encrypt,
+            metadata: TemplateMetadata {
+                message_count: conversations.iter().map(|c| c.messages.len()).sum(),
+                ..TemplateMetadata::default()
+            },
+        };
+        Ok(template.render(&options))
+    }
+}
+
+/// Links from the page at `i` to the index and to its neighbours.
+///
+/// Labels are generic so an encrypted set does not reveal other titles.
+fn page_nav(conversations: &[BatchConversation], i: usize) -> Vec<NavLink> {
+    let mut nav = vec![NavLink {
+        label: "All conversations".to_string(),
+        href: INDEX_FILENAME.to_string(),
+        rel: Some("index".to_string()),
+    }];
+    if let Some(prev) = i.checked_sub(1).and_then(|p| conversations.get(p)) {
+        nav.push(NavLink {
+            label: "Previous".to_string(),
+            href: prev.filename.clone(),
+            rel: Some("prev".to_string()),
+        });
+    }
+    if let Some(next) = conversations.get(i + 1) {
+        nav.push(NavLink {
+            label: "Next".to_string(),
+            href: next.filename.clone(),
+            rel: Some("next".to_string()),
+        });
+    }
+    nav
+}
+
+fn render_index_table(conversations: &[BatchConversation]) -> String {
+    let rows: Vec<String> = conversations.iter().map(render_index_row).collect();
+    format!(
+        r#"            <section class="batch-index">
+                <div class="batch-index-controls">
+                    <input type="search" id="index-filter" placeholder="Filter conversations..." aria-label="Filter conversations">
+                    <span id="index-count" class="index-count">{count} of {count}</span>
+                </div>
+                <table id="index-table" class="index-table">
+                    <thead>
+                        <tr>
+                            <th data-sort="title" aria-sort="none">Title</th>
+                            <th data-sort="agent" aria-sort="none">Agent</th>
+                            <th data-sort="workspace" aria-sort="none">Workspace</th>
+                            <th data-sort="started" data-type="number" aria-sort="none">Started</th>
+                            <th data-sort="messages" data-type="number" aria-sort="none" class="numeric">Messages</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+{rows}
+                    </tbody>
+                </table>
+            </section>"#,
+        count = conversations.len(),
+        rows = rows.join("\n"),
+    )
+}
+
+fn render_index_row(conv: &BatchConversation) -> String {
+    let agent = conv.metadata.agent.as_deref().unwrap_or("");
+    let workspace = conv.metadata.project.as_deref().unwrap_or("");
+    let excerpt = first_user_excerpt(&conv.messages);
+    let search = [conv.title.as_str(), agent, workspace, excerpt.as_str()]
+        .join(" ")
+        .to_lowercase();
+    let started = conv
+        .metadata
+        .timestamp
+        .as_deref()
+        .map(|ts| {
+            let ts = html_escape(ts);
+            format!(r#"<time datetime="{ts}">{ts}</time>"#)
+        })
+        .unwrap_or_default();
+    let excerpt_html = if excerpt.is_empty() {
+        String::new()
+    } else {
+        format!(
+            r#"<div class="index-excerpt">{}</div>"#,
+            html_escape(&excerpt)
+        )
+    };
+
+    format!(
+        r#"                        <tr data-title="{title}" data-agent="{agent}" data-workspace="{workspace}" data-started="{started_at}" data-messages="{count}" data-search="{search}">
+                            <td><a href="{href}">{title}</a>{excerpt_html}</td>
+                            <td>{agent}</td>
+                            <td>{workspace}</td>
+                            <td>{started}</td>
+                            <td class="numeric">{count}</td>
+                        </tr>"#,
+        title = html_escape(&conv.title),
+        agent = html_escape(agent),
+        workspace = html_escape(workspace),
+        started_at = conv.started_at.unwrap_or(0),
+        count = conv.messages.len(),
+        search = html_escape(&search),
+        href = html_escape(&conv.filename),
+    )
+}
+
+/// First line of the first user message, shortened for the index.
+fn first_user_excerpt(messages: &[Message]) -> String {
+    let Some(line) = messages
+        .iter()
+        .filter(|m| m.role == "user")
+        .flat_map(|m| m.content.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+    else {
+        return String::new();
+    };
+    if line.chars().count() <= EXCERPT_CHARS {
+        return line.to_string();
+    }
+    let mut excerpt: String = line.chars().take(EXCERPT_CHARS).collect();
+    excerpt.push('…');
+    excerpt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation(title: &str, filename: &str, text: &str) -> BatchConversation {
+        BatchConversation {
+            title: title.to_string(),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: text.to_string(),
+                timestamp: None,
+                tool_call: None,
+                index: Some(0),
+                author: None,
+            }],
+            metadata: TemplateMetadata {
+                agent: Some("codex".to_string()),
+                project: Some("/work/api".to_string()),
+                message_count: 1,
+                ..TemplateMetadata::default()
+            },
+            filename: filename.to_string(),
+            started_at: Some(1_764_583_200_000),
+        }
+    }
+
+    fn sprint() -> Vec<BatchConversation> {
+        vec![
+            conversation("Rotate keys", "a.html", "rotate the signing keys"),
+            conversation("Cache <ttl>", "b.html", "bump the cache ttl"),
+            conversation("Flaky test", "c.html", "why is ci red"),
+        ]
+    }
+
+    #[test]
+    fn test_batch_links_pages_and_index() {
+        let exporter = HtmlExporter::new();
+        let pages = exporter
+            .export_batch("Sprint 12", &sprint(), None, true)
+            .expect("export");
+
+        let names: Vec<&str> = pages.iter().map(|p| p.filename.as_str()).collect();
+        assert_eq!(names, ["a.html", "b.html", "c.html", INDEX_FILENAME]);
+
+        let first = &pages[0].html;
+        assert!(first.contains(r#"<a href="index.html" rel="index">All conversations</a>"#));
+        assert!(first.contains(r#"<a href="b.html" rel="next">Next</a>"#));
+        assert!(!first.contains(r#"rel="prev""#));
+        let middle = &pages[1].html;
+        assert!(middle.contains(r#"<a href="a.html" rel="prev">Previous</a>"#));
+        assert!(middle.contains(r#"<a href="c.html" rel="next">Next</a>"#));
+        assert!(!pages[2].html.contains(r#"rel="next""#));
+
+        let index = &pages[3].html;
+        assert!(index.contains(r#"id="index-table""#));
+        assert!(index.contains(r#"id="index-filter""#));
+        assert!(index.contains("3 of 3"));
+        assert!(index.contains(r#"<a href="b.html">Cache &lt;ttl&gt;</a>"#));
+        assert!(
+            index.contains(r#"data-search="cache &lt;ttl&gt; codex /work/api bump the cache ttl""#)
+        );
+        assert!(index.contains("BatchIndex.init();"));
+        assert!(!index.contains(r#"id="search-input""#));
+    }
+
+    #[test]
+    fn test_batch_rejects_empty_set() {
+        let result = HtmlExporter::new().export_batch("Empty", &[], None, true);
+        assert!(matches!(result, Err(TemplateError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_batch_requires_password_when_encrypted() {
+        let exporter = HtmlExporter::with_options(ExportOptions {
+            encrypt: true,
+            ..Default::default()
+        });
+        let result = exporter.export_batch("Sprint 12", &sprint(), None, false);
+        assert!(matches!(result, Err(TemplateError::EncryptionRequired)));
+    }
+
+    #[test]
+    #[cfg(feature = "encryption")]
+    fn test_batch_encryption_scope() {
+        let exporter = HtmlExporter::with_options(ExportOptions {
+            encrypt: true,
+            ..Default::default()
+        });
+
+        let pages = exporter
+            .export_batch("Sprint 12", &sprint(), Some("hunter2"), true)
+            .expect("export");
+        assert!(pages.iter().all(|p| p.html.contains("encrypted-content")));
+        assert!(!pages[0].html.contains("rotate the signing keys"));
+        assert!(!pages[3].html.contains("Flaky test"));
+
+        let pages = exporter
+            .export_batch("Sprint 12", &sprint(), Some("hunter2"), false)
+            .expect("export");
+        assert!(pages[0].html.contains("encrypted-content"));
+        let index = &pages[3].html;
+        assert!(!index.contains("encrypted-content"));
+        assert!(index.contains("Flaky test"));
+        assert!(!index.contains("password-modal"));
+    }
+
+    #[test]
+    fn test_excerpt_uses_first_user_line() {
+        let mut messages = sprint().remove(0).messages;
+        messages.insert(
+            0,
+            Message {
+                role: "system".to_string(),
+                content: "You are helpful".to_string(),
+                timestamp: None,
+                tool_call: None,
+                index: None,
+                author: None,
+            },
+        );
+        assert_eq!(first_user_excerpt(&messages), "rotate the signing keys");
+
+        messages[1].content = format!("\n{}\nsecond line", "x".repeat(200));
+        let excerpt = first_user_excerpt(&messages);
+        assert_eq!(excerpt.chars().count(), EXCERPT_CHARS + 1);
+        assert!(excerpt.ends_with('…'));
+    }
+}
@@ -17,6 +17,7 @@
 //! ├── scripts.rs       # JS (decryption, search, theme toggle)
 //! ├── renderer.rs      # Conversation -> HTML rendering
 //! ├── filename.rs      # Smart filename generation
+//! ├── batch.rs         # Multi-conversation export with an index page
 //! └── encryption.rs    # Web Crypto compatible encryption
 //! ```
 //!
@@ -30,6 +31,7 @@
 //! std::fs::write("session.html", html)?;
 //! ```
 
+mod batch;
 mod encryption;
 mod filename;
 mod renderer;
@@ -38,6 +40,7 @@ mod styles;
 mod template;
 
 // Re-export public API
+pub use batch::{BatchConversation, BatchPage, INDEX_FILENAME};
 pub use encryption::{EncryptedContent, EncryptionError, EncryptionParams, encrypt_content};
 pub use filename::{
     FilenameMetadata, FilenameOptions, agent_slug, datetime_slug, extract_topic, generate_filename,
@@ -50,7 +53,9 @@ pub use renderer::{
 };
 pub use scripts::{ScriptBundle, generate_scripts};
 pub use styles::{StyleBundle, generate_styles};
-pub use template::{ExportOptions, HtmlExporter, HtmlTemplate, TemplateError, TemplateMetadata};
+pub use template::{
+    ExportOptions, HtmlExporter, HtmlTemplate, NavLink, TemplateError, TemplateMetadata,
+};
 
 /// Color palette matching TUI theme.rs for visual consistency.
 ///
@@ -587,6 +587,24 @@ const HEADER_STYLES: &str = r#"
     gap: var(--space-xs);
 }
 
+/* Links between pages of a multi-conversation export */
+.page-nav {
+    display: flex;
+    flex-wrap: wrap;
+    gap: var(--space-md);
+    margin-bottom: var(--space-sm);
+    font-size: 0.875rem;
+}
+
+.page-nav a {
+    color: var(--accent);
+    text-decoration: none;
+}
+
+.page-nav a:hover {
+    text-decoration: underline;
+}
+
 .agent-badge {
     display: inline-flex;
     align-items: center;
@@ -3350,6 +3368,7 @@ fn generate_print_css() -> String {
     #search-input,
     [data-testid="search"],
     .search-nav,
+    .page-nav,
     .kbd-hint {
         display: none !important;
     }
@@ -262,6 +262,22 @@ pub struct TemplateMetadata {
 
     /// Source project/directory
     pub project: Option<String>,
+
+    /// Links to related pages, shown above the title
+    pub nav: Vec<NavLink>,
+}
+
+/// A link in the header of one page of a multi-page export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavLink {
+    /// Link text
+    pub label: String,
+
+    /// Relative URL of the target page
+    pub href: String,
+
+    /// Link relation (`prev`, `next`, ...)
+    pub rel: Option<String>,
 }
 
 impl HtmlTemplate {
@@ -473,6 +489,34 @@ impl HtmlTemplate {
             ));
         }
 
+        let nav_html = if self.metadata.nav.is_empty() {
+            String::new()
+        } else {
+            let links: Vec<String> = self
+                .metadata
+                .nav
+                .iter()
+                .map(|link| {
+                    let rel = link
+                        .rel
+                        .as_deref()
+                        .map(|rel| format!(r#" rel="{}""#, html_escape(rel)))
+                        .unwrap_or_default();
+                    format!(
+                        r#"<a href="{}"{}>{}</a>"#,
+                        html_escape(&link.href),
+                        rel,
+                        html_escape(&link.label)
+                    )
+                })
+                .collect();
+            format!(
+                r#"
+            <nav class="page-nav" aria-label="Export pages">{}</nav>"#,
+                links.join("\n                ")
+            )
+        };
+
         let meta_html = if meta_items.is_empty() {
             String::new()
         } else {
@@ -485,9 +529,10 @@ impl HtmlTemplate {
 
         format!(
             r#"        <!-- Header with metadata -->
-        <header class="header" role="banner">
+        <header class="header" role="banner">{}
             <h1 class="title">{}</h1>{}
         </header>"#,
+            nav_html,
             html_escape(&self.title),
             meta_html
         )
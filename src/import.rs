@@ -0,0 +1,1008 @@
+//! Account data exports (`cass import`).
+//!
+//! Reads the `conversations.json` of a ChatGPT or Claude.ai data export, either
+//! the downloaded zip or the extracted file, and ingests it under its own source
+//! id. Both services keep edited and regenerated turns as a tree: the indexed
+//! conversation follows the active path, and each alternate branch is kept in
+//! the conversation metadata under `branches`.
+//!
+//! The export is copied to `<data_dir>/imports/<source_id>/`, and `cass index`
+//! re-reads it from there, so a rebuild keeps imported conversations. Importing
+//! a newer export for the same source id replaces the stored copy; conversations
+//! already indexed gain their new messages.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::connectors::{NormalizedConversation, NormalizedMessage, flatten_content};
+use crate::indexer::{ingest_with_origin, stored_secret_redactor};
+use crate::search::tantivy::{TantivyIndex, index_dir, index_schema_current};
+use crate::sources::provenance::{LOCAL_SOURCE_ID, Origin, Source, SourceKind};
+use crate::storage::sqlite::SqliteStorage;
+
+/// Directory under the data dir holding one stored export per source id.
+pub const IMPORTS_DIR: &str = "imports";
+
+/// File in an export that holds the conversations.
+const CONVERSATIONS_FILE: &str = "conversations.json";
+
+/// Which service produced an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// ChatGPT: conversations with a `mapping` node tree
+    Chatgpt,
+    /// Claude.ai: conversations with `chat_messages`
+    Claude,
+}
+
+impl ExportFormat {
+    /// Agent slug the imported conversations are indexed under.
+    pub fn agent_slug(self) -> &'static str {
+        match self {
+            Self::Chatgpt => "chatgpt",
+            Self::Claude => "claude_ai",
+        }
+    }
+
+    /// Source id used when `--source-id` is not given.
+    pub fn default_source_id(self) -> &'static str {
+        match self {
+            Self::Chatgpt => "chatgpt-export",
+            Self::Claude => "claude-export",
+        }
+    }
+
+    fn detect(conversations: &[Value]) -> Option<Self> {
+        conversations.iter().find_map(|conv| {
+            if conv.get("mapping").is_some() {
+                Some(Self::Chatgpt)
+            } else if conv.get("chat_messages").is_some() {
+                Some(Self::Claude)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// An export kept under [`IMPORTS_DIR`].
+#[derive(Debug, Clone)]
+pub struct StoredExport {
+    pub source_id: String,
+    pub path: PathBuf,
+    /// Modification time of the stored copy, in milliseconds
+    pub modified_ms: i64,
+}
+
+/// Options for [`import`].
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// Format to assume; detected from the conversations when `None`
+    pub format: Option<ExportFormat>,
+    /// Source id to index under; defaults to [`ExportFormat::default_source_id`]
+    pub source_id: Option<String>,
+    pub dry_run: bool,
+}
+
+/// What an import did to the Tantivy index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LexicalStatus {
+    /// The conversations were indexed
+    Updated,
+    /// No current lexical index exists; the next `cass index` ingests the stored export
+    Pending,
+    /// Dry run: nothing was written
+    Untouched,
+}
+
+/// Outcome of [`import`], serialized as-is for `cass import --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub format: ExportFormat,
+    pub agent: &'static str,
+    pub source_id: String,
+    /// Stored copy of the export that `cass index` re-reads
+    pub stored_path: PathBuf,
+    pub conversations: usize,
+    pub messages: usize,
+    /// Conversations with at least one alternate branch
+    pub branched_conversations: usize,
+    pub alternate_branches: usize,
+    pub lexical_index: LexicalStatus,
+}
+
+/// Import the export at `path` into the stores under `data_dir`.
+pub fn import(
+    storage: &mut SqliteStorage,
+    data_dir: &Path,
+    path: &Path,
+    opts: &ImportOptions,
+) -> Result<ImportReport> {
+    let (bytes, zipped) = read_conversations_file(path)?;
+    let conversations = parse_conversations(&bytes)?;
+    let Some(format) = resolve_format(opts.format, &conversations)? else {
+        bail!("the export contains no conversations");
+    };
+    let source_id = opts
+        .source_id
+        .clone()
+        .unwrap_or_else(|| format.default_source_id().to_string());
+    validate_source_id(storage, &source_id, SourceKind::Import)?;
+
+    let source_dir = data_dir.join(IMPORTS_DIR).join(&source_id);
+    let stored_path = source_dir.join(if zipped { "export.zip" } else { "export.json" });
+    let normalized = normalize(format, &conversations, &stored_path);
+    let branch_counts: Vec<usize> = normalized
+        .iter()
+        .map(|conv| conv.metadata["branches"].as_array().map_or(0, Vec::len))
+        .collect();
+    let mut report = ImportReport {
+        dry_run: opts.dry_run,
+        format,
+        agent: format.agent_slug(),
+        source_id: source_id.clone(),
+        stored_path: stored_path.clone(),
+        conversations: normalized.len(),
+        messages: normalized.iter().map(|c| c.messages.len()).sum(),
+        branched_conversations: branch_counts.iter().filter(|n| **n > 0).count(),
+        alternate_branches: branch_counts.iter().sum(),
+        lexical_index: LexicalStatus::Untouched,
+    };
+    if opts.dry_run {
+        return Ok(report);
+    }
+
+    store_export(path, zipped, &bytes, &source_dir, &stored_path)?;
+    storage.upsert_source(&Source {
+        id: source_id.clone(),
+        kind: SourceKind::Import,
+        host_label: None,
+        machine_id: None,
+        platform: None,
+        config_json: Some(json!({ "format": format, "imported_from": path })),
+        created_at: None,
+        updated_at: None,
+    })?;
+
+    let index_path = index_dir(data_dir)?;
+    if !index_path.join("meta.json").exists() || !index_schema_current(&index_path) {
+        // Opening would create or wipe the index; leave that to `cass index`.
+        report.lexical_index = LexicalStatus::Pending;
+        return Ok(report);
+    }
+    let mut t_index = TantivyIndex::open_or_create(&index_path)
+        .context("opening the lexical index (is `cass index --watch` running?)")?;
+    let redactor = stored_secret_redactor(storage)?;
+    ingest_with_origin(
+        storage,
+        &mut t_index,
+        normalized,
+        &Origin::import(&source_id),
+        false,
+        redactor.as_ref(),
+    )?;
+    t_index.commit()?;
+    report.lexical_index = LexicalStatus::Updated;
+    Ok(report)
+}
+
+/// Read the conversations of a stored or downloaded export.
+pub fn read_export(
+    path: &Path,
+    format: Option<ExportFormat>,
+) -> Result<Vec<NormalizedConversation>> {
+    let (bytes, _) = read_conversations_file(path)?;
+    let conversations = parse_conversations(&bytes)?;
+    Ok(match resolve_format(format, &conversations)? {
+        Some(format) => normalize(format, &conversations, path),
+        None => Vec::new(),
+    })
+}
+
+/// The given format, or the detected one; `None` only for an empty export.
+fn resolve_format(
+    format: Option<ExportFormat>,
+    conversations: &[Value],
+) -> Result<Option<ExportFormat>> {
+    match format.or_else(|| ExportFormat::detect(conversations)) {
+        Some(format) => Ok(Some(format)),
+        None if conversations.is_empty() => Ok(None),
+        None => bail!("not a ChatGPT or Claude.ai export; pass --format if it is one"),
+    }
+}
+
+/// Exports kept under `<data_dir>/imports`, one per source id.
+pub fn stored_exports(data_dir: &Path) -> Vec<StoredExport> {
+    let Ok(entries) = fs::read_dir(data_dir.join(IMPORTS_DIR)) else {
+        return Vec::new();
+    };
+    let mut stored: Vec<StoredExport> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let source_id = entry.file_name().to_str()?.to_string();
+            let path = ["export.zip", "export.json"]
+                .iter()
+                .map(|name| entry.path().join(name))
+                .find(|path| path.is_file())?;
+            let modified_ms = fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_millis() as i64);
+            Some(StoredExport {
+                source_id,
+                path,
+                modified_ms,
+            })
+        })
+        .collect();
+    stored.sort_by(|a, b| a.source_id.cmp(&b.source_id));
+    stored
+}
+
+/// Drop the conversations at `source_paths` from the stored export of
+/// `source_id`, so `cass index` does not bring them back, and return the stored
+/// copy if it held any of them. The rest is rewritten as `export.json`; an
+/// export left empty is removed. With `dry_run` nothing is written.
+pub(crate) fn forget_stored_conversations(
+    data_dir: &Path,
+    source_id: &str,
+    source_paths: &HashSet<String>,
+    dry_run: bool,
+) -> Result<Option<PathBuf>> {
+    let Some(stored) = stored_exports(data_dir)
+        .into_iter()
+        .find(|stored| stored.source_id == source_id)
+    else {
+        return Ok(None);
+    };
+    let (bytes, _) = read_conversations_file(&stored.path)?;
+    let conversations = parse_conversations(&bytes)?;
+    let Some(format) = resolve_format(None, &conversations)? else {
+        return Ok(None);
+    };
+    let (forgotten, kept): (Vec<&Value>, Vec<&Value>) = conversations.iter().partition(|conv| {
+        normalize(format, std::slice::from_ref(*conv), &stored.path)
+            .first()
+            .is_some_and(|norm| source_paths.contains(&*norm.source_path.to_string_lossy()))
+    });
+    if forgotten.is_empty() {
+        return Ok(None);
+    }
+    if dry_run {
+        return Ok(Some(stored.path));
+    }
+
+    let source_dir = data_dir.join(IMPORTS_DIR).join(source_id);
+    if kept.is_empty() {
+        fs::remove_dir_all(&source_dir)
+            .with_context(|| format!("removing {}", source_dir.display()))?;
+    } else {
+        let json = serde_json::to_vec(&kept)?;
+        store_export(
+            &stored.path,
+            false,
+            &json,
+            &source_dir,
+            &source_dir.join("export.json"),
+        )?;
+    }
+    Ok(Some(stored.path))
+}
+
+/// Check that `source_id` is usable for a new or existing source of `kind`.
+pub(crate) fn validate_source_id(
+    storage: &SqliteStorage,
+    source_id: &str,
+    kind: SourceKind,
+) -> Result<()> {
+    if source_id.is_empty()
+        || !source_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        || source_id.starts_with('.')
+    {
+        bail!("invalid source id '{source_id}': use letters, digits, '-', '_' and '.'");
+    }
+    if source_id == LOCAL_SOURCE_ID {
+        bail!("source id '{LOCAL_SOURCE_ID}' is reserved for this machine's sessions");
+    }
+    if let Some(existing) = storage.get_source(source_id)?
+        && existing.kind != kind
+    {
+        bail!(
+            "source id '{source_id}' already belongs to a {} source",
+            existing.kind
+        );
+    }
+    Ok(())
+}
+
+/// Replace the stored copy of a source's export, writing a temp file first so a
+/// failed copy never loses the previous one.
+fn store_export(
+    path: &Path,
+    zipped: bool,
+    bytes: &[u8],
+    source_dir: &Path,
+    stored_path: &Path,
+) -> Result<()> {
+    fs::create_dir_all(source_dir).with_context(|| format!("creating {}", source_dir.display()))?;
+    let tmp = stored_path.with_extension("tmp");
+    if zipped {
+        fs::copy(path, &tmp)
+    } else {
+        fs::write(&tmp, bytes).map(|_| 0)
+    }
+    .with_context(|| format!("copying the export to {}", tmp.display()))?;
+    fs::rename(&tmp, stored_path)
+        .with_context(|| format!("storing the export at {}", stored_path.display()))?;
+    for stale in ["export.zip", "export.json"] {
+        let stale = source_dir.join(stale);
+        if stale != stored_path {
+            let _ = fs::remove_file(stale);
+        }
+    }
+    Ok(())
+}
+
+/// Contents of `conversations.json` from a zip, an export directory or the file
+/// itself, and whether it came from a zip.
+fn read_conversations_file(path: &Path) -> Result<(Vec<u8>, bool)> {
+    if path.is_dir() {
+        let file = path.join(CONVERSATIONS_FILE);
+        let bytes = fs::read(&file).with_context(|| format!("reading {}", file.display()))?;
+        return Ok((bytes, false));
+    }
+    let bytes = fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+    if !bytes.starts_with(b"PK\x03\x04") {
+        return Ok((bytes, false));
+    }
+    let archive = ZipArchive::parse(&bytes)
+        .with_context(|| format!("reading zip archive {}", path.display()))?;
+    let entry = archive
+        .entries
+        .iter()
+        .filter(|e| e.name == CONVERSATIONS_FILE || e.name.ends_with("/conversations.json"))
+        .min_by_key(|e| e.name.len())
+        .with_context(|| format!("{} has no {CONVERSATIONS_FILE}", path.display()))?;
+    Ok((archive.read(entry)?, true))
+}
+
+fn parse_conversations(bytes: &[u8]) -> Result<Vec<Value>> {
+    let value: Value =
+        serde_json::from_slice(bytes).context("conversations.json is not valid JSON")?;
+    match value {
+        Value::Array(conversations) => Ok(conversations),
+        _ => bail!("conversations.json is not a list of conversations"),
+    }
+}
+
+/// Entry of a zip central directory.
+#[derive(Debug)]
+struct ZipEntry {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    size: usize,
+    local_header: usize,
+}
+
+/// Just enough of the zip format for the exports: stored and deflated entries, no zip64.
+struct ZipArchive<'a> {
+    bytes: &'a [u8],
+    entries: Vec<ZipEntry>,
+}
+
+impl<'a> ZipArchive<'a> {
+    const EOCD_SIGNATURE: u32 = 0x0605_4b50;
+    const CENTRAL_SIGNATURE: u32 = 0x0201_4b50;
+    const LOCAL_SIGNATURE: u32 = 0x0403_4b50;
+
+    fn parse(bytes: &'a [u8]) -> Result<Self> {
+        // The end-of-central-directory record is last, followed by a comment of at most 64 KiB
+        let search_start = bytes.len().saturating_sub(22 + u16::MAX as usize);
+        let eocd = (search_start..bytes.len().saturating_sub(21))
+            .rev()
+            .find(|&at| read_u32(bytes, at) == Some(Self::EOCD_SIGNATURE))
+            .context("end of central directory not found")?;
+        let count = read_u16(bytes, eocd + 10).context("truncated zip")? as usize;
+        let mut at = read_u32(bytes, eocd + 16).context("truncated zip")? as usize;
+        if count == u16::MAX as usize || at == u32::MAX as usize {
+            bail!(
+                "zip64 archives are not supported; extract the export and import conversations.json"
+            );
+        }
+
+        let mut entries = Vec::with_capacity(count);
+        for _ in 0..count {
+            if read_u32(bytes, at) != Some(Self::CENTRAL_SIGNATURE) {
+                bail!("corrupt central directory");
+            }
+            let field = |offset: usize| read_u16(bytes, at + offset).context("truncated zip");
+            let flags = field(8)?;
+            let method = field(10)?;
+            let compressed_size = read_u32(bytes, at + 20).context("truncated zip")? as usize;
+            let size = read_u32(bytes, at + 24).context("truncated zip")? as usize;
+            let name_len = field(28)? as usize;
+            let extra_len = field(30)? as usize;
+            let comment_len = field(32)? as usize;
+            let local_header = read_u32(bytes, at + 42).context("truncated zip")? as usize;
+            let name = bytes
+                .get(at + 46..at + 46 + name_len)
+                .context("truncated zip")?;
+            if flags & 1 == 0 {
+                entries.push(ZipEntry {
+                    name: String::from_utf8_lossy(name).into_owned(),
+                    method,
+                    compressed_size,
+                    size,
+                    local_header,
+                });
+            }
+            at += 46 + name_len + extra_len + comment_len;
+        }
+        Ok(Self { bytes, entries })
+    }
+
+    fn read(&self, entry: &ZipEntry) -> Result<Vec<u8>> {
+        let at = entry.local_header;
+        if read_u32(self.bytes, at) != Some(Self::LOCAL_SIGNATURE) {
+            bail!("corrupt local header for {}", entry.name);
+        }
+        let name_len = read_u16(self.bytes, at + 26).context("truncated zip")? as usize;
+        let extra_len = read_u16(self.bytes, at + 28).context("truncated zip")? as usize;
+        let start = at + 30 + name_len + extra_len;
+        let data = self
+            .bytes
+            .get(start..start + entry.compressed_size)
+            .with_context(|| format!("truncated data for {}", entry.name))?;
+        match entry.method {
+            0 => Ok(data.to_vec()),
+            8 => {
+                let mut out = Vec::with_capacity(entry.size);
+                flate2::read::DeflateDecoder::new(data)
+                    .read_to_end(&mut out)
+                    .with_context(|| format!("inflating {}", entry.name))?;
+                Ok(out)
+            }
+            method => bail!(
+                "{} uses unsupported compression method {method}",
+                entry.name
+            ),
+        }
+    }
+}
+
+fn read_u16(bytes: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Normalize every conversation; each one's `source_path` is `<stored_path>/<id>`
+/// so `cass forget --path` can select single conversations or the whole export.
+fn normalize(
+    format: ExportFormat,
+    conversations: &[Value],
+    stored_path: &Path,
+) -> Vec<NormalizedConversation> {
+    conversations
+        .iter()
+        .filter_map(|conv| match format {
+            ExportFormat::Chatgpt => chatgpt_conversation(conv, stored_path),
+            ExportFormat::Claude => claude_conversation(conv, stored_path),
+        })
+        .collect()
+}
+
+/// One message of an export, before it gets its index.
+#[derive(Debug, Clone)]
+struct Turn {
+    role: String,
+    author: Option<String>,
+    created_at: Option<i64>,
+    content: String,
+    extra: Value,
+}
+
+/// A conversation tree: nodes by id, each with its children in creation order.
+#[derive(Default)]
+struct Tree {
+    nodes: HashMap<String, TreeNode>,
+    /// Node ids in export order, for finding roots deterministically
+    order: Vec<String>,
+}
+
+struct TreeNode {
+    parent: Option<String>,
+    children: Vec<String>,
+    turn: Option<Turn>,
+}
+
+/// The active path of a tree and the branches that fork off it.
+struct Thread {
+    turns: Vec<Turn>,
+    branches: Vec<Value>,
+}
+
+impl Tree {
+    fn insert(&mut self, id: String, parent: Option<String>, turn: Option<Turn>) {
+        self.order.push(id.clone());
+        self.nodes.insert(
+            id,
+            TreeNode {
+                parent,
+                children: Vec::new(),
+                turn,
+            },
+        );
+    }
+
+    /// Fill in `children` from the parent links, in export order.
+    fn link_children(&mut self) {
+        for id in &self.order {
+            let Some(parent) = self.nodes[id].parent.clone() else {
+                continue;
+            };
+            if let Some(node) = self.nodes.get_mut(&parent) {
+                node.children.push(id.clone());
+            }
+        }
+    }
+
+    /// Follow the newest child from `id` down to a leaf.
+    fn newest_descent(&self, id: &str) -> Vec<&str> {
+        let mut path = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = Some(id);
+        while let Some(id) = current.filter(|id| seen.insert(*id)) {
+            let Some(node) = self.nodes.get(id) else {
+                break;
+            };
+            path.push(id);
+            current = node.children.last().map(String::as_str);
+        }
+        path
+    }
+
+    /// Flatten to the path ending at `leaf`, or the newest path when it is unknown.
+    fn flatten(&self, leaf: Option<&str>) -> Thread {
+        let path: Vec<&str> = match leaf.filter(|leaf| self.nodes.contains_key(*leaf)) {
+            Some(leaf) => {
+                let mut path = Vec::new();
+                let mut seen = HashSet::new();
+                let mut current = Some(leaf);
+                while let Some(id) = current.filter(|id| seen.insert(*id)) {
+                    let Some(node) = self.nodes.get(id) else {
+                        break;
+                    };
+                    path.push(id);
+                    current = node.parent.as_deref();
+                }
+                path.reverse();
+                path
+            }
+            None => self
+                .order
+                .iter()
+                .find(|id| {
+                    self.nodes[*id]
+                        .parent
+                        .as_ref()
+                        .is_none_or(|parent| !self.nodes.contains_key(parent))
+                })
+                .map(|root| self.newest_descent(root))
+                .unwrap_or_default(),
+        };
+
+        let on_path: HashSet<&str> = path.iter().copied().collect();
+        let mut turns = Vec::new();
+        let mut branches = Vec::new();
+        for id in &path {
+            let node = &self.nodes[*id];
+            turns.extend(node.turn.clone());
+            for child in node
+                .children
+                .iter()
+                .filter(|c| !on_path.contains(c.as_str()))
+            {
+                let messages: Vec<Value> = self
+                    .newest_descent(child)
+                    .into_iter()
+                    .filter_map(|id| self.nodes[id].turn.as_ref())
+                    .map(|turn| {
+                        json!({
+                            "role": turn.role,
+                            "content": turn.content,
+                            "created_at": turn.created_at,
+                        })
+                    })
+                    .collect();
+                if !messages.is_empty() {
+                    branches.push(json!({
+                        // Index of the last active-path message before the fork
+                        "fork_after": turns.len().checked_sub(1),
+                        "messages": messages,
+                    }));
+                }
+            }
+        }
+        Thread { turns, branches }
+    }
+}
+
+fn into_conversation(
+    agent_slug: &str,
+    external_id: String,
+    title: Option<String>,
+    stored_path: &Path,
+    thread: Thread,
+    mut metadata: Value,
+) -> Option<NormalizedConversation> {
+    if thread.turns.is_empty() {
+        return None;
+    }
+    if !thread.branches.is_empty() {
+        metadata["branches"] = Value::Array(thread.branches);
+    }
+    let messages: Vec<NormalizedMessage> = thread
+        .turns
+        .into_iter()
+        .enumerate()
+        .map(|(idx, turn)| NormalizedMessage {
+            idx: idx as i64,
+            role: turn.role,
+            author: turn.author,
+            created_at: turn.created_at,
+            content: turn.content,
+            extra: turn.extra,
+            snippets: Vec::new(),
+        })
+        .collect();
+    Some(NormalizedConversation {
+        agent_slug: agent_slug.to_string(),
+        source_path: stored_path.join(&external_id),
+        external_id: Some(external_id),
+        title,
+        workspace: None,
+        started_at: messages.iter().find_map(|m| m.created_at),
+        ended_at: messages.iter().rev().find_map(|m| m.created_at),
+        metadata,
+        messages,
+    })
+}
+
+fn chatgpt_conversation(conv: &Value, stored_path: &Path) -> Option<NormalizedConversation> {
+    let id = conv
+        .get("conversation_id")
+        .or_else(|| conv.get("id"))
+        .and_then(Value::as_str)?;
+    let mapping = conv.get("mapping")?.as_object()?;
+
+    let mut tree = Tree::default();
+    for (node_id, node) in mapping {
+        let parent = node.get("parent").and_then(Value::as_str).map(String::from);
+        let turn = node.get("message").and_then(chatgpt_turn);
+        tree.insert(node_id.clone(), parent, turn);
+    }
+    // The export lists children in creation order; object order is not reliable
+    for (node_id, node) in mapping {
+        let children: Vec<String> = node
+            .get("children")
+            .and_then(Value::as_array)
+            .map(|children| {
+                children
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .filter(|c| tree.nodes.contains_key(*c))
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(node) = tree.nodes.get_mut(node_id) {
+            node.children = children;
+        }
+    }
+
+    let thread = tree.flatten(conv.get("current_node").and_then(Value::as_str));
+    into_conversation(
+        ExportFormat::Chatgpt.agent_slug(),
+        id.to_string(),
+        conv.get("title").and_then(Value::as_str).map(String::from),
+        stored_path,
+        thread,
+        json!({
+            "source": "chatgpt_export",
+            "model": conv.get("default_model_slug").and_then(Value::as_str),
+        }),
+    )
+}
+
+fn chatgpt_turn(msg: &Value) -> Option<Turn> {
+    let author = msg.get("author")?;
+    let role = author.get("role").and_then(Value::as_str)?;
+    let hidden = msg
+        .pointer("/metadata/is_visually_hidden_from_conversation")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if role == "system" || hidden {
+        return None;
+    }
+
+    let content = msg.get("content")?;
+    let text = match content.get("parts").and_then(Value::as_array) {
+        // Image and file parts are objects; only the text parts are searchable
+        Some(parts) => parts
+            .iter()
+            .filter_map(Value::as_str)
+            .filter(|part| !part.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        None => ["text", "result"]
+            .iter()
+            .find_map(|key| content.get(*key).and_then(Value::as_str))
+            .unwrap_or_default()
+            .to_string(),
+    };
+    if text.trim().is_empty() {
+        return None;
+    }
+
+    let author_name = match role {
+        "tool" => author.get("name").and_then(Value::as_str),
+        _ => msg.pointer("/metadata/model_slug").and_then(Value::as_str),
+    };
+    Some(Turn {
+        role: role.to_string(),
+        author: author_name.map(String::from),
+        created_at: msg
+            .get("create_time")
+            .and_then(Value::as_f64)
+            .map(|ts| (ts * 1000.0) as i64),
+        content: text,
+        extra: msg.clone(),
+    })
+}
+
+fn claude_conversation(conv: &Value, stored_path: &Path) -> Option<NormalizedConversation> {
+    let id = conv.get("uuid").and_then(Value::as_str)?;
+    let messages = conv.get("chat_messages")?.as_array()?;
+
+    let mut tree = Tree::default();
+    for (position, msg) in messages.iter().enumerate() {
+        let node_id = msg
+            .get("uuid")
+            .and_then(Value::as_str)
+            .map_or_else(|| format!("#{position}"), String::from);
+        // Older exports are linear and carry no parent links
+        let parent = match msg.get("parent_message_uuid") {
+            Some(parent) => parent.as_str().map(String::from),
+            None => tree.order.last().cloned(),
+        };
+        tree.insert(node_id, parent, claude_turn(msg));
+    }
+    tree.link_children();
+
+    let leaf = conv
+        .get("current_leaf_message_uuid")
+        .and_then(Value::as_str)
+        .map(String::from)
+        .or_else(|| tree.order.last().cloned());
+    let thread = tree.flatten(leaf.as_deref());
+    let title = conv
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|name| !name.trim().is_empty())
+        .map(String::from);
+    into_conversation(
+        ExportFormat::Claude.agent_slug(),
+        id.to_string(),
+        title,
+        stored_path,
+        thread,
+        json!({
+            "source": "claude_ai_export",
+            "summary": conv.get("summary").and_then(Value::as_str).filter(|s| !s.is_empty()),
+        }),
+    )
+}
+
+fn claude_turn(msg: &Value) -> Option<Turn> {
+    let role = match msg.get("sender").and_then(Value::as_str)? {
+        "human" => "user",
+        other => other,
+    };
+    let text = msg
+        .get("content")
+        .map(flatten_content)
+        .filter(|text| !text.trim().is_empty())
+        .or_else(|| msg.get("text").and_then(Value::as_str).map(String::from))?;
+    if text.trim().is_empty() {
+        return None;
+    }
+    Some(Turn {
+        role: role.to_string(),
+        author: None,
+        created_at: msg
+            .get("created_at")
+            .and_then(crate::connectors::parse_timestamp),
+        content: text,
+        extra: msg.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::DeflateEncoder;
+    use std::io::Write;
+
+    fn chatgpt_node(
+        id: &str,
+        parent: Option<&str>,
+        children: &[&str],
+        role: &str,
+        text: &str,
+    ) -> Value {
+        json!({
+            "id": id,
+            "parent": parent,
+            "children": children,
+            "message": {
+                "id": id,
+                "author": { "role": role },
+                "create_time": 1_700_000_000.0 + id.len() as f64,
+                "content": { "content_type": "text", "parts": [text] },
+                "metadata": {}
+            }
+        })
+    }
+
+    /// A ChatGPT conversation whose answer was regenerated once.
+    fn chatgpt_export() -> Value {
+        json!([{
+            "id": "conv-1",
+            "title": "Retry policy",
+            "current_node": "a2",
+            "default_model_slug": "gpt-4o",
+            "mapping": {
+                "root": { "id": "root", "parent": null, "children": ["sys"], "message": null },
+                "sys": chatgpt_node("sys", Some("root"), &["u1"], "system", "You are ChatGPT"),
+                "u1": chatgpt_node("u1", Some("sys"), &["a1", "a2"], "user", "How should retries back off?"),
+                "a1": chatgpt_node("a1", Some("u1"), &[], "assistant", "Use a fixed delay."),
+                "a2": chatgpt_node("a2", Some("u1"), &[], "assistant", "Use exponential backoff with jitter."),
+            }
+        }])
+    }
+
+    /// Build a zip with one deflated entry.
+    fn zip_with(name: &str, data: &[u8]) -> Vec<u8> {
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut zip = Vec::new();
+        zip.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        zip.extend_from_slice(&[20, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        zip.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        zip.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        zip.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&0u16.to_le_bytes());
+        zip.extend_from_slice(name.as_bytes());
+        zip.extend_from_slice(&compressed);
+
+        let central = zip.len();
+        zip.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        zip.extend_from_slice(&[20, 0, 20, 0, 0, 0, 8, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        zip.extend_from_slice(&(compressed.len() as u32).to_le_bytes());
+        zip.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        zip.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&[0; 12]);
+        zip.extend_from_slice(&0u32.to_le_bytes());
+        zip.extend_from_slice(name.as_bytes());
+        let central_len = zip.len() - central;
+
+        zip.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        zip.extend_from_slice(&[0, 0, 0, 0, 1, 0, 1, 0]);
+        zip.extend_from_slice(&(central_len as u32).to_le_bytes());
+        zip.extend_from_slice(&(central as u32).to_le_bytes());
+        zip.extend_from_slice(&0u16.to_le_bytes());
+        zip
+    }
+
+    #[test]
+    fn chatgpt_flattens_to_the_current_node() {
+        let export = chatgpt_export();
+        let convs = normalize(
+            ExportFormat::Chatgpt,
+            export.as_array().unwrap(),
+            Path::new("/data/imports/chatgpt-export/export.zip"),
+        );
+        assert_eq!(convs.len(), 1);
+        let conv = &convs[0];
+        assert_eq!(conv.agent_slug, "chatgpt");
+        assert_eq!(
+            conv.source_path,
+            PathBuf::from("/data/imports/chatgpt-export/export.zip/conv-1")
+        );
+        let contents: Vec<&str> = conv.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            [
+                "How should retries back off?",
+                "Use exponential backoff with jitter."
+            ]
+        );
+
+        let branches = conv.metadata["branches"].as_array().unwrap();
+        assert_eq!(branches.len(), 1);
+        assert_eq!(branches[0]["fork_after"], 0);
+        assert_eq!(branches[0]["messages"][0]["content"], "Use a fixed delay.");
+    }
+
+    #[test]
+    fn claude_follows_parent_links_to_the_current_leaf() {
+        let export = json!([{
+            "uuid": "c-1",
+            "name": "Flaky test",
+            "current_leaf_message_uuid": "m3",
+            "chat_messages": [
+                { "uuid": "m1", "sender": "human", "text": "Why is this test flaky?",
+                  "created_at": "2024-05-01T10:00:00Z", "parent_message_uuid": "00000000-0000-4000-8000-000000000000" },
+                { "uuid": "m2", "sender": "assistant", "text": "",
+                  "content": [{ "type": "text", "text": "It depends on wall-clock time." }],
+                  "created_at": "2024-05-01T10:00:05Z", "parent_message_uuid": "m1" },
+                { "uuid": "m3", "sender": "assistant", "text": "It shares a temp dir with another test.",
+                  "created_at": "2024-05-01T10:01:00Z", "parent_message_uuid": "m1" }
+            ]
+        }, {
+            "uuid": "c-2", "name": "", "chat_messages": []
+        }]);
+        let convs = normalize(
+            ExportFormat::Claude,
+            export.as_array().unwrap(),
+            Path::new("/x/export.json"),
+        );
+        assert_eq!(convs.len(), 1, "empty conversations are skipped");
+        let conv = &convs[0];
+        assert_eq!(conv.agent_slug, "claude_ai");
+        assert_eq!(conv.messages[0].role, "user");
+        assert_eq!(
+            conv.messages[1].content,
+            "It shares a temp dir with another test."
+        );
+        assert_eq!(
+            conv.metadata["branches"][0]["messages"][0]["content"],
+            "It depends on wall-clock time."
+        );
+        assert!(conv.started_at.unwrap() < conv.ended_at.unwrap());
+    }
+
+    #[test]
+    fn reads_conversations_json_from_a_zip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let data = serde_json::to_vec(&chatgpt_export()).unwrap();
+        let path = dir.path().join("export.zip");
+        fs::write(&path, zip_with("conversations.json", &data)).unwrap();
+
+        let convs = read_export(&path, None).unwrap();
+        assert_eq!(convs.len(), 1);
+        assert_eq!(convs[0].messages.len(), 2);
+
+        fs::write(&path, zip_with("user.json", b"{}")).unwrap();
+        assert!(read_export(&path, None).is_err());
+    }
+}
@@ -1,3 +1,4 @@
+pub mod redact;
 pub mod semantic;
 
 use std::collections::HashMap;
@@ -17,16 +18,20 @@ use crate::connectors::{
     Connector, ScanRoot, aider::AiderConnector, amp::AmpConnector, chatgpt::ChatGptConnector,
     claude_code::ClaudeCodeConnector, cline::ClineConnector, codex::CodexConnector,
     cursor::CursorConnector, factory::FactoryConnector, gemini::GeminiConnector,
-    opencode::OpenCodeConnector, pi_agent::PiAgentConnector,
+    generic::GenericConnector, opencode::OpenCodeConnector, pi_agent::PiAgentConnector,
 };
 use crate::search::tantivy::{TantivyIndex, index_dir, schema_hash_matches};
-use crate::search::vector_index::{ROLE_ASSISTANT, ROLE_SYSTEM, ROLE_TOOL, ROLE_USER};
+use crate::search::vector_index::{
+    Quantization, compact_vector_segments, segments_need_compaction, vector_index_path,
+};
 
 use crate::sources::config::{Platform, SourcesConfig};
 use crate::sources::provenance::{LOCAL_SOURCE_ID, Origin, Source};
+use crate::sources::scheduler::{SCHEDULER_TICK, SyncScheduler, changed_mirror_paths};
 use crate::sources::sync::path_to_safe_dirname;
 use crate::storage::sqlite::{SqliteStorage, StatsAggregator};
-use semantic::{EmbeddingInput, SemanticIndexer};
+use redact::{ConversationRedactions, RedactionCounts, SecretRedactor};
+use semantic::SemanticIndexer;
 
 #[derive(Debug, Clone)]
 pub enum ReindexCommand {
@@ -97,6 +102,12 @@ pub struct IndexOptions {
     pub semantic: bool,
     /// Embedder ID to use for semantic indexing (hash, fastembed).
     pub embedder: String,
+    /// Build an HNSW graph for approximate semantic search alongside the vector index.
+    pub ann: bool,
+    /// Encoding for stored vectors (f32, f16, int8, binary).
+    pub quantization: Quantization,
+    /// Redact secrets before storing conversations; remembered in the database once set.
+    pub redact_secrets: bool,
     pub progress: Option<Arc<IndexingProgress>>,
 }
 
@@ -281,6 +292,7 @@ fn run_streaming_consumer(
     t_index: &mut TantivyIndex,
     progress: &Option<Arc<IndexingProgress>>,
     needs_rebuild: bool,
+    redactor: Option<&SecretRedactor>,
 ) -> Result<Vec<String>> {
     use std::collections::HashMap;
 
@@ -338,7 +350,14 @@ fn run_streaming_consumer(
                 }
 
                 // Ingest the batch
-                ingest_batch(storage, t_index, &conversations, progress, needs_rebuild)?;
+                ingest_batch(
+                    storage,
+                    t_index,
+                    &conversations,
+                    progress,
+                    needs_rebuild,
+                    redactor,
+                )?;
 
                 // Periodic commit to make results visible incrementally (every 5s)
                 if last_commit.elapsed() >= Duration::from_secs(5) {
@@ -502,6 +521,7 @@ fn run_streaming_index(
     drop(tx);
 
     // Run consumer on main thread
+    let redactor = secret_redactor(opts)?;
     let discovered_names = run_streaming_consumer(
         rx,
         num_connectors,
@@ -509,6 +529,7 @@ fn run_streaming_index(
         t_index,
         &opts.progress,
         needs_rebuild,
+        redactor.as_ref(),
     )?;
 
     // Wait for all producer threads to complete
@@ -675,8 +696,16 @@ fn run_batch_index(
         p.current.store(0, Ordering::Relaxed);
     }
 
+    let redactor = secret_redactor(opts)?;
     for (name, convs, _discovered) in pending_batches {
-        ingest_batch(storage, t_index, &convs, &opts.progress, needs_rebuild)?;
+        ingest_batch(
+            storage,
+            t_index,
+            &convs,
+            &opts.progress,
+            needs_rebuild,
+            redactor.as_ref(),
+        )?;
         tracing::info!(
             connector = name,
             conversations = convs.len(),
@@ -688,10 +717,11 @@ fn run_batch_index(
 }
 
 pub fn run_index(
-    opts: IndexOptions,
+    mut opts: IndexOptions,
     event_channel: Option<(Sender<IndexerEvent>, Receiver<IndexerEvent>)>,
 ) -> Result<()> {
     let mut storage = SqliteStorage::open(&opts.db_path)?;
+    opts.redact_secrets = resolve_redact_secrets(&mut storage, opts.redact_secrets)?;
     let index_path = index_dir(&opts.data_dir)?;
 
     // Detect if we are rebuilding due to missing meta/schema mismatch/index corruption.
@@ -800,63 +830,56 @@ pub fn run_index(
         )?;
     }
 
+    let redactor = secret_redactor(&opts)?;
+    ingest_stored_imports(
+        &mut storage,
+        &mut t_index,
+        &opts.data_dir,
+        since_ts,
+        needs_rebuild,
+        redactor.as_ref(),
+    );
+    ingest_stored_bundles(
+        &mut storage,
+        &mut t_index,
+        &opts.data_dir,
+        since_ts,
+        needs_rebuild,
+        redactor.as_ref(),
+    );
+
     t_index.commit()?;
 
-    // Semantic indexing (if enabled)
-    if opts.semantic {
+    // Semantic indexing (if enabled). Incremental by default: only messages added since
+    // the last run are embedded into an append segment. A full reindex resets SQLite
+    // message ids, so it rebuilds the vector index as well.
+    let compacting = Arc::new(AtomicBool::new(false));
+    let mut compaction = None;
+    let semantic_indexer = if opts.semantic {
         tracing::info!(embedder = %opts.embedder, "starting semantic indexing");
 
-        let semantic_indexer = SemanticIndexer::new(&opts.embedder, Some(&opts.data_dir))?;
-
-        // Fetch all messages with metadata from SQLite
-        let raw_messages = storage.fetch_messages_for_embedding()?;
-        tracing::info!(
-            message_count = raw_messages.len(),
-            "fetched messages for embedding"
-        );
-
-        // Convert to EmbeddingInput format
-        let embedding_inputs: Vec<EmbeddingInput> = raw_messages
-            .into_iter()
-            .map(|msg| {
-                let role_u8 = match msg.role.as_str() {
-                    "user" => ROLE_USER,
-                    "agent" | "assistant" => ROLE_ASSISTANT,
-                    "system" => ROLE_SYSTEM,
-                    "tool" => ROLE_TOOL,
-                    _ => ROLE_USER, // default to user for unknown roles
-                };
-
-                EmbeddingInput {
-                    message_id: msg.message_id as u64,
-                    created_at_ms: msg.created_at.unwrap_or(0),
-                    agent_id: msg.agent_id as u32,
-                    workspace_id: msg.workspace_id.unwrap_or(0) as u32,
-                    source_id: msg.source_id_hash,
-                    role: role_u8,
-                    chunk_idx: 0,
-                    content: msg.content,
-                }
-            })
-            .collect();
-
-        // Generate embeddings
-        let embedded_messages = semantic_indexer.embed_messages(&embedding_inputs)?;
-        tracing::info!(
-            embedded_count = embedded_messages.len(),
-            "generated embeddings"
-        );
-
-        if !embedded_messages.is_empty() {
-            let vector_index = semantic_indexer.build_index(embedded_messages)?;
-            let index_path = semantic_indexer.save_index(&vector_index, &opts.data_dir)?;
+        let semantic_indexer = SemanticIndexer::new(&opts.embedder, Some(&opts.data_dir))?
+            .with_ann(opts.ann)
+            .with_quantization(opts.quantization);
+        let update = semantic_indexer.update_index(&storage, &opts.data_dir, opts.full)?;
+        if let Some(path) = &update.written {
             tracing::info!(
-                path = %index_path.display(),
+                path = %path.display(),
                 embedder = semantic_indexer.embedder_id(),
+                embedded = update.embedded,
+                reused = update.reused,
+                rebuilt = update.rebuilt,
                 "saved semantic vector index"
             );
         }
-    }
+        compaction = spawn_vector_compaction(
+            vector_index_path(&opts.data_dir, semantic_indexer.embedder_id()),
+            compacting.clone(),
+        );
+        Some(Arc::new(semantic_indexer))
+    } else {
+        None
+    };
 
     // Update last_scan_ts after successful scan and commit
     storage.set_last_scan_ts(scan_start_ts)?;
@@ -885,6 +908,16 @@ pub fn run_index(
         // Includes both local detected roots and all remote mirror roots
         let watch_roots = build_watch_roots(remote_roots.clone());
 
+        if opts.watch_once_paths.is_none() {
+            spawn_sync_scheduler(
+                opts.clone(),
+                state.clone(),
+                storage.clone(),
+                t_index.clone(),
+                semantic_indexer.clone(),
+            );
+        }
+
         watch_sources(
             opts.watch_once_paths.clone(),
             watch_roots.clone(),
@@ -918,22 +951,202 @@ pub fn run_index(
                         false,
                     );
                 }
+                if let Some(indexer) = &semantic_indexer {
+                    let updated =
+                        indexer.update_index_shared(&storage, &opts_clone.data_dir, false);
+                    match updated {
+                        Ok(_) => {
+                            // Fire and forget: the next batch checks the flag.
+                            let _ = spawn_vector_compaction(
+                                vector_index_path(&opts_clone.data_dir, indexer.embedder_id()),
+                                compacting.clone(),
+                            );
+                        }
+                        Err(e) => tracing::warn!("watch semantic update failed: {e}"),
+                    }
+                }
             },
         )?;
     }
 
+    if let Some(handle) = compaction {
+        let _ = handle.join();
+    }
+
     Ok(())
 }
 
+/// Sync sources on their `sync_schedule` while `--watch` runs, reindexing the
+/// mirrors each sync changed. `sources.toml` is re-read every tick.
+fn spawn_sync_scheduler(
+    opts: IndexOptions,
+    state: Arc<Mutex<HashMap<ConnectorKind, i64>>>,
+    storage: Arc<Mutex<SqliteStorage>>,
+    t_index: Arc<Mutex<TantivyIndex>>,
+    semantic_indexer: Option<Arc<SemanticIndexer>>,
+) {
+    if dotenvy::var("CASS_IGNORE_SOURCES_CONFIG").is_ok() {
+        return;
+    }
+    let spawned = thread::Builder::new()
+        .name("cass-sync-scheduler".into())
+        .spawn(move || {
+            let scheduler = SyncScheduler::new(&opts.data_dir);
+            loop {
+                match SourcesConfig::load() {
+                    Ok(config) => {
+                        let reports =
+                            scheduler.run_due(&config.sources, SqliteStorage::now_millis());
+                        let changed = changed_mirror_paths(&reports);
+                        if !changed.is_empty() {
+                            let reindexed = reindex_synced_mirrors(
+                                &opts,
+                                changed,
+                                state.clone(),
+                                storage.clone(),
+                                t_index.clone(),
+                            );
+                            if let Err(e) = reindexed {
+                                tracing::warn!("reindex after scheduled sync failed: {e}");
+                            } else if let Some(indexer) = &semantic_indexer {
+                                let updated =
+                                    indexer.update_index_shared(&storage, &opts.data_dir, false);
+                                if let Err(e) = updated {
+                                    tracing::warn!(
+                                        "semantic update after scheduled sync failed: {e}"
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => tracing::warn!("sync scheduler: failed to load sources config: {e}"),
+                }
+                thread::sleep(SCHEDULER_TICK);
+            }
+        });
+    if let Err(e) = spawned {
+        tracing::warn!("failed to spawn sync scheduler thread: {e}");
+    }
+}
+
+/// Reindex the mirror directories a sync just wrote to.
+///
+/// rsync keeps the remote modification times, so the watch state cannot tell
+/// new files apart by mtime: each changed mirror is rescanned in full. Roots
+/// are rebuilt so mirrors created by a source's first sync are included.
+fn reindex_synced_mirrors(
+    opts: &IndexOptions,
+    mirrors: Vec<PathBuf>,
+    state: Arc<Mutex<HashMap<ConnectorKind, i64>>>,
+    storage: Arc<Mutex<SqliteStorage>>,
+    t_index: Arc<Mutex<TantivyIndex>>,
+) -> Result<()> {
+    let remote_roots: Vec<ScanRoot> = {
+        let guard = storage
+            .lock()
+            .map_err(|_| anyhow::anyhow!("storage lock poisoned"))?;
+        sync_sources_config_to_db(&guard);
+        build_scan_roots(&guard, &opts.data_dir)
+    }
+    .into_iter()
+    .filter(|r| r.origin.is_remote())
+    .collect();
+    let roots = build_watch_roots(remote_roots);
+    reindex_paths(opts, mirrors, &roots, state, storage, t_index, true)
+}
+
+/// Index only the given mirror directories, as written by `cass sources sync`.
+///
+/// Falls back to a regular [`run_index`] when there is no index yet.
+pub fn reindex_mirrors(mut opts: IndexOptions, mirrors: Vec<PathBuf>) -> Result<()> {
+    let index_path = index_dir(&opts.data_dir)?;
+    if !index_path.join("meta.json").exists() || !opts.db_path.exists() {
+        opts.watch = false;
+        opts.watch_once_paths = None;
+        return run_index(opts, None);
+    }
+
+    let mut storage = SqliteStorage::open(&opts.db_path)?;
+    opts.redact_secrets = resolve_redact_secrets(&mut storage, opts.redact_secrets)?;
+    let t_index = TantivyIndex::open_or_create(&index_path)?;
+    let state = Arc::new(Mutex::new(load_watch_state(&opts.data_dir)));
+    reindex_synced_mirrors(
+        &opts,
+        mirrors,
+        state,
+        Arc::new(Mutex::new(storage)),
+        Arc::new(Mutex::new(t_index)),
+    )
+}
+
+/// Fold vector index append segments into the base file on a background thread once
+/// they are worth compacting. `running` keeps at most one compaction in flight.
+fn spawn_vector_compaction(base: PathBuf, running: Arc<AtomicBool>) -> Option<JoinHandle<()>> {
+    if !segments_need_compaction(&base) || running.swap(true, Ordering::AcqRel) {
+        return None;
+    }
+    let handle = thread::Builder::new()
+        .name("cass-vector-compact".into())
+        .spawn(move || {
+            match compact_vector_segments(&base) {
+                Ok(folded) => {
+                    tracing::info!(path = %base.display(), folded, "compacted vector segments");
+                }
+                Err(e) => {
+                    tracing::warn!(path = %base.display(), "vector compaction failed: {e}");
+                }
+            }
+            running.store(false, Ordering::Release);
+        });
+    match handle {
+        Ok(handle) => Some(handle),
+        Err(e) => {
+            tracing::warn!("failed to spawn vector compaction thread: {e}");
+            running.store(false, Ordering::Release);
+            None
+        }
+    }
+}
+
 fn ingest_batch(
     storage: &mut SqliteStorage,
     t_index: &mut TantivyIndex,
     convs: &[NormalizedConversation],
     progress: &Option<Arc<IndexingProgress>>,
     force_tantivy_reindex: bool,
+    redactor: Option<&SecretRedactor>,
 ) -> Result<()> {
+    // Redact before anything is written, so neither SQLite nor Tantivy sees the secrets
+    let redacted: Option<(Vec<NormalizedConversation>, Vec<ConversationRedactions>)> = redactor
+        .map(|redactor| {
+            let mut convs = convs.to_vec();
+            let redactions = convs
+                .iter_mut()
+                .map(|conv| redactor.redact_conversation(conv))
+                .collect();
+            (convs, redactions)
+        });
+    let convs = redacted
+        .as_ref()
+        .map_or(convs, |(convs, _)| convs.as_slice());
+
     // Use batched insert for better SQLite performance (single transaction)
-    persist::persist_conversations_batched(storage, t_index, convs, force_tantivy_reindex)?;
+    let outcomes =
+        persist::persist_conversations_batched(storage, t_index, convs, force_tantivy_reindex)?;
+
+    if let Some((_, redactions)) = &redacted {
+        let counts: Vec<(i64, RedactionCounts)> = convs
+            .iter()
+            .zip(redactions)
+            .zip(&outcomes)
+            .filter_map(|((conv, redactions), outcome)| {
+                let is_new = outcome.inserted_indices.len() == conv.messages.len();
+                let counts = redactions.for_inserted(&outcome.inserted_indices, is_new);
+                (!counts.is_empty()).then_some((outcome.conversation_id, counts))
+            })
+            .collect();
+        storage.add_conversation_redactions(&counts)?;
+    }
 
     // Aggregate and update daily_stats in a single batch (kzxu fix)
     // This prevents N×4 database writes by aggregating in memory first
@@ -995,6 +1208,7 @@ pub fn get_connector_factories() -> Vec<(&'static str, fn() -> Box<dyn Connector
         ("chatgpt", || Box::new(ChatGptConnector::new())),
         ("pi_agent", || Box::new(PiAgentConnector::new())),
         ("factory", || Box::new(FactoryConnector::new())),
+        ("generic", || Box::new(GenericConnector::new())),
     ]
 }
 
@@ -1045,6 +1259,7 @@ impl ConnectorKind {
             "chatgpt" => Some(Self::ChatGpt),
             "pi_agent" => Some(Self::PiAgent),
             "factory" => Some(Self::Factory),
+            "generic" => Some(Self::Generic),
             _ => None,
         }
     }
@@ -1064,6 +1279,7 @@ impl ConnectorKind {
             Self::ChatGpt => Box::new(ChatGptConnector::new()),
             Self::PiAgent => Box::new(PiAgentConnector::new()),
             Self::Factory => Box::new(FactoryConnector::new()),
+            Self::Generic => Box::new(GenericConnector::new()),
         }
     }
 }
@@ -1158,6 +1374,132 @@ fn watch_sources<F: Fn(Vec<PathBuf>, &[(ConnectorKind, ScanRoot)], bool) + Send
     Ok(())
 }
 
+/// Decide whether this run redacts secrets at ingest.
+///
+/// `--redact-secrets` turns redaction on and records it in the database, so
+/// later runs (including `--watch`) keep redacting. `CASS_REDACT_SECRETS`
+/// overrides the stored setting for one run.
+fn resolve_redact_secrets(storage: &mut SqliteStorage, requested: bool) -> Result<bool> {
+    if requested {
+        storage.set_redact_secrets(true)?;
+    }
+    if let Ok(value) = dotenvy::var("CASS_REDACT_SECRETS") {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => return Ok(true),
+            "0" | "false" | "no" | "off" => return Ok(false),
+            other => {
+                tracing::warn!(value = other, "ignoring unrecognized CASS_REDACT_SECRETS");
+            }
+        }
+    }
+    Ok(requested || storage.get_redact_secrets()?)
+}
+
+fn secret_redactor(opts: &IndexOptions) -> Result<Option<SecretRedactor>> {
+    if !opts.redact_secrets {
+        return Ok(None);
+    }
+    SecretRedactor::from_env().map(Some)
+}
+
+/// Redactor for writers outside `cass index`, honoring the stored setting.
+pub(crate) fn stored_secret_redactor(
+    storage: &mut SqliteStorage,
+) -> Result<Option<SecretRedactor>> {
+    if !resolve_redact_secrets(storage, false)? {
+        return Ok(None);
+    }
+    SecretRedactor::from_env().map(Some)
+}
+
+/// Ingest conversations that did not come from a connector scan, stamped with `origin`.
+pub(crate) fn ingest_with_origin(
+    storage: &mut SqliteStorage,
+    t_index: &mut TantivyIndex,
+    mut convs: Vec<NormalizedConversation>,
+    origin: &Origin,
+    force_tantivy_reindex: bool,
+    redactor: Option<&SecretRedactor>,
+) -> Result<()> {
+    for conv in &mut convs {
+        inject_provenance(conv, origin);
+    }
+    ingest_batch(
+        storage,
+        t_index,
+        &convs,
+        &None,
+        force_tantivy_reindex,
+        redactor,
+    )
+}
+
+/// Re-read the exports kept by `cass import`, so rebuilds and `--full` runs keep them.
+fn ingest_stored_imports(
+    storage: &mut SqliteStorage,
+    t_index: &mut TantivyIndex,
+    data_dir: &Path,
+    since_ts: Option<i64>,
+    force_tantivy_reindex: bool,
+    redactor: Option<&SecretRedactor>,
+) {
+    for stored in crate::import::stored_exports(data_dir) {
+        if since_ts.is_some_and(|since| stored.modified_ms < since) {
+            continue;
+        }
+        let result = crate::import::read_export(&stored.path, None).and_then(|convs| {
+            ingest_with_origin(
+                storage,
+                t_index,
+                convs,
+                &Origin::import(&stored.source_id),
+                force_tantivy_reindex,
+                redactor,
+            )
+        });
+        if let Err(e) = result {
+            tracing::warn!(
+                source_id = %stored.source_id,
+                path = %stored.path.display(),
+                "re-reading imported export failed: {e:#}"
+            );
+        }
+    }
+}
+
+/// Re-read the bundles kept by `cass bundle import`, so rebuilds and `--full` runs keep them.
+fn ingest_stored_bundles(
+    storage: &mut SqliteStorage,
+    t_index: &mut TantivyIndex,
+    data_dir: &Path,
+    since_ts: Option<i64>,
+    force_tantivy_reindex: bool,
+    redactor: Option<&SecretRedactor>,
+) {
+    for stored in crate::bundle::stored_bundles(data_dir) {
+        if since_ts.is_some_and(|since| stored.modified_ms < since) {
+            continue;
+        }
+        let result = crate::bundle::read_stored(&stored.path).and_then(|(header, convs)| {
+            ingest_with_origin(
+                storage,
+                t_index,
+                convs,
+                &Origin::bundle(&stored.source_id, header.exported_from),
+                force_tantivy_reindex,
+                redactor,
+            )
+        });
+        if let Err(e) = result {
+            tracing::warn!(
+                source_id = %stored.source_id,
+                path = %stored.path.display(),
+                "re-reading imported bundle failed: {e:#}"
+            );
+        }
+    }
+}
+
 fn reset_storage(storage: &mut SqliteStorage) -> Result<()> {
     // Wrap in transaction to ensure atomic reset - if any DELETE fails,
     // all changes are rolled back to prevent inconsistent state
@@ -1165,12 +1507,14 @@ fn reset_storage(storage: &mut SqliteStorage) -> Result<()> {
         "BEGIN TRANSACTION;
          DELETE FROM fts_messages;
          DELETE FROM snippets;
+         DELETE FROM message_usage;
          DELETE FROM messages;
          DELETE FROM conversations;
          DELETE FROM agents;
          DELETE FROM workspaces;
          DELETE FROM tags;
          DELETE FROM conversation_tags;
+         DELETE FROM conversation_redactions;
          DELETE FROM meta WHERE key = 'last_scan_ts';
          COMMIT;",
     )?;
@@ -1193,6 +1537,7 @@ fn reindex_paths(
     if triggers.is_empty() {
         return Ok(());
     }
+    let redactor = secret_redactor(opts)?;
 
     for (kind, root, ts) in triggers {
         let conn = kind.create_connector();
@@ -1266,7 +1611,14 @@ fn reindex_paths(
                 .lock()
                 .map_err(|_| anyhow::anyhow!("index lock poisoned"))?;
 
-            ingest_batch(&mut storage, &mut t_index, &convs, &opts.progress, false)?;
+            ingest_batch(
+                &mut storage,
+                &mut t_index,
+                &convs,
+                &opts.progress,
+                false,
+                redactor.as_ref(),
+            )?;
 
             // Commit to Tantivy immediately to ensure index consistency before advancing watch state.
             t_index.commit()?;
@@ -1317,6 +1669,8 @@ enum ConnectorKind {
     PiAgent,
     #[serde(rename = "fa", alias = "Factory")]
     Factory,
+    #[serde(rename = "gn", alias = "Generic")]
+    Generic,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Default)]
@@ -1413,18 +1767,26 @@ fn sync_sources_config_to_db(storage: &SqliteStorage) {
         }
     };
 
-    for source in config.remote_sources() {
+    for source in config
+        .sources
+        .iter()
+        .filter(|s| s.is_remote() || s.is_path())
+    {
         let platform = source.platform.map(|p| match p {
             Platform::Macos => "macos".to_string(),
             Platform::Linux => "linux".to_string(),
             Platform::Windows => "windows".to_string(),
         });
 
-        let config_json = serde_json::json!({
+        let mut config_json = serde_json::json!({
             "paths": source.paths.clone(),
             "path_mappings": source.path_mappings.clone(),
             "sync_schedule": source.sync_schedule,
         });
+        if source.is_path() {
+            config_json["root"] = serde_json::json!(source.root);
+            config_json["mirror"] = serde_json::json!(source.mirror);
+        }
 
         let record = Source {
             id: source.name.clone(),
@@ -1448,9 +1810,10 @@ fn sync_sources_config_to_db(storage: &SqliteStorage) {
 
 /// Build a list of scan roots for multi-root indexing.
 ///
-/// This function collects both:
+/// This function collects:
 /// 1. Local default roots (from watch_roots() or standard locations)
 /// 2. Remote mirror roots (from registered sources in the database)
+/// 3. Directories of path sources that are indexed in place
 ///
 /// Part of P2.2 - Indexer multi-root orchestration.
 pub fn build_scan_roots(storage: &SqliteStorage, data_dir: &Path) -> Vec<ScanRoot> {
@@ -1465,8 +1828,33 @@ pub fn build_scan_roots(storage: &SqliteStorage, data_dir: &Path) -> Vec<ScanRoo
     if dotenvy::var("CASS_IGNORE_SOURCES_CONFIG").is_err()
         && let Ok(config) = SourcesConfig::load()
     {
-        let remotes: Vec<_> = config.remote_sources().collect();
-        if !remotes.is_empty() {
+        let direct: Vec<_> = config
+            .sources
+            .iter()
+            .filter(|s| s.is_path() && !s.mirror)
+            .collect();
+        for source in &direct {
+            for path in &source.paths {
+                let Some(dir) = source.resolve_path(path) else {
+                    continue;
+                };
+                if !dir.exists() {
+                    tracing::debug!(
+                        source = %source.name,
+                        path = %dir.display(),
+                        "path source directory not available"
+                    );
+                    continue;
+                }
+                let mut scan_root =
+                    ScanRoot::remote(dir, Origin::path(&source.name), source.platform);
+                scan_root.workspace_rewrites = source.path_mappings.clone();
+                roots.push(scan_root);
+            }
+        }
+
+        let remotes: Vec<_> = config.synced_sources().collect();
+        if !remotes.is_empty() || !direct.is_empty() {
             for source in remotes {
                 let origin = Origin {
                     source_id: source.name.clone(),
@@ -1721,7 +2109,9 @@ pub mod persist {
     use anyhow::Result;
 
     use crate::connectors::NormalizedConversation;
-    use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole, Snippet};
+    use crate::model::types::{
+        Agent, AgentKind, Conversation, Message, MessageRole, Snippet, TokenUsage,
+    };
     use crate::search::tantivy::TantivyIndex;
     use crate::storage::sqlite::{IndexingCache, InsertOutcome, SqliteStorage};
 
@@ -1754,6 +2144,13 @@ pub mod persist {
     pub fn map_to_internal(conv: &NormalizedConversation) -> Conversation {
         // Extract provenance from metadata (P2.2)
         let (source_id, origin_host) = extract_provenance(&conv.metadata);
+        // Tokens the agent reported, when its connector records usage
+        let reported_tokens = conv
+            .messages
+            .iter()
+            .filter_map(|m| TokenUsage::from_extra(&m.extra))
+            .map(|u| u.total())
+            .reduce(|a, b| a + b);
 
         Conversation {
             id: None,
@@ -1764,7 +2161,7 @@ pub mod persist {
             source_path: conv.source_path.clone(),
             started_at: conv.started_at,
             ended_at: conv.ended_at,
-            approx_tokens: None,
+            approx_tokens: reported_tokens,
             metadata_json: conv.metadata.clone(),
             messages: conv
                 .messages
@@ -1847,9 +2244,9 @@ pub mod persist {
         t_index: &mut TantivyIndex,
         convs: &[NormalizedConversation],
         force_tantivy_reindex: bool,
-    ) -> Result<()> {
+    ) -> Result<Vec<InsertOutcome>> {
         if convs.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         let cache_enabled = IndexingCache::is_enabled();
@@ -1923,7 +2320,7 @@ pub mod persist {
             }
         }
 
-        Ok(())
+        Ok(outcomes)
     }
 
     fn map_role(role: &str) -> MessageRole {
@@ -2261,6 +2658,9 @@ mod tests {
             data_dir: data_dir.clone(),
             semantic: false,
             embedder: "fastembed".to_string(),
+            ann: false,
+            quantization: Quantization::F32,
+            redact_secrets: false,
             progress: None,
             watch_once_paths: None,
         };
@@ -2375,6 +2775,9 @@ CREATE VIRTUAL TABLE fts_messages USING fts5(
             data_dir: data_dir.clone(),
             semantic: false,
             embedder: "fastembed".to_string(),
+            ann: false,
+            quantization: Quantization::F32,
+            redact_secrets: false,
             progress: Some(progress.clone()),
         };
 
@@ -1,16 +1,21 @@
+use std::collections::{HashMap, HashSet};
 use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
-use anyhow::{Result, bail};
+use anyhow::{Result, anyhow, bail};
 use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
 
-use crate::search::canonicalize::{canonicalize_for_embedding, content_hash};
+use crate::search::ann_index::{build_hnsw_for, hnsw_index_path};
+use crate::search::canonicalize::{chunk_for_embedding, content_hash};
 use crate::search::embedder::Embedder;
 use crate::search::fastembed_embedder::FastEmbedder;
 use crate::search::hash_embedder::HashEmbedder;
 use crate::search::vector_index::{
-    Quantization, ROLE_USER, VectorEntry, VectorIndex, vector_index_path,
+    Quantization, ROLE_ASSISTANT, ROLE_SYSTEM, ROLE_TOOL, ROLE_USER, VectorEntry, VectorIndex,
+    replace_vector_index, save_vector_segment, vector_index_path, vector_segment_paths,
 };
+use crate::storage::sqlite::{MessageForEmbedding, SqliteStorage};
 
 #[derive(Debug, Clone)]
 pub struct EmbeddingInput {
@@ -39,6 +44,43 @@ impl EmbeddingInput {
     }
 }
 
+impl From<MessageForEmbedding> for EmbeddingInput {
+    fn from(msg: MessageForEmbedding) -> Self {
+        let role = match msg.role.as_str() {
+            "user" => ROLE_USER,
+            "agent" | "assistant" => ROLE_ASSISTANT,
+            "system" => ROLE_SYSTEM,
+            "tool" => ROLE_TOOL,
+            _ => ROLE_USER, // default to user for unknown roles
+        };
+        Self {
+            message_id: msg.message_id as u64,
+            created_at_ms: msg.created_at.unwrap_or(0),
+            agent_id: msg.agent_id as u32,
+            workspace_id: msg.workspace_id.unwrap_or(0) as u32,
+            source_id: msg.source_id_hash,
+            role,
+            chunk_idx: 0,
+            content: msg.content,
+        }
+    }
+}
+
+/// Outcome of [`SemanticIndexer::update_index`].
+#[derive(Debug, Clone, Default)]
+pub struct SemanticUpdate {
+    /// Chunks run through the embedder.
+    pub embedded: usize,
+    /// Chunks whose content hash matched an indexed vector, copied instead of re-embedded.
+    pub reused: usize,
+    /// Whether the base index was rewritten from scratch.
+    pub rebuilt: bool,
+    /// File written by this update (base index or new segment), if any.
+    pub written: Option<PathBuf>,
+    /// HNSW graph (re)built by this update, if any.
+    pub ann: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone)]
 pub struct EmbeddedMessage {
     pub message_id: u64,
@@ -68,9 +110,46 @@ impl EmbeddedMessage {
     }
 }
 
+/// One canonicalized chunk of a message, ready to embed.
+struct PreparedChunk<'a> {
+    msg: &'a EmbeddingInput,
+    chunk_idx: u8,
+    canonical: String,
+    hash: [u8; 32],
+}
+
+fn prepare_chunks(msg: &EmbeddingInput) -> impl Iterator<Item = PreparedChunk<'_>> {
+    chunk_for_embedding(&msg.content)
+        .into_iter()
+        .enumerate()
+        .map(move |(i, canonical)| PreparedChunk {
+            msg,
+            chunk_idx: msg.chunk_idx.saturating_add(i as u8),
+            hash: content_hash(&canonical),
+            canonical,
+        })
+}
+
+fn progress_bar(len: usize, unit: &str) -> ProgressBar {
+    let pb = ProgressBar::new(len as u64);
+    if std::io::stderr().is_terminal() {
+        let template =
+            format!("{{spinner:.green}} [{{bar:40.cyan/blue}}] {{pos}}/{{len}} {unit} embedded");
+        let style = ProgressStyle::default_bar()
+            .template(&template)
+            .unwrap_or_else(|_| ProgressStyle::default_bar());
+        pb.set_style(style);
+    } else {
+        pb.set_draw_target(ProgressDrawTarget::hidden());
+    }
+    pb
+}
+
 pub struct SemanticIndexer {
     embedder: Box<dyn Embedder>,
     batch_size: usize,
+    build_ann: bool,
+    quantization: Quantization,
 }
 
 impl SemanticIndexer {
@@ -92,9 +171,25 @@ impl SemanticIndexer {
         Ok(Self {
             embedder,
             batch_size: 32,
+            build_ann: false,
+            quantization: Quantization::F32,
         })
     }
 
+    /// Also maintain an HNSW graph next to the base vector index. Once a graph
+    /// exists it is kept up to date whether or not this is set.
+    pub fn with_ann(mut self, build_ann: bool) -> Self {
+        self.build_ann = build_ann;
+        self
+    }
+
+    /// Encoding for vectors written by this indexer. An existing index stored with a
+    /// different encoding is re-encoded from its stored vectors on the next update.
+    pub fn with_quantization(mut self, quantization: Quantization) -> Self {
+        self.quantization = quantization;
+        self
+    }
+
     pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self> {
         if batch_size == 0 {
             bail!("batch_size must be > 0");
@@ -115,104 +210,100 @@ impl SemanticIndexer {
         self.embedder.dimension()
     }
 
+    /// Embed messages, splitting long ones into overlapping chunks (one
+    /// [`EmbeddedMessage`] per chunk, numbered from the input's `chunk_idx`).
     pub fn embed_messages(&self, messages: &[EmbeddingInput]) -> Result<Vec<EmbeddedMessage>> {
         if messages.is_empty() {
             return Ok(Vec::new());
         }
 
-        let show_progress = std::io::stderr().is_terminal();
-        let pb = ProgressBar::new(messages.len() as u64);
-        if show_progress {
-            let style = ProgressStyle::default_bar()
-                .template("{spinner:.green} [{bar:40.cyan/blue}] {pos}/{len} messages embedded")
-                .unwrap_or_else(|_| ProgressStyle::default_bar());
-            pb.set_style(style);
-        } else {
-            pb.set_draw_target(ProgressDrawTarget::hidden());
-        }
-
-        struct Prepared<'a> {
-            msg: &'a EmbeddingInput,
-            canonical: String,
-            hash: [u8; 32],
-        }
-
+        let pb = progress_bar(messages.len(), "messages");
         let mut embeddings = Vec::with_capacity(messages.len());
-        let mut batch: Vec<Prepared> = Vec::with_capacity(self.batch_size);
-
-        let flush_batch = |batch: &mut Vec<Prepared>,
-                           embeddings: &mut Vec<EmbeddedMessage>,
-                           pb: &ProgressBar,
-                           embedder: &dyn Embedder|
-         -> Result<()> {
-            if batch.is_empty() {
-                return Ok(());
+        let mut batch: Vec<PreparedChunk> = Vec::with_capacity(self.batch_size);
+        for msg in messages {
+            for prepared in prepare_chunks(msg) {
+                batch.push(prepared);
+                if batch.len() >= self.batch_size {
+                    self.flush_batch(&mut batch, &mut embeddings)?;
+                }
             }
+            pb.inc(1);
+        }
+        self.flush_batch(&mut batch, &mut embeddings)?;
 
-            let texts: Vec<&str> = batch.iter().map(|p| p.canonical.as_str()).collect();
-            let vectors = embedder
-                .embed_batch(&texts)
-                .map_err(|e| anyhow::anyhow!("embedding failed: {e}"))?;
+        pb.finish_with_message("Embedding complete");
+        Ok(embeddings)
+    }
 
-            if vectors.len() != batch.len() {
-                bail!(
-                    "embedder returned {} embeddings for {} inputs",
-                    vectors.len(),
-                    batch.len()
-                );
-            }
+    /// Embed already-canonicalized chunks in batches.
+    fn embed_chunks(&self, chunks: Vec<PreparedChunk>) -> Result<Vec<EmbeddedMessage>> {
+        if chunks.is_empty() {
+            return Ok(Vec::new());
+        }
 
-            for (prepared, vector) in batch.iter().zip(vectors.into_iter()) {
-                if vector.len() != embedder.dimension() {
-                    bail!(
-                        "embedding dimension mismatch: expected {}, got {}",
-                        embedder.dimension(),
-                        vector.len()
-                    );
-                }
-                embeddings.push(EmbeddedMessage {
-                    message_id: prepared.msg.message_id,
-                    created_at_ms: prepared.msg.created_at_ms,
-                    agent_id: prepared.msg.agent_id,
-                    workspace_id: prepared.msg.workspace_id,
-                    source_id: prepared.msg.source_id,
-                    role: prepared.msg.role,
-                    chunk_idx: prepared.msg.chunk_idx,
-                    content_hash: prepared.hash,
-                    embedding: vector,
-                });
+        let pb = progress_bar(chunks.len(), "chunks");
+        let mut embeddings = Vec::with_capacity(chunks.len());
+        let mut batch: Vec<PreparedChunk> = Vec::with_capacity(self.batch_size);
+        for prepared in chunks {
+            batch.push(prepared);
+            if batch.len() >= self.batch_size {
+                pb.inc(batch.len() as u64);
+                self.flush_batch(&mut batch, &mut embeddings)?;
             }
+        }
+        pb.inc(batch.len() as u64);
+        self.flush_batch(&mut batch, &mut embeddings)?;
 
-            pb.inc(batch.len() as u64);
-            batch.clear();
-            Ok(())
-        };
+        pb.finish_with_message("Embedding complete");
+        Ok(embeddings)
+    }
 
-        for msg in messages {
-            let canonical = canonicalize_for_embedding(&msg.content);
-            if canonical.is_empty() {
-                pb.inc(1);
-                continue;
-            }
+    fn flush_batch(
+        &self,
+        batch: &mut Vec<PreparedChunk>,
+        embeddings: &mut Vec<EmbeddedMessage>,
+    ) -> Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
 
-            let hash = content_hash(&canonical);
-            batch.push(Prepared {
-                msg,
-                canonical,
-                hash,
-            });
+        let embedder = self.embedder.as_ref();
+        let texts: Vec<&str> = batch.iter().map(|p| p.canonical.as_str()).collect();
+        let vectors = embedder
+            .embed_batch(&texts)
+            .map_err(|e| anyhow::anyhow!("embedding failed: {e}"))?;
 
-            if batch.len() >= self.batch_size {
-                flush_batch(&mut batch, &mut embeddings, &pb, self.embedder.as_ref())?;
-            }
+        if vectors.len() != batch.len() {
+            bail!(
+                "embedder returned {} embeddings for {} inputs",
+                vectors.len(),
+                batch.len()
+            );
         }
 
-        if !batch.is_empty() {
-            flush_batch(&mut batch, &mut embeddings, &pb, self.embedder.as_ref())?;
+        for (prepared, vector) in batch.iter().zip(vectors.into_iter()) {
+            if vector.len() != embedder.dimension() {
+                bail!(
+                    "embedding dimension mismatch: expected {}, got {}",
+                    embedder.dimension(),
+                    vector.len()
+                );
+            }
+            embeddings.push(EmbeddedMessage {
+                message_id: prepared.msg.message_id,
+                created_at_ms: prepared.msg.created_at_ms,
+                agent_id: prepared.msg.agent_id,
+                workspace_id: prepared.msg.workspace_id,
+                source_id: prepared.msg.source_id,
+                role: prepared.msg.role,
+                chunk_idx: prepared.chunk_idx,
+                content_hash: prepared.hash,
+                embedding: vector,
+            });
         }
 
-        pb.finish_with_message("Embedding complete");
-        Ok(embeddings)
+        batch.clear();
+        Ok(())
     }
 
     pub fn build_index<I>(&self, embedded_messages: I) -> Result<VectorIndex>
@@ -227,7 +318,7 @@ impl SemanticIndexer {
             self.embedder_id(),
             "1.0",
             self.embedder_dimension(),
-            Quantization::F32,
+            self.quantization,
             entries,
         )
     }
@@ -253,16 +344,263 @@ impl SemanticIndexer {
         if let Some(parent) = index_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        index.save(&index_path)?;
+        replace_vector_index(index, &index_path)?;
         Ok(index_path)
     }
+
+    /// Bring the vector index for this embedder up to date with SQLite.
+    ///
+    /// Only messages newer than the highest indexed message id are considered, and
+    /// of those only content not already in the index is embedded; the new rows are
+    /// written as an append segment. With `rebuild` (or when no usable base index
+    /// exists) every message is indexed into a fresh base and old segments are dropped.
+    pub fn update_index(
+        &self,
+        storage: &SqliteStorage,
+        data_dir: &Path,
+        rebuild: bool,
+    ) -> Result<SemanticUpdate> {
+        self.update_index_with(data_dir, rebuild, |after_id| {
+            storage.fetch_messages_for_embedding_after(after_id)
+        })
+    }
+
+    /// [`Self::update_index`] for storage shared with the indexing threads of
+    /// `--watch`: the lock is held only while the pending messages are read, so
+    /// embedding them does not stall the next lexical batch.
+    pub fn update_index_shared(
+        &self,
+        storage: &Mutex<SqliteStorage>,
+        data_dir: &Path,
+        rebuild: bool,
+    ) -> Result<SemanticUpdate> {
+        self.update_index_with(data_dir, rebuild, |after_id| {
+            storage
+                .lock()
+                .map_err(|_| anyhow!("lock storage"))?
+                .fetch_messages_for_embedding_after(after_id)
+        })
+    }
+
+    /// Shared body of the `update_index` variants; `fetch` returns the messages
+    /// with an id above its argument.
+    fn update_index_with(
+        &self,
+        data_dir: &Path,
+        rebuild: bool,
+        fetch: impl FnOnce(i64) -> Result<Vec<MessageForEmbedding>>,
+    ) -> Result<SemanticUpdate> {
+        let base_path = vector_index_path(data_dir, self.embedder_id());
+        let existing = if rebuild || !base_path.is_file() {
+            Vec::new()
+        } else {
+            self.load_parts(&base_path)
+        };
+        let rebuild = existing.is_empty();
+
+        let mut known: HashSet<(u64, u8)> = HashSet::new();
+        let mut by_hash: HashMap<[u8; 32], (usize, usize)> = HashMap::new();
+        let mut watermark = 0u64;
+        for (part_idx, part) in existing.iter().enumerate() {
+            for (row_idx, row) in part.rows().iter().enumerate() {
+                known.insert((row.message_id, row.chunk_idx));
+                by_hash
+                    .entry(row.content_hash)
+                    .or_insert((part_idx, row_idx));
+                watermark = watermark.max(row.message_id);
+            }
+        }
+
+        let raw_messages = fetch(if rebuild { 0 } else { watermark as i64 })?;
+        tracing::info!(
+            message_count = raw_messages.len(),
+            after_message_id = watermark,
+            rebuild,
+            "fetched messages for embedding"
+        );
+
+        let inputs: Vec<EmbeddingInput> = raw_messages
+            .into_iter()
+            .map(EmbeddingInput::from)
+            .filter(|msg| !known.contains(&(msg.message_id, msg.chunk_idx)))
+            .collect();
+
+        // Switching quantization rewrites the base from the stored vectors rather
+        // than re-embedding everything.
+        let requantize = existing
+            .first()
+            .is_some_and(|base| base.header().quantization != self.quantization);
+        let mut entries = if requantize {
+            tracing::info!(
+                from = existing[0].header().quantization.as_str(),
+                to = self.quantization.as_str(),
+                "re-encoding vector index"
+            );
+            VectorIndex::merged_entries(&existing)?
+        } else {
+            Vec::new()
+        };
+        let mut to_embed = Vec::new();
+        let mut update = SemanticUpdate {
+            rebuilt: rebuild,
+            ..SemanticUpdate::default()
+        };
+        for prepared in inputs.iter().flat_map(prepare_chunks) {
+            if let Some(&(part_idx, row_idx)) = by_hash.get(&prepared.hash) {
+                let part = &existing[part_idx];
+                let msg = prepared.msg;
+                entries.push(VectorEntry {
+                    message_id: msg.message_id,
+                    created_at_ms: msg.created_at_ms,
+                    agent_id: msg.agent_id,
+                    workspace_id: msg.workspace_id,
+                    source_id: msg.source_id,
+                    role: msg.role,
+                    chunk_idx: prepared.chunk_idx,
+                    content_hash: prepared.hash,
+                    vector: part.vector_at_f32(&part.rows()[row_idx])?,
+                });
+                update.reused += 1;
+            } else {
+                to_embed.push(prepared);
+            }
+        }
+        drop(existing);
+
+        let embedded = self.embed_chunks(to_embed)?;
+        update.embedded = embedded.len();
+        entries.extend(embedded.into_iter().map(EmbeddedMessage::into_vector_entry));
+        tracing::info!(
+            embedded = update.embedded,
+            reused = update.reused,
+            "generated embeddings"
+        );
+
+        if entries.is_empty() {
+            update.ann = self.refresh_ann(&base_path, false)?;
+            return Ok(update);
+        }
+
+        let index = VectorIndex::build(
+            self.embedder_id(),
+            "1.0",
+            self.embedder_dimension(),
+            self.quantization,
+            entries,
+        )?;
+        if let Some(parent) = base_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let rewrite_base = rebuild || requantize;
+        update.written = Some(if rewrite_base {
+            replace_vector_index(&index, &base_path)?;
+            base_path.clone()
+        } else {
+            save_vector_segment(&index, &base_path)?
+        });
+        update.ann = self.refresh_ann(&base_path, rewrite_base)?;
+        Ok(update)
+    }
+
+    /// (Re)build the HNSW graph for the base index when it is wanted and missing, or
+    /// when the base was just rewritten. Rows in append segments are not in the graph;
+    /// searches scan them exactly until compaction rebuilds it.
+    fn refresh_ann(&self, base_path: &Path, base_rewritten: bool) -> Result<Option<PathBuf>> {
+        let ann_path = hnsw_index_path(base_path);
+        let exists = ann_path.is_file();
+        let stale = base_rewritten && exists;
+        if !base_path.is_file() || !(stale || (self.build_ann && !exists)) {
+            return Ok(None);
+        }
+        let path = build_hnsw_for(base_path)?;
+        tracing::info!(path = %path.display(), "built HNSW graph");
+        Ok(Some(path))
+    }
+
+    /// Load the base index and its segments without merging them; an index built by a
+    /// different embedder or dimension yields nothing so the caller rebuilds.
+    fn load_parts(&self, base_path: &Path) -> Vec<VectorIndex> {
+        let mut parts = Vec::new();
+        let paths = std::iter::once(base_path.to_path_buf()).chain(vector_segment_paths(base_path));
+        for path in paths {
+            let part = match VectorIndex::load_mapped(&path) {
+                Ok(part) => part,
+                // Folded into the base by a concurrent compaction.
+                Err(_) if path != base_path && !path.exists() => continue,
+                Err(err) => {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %err,
+                        "unreadable vector index; rebuilding"
+                    );
+                    return Vec::new();
+                }
+            };
+            let header = part.header();
+            if header.embedder_id != self.embedder_id()
+                || header.dimension as usize != self.embedder_dimension()
+            {
+                tracing::info!(
+                    path = %path.display(),
+                    "vector index embedder changed; rebuilding"
+                );
+                return Vec::new();
+            }
+            parts.push(part);
+        }
+        parts
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
     use tempfile::tempdir;
 
+    fn insert_session(storage: &mut SqliteStorage, source_path: &str, contents: &[&str]) {
+        let agent_id = storage
+            .ensure_agent(&Agent {
+                id: None,
+                slug: "codex".into(),
+                name: "Codex".into(),
+                version: None,
+                kind: AgentKind::Cli,
+            })
+            .unwrap();
+        let conv = Conversation {
+            id: None,
+            agent_slug: "codex".into(),
+            workspace: None,
+            external_id: Some(source_path.to_string()),
+            title: None,
+            source_path: PathBuf::from(source_path),
+            started_at: Some(1),
+            ended_at: None,
+            approx_tokens: None,
+            metadata_json: serde_json::json!({}),
+            messages: contents
+                .iter()
+                .enumerate()
+                .map(|(idx, content)| Message {
+                    id: None,
+                    idx: idx as i64,
+                    role: MessageRole::User,
+                    author: None,
+                    created_at: Some(idx as i64),
+                    content: (*content).to_string(),
+                    extra_json: serde_json::json!({}),
+                    snippets: Vec::new(),
+                })
+                .collect(),
+            source_id: "local".into(),
+            origin_host: None,
+        };
+        storage
+            .insert_conversation_tree(agent_id, None, &conv)
+            .unwrap();
+    }
+
     #[test]
     fn test_batch_embedding() {
         let indexer = SemanticIndexer::new("hash", None).unwrap();
@@ -279,6 +617,27 @@ mod tests {
         assert_eq!(embeddings[0].embedding.len(), indexer.embedder_dimension());
     }
 
+    #[test]
+    fn test_long_message_embeds_one_vector_per_chunk() {
+        let indexer = SemanticIndexer::new("hash", None).unwrap();
+        let long: Vec<String> = (0..1000).map(|i| format!("design{i}")).collect();
+        let messages = vec![
+            EmbeddingInput::new(1, "short"),
+            EmbeddingInput::new(2, long.join(" ")),
+        ];
+
+        let embeddings = indexer.embed_messages(&messages).unwrap();
+        let chunks: Vec<u8> = embeddings
+            .iter()
+            .filter(|e| e.message_id == 2)
+            .map(|e| e.chunk_idx)
+            .collect();
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks, (0..chunks.len() as u8).collect::<Vec<_>>());
+        assert_eq!(embeddings[0].message_id, 1);
+        assert_eq!(embeddings[0].chunk_idx, 0);
+    }
+
     #[test]
     fn test_progress_indicator() {
         let indexer = SemanticIndexer::new("hash", None).unwrap();
@@ -312,4 +671,111 @@ mod tests {
             indexer.embedder_dimension() as u32
         );
     }
+
+    #[test]
+    fn update_index_appends_segment_and_reuses_known_content() {
+        let tmp = tempdir().unwrap();
+        let mut storage = SqliteStorage::open(&tmp.path().join("agent_search.db")).unwrap();
+        let indexer = SemanticIndexer::new("hash", None).unwrap();
+
+        insert_session(
+            &mut storage,
+            "/s/a.jsonl",
+            &["Hello world", "Goodbye world"],
+        );
+        let first = indexer.update_index(&storage, tmp.path(), false).unwrap();
+        assert!(first.rebuilt);
+        assert_eq!(first.embedded, 2);
+        let base = vector_index_path(tmp.path(), indexer.embedder_id());
+        assert_eq!(first.written.as_deref(), Some(base.as_path()));
+
+        let unchanged = indexer.update_index(&storage, tmp.path(), false).unwrap();
+        assert_eq!(unchanged.embedded + unchanged.reused, 0);
+        assert!(unchanged.written.is_none());
+
+        insert_session(
+            &mut storage,
+            "/s/b.jsonl",
+            &["Hello world", "Something new"],
+        );
+        let second = indexer.update_index(&storage, tmp.path(), false).unwrap();
+        assert!(!second.rebuilt);
+        assert_eq!(second.reused, 1);
+        assert_eq!(second.embedded, 1);
+        assert_eq!(vector_segment_paths(&base).len(), 1);
+
+        let merged = VectorIndex::load_with_segments(&base).unwrap();
+        assert_eq!(merged.rows().len(), 4);
+    }
+
+    #[test]
+    fn update_index_shared_releases_the_storage_lock() {
+        let tmp = tempdir().unwrap();
+        let mut storage = SqliteStorage::open(&tmp.path().join("agent_search.db")).unwrap();
+        let indexer = SemanticIndexer::new("hash", None).unwrap();
+        insert_session(&mut storage, "/s/a.jsonl", &["Hello world"]);
+        let storage = Mutex::new(storage);
+
+        let first = indexer
+            .update_index_shared(&storage, tmp.path(), false)
+            .unwrap();
+        assert_eq!(first.embedded, 1);
+        insert_session(
+            &mut storage.try_lock().expect("lock released"),
+            "/s/b.jsonl",
+            &["Another message"],
+        );
+        let second = indexer
+            .update_index_shared(&storage, tmp.path(), false)
+            .unwrap();
+        assert!(!second.rebuilt);
+        assert_eq!(second.embedded, 1);
+    }
+
+    #[test]
+    fn update_index_rebuild_drops_segments() {
+        let tmp = tempdir().unwrap();
+        let mut storage = SqliteStorage::open(&tmp.path().join("agent_search.db")).unwrap();
+        let indexer = SemanticIndexer::new("hash", None).unwrap();
+
+        insert_session(&mut storage, "/s/a.jsonl", &["Hello world"]);
+        indexer.update_index(&storage, tmp.path(), false).unwrap();
+        insert_session(&mut storage, "/s/b.jsonl", &["Another message"]);
+        indexer.update_index(&storage, tmp.path(), false).unwrap();
+        let base = vector_index_path(tmp.path(), indexer.embedder_id());
+        assert_eq!(vector_segment_paths(&base).len(), 1);
+
+        let rebuilt = indexer.update_index(&storage, tmp.path(), true).unwrap();
+        assert!(rebuilt.rebuilt);
+        assert_eq!(rebuilt.embedded, 2);
+        assert!(vector_segment_paths(&base).is_empty());
+        assert_eq!(VectorIndex::load(&base).unwrap().rows().len(), 2);
+    }
+
+    #[test]
+    fn update_index_reencodes_when_quantization_changes() {
+        let tmp = tempdir().unwrap();
+        let mut storage = SqliteStorage::open(&tmp.path().join("agent_search.db")).unwrap();
+        let indexer = SemanticIndexer::new("hash", None).unwrap();
+
+        insert_session(&mut storage, "/s/a.jsonl", &["Hello world"]);
+        indexer.update_index(&storage, tmp.path(), false).unwrap();
+        insert_session(&mut storage, "/s/b.jsonl", &["Another message"]);
+        indexer.update_index(&storage, tmp.path(), false).unwrap();
+        let base = vector_index_path(tmp.path(), indexer.embedder_id());
+
+        let int8 = SemanticIndexer::new("hash", None)
+            .unwrap()
+            .with_quantization(Quantization::Int8);
+        let update = int8.update_index(&storage, tmp.path(), false).unwrap();
+        assert_eq!(
+            update.embedded, 0,
+            "stored vectors are re-encoded, not re-embedded"
+        );
+        assert_eq!(update.written.as_deref(), Some(base.as_path()));
+        assert!(vector_segment_paths(&base).is_empty());
+        let loaded = VectorIndex::load(&base).unwrap();
+        assert_eq!(loaded.header().quantization, Quantization::Int8);
+        assert_eq!(loaded.rows().len(), 2);
+    }
 }
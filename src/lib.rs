@@ -1,17 +1,24 @@
 pub mod bakeoff;
 pub mod bookmarks;
+pub mod bundle;
 pub mod connectors;
 pub mod encryption;
 pub mod export;
+pub mod forget;
 pub mod html_export;
+pub mod import;
 pub mod indexer;
+pub mod mcp;
 pub mod model;
 pub mod pages;
 pub mod search;
+pub mod serve;
 pub mod sources;
 pub mod storage;
 pub mod ui;
 pub mod update_check;
+pub mod usage;
+pub mod vault;
 
 use anyhow::Result;
 use base64::prelude::*;
@@ -134,6 +141,19 @@ pub enum Commands {
         #[arg(long, default_value = "fastembed")]
         embedder: String,
 
+        /// Also build an HNSW graph for fast approximate semantic search (with --semantic)
+        #[arg(long)]
+        ann: bool,
+
+        /// Vector encoding for the semantic index: f32 (default), f16, int8, or binary
+        #[arg(long, value_enum, default_value_t = crate::search::vector_index::Quantization::F32)]
+        quantization: crate::search::vector_index::Quantization,
+
+        /// Replace API keys, tokens and private keys with `[REDACTED:<kind>]` before
+        /// storing. Remembered for later runs; `CASS_REDACT_SECRETS=0` turns it off.
+        #[arg(long)]
+        redact_secrets: bool,
+
         /// Override data dir (index + db). Defaults to platform data dir.
         #[arg(long)]
         data_dir: Option<PathBuf>,
@@ -245,10 +265,31 @@ pub enum Commands {
         /// Filter by source: 'local', 'remote', 'all', or a specific source hostname
         #[arg(long)]
         source: Option<String>,
+        /// Also run the query over SSH on remote sources that have cass installed and merge
+        /// their hits, without syncing sessions first. Per-source status is in `_meta.federation`.
+        #[arg(long)]
+        federated: bool,
         /// Filter to sessions from file (one path per line). Use '-' for stdin.
         /// Enables chained searches: `cass search "query1" --robot-format sessions | cass search "query2" --sessions-from -`
         #[arg(long)]
         sessions_from: Option<String>,
+        /// Only search sessions that have a bookmark (see `cass bookmarks`)
+        #[arg(long)]
+        bookmarked: bool,
+        /// Only search sessions bookmarked with this tag (repeatable, implies --bookmarked)
+        #[arg(long = "bookmark-tag")]
+        bookmark_tags: Vec<String>,
+        /// Only search conversations tagged with this tag via `cass tag` (repeatable, matches
+        /// any); the same store as `tag:` qualifiers. Bookmark tags use --bookmark-tag
+        #[arg(long = "tag", visible_alias = "session-tag")]
+        session_tags: Vec<String>,
+        /// Only match messages from this speaker (repeatable; same across lexical/semantic/hybrid)
+        #[arg(long = "role", value_parser = ["user", "assistant", "tool", "system"])]
+        roles: Vec<String>,
+        /// Only search sessions whose tool calls read or edited this file (repeatable;
+        /// relative paths match any workspace, e.g. `src/foo.rs`)
+        #[arg(long = "file")]
+        files: Vec<String>,
         /// Search mode: lexical (default), semantic, or hybrid
         #[arg(long, value_enum)]
         mode: Option<crate::search::query::SearchMode>,
@@ -280,6 +321,10 @@ pub enum Commands {
         /// Disable daemon usage even if available (force direct inference).
         #[arg(long, default_value_t = false)]
         no_daemon: bool,
+
+        /// Score every vector instead of walking the HNSW graph (built by `index --ann`)
+        #[arg(long, default_value_t = false)]
+        exact: bool,
     },
     /// Show statistics about indexed data
     Stats {
@@ -366,6 +411,9 @@ pub enum Commands {
         /// Number of context lines before/after
         #[arg(long, short = 'C', default_value_t = 5)]
         context: usize,
+        /// Show or collapse Claude Code subagent threads
+        #[arg(long, value_enum)]
+        subagents: Option<SubagentDisplay>,
         /// Output as JSON
         #[arg(long, visible_alias = "robot")]
         json: bool,
@@ -433,12 +481,52 @@ pub enum Commands {
         include_tools: bool,
     },
     /// Export session as beautiful, self-contained HTML (with optional encryption)
+    ///
+    /// Pass a session file to export one conversation, or selectors (--query,
+    /// --agent, --workspace, --since/--until, --tag, --source) to export every
+    /// matching indexed conversation plus an index.html linking them.
     #[command(name = "export-html")]
     ExportHtml {
-        /// Path to session file
-        session: PathBuf,
+        /// Path to session file (omit when exporting by selectors)
+        session: Option<PathBuf>,
+
+        /// Only conversations with a message containing all of these words
+        #[arg(long)]
+        query: Option<String>,
+
+        /// Only conversations from this agent slug
+        #[arg(long = "agent")]
+        agents: Vec<String>,
+
+        /// Only conversations in this workspace path
+        #[arg(long = "workspace")]
+        workspaces: Vec<String>,
 
-        /// Output directory (default: downloads folder)
+        /// Only conversations started at or after this time (ISO date, `7d`, `today`, ...)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Only conversations started at or before this time
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Only conversations with this tag (`cass tag add`)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+
+        /// Only conversations from this source (e.g. `local`, `work-laptop`)
+        #[arg(long = "source")]
+        sources: Vec<String>,
+
+        /// Export at most this many conversations, newest first
+        #[arg(long)]
+        limit: Option<usize>,
+
+        /// With --encrypt, also encrypt the index (`all`) or leave it readable (`sessions`)
+        #[arg(long, value_enum, default_value_t = HtmlEncryptScope::All)]
+        encrypt_scope: HtmlEncryptScope,
+
+        /// Output directory (default: downloads folder; a new subfolder when exporting by selectors)
         #[arg(long)]
         output_dir: Option<PathBuf>,
 
@@ -486,6 +574,10 @@ pub enum Commands {
         #[arg(long)]
         open: bool,
 
+        /// Override data dir (when exporting by selectors)
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+
         /// JSON output (for automation)
         #[arg(long, visible_alias = "robot")]
         json: bool,
@@ -500,6 +592,9 @@ pub enum Commands {
         /// Number of messages before/after (default: 3)
         #[arg(long, short = 'C', default_value_t = 3)]
         context: usize,
+        /// Show or collapse Claude Code subagent threads
+        #[arg(long, value_enum)]
+        subagents: Option<SubagentDisplay>,
         /// Output as JSON
         #[arg(long, visible_alias = "robot")]
         json: bool,
@@ -624,6 +719,9 @@ pub enum Commands {
         /// Show example config file
         #[arg(long)]
         example_config: bool,
+
+        #[command(subcommand)]
+        command: Option<PagesCommand>,
     },
     /// Manage remote sources (P5.x)
     #[command(subcommand)]
@@ -631,6 +729,265 @@ pub enum Commands {
     /// Manage semantic search models
     #[command(subcommand)]
     Models(ModelsCommand),
+    /// Manage bookmarked search results
+    #[command(subcommand)]
+    Bookmarks(BookmarksCommand),
+    /// Tag whole conversations (e.g. "postmortem") to filter on later
+    #[command(subcommand)]
+    Tag(TagCommand),
+    /// Encrypt the data directory at rest and lock/unlock it
+    #[command(subcommand)]
+    Vault(VaultCommand),
+    /// List the files a session read or edited through its tool calls
+    Files {
+        /// Session path (source_path from search results)
+        session: String,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Remove conversations from the database, the lexical index and the vector index
+    ///
+    /// Selectors combine with AND; repeat a flag to match any of its values.
+    /// Session files on disk are not touched.
+    Forget {
+        /// Only conversations from this source (e.g. `local`, `work-laptop`)
+        #[arg(long = "source")]
+        sources: Vec<String>,
+        /// Only conversations from this agent slug
+        #[arg(long = "agent")]
+        agents: Vec<String>,
+        /// Only conversations in this workspace path
+        #[arg(long = "workspace")]
+        workspaces: Vec<String>,
+        /// Session file path or glob, e.g. `'/home/me/.codex/sessions/2025/06/*'`
+        #[arg(long = "path")]
+        paths: Vec<String>,
+        /// Only conversations started at or after this time (ISO date, `7d`, `today`, ...)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only conversations started at or before this time
+        #[arg(long)]
+        until: Option<String>,
+        /// Only conversations with a message containing all of these words
+        #[arg(long)]
+        query: Option<String>,
+        /// List what would be removed without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt (required with --json unless --dry-run)
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Audit the local index for API keys, tokens, passwords and private keys
+    ScanSecrets {
+        /// Only conversations from this agent slug (repeatable or comma-separated)
+        #[arg(long = "agent", value_delimiter = ',')]
+        agents: Vec<String>,
+        /// Only conversations in this workspace path (repeatable or comma-separated)
+        #[arg(long = "workspace", value_delimiter = ',')]
+        workspaces: Vec<String>,
+        /// Only conversations started at or after this time (ISO date, `7d`, `today`, ...)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only conversations started at or before this time
+        #[arg(long)]
+        until: Option<String>,
+        /// Allowlist regex patterns to suppress findings (repeatable or comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        secrets_allow: Vec<String>,
+        /// Denylist regex patterns to force findings (repeatable or comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        secrets_deny: Vec<String>,
+        /// Exit with code 1 if anything is found (for CI and sync hooks)
+        #[arg(long)]
+        fail_on_secrets: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Import a ChatGPT or Claude.ai account data export (zip or conversations.json)
+    Import {
+        /// Export zip, extracted export directory, or its conversations.json
+        path: PathBuf,
+        /// Export format (detected from the conversations by default)
+        #[arg(long, value_enum)]
+        format: Option<crate::import::ExportFormat>,
+        /// Source id to index the conversations under (default: chatgpt-export or claude-export)
+        #[arg(long)]
+        source_id: Option<String>,
+        /// Report what would be imported without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Move sessions between machines without SSH, as portable bundle files
+    #[command(subcommand)]
+    Bundle(BundleCommand),
+    /// Token usage and cost by day, agent, workspace or model
+    Usage {
+        /// Group by: day, agent, workspace, or model
+        #[arg(long, value_enum, default_value_t = crate::usage::UsageGrouping::Day)]
+        by: crate::usage::UsageGrouping,
+        /// Start time (ISO date or RFC 3339)
+        #[arg(long)]
+        since: Option<String>,
+        /// End time (ISO date or RFC 3339)
+        #[arg(long)]
+        until: Option<String>,
+        /// Filter by agent (can be repeated)
+        #[arg(long)]
+        agent: Vec<String>,
+        /// Filter by workspace (substring of the path)
+        #[arg(long)]
+        workspace: Option<String>,
+        /// Filter by source: 'local', 'remote', 'all', or a specific source hostname
+        #[arg(long)]
+        source: Option<String>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Run the semantic daemon: load models once and serve them to `--daemon` searches
+    Daemon {
+        /// Unix socket to listen on (default: <data-dir>/daemon.sock, or CASS_DAEMON_SOCKET)
+        #[arg(long)]
+        socket: Option<PathBuf>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Requests served concurrently before replying "overloaded"
+        #[arg(long, default_value_t = 8)]
+        max_in_flight: usize,
+        /// Do not load the reranker
+        #[arg(long)]
+        no_rerank: bool,
+        /// Report the health of a running daemon and exit
+        #[arg(long, conflicts_with = "stop")]
+        status: bool,
+        /// Ask a running daemon to shut down
+        #[arg(long)]
+        stop: bool,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Serve search, view, expand, context, timeline and stats as MCP tools over stdio
+    Mcp {
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+    },
+    /// Serve search, sessions, expand, timeline, stats and health as a local HTTP/JSON API
+    Serve {
+        /// Address to listen on: HOST:PORT, or unix:/path/to/socket
+        #[arg(long, default_value = "127.0.0.1:8765")]
+        listen: String,
+        /// Require `Authorization: Bearer <token>` on every request (default: CASS_SERVE_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+    },
+}
+
+/// Subcommands for portable session bundles
+#[derive(Subcommand, Debug, Clone)]
+pub enum BundleCommand {
+    /// Write conversations to a compressed, optionally encrypted bundle file
+    ///
+    /// Selectors combine with AND, as in `cass forget`; without any, every
+    /// conversation is exported.
+    Export {
+        /// Bundle file to write (e.g. `runner-7.cassbundle`)
+        #[arg(long, short = 'o')]
+        output: PathBuf,
+        /// Only conversations from this source (e.g. `local`, `work-laptop`)
+        #[arg(long = "source")]
+        sources: Vec<String>,
+        /// Only conversations from this agent slug
+        #[arg(long = "agent")]
+        agents: Vec<String>,
+        /// Only conversations in this workspace path
+        #[arg(long = "workspace")]
+        workspaces: Vec<String>,
+        /// Session file path or glob
+        #[arg(long = "path")]
+        paths: Vec<String>,
+        /// Only conversations started at or after this time (ISO date, `7d`, `today`, ...)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only conversations started at or before this time
+        #[arg(long)]
+        until: Option<String>,
+        /// Only conversations with a message containing all of these words
+        #[arg(long)]
+        query: Option<String>,
+        /// Machine name recorded in the bundle, the default source id on import (default: host name)
+        #[arg(long)]
+        name: Option<String>,
+        /// Encrypt the bundle under a password (AES-256-GCM, Argon2id key)
+        #[arg(long)]
+        encrypt: bool,
+        /// Read the password from stdin
+        #[arg(long, requires = "encrypt")]
+        password_stdin: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Index a bundle under its own source id; importing it again only adds new messages
+    Import {
+        /// Bundle file written by `cass bundle export`
+        path: PathBuf,
+        /// Source id to index the conversations under (default: the exporting machine's name)
+        #[arg(long)]
+        source_id: Option<String>,
+        /// Read the password of an encrypted bundle from stdin
+        #[arg(long)]
+        password_stdin: bool,
+        /// Report what would be imported without writing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+impl BundleCommand {
+    fn json(&self) -> bool {
+        match self {
+            BundleCommand::Export { json, .. } | BundleCommand::Import { json, .. } => *json,
+        }
+    }
 }
 
 /// Subcommands for managing remote sources (P5.x)
@@ -647,7 +1004,8 @@ pub enum SourcesCommand {
     },
     /// Add a new remote source
     Add {
-        /// Source URL (e.g., user@host or ssh://user@host)
+        /// Source URL (e.g., user@host or ssh://user@host), or a directory laid
+        /// out like another machine's home (e.g., /mnt/nas/laptop or path:///mnt/nas/laptop)
         url: String,
         /// Friendly name for this source (becomes source_id)
         #[arg(long)]
@@ -661,6 +1019,10 @@ pub enum SourcesCommand {
         /// Skip connectivity test
         #[arg(long)]
         no_test: bool,
+        /// For directory sources: copy changed files into the local mirror on
+        /// `cass sources sync` instead of indexing the directory in place
+        #[arg(long)]
+        mirror: bool,
     },
     /// Remove a configured source
     Remove {
@@ -696,6 +1058,10 @@ pub enum SourcesCommand {
         /// Dry run - show what would be synced without actually syncing
         #[arg(long)]
         dry_run: bool,
+        /// Keep running and sync each source on its `sync_schedule` (hourly/daily).
+        /// With --json, prints one JSON line per sync.
+        #[arg(long, conflicts_with = "dry_run")]
+        daemon: bool,
         /// Output as JSON
         #[arg(long, visible_alias = "robot")]
         json: bool,
@@ -864,56 +1230,374 @@ pub enum ModelsCommand {
     },
 }
 
-/// Subcommands for managing path mappings (P6.3)
+/// Subcommands for managing bookmarks
 #[derive(Subcommand, Debug, Clone)]
-pub enum MappingsAction {
-    /// List path mappings for a source
-    List {
-        /// Source name
-        source: String,
+pub enum BookmarksCommand {
+    /// Bookmark a session (optionally at a specific line)
+    Add {
+        /// Path to the source session file (as shown in search results)
+        path: String,
+        /// Line number within the session (1-indexed, matches search hit `line_number`)
+        #[arg(long, short = 'n')]
+        line: Option<usize>,
+        /// Bookmark title (defaults to the conversation title)
+        #[arg(long)]
+        title: Option<String>,
+        /// Note explaining why this was bookmarked
+        #[arg(long, default_value = "")]
+        note: String,
+        /// Comma-separated tags (e.g. "auth, important")
+        #[arg(long, default_value = "")]
+        tags: String,
+        /// Agent slug (defaults to the indexed conversation's agent)
+        #[arg(long)]
+        agent: Option<String>,
+        /// Workspace path (defaults to the indexed conversation's workspace)
+        #[arg(long)]
+        workspace: Option<String>,
+        /// Snippet to store (defaults to the message at --line)
+        #[arg(long)]
+        snippet: Option<String>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
         /// Output as JSON
         #[arg(long, visible_alias = "robot")]
         json: bool,
     },
-    /// Add a path mapping
-    Add {
-        /// Source name
-        source: String,
-        /// Remote path prefix to match
+    /// List bookmarks
+    List {
+        /// Only show bookmarks with this tag
         #[arg(long)]
-        from: String,
-        /// Local path prefix to replace with
+        tag: Option<String>,
+        /// Only show bookmarks whose title, note, or snippet contains this text
+        #[arg(long, short)]
+        query: Option<String>,
+        /// Override data dir
         #[arg(long)]
-        to: String,
-        /// Only apply to specific agents (comma-separated)
-        #[arg(long, value_delimiter = ',')]
-        agents: Option<Vec<String>>,
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
     },
-    /// Remove a path mapping by index
+    /// Remove a bookmark by id
     Remove {
-        /// Source name
-        source: String,
-        /// Index of mapping to remove (from list output, 0-based)
-        index: usize,
+        /// Bookmark id (from `cass bookmarks list`)
+        id: i64,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
     },
-    /// Test how a path would be rewritten
-    Test {
-        /// Source name
-        source: String,
-        /// Path to test
-        path: String,
-        /// Optional agent to simulate (for agent-specific rules)
+    /// Edit a bookmark's title, note, or tags
+    Edit {
+        /// Bookmark id (from `cass bookmarks list`)
+        id: i64,
+        /// New title
         #[arg(long)]
-        agent: Option<String>,
+        title: Option<String>,
+        /// New note
+        #[arg(long)]
+        note: Option<String>,
+        /// New comma-separated tags (replaces existing tags)
+        #[arg(long)]
+        tags: Option<String>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Export all bookmarks as JSON
+    Export {
+        /// Output file (defaults to stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+    },
+    /// Import bookmarks from a JSON export (skips duplicates)
+    Import {
+        /// JSON file produced by `cass bookmarks export` (use '-' for stdin)
+        input: String,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
     },
 }
 
-#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
-pub enum ColorPref {
-    Auto,
-    Never,
-    Always,
-}
+/// Subcommands for tagging conversations
+#[derive(Subcommand, Debug, Clone)]
+pub enum TagCommand {
+    /// Add one or more tags to a session
+    Add {
+        /// Path to the source session file (as shown in search results)
+        path: String,
+        /// Tags to add (lowercased; no spaces or commas)
+        #[arg(required = true)]
+        tags: Vec<String>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Remove one or more tags from a session
+    Remove {
+        /// Path to the source session file
+        path: String,
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// List all tags with counts, or the tags of one session
+    List {
+        /// Only show tags for this session file
+        path: Option<String>,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+/// At-rest encryption of the data directory (`cass vault ...`)
+///
+/// Passwords are prompted for without echo; with `--password-stdin` the
+/// password is read from the first line of stdin instead.
+#[derive(Subcommand, Debug, Clone)]
+pub enum VaultCommand {
+    /// Encrypt the data dir under a new password, then lock it
+    Init {
+        /// Also accept this key file for unlocking (created with a random secret if missing)
+        #[arg(long)]
+        key_file: Option<PathBuf>,
+        /// Minutes an unlock lasts before the next cass command locks again
+        #[arg(long, default_value_t = crate::vault::DEFAULT_SESSION_TIMEOUT_SECS / 60)]
+        timeout: u64,
+        /// Read the new password from stdin
+        #[arg(long)]
+        password_stdin: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Decrypt the data dir and start a session (extends an open session)
+    Unlock {
+        /// Unlock with a key file instead of the password
+        #[arg(long)]
+        key_file: Option<PathBuf>,
+        /// Session length in minutes (default: the vault's timeout)
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Read the password from stdin
+        #[arg(long)]
+        password_stdin: bool,
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Encrypt the data dir now and end the session
+    Lock {
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Show whether the data dir is encrypted and locked
+    Status {
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Stop encrypting an unlocked data dir (keeps the plaintext, deletes the vault)
+    Disable {
+        /// Override data dir
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+impl VaultCommand {
+    fn json(&self) -> bool {
+        match self {
+            VaultCommand::Init { json, .. }
+            | VaultCommand::Unlock { json, .. }
+            | VaultCommand::Lock { json, .. }
+            | VaultCommand::Status { json, .. }
+            | VaultCommand::Disable { json, .. } => *json,
+        }
+    }
+
+    fn data_dir(&self) -> PathBuf {
+        match self {
+            VaultCommand::Init { data_dir, .. }
+            | VaultCommand::Unlock { data_dir, .. }
+            | VaultCommand::Lock { data_dir, .. }
+            | VaultCommand::Status { data_dir, .. }
+            | VaultCommand::Disable { data_dir, .. } => {
+                data_dir.clone().unwrap_or_else(default_data_dir)
+            }
+        }
+    }
+}
+
+/// Subcommands of `cass pages` that act on an existing archive
+#[derive(Subcommand, Debug, Clone)]
+pub enum PagesCommand {
+    /// Manage the passwords and recovery secrets that unlock an encrypted archive
+    #[command(subcommand)]
+    Key(PagesKeyCommand),
+}
+
+/// Key-slot operations on an encrypted archive (`cass pages key ...`)
+///
+/// Passwords are prompted for without echo; with `--password-stdin` they are
+/// read one per line instead, current password first.
+#[derive(Subcommand, Debug, Clone)]
+pub enum PagesKeyCommand {
+    /// List the key slots of an archive
+    List {
+        /// Archive directory (the export root or its site/ directory)
+        archive: PathBuf,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Add a password slot, or a recovery secret with --recovery
+    Add {
+        /// Archive directory (the export root or its site/ directory)
+        archive: PathBuf,
+        /// Add a generated recovery secret instead of a new password
+        #[arg(long)]
+        recovery: bool,
+        /// Read the current (then the new) password from stdin, one per line
+        #[arg(long)]
+        password_stdin: bool,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Revoke a key slot; unlock with a password from a slot you keep
+    Revoke {
+        /// Archive directory (the export root or its site/ directory)
+        archive: PathBuf,
+        /// Slot id to revoke (see `cass pages key list`)
+        slot: u8,
+        /// Read the current password from stdin
+        #[arg(long)]
+        password_stdin: bool,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Re-encrypt the payload under a new key, replacing every slot with one new password
+    Rotate {
+        /// Archive directory (the export root or its site/ directory)
+        archive: PathBuf,
+        /// Also issue a fresh recovery secret (old recovery secrets stop working either way)
+        #[arg(long)]
+        recovery: bool,
+        /// Read the current (then the new) password from stdin, one per line
+        #[arg(long)]
+        password_stdin: bool,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+}
+
+impl PagesKeyCommand {
+    fn json(&self) -> bool {
+        match self {
+            PagesKeyCommand::List { json, .. }
+            | PagesKeyCommand::Add { json, .. }
+            | PagesKeyCommand::Revoke { json, .. }
+            | PagesKeyCommand::Rotate { json, .. } => *json,
+        }
+    }
+}
+
+/// Subcommands for managing path mappings (P6.3)
+#[derive(Subcommand, Debug, Clone)]
+pub enum MappingsAction {
+    /// List path mappings for a source
+    List {
+        /// Source name
+        source: String,
+        /// Output as JSON
+        #[arg(long, visible_alias = "robot")]
+        json: bool,
+    },
+    /// Add a path mapping
+    Add {
+        /// Source name
+        source: String,
+        /// Remote path prefix to match
+        #[arg(long)]
+        from: String,
+        /// Local path prefix to replace with
+        #[arg(long)]
+        to: String,
+        /// Only apply to specific agents (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        agents: Option<Vec<String>>,
+    },
+    /// Remove a path mapping by index
+    Remove {
+        /// Source name
+        source: String,
+        /// Index of mapping to remove (from list output, 0-based)
+        index: usize,
+    },
+    /// Test how a path would be rewritten
+    Test {
+        /// Source name
+        source: String,
+        /// Path to test
+        path: String,
+        /// Optional agent to simulate (for agent-specific rules)
+        #[arg(long)]
+        agent: Option<String>,
+    },
+}
+
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum ColorPref {
+    Auto,
+    Never,
+    Always,
+}
 
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
 pub enum ProgressMode {
@@ -965,6 +1649,16 @@ pub enum DisplayFormat {
     Markdown,
 }
 
+/// What `export-html --encrypt` encrypts when exporting several conversations
+#[derive(Copy, Clone, Debug, Default, ValueEnum, PartialEq, Eq)]
+pub enum HtmlEncryptScope {
+    /// Every conversation page and the index
+    #[default]
+    All,
+    /// Only the conversation pages; the index stays readable
+    Sessions,
+}
+
 /// Conversation export format (for export command)
 #[derive(Copy, Clone, Debug, Default, ValueEnum, PartialEq, Eq)]
 pub enum ConvExportFormat {
@@ -991,6 +1685,15 @@ pub enum TimelineGrouping {
     None,
 }
 
+/// How `view` and `expand` present Claude Code subagent threads
+#[derive(Copy, Clone, Debug, ValueEnum, PartialEq, Eq)]
+pub enum SubagentDisplay {
+    /// Nest each subagent's messages under the message that spawned it
+    Show,
+    /// Replace each subagent's messages with a one-line summary
+    Collapse,
+}
+
 /// Aggregation field types for --aggregate flag
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AggregateField {
@@ -1187,6 +1890,19 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         "no-cache",
         "source",
         "sessions-from",
+        "bookmarked",
+        "tag",
+        "session-tag",
+        "bookmark-tag",
+        "role",
+        "file",
+        "path",
+        "query",
+        "yes",
+        "socket",
+        "max-in-flight",
+        "no-rerank",
+        "stop",
         "mode",
         "highlight",
         "timeout",
@@ -1205,6 +1921,15 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         "watch-once",
         "semantic",
         "embedder",
+        "ann",
+        "quantization",
+        "redact-secrets",
+        "secrets-allow",
+        "secrets-deny",
+        "fail-on-secrets",
+        "key-file",
+        "password-stdin",
+        "exact",
         "idempotency-key",
         "model",
         "rerank",
@@ -1221,6 +1946,14 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         "skip-sync",
         "resume",
         "non-interactive",
+        "listen",
+        "token",
+        "source-id",
+        "by",
+        "subagents",
+        "federated",
+        "mirror",
+        "encrypt-scope",
     ];
 
     // Subcommand aliases for common mistakes
@@ -1261,6 +1994,9 @@ fn normalize_args(raw: Vec<String>) -> (Vec<String>, Option<String>) {
         ("docs", "robot-docs"),
         ("help-robot", "robot-docs"),
         ("robotdocs", "robot-docs"),
+        // Bookmarks aliases
+        ("bookmark", "bookmarks"),
+        ("bm", "bookmarks"),
     ];
 
     // Short flags that should remain as single-dash
@@ -2038,6 +2774,19 @@ async fn execute_cli(
         return Ok(());
     }
 
+    // Held until the command returns so the vault cannot seal files it has open
+    let _in_use = match vault_gated_data_dir(&command) {
+        Some(data_dir) => {
+            ensure_vault_unlocked(&data_dir)?;
+            if holds_data_dir_open(&command) {
+                Some(crate::vault::hold_in_use(&data_dir).map_err(|e| vault_error(&data_dir, e))?)
+            } else {
+                None
+            }
+        }
+        None => None,
+    };
+
     // Block TUI in non-TTY contexts unless TUI_HEADLESS is set (for testing)
     if matches!(command, Commands::Tui { .. })
         && !stdout_is_tty
@@ -2161,6 +2910,9 @@ async fn execute_cli(
                     data_dir,
                     semantic,
                     embedder,
+                    ann,
+                    quantization,
+                    redact_secrets,
                     json,
                     idempotency_key,
                 } => {
@@ -2173,111 +2925,16 @@ async fn execute_cli(
                         data_dir,
                         semantic,
                         embedder,
+                        ann,
+                        quantization,
+                        redact_secrets,
                         progress,
                         json,
                         idempotency_key,
                     )?;
                 }
-                Commands::Search {
-                    query,
-                    agent,
-                    workspace,
-                    limit,
-                    offset,
-                    json,
-                    robot_format,
-                    robot_meta,
-                    fields,
-                    max_content_length,
-                    max_tokens,
-                    request_id,
-                    cursor,
-                    display,
-                    data_dir,
-                    days,
-                    today,
-                    yesterday,
-                    week,
-                    since,
-                    until,
-                    aggregate,
-                    explain,
-                    dry_run,
-                    timeout,
-                    highlight,
-                    source,
-                    sessions_from,
-                    mode,
-                    model,
-                    rerank,
-                    reranker,
-                    daemon,
-                    no_daemon,
-                } => {
-                    // Validate mutually exclusive flags
-                    if daemon && no_daemon {
-                        return Err(CliError::usage(
-                            "Cannot specify both --daemon and --no-daemon",
-                            Some(
-                                "Use --daemon to enable daemon or --no-daemon to disable it"
-                                    .to_string(),
-                            ),
-                        ));
-                    }
-
-                    // Warn about reranker without rerank flag
-                    if reranker.is_some() && !rerank {
-                        eprintln!(
-                            "Warning: --reranker specified but --rerank not enabled; reranker will be ignored"
-                        );
-                    }
-
-                    // Build semantic options from new flags
-                    let semantic_opts = SemanticSearchOptions {
-                        model: model.clone(),
-                        rerank,
-                        reranker: reranker.clone(),
-                        use_daemon: daemon && !no_daemon,
-                    };
-
-                    run_cli_search(
-                        &query,
-                        &agent,
-                        &workspace,
-                        &limit,
-                        &offset,
-                        &json,
-                        robot_format,
-                        robot_meta,
-                        fields,
-                        max_content_length,
-                        max_tokens,
-                        request_id.clone(),
-                        cursor.clone(),
-                        display,
-                        &data_dir,
-                        cli.db.clone(),
-                        wrap,
-                        progress,
-                        robot_mode,
-                        TimeFilter::new(
-                            days,
-                            today,
-                            yesterday,
-                            week,
-                            since.as_deref(),
-                            until.as_deref(),
-                        ),
-                        aggregate,
-                        explain,
-                        dry_run,
-                        timeout,
-                        highlight,
-                        source,
-                        sessions_from,
-                        mode,
-                        semantic_opts,
-                    )?;
+                search @ Commands::Search { .. } => {
+                    run_search_command(search, cli.db.clone(), wrap, progress, robot_mode, None)?;
                 }
                 Commands::Stats {
                     data_dir,
@@ -2312,9 +2969,10 @@ async fn execute_cli(
                     path,
                     line,
                     context,
+                    subagents,
                     json,
                 } => {
-                    run_view(&path, line, context, json || robot_mode)?;
+                    run_view(&path, line, context, subagents, json || robot_mode)?;
                 }
                 Commands::Pages {
                     export_only,
@@ -2340,7 +2998,12 @@ async fn execute_cli(
                     config,
                     validate_config,
                     example_config,
+                    command,
                 } => {
+                    if let Some(PagesCommand::Key(cmd)) = command {
+                        return run_pages_key_command(cmd);
+                    }
+
                     // Handle --example-config (show example config and exit)
                     if example_config {
                         println!("{}", crate::pages::config_input::example_config());
@@ -2781,6 +3444,15 @@ async fn execute_cli(
                 }
                 Commands::ExportHtml {
                     session,
+                    query,
+                    agents,
+                    workspaces,
+                    since,
+                    until,
+                    tags,
+                    sources,
+                    limit,
+                    encrypt_scope,
                     output_dir,
                     filename,
                     encrypt,
@@ -2793,32 +3465,93 @@ async fn execute_cli(
                     dry_run,
                     explain,
                     open,
+                    data_dir,
                     json,
                 } => {
-                    run_export_html(
-                        &session,
-                        output_dir.as_deref(),
-                        filename.as_deref(),
-                        encrypt,
-                        password.as_deref(),
-                        password_stdin,
-                        include_tools,
-                        show_timestamps,
-                        !no_cdns,
-                        &theme,
-                        dry_run,
-                        explain,
-                        open,
-                        json,
-                    )?;
+                    let selector = crate::model::types::ConversationSelector {
+                        source_ids: sources,
+                        agents,
+                        workspaces,
+                        path_globs: Vec::new(),
+                        started_from: parse_time_flag("since", since.as_deref())?,
+                        started_to: parse_time_flag("until", until.as_deref())?,
+                        query: query.filter(|q| !q.trim().is_empty()),
+                        tags,
+                    };
+                    match session {
+                        Some(session) => {
+                            if !selector.is_empty() {
+                                return Err(CliError::usage(
+                                    "export-html takes a session file or selectors, not both",
+                                    Some(
+                                        "Drop the session path to export every matching conversation"
+                                            .into(),
+                                    ),
+                                ));
+                            }
+                            run_export_html(
+                                &session,
+                                output_dir.as_deref(),
+                                filename.as_deref(),
+                                encrypt,
+                                password.as_deref(),
+                                password_stdin,
+                                include_tools,
+                                show_timestamps,
+                                !no_cdns,
+                                &theme,
+                                dry_run,
+                                explain,
+                                open,
+                                json,
+                            )?;
+                        }
+                        None => {
+                            if selector.is_empty() {
+                                return Err(CliError::usage(
+                                    "export-html needs a session file or at least one selector",
+                                    Some(
+                                        "Use --query, --agent, --workspace, --since/--until, --tag or --source"
+                                            .into(),
+                                    ),
+                                ));
+                            }
+                            if filename.is_some() {
+                                return Err(CliError::usage(
+                                    "--filename only applies to a single session",
+                                    Some("Use --output-dir to choose where the pages go".into()),
+                                ));
+                            }
+                            run_export_html_batch(
+                                &selector,
+                                limit,
+                                output_dir.as_deref(),
+                                encrypt,
+                                encrypt_scope,
+                                password.as_deref(),
+                                password_stdin,
+                                include_tools,
+                                show_timestamps,
+                                !no_cdns,
+                                &theme,
+                                dry_run,
+                                explain,
+                                open,
+                                json,
+                                &data_dir,
+                                cli.db.clone(),
+                            )?;
+                        }
+                    }
                 }
                 Commands::Expand {
                     path,
                     line,
                     context,
+                    subagents,
                     json,
                 } => {
-                    run_expand(&path, line, context, json)?;
+                    run_expand(&path, line, context, subagents, json)?;
                 }
                 Commands::Timeline {
                     since,
@@ -2845,19 +3578,200 @@ async fn execute_cli(
                 Commands::Sources(subcmd) => {
                     run_sources_command(subcmd)?;
                 }
-                Commands::Models(subcmd) => {
-                    let subcmd = subcmd.clone();
-                    let result = tokio::task::spawn_blocking(move || run_models_command(subcmd))
-                        .await
-                        .map_err(|err| CliError {
-                            code: 70,
-                            kind: "runtime",
-                            message: format!("models command panicked: {err}"),
-                            hint: Some(
-                                "Retry the command; if it persists, report the panic output."
-                                    .into(),
-                            ),
-                            retryable: true,
+                Commands::Bookmarks(subcmd) => {
+                    run_bookmarks_command(subcmd, cli.db.clone())?;
+                }
+                Commands::Tag(subcmd) => {
+                    run_tag_command(subcmd, cli.db.clone())?;
+                }
+                Commands::Vault(subcmd) => {
+                    run_vault_command(subcmd)?;
+                }
+                Commands::Files {
+                    session,
+                    data_dir,
+                    json,
+                } => {
+                    run_files(&session, &data_dir, cli.db.clone(), json)?;
+                }
+                Commands::Forget {
+                    sources,
+                    agents,
+                    workspaces,
+                    paths,
+                    since,
+                    until,
+                    query,
+                    dry_run,
+                    yes,
+                    data_dir,
+                    json,
+                } => {
+                    let selector = forget_selector(
+                        sources,
+                        agents,
+                        workspaces,
+                        paths,
+                        since.as_deref(),
+                        until.as_deref(),
+                        query,
+                    )?;
+                    run_forget(&selector, dry_run, yes, &data_dir, cli.db.clone(), json)?;
+                }
+                Commands::ScanSecrets {
+                    agents,
+                    workspaces,
+                    since,
+                    until,
+                    secrets_allow,
+                    secrets_deny,
+                    fail_on_secrets,
+                    data_dir,
+                    json,
+                } => {
+                    let filters = scan_secrets_filters(
+                        agents,
+                        workspaces,
+                        since.as_deref(),
+                        until.as_deref(),
+                    )?;
+                    run_scan_secrets(
+                        &filters,
+                        &secrets_allow,
+                        &secrets_deny,
+                        fail_on_secrets,
+                        &data_dir,
+                        cli.db.clone(),
+                        json,
+                    )?;
+                }
+                Commands::Import {
+                    path,
+                    format,
+                    source_id,
+                    dry_run,
+                    data_dir,
+                    json,
+                } => {
+                    let opts = crate::import::ImportOptions {
+                        format,
+                        source_id,
+                        dry_run,
+                    };
+                    run_import(&path, &opts, &data_dir, cli.db.clone(), json)?;
+                }
+                Commands::Bundle(subcmd) => {
+                    run_bundle_command(subcmd, cli.db.clone())?;
+                }
+                Commands::Usage {
+                    by,
+                    since,
+                    until,
+                    agent,
+                    workspace,
+                    source,
+                    data_dir,
+                    json,
+                } => {
+                    run_usage(
+                        by,
+                        since.as_deref(),
+                        until.as_deref(),
+                        agent,
+                        workspace,
+                        source,
+                        &data_dir,
+                        cli.db.clone(),
+                        json,
+                    )?;
+                }
+                Commands::Daemon {
+                    socket,
+                    data_dir,
+                    max_in_flight,
+                    no_rerank,
+                    status,
+                    stop,
+                    json,
+                } => {
+                    let result = tokio::task::spawn_blocking(move || {
+                        run_daemon(
+                            socket,
+                            data_dir,
+                            max_in_flight,
+                            no_rerank,
+                            status,
+                            stop,
+                            json,
+                        )
+                    })
+                    .await
+                    .map_err(|err| CliError {
+                        code: 70,
+                        kind: "runtime",
+                        message: format!("daemon panicked: {err}"),
+                        hint: None,
+                        retryable: true,
+                    })?;
+                    result?;
+                }
+                Commands::Mcp { data_dir } => {
+                    let db_override = cli.db.clone();
+                    let result = tokio::task::spawn_blocking(move || {
+                        crate::mcp::run_stdio(
+                            data_dir.unwrap_or_else(default_data_dir),
+                            db_override,
+                        )
+                    })
+                    .await
+                    .map_err(|err| CliError {
+                        code: 70,
+                        kind: "runtime",
+                        message: format!("mcp server panicked: {err}"),
+                        hint: None,
+                        retryable: true,
+                    })?;
+                    result?;
+                }
+                Commands::Serve {
+                    listen,
+                    token,
+                    data_dir,
+                } => {
+                    let listen = crate::serve::ListenAddr::parse(&listen)?;
+                    let token = token.or_else(|| dotenvy::var(crate::serve::TOKEN_ENV).ok());
+                    let db_override = cli.db.clone();
+                    let result = tokio::task::spawn_blocking(move || {
+                        crate::serve::run(
+                            listen,
+                            token,
+                            data_dir.unwrap_or_else(default_data_dir),
+                            db_override,
+                        )
+                    })
+                    .await
+                    .map_err(|err| CliError {
+                        code: 70,
+                        kind: "runtime",
+                        message: format!("http server panicked: {err}"),
+                        hint: None,
+                        retryable: true,
+                    })?;
+                    result?;
+                }
+                Commands::Models(subcmd) => {
+                    let subcmd = subcmd.clone();
+                    let result = tokio::task::spawn_blocking(move || run_models_command(subcmd))
+                        .await
+                        .map_err(|err| CliError {
+                            code: 70,
+                            kind: "runtime",
+                            message: format!("models command panicked: {err}"),
+                            hint: Some(
+                                "Retry the command; if it persists, report the panic output."
+                                    .into(),
+                            ),
+                            retryable: true,
                         })?;
                     result?;
                 }
@@ -2874,6 +3788,9 @@ fn state_meta_json(data_dir: &Path, db_path: &Path, stale_threshold: u64) -> ser
     use rusqlite::Connection;
     use std::time::{SystemTime, UNIX_EPOCH};
 
+    // First, so an expired session is locked before anything is read
+    let vault = vault_report(data_dir);
+
     // Use the actual versioned index path (index/v4, not tantivy_index)
     let index_path = crate::search::tantivy::index_dir(data_dir)
         .unwrap_or_else(|_| data_dir.join("index").join("v4"));
@@ -2932,6 +3849,7 @@ fn state_meta_json(data_dir: &Path, db_path: &Path, stale_threshold: u64) -> ser
         .to_rfc3339();
 
     serde_json::json!({
+        "vault": vault_status_json(&vault),
         "index": {
             "exists": index_exists,
             "fresh": fresh,
@@ -3024,6 +3942,18 @@ fn describe_command(cli: &Cli) -> String {
         Some(Commands::Timeline { .. }) => "timeline".to_string(),
         Some(Commands::Sources(..)) => "sources".to_string(),
         Some(Commands::Models(..)) => "models".to_string(),
+        Some(Commands::Bookmarks(..)) => "bookmarks".to_string(),
+        Some(Commands::Tag(..)) => "tag".to_string(),
+        Some(Commands::Vault(..)) => "vault".to_string(),
+        Some(Commands::Files { .. }) => "files".to_string(),
+        Some(Commands::Forget { .. }) => "forget".to_string(),
+        Some(Commands::ScanSecrets { .. }) => "scan-secrets".to_string(),
+        Some(Commands::Import { .. }) => "import".to_string(),
+        Some(Commands::Bundle(..)) => "bundle".to_string(),
+        Some(Commands::Usage { .. }) => "usage".to_string(),
+        Some(Commands::Daemon { .. }) => "daemon".to_string(),
+        Some(Commands::Mcp { .. }) => "mcp".to_string(),
+        Some(Commands::Serve { .. }) => "serve".to_string(),
         Some(Commands::Pages { .. }) => "pages".to_string(),
         None => "(default)".to_string(),
     }
@@ -3066,6 +3996,34 @@ fn is_robot_mode(command: &Commands) -> bool {
             | SourcesCommand::Setup { json, .. } => *json,
             _ => false,
         },
+        Commands::Bookmarks(cmd) => match cmd {
+            BookmarksCommand::Add { json, .. }
+            | BookmarksCommand::List { json, .. }
+            | BookmarksCommand::Remove { json, .. }
+            | BookmarksCommand::Edit { json, .. }
+            | BookmarksCommand::Import { json, .. } => *json || env_robot_mode,
+            // Export always writes JSON to stdout (or a file).
+            BookmarksCommand::Export { .. } => true,
+        },
+        Commands::Tag(cmd) => match cmd {
+            TagCommand::Add { json, .. }
+            | TagCommand::Remove { json, .. }
+            | TagCommand::List { json, .. } => *json || env_robot_mode,
+        },
+        Commands::Vault(cmd) => cmd.json() || env_robot_mode,
+        Commands::Files { json, .. } => *json || env_robot_mode,
+        Commands::Forget { json, .. } => *json || env_robot_mode,
+        Commands::ScanSecrets { json, .. } => *json || env_robot_mode,
+        Commands::Import { json, .. } => *json || env_robot_mode,
+        Commands::Bundle(cmd) => cmd.json() || env_robot_mode,
+        Commands::Usage { json, .. } => *json || env_robot_mode,
+        Commands::Daemon { json, .. } => *json || env_robot_mode,
+        // stdout carries JSON-RPC
+        Commands::Mcp { .. } | Commands::Serve { .. } => true,
+        Commands::Pages {
+            command: Some(PagesCommand::Key(cmd)),
+            ..
+        } => cmd.json() || env_robot_mode,
         _ => false,
     }
 }
@@ -3323,7 +4281,7 @@ fn print_robot_docs(topic: RobotTopic, wrap: WrapConfig) -> CliResult<()> {
             "    --json | --robot  JSON output for automation".to_string(),
             "    --fields F1,F2    Select specific fields in hits (reduces token usage)".to_string(),
             "                      Presets: minimal (path,line,agent), summary (+title,score), provenance (source_id,origin_kind,origin_host)".to_string(),
-            "                      Fields: score,agent,workspace,source_path,snippet,content,title,created_at,line_number,match_type,source_id,origin_kind,origin_host".to_string(),
+            "                      Fields: score,agent,workspace,source_path,snippet,content,title,created_at,line_number,match_type,source_id,origin_kind,origin_host,parent".to_string(),
             "    --max-content-length N  Truncate content/snippet/title to N chars (UTF-8 safe, adds '...')".to_string(),
             "                            Adds *_truncated: true indicator for each truncated field".to_string(),
             "    --today           Filter to today only".to_string(),
@@ -3637,6 +4595,8 @@ pub struct SemanticSearchOptions {
     pub reranker: Option<String>,
     /// Use daemon for warm model inference
     pub use_daemon: bool,
+    /// Skip the HNSW graph and scan every vector
+    pub exact: bool,
 }
 
 impl TimeFilter {
@@ -3789,7 +4749,323 @@ fn parse_aggregate_fields(fields: &[String]) -> Vec<AggregateField> {
         .collect()
 }
 
+/// A search client kept open by a long-lived caller (`cass mcp`, `cass serve`),
+/// together with the semantic context it currently holds.
+pub(crate) struct SharedSearch {
+    client: crate::search::query::SearchClient,
+    /// What the client's semantic context was loaded for; `None` when it has none
+    semantic: std::sync::Mutex<Option<SemanticContextKey>>,
+}
+
+impl SharedSearch {
+    pub(crate) fn new(client: crate::search::query::SearchClient) -> Self {
+        Self {
+            client,
+            semantic: std::sync::Mutex::new(None),
+        }
+    }
+
+    fn holds_semantic(&self, key: &SemanticContextKey) -> bool {
+        self.semantic
+            .lock()
+            .is_ok_and(|loaded| loaded.as_ref() == Some(key))
+    }
+
+    fn set_semantic(&self, key: Option<SemanticContextKey>) {
+        if let Ok(mut loaded) = self.semantic.lock() {
+            *loaded = key;
+        }
+    }
+}
+
+/// The inputs a semantic context is loaded from. A shared client reloads its
+/// context only when these change, e.g. after `cass index --semantic` writes
+/// a new segment or another embedder is requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SemanticContextKey {
+    embedder: Option<String>,
+    exact: bool,
+    use_daemon: bool,
+    /// Name, size and modification time of every vector index file
+    index_files: Vec<(std::ffi::OsString, u64, Option<std::time::SystemTime>)>,
+}
+
+impl SemanticContextKey {
+    fn new(data_dir: &Path, embedder: Option<&str>, opts: &SemanticSearchOptions) -> Self {
+        let dir = data_dir.join(crate::search::vector_index::VECTOR_INDEX_DIR);
+        let mut index_files: Vec<_> = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| {
+                        let metadata = entry.metadata().ok()?;
+                        Some((entry.file_name(), metadata.len(), metadata.modified().ok()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        index_files.sort();
+        Self {
+            embedder: embedder.map(String::from),
+            exact: opts.exact,
+            use_daemon: opts.use_daemon,
+            index_files,
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
+/// Run a parsed `cass search`, reusing `shared` instead of opening the index if given.
+fn run_search_command(
+    command: Commands,
+    db_override: Option<PathBuf>,
+    wrap: WrapConfig,
+    progress: ProgressResolved,
+    robot_mode: bool,
+    shared: Option<&SharedSearch>,
+) -> CliResult<()> {
+    let Commands::Search {
+        query,
+        agent,
+        workspace,
+        limit,
+        offset,
+        json,
+        robot_format,
+        robot_meta,
+        fields,
+        max_content_length,
+        max_tokens,
+        request_id,
+        cursor,
+        display,
+        data_dir,
+        days,
+        today,
+        yesterday,
+        week,
+        since,
+        until,
+        aggregate,
+        explain,
+        dry_run,
+        timeout,
+        highlight,
+        source,
+        federated,
+        sessions_from,
+        bookmarked,
+        bookmark_tags,
+        session_tags,
+        roles,
+        files,
+        mode,
+        model,
+        rerank,
+        reranker,
+        daemon,
+        no_daemon,
+        exact,
+    } = command
+    else {
+        return Err(CliError::unknown("not a search command"));
+    };
+
+    // Validate mutually exclusive flags
+    if daemon && no_daemon {
+        return Err(CliError::usage(
+            "Cannot specify both --daemon and --no-daemon",
+            Some("Use --daemon to enable daemon or --no-daemon to disable it".to_string()),
+        ));
+    }
+
+    // Bookmarks, tags and session lists only exist in the local database
+    if federated
+        && (bookmarked
+            || !bookmark_tags.is_empty()
+            || !session_tags.is_empty()
+            || sessions_from.is_some())
+    {
+        return Err(CliError::usage(
+            "--federated cannot be combined with --bookmarked, --bookmark-tag, --tag or --sessions-from",
+            Some("Bookmarks and tags are local; drop --federated to search them".to_string()),
+        ));
+    }
+
+    // Filters forwarded to remote `cass search` runs
+    let federated = federated.then(|| {
+        let mut args = Vec::new();
+        for (flag, values) in [
+            ("--agent", &agent),
+            ("--workspace", &workspace),
+            ("--role", &roles),
+            ("--file", &files),
+        ] {
+            for value in values {
+                args.extend([flag.to_string(), value.clone()]);
+            }
+        }
+        for (flag, set) in [
+            ("--today", today),
+            ("--yesterday", yesterday),
+            ("--week", week),
+        ] {
+            if set {
+                args.push(flag.to_string());
+            }
+        }
+        if let Some(days) = days {
+            args.extend(["--days".to_string(), days.to_string()]);
+        }
+        for (flag, value) in [("--since", &since), ("--until", &until)] {
+            if let Some(value) = value {
+                args.extend([flag.to_string(), value.clone()]);
+            }
+        }
+        if let Some(mode) = mode.and_then(|m| m.to_possible_value()) {
+            args.extend(["--mode".to_string(), mode.get_name().to_string()]);
+        }
+        args
+    });
+
+    // Warn about reranker without rerank flag
+    if reranker.is_some() && !rerank {
+        eprintln!(
+            "Warning: --reranker specified but --rerank not enabled; reranker will be ignored"
+        );
+    }
+
+    // Build semantic options from new flags
+    let semantic_opts = SemanticSearchOptions {
+        model,
+        rerank,
+        reranker,
+        use_daemon: daemon && !no_daemon,
+        exact,
+    };
+
+    run_cli_search(
+        &query,
+        &agent,
+        &workspace,
+        &limit,
+        &offset,
+        &json,
+        robot_format,
+        robot_meta,
+        fields,
+        max_content_length,
+        max_tokens,
+        request_id,
+        cursor,
+        display,
+        &data_dir,
+        db_override,
+        wrap,
+        progress,
+        robot_mode,
+        TimeFilter::new(
+            days,
+            today,
+            yesterday,
+            week,
+            since.as_deref(),
+            until.as_deref(),
+        ),
+        aggregate,
+        explain,
+        dry_run,
+        timeout,
+        highlight,
+        source,
+        sessions_from,
+        bookmarked,
+        bookmark_tags,
+        session_tags,
+        roles,
+        files,
+        mode,
+        federated,
+        semantic_opts,
+        shared,
+    )
+}
+
+/// Load the semantic context for `cass search --mode semantic|hybrid` into `client`.
+fn install_semantic_context(
+    client: &crate::search::query::SearchClient,
+    data_dir: &Path,
+    db_path: &Path,
+    prefer_hash: bool,
+    semantic_opts: &SemanticSearchOptions,
+) -> CliResult<()> {
+    use crate::search::model_manager::{load_hash_semantic_context, load_semantic_context};
+    use std::sync::Arc;
+
+    let setup = if prefer_hash {
+        load_hash_semantic_context(data_dir, db_path)
+    } else {
+        load_semantic_context(data_dir, db_path)
+    };
+
+    if let Some(context) = setup.context {
+        let embedder = context.embedder;
+        let index = context.index;
+        let ann = if semantic_opts.exact {
+            None
+        } else {
+            context.ann
+        };
+        let filter_maps = context.filter_maps;
+        let roles = context.roles;
+
+        let embedder: Arc<dyn crate::search::embedder::Embedder> = if semantic_opts.use_daemon {
+            use crate::search::daemon_client::{DaemonFallbackEmbedder, DaemonRetryConfig};
+
+            let daemon = semantic_daemon_client(data_dir, Some(embedder.id()));
+            let config = DaemonRetryConfig::from_env();
+            Arc::new(DaemonFallbackEmbedder::new(daemon, embedder, config))
+        } else {
+            embedder
+        };
+
+        if let Err(err) = client.set_semantic_context(embedder, index, ann, filter_maps, roles) {
+            let hint = if prefer_hash {
+                "Run 'cass index --semantic --embedder hash' to rebuild the hash vector index, or use --mode lexical"
+                    .to_string()
+            } else {
+                "Run 'cass models install' and then 'cass index --semantic', or use --mode lexical"
+                    .to_string()
+            };
+            return Err(CliError {
+                code: 15,
+                kind: "semantic-unavailable",
+                message: format!("Semantic search not available: {err}"),
+                hint: Some(hint),
+                retryable: false,
+            });
+        }
+    } else {
+        let _ = client.clear_semantic_context();
+        let summary = setup.availability.summary();
+        let hint = if prefer_hash {
+            "Run 'cass index --semantic --embedder hash' to build the hash vector index, or use --mode lexical"
+                .to_string()
+        } else {
+            "Run 'cass models install' and then 'cass index --semantic', or use --mode lexical"
+                .to_string()
+        };
+        return Err(CliError {
+            code: 15,
+            kind: "semantic-unavailable",
+            message: format!("Semantic search not available: {summary}"),
+            hint: Some(hint),
+            retryable: false,
+        });
+    }
+    Ok(())
+}
+
 fn run_cli_search(
     query: &str,
     agents: &[String],
@@ -3818,10 +5094,16 @@ fn run_cli_search(
     highlight: bool,
     source: Option<String>,
     sessions_from: Option<String>,
+    bookmarked: bool,
+    bookmark_tags: Vec<String>,
+    session_tags: Vec<String>,
+    roles: Vec<String>,
+    files: Vec<String>,
     mode: Option<crate::search::query::SearchMode>,
+    federated: Option<Vec<String>>,
     semantic_opts: SemanticSearchOptions,
+    shared: Option<&SharedSearch>,
 ) -> CliResult<()> {
-    use crate::search::model_manager::{load_hash_semantic_context, load_semantic_context};
     use crate::search::query::{
         QueryExplanation, SearchClient, SearchClientOptions, SearchFilters, SearchMode,
     };
@@ -3843,31 +5125,22 @@ fn run_cli_search(
     })?;
     let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
 
-    let client = SearchClient::open_with_options(
-        &index_path,
-        Some(&db_path),
-        SearchClientOptions {
-            enable_reload: false,
-            enable_warm: false,
-        },
-    )
-    .map_err(|e| CliError {
-        code: 9,
-        kind: "open-index",
-        message: format!("failed to open index: {e}"),
-        hint: Some("try cass index --full".to_string()),
-        retryable: true,
-    })?
-    .ok_or_else(|| CliError {
-        code: 3,
-        kind: "missing-index",
-        message: format!(
-            "Index not found at {}. Run 'cass index --full' first.",
-            index_path.display()
-        ),
-        hint: None,
-        retryable: true,
-    })?;
+    // Long-lived callers (`cass mcp`) pass their own client to keep its caches warm
+    let opened;
+    let client = match shared {
+        Some(shared) => &shared.client,
+        None => {
+            opened = open_search_client(
+                &index_path,
+                &db_path,
+                SearchClientOptions {
+                    enable_reload: false,
+                    enable_warm: false,
+                },
+            )?;
+            &opened
+        }
+    };
 
     // Determine effective search mode (default to Lexical)
     let effective_mode = mode.unwrap_or(SearchMode::Lexical);
@@ -3899,63 +5172,22 @@ fn run_cli_search(
         };
         let prefer_hash = embedder_info.is_some_and(|e| e.name == HASH_EMBEDDER);
 
-        let setup = if prefer_hash {
-            load_hash_semantic_context(&data_dir, &db_path)
-        } else {
-            load_semantic_context(&data_dir, &db_path)
+        // A shared client keeps the context it loaded while its inputs are unchanged
+        let key = shared.map(|_| {
+            SemanticContextKey::new(&data_dir, embedder_info.map(|e| e.name), &semantic_opts)
+        });
+        let reuse = match (shared, &key) {
+            (Some(shared), Some(key)) => shared.holds_semantic(key),
+            _ => false,
         };
-
-        if let Some(context) = setup.context {
-            let embedder = context.embedder;
-            let index = context.index;
-            let filter_maps = context.filter_maps;
-            let roles = context.roles;
-
-            let embedder: Arc<dyn crate::search::embedder::Embedder> = if semantic_opts.use_daemon {
-                use crate::search::daemon_client::{
-                    DaemonFallbackEmbedder, DaemonRetryConfig, NoopDaemonClient,
-                };
-
-                let daemon = Arc::new(NoopDaemonClient::new("daemon-unconfigured"));
-                let config = DaemonRetryConfig::from_env();
-                Arc::new(DaemonFallbackEmbedder::new(daemon, embedder, config))
-            } else {
-                embedder
-            };
-
-            if let Err(err) = client.set_semantic_context(embedder, index, filter_maps, roles) {
-                let hint = if prefer_hash {
-                    "Run 'cass index --semantic --embedder hash' to rebuild the hash vector index, or use --mode lexical"
-                        .to_string()
-                } else {
-                    "Run 'cass models install' and then 'cass index --semantic', or use --mode lexical"
-                        .to_string()
-                };
-                return Err(CliError {
-                    code: 15,
-                    kind: "semantic-unavailable",
-                    message: format!("Semantic search not available: {err}"),
-                    hint: Some(hint),
-                    retryable: false,
-                });
+        if !reuse {
+            if let Some(shared) = shared {
+                shared.set_semantic(None);
+            }
+            install_semantic_context(client, &data_dir, &db_path, prefer_hash, &semantic_opts)?;
+            if let Some(shared) = shared {
+                shared.set_semantic(key);
             }
-        } else {
-            let _ = client.clear_semantic_context();
-            let summary = setup.availability.summary();
-            let hint = if prefer_hash {
-                "Run 'cass index --semantic --embedder hash' to build the hash vector index, or use --mode lexical"
-                    .to_string()
-            } else {
-                "Run 'cass models install' and then 'cass index --semantic', or use --mode lexical"
-                    .to_string()
-            };
-            return Err(CliError {
-                code: 15,
-                kind: "semantic-unavailable",
-                message: format!("Semantic search not available: {summary}"),
-                hint: Some(hint),
-                retryable: false,
-            });
         }
     }
 
@@ -3968,17 +5200,46 @@ fn run_cli_search(
     }
     filters.created_from = time_filter.since;
     filters.created_to = time_filter.until;
+    if !session_tags.is_empty() {
+        filters.tags = HashSet::from_iter(session_tags.iter().cloned());
+    }
+    if !roles.is_empty() {
+        filters.roles = HashSet::from_iter(roles.iter().cloned());
+    }
+    if !files.is_empty() {
+        filters.files = HashSet::from_iter(files.iter().cloned());
+    }
 
     // Apply source filter (P3.1)
     if let Some(ref source_str) = source {
         filters.source_filter = SourceFilter::parse(source_str);
     }
 
-    // Apply session paths filter (for chained searches)
-    if let Some(ref sessions_from_arg) = sessions_from {
-        let session_paths = read_session_paths(sessions_from_arg).map_err(|e| CliError {
-            code: 2,
-            kind: "sessions-from",
+    // Inline `field:value` qualifiers add to the flag filters; only the remaining
+    // text is searched. `full_query` keeps the string as typed for output.
+    let full_query = query;
+    let scoped_query = crate::search::query::parse_field_qualifiers(query);
+    scoped_query.apply_to(&mut filters).map_err(|e| CliError {
+        code: 2,
+        kind: "query-field",
+        message: e.to_string(),
+        hint: Some(format!(
+            "Supported qualifiers: {}",
+            crate::search::query::QUERY_FIELDS
+                .iter()
+                .map(|f| format!("{f}:"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )),
+        retryable: false,
+    })?;
+    let query = scoped_query.text.as_str();
+
+    // Apply session paths filter (for chained searches)
+    if let Some(ref sessions_from_arg) = sessions_from {
+        let session_paths = read_session_paths(sessions_from_arg).map_err(|e| CliError {
+            code: 2,
+            kind: "sessions-from",
             message: format!("failed to read session paths: {e}"),
             hint: Some("Provide a file path or '-' for stdin".to_string()),
             retryable: false,
@@ -3986,6 +5247,36 @@ fn run_cli_search(
         filters.session_paths = session_paths;
     }
 
+    // Restrict to bookmarked sessions (--bookmarked / --bookmark-tag). An empty set here
+    // means "nothing matches", not "no filter", so remember it and skip the search.
+    let mut no_bookmarked_sessions = false;
+    if bookmarked || !bookmark_tags.is_empty() {
+        let store = crate::bookmarks::BookmarkStore::open_in(&data_dir).map_err(|e| CliError {
+            code: 9,
+            kind: "bookmarks",
+            message: format!("failed to open bookmarks: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+        let bookmarked_paths = store.source_paths(&bookmark_tags).map_err(|e| CliError {
+            code: 9,
+            kind: "bookmarks",
+            message: format!("failed to read bookmarks: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+        filters.session_paths = if filters.session_paths.is_empty() {
+            bookmarked_paths
+        } else {
+            filters
+                .session_paths
+                .intersection(&bookmarked_paths)
+                .cloned()
+                .collect()
+        };
+        no_bookmarked_sessions = filters.session_paths.is_empty();
+    }
+
     // Apply cursor overrides (base64-encoded JSON { "offset": usize, "limit": usize })
     let mut limit_val = *limit;
     let mut offset_val = *offset;
@@ -4037,13 +5328,13 @@ fn run_cli_search(
 
     // Handle dry-run mode: validate and analyze query without executing
     if dry_run {
-        let explanation = QueryExplanation::analyze(query, &filters);
+        let explanation = QueryExplanation::analyze(full_query, &filters);
         let elapsed_ms = start_time.elapsed().as_millis();
 
         let output = serde_json::json!({
             "dry_run": true,
             "valid": explanation.warnings.iter().all(|w| !w.contains("error") && !w.contains("invalid")),
-            "query": query,
+            "query": full_query,
             "explanation": explanation,
             "estimated_cost": format!("{:?}", explanation.estimated_cost),
             "warnings": explanation.warnings,
@@ -4054,11 +5345,7 @@ fn run_cli_search(
             }
         });
 
-        println!(
-            "{}",
-            serde_json::to_string_pretty(&output).unwrap_or_else(|_| output.to_string())
-        );
-        return Ok(());
+        return output_structured_value(output, RobotFormat::Json);
     }
 
     // Use search_with_fallback to get full metadata (wildcard_fallback, cache_stats)
@@ -4068,6 +5355,9 @@ fn run_cli_search(
     // Fetch up to 1000 for aggregation starting at offset 0, then apply offset/limit
     let (search_limit, search_offset) = if has_aggregation {
         (1000.max(limit_val + offset_val), 0)
+    } else if federated.is_some() {
+        // Federated pages are cut from the merged list, so every side starts at 0
+        (limit_val + offset_val, 0)
     } else {
         (limit_val, offset_val)
     };
@@ -4104,12 +5394,51 @@ fn run_cli_search(
         );
 
         // TODO(bd-2mbe): Wire model selection to embedder registry
-        // TODO(bd-1lps): Implement daemon client integration
     }
 
+    // Federated mode: remote searches run while the local one does
+    let remote_searches = match federated {
+        Some(forwarded) => {
+            use crate::sources::federated::{DEFAULT_REMOTE_TIMEOUT_MS, RemoteSearch};
+
+            let config = crate::sources::SourcesConfig::load().map_err(|e| CliError {
+                code: 9,
+                kind: "config",
+                message: format!("failed to load sources config: {e}"),
+                hint: Some("Run 'cass sources list' to check sources.toml".to_string()),
+                retryable: false,
+            })?;
+            let remotes: Vec<_> = config
+                .remote_sources()
+                .filter(|s| match &filters.source_filter {
+                    SourceFilter::All | SourceFilter::Remote => true,
+                    SourceFilter::Local => false,
+                    SourceFilter::SourceId(id) => s.name == *id,
+                })
+                .cloned()
+                .collect();
+            let search = RemoteSearch {
+                query: full_query.to_string(),
+                limit: search_limit,
+                filters: forwarded,
+                timeout: Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_REMOTE_TIMEOUT_MS)),
+            };
+            Some(std::thread::spawn(move || {
+                crate::sources::search_remotes(&remotes, &search)
+            }))
+        }
+        None => None,
+    };
+
     // Track search timing breakdown (T7.4)
     let search_start = Instant::now();
     let result = match effective_mode {
+        _ if no_bookmarked_sessions => crate::search::query::SearchResult {
+            hits: Vec::new(),
+            wildcard_fallback: false,
+            cache_stats: crate::search::query::CacheStats::default(),
+            suggestions: Vec::new(),
+        },
         SearchMode::Lexical => client
             .search_with_fallback(
                 query,
@@ -4198,9 +5527,7 @@ fn run_cli_search(
     // Apply reranking if enabled (bd-2t2d)
     let rerank_start = Instant::now();
     let result = if semantic_opts.rerank && !result.hits.is_empty() {
-        use crate::search::daemon_client::{
-            DaemonFallbackReranker, DaemonRetryConfig, NoopDaemonClient,
-        };
+        use crate::search::daemon_client::{DaemonFallbackReranker, DaemonRetryConfig};
         use crate::search::fastembed_reranker::FastEmbedReranker;
         use crate::search::reranker::Reranker;
 
@@ -4217,7 +5544,7 @@ fn run_cli_search(
             };
 
         let reranker: Option<Arc<dyn Reranker>> = if semantic_opts.use_daemon {
-            let daemon = Arc::new(NoopDaemonClient::new("daemon-unconfigured"));
+            let daemon = semantic_daemon_client(&data_dir, None);
             let config = DaemonRetryConfig::from_env();
             Some(Arc::new(DaemonFallbackReranker::new(
                 daemon,
@@ -4303,13 +5630,58 @@ fn run_cli_search(
         0
     };
 
+    // Merge remote hits by rank. A source that answered live replaces any
+    // copies of its sessions that were synced into the local index.
+    let (result, federation) = match remote_searches {
+        Some(handle) => {
+            let outcomes = handle.join().unwrap_or_default();
+            let answered: HashSet<String> = outcomes
+                .iter()
+                .filter(|o| o.status == crate::sources::RemoteStatus::Ok)
+                .map(|o| o.source.clone())
+                .collect();
+            let meta = serde_json::json!({
+                "merge": "rrf",
+                "partial": answered.len() < outcomes.len(),
+                "sources": outcomes.iter().map(|o| o.to_meta()).collect::<Vec<_>>(),
+            });
+            if effective_robot.is_none() {
+                for o in outcomes.iter().filter(|o| o.error.is_some()) {
+                    eprintln!(
+                        "Warning: remote source '{}' ({}) skipped: {}",
+                        o.source,
+                        o.host,
+                        o.error.as_deref().unwrap_or_default()
+                    );
+                }
+            }
+
+            let local: Vec<_> = result
+                .hits
+                .into_iter()
+                .filter(|hit| !answered.contains(&hit.source_id))
+                .collect();
+            let mut lists = vec![local];
+            lists.extend(outcomes.into_iter().map(|o| o.hits));
+            let merged = crate::sources::federated::rrf_merge(lists);
+            (
+                crate::search::query::SearchResult {
+                    hits: merged,
+                    ..result
+                },
+                Some(meta),
+            )
+        }
+        None => (result, None),
+    };
+
     // Check if search exceeded timeout - return partial results with timeout indicator
     let timed_out = timeout_duration.is_some_and(|t| start_time.elapsed() > t);
 
     // Build query explanation if requested
     let explanation = if explain {
         Some(
-            QueryExplanation::analyze(query, &filters)
+            QueryExplanation::analyze(full_query, &filters)
                 .with_wildcard_fallback(result.wildcard_fallback),
         )
     } else {
@@ -4317,7 +5689,7 @@ fn run_cli_search(
     };
 
     // Compute aggregations and create display result based on mode
-    let (aggregations, display_result, total_matches) = if has_aggregation {
+    let (aggregations, display_result, total_matches) = if has_aggregation || federation.is_some() {
         // Compute aggregations from all fetched results
         let aggs = compute_aggregations(&result.hits, &agg_fields);
         let total = result.hits.len();
@@ -4437,7 +5809,7 @@ fn run_cli_search(
     if let Some(format) = effective_robot {
         // Robot output mode (JSON)
         output_robot_results(
-            query,
+            full_query,
             limit_val,
             offset_val,
             &display_result,
@@ -4461,6 +5833,7 @@ fn run_cli_search(
             effective_mode,
             search_ms,
             rerank_ms,
+            federation,
         )?;
     } else if display_result.hits.is_empty() {
         eprintln!("No results found.");
@@ -4490,6 +5863,31 @@ fn run_cli_search(
     Ok(())
 }
 
+fn open_search_client(
+    index_path: &Path,
+    db_path: &Path,
+    options: crate::search::query::SearchClientOptions,
+) -> CliResult<crate::search::query::SearchClient> {
+    crate::search::query::SearchClient::open_with_options(index_path, Some(db_path), options)
+        .map_err(|e| CliError {
+            code: 9,
+            kind: "open-index",
+            message: format!("failed to open index: {e}"),
+            hint: Some("try cass index --full".to_string()),
+            retryable: true,
+        })?
+        .ok_or_else(|| CliError {
+            code: 3,
+            kind: "missing-index",
+            message: format!(
+                "Index not found at {}. Run 'cass index --full' first.",
+                index_path.display()
+            ),
+            hint: None,
+            retryable: true,
+        })
+}
+
 /// Output search results in human-readable display format
 fn output_display_results(
     hits: &[crate::search::query::SearchHit],
@@ -4664,6 +6062,7 @@ fn filter_hit_fields(
                 "source_id",
                 "origin_kind",
                 "origin_host",
+                "parent",
             ];
 
             for field in field_list {
@@ -4816,7 +6215,41 @@ fn toon_encode_options_from_env() -> toon_rust::EncodeOptions {
     }
 }
 
+thread_local! {
+    /// Set while [`capture_structured_output`] runs: payloads are kept instead of printed.
+    static CAPTURED_OUTPUT: std::cell::RefCell<Option<Vec<serde_json::Value>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Run a command in-process and return the payload it would have printed (`cass mcp`, `cass serve`).
+fn capture_structured_output(run: impl FnOnce() -> CliResult<()>) -> CliResult<serde_json::Value> {
+    CAPTURED_OUTPUT.with(|slot| *slot.borrow_mut() = Some(Vec::new()));
+    let result = run();
+    let captured = CAPTURED_OUTPUT
+        .with(|slot| slot.borrow_mut().take())
+        .unwrap_or_default();
+    result?;
+    captured
+        .into_iter()
+        .last()
+        .ok_or_else(|| CliError::unknown("command produced no structured output"))
+}
+
+/// Keep `payload` if [`capture_structured_output`] is running, otherwise hand it back to print.
+fn capture_payload(payload: serde_json::Value) -> Option<serde_json::Value> {
+    CAPTURED_OUTPUT.with(|slot| match slot.borrow_mut().as_mut() {
+        Some(captured) => {
+            captured.push(payload);
+            None
+        }
+        None => Some(payload),
+    })
+}
+
 fn output_structured_value(payload: serde_json::Value, format: RobotFormat) -> CliResult<()> {
+    let Some(payload) = capture_payload(payload) else {
+        return Ok(());
+    };
     match format {
         RobotFormat::Json => {
             println!(
@@ -4835,6 +6268,22 @@ fn output_structured_value(payload: serde_json::Value, format: RobotFormat) -> C
     Ok(())
 }
 
+/// Line-oriented robot output (`jsonl`, `sessions`): one value per line, strings as-is.
+/// While [`capture_structured_output`] runs the lines are kept as one array instead.
+fn output_structured_lines(lines: Vec<serde_json::Value>) -> CliResult<()> {
+    let Some(serde_json::Value::Array(lines)) = capture_payload(serde_json::Value::Array(lines))
+    else {
+        return Ok(());
+    };
+    for line in lines {
+        match line {
+            serde_json::Value::String(text) => println!("{text}"),
+            other => println!("{}", serde_json::to_string(&other).unwrap_or_default()),
+        }
+    }
+    Ok(())
+}
+
 /// Output search results in robot-friendly format
 #[allow(clippy::too_many_arguments, unused_variables)]
 fn output_robot_results(
@@ -4862,6 +6311,7 @@ fn output_robot_results(
     search_mode: crate::search::query::SearchMode,
     search_ms: u64,
     rerank_ms: u64,
+    federation: Option<serde_json::Value>,
 ) -> CliResult<()> {
     if matches!(format, RobotFormat::Sessions) {
         // Output unique session paths only, one per line.
@@ -4872,10 +6322,7 @@ fn output_robot_results(
             .iter()
             .map(|hit| hit.source_path.as_str())
             .collect();
-        for path in paths {
-            println!("{path}");
-        }
-        return Ok(());
+        return output_structured_lines(paths.into_iter().map(serde_json::Value::from).collect());
     }
 
     // Expand presets (minimal, summary, provenance, all, *)
@@ -5003,22 +6450,20 @@ fn output_robot_results(
                     );
                 }
             }
+            if let Some(fed) = &federation {
+                attach_federation_meta(&mut payload, fed);
+            }
 
-            let out = serde_json::to_string_pretty(&payload).map_err(|e| CliError {
-                code: 9,
-                kind: "encode-json",
-                message: format!("failed to encode json: {e}"),
-                hint: None,
-                retryable: false,
-            })?;
-            println!("{out}");
+            output_structured_value(payload, RobotFormat::Json)?;
         }
         RobotFormat::Jsonl => {
             // JSONL: one object per line, optional _meta header
+            let mut lines = Vec::with_capacity(filtered_hits.len() + 1);
             if include_meta
                 || agg_json.is_some()
                 || !result.suggestions.is_empty()
                 || explanation.is_some()
+                || federation.is_some()
             {
                 let mut meta = serde_json::json!({
                     "_meta": {
@@ -5105,12 +6550,14 @@ fn output_robot_results(
                         }),
                     );
                 }
-                println!("{}", serde_json::to_string(&meta).unwrap_or_default());
+                if let Some(fed) = &federation {
+                    attach_federation_meta(&mut meta, fed);
+                }
+                lines.push(meta);
             }
             // One hit per line (with field filtering applied)
-            for hit in &filtered_hits {
-                println!("{}", serde_json::to_string(hit).unwrap_or_default());
-            }
+            lines.extend(filtered_hits);
+            output_structured_lines(lines)?;
         }
         RobotFormat::Compact => {
             // Single-line compact JSON
@@ -5203,14 +6650,11 @@ fn output_robot_results(
                 }
             }
 
-            let out = serde_json::to_string(&payload).map_err(|e| CliError {
-                code: 9,
-                kind: "encode-json",
-                message: format!("failed to encode json: {e}"),
-                hint: None,
-                retryable: false,
-            })?;
-            println!("{out}");
+            if let Some(fed) = &federation {
+                attach_federation_meta(&mut payload, fed);
+            }
+
+            output_structured_value(payload, RobotFormat::Compact)?;
         }
         RobotFormat::Toon => {
             // TOON: Token-Optimized Object Notation
@@ -5302,20 +6746,11 @@ fn output_robot_results(
                 }
             }
 
-            let json_str = serde_json::to_string(&payload).map_err(|e| CliError {
-                code: 9,
-                kind: "encode-json",
-                message: format!("failed to encode json: {e}"),
-                hint: None,
-                retryable: false,
-            })?;
-
-            let toon_str = toon_rust::encode(payload, Some(toon_encode_options_from_env()));
+            if let Some(fed) = &federation {
+                attach_federation_meta(&mut payload, fed);
+            }
 
-            // Preserve the existing "compact JSON" behavior by first ensuring the payload is
-            // valid JSON (serde_json::to_string above). We don't need the string itself here.
-            drop(json_str);
-            print!("{toon_str}");
+            output_structured_value(payload, RobotFormat::Toon)?;
         }
         RobotFormat::Sessions => {
             unreachable!("RobotFormat::Sessions is handled above to avoid building hit payloads");
@@ -5325,6 +6760,22 @@ fn output_robot_results(
     Ok(())
 }
 
+/// SQL condition that keeps `--source remote` from matching `cass import` sources.
+const NOT_IMPORTED_SQL: &str = "c.source_id NOT IN (SELECT id FROM sources WHERE kind = 'import')";
+
+/// Add `_meta.federation`, creating `_meta` when `--robot-meta` was not given:
+/// remote failures must be visible either way.
+fn attach_federation_meta(payload: &mut serde_json::Value, federation: &serde_json::Value) {
+    if let Some(map) = payload.as_object_mut()
+        && let Some(meta) = map
+            .entry("_meta")
+            .or_insert_with(|| serde_json::json!({}))
+            .as_object_mut()
+    {
+        meta.insert("federation".to_string(), federation.clone());
+    }
+}
+
 fn run_stats(
     data_dir_override: &Option<PathBuf>,
     db_override: Option<PathBuf>,
@@ -5366,7 +6817,10 @@ fn run_stats(
     let (source_where, source_param): (String, Option<String>) = match &source_filter {
         None | Some(SourceFilter::All) => (String::new(), None),
         Some(SourceFilter::Local) => (" WHERE c.source_id = 'local'".to_string(), None),
-        Some(SourceFilter::Remote) => (" WHERE c.source_id != 'local'".to_string(), None),
+        Some(SourceFilter::Remote) => (
+            format!(" WHERE c.source_id != 'local' AND {NOT_IMPORTED_SQL}"),
+            None,
+        ),
         Some(SourceFilter::SourceId(id)) => {
             (" WHERE c.source_id = ?".to_string(), Some(id.clone()))
         }
@@ -5862,6 +7316,7 @@ fn run_status(
     let index_path = crate::search::tantivy::index_dir(&data_dir)
         .unwrap_or_else(|_| data_dir.join("index").join("v4"));
     let watch_state_path = data_dir.join("watch_state.json");
+    let vault = vault_report(&data_dir);
 
     // Check if database exists
     let db_exists = db_path.exists();
@@ -5920,10 +7375,15 @@ fn run_status(
     };
 
     // Determine overall health
-    let healthy = db_exists && index_exists && !is_stale;
+    let healthy = !vault.locked && db_exists && index_exists && !is_stale;
 
     // Build recommended action
-    let recommended_action = if !db_exists {
+    let recommended_action = if vault.locked {
+        Some(format!(
+            "Run 'cass vault unlock --data-dir {}' to decrypt the data directory",
+            data_dir.display()
+        ))
+    } else if !db_exists {
         Some("Run 'cass index --full' to create the database".to_string())
     } else if !index_exists {
         Some("Run 'cass index --full' to rebuild the search index".to_string())
@@ -5980,6 +7440,7 @@ fn run_status(
                 "sessions": pending_sessions,
                 "watch_active": watch_state_path.exists(),
             },
+            "vault": vault_status_json(&vault),
             "recommended_action": recommended_action,
             "_meta": {
                 "timestamp": ts_str,
@@ -6001,6 +7462,11 @@ fn run_status(
     println!("{status_icon} CASS Status: {status_word}");
     println!();
 
+    if vault.enabled {
+        println!("Vault: {}", describe_vault_status(&vault));
+        println!();
+    }
+
     // Index info
     println!("Index:");
     if index_exists {
@@ -6085,9 +7551,15 @@ fn run_health(
         .and_then(|v| v.as_u64())
         .unwrap_or(0);
 
+    let vault_locked = state
+        .get("vault")
+        .and_then(|v| v.get("locked"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+
     // Core operational health: can the tool be used at all?
     // Freshness and pending sessions are informational (reported in state) but don't prevent searching
-    let healthy = db_exists && index_exists;
+    let healthy = !vault_locked && db_exists && index_exists;
     let latency_ms = start.elapsed().as_millis() as u64;
 
     let structured_format = if json {
@@ -6119,6 +7591,10 @@ fn run_health(
         if pending_sessions > 0 {
             println!("  Note: {pending_sessions} sessions pending reindex");
         }
+    } else if vault_locked {
+        println!("✗ Unhealthy ({latency_ms}ms)");
+        println!("  - data directory is encrypted and locked");
+        println!("Run 'cass vault unlock' to decrypt it.");
     } else {
         println!("✗ Unhealthy ({latency_ms}ms)");
         if !db_exists {
@@ -6132,6 +7608,14 @@ fn run_health(
 
     if healthy {
         Ok(())
+    } else if vault_locked {
+        Err(CliError {
+            code: 1,
+            kind: "health",
+            message: "Health check failed: the data directory is locked".to_string(),
+            hint: Some("Run 'cass vault unlock' to decrypt it.".to_string()),
+            retryable: false,
+        })
     } else {
         Err(CliError {
             code: 1,
@@ -6993,6 +8477,9 @@ fn run_doctor(
                     data_dir: data_dir.clone(),
                     semantic: false,
                     embedder: "fastembed".to_string(),
+                    ann: false,
+                    quantization: crate::search::vector_index::Quantization::F32,
+                    redact_secrets: false,
                     progress: Some(progress.clone()),
                 };
 
@@ -7712,6 +9199,30 @@ fn run_capabilities(json: bool) -> CliResult<()> {
             "expand_command".to_string(),
             "timeline_command".to_string(),
             "highlight_matches".to_string(),
+            "bookmarks_command".to_string(),
+            "tag_command".to_string(),
+            "daemon_command".to_string(),
+            "role_filter".to_string(),
+            "files_command".to_string(),
+            "ann_index".to_string(),
+            "vector_quantization".to_string(),
+            "field_qualifiers".to_string(),
+            "forget_command".to_string(),
+            "pages_key_command".to_string(),
+            "scan_secrets_command".to_string(),
+            "ingest_redaction".to_string(),
+            "vault_command".to_string(),
+            "mcp_server".to_string(),
+            "http_server".to_string(),
+            "generic_connector".to_string(),
+            "import_command".to_string(),
+            "usage_command".to_string(),
+            "subagent_threads".to_string(),
+            "federated_search".to_string(),
+            "scheduled_sync".to_string(),
+            "path_sources".to_string(),
+            "session_bundles".to_string(),
+            "html_batch_export".to_string(),
         ],
         connectors: vec![
             "codex".to_string(),
@@ -7890,69 +9401,626 @@ fn run_introspect(json: bool) -> CliResult<()> {
     Ok(())
 }
 
-/// Run export based on JSON config file.
-fn run_config_based_export(
-    config: &crate::pages::config_input::PagesConfig,
-    wizard_state: &crate::pages::wizard::WizardState,
-    db_path: &std::path::Path,
-    dry_run: bool,
-    json_output: bool,
-    _verbose: bool,
-) -> anyhow::Result<()> {
-    use chrono::DateTime;
-    use rand::RngCore;
-    use std::sync::Arc;
-    use std::sync::atomic::AtomicBool;
+fn run_pages_key_command(cmd: PagesKeyCommand) -> CliResult<()> {
+    use crate::pages::key_management::{
+        key_add_password, key_add_recovery, key_list, key_revoke, key_rotate,
+    };
 
-    if dry_run {
-        if json_output {
-            let result = serde_json::json!({
-                "status": "dry_run",
-                "output_dir": wizard_state.output_dir,
-                "config_valid": true,
-            });
-            println!("{}", serde_json::to_string_pretty(&result)?);
-        } else {
-            println!("Dry run: would export to {:?}", wizard_state.output_dir);
+    let structured_format = if cmd.json() {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+    let structured = structured_format.is_some();
+    let key_error = |action: &str, e: anyhow::Error| CliError {
+        code: 9,
+        kind: "pages",
+        message: format!("Key {action} failed: {e:#}"),
+        hint: None,
+        retryable: false,
+    };
+
+    match cmd {
+        PagesKeyCommand::List { archive, .. } => {
+            let site_dir = pages_archive_dir(&archive)?;
+            let result = key_list(&site_dir).map_err(|e| key_error("list", e))?;
+            if let Some(fmt) = structured_format {
+                let payload = serde_json::to_value(&result)
+                    .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+                return output_structured_value(payload, fmt);
+            }
+            println!(
+                "Archive {}: {} key slot(s)",
+                result.export_id, result.active_slots
+            );
+            for slot in &result.slots {
+                println!("  #{:<3} {:9} {}", slot.id, slot.slot_type, slot.kdf);
+            }
+            Ok(())
         }
-        return Ok(());
-    }
+        PagesKeyCommand::Add {
+            archive,
+            recovery,
+            password_stdin,
+            ..
+        } => {
+            let site_dir = pages_archive_dir(&archive)?;
+            let (slot_id, recovery_secret) = if recovery {
+                let passwords = read_cli_passwords(
+                    &[("Current archive password", false)],
+                    password_stdin,
+                    structured,
+                )?;
+                let (slot_id, secret) =
+                    key_add_recovery(&site_dir, &passwords[0]).map_err(|e| key_error("add", e))?;
+                (slot_id, Some(secret.encoded().to_string()))
+            } else {
+                let passwords = read_cli_passwords(
+                    &[
+                        ("Current archive password", false),
+                        ("New password (min 8 characters)", true),
+                    ],
+                    password_stdin,
+                    structured,
+                )?;
+                let slot_id = key_add_password(&site_dir, &passwords[0], &passwords[1])
+                    .map_err(|e| key_error("add", e))?;
+                (slot_id, None)
+            };
+            let slot_type = if recovery { "recovery" } else { "password" };
 
-    // Output directory is the bundle root (contains site/ and private/)
-    let output_dir = &wizard_state.output_dir;
-    std::fs::create_dir_all(output_dir)?;
+            if let Some(fmt) = structured_format {
+                let mut payload = serde_json::json!({
+                    "slot_id": slot_id,
+                    "slot_type": slot_type,
+                });
+                if let Some(secret) = &recovery_secret {
+                    payload["recovery_secret"] = serde_json::json!(secret);
+                }
+                return output_structured_value(payload, fmt);
+            }
+            println!("Added {slot_type} slot #{slot_id}");
+            if let Some(secret) = &recovery_secret {
+                print_pages_recovery_secret(secret);
+            }
+            Ok(())
+        }
+        PagesKeyCommand::Revoke {
+            archive,
+            slot,
+            password_stdin,
+            ..
+        } => {
+            let site_dir = pages_archive_dir(&archive)?;
+            let passwords = read_cli_passwords(
+                &[("Password of a slot you keep", false)],
+                password_stdin,
+                structured,
+            )?;
+            let result =
+                key_revoke(&site_dir, &passwords[0], slot).map_err(|e| key_error("revoke", e))?;
+            if let Some(fmt) = structured_format {
+                let payload = serde_json::to_value(&result)
+                    .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+                return output_structured_value(payload, fmt);
+            }
+            println!(
+                "Revoked slot #{} ({} slot(s) remaining)",
+                result.revoked_slot_id, result.remaining_slots
+            );
+            Ok(())
+        }
+        PagesKeyCommand::Rotate {
+            archive,
+            recovery,
+            password_stdin,
+            ..
+        } => {
+            let site_dir = pages_archive_dir(&archive)?;
+            let passwords = read_cli_passwords(
+                &[
+                    ("Current archive password", false),
+                    ("New password (min 8 characters)", true),
+                ],
+                password_stdin,
+                structured,
+            )?;
+            let result = key_rotate(&site_dir, &passwords[0], &passwords[1], recovery, |_| {})
+                .map_err(|e| key_error("rotate", e))?;
+            // The payload was rewritten; make sure the archive still verifies.
+            let verify = crate::pages::verify::verify_bundle(&site_dir, false)
+                .map_err(|e| key_error("rotate verification", e))?;
+            let valid = verify.status == "valid";
+
+            if let Some(fmt) = structured_format {
+                let mut payload = serde_json::to_value(&result)
+                    .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+                payload["verify"] = serde_json::to_value(&verify)
+                    .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+                output_structured_value(payload, fmt)?;
+            } else {
+                println!(
+                    "Rotated archive key: payload re-encrypted, {} slot(s)",
+                    result.slot_count
+                );
+                if let Some(secret) = &result.recovery_secret {
+                    print_pages_recovery_secret(secret);
+                }
+                crate::pages::verify::print_result(&verify, false);
+            }
 
-    // Create temp directory for intermediate export and encryption output
-    let temp_dir = tempfile::tempdir()?;
-    let export_db_path = temp_dir.path().join("export.db");
-    let encrypted_dir = temp_dir.path().join("encrypted");
-    std::fs::create_dir_all(&encrypted_dir)?;
+            if !valid {
+                return Err(CliError {
+                    code: 1,
+                    kind: "pages",
+                    message: "Archive failed verification after key rotation".to_string(),
+                    hint: Some(format!(
+                        "Inspect with: cass pages --verify {} --verbose",
+                        archive.display()
+                    )),
+                    retryable: false,
+                });
+            }
+            Ok(())
+        }
+    }
+}
 
-    // Parse time filters to DateTime<Utc>
-    let since_dt = config.since_ts().and_then(DateTime::from_timestamp_millis);
-    let until_dt = config.until_ts().and_then(DateTime::from_timestamp_millis);
+/// Resolve a `cass pages key` archive argument to the directory holding config.json
+fn pages_archive_dir(archive: &Path) -> CliResult<PathBuf> {
+    let site_dir = crate::pages::verify::resolve_site_dir(archive).map_err(|e| CliError {
+        code: 3,
+        kind: "pages",
+        message: e.to_string(),
+        hint: None,
+        retryable: false,
+    })?;
+    if !site_dir.join("config.json").exists() {
+        return Err(CliError {
+            code: 3,
+            kind: "pages",
+            message: format!("No config.json in {}", site_dir.display()),
+            hint: Some("Pass the directory of an encrypted `cass pages` export".to_string()),
+            retryable: false,
+        });
+    }
+    Ok(site_dir)
+}
 
-    // Build export filter
-    let filter = crate::pages::export::ExportFilter {
-        agents: if wizard_state.agents.is_empty() {
-            None
-        } else {
-            Some(wizard_state.agents.clone())
-        },
-        workspaces: wizard_state.workspaces.clone(),
-        since: since_dt,
-        until: until_dt,
-        path_mode: config.path_mode(),
+/// Collect the passwords a key operation needs, in order. Prompts flagged as new
+/// ask for confirmation; with `--password-stdin` each password is one line of stdin.
+fn read_cli_passwords(
+    prompts: &[(&str, bool)],
+    password_stdin: bool,
+    structured: bool,
+) -> CliResult<Vec<String>> {
+    let password_error = |kind: &'static str, message: String, hint: Option<String>| CliError {
+        code: 6,
+        kind,
+        message,
+        hint,
+        retryable: false,
     };
 
-    // Run export
-    let export_engine = crate::pages::export::ExportEngine::new(db_path, &export_db_path, filter);
-
-    let running = Arc::new(AtomicBool::new(true));
-    let stats = export_engine.execute(|_current, _total| {}, Some(running))?;
-
-    let mut recovery_secret: Option<Vec<u8>> = None;
-    let encryption_enabled = !wizard_state.no_encryption;
+    let passwords: Vec<String> = if password_stdin {
+        let mut lines = std::io::stdin().lines();
+        prompts
+            .iter()
+            .map(|(prompt, _)| match lines.next() {
+                Some(Ok(line)) => Ok(line),
+                Some(Err(e)) => Err(password_error(
+                    "password_read_error",
+                    format!("Failed to read password from stdin: {e}"),
+                    None,
+                )),
+                None => Err(password_error(
+                    "password_required",
+                    format!("Missing on stdin: {prompt}"),
+                    Some(format!("Pass {} password line(s) on stdin", prompts.len())),
+                )),
+            })
+            .collect::<CliResult<_>>()?
+    } else if structured {
+        return Err(password_error(
+            "password_required",
+            "Passwords are not prompted for in robot mode".to_string(),
+            Some("Use --password-stdin".to_string()),
+        ));
+    } else {
+        prompts
+            .iter()
+            .map(|(prompt, new)| {
+                let mut input = dialoguer::Password::new().with_prompt(*prompt);
+                if *new {
+                    input = input.with_confirmation("Confirm password", "Passwords don't match");
+                }
+                input.interact().map_err(|e| {
+                    password_error(
+                        "password_read_error",
+                        format!("Failed to read password: {e}"),
+                        Some("Use --password-stdin when not running in a terminal".to_string()),
+                    )
+                })
+            })
+            .collect::<CliResult<_>>()?
+    };
+
+    for ((prompt, new), password) in prompts.iter().zip(&passwords) {
+        if *new && password.len() < 8 {
+            return Err(CliError::usage(
+                format!("{prompt}: password must be at least 8 characters"),
+                None,
+            ));
+        }
+    }
+    Ok(passwords)
+}
+
+fn print_pages_recovery_secret(secret: &str) {
+    println!();
+    println!("Recovery secret (store it offline; it is not shown again):");
+    println!("  {secret}");
+}
+
+/// Data dir that must be unlocked before `command` runs, if it reads or writes one.
+///
+/// Reporting commands (status, health, state, vault) handle a locked vault themselves.
+fn vault_gated_data_dir(command: &Commands) -> Option<PathBuf> {
+    let data_dir = match command {
+        Commands::Completions { .. }
+        | Commands::Man
+        | Commands::RobotDocs { .. }
+        | Commands::Capabilities { .. }
+        | Commands::ApiVersion { .. }
+        | Commands::Introspect { .. }
+        | Commands::Status { .. }
+        | Commands::State { .. }
+        | Commands::Health { .. }
+        | Commands::Models(..)
+        | Commands::Vault(..) => return None,
+        Commands::Tui { data_dir, .. }
+        | Commands::Index { data_dir, .. }
+        | Commands::Search { data_dir, .. }
+        | Commands::Stats { data_dir, .. }
+        | Commands::Diag { data_dir, .. }
+        | Commands::Doctor { data_dir, .. }
+        | Commands::Context { data_dir, .. }
+        | Commands::Timeline { data_dir, .. }
+        | Commands::Files { data_dir, .. }
+        | Commands::Forget { data_dir, .. }
+        | Commands::ScanSecrets { data_dir, .. }
+        | Commands::Import { data_dir, .. }
+        | Commands::Usage { data_dir, .. }
+        | Commands::Daemon { data_dir, .. }
+        | Commands::Mcp { data_dir }
+        | Commands::Serve { data_dir, .. }
+        | Commands::Tag(
+            TagCommand::Add { data_dir, .. }
+            | TagCommand::Remove { data_dir, .. }
+            | TagCommand::List { data_dir, .. },
+        )
+        | Commands::ExportHtml { data_dir, .. }
+        | Commands::Bundle(
+            BundleCommand::Export { data_dir, .. } | BundleCommand::Import { data_dir, .. },
+        ) => data_dir.clone(),
+        // Everything else works on the default data dir
+        _ => None,
+    };
+    Some(data_dir.unwrap_or_else(default_data_dir))
+}
+
+/// Long-running commands, which keep the database and indexes open for their lifetime.
+fn holds_data_dir_open(command: &Commands) -> bool {
+    match command {
+        Commands::Tui { once, .. } => !once,
+        Commands::Index { watch, .. } => *watch,
+        Commands::Daemon { status, stop, .. } => !status && !stop,
+        Commands::Mcp { .. } | Commands::Serve { .. } => true,
+        _ => false,
+    }
+}
+
+/// Re-lock an expired vault session and refuse to run against a locked data dir.
+fn ensure_vault_unlocked(data_dir: &Path) -> CliResult<()> {
+    crate::vault::ensure_unlocked(data_dir).map_err(|e| vault_error(data_dir, e))
+}
+
+fn vault_error(data_dir: &Path, e: anyhow::Error) -> CliError {
+    use crate::vault::VaultError;
+
+    let data_dir_arg = format!(" --data-dir {}", data_dir.display());
+    let (code, kind, hint, retryable) = match e.downcast_ref::<VaultError>() {
+        Some(VaultError::Locked) => (
+            3,
+            "vault_locked",
+            Some(format!("Run 'cass vault unlock{data_dir_arg}' first")),
+            false,
+        ),
+        Some(VaultError::NotEnabled) => (
+            3,
+            "vault",
+            Some(format!("Set one up with 'cass vault init{data_dir_arg}'")),
+            false,
+        ),
+        Some(VaultError::AlreadyEnabled) => (2, "vault", None, false),
+        Some(VaultError::WrongSecret) => (6, "vault_auth", None, false),
+        Some(VaultError::NoSession) => (
+            9,
+            "vault",
+            Some(format!(
+                "Run 'cass vault unlock{data_dir_arg}' to start a session, then lock"
+            )),
+            false,
+        ),
+        Some(VaultError::InUse) => (
+            9,
+            "vault_in_use",
+            Some(
+                "Stop the running cass TUI, index --watch, serve, mcp or daemon, then lock"
+                    .to_string(),
+            ),
+            true,
+        ),
+        Some(VaultError::Conflict(_)) => (
+            9,
+            "vault_conflict",
+            Some(
+                "Move those files aside (they were created while the vault was locked), then unlock"
+                    .to_string(),
+            ),
+            false,
+        ),
+        None => (9, "vault", None, true),
+    };
+    CliError {
+        code,
+        kind,
+        message: format!("Vault: {e:#}"),
+        hint,
+        retryable,
+    }
+}
+
+fn vault_status_json(status: &crate::vault::VaultStatus) -> serde_json::Value {
+    serde_json::json!({
+        "enabled": status.enabled,
+        "locked": status.locked,
+        "session_timeout_seconds": status.session_timeout_secs,
+        "expires_at": status.expires_at.and_then(|ts| {
+            chrono::DateTime::from_timestamp_millis(ts).map(|d| d.to_rfc3339())
+        }),
+        "password_slots": status.password_slots,
+        "key_file_slots": status.key_file_slots,
+    })
+}
+
+/// Vault state of `data_dir` for status/health. Read-only: an expired session is
+/// reported, not locked.
+fn vault_report(data_dir: &Path) -> crate::vault::VaultStatus {
+    crate::vault::status(data_dir).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "could not read vault state");
+        crate::vault::VaultStatus::default()
+    })
+}
+
+fn describe_vault_status(status: &crate::vault::VaultStatus) -> String {
+    if !status.enabled {
+        "not encrypted".to_string()
+    } else if status.locked {
+        "encrypted, locked".to_string()
+    } else {
+        let until = status
+            .expires_at
+            .and_then(chrono::DateTime::from_timestamp_millis)
+            .map(|d| {
+                d.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_else(|| "unknown".to_string());
+        if status
+            .expires_at
+            .is_some_and(|at| at <= chrono::Utc::now().timestamp_millis())
+        {
+            return format!("encrypted, session expired at {until} (locks on next use)");
+        }
+        format!("encrypted, unlocked until {until}")
+    }
+}
+
+fn run_vault_command(cmd: VaultCommand) -> CliResult<()> {
+    use crate::vault::VaultSecret;
+
+    let structured_format = if cmd.json() {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+    let structured = structured_format.is_some();
+    let data_dir = cmd.data_dir();
+    let key_file_error = |e: anyhow::Error| CliError {
+        code: 6,
+        kind: "vault_auth",
+        message: format!("{e:#}"),
+        hint: None,
+        retryable: false,
+    };
+
+    let (action, status, key_file_created) = match cmd {
+        VaultCommand::Init {
+            key_file,
+            timeout,
+            password_stdin,
+            ..
+        } => {
+            if crate::vault::status(&data_dir)
+                .map_err(|e| vault_error(&data_dir, e))?
+                .enabled
+            {
+                return Err(vault_error(
+                    &data_dir,
+                    crate::vault::VaultError::AlreadyEnabled.into(),
+                ));
+            }
+            let passwords = read_cli_passwords(
+                &[("New vault password (min 8 characters)", true)],
+                password_stdin,
+                structured,
+            )?;
+            let (secret, created) = match &key_file {
+                Some(path) if path.exists() => (
+                    Some(crate::vault::read_key_file(path).map_err(key_file_error)?),
+                    None,
+                ),
+                Some(path) => (
+                    Some(crate::vault::create_key_file(path).map_err(key_file_error)?),
+                    Some(path.display().to_string()),
+                ),
+                None => (None, None),
+            };
+            std::fs::create_dir_all(&data_dir).map_err(|e| CliError {
+                code: 9,
+                kind: "vault",
+                message: format!("Failed to create data dir {}: {e}", data_dir.display()),
+                hint: None,
+                retryable: false,
+            })?;
+            let status = crate::vault::init(
+                &data_dir,
+                &passwords[0],
+                secret.as_deref(),
+                timeout.saturating_mul(60),
+            )
+            .map_err(|e| vault_error(&data_dir, e))?;
+            ("init", status, created)
+        }
+        VaultCommand::Unlock {
+            key_file,
+            timeout,
+            password_stdin,
+            ..
+        } => {
+            let status = if let Some(path) = &key_file {
+                let secret = crate::vault::read_key_file(path).map_err(key_file_error)?;
+                crate::vault::unlock(
+                    &data_dir,
+                    VaultSecret::KeyFile(&secret),
+                    timeout.map(|m| m.saturating_mul(60)),
+                )
+            } else {
+                if !crate::vault::status(&data_dir)
+                    .map_err(|e| vault_error(&data_dir, e))?
+                    .enabled
+                {
+                    return Err(vault_error(
+                        &data_dir,
+                        crate::vault::VaultError::NotEnabled.into(),
+                    ));
+                }
+                let passwords =
+                    read_cli_passwords(&[("Vault password", false)], password_stdin, structured)?;
+                crate::vault::unlock(
+                    &data_dir,
+                    VaultSecret::Password(&passwords[0]),
+                    timeout.map(|m| m.saturating_mul(60)),
+                )
+            }
+            .map_err(|e| vault_error(&data_dir, e))?;
+            ("unlock", status, None)
+        }
+        VaultCommand::Lock { .. } => {
+            let status = crate::vault::lock(&data_dir).map_err(|e| vault_error(&data_dir, e))?;
+            ("lock", status, None)
+        }
+        VaultCommand::Status { .. } => ("status", vault_report(&data_dir), None),
+        VaultCommand::Disable { .. } => {
+            crate::vault::disable(&data_dir).map_err(|e| vault_error(&data_dir, e))?;
+            ("disable", crate::vault::VaultStatus::default(), None)
+        }
+    };
+
+    if let Some(fmt) = structured_format {
+        let mut payload = serde_json::json!({
+            "action": action,
+            "data_dir": data_dir.display().to_string(),
+            "vault": vault_status_json(&status),
+        });
+        if let Some(path) = &key_file_created {
+            payload["key_file_created"] = serde_json::json!(path);
+        }
+        return output_structured_value(payload, fmt);
+    }
+
+    if let Some(path) = &key_file_created {
+        println!("Created key file {path} (keep it off this disk, e.g. on a USB key).");
+    }
+    println!("{}: {}", data_dir.display(), describe_vault_status(&status));
+    Ok(())
+}
+
+/// Run export based on JSON config file.
+fn run_config_based_export(
+    config: &crate::pages::config_input::PagesConfig,
+    wizard_state: &crate::pages::wizard::WizardState,
+    db_path: &std::path::Path,
+    dry_run: bool,
+    json_output: bool,
+    _verbose: bool,
+) -> anyhow::Result<()> {
+    use chrono::DateTime;
+    use rand::RngCore;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    if dry_run {
+        if json_output {
+            let result = serde_json::json!({
+                "status": "dry_run",
+                "output_dir": wizard_state.output_dir,
+                "config_valid": true,
+            });
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        } else {
+            println!("Dry run: would export to {:?}", wizard_state.output_dir);
+        }
+        return Ok(());
+    }
+
+    // Output directory is the bundle root (contains site/ and private/)
+    let output_dir = &wizard_state.output_dir;
+    std::fs::create_dir_all(output_dir)?;
+
+    // Create temp directory for intermediate export and encryption output
+    let temp_dir = tempfile::tempdir()?;
+    let export_db_path = temp_dir.path().join("export.db");
+    let encrypted_dir = temp_dir.path().join("encrypted");
+    std::fs::create_dir_all(&encrypted_dir)?;
+
+    // Parse time filters to DateTime<Utc>
+    let since_dt = config.since_ts().and_then(DateTime::from_timestamp_millis);
+    let until_dt = config.until_ts().and_then(DateTime::from_timestamp_millis);
+
+    // Build export filter
+    let filter = crate::pages::export::ExportFilter {
+        agents: if wizard_state.agents.is_empty() {
+            None
+        } else {
+            Some(wizard_state.agents.clone())
+        },
+        workspaces: wizard_state.workspaces.clone(),
+        since: since_dt,
+        until: until_dt,
+        path_mode: config.path_mode(),
+    };
+
+    // Run export
+    let export_engine = crate::pages::export::ExportEngine::new(db_path, &export_db_path, filter);
+
+    let running = Arc::new(AtomicBool::new(true));
+    let stats = export_engine.execute(|_current, _total| {}, Some(running))?;
+
+    let mut recovery_secret: Option<Vec<u8>> = None;
+    let encryption_enabled = !wizard_state.no_encryption;
 
     if encryption_enabled {
         let password = wizard_state
@@ -8315,8 +10383,16 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
                             "created_at": { "type": ["integer", "string", "null"] },
                             "match_type": { "type": ["string", "null"] },
                             "source_id": { "type": "string", "description": "Source identifier (e.g., 'local', 'work-laptop')" },
-                            "origin_kind": { "type": "string", "description": "Origin kind ('local' or 'ssh')" },
-                            "origin_host": { "type": ["string", "null"], "description": "Host label for remote sources" }
+                            "origin_kind": { "type": "string", "description": "Origin kind ('local', 'ssh', 'path' or 'bundle')" },
+                            "origin_host": { "type": ["string", "null"], "description": "Host label for remote sources" },
+                            "parent": {
+                                "type": ["object", "null"],
+                                "description": "Session that spawned the subagent transcript the hit comes from",
+                                "properties": {
+                                    "title": { "type": ["string", "null"] },
+                                    "source_path": { "type": "string" }
+                                }
+                            }
                         }
                     }
                 },
@@ -8664,10 +10740,14 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
                         "properties": {
                             "number": { "type": "integer" },
                             "content": { "type": "string" },
-                            "highlighted": { "type": "boolean" }
+                            "highlighted": { "type": "boolean" },
+                            "subagent": { "type": "string" },
+                            "spawns": { "type": "array" }
                         }
                     }
-                }
+                },
+                "subagents": { "type": "array" },
+                "parent": { "type": ["object", "null"] }
             }
         }),
     );
@@ -8764,7 +10844,69 @@ fn build_response_schemas() -> std::collections::HashMap<String, serde_json::Val
     schemas
 }
 
-fn run_view(path: &PathBuf, line: Option<usize>, context: usize, json: bool) -> CliResult<()> {
+/// Session line -> index in `threads.subagents` of the sidechain it belongs to.
+fn subagent_lines(
+    threads: &connectors::claude_code::SessionThreads,
+) -> std::collections::HashMap<usize, usize> {
+    threads
+        .subagents
+        .iter()
+        .enumerate()
+        .flat_map(|(i, t)| t.lines.iter().map(move |&line| (line, i)))
+        .collect()
+}
+
+/// Name of a subagent thread in `view`/`expand` output.
+fn subagent_label(thread: &connectors::claude_code::SubagentThread) -> String {
+    thread
+        .description
+        .clone()
+        .or_else(|| thread.agent_id.clone())
+        .unwrap_or_else(|| "subagent".to_string())
+}
+
+/// Mark a `view`/`expand` JSON entry with the subagent thread it belongs to
+/// and the threads it spawned (with whether each is collapsed).
+fn annotate_subagent_entry(
+    entry: &mut serde_json::Value,
+    thread: Option<&connectors::claude_code::SubagentThread>,
+    spawns: &[(&connectors::claude_code::SubagentThread, bool)],
+) {
+    if let Some(thread) = thread {
+        entry["subagent"] = subagent_label(thread).into();
+    }
+    if !spawns.is_empty() {
+        entry["spawns"] = spawns
+            .iter()
+            .map(|(t, collapsed)| {
+                serde_json::json!({
+                    "agent_id": t.agent_id,
+                    "description": t.description,
+                    "transcript": t.transcript,
+                    "inline_messages": t.messages.len(),
+                    "collapsed": collapsed,
+                })
+            })
+            .collect();
+    }
+}
+
+/// Header line naming the session a subagent transcript belongs to.
+fn print_subagent_parent(parent: &connectors::claude_code::ParentLink) {
+    println!(
+        "Subagent of: {} ({})",
+        parent.title.as_deref().unwrap_or("untitled session"),
+        parent.source_path.display()
+    );
+}
+
+fn run_view(
+    path: &PathBuf,
+    line: Option<usize>,
+    context: usize,
+    subagents: Option<SubagentDisplay>,
+    json: bool,
+) -> CliResult<()> {
     use std::fs::File;
     use std::io::{BufRead, BufReader};
 
@@ -8832,6 +10974,35 @@ fn run_view(path: &PathBuf, line: Option<usize>, context: usize, json: bool) ->
     // Only highlight a specific line if -n was explicitly provided
     let highlight_line = line.is_some();
 
+    let threads = subagents.and_then(|_| connectors::claude_code::session_threads(path));
+    let thread_lines = threads.as_ref().map(subagent_lines).unwrap_or_default();
+    // Collapsed sidechains stay open when the target line is inside them
+    let open_thread = highlight_line
+        .then(|| thread_lines.get(&target_line).copied())
+        .flatten();
+    let hidden = |line_num: usize| {
+        subagents == Some(SubagentDisplay::Collapse)
+            && thread_lines
+                .get(&line_num)
+                .is_some_and(|&t| Some(t) != open_thread)
+    };
+    // Threads spawned at a line, and whether each is collapsed
+    let spawned_at = |line_num: usize| {
+        threads.as_ref().map_or_else(Vec::new, |threads| {
+            threads
+                .subagents
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| t.spawned_at_line == Some(line_num))
+                .map(|(i, t)| {
+                    let collapsed =
+                        subagents == Some(SubagentDisplay::Collapse) && Some(i) != open_thread;
+                    (t, collapsed)
+                })
+                .collect()
+        })
+    };
+
     let structured_format = if json {
         Some(RobotFormat::Json)
     } else {
@@ -8851,38 +11022,83 @@ fn run_view(path: &PathBuf, line: Option<usize>, context: usize, json: bool) ->
             .enumerate()
             .skip(start)
             .take(end - start)
+            .filter(|(i, _)| !hidden(i + 1))
             .map(|(i, l)| {
-                serde_json::json!({
+                let mut entry = serde_json::json!({
                     "line": i + 1,
                     "content": l,
                     "highlighted": highlight_line && i + 1 == target_line,
-                })
+                });
+                if let Some(threads) = &threads {
+                    annotate_subagent_entry(
+                        &mut entry,
+                        thread_lines.get(&(i + 1)).map(|&t| &threads.subagents[t]),
+                        &spawned_at(i + 1),
+                    );
+                }
+                entry
             })
             .collect();
 
-        let payload = serde_json::json!({
+        let mut payload = serde_json::json!({
             "path": path.display().to_string(),
             "target_line": if highlight_line { Some(target_line) } else { None::<usize> },
             "context": context,
             "lines": content_lines,
             "total_lines": lines.len(),
         });
+        if let Some(threads) = &threads {
+            payload["subagents"] = serde_json::json!(threads.subagents);
+            payload["parent"] = serde_json::json!(threads.parent);
+        }
         return output_structured_value(payload, fmt);
     }
 
     println!("File: {}", path.display());
+    if let Some(parent) = threads.as_ref().and_then(|t| t.parent.as_ref()) {
+        print_subagent_parent(parent);
+    }
     if highlight_line {
         println!("Line: {target_line} (context: {context})");
     }
     println!("----------------------------------------");
+    let mut collapsed_shown = std::collections::HashSet::new();
     for (i, l) in lines.iter().enumerate().skip(start).take(end - start) {
         let line_num = i + 1;
+        let thread = thread_lines.get(&line_num).copied();
+        if hidden(line_num) {
+            if let (Some(t), Some(threads)) = (thread, &threads)
+                && collapsed_shown.insert(t)
+            {
+                let sub = &threads.subagents[t];
+                println!(
+                    "       ┆ ↳ subagent: {} ({} lines collapsed)",
+                    subagent_label(sub),
+                    sub.lines.len()
+                );
+            }
+            continue;
+        }
         let marker = if highlight_line && line_num == target_line {
             ">"
         } else {
             " "
         };
-        println!("{marker}{line_num:5} | {l}");
+        let nest = if thread.is_some() { "↳ " } else { "" };
+        println!("{marker}{line_num:5} | {nest}{l}");
+        for (sub, _) in spawned_at(line_num) {
+            let label = subagent_label(sub);
+            match (&sub.transcript, sub.lines.first(), sub.lines.last()) {
+                (Some(transcript), _, _) => println!(
+                    "       ┆ ↳ subagent: {label} (transcript: {})",
+                    transcript.display()
+                ),
+                (None, Some(first), Some(last)) => {
+                    println!("       ┆ ↳ subagent: {label} (lines {first}-{last})")
+                }
+                _ => println!("       ┆ ↳ subagent: {label}"),
+            }
+        }
     }
     println!("----------------------------------------");
     if lines.len() > end {
@@ -8914,6 +11130,9 @@ fn spawn_background_indexer(
             data_dir,
             semantic: false,
             embedder: "fastembed".to_string(),
+            ann: false,
+            quantization: crate::search::vector_index::Quantization::F32,
+            redact_secrets: false,
             progress,
         };
         // Pass the receiver to run_index so it can listen for commands
@@ -8942,6 +11161,9 @@ fn run_index_with_data(
     data_dir_override: Option<PathBuf>,
     semantic: bool,
     embedder: String,
+    ann: bool,
+    quantization: crate::search::vector_index::Quantization,
+    redact_secrets: bool,
     progress: ProgressResolved,
     json: bool,
     idempotency_key: Option<String>,
@@ -8975,6 +11197,9 @@ fn run_index_with_data(
         watch.hash(&mut hasher);
         semantic.hash(&mut hasher);
         embedder.hash(&mut hasher);
+        ann.hash(&mut hasher);
+        quantization.hash(&mut hasher);
+        redact_secrets.hash(&mut hasher);
         format!("{}", data_dir.display()).hash(&mut hasher);
         hasher.finish()
     };
@@ -9064,6 +11289,9 @@ fn run_index_with_data(
         data_dir: data_dir.clone(),
         semantic,
         embedder: embedder.clone(),
+        ann,
+        quantization,
+        redact_secrets,
         progress: Some(index_progress.clone()),
     };
 
@@ -10131,32 +12359,13 @@ fn run_export_html(
         .collect();
 
     // --- Build metadata ---
-    let duration = match (session_start, session_end) {
-        (Some(start), Some(end)) if end > start => {
-            let mins = (end - start) / 60_000;
-            if mins >= 60 {
-                Some(format!("{}h {}m", mins / 60, mins % 60))
-            } else if mins > 0 {
-                Some(format!("{}m", mins))
-            } else {
-                Some("< 1m".to_string())
-            }
-        }
-        _ => None,
-    };
-
     let metadata = TemplateMetadata {
-        timestamp: session_start.map(|ts| {
-            chrono::Utc
-                .timestamp_millis_opt(ts)
-                .single()
-                .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
-                .unwrap_or_default()
-        }),
+        timestamp: session_start.map(html_export_timestamp),
         agent: agent_name.clone(),
         message_count: messages.len(),
-        duration,
+        duration: html_export_duration(session_start, session_end),
         project: workspace.clone(),
+        nav: Vec::new(),
     };
 
     // --- Generate output path ---
@@ -10333,17 +12542,373 @@ fn run_export_html(
     Ok(())
 }
 
-/// Extract tool call information from a message for HTML export.
-///
-/// Supports multiple formats:
-/// 1. Claude/Anthropic format: `content` array with `type: "tool_use"` or `type: "tool_result"` blocks
-/// 2. Cursor/generic format: `type: "tool"` at top level with `message.tool_name`, `message.tool_input`, `message.tool_output`
-fn extract_tool_call(msg: &serde_json::Value) -> Option<html_export::ToolCall> {
-    // Format 2: Cursor/generic format - check for top-level type: "tool"
-    if let Some(msg_type) = msg.get("type").and_then(|t| t.as_str())
-        && msg_type == "tool"
-    {
-        // Look for tool info in the message object
+/// Session start as shown in an HTML export header.
+fn html_export_timestamp(ts: i64) -> String {
+    use chrono::TimeZone;
+    chrono::Utc
+        .timestamp_millis_opt(ts)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_default()
+}
+
+/// Session length as shown in an HTML export header ("1h 5m", "12m", "< 1m").
+fn html_export_duration(start: Option<i64>, end: Option<i64>) -> Option<String> {
+    match (start, end) {
+        (Some(start), Some(end)) if end > start => {
+            let mins = (end - start) / 60_000;
+            if mins >= 60 {
+                Some(format!("{}h {}m", mins / 60, mins % 60))
+            } else if mins > 0 {
+                Some(format!("{}m", mins))
+            } else {
+                Some("< 1m".to_string())
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Export every indexed conversation matching `selector` as linked HTML pages
+/// plus an `index.html` with a sortable, filterable table.
+#[allow(clippy::too_many_arguments)]
+fn run_export_html_batch(
+    selector: &crate::model::types::ConversationSelector,
+    limit: Option<usize>,
+    output_dir: Option<&Path>,
+    encrypt: bool,
+    encrypt_scope: HtmlEncryptScope,
+    password: Option<&str>,
+    password_stdin: bool,
+    include_tools: bool,
+    show_timestamps: bool,
+    enable_cdns: bool,
+    theme: &str,
+    dry_run: bool,
+    explain: bool,
+    open: bool,
+    json_output: bool,
+    data_dir: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+) -> CliResult<()> {
+    use chrono::TimeZone;
+    use html_export::{
+        BatchConversation, ExportOptions as HtmlExportOptions, HtmlExporter, INDEX_FILENAME,
+        Message, TemplateMetadata, datetime_slug, generate_full_filename, get_downloads_dir,
+        unique_filename,
+    };
+    use std::collections::HashSet;
+
+    let storage = open_cli_storage(data_dir, db_override)?;
+    let mut selected = storage
+        .select_conversations(selector)
+        .map_err(|e| CliError {
+            code: 9,
+            kind: "export_failed",
+            message: format!("Failed to select conversations: {e:#}"),
+            hint: None,
+            retryable: false,
+        })?;
+    // Newest first, undated conversations last
+    selected.sort_by_key(|c| std::cmp::Reverse(c.started_at));
+    selected.retain(|c| c.message_count > 0);
+    if let Some(limit) = limit {
+        selected.truncate(limit);
+    }
+    if selected.is_empty() {
+        return Err(CliError {
+            code: 3,
+            kind: "no_conversations",
+            message: "No indexed conversations match the selectors".to_string(),
+            hint: Some("Check the selectors with 'cass search', or run 'cass index'".to_string()),
+            retryable: false,
+        });
+    }
+
+    // --- Load conversations ---
+    let mut conversations = Vec::with_capacity(selected.len());
+    let mut filenames = HashSet::new();
+    for conv in &selected {
+        let stored = storage
+            .fetch_messages(conv.conversation_id)
+            .map_err(|e| CliError {
+                code: 9,
+                kind: "export_failed",
+                message: format!(
+                    "Failed to load conversation {}: {e:#}",
+                    conv.conversation_id
+                ),
+                hint: None,
+                retryable: false,
+            })?;
+        let messages: Vec<Message> = stored
+            .iter()
+            .map(|m| Message {
+                role: crate::ui::tui::html_role_slug(&m.role),
+                content: m.content.clone(),
+                timestamp: m
+                    .created_at
+                    .and_then(|ts| chrono::Utc.timestamp_millis_opt(ts).single())
+                    .map(|dt| dt.to_rfc3339()),
+                tool_call: if include_tools {
+                    extract_tool_call(&m.extra_json)
+                } else {
+                    None
+                },
+                index: usize::try_from(m.idx).ok(),
+                author: m.author.clone(),
+            })
+            .collect();
+
+        let title = conv
+            .title
+            .clone()
+            .filter(|t| !t.trim().is_empty())
+            .or_else(|| {
+                messages
+                    .iter()
+                    .filter(|m| m.role == "user")
+                    .flat_map(|m| m.content.lines())
+                    .find(|line| !line.trim().is_empty())
+                    .map(|line| line.chars().take(80).collect())
+            })
+            .unwrap_or_else(|| "Untitled Session".to_string());
+
+        let base = generate_full_filename(
+            &conv.agent,
+            conv.workspace.as_deref().map(Path::new),
+            conv.started_at,
+            Some(title.as_str()),
+            Some(title.as_str()),
+        );
+        let stem = base.trim_end_matches(".html");
+        let mut filename = base.clone();
+        let mut n = 1;
+        while !filenames.insert(filename.clone()) {
+            filename = format!("{stem}_{n}.html");
+            n += 1;
+        }
+
+        let ended_at = stored.iter().filter_map(|m| m.created_at).max();
+        conversations.push(BatchConversation {
+            metadata: TemplateMetadata {
+                timestamp: conv.started_at.map(html_export_timestamp),
+                agent: Some(conv.agent.clone()),
+                message_count: messages.len(),
+                duration: html_export_duration(conv.started_at, ended_at),
+                project: conv.workspace.clone(),
+                nav: Vec::new(),
+            },
+            title,
+            messages,
+            filename,
+            started_at: conv.started_at,
+        });
+    }
+
+    // --- Output directory ---
+    let output_directory = match output_dir {
+        Some(dir) => dir.to_path_buf(),
+        None => {
+            let name = format!(
+                "cass_export_{}",
+                datetime_slug(Some(chrono::Utc::now().timestamp_millis()))
+            );
+            unique_filename(&get_downloads_dir(), &name)
+        }
+    };
+    let index_path = output_directory.join(INDEX_FILENAME);
+    let encrypt_index = encrypt && encrypt_scope == HtmlEncryptScope::All;
+    let message_total: usize = conversations.iter().map(|c| c.messages.len()).sum();
+    let files: Vec<serde_json::Value> = conversations
+        .iter()
+        .zip(&selected)
+        .map(|(c, sel)| {
+            serde_json::json!({
+                "filename": c.filename,
+                "title": c.title,
+                "agent": sel.agent,
+                "workspace": sel.workspace,
+                "source_id": sel.source_id,
+                "messages": c.messages.len(),
+            })
+        })
+        .collect();
+    let scope = match encrypt_scope {
+        HtmlEncryptScope::All => "all",
+        HtmlEncryptScope::Sessions => "sessions",
+    };
+
+    // --- Explain mode ---
+    if explain {
+        let plan = serde_json::json!({
+            "plan": {
+                "conversations": conversations.len(),
+                "messages": message_total,
+                "output_dir": output_directory.display().to_string(),
+                "index_path": index_path.display().to_string(),
+                "files": files,
+                "options": {
+                    "encrypted": encrypt,
+                    "encrypt_scope": scope,
+                    "include_tools": include_tools,
+                    "show_timestamps": show_timestamps,
+                    "cdns_enabled": enable_cdns,
+                    "default_theme": theme
+                }
+            },
+            "warnings": []
+        });
+        println!("{}", serde_json::to_string_pretty(&plan).unwrap());
+        return Ok(());
+    }
+
+    // --- Dry run mode ---
+    if dry_run {
+        let result = serde_json::json!({
+            "dry_run": true,
+            "valid": true,
+            "output_dir": output_directory.display().to_string(),
+            "index_path": index_path.display().to_string(),
+            "conversations": conversations.len(),
+            "messages": message_total,
+            "encrypted": encrypt,
+            "files": files
+        });
+        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+        return Ok(());
+    }
+
+    // --- Get password if encryption requested ---
+    let final_password = match (encrypt, password) {
+        (false, _) => None,
+        (true, Some(p)) => Some(p.to_string()),
+        (true, None) => {
+            read_cli_passwords(&[("Export password", true)], password_stdin, json_output)?.pop()
+        }
+    };
+
+    // --- Export ---
+    let exporter = HtmlExporter::with_options(HtmlExportOptions {
+        title: None,
+        include_cdn: enable_cdns,
+        syntax_highlighting: true,
+        include_search: true,
+        include_theme_toggle: true,
+        encrypt,
+        print_styles: true,
+        agent_name: None,
+        show_timestamps,
+        show_tool_calls: include_tools,
+    });
+    let pages = exporter
+        .export_batch(
+            "Conversation Export",
+            &conversations,
+            final_password.as_deref(),
+            encrypt_index,
+        )
+        .map_err(|e| CliError {
+            code: 5,
+            kind: "export_failed",
+            message: format!("Failed to export HTML: {e}"),
+            hint: None,
+            retryable: false,
+        })?;
+
+    // --- Write files ---
+    std::fs::create_dir_all(&output_directory).map_err(|e| CliError {
+        code: 4,
+        kind: "output_not_writable",
+        message: format!("Could not create output directory: {e}"),
+        hint: Some(format!(
+            "Check permissions for {}",
+            output_directory.display()
+        )),
+        retryable: false,
+    })?;
+    let mut total_size = 0;
+    for page in &pages {
+        std::fs::write(output_directory.join(&page.filename), &page.html).map_err(|e| {
+            CliError {
+                code: 4,
+                kind: "write_failed",
+                message: format!("Failed to write {}: {e}", page.filename),
+                hint: None,
+                retryable: false,
+            }
+        })?;
+        total_size += page.html.len();
+    }
+
+    // --- Open index in browser if requested ---
+    if open {
+        #[cfg(target_os = "macos")]
+        {
+            let _ = std::process::Command::new("open").arg(&index_path).spawn();
+        }
+        #[cfg(target_os = "linux")]
+        {
+            let _ = std::process::Command::new("xdg-open")
+                .arg(&index_path)
+                .spawn();
+        }
+        #[cfg(target_os = "windows")]
+        {
+            let _ = std::process::Command::new("explorer")
+                .arg(&index_path)
+                .spawn();
+        }
+    }
+
+    // --- Output result ---
+    if json_output {
+        let result = serde_json::json!({
+            "success": true,
+            "exported": {
+                "output_dir": output_directory.display().to_string(),
+                "index_path": index_path.display().to_string(),
+                "conversations": conversations.len(),
+                "messages": message_total,
+                "size_bytes": total_size,
+                "encrypted": encrypt,
+                "encrypt_scope": encrypt.then_some(scope),
+                "files": files
+            }
+        });
+        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+    } else {
+        println!(
+            "✓ Exported {} conversations to {}",
+            conversations.len(),
+            output_directory.display()
+        );
+        if encrypt {
+            let what = if encrypt_index {
+                "all pages"
+            } else {
+                "conversation pages; index readable"
+            };
+            println!("  🔒 Encrypted with Web Crypto (AES-256-GCM): {what}");
+        }
+        println!("  {} messages, {} bytes", message_total, total_size);
+        println!("  Open {}", index_path.display());
+    }
+
+    Ok(())
+}
+
+/// Extract tool call information from a message for HTML export.
+///
+/// Supports multiple formats:
+/// 1. Claude/Anthropic format: `content` array with `type: "tool_use"` or `type: "tool_result"` blocks
+/// 2. Cursor/generic format: `type: "tool"` at top level with `message.tool_name`, `message.tool_input`, `message.tool_output`
+fn extract_tool_call(msg: &serde_json::Value) -> Option<html_export::ToolCall> {
+    // Format 2: Cursor/generic format - check for top-level type: "tool"
+    if let Some(msg_type) = msg.get("type").and_then(|t| t.as_str())
+        && msg_type == "tool"
+    {
+        // Look for tool info in the message object
         let inner = msg.get("message").unwrap_or(msg);
 
         let tool_name = inner
@@ -10653,7 +13218,13 @@ fn html_escape(s: &str) -> String {
 }
 
 /// Show messages around a specific line in a session file
-fn run_expand(path: &Path, line: usize, context: usize, json: bool) -> CliResult<()> {
+fn run_expand(
+    path: &Path,
+    line: usize,
+    context: usize,
+    subagents: Option<SubagentDisplay>,
+    json: bool,
+) -> CliResult<()> {
     use std::fs::File;
     use std::io::{BufRead, BufReader};
 
@@ -10713,16 +13284,27 @@ fn run_expand(path: &Path, line: usize, context: usize, json: bool) -> CliResult
         retryable: false,
     })?;
 
+    let target_line = messages[target_idx].0;
+    let threads = subagents.and_then(|_| connectors::claude_code::session_threads(path));
+    let messages = match (&threads, subagents) {
+        (Some(threads), Some(mode)) => thread_expand_messages(messages, threads, mode, target_line),
+        _ => messages
+            .into_iter()
+            .map(|(line, msg)| ExpandMessage::new(line, msg))
+            .collect(),
+    };
+    let target_idx = messages
+        .iter()
+        .position(|m| m.path.is_none() && m.line == target_line)
+        .unwrap_or_default();
+
     let start = target_idx.saturating_sub(context);
     let end = (target_idx + context + 1).min(messages.len());
 
     let context_messages: Vec<_> = messages[start..end]
         .iter()
         .enumerate()
-        .map(|(i, (line_num, msg))| {
-            let is_target = start + i == target_idx;
-            (line_num, msg, is_target)
-        })
+        .map(|(i, msg)| (msg, start + i == target_idx))
         .collect();
 
     let structured_format = if json {
@@ -10741,26 +13323,34 @@ fn run_expand(path: &Path, line: usize, context: usize, json: bool) -> CliResult
     if let Some(fmt) = structured_format {
         let output: Vec<serde_json::Value> = context_messages
             .iter()
-            .map(|(line_num, msg, is_target)| {
-                let role = extract_role(msg);
-                let content = extract_text_content(msg);
-                serde_json::json!({
-                    "line": line_num,
+            .map(|(entry, is_target)| {
+                let role = extract_role(&entry.msg);
+                let content = extract_text_content(&entry.msg);
+                let mut value = serde_json::json!({
+                    "line": entry.line,
                     "role": role,
                     "is_target": is_target,
                     "content": content,
-                })
+                });
+                if let Some(transcript) = &entry.path {
+                    value["path"] = transcript.display().to_string().into();
+                }
+                annotate_subagent_entry(&mut value, entry.subagent, &entry.spawns);
+                value
             })
             .collect();
         return output_structured_value(serde_json::Value::Array(output), fmt);
     }
 
     println!("\n📍 Context around line {} in {}\n", line, path.display());
+    if let Some(parent) = threads.as_ref().and_then(|t| t.parent.as_ref()) {
+        print_subagent_parent(parent);
+    }
     println!("{}", "─".repeat(60));
 
-    for (line_num, msg, is_target) in context_messages {
-        let role = extract_role(msg);
-        let content = extract_text_content(msg);
+    for (entry, is_target) in context_messages {
+        let role = extract_role(&entry.msg);
+        let content = extract_text_content(&entry.msg);
         let preview: String = content.chars().take(300).collect();
         let marker = if is_target { ">>>" } else { "   " };
         let role_icon = match role.as_str() {
@@ -10768,11 +13358,16 @@ fn run_expand(path: &Path, line: usize, context: usize, json: bool) -> CliResult
             "assistant" => "🤖",
             _ => "📝",
         };
+        let nest = match entry.subagent {
+            Some(thread) => format!("↳ [{}] ", subagent_label(thread)),
+            None => String::new(),
+        };
 
         println!(
-            "{} L{:>4} {} {}",
+            "{} L{:>4} {}{} {}",
             marker,
-            line_num,
+            entry.line,
+            nest,
             role_icon,
             role.to_uppercase()
         );
@@ -10780,6 +13375,13 @@ fn run_expand(path: &Path, line: usize, context: usize, json: bool) -> CliResult
         if content.len() > 300 {
             println!("        ... ({} more chars)", content.len() - 300);
         }
+        for (thread, collapsed) in &entry.spawns {
+            if *collapsed {
+                println!("        ↳ subagent: {} (collapsed)", subagent_label(thread));
+            } else {
+                println!("        ↳ subagent: {}", subagent_label(thread));
+            }
+        }
         println!();
     }
 
@@ -10793,6 +13395,100 @@ fn run_expand(path: &Path, line: usize, context: usize, json: bool) -> CliResult
     Ok(())
 }
 
+/// A message line shown by `cass expand`.
+struct ExpandMessage<'a> {
+    line: usize,
+    msg: serde_json::Value,
+    /// Subagent transcript the line was read from, for `--subagents show`
+    path: Option<PathBuf>,
+    /// Subagent thread the message belongs to
+    subagent: Option<&'a connectors::claude_code::SubagentThread>,
+    /// Threads the message spawned, and whether each is collapsed
+    spawns: Vec<(&'a connectors::claude_code::SubagentThread, bool)>,
+}
+
+impl ExpandMessage<'_> {
+    fn new(line: usize, msg: serde_json::Value) -> Self {
+        Self {
+            line,
+            msg,
+            path: None,
+            subagent: None,
+            spawns: Vec::new(),
+        }
+    }
+}
+
+/// Regroup subagent messages under the message that spawned them, reading
+/// transcripts for `show` and dropping sidechains for `collapse` (except the
+/// one holding the target line).
+fn thread_expand_messages(
+    messages: Vec<(usize, serde_json::Value)>,
+    threads: &connectors::claude_code::SessionThreads,
+    mode: SubagentDisplay,
+    target_line: usize,
+) -> Vec<ExpandMessage<'_>> {
+    use std::io::BufRead;
+
+    let thread_lines = subagent_lines(threads);
+    let open_thread = thread_lines.get(&target_line).copied();
+    let expanded = |t: usize| mode == SubagentDisplay::Show || Some(t) == open_thread;
+
+    // Attached sidechains move under their spawning message; others stay put
+    let mut pulled: Vec<Vec<ExpandMessage>> =
+        threads.subagents.iter().map(|_| Vec::new()).collect();
+    let mut kept = Vec::new();
+    for (line, msg) in messages {
+        let mut entry = ExpandMessage::new(line, msg);
+        match thread_lines.get(&line).copied() {
+            Some(t) => {
+                entry.subagent = Some(&threads.subagents[t]);
+                if !expanded(t) {
+                    continue;
+                }
+                if threads.subagents[t].spawned_at_line.is_some() {
+                    pulled[t].push(entry);
+                } else {
+                    kept.push(entry);
+                }
+            }
+            None => kept.push(entry),
+        }
+    }
+
+    let mut out = Vec::new();
+    for mut entry in kept {
+        let spawned: Vec<usize> = (0..threads.subagents.len())
+            .filter(|&t| threads.subagents[t].spawned_at_line == Some(entry.line))
+            .collect();
+        entry.spawns = spawned
+            .iter()
+            .map(|&t| (&threads.subagents[t], !expanded(t)))
+            .collect();
+        out.push(entry);
+        for t in spawned {
+            let thread = &threads.subagents[t];
+            out.append(&mut pulled[t]);
+            if let Some(transcript) = &thread.transcript
+                && expanded(t)
+                && let Ok(file) = std::fs::File::open(transcript)
+            {
+                let lines = std::io::BufReader::new(file).lines().map_while(Result::ok);
+                for (i, raw) in lines.enumerate() {
+                    let Ok(msg) = serde_json::from_str::<serde_json::Value>(&raw) else {
+                        continue;
+                    };
+                    let mut entry = ExpandMessage::new(i + 1, msg);
+                    entry.path = Some(transcript.clone());
+                    entry.subagent = Some(thread);
+                    out.push(entry);
+                }
+            }
+        }
+    }
+    out
+}
+
 fn extract_text_content(msg: &serde_json::Value) -> String {
     // Use the well-tested flatten_content helper from connectors module
     // It handles: direct strings, {"type": "text"}, {"type": "input_text"},
@@ -10953,7 +13649,8 @@ fn run_timeline(
                 sql.push_str(" AND c.source_id = 'local'");
             }
             SourceFilter::Remote => {
-                sql.push_str(" AND c.source_id != 'local'");
+                sql.push_str(" AND c.source_id != 'local' AND ");
+                sql.push_str(NOT_IMPORTED_SQL);
             }
             SourceFilter::SourceId(id) => {
                 sql.push_str(&format!(" AND c.source_id = ?{}", params.len() + 1));
@@ -11225,8 +13922,9 @@ fn run_sources_command(cmd: SourcesCommand) -> CliResult<()> {
             preset,
             paths,
             no_test,
+            mirror,
         } => {
-            run_sources_add(&url, name, preset, paths, no_test)?;
+            run_sources_add(&url, name, preset, paths, no_test, mirror)?;
         }
         SourcesCommand::Remove { name, purge, yes } => {
             run_sources_remove(&name, purge, yes)?;
@@ -11239,9 +13937,14 @@ fn run_sources_command(cmd: SourcesCommand) -> CliResult<()> {
             no_index,
             verbose,
             dry_run,
+            daemon,
             json,
         } => {
-            run_sources_sync(source, no_index, verbose, dry_run, json)?;
+            if daemon {
+                run_sources_sync_daemon(source, no_index, json)?;
+            } else {
+                run_sources_sync(source, no_index, verbose, dry_run, json)?;
+            }
         }
         SourcesCommand::Mappings(action) => {
             run_mappings_command(action)?;
@@ -11313,18 +14016,28 @@ fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
         }
     });
 
+    let sync_status =
+        crate::sources::sync::SyncStatus::load(&default_data_dir()).unwrap_or_default();
+
     if let Some(fmt) = structured_format {
         let sources_json: Vec<serde_json::Value> = config
             .sources
             .iter()
             .map(|s| {
+                let info = sync_status.get(&s.name);
                 serde_json::json!({
                     "name": s.name,
                     "type": s.source_type.as_str(),
                     "host": s.host,
+                    "root": s.root,
+                    "mirror": s.mirror,
                     "paths": s.paths,
                     "sync_schedule": s.sync_schedule.to_string(),
                     "platform": s.platform.map(|p| p.to_string()),
+                    "last_sync": info.and_then(|i| i.last_sync),
+                    "last_result": info.map(|i| &i.last_result),
+                    "consecutive_failures": info.map_or(0, |i| i.consecutive_failures),
+                    "next_run": if s.is_synced() { source_next_run(s, &sync_status) } else { None },
                 })
             })
             .collect();
@@ -11358,7 +14071,24 @@ fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
             if let Some(ref host) = source.host {
                 println!("  Host: {host}");
             }
+            if let Some(ref root) = source.root {
+                let mode = if source.mirror {
+                    "mirrored"
+                } else {
+                    "indexed in place"
+                };
+                println!("  Root: {root} ({mode})");
+            }
             println!("  Schedule: {}", source.sync_schedule);
+            if source.is_synced()
+                && let Some(next) = source_next_run(source, &sync_status)
+                    .and_then(chrono::DateTime::from_timestamp_millis)
+            {
+                println!(
+                    "  Next sync: {}",
+                    next.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                );
+            }
             if let Some(platform) = source.platform {
                 println!("  Platform: {platform}");
             }
@@ -11390,9 +14120,13 @@ fn run_sources_list(verbose: bool, json: bool) -> CliResult<()> {
         println!("  {:15} {:8} {:30} {:>5}", "NAME", "TYPE", "HOST", "PATHS");
         println!("  {}", "-".repeat(62));
         for source in &config.sources {
-            let host = source.host.as_deref().unwrap_or("-");
-            let host_truncated = if host.len() > 30 {
-                format!("{}...", &host[..27])
+            let host = source
+                .host
+                .as_deref()
+                .or(source.root.as_deref())
+                .unwrap_or("-");
+            let host_truncated = if host.chars().count() > 30 {
+                format!("{}...", host.chars().take(27).collect::<String>())
             } else {
                 host.to_string()
             };
@@ -11419,12 +14153,39 @@ fn run_sources_add(
     preset: Option<String>,
     paths_arg: Vec<String>,
     no_test: bool,
+    mirror: bool,
 ) -> CliResult<()> {
     use crate::sources::config::{Platform, SourceDefinition, SourcesConfig, get_preset_paths};
     use crate::sources::provenance::SourceKind;
 
-    // Parse URL to extract host
-    let (host, source_id) = parse_source_url(url, name.as_deref())?;
+    // A directory adds a path source; anything else is an SSH URL
+    let root = parse_source_root(url);
+    if mirror && root.is_none() {
+        return Err(CliError::usage(
+            "--mirror only applies to directory sources",
+            Some("Pass a directory such as /mnt/nas/laptop, or drop --mirror".into()),
+        ));
+    }
+    let (host, source_id) = match &root {
+        Some(root) => {
+            let source_id = name.clone().or_else(|| {
+                Path::new(root)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+            });
+            let Some(source_id) = source_id else {
+                return Err(CliError::usage(
+                    format!("Cannot derive a source name from '{root}'"),
+                    Some("Use --name to name this source".into()),
+                ));
+            };
+            (None, source_id)
+        }
+        None => {
+            let (host, source_id) = parse_source_url(url, name.as_deref())?;
+            (Some(host), source_id)
+        }
+    };
 
     // Determine paths: preset, explicit args, or error
     let paths = if let Some(ref preset_name) = preset {
@@ -11447,11 +14208,24 @@ fn run_sources_add(
         });
     };
 
-    // Test SSH connectivity unless --no-test
+    // Test SSH connectivity (or that the directory is there) unless --no-test
     if !no_test {
-        println!("Testing SSH connectivity to {host}...");
-        test_ssh_connectivity(&host)?;
-        println!("  Connected successfully");
+        if let Some(host) = &host {
+            println!("Testing SSH connectivity to {host}...");
+            test_ssh_connectivity(host)?;
+            println!("  Connected successfully");
+        } else if let Some(root) = &root {
+            let dir = SourceDefinition::path(&source_id, root.as_str()).root_dir();
+            if !dir.as_deref().is_some_and(Path::is_dir) {
+                return Err(CliError {
+                    code: 10,
+                    kind: "config",
+                    message: format!("Directory not found: {root}"),
+                    hint: Some("Mount the drive first, or use --no-test to add it anyway".into()),
+                    retryable: true,
+                });
+            }
+        }
     }
 
     // Load existing config
@@ -11488,8 +14262,14 @@ fn run_sources_add(
     // Create source definition
     let source = SourceDefinition {
         name: source_id.clone(),
-        source_type: SourceKind::Ssh,
-        host: Some(host.clone()),
+        source_type: if root.is_some() {
+            SourceKind::Path
+        } else {
+            SourceKind::Ssh
+        },
+        host: host.clone(),
+        root: root.clone(),
+        mirror,
         paths: paths.clone(),
         platform,
         ..Default::default()
@@ -11520,17 +14300,44 @@ fn run_sources_add(
 
     println!();
     println!("Added source '{source_id}'");
-    println!("  Host: {host}");
+    if let Some(host) = &host {
+        println!("  Host: {host}");
+    }
+    if let Some(root) = &root {
+        println!("  Root: {root}");
+    }
     println!("  Paths: {} path(s)", paths.len());
     println!("  Config: {config_path}");
     println!();
     println!("Next steps:");
-    println!("  cass sources sync {source_id}   # Fetch sessions from this source");
+    if host.is_some() || mirror {
+        println!("  cass sources sync {source_id}   # Fetch sessions from this source");
+    } else {
+        println!("  cass index                      # Index sessions from this directory");
+    }
     println!("  cass sources list               # View all configured sources");
 
     Ok(())
 }
 
+/// Directory root of a path source URL: `path://<dir>`, or a bare path
+/// starting with `/`, `~` or `.`. Relative paths are made absolute.
+fn parse_source_root(url: &str) -> Option<String> {
+    let dir = if let Some(dir) = url.strip_prefix("path://") {
+        dir
+    } else if url.starts_with(['/', '~', '.']) || (cfg!(windows) && Path::new(url).is_absolute()) {
+        url
+    } else {
+        return None;
+    };
+    if dir.starts_with('.') {
+        return Some(
+            std::path::absolute(dir).map_or_else(|_| dir.to_string(), |p| p.display().to_string()),
+        );
+    }
+    Some(dir.to_string())
+}
+
 /// Parse source URL and extract host and source_id.
 /// Accepts formats: user@host, ssh://user@host
 fn parse_source_url(url: &str, name: Option<&str>) -> Result<(String, String), CliError> {
@@ -11702,7 +14509,31 @@ fn run_sources_remove(name: &str, purge: bool, skip_confirm: bool) -> CliResult<
             })?;
             println!("Deleted synced data at {}", source_dir.display());
         }
-        println!("Note: Run 'cass reindex' to remove entries from the search index.");
+
+        // Drop the source's conversations from the database and both indexes
+        let db_path = data_dir.join("agent_search.db");
+        if db_path.exists() {
+            let purge_error = |e: anyhow::Error| CliError {
+                code: 9,
+                kind: "forget",
+                message: format!("Failed to purge indexed data for '{name}': {e:#}"),
+                hint: Some(format!("Retry with 'cass forget --source {name} --yes'")),
+                retryable: true,
+            };
+            let mut storage =
+                crate::storage::sqlite::SqliteStorage::open(&db_path).map_err(purge_error)?;
+            let selector = crate::model::types::ConversationSelector {
+                source_ids: vec![name.to_string()],
+                ..Default::default()
+            };
+            let report = crate::forget::forget(&mut storage, &data_dir, &selector, false)
+                .map_err(purge_error)?;
+            storage.delete_source(name, true).map_err(purge_error)?;
+            println!(
+                "Removed {} conversation(s) from the index.",
+                report.conversation_count
+            );
+        }
     }
 
     Ok(())
@@ -11778,25 +14609,36 @@ fn run_sources_doctor(source_filter: Option<&str>, json_output: bool) -> CliResu
     for source in sources_to_check {
         let mut checks = Vec::new();
 
-        // Check 1: SSH connectivity
-        let host = source.host.as_deref().unwrap_or("unknown");
-        let ssh_check = check_ssh_connectivity(host);
-        checks.push(ssh_check);
-
-        // Check 2: rsync availability on remote
-        let rsync_check = check_rsync_available(host);
-        checks.push(rsync_check);
+        if source.is_path() {
+            // Path sources: the directory must be mounted and readable
+            checks.push(check_path_root(source));
+            for path in &source.paths {
+                checks.push(check_source_dir(source, path));
+            }
+            if source.mirror {
+                checks.push(check_local_storage(&source.name));
+            }
+        } else {
+            // Check 1: SSH connectivity
+            let host = source.host.as_deref().unwrap_or("unknown");
+            let ssh_check = check_ssh_connectivity(host);
+            checks.push(ssh_check);
+
+            // Check 2: rsync availability on remote
+            let rsync_check = check_rsync_available(host);
+            checks.push(rsync_check);
+
+            // Check 3: Remote paths exist
+            for path in &source.paths {
+                let path_check = check_remote_path(host, path);
+                checks.push(path_check);
+            }
 
-        // Check 3: Remote paths exist
-        for path in &source.paths {
-            let path_check = check_remote_path(host, path);
-            checks.push(path_check);
+            // Check 4: Local storage writable
+            let storage_check = check_local_storage(&source.name);
+            checks.push(storage_check);
         }
 
-        // Check 4: Local storage writable
-        let storage_check = check_local_storage(&source.name);
-        checks.push(storage_check);
-
         // Compute summary
         let passed = checks.iter().filter(|c| c.status == "pass").count();
         let warnings = checks.iter().filter(|c| c.status == "warn").count();
@@ -12022,6 +14864,62 @@ fn check_remote_path(host: &str, path: &str) -> DiagnosticCheck {
     }
 }
 
+/// Check that a path source's root directory is mounted and readable
+fn check_path_root(source: &crate::sources::config::SourceDefinition) -> DiagnosticCheck {
+    let root = source.root.as_deref().unwrap_or("unknown");
+    match source.root_dir().map(std::fs::read_dir) {
+        Some(Ok(_)) => DiagnosticCheck {
+            name: "Root Directory".into(),
+            status: "pass".into(),
+            message: format!("{root} is readable"),
+            remediation: None,
+        },
+        Some(Err(e)) if e.kind() == std::io::ErrorKind::PermissionDenied => DiagnosticCheck {
+            name: "Root Directory".into(),
+            status: "fail".into(),
+            message: format!("{root} is not readable: {e}"),
+            remediation: Some("Check permissions on the directory".into()),
+        },
+        _ => DiagnosticCheck {
+            name: "Root Directory".into(),
+            status: "fail".into(),
+            message: format!("{root} does not exist"),
+            remediation: Some("Mount the drive or share, or fix `root` in sources.toml".into()),
+        },
+    }
+}
+
+/// Check if a path of a path source exists under its root
+fn check_source_dir(
+    source: &crate::sources::config::SourceDefinition,
+    path: &str,
+) -> DiagnosticCheck {
+    let name = format!("Source Path: {path}");
+    let Some(dir) = source.resolve_path(path).filter(|d| d.exists()) else {
+        return DiagnosticCheck {
+            name,
+            status: "fail".into(),
+            message: "Path does not exist".into(),
+            remediation: Some("Remove this path or check the source's root".into()),
+        };
+    };
+    let count = if dir.is_dir() {
+        std::fs::read_dir(&dir).map_or(0, |entries| entries.count())
+    } else {
+        1
+    };
+    DiagnosticCheck {
+        name,
+        status: if count > 0 { "pass" } else { "warn" }.into(),
+        message: if count > 0 {
+            format!("{} exists, {count} items found", dir.display())
+        } else {
+            format!("{} exists but is empty", dir.display())
+        },
+        remediation: (count == 0).then(|| "No agent sessions in this directory yet".into()),
+    }
+}
+
 /// Check if local storage directory is writable
 fn check_local_storage(source_name: &str) -> DiagnosticCheck {
     let data_dir = default_data_dir();
@@ -12086,8 +14984,8 @@ fn run_sources_sync(
         retryable: false,
     })?;
 
-    // Filter to remote sources only
-    let remote_sources: Vec<_> = config.remote_sources().collect();
+    // Filter to sources that are copied into the local mirror
+    let remote_sources: Vec<_> = config.synced_sources().collect();
 
     if remote_sources.is_empty() {
         if json_output {
@@ -12295,6 +15193,9 @@ fn run_sources_sync(
             Some(data_dir), // data_dir
             false,          // semantic
             "fastembed".to_string(),
+            false, // ann
+            crate::search::vector_index::Quantization::F32,
+            false, // redact_secrets (the stored setting still applies)
             progress,
             json_output,
             None, // idempotency_key
@@ -12304,6 +15205,141 @@ fn run_sources_sync(
     Ok(())
 }
 
+/// Sync sources on their `sync_schedule` until interrupted (`sources sync --daemon`).
+fn run_sources_sync_daemon(
+    source_filter: Option<Vec<String>>,
+    no_index: bool,
+    json_output: bool,
+) -> CliResult<()> {
+    use crate::sources::config::SourcesConfig;
+    use crate::sources::scheduler::{SCHEDULER_TICK, SyncScheduler, changed_mirror_paths};
+    use crate::sources::sync::SyncStatus;
+    use colored::Colorize;
+
+    let data_dir = default_data_dir();
+    let scheduler = SyncScheduler::new(&data_dir);
+    if !json_output {
+        println!(
+            "{} checking sources every {}s (Ctrl+C to stop)",
+            "Sync scheduler".cyan().bold(),
+            SCHEDULER_TICK.as_secs()
+        );
+    }
+
+    loop {
+        // Re-read the config each tick so schedule edits apply without a restart
+        let sources: Vec<_> = match SourcesConfig::load() {
+            Ok(config) => config
+                .sources
+                .into_iter()
+                .filter(|s| {
+                    source_filter
+                        .as_ref()
+                        .is_none_or(|names| names.contains(&s.name))
+                })
+                .collect(),
+            Err(e) => {
+                tracing::warn!("failed to load sources config: {e}");
+                Vec::new()
+            }
+        };
+
+        let reports = scheduler.run_due(
+            &sources,
+            crate::storage::sqlite::SqliteStorage::now_millis(),
+        );
+        let status = SyncStatus::load(&data_dir).unwrap_or_default();
+        for report in &reports {
+            let next_run = sources
+                .iter()
+                .find(|s| s.name == report.source_name)
+                .and_then(|s| source_next_run(s, &status));
+            if json_output {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "source": report.source_name,
+                        "status": if report.all_succeeded { "success" } else if report.successful_paths() > 0 { "partial" } else { "error" },
+                        "total_files": report.total_files(),
+                        "total_bytes": report.total_bytes(),
+                        "duration_ms": report.total_duration_ms,
+                        "errors": report.path_results.iter().filter_map(|r| r.error.clone()).collect::<Vec<_>>(),
+                        "next_run": next_run,
+                    })
+                );
+            } else {
+                let next = next_run
+                    .and_then(chrono::DateTime::from_timestamp_millis)
+                    .map(|t| {
+                        t.with_timezone(&chrono::Local)
+                            .format("%Y-%m-%d %H:%M")
+                            .to_string()
+                    })
+                    .unwrap_or_else(|| "-".to_string());
+                if report.all_succeeded {
+                    println!(
+                        "  {} {}: {} files ({}), next run {next}",
+                        "Synced".green(),
+                        report.source_name.white().bold(),
+                        report.total_files(),
+                        format_bytes(report.total_bytes())
+                    );
+                } else {
+                    let errors: Vec<_> = report
+                        .path_results
+                        .iter()
+                        .filter_map(|r| r.error.as_deref())
+                        .collect();
+                    println!(
+                        "  {} {}: {}, retrying {next}",
+                        "Failed".red(),
+                        report.source_name.white().bold(),
+                        errors.join("; ")
+                    );
+                }
+            }
+        }
+
+        let changed = changed_mirror_paths(&reports);
+        if !no_index && !changed.is_empty() {
+            let opts = indexer::IndexOptions {
+                full: false,
+                force_rebuild: false,
+                watch: false,
+                watch_once_paths: None,
+                db_path: data_dir.join("agent_search.db"),
+                data_dir: data_dir.clone(),
+                semantic: false,
+                embedder: "fastembed".to_string(),
+                ann: false,
+                quantization: crate::search::vector_index::Quantization::F32,
+                redact_secrets: false,
+                progress: None,
+            };
+            if let Err(e) = indexer::reindex_mirrors(opts, changed) {
+                tracing::warn!("reindex after scheduled sync failed: {e}");
+                if !json_output {
+                    eprintln!("  {} {e}", "Re-index failed:".red());
+                }
+            }
+        }
+
+        std::thread::sleep(SCHEDULER_TICK);
+    }
+}
+
+/// Next scheduled sync of `source` in epoch milliseconds (`None` for manual sources).
+fn source_next_run(
+    source: &crate::sources::config::SourceDefinition,
+    status: &crate::sources::sync::SyncStatus,
+) -> Option<i64> {
+    crate::sources::scheduler::next_run(
+        source.sync_schedule,
+        status.get(&source.name),
+        crate::storage::sqlite::SqliteStorage::now_millis(),
+    )
+}
+
 /// Auto-discover SSH hosts from ~/.ssh/config (P5.6)
 fn run_sources_discover(preset: &str, skip_existing: bool, json_output: bool) -> CliResult<()> {
     use crate::sources::config::{SourcesConfig, discover_ssh_hosts, get_preset_paths};
@@ -13437,6 +16473,1439 @@ fn run_mappings_test(source_name: &str, path: &str, agent: Option<&str>) -> CliR
     Ok(())
 }
 
+/// Handle bookmarks subcommands
+fn run_bookmarks_command(cmd: BookmarksCommand, db_override: Option<PathBuf>) -> CliResult<()> {
+    match cmd {
+        BookmarksCommand::Add {
+            path,
+            line,
+            title,
+            note,
+            tags,
+            agent,
+            workspace,
+            snippet,
+            data_dir,
+            json,
+        } => run_bookmarks_add(
+            &path,
+            line,
+            title,
+            &note,
+            &tags,
+            agent,
+            workspace,
+            snippet,
+            &data_dir,
+            db_override,
+            json,
+        ),
+        BookmarksCommand::List {
+            tag,
+            query,
+            data_dir,
+            json,
+        } => run_bookmarks_list(tag.as_deref(), query.as_deref(), &data_dir, json),
+        BookmarksCommand::Remove { id, data_dir, json } => {
+            run_bookmarks_remove(id, &data_dir, json)
+        }
+        BookmarksCommand::Edit {
+            id,
+            title,
+            note,
+            tags,
+            data_dir,
+            json,
+        } => run_bookmarks_edit(id, title, note, tags, &data_dir, json),
+        BookmarksCommand::Export { output, data_dir } => {
+            run_bookmarks_export(output.as_deref(), &data_dir)
+        }
+        BookmarksCommand::Import {
+            input,
+            data_dir,
+            json,
+        } => run_bookmarks_import(&input, &data_dir, json),
+    }
+}
+
+fn open_bookmark_store(
+    data_dir_override: &Option<PathBuf>,
+) -> CliResult<crate::bookmarks::BookmarkStore> {
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    crate::bookmarks::BookmarkStore::open_in(&data_dir).map_err(|e| CliError {
+        code: 9,
+        kind: "bookmarks",
+        message: format!("Failed to open bookmarks database: {e}"),
+        hint: Some("Check that the data dir is writable (see --data-dir)".into()),
+        retryable: false,
+    })
+}
+
+fn bookmarks_error(action: &str, e: anyhow::Error) -> CliError {
+    CliError {
+        code: 9,
+        kind: "bookmarks",
+        message: format!("Failed to {action}: {e}"),
+        hint: None,
+        retryable: false,
+    }
+}
+
+fn bookmark_not_found(id: i64) -> CliError {
+    CliError {
+        code: 2,
+        kind: "not_found",
+        message: format!("No bookmark with id {id}"),
+        hint: Some("Use 'cass bookmarks list' to see bookmark ids".into()),
+        retryable: false,
+    }
+}
+
+fn bookmarks_output_format(json: bool) -> Option<RobotFormat> {
+    if json {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    }
+    .map(|fmt| {
+        if matches!(fmt, RobotFormat::Sessions) {
+            RobotFormat::Compact
+        } else {
+            fmt
+        }
+    })
+}
+
+/// Bookmark a session, filling title/agent/workspace/snippet from the index when known
+#[allow(clippy::too_many_arguments)]
+fn run_bookmarks_add(
+    path: &str,
+    line: Option<usize>,
+    title: Option<String>,
+    note: &str,
+    tags: &str,
+    agent: Option<String>,
+    workspace: Option<String>,
+    snippet: Option<String>,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    use crate::bookmarks::Bookmark;
+    use rusqlite::{Connection, OptionalExtension};
+
+    let store = open_bookmark_store(data_dir_override)?;
+
+    if let Some(existing) = store
+        .find(path, line)
+        .map_err(|e| bookmarks_error("look up bookmark", e))?
+    {
+        return Err(CliError {
+            code: 2,
+            kind: "duplicate",
+            message: format!("Already bookmarked as #{}", existing.id),
+            hint: Some(format!(
+                "Use 'cass bookmarks edit {}' to change it",
+                existing.id
+            )),
+            retryable: false,
+        });
+    }
+
+    // Best-effort lookup of conversation metadata; bookmarks work without an index.
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
+    let mut indexed: Option<(i64, String, String, String)> = None;
+    let mut indexed_snippet: Option<String> = None;
+    if db_path.exists()
+        && let Ok(conn) = Connection::open(&db_path)
+    {
+        indexed = conn
+            .query_row(
+                "SELECT c.id, c.title, a.slug, w.path
+                 FROM conversations c
+                 JOIN agents a ON c.agent_id = a.id
+                 LEFT JOIN workspaces w ON c.workspace_id = w.id
+                 WHERE c.source_path = ?1
+                 LIMIT 1",
+                [path],
+                |r: &rusqlite::Row| {
+                    Ok((
+                        r.get(0)?,
+                        r.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                        r.get(2)?,
+                        r.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                    ))
+                },
+            )
+            .optional()
+            .ok()
+            .flatten();
+        if let (Some((conv_id, ..)), Some(line_no)) = (&indexed, line) {
+            indexed_snippet = conn
+                .query_row(
+                    "SELECT content FROM messages WHERE conversation_id = ?1 AND idx = ?2",
+                    rusqlite::params![conv_id, line_no.saturating_sub(1) as i64],
+                    |r: &rusqlite::Row| r.get::<_, String>(0),
+                )
+                .optional()
+                .ok()
+                .flatten()
+                .map(|content| content.chars().take(200).collect());
+        }
+    }
+
+    let (indexed_title, indexed_agent, indexed_workspace) = indexed
+        .map(|(_, t, a, w)| (Some(t), Some(a), Some(w)))
+        .unwrap_or_default();
+    let title = title
+        .or(indexed_title.filter(|t| !t.is_empty()))
+        .unwrap_or_else(|| {
+            Path::new(path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string())
+        });
+
+    let mut bookmark = Bookmark::new(
+        title,
+        path,
+        agent.or(indexed_agent).unwrap_or_default(),
+        workspace.or(indexed_workspace).unwrap_or_default(),
+    )
+    .with_note(note)
+    .with_tags(tags);
+    if let Some(line_no) = line {
+        bookmark = bookmark.with_line(line_no);
+    }
+    if let Some(text) = snippet.or(indexed_snippet) {
+        bookmark = bookmark.with_snippet(text);
+    }
+
+    let id = store
+        .add(&bookmark)
+        .map_err(|e| bookmarks_error("add bookmark", e))?;
+    bookmark.id = id;
+
+    if let Some(fmt) = bookmarks_output_format(json) {
+        let payload = serde_json::json!({ "added": true, "bookmark": bookmark });
+        return output_structured_value(payload, fmt);
+    }
+
+    println!("Bookmarked #{id}: {}", bookmark.title);
+    Ok(())
+}
+
+/// List bookmarks, optionally filtered by tag and/or text
+fn run_bookmarks_list(
+    tag: Option<&str>,
+    query: Option<&str>,
+    data_dir_override: &Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    let store = open_bookmark_store(data_dir_override)?;
+
+    let mut bookmarks = match query {
+        Some(q) => store.search(q),
+        None => store.list(None),
+    }
+    .map_err(|e| bookmarks_error("list bookmarks", e))?;
+    if let Some(tag) = tag {
+        bookmarks.retain(|b| b.has_tag(tag));
+    }
+
+    if let Some(fmt) = bookmarks_output_format(json) {
+        let payload = serde_json::json!({
+            "bookmarks": bookmarks,
+            "count": bookmarks.len(),
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    if bookmarks.is_empty() {
+        println!("No bookmarks found.");
+        println!();
+        println!("Add one with:");
+        println!("  cass bookmarks add /path/to/session.jsonl --line 42 --tags auth");
+        return Ok(());
+    }
+
+    for b in &bookmarks {
+        let location = match b.line_number {
+            Some(line) => format!("{}:{line}", b.source_path),
+            None => b.source_path.clone(),
+        };
+        println!("#{:<4} {}", b.id, b.title);
+        println!("      {location}");
+        if !b.agent.is_empty() {
+            println!("      agent: {}", b.agent);
+        }
+        if !b.tags.is_empty() {
+            println!("      tags: {}", b.tag_list().join(", "));
+        }
+        if !b.note.is_empty() {
+            println!("      note: {}", b.note);
+        }
+    }
+    println!();
+    println!("{} bookmark(s)", bookmarks.len());
+    Ok(())
+}
+
+/// Remove a bookmark by id
+fn run_bookmarks_remove(id: i64, data_dir_override: &Option<PathBuf>, json: bool) -> CliResult<()> {
+    let store = open_bookmark_store(data_dir_override)?;
+    let removed = store
+        .remove(id)
+        .map_err(|e| bookmarks_error("remove bookmark", e))?;
+    if !removed {
+        return Err(bookmark_not_found(id));
+    }
+
+    if let Some(fmt) = bookmarks_output_format(json) {
+        return output_structured_value(serde_json::json!({ "removed": true, "id": id }), fmt);
+    }
+    println!("Removed bookmark #{id}");
+    Ok(())
+}
+
+/// Update a bookmark's title, note, or tags
+fn run_bookmarks_edit(
+    id: i64,
+    title: Option<String>,
+    note: Option<String>,
+    tags: Option<String>,
+    data_dir_override: &Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    let store = open_bookmark_store(data_dir_override)?;
+    let mut bookmark = store
+        .get(id)
+        .map_err(|e| bookmarks_error("read bookmark", e))?
+        .ok_or_else(|| bookmark_not_found(id))?;
+
+    if title.is_none() && note.is_none() && tags.is_none() {
+        return Err(CliError::usage(
+            "Nothing to edit",
+            Some("Pass at least one of --title, --note, --tags".to_string()),
+        ));
+    }
+    if let Some(title) = title {
+        bookmark.title = title;
+    }
+    if let Some(note) = note {
+        bookmark.note = note;
+    }
+    if let Some(tags) = tags {
+        bookmark.tags = tags;
+    }
+
+    store
+        .update(&bookmark)
+        .map_err(|e| bookmarks_error("update bookmark", e))?;
+    let bookmark = store
+        .get(id)
+        .map_err(|e| bookmarks_error("read bookmark", e))?
+        .ok_or_else(|| bookmark_not_found(id))?;
+
+    if let Some(fmt) = bookmarks_output_format(json) {
+        return output_structured_value(serde_json::json!({ "bookmark": bookmark }), fmt);
+    }
+    println!("Updated bookmark #{id}: {}", bookmark.title);
+    Ok(())
+}
+
+/// Export all bookmarks as JSON (stdout or file)
+fn run_bookmarks_export(
+    output: Option<&Path>,
+    data_dir_override: &Option<PathBuf>,
+) -> CliResult<()> {
+    let store = open_bookmark_store(data_dir_override)?;
+    let exported = store
+        .export_json()
+        .map_err(|e| bookmarks_error("export bookmarks", e))?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &exported).map_err(|e| CliError {
+                code: 9,
+                kind: "io",
+                message: format!("Failed to write {}: {e}", path.display()),
+                hint: None,
+                retryable: false,
+            })?;
+            eprintln!(
+                "Exported {} bookmark(s) to {}",
+                store.count().unwrap_or(0),
+                path.display()
+            );
+        }
+        None => println!("{exported}"),
+    }
+    Ok(())
+}
+
+/// Import bookmarks from a JSON export
+fn run_bookmarks_import(
+    input: &str,
+    data_dir_override: &Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    use std::io::Read;
+
+    let body = if input == "-" {
+        let mut buf = String::new();
+        std::io::stdin()
+            .read_to_string(&mut buf)
+            .map(|_| buf)
+            .map_err(|e| e.to_string())
+    } else {
+        std::fs::read_to_string(input).map_err(|e| e.to_string())
+    }
+    .map_err(|e| CliError {
+        code: 2,
+        kind: "io",
+        message: format!("Failed to read {input}: {e}"),
+        hint: Some("Provide a file produced by 'cass bookmarks export' or '-' for stdin".into()),
+        retryable: false,
+    })?;
+
+    let store = open_bookmark_store(data_dir_override)?;
+    let imported = store.import_json(&body).map_err(|e| CliError {
+        code: 2,
+        kind: "bookmarks",
+        message: format!("Failed to import bookmarks: {e}"),
+        hint: Some("Input must be a JSON array from 'cass bookmarks export'".into()),
+        retryable: false,
+    })?;
+
+    if let Some(fmt) = bookmarks_output_format(json) {
+        return output_structured_value(
+            serde_json::json!({ "imported": imported, "total": store.count().unwrap_or(imported) }),
+            fmt,
+        );
+    }
+    println!("Imported {imported} bookmark(s)");
+    Ok(())
+}
+
+/// Daemon client for `--daemon` searches: the Unix socket served by `cass daemon`,
+/// or a no-op client on platforms without Unix sockets.
+fn semantic_daemon_client(
+    data_dir: &Path,
+    embedder_id: Option<&str>,
+) -> std::sync::Arc<dyn crate::search::daemon_client::DaemonClient> {
+    use std::sync::Arc;
+
+    #[cfg(unix)]
+    {
+        use crate::search::daemon_socket::{UnixSocketDaemonClient, default_socket_path};
+
+        let client = UnixSocketDaemonClient::new(default_socket_path(data_dir));
+        match embedder_id {
+            Some(id) => Arc::new(client.with_embedder_id(id)),
+            None => Arc::new(client),
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (data_dir, embedder_id);
+        Arc::new(crate::search::daemon_client::NoopDaemonClient::new(
+            "daemon-unsupported",
+        ))
+    }
+}
+
+/// Run, query (`--status`) or stop (`--stop`) the semantic daemon
+#[cfg(unix)]
+fn run_daemon(
+    socket: Option<PathBuf>,
+    data_dir_override: Option<PathBuf>,
+    max_in_flight: usize,
+    no_rerank: bool,
+    status: bool,
+    stop: bool,
+    json: bool,
+) -> CliResult<()> {
+    use crate::search::daemon_socket::{DaemonServer, UnixSocketDaemonClient, default_socket_path};
+    use crate::search::embedder::Embedder;
+    use crate::search::fastembed_embedder::FastEmbedder;
+    use crate::search::fastembed_reranker::FastEmbedReranker;
+    use crate::search::reranker::Reranker;
+    use std::sync::Arc;
+
+    let data_dir = data_dir_override.unwrap_or_else(default_data_dir);
+    let socket_path = socket.unwrap_or_else(|| default_socket_path(&data_dir));
+    let structured_format = if json {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+
+    if status || stop {
+        let client = UnixSocketDaemonClient::new(&socket_path);
+        let not_running = |e: crate::search::daemon_client::DaemonError| CliError {
+            code: 3,
+            kind: "daemon-unavailable",
+            message: format!("No daemon reachable at {}: {e}", socket_path.display()),
+            hint: Some("Start one with 'cass daemon'.".to_string()),
+            retryable: true,
+        };
+        if stop {
+            client.shutdown().map_err(not_running)?;
+            if let Some(fmt) = structured_format {
+                let payload = serde_json::json!({
+                    "socket": socket_path.display().to_string(),
+                    "stopped": true,
+                });
+                return output_structured_value(payload, fmt);
+            }
+            println!("Daemon at {} is shutting down.", socket_path.display());
+            return Ok(());
+        }
+
+        let health = client.health().map_err(not_running)?;
+        if let Some(fmt) = structured_format {
+            let mut payload = serde_json::to_value(&health)
+                .map_err(|e| CliError::unknown(format!("serialize health: {e}")))?;
+            payload["socket"] = serde_json::json!(socket_path.display().to_string());
+            return output_structured_value(payload, fmt);
+        }
+        println!("Daemon at {} (pid {})", socket_path.display(), health.pid);
+        println!(
+            "  embedder:  {}",
+            health.embedder.as_deref().unwrap_or("(none)")
+        );
+        println!(
+            "  reranker:  {}",
+            health.reranker.as_deref().unwrap_or("(none)")
+        );
+        println!(
+            "  load:      {}/{} in flight, {} served",
+            health.in_flight, health.max_in_flight, health.requests_served
+        );
+        println!("  uptime:    {}s", health.uptime_ms / 1000);
+        return Ok(());
+    }
+
+    let embedder_dir = FastEmbedder::default_model_dir(&data_dir);
+    let embedder: Arc<dyn Embedder> = match FastEmbedder::load_from_dir(&embedder_dir) {
+        Ok(embedder) => Arc::new(embedder),
+        Err(e) => {
+            return Err(CliError {
+                code: 15,
+                kind: "semantic-unavailable",
+                message: format!("Cannot load embedder from {}: {e}", embedder_dir.display()),
+                hint: Some("Run 'cass models install' first.".to_string()),
+                retryable: false,
+            });
+        }
+    };
+    let reranker: Option<Arc<dyn Reranker>> = if no_rerank {
+        None
+    } else {
+        match FastEmbedReranker::load_from_dir(&FastEmbedReranker::default_model_dir(&data_dir)) {
+            Ok(reranker) => Some(Arc::new(reranker)),
+            Err(e) => {
+                tracing::warn!(error = %e, "Reranker not available; daemon will serve embeddings only");
+                None
+            }
+        }
+    };
+
+    let server = Arc::new(DaemonServer::new(Some(embedder), reranker, max_in_flight));
+    let health = server.health();
+    if let Some(fmt) = structured_format {
+        let mut payload = serde_json::to_value(&health)
+            .map_err(|e| CliError::unknown(format!("serialize health: {e}")))?;
+        payload["socket"] = serde_json::json!(socket_path.display().to_string());
+        output_structured_value(payload, fmt)?;
+    } else {
+        eprintln!(
+            "cass daemon listening on {} (embedder: {}, reranker: {})",
+            socket_path.display(),
+            health.embedder.as_deref().unwrap_or("none"),
+            health.reranker.as_deref().unwrap_or("none")
+        );
+        eprintln!("Use 'cass search --daemon ...' to query; 'cass daemon --stop' to exit.");
+    }
+
+    server.serve(&socket_path).map_err(|e| {
+        let in_use = e.kind() == std::io::ErrorKind::AddrInUse;
+        CliError {
+            code: if in_use { 2 } else { 9 },
+            kind: if in_use { "daemon-running" } else { "daemon" },
+            message: format!("Daemon failed on {}: {e}", socket_path.display()),
+            hint: in_use.then(|| "Stop it with 'cass daemon --stop'.".to_string()),
+            retryable: !in_use,
+        }
+    })
+}
+
+#[cfg(not(unix))]
+fn run_daemon(
+    _socket: Option<PathBuf>,
+    _data_dir_override: Option<PathBuf>,
+    _max_in_flight: usize,
+    _no_rerank: bool,
+    _status: bool,
+    _stop: bool,
+    _json: bool,
+) -> CliResult<()> {
+    Err(CliError {
+        code: 2,
+        kind: "unsupported",
+        message: "cass daemon requires Unix domain sockets".to_string(),
+        hint: Some("Search without --daemon on this platform.".to_string()),
+        retryable: false,
+    })
+}
+
+/// Handle tag subcommands
+fn run_tag_command(cmd: TagCommand, db_override: Option<PathBuf>) -> CliResult<()> {
+    match cmd {
+        TagCommand::Add {
+            path,
+            tags,
+            data_dir,
+            json,
+        } => run_tag_update(&path, &tags, true, &data_dir, db_override, json),
+        TagCommand::Remove {
+            path,
+            tags,
+            data_dir,
+            json,
+        } => run_tag_update(&path, &tags, false, &data_dir, db_override, json),
+        TagCommand::List {
+            path,
+            data_dir,
+            json,
+        } => run_tag_list(path.as_deref(), &data_dir, db_override, json),
+    }
+}
+
+fn open_cli_storage(
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+) -> CliResult<crate::storage::sqlite::SqliteStorage> {
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let db_path = db_override.unwrap_or_else(|| data_dir.join("agent_search.db"));
+    if !db_path.exists() {
+        return Err(CliError {
+            code: 3,
+            kind: "missing_index",
+            message: "Database not found".to_string(),
+            hint: Some("Run 'cass index --full' to create the database.".to_string()),
+            retryable: true,
+        });
+    }
+    crate::storage::sqlite::SqliteStorage::open(&db_path).map_err(|e| CliError {
+        code: 9,
+        kind: "db-open",
+        message: format!("Failed to open database: {e}"),
+        hint: None,
+        retryable: false,
+    })
+}
+
+fn session_conversation_ids(
+    storage: &crate::storage::sqlite::SqliteStorage,
+    path: &str,
+) -> CliResult<Vec<i64>> {
+    let ids = storage
+        .conversation_ids_for_path(path)
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+    if ids.is_empty() {
+        return Err(CliError {
+            code: 2,
+            kind: "not_found",
+            message: format!("No session found at path: {path}"),
+            hint: Some(
+                "Use 'cass search' to find sessions, then use the source_path from results."
+                    .to_string(),
+            ),
+            retryable: false,
+        });
+    }
+    Ok(ids)
+}
+
+/// Add (`add == true`) or remove tags on every conversation stored for `path`
+fn run_tag_update(
+    path: &str,
+    tags: &[String],
+    add: bool,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    let names = tags
+        .iter()
+        .map(|t| crate::storage::sqlite::normalize_tag_name(t))
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(|e| CliError::usage(e.to_string(), None))?;
+
+    let storage = open_cli_storage(data_dir_override, db_override)?;
+    let conversation_ids = session_conversation_ids(&storage, path)?;
+
+    let mut changed = 0usize;
+    for conversation_id in &conversation_ids {
+        for name in &names {
+            let did_change = if add {
+                storage.add_conversation_tag(*conversation_id, name)
+            } else {
+                storage.remove_conversation_tag(*conversation_id, name)
+            }
+            .map_err(|e| CliError::unknown(format!("tag update: {e}")))?;
+            if did_change {
+                changed += 1;
+            }
+        }
+    }
+
+    let current: Vec<String> = storage
+        .conversation_tags(conversation_ids[0])
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+
+    let structured_format = if json {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+    if let Some(fmt) = structured_format {
+        let mut payload = serde_json::json!({
+            "source_path": path,
+            "conversations": conversation_ids.len(),
+            "changed": changed,
+            "tags": current,
+        });
+        payload[if add { "added" } else { "removed" }] = serde_json::json!(names);
+        return output_structured_value(payload, fmt);
+    }
+
+    let verb = if add { "Tagged" } else { "Untagged" };
+    println!("{verb} {path} ({changed} change(s))");
+    if current.is_empty() {
+        println!("  tags: (none)");
+    } else {
+        println!("  tags: {}", current.join(", "));
+    }
+    Ok(())
+}
+
+/// List all tags with counts, or the tags of a single session
+fn run_tag_list(
+    path: Option<&str>,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    let storage = open_cli_storage(data_dir_override, db_override)?;
+    let structured_format = if json {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+
+    if let Some(path) = path {
+        let conversation_ids = session_conversation_ids(&storage, path)?;
+        let mut names: Vec<String> = Vec::new();
+        for conversation_id in conversation_ids {
+            for tag in storage
+                .conversation_tags(conversation_id)
+                .map_err(|e| CliError::unknown(format!("query: {e}")))?
+            {
+                if !names.contains(&tag.name) {
+                    names.push(tag.name);
+                }
+            }
+        }
+        names.sort();
+
+        if let Some(fmt) = structured_format {
+            let payload = serde_json::json!({ "source_path": path, "tags": names });
+            return output_structured_value(payload, fmt);
+        }
+        if names.is_empty() {
+            println!("{path} has no tags.");
+        } else {
+            println!("{}", names.join(", "));
+        }
+        return Ok(());
+    }
+
+    let tags = storage
+        .list_tags()
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+    if let Some(fmt) = structured_format {
+        let items: Vec<serde_json::Value> = tags
+            .iter()
+            .map(|(tag, count)| serde_json::json!({ "name": tag.name, "conversations": count }))
+            .collect();
+        let payload = serde_json::json!({ "tags": items, "count": items.len() });
+        return output_structured_value(payload, fmt);
+    }
+
+    if tags.is_empty() {
+        println!("No tags yet.");
+        println!();
+        println!("Tag a session with:");
+        println!("  cass tag add /path/to/session.jsonl postmortem");
+        return Ok(());
+    }
+    for (tag, count) in &tags {
+        println!("  {:24} {count:>5}", tag.name);
+    }
+    println!();
+    println!("Search tagged sessions with: cass search \"query\" --tag <tag>");
+    Ok(())
+}
+
+/// `cass files <session>`: files touched by a session's tool calls (snippets table)
+fn run_files(
+    session: &str,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    let storage = open_cli_storage(data_dir_override, db_override)?;
+    session_conversation_ids(&storage, session)?;
+    let files = storage
+        .session_files(session)
+        .map_err(|e| CliError::unknown(format!("query: {e}")))?;
+
+    let structured_format = if json {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::json!({
+            "source_path": session,
+            "files": files,
+            "count": files.len(),
+        });
+        return output_structured_value(payload, fmt);
+    }
+
+    if files.is_empty() {
+        println!("No file activity recorded for {session}.");
+        println!("Sessions indexed before file tracking need 'cass index --full'.");
+        return Ok(());
+    }
+    println!("{} file(s) touched by {session}:", files.len());
+    for file in &files {
+        let lines = match (file.first_line, file.last_line) {
+            (Some(first), Some(last)) => format!("  lines {first}-{last}"),
+            _ => String::new(),
+        };
+        println!(
+            "  {}  ({} edit{}, {} read{}){lines}",
+            file.file_path,
+            file.edits,
+            if file.edits == 1 { "" } else { "s" },
+            file.reads,
+            if file.reads == 1 { "" } else { "s" },
+        );
+    }
+    Ok(())
+}
+
+/// Build the `cass forget` selector, rejecting unparseable times and empty selections.
+/// Parse a `--since`/`--until` style value, naming the flag in the error.
+fn parse_time_flag(flag: &str, value: Option<&str>) -> CliResult<Option<i64>> {
+    value
+        .map(|v| {
+            parse_datetime_flexible(v).ok_or_else(|| {
+                CliError::usage(
+                    format!("Invalid --{flag} value: {v}"),
+                    Some("Use an ISO date (2025-06-01), RFC 3339, 'today', or e.g. '7d'".into()),
+                )
+            })
+        })
+        .transpose()
+}
+
+fn forget_selector(
+    source_ids: Vec<String>,
+    agents: Vec<String>,
+    workspaces: Vec<String>,
+    path_globs: Vec<String>,
+    since: Option<&str>,
+    until: Option<&str>,
+    query: Option<String>,
+) -> CliResult<crate::model::types::ConversationSelector> {
+    let selector = crate::model::types::ConversationSelector {
+        source_ids,
+        agents,
+        workspaces,
+        path_globs,
+        started_from: parse_time_flag("since", since)?,
+        started_to: parse_time_flag("until", until)?,
+        query: query.filter(|q| !q.trim().is_empty()),
+        tags: Vec::new(),
+    };
+    if selector.is_empty() {
+        return Err(CliError::usage(
+            "cass forget needs at least one selector",
+            Some("Use --source, --agent, --workspace, --path, --since/--until or --query".into()),
+        ));
+    }
+    Ok(selector)
+}
+
+/// `cass forget`: remove matching conversations from every store (see [`crate::forget`])
+fn run_forget(
+    selector: &crate::model::types::ConversationSelector,
+    dry_run: bool,
+    yes: bool,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let mut storage = open_cli_storage(data_dir_override, db_override)?;
+    let structured_format = if json {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+    let run = |storage: &mut crate::storage::sqlite::SqliteStorage, dry_run: bool| {
+        crate::forget::forget(storage, &data_dir, selector, dry_run).map_err(|e| CliError {
+            code: 9,
+            kind: "forget",
+            message: format!("Forget failed: {e:#}"),
+            hint: Some(
+                "Nothing is lost by retrying: the selection is deleted from the database last."
+                    .into(),
+            ),
+            retryable: true,
+        })
+    };
+
+    if !dry_run && !yes {
+        if structured_format.is_some() {
+            return Err(CliError::usage(
+                "cass forget needs --yes to delete in robot mode",
+                Some("Preview with --dry-run, then repeat with --yes".into()),
+            ));
+        }
+        let preview = run(&mut storage, true)?;
+        if preview.conversation_count == 0 {
+            println!("No conversations match.");
+            return Ok(());
+        }
+        print_forget_conversations(&preview);
+        print!(
+            "Forget {} conversation(s) with {} message(s)? This cannot be undone. [y/N]: ",
+            preview.conversation_count, preview.message_count
+        );
+        std::io::Write::flush(&mut std::io::stdout()).ok();
+        let mut input = String::new();
+        std::io::stdin()
+            .read_line(&mut input)
+            .map_err(|e| CliError {
+                code: 14,
+                kind: "io",
+                message: format!("Failed to read input: {e}"),
+                hint: None,
+                retryable: false,
+            })?;
+        let input = input.trim().to_lowercase();
+        if input != "y" && input != "yes" {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let report = run(&mut storage, dry_run)?;
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::to_value(&report)
+            .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+        return output_structured_value(payload, fmt);
+    }
+
+    if report.conversation_count == 0 {
+        println!("No conversations match.");
+        return Ok(());
+    }
+    if dry_run {
+        print_forget_conversations(&report);
+        println!(
+            "Would forget {} conversation(s) with {} message(s) from {} session file(s).",
+            report.conversation_count, report.message_count, report.session_files
+        );
+        for path in &report.stored_copies {
+            println!("Would rewrite stored copy {}", path.display());
+        }
+        return Ok(());
+    }
+    println!(
+        "Forgot {} conversation(s) with {} message(s); removed {} vector(s).",
+        report.conversation_count, report.message_count, report.vectors_removed
+    );
+    match report.lexical_index {
+        crate::forget::LexicalStatus::Stale => {
+            println!("The lexical index is out of date; run 'cass index --full' to rebuild it.")
+        }
+        crate::forget::LexicalStatus::Updated if report.lexical_reindexed > 0 => println!(
+            "Re-indexed {} conversation(s) sharing a session file.",
+            report.lexical_reindexed
+        ),
+        _ => {}
+    }
+    for path in &report.stored_copies {
+        println!("Rewrote stored copy {}", path.display());
+    }
+    println!(
+        "Session files on disk are untouched; delete them to keep a full reindex from restoring the data."
+    );
+    Ok(())
+}
+
+/// `cass import`: ingest an account data export under its own source id
+fn run_import(
+    path: &Path,
+    opts: &crate::import::ImportOptions,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    if !path.exists() {
+        return Err(CliError {
+            code: 3,
+            kind: "import",
+            message: format!("Export not found: {}", path.display()),
+            hint: Some("Pass the downloaded zip or its conversations.json".into()),
+            retryable: false,
+        });
+    }
+    let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+    let mut storage = open_cli_storage(data_dir_override, db_override)?;
+    let structured_format = if json {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+
+    let report =
+        crate::import::import(&mut storage, &data_dir, path, opts).map_err(|e| CliError {
+            code: 9,
+            kind: "import",
+            message: format!("Import failed: {e:#}"),
+            hint: None,
+            retryable: false,
+        })?;
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::to_value(&report)
+            .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+        return output_structured_value(payload, fmt);
+    }
+
+    let verb = if report.dry_run {
+        "Would import"
+    } else {
+        "Imported"
+    };
+    println!(
+        "{verb} {} {} conversation(s) with {} message(s) as source '{}'.",
+        report.conversations, report.agent, report.messages, report.source_id
+    );
+    if report.alternate_branches > 0 {
+        println!(
+            "{} conversation(s) had {} alternate branch(es), kept in metadata.",
+            report.branched_conversations, report.alternate_branches
+        );
+    }
+    match report.lexical_index {
+        crate::import::LexicalStatus::Pending => {
+            println!("No current index yet; run 'cass index' to make the import searchable.")
+        }
+        crate::import::LexicalStatus::Updated => println!(
+            "Stored the export at {}; 'cass index' re-reads it on rebuilds.",
+            report.stored_path.display()
+        ),
+        crate::import::LexicalStatus::Untouched => {}
+    }
+    Ok(())
+}
+
+/// `cass bundle export|import`: move sessions between machines as bundle files
+fn run_bundle_command(cmd: BundleCommand, db_override: Option<PathBuf>) -> CliResult<()> {
+    let structured_format = if cmd.json() {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+    let bundle_error = |e: anyhow::Error| CliError {
+        code: 9,
+        kind: "bundle",
+        message: format!("Bundle failed: {e:#}"),
+        hint: None,
+        retryable: false,
+    };
+
+    match cmd {
+        BundleCommand::Export {
+            output,
+            sources,
+            agents,
+            workspaces,
+            paths,
+            since,
+            until,
+            query,
+            name,
+            encrypt,
+            password_stdin,
+            data_dir,
+            json: _,
+        } => {
+            let selector = crate::model::types::ConversationSelector {
+                source_ids: sources,
+                agents,
+                workspaces,
+                path_globs: paths,
+                started_from: parse_time_flag("since", since.as_deref())?,
+                started_to: parse_time_flag("until", until.as_deref())?,
+                query: query.filter(|q| !q.trim().is_empty()),
+                tags: Vec::new(),
+            };
+            let password = if encrypt {
+                read_cli_passwords(
+                    &[("Bundle password", true)],
+                    password_stdin,
+                    structured_format.is_some(),
+                )?
+                .pop()
+            } else {
+                None
+            };
+            let storage = open_cli_storage(&data_dir, db_override)?;
+            let opts = crate::bundle::ExportOptions { name, password };
+            let report =
+                crate::bundle::export(&storage, &selector, &output, &opts).map_err(bundle_error)?;
+            if let Some(fmt) = structured_format {
+                let payload = serde_json::to_value(&report)
+                    .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+                return output_structured_value(payload, fmt);
+            }
+            println!(
+                "Exported {} conversation(s) with {} message(s) from '{}' to {}{}.",
+                report.conversations,
+                report.messages,
+                report.exported_from,
+                report.output.display(),
+                if report.encrypted { " (encrypted)" } else { "" }
+            );
+            println!(
+                "Import it elsewhere with 'cass bundle import {}'.",
+                report.output.display()
+            );
+            Ok(())
+        }
+        BundleCommand::Import {
+            path,
+            source_id,
+            password_stdin,
+            dry_run,
+            data_dir: data_dir_override,
+            json: _,
+        } => {
+            if !path.exists() {
+                return Err(CliError {
+                    code: 3,
+                    kind: "bundle",
+                    message: format!("Bundle not found: {}", path.display()),
+                    hint: Some("Pass a file written by 'cass bundle export'".into()),
+                    retryable: false,
+                });
+            }
+            let header = crate::bundle::read_header(&path).map_err(bundle_error)?;
+            let password = if header.encryption.is_some() {
+                read_cli_passwords(
+                    &[("Bundle password", false)],
+                    password_stdin,
+                    structured_format.is_some(),
+                )?
+                .pop()
+            } else {
+                None
+            };
+            let data_dir = data_dir_override.clone().unwrap_or_else(default_data_dir);
+            let mut storage = open_cli_storage(&data_dir_override, db_override)?;
+            let opts = crate::bundle::ImportOptions {
+                source_id,
+                password,
+                dry_run,
+            };
+            let report = crate::bundle::import(&mut storage, &data_dir, &path, &opts)
+                .map_err(bundle_error)?;
+            if let Some(fmt) = structured_format {
+                let payload = serde_json::to_value(&report)
+                    .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+                return output_structured_value(payload, fmt);
+            }
+
+            let verb = if report.dry_run {
+                "Would import"
+            } else {
+                "Imported"
+            };
+            println!(
+                "{verb} {} conversation(s) with {} message(s) from '{}' as source '{}'.",
+                report.conversations, report.messages, report.exported_from, report.source_id
+            );
+            match report.lexical_index {
+                crate::import::LexicalStatus::Pending => println!(
+                    "No current index yet; run 'cass index' to make the bundle searchable."
+                ),
+                crate::import::LexicalStatus::Updated => println!(
+                    "{} message(s) were new. Stored the bundle at {}; 'cass index' re-reads it on rebuilds.",
+                    report.new_messages.unwrap_or(0),
+                    report.stored_path.display()
+                ),
+                crate::import::LexicalStatus::Untouched => {}
+            }
+            Ok(())
+        }
+    }
+}
+
+/// `cass usage`: token totals and cost grouped by day, agent, workspace or model
+#[allow(clippy::too_many_arguments)]
+fn run_usage(
+    by: crate::usage::UsageGrouping,
+    since: Option<&str>,
+    until: Option<&str>,
+    agents: Vec<String>,
+    workspace: Option<String>,
+    source: Option<String>,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    use crate::sources::provenance::SourceFilter;
+    use crate::usage::{PricingTable, UsageFilter, UsageGrouping};
+
+    let parse_time = |flag: &str, value: Option<&str>| -> CliResult<Option<i64>> {
+        value
+            .map(|v| {
+                parse_datetime_flexible(v).ok_or_else(|| {
+                    CliError::usage(
+                        format!("Invalid --{flag} value: {v}"),
+                        Some("Use an ISO date like 2025-01-31 or an RFC 3339 timestamp".into()),
+                    )
+                })
+            })
+            .transpose()
+    };
+    let filter = UsageFilter {
+        since: parse_time("since", since)?,
+        until: parse_time("until", until)?,
+        agents,
+        workspace,
+        source: source.as_deref().map(SourceFilter::parse),
+    };
+
+    let pricing_file = PricingTable::path();
+    let pricing = match &pricing_file {
+        Some(path) => PricingTable::load(path).map_err(|e| CliError {
+            code: 2,
+            kind: "config",
+            message: format!("Invalid pricing table: {e:#}"),
+            hint: Some(
+                "Expected [models.\"<model>\"] tables with input and output USD per million tokens"
+                    .into(),
+            ),
+            retryable: false,
+        })?,
+        None => PricingTable::default(),
+    };
+    let storage = open_cli_storage(data_dir_override, db_override)?;
+    let structured_format = if json {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+
+    let report = crate::usage::report(storage.raw(), by, &filter, &pricing, pricing_file)
+        .map_err(|e| CliError::unknown(format!("usage query: {e:#}")))?;
+    if let Some(fmt) = structured_format {
+        let payload = serde_json::to_value(&report)
+            .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+        return output_structured_value(payload, fmt);
+    }
+
+    if report.rows.is_empty() {
+        println!(
+            "No token usage recorded. Usage is captured for Codex and Claude Code sessions indexed by this version; run 'cass index --full' to backfill."
+        );
+        return Ok(());
+    }
+    let label = match by {
+        UsageGrouping::Day => "Day",
+        UsageGrouping::Agent => "Agent",
+        UsageGrouping::Workspace => "Workspace",
+        UsageGrouping::Model => "Model",
+    };
+    println!(
+        "{label:<32} {:>8} {:>12} {:>12} {:>12} {:>12} {:>10}",
+        "Sessions", "Input", "Output", "Cache read", "Cache write", "Cost"
+    );
+    for row in report.rows.iter().chain(std::iter::once(&report.totals)) {
+        println!(
+            "{:<32} {:>8} {:>12} {:>12} {:>12} {:>12} {:>10}",
+            row.key,
+            row.sessions,
+            row.input_tokens,
+            row.output_tokens,
+            row.cache_read_tokens,
+            row.cache_write_tokens,
+            format!("${:.2}", row.cost_usd)
+        );
+    }
+    if !report.unpriced_models.is_empty() {
+        let location = report
+            .pricing_file
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "pricing.toml".into());
+        println!(
+            "\nNo price for: {}. Add them to {location} to include them in the cost.",
+            report.unpriced_models.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn print_forget_conversations(report: &crate::forget::ForgetReport) {
+    const SHOWN: usize = 20;
+    for conv in report.conversations.iter().take(SHOWN) {
+        println!(
+            "  {:<12} {:>4} msgs  {}  {}",
+            conv.agent,
+            conv.message_count,
+            conv.title.as_deref().unwrap_or("(untitled)"),
+            conv.source_path
+        );
+    }
+    if report.conversations.len() > SHOWN {
+        println!("  ... and {} more", report.conversations.len() - SHOWN);
+    }
+}
+
+fn scan_secrets_filters(
+    agents: Vec<String>,
+    workspaces: Vec<String>,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> CliResult<crate::pages::secret_scan::SecretScanFilters> {
+    Ok(crate::pages::secret_scan::SecretScanFilters {
+        agents: (!agents.is_empty()).then_some(agents),
+        workspaces: (!workspaces.is_empty())
+            .then(|| workspaces.into_iter().map(PathBuf::from).collect()),
+        since_ts: parse_time_flag("since", since)?,
+        until_ts: parse_time_flag("until", until)?,
+    })
+}
+
+/// `cass scan-secrets`: audit the index with the pages secret patterns, plus the
+/// counts of what ingest-time redaction already replaced
+fn run_scan_secrets(
+    filters: &crate::pages::secret_scan::SecretScanFilters,
+    secrets_allow: &[String],
+    secrets_deny: &[String],
+    fail_on_secrets: bool,
+    data_dir_override: &Option<PathBuf>,
+    db_override: Option<PathBuf>,
+    json: bool,
+) -> CliResult<()> {
+    use crate::pages::secret_scan::{SecretScanConfig, print_human_report, scan_database};
+
+    let structured_format = if json {
+        Some(RobotFormat::Json)
+    } else {
+        robot_format_from_env()
+    };
+    let config = SecretScanConfig::from_inputs(secrets_allow, secrets_deny).map_err(|e| {
+        CliError::usage(
+            format!("Invalid secret pattern: {e}"),
+            Some("--secrets-allow and --secrets-deny take regular expressions".into()),
+        )
+    })?;
+
+    // Opening through SqliteStorage also migrates older databases
+    let db_path = db_override.clone().unwrap_or_else(|| {
+        data_dir_override
+            .clone()
+            .unwrap_or_else(default_data_dir)
+            .join("agent_search.db")
+    });
+    let storage = open_cli_storage(data_dir_override, db_override)?;
+    let redaction_error = |e: anyhow::Error| CliError {
+        code: 9,
+        kind: "scan-secrets",
+        message: format!("Failed to read redaction counts: {e:#}"),
+        hint: None,
+        retryable: false,
+    };
+    let redact_enabled = storage.get_redact_secrets().map_err(redaction_error)?;
+    let redactions = storage.redaction_summary().map_err(redaction_error)?;
+    drop(storage);
+
+    let report = scan_database(&db_path, filters, &config, None, None).map_err(|e| CliError {
+        code: 9,
+        kind: "scan-secrets",
+        message: format!("Secret scan failed: {e:#}"),
+        hint: None,
+        retryable: false,
+    })?;
+
+    if let Some(fmt) = structured_format {
+        let mut payload = serde_json::to_value(&report)
+            .map_err(|e| CliError::unknown(format!("serialize: {e}")))?;
+        payload["ingest_redaction"] = serde_json::json!({
+            "enabled": redact_enabled,
+            "conversations": redactions.conversations,
+            "total": redactions.total,
+            "by_kind": redactions.by_kind,
+        });
+        output_structured_value(payload, fmt)?;
+    } else {
+        let mut term = console::Term::stdout();
+        print_human_report(&mut term, &report, 3)
+            .map_err(|e| CliError::unknown(format!("print report: {e}")))?;
+        if redact_enabled {
+            println!(
+                "  Ingest redaction is on: {} secret(s) replaced in {} conversation(s).",
+                redactions.total, redactions.conversations
+            );
+        } else {
+            println!("  Ingest redaction is off; enable it with 'cass index --redact-secrets'.");
+        }
+    }
+
+    if fail_on_secrets && report.summary.total > 0 {
+        return Err(CliError {
+            code: 1,
+            kind: "secrets_found",
+            message: format!(
+                "Secrets detected in the index ({} finding(s))",
+                report.summary.total
+            ),
+            hint: Some(
+                "Remove them with 'cass forget --query <secret> --yes', then keep new ones out with 'cass index --redact-secrets'".into(),
+            ),
+            retryable: false,
+        });
+    }
+    Ok(())
+}
+
 fn parse_datetime_flexible(s: &str) -> Option<i64> {
     use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
 
@@ -0,0 +1,762 @@
+//! Model Context Protocol server over stdio (`cass mcp`).
+//!
+//! Serves the robot-mode commands (search, view, expand, context, timeline,
+//! stats) as MCP tools and indexed sessions as resources, so agent harnesses
+//! do not pay a process spawn per call. Tool input schemas are generated from
+//! the same clap definitions as `cass introspect`. A tool call is turned back
+//! into a command line, parsed by clap and run in-process with its structured
+//! output captured, so results are exactly what `--json` prints. The search
+//! client (Tantivy reader and result cache) stays open for the life of the
+//! server and reloads when the index is updated. The HTTP API (`cass serve`)
+//! dispatches its requests through the same [`McpServer`].
+//!
+//! Transport: one JSON-RPC 2.0 message per line on stdin/stdout; logs go to
+//! stderr.
+
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use serde_json::{Map, Value, json};
+
+use crate::model::types::Conversation;
+use crate::search::query::SearchClientOptions;
+use crate::search::tantivy::index_dir;
+use crate::storage::sqlite::SqliteStorage;
+use crate::{
+    ArgumentSchema, Cli, CliError, CliResult, CommandSchema, Commands, ProgressResolved,
+    SharedSearch, WrapConfig,
+};
+
+/// Protocol revisions this server speaks, newest first
+pub const SUPPORTED_PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// Commands exposed as tools
+pub const TOOL_COMMANDS: &[&str] = &["search", "view", "expand", "context", "timeline", "stats"];
+
+/// Arguments the server sets itself, or that only shape terminal output
+const HIDDEN_ARGS: &[&str] = &["json", "display", "highlight", "data-dir", "sessions-from"];
+
+/// Sessions per `resources/list` page
+const RESOURCE_PAGE_SIZE: i64 = 100;
+
+const SESSION_URI_PREFIX: &str = "cass://session/";
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// MCP: the requested resource does not exist
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// Serve MCP on stdin/stdout until stdin is closed.
+pub fn run_stdio(data_dir: PathBuf, db_override: Option<PathBuf>) -> CliResult<()> {
+    let mut server = McpServer::new(data_dir, db_override);
+    server
+        .serve(io::stdin().lock(), io::stdout().lock())
+        .map_err(|e| CliError {
+            code: 9,
+            kind: "mcp",
+            message: format!("MCP transport failed: {e}"),
+            hint: None,
+            retryable: false,
+        })
+}
+
+pub struct McpServer {
+    data_dir: PathBuf,
+    db_override: Option<PathBuf>,
+    tools: Vec<CommandSchema>,
+    /// Opened by the first search and kept open, with its semantic context
+    search: Option<SharedSearch>,
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl McpServer {
+    pub fn new(data_dir: PathBuf, db_override: Option<PathBuf>) -> Self {
+        let tools = crate::build_command_schemas()
+            .into_iter()
+            .filter(|command| TOOL_COMMANDS.contains(&command.name.as_str()))
+            .collect();
+        Self {
+            data_dir,
+            db_override,
+            tools,
+            search: None,
+        }
+    }
+
+    /// Answer requests from `input` until it is closed.
+    pub fn serve(&mut self, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(message) => self.handle(message),
+                Err(e) => Some(error_response(
+                    Value::Null,
+                    PARSE_ERROR,
+                    format!("invalid JSON: {e}"),
+                )),
+            };
+            if let Some(response) = response {
+                serde_json::to_writer(&mut output, &response)?;
+                output.write_all(b"\n")?;
+                output.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Answer one JSON-RPC message. Notifications and responses get no reply.
+    pub fn handle(&mut self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            if message.get("result").is_some() || message.get("error").is_some() {
+                return None;
+            }
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "expected a JSON-RPC 2.0 request",
+            ));
+        };
+        let id = id?;
+        if message.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+            return Some(error_response(
+                id,
+                INVALID_REQUEST,
+                "jsonrpc must be \"2.0\"",
+            ));
+        }
+
+        let params = message.get("params").cloned().unwrap_or_else(|| json!({}));
+        let result = match method {
+            "initialize" => Ok(initialize(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({
+                "tools": self.tools.iter().map(tool_definition).collect::<Vec<_>>(),
+            })),
+            "tools/call" => self.call_tool(&params),
+            "resources/list" => self.list_resources(&params),
+            "resources/templates/list" => Ok(json!({
+                "resourceTemplates": [{
+                    "uriTemplate": format!("{SESSION_URI_PREFIX}{{id}}"),
+                    "name": "session",
+                    "description": "An indexed session with its messages",
+                    "mimeType": "application/json",
+                }],
+            })),
+            "resources/read" => self.read_resource(&params),
+            other => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method {other}"),
+            )),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_response(id, e.code, e.message),
+        })
+    }
+
+    fn call_tool(&mut self, params: &Value) -> Result<Value, RpcError> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "missing tool name"))?;
+        let arguments = match params.get("arguments") {
+            None | Some(Value::Null) => Map::new(),
+            Some(Value::Object(arguments)) => arguments.clone(),
+            Some(_) => {
+                return Err(RpcError::new(
+                    INVALID_PARAMS,
+                    "tool arguments must be an object",
+                ));
+            }
+        };
+        if self.tool(name).is_none() {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("unknown tool {name}"),
+            ));
+        }
+        Ok(match self.run_command(name, &arguments) {
+            Ok(payload) => tool_result(payload, false),
+            Err(err) => tool_result(error_payload(&err), true),
+        })
+    }
+
+    /// Schema of the tool called `name`
+    pub(crate) fn tool(&self, name: &str) -> Option<&CommandSchema> {
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+
+    /// Run tool `name` with `arguments` and return its `--json` payload.
+    pub(crate) fn run_command(
+        &mut self,
+        name: &str,
+        arguments: &Map<String, Value>,
+    ) -> CliResult<Value> {
+        let schema = self
+            .tool(name)
+            .ok_or_else(|| CliError::usage(format!("unknown command {name}"), None))?;
+        let argv = command_line(
+            schema,
+            arguments,
+            &self.data_dir,
+            self.db_override.as_deref(),
+        )?;
+        self.run_tool(argv)
+    }
+
+    fn run_tool(&mut self, argv: Vec<String>) -> CliResult<Value> {
+        let cli = Cli::try_parse_from(&argv)
+            .map_err(|e| CliError::usage(e.to_string().trim().to_string(), None))?;
+        let db = cli.db;
+        let Some(command) = cli.command else {
+            return Err(CliError::unknown("no command given"));
+        };
+        if matches!(command, Commands::Search { .. }) {
+            self.ensure_search_client()?;
+        }
+        let shared = self.search.as_ref();
+
+        crate::capture_structured_output(|| match command {
+            search @ Commands::Search { .. } => crate::run_search_command(
+                search,
+                db,
+                WrapConfig::new(None, true),
+                ProgressResolved::None,
+                true,
+                shared,
+            ),
+            Commands::View {
+                path,
+                line,
+                context,
+                subagents,
+                ..
+            } => crate::run_view(&path, line, context, subagents, true),
+            Commands::Expand {
+                path,
+                line,
+                context,
+                subagents,
+                ..
+            } => crate::run_expand(&path, line, context, subagents, true),
+            Commands::Context {
+                path,
+                data_dir,
+                limit,
+                ..
+            } => crate::run_context(&path, &data_dir, db, true, limit),
+            Commands::Timeline {
+                since,
+                until,
+                today,
+                agent,
+                data_dir,
+                group_by,
+                source,
+                ..
+            } => crate::run_timeline(
+                since.as_deref(),
+                until.as_deref(),
+                today,
+                &agent,
+                &data_dir,
+                db,
+                true,
+                group_by,
+                source,
+            ),
+            Commands::Stats {
+                data_dir,
+                source,
+                by_source,
+                ..
+            } => crate::run_stats(&data_dir, db, true, source.as_deref(), by_source),
+            _ => Err(CliError::unknown("not an MCP tool")),
+        })
+    }
+
+    pub(crate) fn ensure_search_client(&mut self) -> CliResult<()> {
+        if self.search.is_none() {
+            let index_path = index_dir(&self.data_dir).map_err(|e| CliError {
+                code: 9,
+                kind: "path",
+                message: format!("failed to open index dir: {e}"),
+                hint: None,
+                retryable: false,
+            })?;
+            self.search = Some(SharedSearch::new(crate::open_search_client(
+                &index_path,
+                &self.db_path(),
+                SearchClientOptions::default(),
+            )?));
+        }
+        Ok(())
+    }
+
+    fn db_path(&self) -> PathBuf {
+        self.db_override
+            .clone()
+            .unwrap_or_else(|| self.data_dir.join("agent_search.db"))
+    }
+
+    fn open_storage(&self) -> Result<SqliteStorage, RpcError> {
+        let db_path = self.db_path();
+        if !db_path.exists() {
+            return Err(RpcError::new(
+                INTERNAL_ERROR,
+                format!(
+                    "Database not found at {}. Run 'cass index --full' first.",
+                    db_path.display()
+                ),
+            ));
+        }
+        SqliteStorage::open_readonly(&db_path)
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, format!("{e:#}")))
+    }
+
+    fn list_resources(&self, params: &Value) -> Result<Value, RpcError> {
+        let offset = match params.get("cursor") {
+            None | Some(Value::Null) => 0,
+            Some(Value::String(cursor)) => cursor
+                .parse::<i64>()
+                .map_err(|_| RpcError::new(INVALID_PARAMS, "invalid cursor"))?,
+            Some(_) => return Err(RpcError::new(INVALID_PARAMS, "invalid cursor")),
+        };
+        let conversations = self
+            .open_storage()?
+            .list_conversations(RESOURCE_PAGE_SIZE, offset)
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, format!("{e:#}")))?;
+
+        let mut result = json!({
+            "resources": conversations.iter().filter_map(session_resource).collect::<Vec<_>>(),
+        });
+        if conversations.len() as i64 == RESOURCE_PAGE_SIZE {
+            result["nextCursor"] = json!((offset + RESOURCE_PAGE_SIZE).to_string());
+        }
+        Ok(result)
+    }
+
+    fn read_resource(&self, params: &Value) -> Result<Value, RpcError> {
+        let uri = params
+            .get("uri")
+            .and_then(Value::as_str)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "missing uri"))?;
+        let not_found = || RpcError::new(RESOURCE_NOT_FOUND, format!("resource not found: {uri}"));
+        let id = uri
+            .strip_prefix(SESSION_URI_PREFIX)
+            .and_then(|id| id.parse::<i64>().ok())
+            .ok_or_else(not_found)?;
+
+        let session = session_json(&self.open_storage()?, id)
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, format!("{e:#}")))?
+            .ok_or_else(not_found)?;
+        Ok(json!({
+            "contents": [{
+                "uri": uri,
+                "mimeType": "application/json",
+                "text": serde_json::to_string_pretty(&session).unwrap_or_default(),
+            }],
+        }))
+    }
+}
+
+fn initialize(params: &Value) -> Value {
+    let version = params
+        .get("protocolVersion")
+        .and_then(Value::as_str)
+        .filter(|requested| SUPPORTED_PROTOCOL_VERSIONS.contains(requested))
+        .unwrap_or(SUPPORTED_PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": {
+            "tools": { "listChanged": false },
+            "resources": { "subscribe": false, "listChanged": false },
+        },
+        "serverInfo": { "name": "cass", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": "Search coding agent sessions indexed by cass. Start with `search`; \
+            pass a hit's source_path (and line_number) to `view`, `expand` or `context`.",
+    })
+}
+
+fn error_response(id: Value, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message.into() },
+    })
+}
+
+/// The `{"error": {...}}` payload robot mode prints for a failed command
+pub(crate) fn error_payload(err: &CliError) -> Value {
+    json!({
+        "error": {
+            "code": err.code,
+            "kind": err.kind,
+            "message": err.message,
+            "hint": err.hint,
+            "retryable": err.retryable,
+        }
+    })
+}
+
+fn tool_result(payload: Value, is_error: bool) -> Value {
+    let mut result = json!({
+        "content": [{
+            "type": "text",
+            "text": serde_json::to_string_pretty(&payload).unwrap_or_default(),
+        }],
+        "isError": is_error,
+    });
+    // Structured content must be an object; `expand` returns an array
+    if payload.is_object() {
+        result["structuredContent"] = payload;
+    }
+    result
+}
+
+pub(crate) fn exposed_args(schema: &CommandSchema) -> impl Iterator<Item = &ArgumentSchema> {
+    schema
+        .arguments
+        .iter()
+        .filter(|arg| !HIDDEN_ARGS.contains(&arg.name.as_str()))
+}
+
+/// JSON property name of an argument (`max-tokens` → `max_tokens`)
+pub(crate) fn property_name(arg: &ArgumentSchema) -> String {
+    arg.name.replace('-', "_")
+}
+
+/// MCP tool definition, with the input schema derived from the introspect data.
+fn tool_definition(schema: &CommandSchema) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for arg in exposed_args(schema) {
+        if arg.required {
+            required.push(property_name(arg));
+        }
+        properties.insert(property_name(arg), argument_schema(arg));
+    }
+    json!({
+        "name": schema.name,
+        "description": format!(
+            "{}. Returns the JSON of `cass {} --json`.",
+            schema.description.trim_end_matches('.'),
+            schema.name
+        ),
+        "inputSchema": {
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        },
+    })
+}
+
+fn argument_schema(arg: &ArgumentSchema) -> Value {
+    let is_flag = arg.arg_type == "flag";
+    let mut schema = match arg.value_type.as_deref() {
+        _ if is_flag => json!({ "type": "boolean" }),
+        Some("integer") => json!({ "type": "integer" }),
+        Some("enum") => json!({ "type": "string", "enum": arg.enum_values }),
+        _ => json!({ "type": "string" }),
+    };
+    let repeatable = !is_flag && arg.repeatable == Some(true);
+    if repeatable {
+        schema = json!({ "type": "array", "items": schema });
+    }
+    if !arg.description.is_empty() {
+        schema["description"] = json!(arg.description);
+    }
+    if let Some(default) = arg.default.as_deref().filter(|_| !is_flag && !repeatable) {
+        schema["default"] = match default.parse::<u64>() {
+            Ok(n) if arg.value_type.as_deref() == Some("integer") => json!(n),
+            _ => json!(default),
+        };
+    }
+    schema
+}
+
+/// Turn tool arguments back into a `cass` command line.
+fn command_line(
+    schema: &CommandSchema,
+    arguments: &Map<String, Value>,
+    data_dir: &Path,
+    db_override: Option<&Path>,
+) -> CliResult<Vec<String>> {
+    if let Some(unknown) = arguments
+        .keys()
+        .find(|key| !exposed_args(schema).any(|arg| property_name(arg) == **key))
+    {
+        return Err(CliError::usage(
+            format!("unknown argument '{unknown}' for {}", schema.name),
+            None,
+        ));
+    }
+
+    let mut argv = vec!["cass".to_string()];
+    if let Some(db) = db_override {
+        argv.push(format!("--db={}", db.display()));
+    }
+    argv.push(schema.name.clone());
+    let mut positionals = Vec::new();
+    for arg in exposed_args(schema) {
+        let key = property_name(arg);
+        let values = match arguments.get(&key) {
+            None | Some(Value::Null) => continue,
+            Some(Value::Array(items)) if arg.repeatable == Some(true) => items.iter().collect(),
+            Some(value) => vec![value],
+        };
+        for value in values {
+            if arg.arg_type == "flag" {
+                match value {
+                    Value::Bool(true) => argv.push(format!("--{}", arg.name)),
+                    Value::Bool(false) => {}
+                    _ => {
+                        return Err(CliError::usage(format!("'{key}' must be a boolean"), None));
+                    }
+                }
+                continue;
+            }
+            let text = match value {
+                Value::String(text) => text.clone(),
+                Value::Number(n) => n.to_string(),
+                _ => {
+                    return Err(CliError::usage(
+                        format!("'{key}' must be a string or a number"),
+                        None,
+                    ));
+                }
+            };
+            if arg.arg_type == "positional" {
+                positionals.push(text);
+            } else {
+                // `--flag=value` so values starting with '-' are not read as flags
+                argv.push(format!("--{}={text}", arg.name));
+            }
+        }
+    }
+
+    if schema.arguments.iter().any(|arg| arg.name == "data-dir") {
+        argv.push(format!("--data-dir={}", data_dir.display()));
+    }
+    if schema.arguments.iter().any(|arg| arg.name == "json") {
+        argv.push("--json".to_string());
+    }
+    if !positionals.is_empty() {
+        argv.push("--".to_string());
+        argv.extend(positionals);
+    }
+    Ok(argv)
+}
+
+/// A session with its messages, as served by `resources/read` and `/sessions/{id}`.
+pub(crate) fn session_json(storage: &SqliteStorage, id: i64) -> anyhow::Result<Option<Value>> {
+    let Some(conv) = storage.get_conversation(id)? else {
+        return Ok(None);
+    };
+    let messages = storage.fetch_messages(id)?;
+    Ok(Some(json!({
+        "id": id,
+        "agent": conv.agent_slug,
+        "title": conv.title,
+        "workspace": conv.workspace,
+        "source_path": conv.source_path,
+        "source_id": conv.source_id,
+        "started_at": format_ts(conv.started_at),
+        "ended_at": format_ts(conv.ended_at),
+        "messages": messages.iter().map(|m| json!({
+            "idx": m.idx,
+            "role": m.role.to_string(),
+            "author": m.author,
+            "created_at": format_ts(m.created_at),
+            "content": m.content,
+        })).collect::<Vec<_>>(),
+    })))
+}
+
+/// One line of the `/sessions` listing
+pub(crate) fn session_summary(conv: &Conversation) -> Option<Value> {
+    Some(json!({
+        "id": conv.id?,
+        "agent": conv.agent_slug,
+        "title": conv.title,
+        "workspace": conv.workspace,
+        "source_path": conv.source_path,
+        "source_id": conv.source_id,
+        "started_at": format_ts(conv.started_at),
+    }))
+}
+
+fn session_resource(conv: &Conversation) -> Option<Value> {
+    let id = conv.id?;
+    let name = conv
+        .title
+        .clone()
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| conv.source_path.display().to_string());
+    let mut description = format!("{} session", conv.agent_slug);
+    if let Some(workspace) = &conv.workspace {
+        description.push_str(&format!(" in {}", workspace.display()));
+    }
+    if let Some(started) = format_ts(conv.started_at) {
+        description.push_str(&format!(", started {started}"));
+    }
+    Some(json!({
+        "uri": format!("{SESSION_URI_PREFIX}{id}"),
+        "name": name,
+        "description": description,
+        "mimeType": "application/json",
+    }))
+}
+
+fn format_ts(ts: Option<i64>) -> Option<String> {
+    ts.and_then(chrono::DateTime::from_timestamp_millis)
+        .map(|d| d.to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server() -> McpServer {
+        McpServer::new(PathBuf::from("/nonexistent/cass"), None)
+    }
+
+    fn request(id: i64, method: &str, params: Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+    }
+
+    #[test]
+    fn initialize_negotiates_protocol_version() {
+        let mut server = server();
+        let known = server
+            .handle(request(
+                1,
+                "initialize",
+                json!({ "protocolVersion": "2025-03-26" }),
+            ))
+            .unwrap();
+        assert_eq!(known["result"]["protocolVersion"], "2025-03-26");
+        assert_eq!(known["result"]["serverInfo"]["name"], "cass");
+
+        let unknown = server
+            .handle(request(
+                2,
+                "initialize",
+                json!({ "protocolVersion": "1999-01-01" }),
+            ))
+            .unwrap();
+        assert_eq!(
+            unknown["result"]["protocolVersion"],
+            SUPPORTED_PROTOCOL_VERSIONS[0]
+        );
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(server.handle(notification).is_none());
+        let missing = server.handle(request(3, "sampling/createMessage", json!({})));
+        assert_eq!(missing.unwrap()["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn tools_mirror_introspect_arguments() {
+        let mut server = server();
+        let response = server.handle(request(1, "tools/list", json!({}))).unwrap();
+        let tools = response["result"]["tools"].as_array().unwrap();
+        let names: Vec<&str> = tools.iter().filter_map(|t| t["name"].as_str()).collect();
+        assert_eq!(names.len(), TOOL_COMMANDS.len());
+        for name in TOOL_COMMANDS {
+            assert!(names.contains(name), "missing tool {name}");
+        }
+
+        let search = tools.iter().find(|t| t["name"] == "search").unwrap();
+        let schema = &search["inputSchema"];
+        assert_eq!(schema["required"], json!(["query"]));
+        assert_eq!(schema["properties"]["limit"]["type"], "integer");
+        assert_eq!(schema["properties"]["agent"]["type"], "array");
+        assert_eq!(schema["properties"]["explain"]["type"], "boolean");
+        assert!(schema["properties"]["max_tokens"].is_object());
+        assert!(schema["properties"]["robot_format"].is_object());
+        assert!(schema["properties"].get("json").is_none());
+        assert!(schema["properties"].get("data_dir").is_none());
+    }
+
+    #[test]
+    fn arguments_become_a_command_line() {
+        let server = server();
+        let search = server.tools.iter().find(|t| t.name == "search").unwrap();
+        let arguments = json!({
+            "query": "-leading dash",
+            "agent": ["codex", "claude_code"],
+            "limit": 5,
+            "explain": true,
+            "today": false,
+            "since": "-7d",
+        });
+        let argv = command_line(
+            search,
+            arguments.as_object().unwrap(),
+            Path::new("/data"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(argv[..2], ["cass", "search"]);
+        assert!(argv.contains(&"--agent=claude_code".to_string()));
+        assert!(argv.contains(&"--limit=5".to_string()));
+        assert!(argv.contains(&"--explain".to_string()));
+        assert!(!argv.contains(&"--today".to_string()));
+        assert!(argv.ends_with(&["--".to_string(), "-leading dash".to_string()]));
+
+        let cli = Cli::try_parse_from(&argv).unwrap();
+        let Some(Commands::Search {
+            query,
+            agent,
+            limit,
+            data_dir,
+            json,
+            since,
+            ..
+        }) = cli.command
+        else {
+            panic!("expected a search command");
+        };
+        assert_eq!(query, "-leading dash");
+        assert_eq!(agent, vec!["codex", "claude_code"]);
+        assert_eq!(limit, 5);
+        assert_eq!(data_dir, Some(PathBuf::from("/data")));
+        assert!(json);
+        assert_eq!(since.as_deref(), Some("-7d"));
+
+        let unknown = json!({ "query": "x", "data_dir": "/etc" });
+        assert!(
+            command_line(
+                search,
+                unknown.as_object().unwrap(),
+                Path::new("/data"),
+                None
+            )
+            .is_err()
+        );
+    }
+}
@@ -86,6 +86,69 @@ pub struct Message {
     pub snippets: Vec<Snippet>,
 }
 
+/// Tokens an agent reported for one message (one model request, or several
+/// summed up). Connectors put it in the message's `extra` under `cass.usage`;
+/// storage keeps it in `message_usage`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Input tokens that were not read from the prompt cache
+    #[serde(default)]
+    pub input_tokens: i64,
+    #[serde(default)]
+    pub output_tokens: i64,
+    #[serde(default)]
+    pub cache_read_tokens: i64,
+    #[serde(default)]
+    pub cache_write_tokens: i64,
+}
+
+impl TokenUsage {
+    pub fn total(&self) -> i64 {
+        self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_write_tokens
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// Add `other`'s counts; the model is kept unless this usage has none.
+    pub fn add(&mut self, other: &TokenUsage) {
+        if self.model.is_none() {
+            self.model.clone_from(&other.model);
+        }
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cache_read_tokens += other.cache_read_tokens;
+        self.cache_write_tokens += other.cache_write_tokens;
+    }
+
+    /// Usage stored in a message's `extra` by its connector.
+    pub fn from_extra(extra: &serde_json::Value) -> Option<Self> {
+        let usage = extra.get("cass")?.get("usage")?;
+        serde_json::from_value(usage.clone()).ok()
+    }
+
+    /// Add this usage to whatever `extra` already carries.
+    pub fn add_to_extra(&self, extra: &mut serde_json::Value) {
+        let mut total = Self::from_extra(extra).unwrap_or_default();
+        total.add(self);
+        if !extra.is_object() {
+            *extra = serde_json::json!({});
+        }
+        let Some(obj) = extra.as_object_mut() else {
+            return;
+        };
+        let cass = obj
+            .entry("cass".to_string())
+            .or_insert_with(|| serde_json::json!({}));
+        if let (Some(cass), Ok(value)) = (cass.as_object_mut(), serde_json::to_value(&total)) {
+            cass.insert("usage".to_string(), value);
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Snippet {
     pub id: Option<i64>,
@@ -102,6 +165,68 @@ pub struct Tag {
     pub name: String,
 }
 
+/// A file a session touched, aggregated from the snippets of its tool calls.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TouchedFile {
+    pub file_path: String,
+    pub language: Option<String>,
+    /// Tool calls that carried file content (edits, writes, patches)
+    pub edits: i64,
+    /// Tool calls that only referenced the file (reads, deletes)
+    pub reads: i64,
+    pub first_line: Option<i64>,
+    pub last_line: Option<i64>,
+    /// Latest timestamp of a message touching the file (ms since epoch)
+    pub last_touched_at: Option<i64>,
+}
+
+/// Criteria for picking stored conversations (used by `cass forget`).
+///
+/// Every non-empty criterion must match; repeated values within one criterion
+/// match any of them. An empty selector matches every conversation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConversationSelector {
+    pub source_ids: Vec<String>,
+    pub agents: Vec<String>,
+    pub workspaces: Vec<String>,
+    /// SQLite `GLOB` patterns over `source_path` (`*` also matches `/`)
+    pub path_globs: Vec<String>,
+    /// Start of the conversation (ms since epoch), inclusive
+    pub started_from: Option<i64>,
+    /// Start of the conversation (ms since epoch), inclusive
+    pub started_to: Option<i64>,
+    /// Full-text query; picks conversations with at least one matching message
+    pub query: Option<String>,
+    /// Conversation tags (`cass tag add`), matched after normalization
+    pub tags: Vec<String>,
+}
+
+impl ConversationSelector {
+    pub fn is_empty(&self) -> bool {
+        self.source_ids.is_empty()
+            && self.agents.is_empty()
+            && self.workspaces.is_empty()
+            && self.path_globs.is_empty()
+            && self.started_from.is_none()
+            && self.started_to.is_none()
+            && self.query.is_none()
+            && self.tags.is_empty()
+    }
+}
+
+/// A conversation picked by a [`ConversationSelector`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SelectedConversation {
+    pub conversation_id: i64,
+    pub agent: String,
+    pub workspace: Option<String>,
+    pub source_id: String,
+    pub source_path: String,
+    pub title: Option<String>,
+    pub started_at: Option<i64>,
+    pub message_count: i64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -656,4 +781,25 @@ mod tests {
 
         assert_eq!(deserialized.metadata_json, metadata);
     }
+
+    #[test]
+    fn token_usage_accumulates_in_extra() {
+        let mut extra = json!({"type": "assistant"});
+        let request = TokenUsage {
+            model: Some("gpt-5-codex".into()),
+            input_tokens: 100,
+            output_tokens: 20,
+            cache_read_tokens: 400,
+            cache_write_tokens: 0,
+        };
+        request.add_to_extra(&mut extra);
+        request.add_to_extra(&mut extra);
+
+        let usage = TokenUsage::from_extra(&extra).unwrap();
+        assert_eq!(usage.model.as_deref(), Some("gpt-5-codex"));
+        assert_eq!(usage.input_tokens, 200);
+        assert_eq!(usage.total(), 1040);
+        assert_eq!(extra["type"], "assistant");
+        assert!(TokenUsage::from_extra(&json!({})).is_none());
+    }
 }
@@ -429,7 +429,7 @@ fn copy_blobs_directory(src_dir: &Path, dest_dir: &Path) -> Result<usize> {
 }
 
 /// Generate integrity manifest for all files in a directory
-fn generate_integrity_manifest(dir: &Path) -> Result<IntegrityManifest> {
+pub(crate) fn generate_integrity_manifest(dir: &Path) -> Result<IntegrityManifest> {
     let mut files = BTreeMap::new();
 
     collect_file_hashes(dir, dir, &mut files)?;
@@ -160,6 +160,28 @@ impl EncryptionEngine {
         }
     }
 
+    /// Create an engine around an existing DEK and its key slots
+    ///
+    /// Used to re-encrypt a payload without re-entering every password. The
+    /// export id is kept (slots are bound to it); the base nonce is fresh.
+    pub fn with_key(
+        dek: SecretKey,
+        export_id: [u8; 16],
+        key_slots: Vec<KeySlot>,
+        chunk_size: usize,
+    ) -> Self {
+        let mut base_nonce = [0u8; 12];
+        OsRng.fill_bytes(&mut base_nonce);
+
+        Self {
+            dek,
+            export_id,
+            base_nonce,
+            chunk_size: chunk_size.min(MAX_CHUNK_SIZE),
+            key_slots,
+        }
+    }
+
     /// Add a password-based key slot using Argon2id
     pub fn add_password_slot(&mut self, password: &str) -> Result<u8> {
         // Validate password
@@ -230,18 +252,31 @@ impl EncryptionEngine {
         progress: impl Fn(u64, u64),
     ) -> Result<EncryptionConfig> {
         let input_path = input.as_ref();
-        let output_dir = output_dir.as_ref();
-
-        std::fs::create_dir_all(output_dir)?;
-        let payload_dir = output_dir.join("payload");
-        std::fs::create_dir_all(&payload_dir)?;
 
         // Read input file size for progress
         let input_size = std::fs::metadata(input_path)?.len();
 
         // Open input file
         let input_file = File::open(input_path).context("Failed to open input file")?;
-        let mut reader = BufReader::new(input_file);
+        self.encrypt_reader(
+            BufReader::new(input_file),
+            input_size,
+            output_dir.as_ref(),
+            progress,
+        )
+    }
+
+    /// Encrypt `input_size` bytes from `reader` into `output_dir`, as [`Self::encrypt_file`]
+    pub fn encrypt_reader<R: Read>(
+        &self,
+        mut reader: R,
+        input_size: u64,
+        output_dir: &Path,
+        progress: impl Fn(u64, u64),
+    ) -> Result<EncryptionConfig> {
+        std::fs::create_dir_all(output_dir)?;
+        let payload_dir = output_dir.join("payload");
+        std::fs::create_dir_all(&payload_dir)?;
 
         // Compress and encrypt in chunks
         let mut chunk_files = Vec::new();
@@ -399,6 +434,15 @@ impl DecryptionEngine {
         bail!("Invalid recovery secret or no matching key slot")
     }
 
+    /// The unwrapped DEK, for re-encrypting with [`EncryptionEngine::with_key`]
+    pub fn key(&self) -> &SecretKey {
+        &self.dek
+    }
+
+    pub fn config(&self) -> &EncryptionConfig {
+        &self.config
+    }
+
     /// Decrypt all chunks to output file
     pub fn decrypt_to_file<P: AsRef<Path>>(
         &self,
@@ -406,9 +450,17 @@ impl DecryptionEngine {
         output: P,
         progress: impl Fn(usize, usize),
     ) -> Result<()> {
-        let encrypted_dir = encrypted_dir.as_ref();
-        let output_path = output.as_ref();
+        let output_file = File::create(output.as_ref())?;
+        self.decrypt_to_writer(encrypted_dir.as_ref(), output_file, progress)
+    }
 
+    /// Decrypt all chunks into `output`, in order
+    pub fn decrypt_to_writer<W: Write>(
+        &self,
+        encrypted_dir: &Path,
+        output: W,
+        progress: impl Fn(usize, usize),
+    ) -> Result<()> {
         let cipher = Aes256Gcm::new_from_slice(self.dek.as_bytes()).expect("Invalid key length");
 
         let base_nonce = BASE64_STANDARD.decode(&self.config.base_nonce)?;
@@ -423,8 +475,7 @@ impl DecryptionEngine {
             );
         }
 
-        let mut output_file = File::create(output_path)?;
-        let mut writer = BufWriter::new(&mut output_file);
+        let mut writer = BufWriter::new(output);
 
         for (chunk_index, chunk_file) in self.config.payload.files.iter().enumerate() {
             progress(chunk_index, self.config.payload.chunk_count);
@@ -695,6 +746,46 @@ mod tests {
         assert_eq!(decrypted, test_data);
     }
 
+    #[test]
+    fn test_with_key_reencrypts_under_existing_slots() {
+        let temp_dir = TempDir::new().unwrap();
+        let first_dir = temp_dir.path().join("first");
+        let second_dir = temp_dir.path().join("second");
+
+        let mut engine = EncryptionEngine::new(1024);
+        engine.add_password_slot("test-password").unwrap();
+        let data = b"first payload".to_vec();
+        let config = engine
+            .encrypt_reader(&data[..], data.len() as u64, &first_dir, |_, _| {})
+            .unwrap();
+        let decryptor = DecryptionEngine::unlock_with_password(config, "test-password").unwrap();
+
+        let export_id: [u8; 16] = BASE64_STANDARD
+            .decode(&decryptor.config().export_id)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let engine = EncryptionEngine::with_key(
+            decryptor.key().clone(),
+            export_id,
+            decryptor.config().key_slots.clone(),
+            1024,
+        );
+        let data = vec![7u8; 3000];
+        let config = engine
+            .encrypt_reader(&data[..], data.len() as u64, &second_dir, |_, _| {})
+            .unwrap();
+        assert_eq!(config.payload.chunk_count, 3);
+        assert_ne!(config.base_nonce, decryptor.config().base_nonce);
+
+        let decryptor = DecryptionEngine::unlock_with_password(config, "test-password").unwrap();
+        let mut decrypted = Vec::new();
+        decryptor
+            .decrypt_to_writer(&second_dir, &mut decrypted, |_, _| {})
+            .unwrap();
+        assert_eq!(decrypted, data);
+    }
+
     #[test]
     fn test_multiple_key_slots() {
         let temp_dir = TempDir::new().unwrap();
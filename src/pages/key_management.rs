@@ -15,6 +15,7 @@
 //! - Add/revoke only modifies config.json; payload unchanged
 //! - Rotate re-encrypts entire payload with new DEK
 
+use crate::pages::bundle::generate_integrity_manifest;
 use crate::pages::encrypt::{
     Argon2Params, EncryptionConfig, KdfAlgorithm, KeySlot, SlotType, load_config,
 };
@@ -33,7 +34,7 @@ use rand::{RngCore, rngs::OsRng};
 use serde::Serialize;
 use sha2::Sha256;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufWriter, Read, Write};
 use std::path::Path;
 use tracing::info;
 use zeroize::Zeroize;
@@ -152,7 +153,7 @@ pub fn key_add_password(
     serde_json::to_writer_pretty(BufWriter::new(file), &config)?;
 
     // Update integrity.json if present
-    update_integrity_hash(archive_dir, "config.json")?;
+    update_integrity_hash(archive_dir)?;
 
     info!(slot_id, "Added password key slot");
     Ok(slot_id)
@@ -192,7 +193,7 @@ pub fn key_add_recovery(
     serde_json::to_writer_pretty(BufWriter::new(file), &config)?;
 
     // Update integrity.json if present
-    update_integrity_hash(archive_dir, "config.json")?;
+    update_integrity_hash(archive_dir)?;
 
     info!(slot_id, "Added recovery key slot");
     Ok((slot_id, secret))
@@ -239,7 +240,7 @@ pub fn key_revoke(
     serde_json::to_writer_pretty(BufWriter::new(file), &config)?;
 
     // Update integrity.json if present
-    update_integrity_hash(archive_dir, "config.json")?;
+    update_integrity_hash(archive_dir)?;
 
     info!(slot_id = slot_id_to_revoke, "Revoked key slot");
     Ok(RevokeResult {
@@ -680,88 +681,23 @@ fn build_chunk_aad(export_id: &[u8], chunk_index: u32) -> Vec<u8> {
     aad
 }
 
-/// Update integrity.json for a single file
-fn update_integrity_hash(archive_dir: &Path, filename: &str) -> Result<()> {
-    let integrity_path = archive_dir.join("integrity.json");
-    if !integrity_path.exists() {
+/// Refresh integrity.json after config.json changed, if the archive has one
+fn update_integrity_hash(archive_dir: &Path) -> Result<()> {
+    if !archive_dir.join("integrity.json").exists() {
         return Ok(());
     }
-
-    // Read current integrity.json
-    let file = File::open(&integrity_path)?;
-    let mut integrity: serde_json::Value = serde_json::from_reader(BufReader::new(file))?;
-
-    // Calculate new hash for file
-    let file_path = archive_dir.join(filename);
-    let content = std::fs::read(&file_path)?;
-    let hash = sha256_hex(&content);
-
-    // Update hash in integrity
-    if let Some(files) = integrity.get_mut("files").and_then(|f| f.as_object_mut()) {
-        files.insert(filename.to_string(), serde_json::json!(hash));
-    }
-
-    // Write updated integrity.json
-    let file = File::create(&integrity_path)?;
-    serde_json::to_writer_pretty(BufWriter::new(file), &integrity)?;
-
-    Ok(())
+    regenerate_integrity_manifest(archive_dir)
 }
 
-/// Regenerate entire integrity.json
+/// Regenerate entire integrity.json in the same format `BundleBuilder` writes,
+/// so `cass pages --verify` keeps passing after key changes
 fn regenerate_integrity_manifest(archive_dir: &Path) -> Result<()> {
-    let integrity_path = archive_dir.join("integrity.json");
-
-    // Find all files to hash
-    let mut files_map = serde_json::Map::new();
-
-    // Hash config.json
-    let config_path = archive_dir.join("config.json");
-    if config_path.exists() {
-        let content = std::fs::read(&config_path)?;
-        files_map.insert(
-            "config.json".to_string(),
-            serde_json::json!(sha256_hex(&content)),
-        );
-    }
-
-    // Hash all payload chunks
-    let payload_dir = archive_dir.join("payload");
-    if payload_dir.exists() {
-        for entry in std::fs::read_dir(&payload_dir)? {
-            let entry = entry?;
-            if entry.file_type()?.is_file() {
-                let filename = entry.file_name().to_string_lossy().to_string();
-                let content = std::fs::read(entry.path())?;
-                files_map.insert(
-                    format!("payload/{}", filename),
-                    serde_json::json!(sha256_hex(&content)),
-                );
-            }
-        }
-    }
-
-    let integrity = serde_json::json!({
-        "version": 1,
-        "algorithm": "sha256",
-        "files": files_map,
-        "generated_at": Utc::now().to_rfc3339(),
-    });
-
-    let file = File::create(&integrity_path)?;
-    serde_json::to_writer_pretty(BufWriter::new(file), &integrity)?;
-
+    let manifest = generate_integrity_manifest(archive_dir)?;
+    let file = File::create(archive_dir.join("integrity.json"))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), &manifest)?;
     Ok(())
 }
 
-/// Calculate SHA-256 hash as hex string
-fn sha256_hex(data: &[u8]) -> String {
-    use sha2::{Digest, Sha256};
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    hex::encode(hasher.finalize())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -955,4 +891,24 @@ mod tests {
         assert!(unwrap_dek_with_password(&config, "password-2").is_ok());
         assert!(unwrap_dek_with_password(&config, "password-3").is_ok());
     }
+
+    #[test]
+    fn test_key_changes_keep_bundle_integrity_format() {
+        use sha2::Digest;
+
+        let (_temp_dir, archive_dir) = setup_test_archive();
+        regenerate_integrity_manifest(&archive_dir).unwrap();
+
+        key_add_password(&archive_dir, "test-password", "new-password").unwrap();
+
+        let manifest: crate::pages::bundle::IntegrityManifest =
+            serde_json::from_slice(&std::fs::read(archive_dir.join("integrity.json")).unwrap())
+                .unwrap();
+        let config = std::fs::read(archive_dir.join("config.json")).unwrap();
+        let entry = &manifest.files["config.json"];
+        assert_eq!(entry.size, config.len() as u64);
+        assert_eq!(entry.sha256, hex::encode(Sha256::digest(&config)));
+        assert!(manifest.files.contains_key("payload/chunk-00000.bin"));
+        assert!(!manifest.files.contains_key("integrity.json"));
+    }
 }
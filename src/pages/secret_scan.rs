@@ -27,7 +27,8 @@ pub enum SecretSeverity {
 }
 
 impl SecretSeverity {
-    fn rank(self) -> u8 {
+    /// Sort key: `Critical` is 0, `Low` is 3.
+    pub fn rank(self) -> u8 {
         match self {
             SecretSeverity::Critical => 0,
             SecretSeverity::High => 1,
@@ -559,6 +560,29 @@ pub fn wizard_secret_scan<P: AsRef<Path>>(
     Ok(report)
 }
 
+/// A secret located in a piece of text, as byte offsets into that text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SecretSpan<'a> {
+    pub severity: SecretSeverity,
+    pub kind: &'a str,
+    pub pattern: &'a str,
+    pub start: usize,
+    pub end: usize,
+    /// Found by the entropy heuristics rather than a known credential pattern
+    pub heuristic: bool,
+}
+
+/// Every secret in `text`, using the same denylist, built-in patterns,
+/// allowlist and entropy heuristics as [`scan_database`]. Spans may overlap.
+pub fn find_secrets<'a>(text: &str, config: &'a SecretScanConfig) -> Vec<SecretSpan<'a>> {
+    let mut spans = Vec::new();
+    visit_secrets(text, config, |span| {
+        spans.push(span);
+        true
+    });
+    spans
+}
+
 fn scan_text(
     text: &str,
     location: SecretLocation,
@@ -568,126 +592,119 @@ fn scan_text(
     seen: &mut HashSet<String>,
     truncated: &mut bool,
 ) {
-    if *truncated || text.is_empty() {
+    if *truncated {
+        return;
+    }
+
+    visit_secrets(text, config, |span| {
+        if findings.len() >= config.max_findings {
+            *truncated = true;
+            return false;
+        }
+        push_finding(
+            findings,
+            seen,
+            FindingCandidate {
+                severity: span.severity,
+                kind: span.kind,
+                pattern: span.pattern,
+                text,
+                start: span.start,
+                end: span.end,
+                location: location.clone(),
+                ctx,
+            },
+            config,
+        );
+        true
+    });
+}
+
+/// Feed each secret found in `text` to `visit` until it returns false.
+fn visit_secrets<'a>(
+    text: &str,
+    config: &'a SecretScanConfig,
+    mut visit: impl FnMut(SecretSpan<'a>) -> bool,
+) {
+    if text.is_empty() {
         return;
     }
 
     // Denylist first (always critical)
     for deny in &config.denylist {
         for mat in deny.find_iter(text) {
-            if findings.len() >= config.max_findings {
-                *truncated = true;
+            let span = SecretSpan {
+                severity: SecretSeverity::Critical,
+                kind: "denylist",
+                pattern: deny.as_str(),
+                start: mat.start(),
+                end: mat.end(),
+                heuristic: false,
+            };
+            if !visit(span) {
                 return;
             }
-            push_finding(
-                findings,
-                seen,
-                FindingCandidate {
-                    severity: SecretSeverity::Critical,
-                    kind: "denylist",
-                    pattern: deny.as_str(),
-                    text,
-                    start: mat.start(),
-                    end: mat.end(),
-                    location: location.clone(),
-                    ctx,
-                },
-                config,
-            );
         }
     }
 
     // Built-in patterns
     for pattern in BUILTIN_PATTERNS.iter() {
         for mat in pattern.regex.find_iter(text) {
-            if findings.len() >= config.max_findings {
-                *truncated = true;
-                return;
-            }
-            let matched = &text[mat.start()..mat.end()];
-            if is_allowlisted(matched, config) {
+            if is_allowlisted(mat.as_str(), config) {
                 continue;
             }
-            push_finding(
-                findings,
-                seen,
-                FindingCandidate {
-                    severity: pattern.severity,
-                    kind: pattern.id,
-                    pattern: pattern.regex.as_str(),
-                    text,
-                    start: mat.start(),
-                    end: mat.end(),
-                    location: location.clone(),
-                    ctx,
-                },
-                config,
-            );
+            let span = SecretSpan {
+                severity: pattern.severity,
+                kind: pattern.id,
+                pattern: pattern.regex.as_str(),
+                start: mat.start(),
+                end: mat.end(),
+                heuristic: false,
+            };
+            if !visit(span) {
+                return;
+            }
         }
     }
 
     // Entropy-based detection
     for mat in ENTROPY_BASE64_RE.find_iter(text) {
-        if findings.len() >= config.max_findings {
-            *truncated = true;
-            return;
-        }
-        let candidate = &text[mat.start()..mat.end()];
-        if candidate.len() < config.entropy_min_len {
-            continue;
-        }
-        if is_allowlisted(candidate, config) {
+        let candidate = mat.as_str();
+        if candidate.len() < config.entropy_min_len || is_allowlisted(candidate, config) {
             continue;
         }
-        let entropy = shannon_entropy(candidate);
-        if entropy >= config.entropy_threshold {
-            push_finding(
-                findings,
-                seen,
-                FindingCandidate {
-                    severity: SecretSeverity::Medium,
-                    kind: "high_entropy_base64",
-                    pattern: "entropy",
-                    text,
-                    start: mat.start(),
-                    end: mat.end(),
-                    location: location.clone(),
-                    ctx,
-                },
-                config,
-            );
+        if shannon_entropy(candidate) >= config.entropy_threshold {
+            let span = SecretSpan {
+                severity: SecretSeverity::Medium,
+                kind: "high_entropy_base64",
+                pattern: "entropy",
+                start: mat.start(),
+                end: mat.end(),
+                heuristic: true,
+            };
+            if !visit(span) {
+                return;
+            }
         }
     }
 
     for mat in ENTROPY_HEX_RE.find_iter(text) {
-        if findings.len() >= config.max_findings {
-            *truncated = true;
-            return;
-        }
-        let candidate = &text[mat.start()..mat.end()];
-        if candidate.len() < 32 {
-            continue;
-        }
-        if is_allowlisted(candidate, config) {
+        let candidate = mat.as_str();
+        if candidate.len() < 32 || is_allowlisted(candidate, config) {
             continue;
         }
-        let entropy = shannon_entropy(candidate);
-        if entropy >= 3.0 {
-            push_finding(
-                findings,
-                seen,
-                FindingCandidate {
-                    severity: SecretSeverity::Low,
-                    kind: "high_entropy_hex",
-                    pattern: "entropy",
-                    text,
-                    start: mat.start(),
-                    end: mat.end(),
-                    location: location.clone(),
-                    ctx,
-                },
-                config,
-            );
+        if shannon_entropy(candidate) >= 3.0 {
+            let span = SecretSpan {
+                severity: SecretSeverity::Low,
+                kind: "high_entropy_hex",
+                pattern: "entropy",
+                start: mat.start(),
+                end: mat.end(),
+                heuristic: true,
+            };
+            if !visit(span) {
+                return;
+            }
         }
     }
 }
@@ -198,7 +198,7 @@ pub fn verify_bundle(path: &Path, verbose: bool) -> Result<VerifyResult> {
 }
 
 /// Resolve the site directory from a path
-fn resolve_site_dir(path: &Path) -> Result<PathBuf> {
+pub fn resolve_site_dir(path: &Path) -> Result<PathBuf> {
     if !path.exists() {
         bail!("Path does not exist: {}", path.display());
     }
@@ -0,0 +1,830 @@
+//! HNSW approximate nearest-neighbour graph over a CVVI vector index.
+//!
+//! The graph is an optional sidecar (`index-<embedder>.hnsw`) next to the `.cvvi`
+//! file it was built from. Nodes are CVVI row positions, so the graph stores only
+//! adjacency lists; vectors and row metadata are always read from the CVVI index.
+//!
+//! # File Layout (little-endian)
+//!
+//! ```text
+//! Magic "CVHN" | version u16 | m u16 | ef_construction u32 | count u32
+//! rows_crc u32 | entry_point u32 (u32::MAX = empty) | max_level u8
+//! count × { level u8, (level + 1) × { degree u16, degree × neighbour u32 } }
+//! CRC32 of everything above (u32)
+//! ```
+//!
+//! `rows_crc` fingerprints the (message_id, chunk_idx) sequence of the rows the
+//! graph was built over. A graph only applies to an index whose leading rows
+//! still have that fingerprint; rows appended after them (append segments) are
+//! scanned exactly and merged into the results.
+//!
+//! Over a binary CVVI index the walk compares sign codes by Hamming distance, and
+//! the final beam is reranked against the f16 vectors stored alongside the codes.
+//!
+//! # Filtering
+//!
+//! `SemanticFilter` constraints are applied during traversal: non-matching nodes
+//! are still expanded (so the graph stays connected) but never enter the result
+//! set, and the beam is widened by [`FILTER_OVERSAMPLE`]. Callers fall back to the
+//! exact scan when a restrictive filter leaves fewer than `k` results.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, anyhow, bail};
+
+use crate::search::vector_index::{
+    RowScorer, SemanticFilter, VectorIndex, VectorRow, VectorSearchResult,
+};
+
+pub const HNSW_MAGIC: [u8; 4] = *b"CVHN";
+pub const HNSW_VERSION: u16 = 1;
+
+/// Neighbours kept per node on upper layers (twice this on layer 0).
+pub const DEFAULT_M: usize = 16;
+/// Beam width while inserting nodes.
+pub const DEFAULT_EF_CONSTRUCTION: usize = 100;
+/// Default beam width at query time (raised to `k` when smaller).
+pub const DEFAULT_EF_SEARCH: usize = 64;
+/// Beam widening factor for filtered searches.
+pub const FILTER_OVERSAMPLE: usize = 4;
+/// Chunk-level candidates fetched per requested message when collapsing chunks.
+const COLLAPSE_OVERSAMPLE: usize = 3;
+/// Filtered traversals give up after visiting this many nodes per beam slot; a
+/// filter that selective is cheaper to serve with the exact scan.
+const FILTER_VISIT_BUDGET: usize = 64;
+/// Upper bound on node levels; 2^-16 of nodes would need a 17th layer.
+const MAX_LEVEL: usize = 16;
+
+/// Path of the HNSW sidecar for the CVVI file at `cvvi_path`.
+pub fn hnsw_index_path(cvvi_path: &Path) -> PathBuf {
+    cvvi_path.with_extension("hnsw")
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct HnswParams {
+    pub m: usize,
+    pub ef_construction: usize,
+}
+
+impl Default for HnswParams {
+    fn default() -> Self {
+        Self {
+            m: DEFAULT_M,
+            ef_construction: DEFAULT_EF_CONSTRUCTION,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct HnswIndex {
+    m: usize,
+    ef_construction: usize,
+    rows_crc: u32,
+    entry_point: Option<u32>,
+    max_level: usize,
+    /// `neighbors[node][level]`; a node has one list per level it lives on.
+    neighbors: Vec<Vec<Vec<u32>>>,
+}
+
+/// Similarity-ordered candidate (higher score sorts greater).
+#[derive(Debug, Clone, Copy)]
+struct Scored {
+    score: f32,
+    node: u32,
+}
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl HnswIndex {
+    /// Build a graph over every row of `index`.
+    pub fn build(index: &VectorIndex, params: HnswParams) -> Result<Self> {
+        let count = index.rows().len();
+        if u32::try_from(count).is_err() {
+            bail!("too many vectors for HNSW: {count}");
+        }
+        let m = params.m.clamp(2, u16::MAX as usize / 2);
+        let mut graph = Self {
+            m,
+            ef_construction: params.ef_construction.max(m),
+            rows_crc: rows_crc(index.rows()),
+            entry_point: None,
+            max_level: 0,
+            neighbors: Vec::with_capacity(count),
+        };
+        let level_mult = 1.0 / (m as f64).ln();
+        for node in 0..count {
+            let level = random_level(node as u64, level_mult);
+            graph.neighbors.push(vec![Vec::new(); level + 1]);
+            graph.insert(index, node as u32, level)?;
+        }
+        Ok(graph)
+    }
+
+    /// Number of CVVI rows covered by the graph.
+    pub fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.neighbors.is_empty()
+    }
+
+    /// Whether this graph was built over the leading rows of `index`.
+    pub fn matches(&self, index: &VectorIndex) -> bool {
+        index.rows().len() >= self.len() && rows_crc(&index.rows()[..self.len()]) == self.rows_crc
+    }
+
+    /// Approximate top-`k` rows by dot product, honouring `filter`.
+    ///
+    /// Rows of `index` beyond the graph (appended after it was built) are scanned
+    /// exactly and merged in.
+    pub fn search(
+        &self,
+        index: &VectorIndex,
+        query: &[f32],
+        k: usize,
+        ef: usize,
+        filter: Option<&SemanticFilter>,
+    ) -> Result<Vec<VectorSearchResult>> {
+        if query.len() != index.header().dimension as usize {
+            bail!(
+                "query dimension mismatch: expected {}, got {}",
+                index.header().dimension,
+                query.len()
+            );
+        }
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        let mut ef = ef.max(k);
+        if filter.is_some() {
+            ef = ef.saturating_mul(FILTER_OVERSAMPLE);
+        }
+
+        let scorer = index.row_scorer(query);
+        let mut found = match self.entry_point {
+            Some(entry) => {
+                let mut ep = Scored {
+                    score: scorer.score(entry as usize)?,
+                    node: entry,
+                };
+                for level in (1..=self.max_level).rev() {
+                    ep = self.greedy_closest(&scorer, ep, level)?;
+                }
+                self.search_layer(index, &scorer, &[ep], ef, 0, filter)?
+            }
+            None => Vec::new(),
+        };
+
+        let rows = index.rows();
+        for (row_idx, row) in rows.iter().enumerate().skip(self.len()) {
+            if filter.is_some_and(|f| !f.matches(row)) {
+                continue;
+            }
+            found.push(Scored {
+                score: scorer.score(row_idx)?,
+                node: row_idx as u32,
+            });
+        }
+
+        // Binary indexes walk by Hamming distance; rerank the beam against the query
+        if scorer.needs_rerank() {
+            for candidate in &mut found {
+                candidate.score = index.score_row(candidate.node as usize, query)?;
+            }
+        }
+        found.sort_by(|a, b| b.cmp(a));
+        found.truncate(k);
+        Ok(found
+            .into_iter()
+            .map(|s| {
+                let row = &rows[s.node as usize];
+                VectorSearchResult {
+                    message_id: row.message_id,
+                    chunk_idx: row.chunk_idx,
+                    score: s.score,
+                }
+            })
+            .collect())
+    }
+
+    /// Approximate counterpart of `VectorIndex::search_top_k_collapsed`: the best
+    /// chunk per message, top-`k` messages.
+    pub fn search_collapsed(
+        &self,
+        index: &VectorIndex,
+        query: &[f32],
+        k: usize,
+        ef: usize,
+        filter: Option<&SemanticFilter>,
+    ) -> Result<Vec<VectorSearchResult>> {
+        let fetch = k.saturating_mul(COLLAPSE_OVERSAMPLE);
+        let hits = self.search(index, query, fetch, ef.max(fetch), filter)?;
+        let mut best: HashMap<u64, VectorSearchResult> = HashMap::new();
+        for hit in hits {
+            match best.get(&hit.message_id) {
+                Some(existing) if existing.score >= hit.score => {}
+                _ => {
+                    best.insert(hit.message_id, hit);
+                }
+            }
+        }
+        let mut results: Vec<VectorSearchResult> = best.into_values().collect();
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.message_id.cmp(&b.message_id))
+        });
+        results.truncate(k);
+        Ok(results)
+    }
+
+    fn insert(&mut self, index: &VectorIndex, node: u32, level: usize) -> Result<()> {
+        let Some(entry) = self.entry_point else {
+            self.entry_point = Some(node);
+            self.max_level = level;
+            return Ok(());
+        };
+        let vector = index.vector_at_f32(&index.rows()[node as usize])?;
+        let scorer = index.row_scorer(&vector);
+
+        let mut ep = Scored {
+            score: scorer.score(entry as usize)?,
+            node: entry,
+        };
+        for l in (level + 1..=self.max_level).rev() {
+            ep = self.greedy_closest(&scorer, ep, l)?;
+        }
+
+        let mut entry_points = vec![ep];
+        for l in (0..=level.min(self.max_level)).rev() {
+            let candidates =
+                self.search_layer(index, &scorer, &entry_points, self.ef_construction, l, None)?;
+            let selected = select_neighbors(index, &candidates, self.m)?;
+            self.neighbors[node as usize][l] = selected.iter().map(|s| s.node).collect();
+
+            let max_degree = self.max_degree(l);
+            for neighbour in &selected {
+                let list = &mut self.neighbors[neighbour.node as usize][l];
+                list.push(node);
+                if list.len() > max_degree {
+                    self.prune(index, neighbour.node, l, max_degree)?;
+                }
+            }
+            entry_points = candidates;
+        }
+
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry_point = Some(node);
+        }
+        Ok(())
+    }
+
+    fn max_degree(&self, level: usize) -> usize {
+        if level == 0 { self.m * 2 } else { self.m }
+    }
+
+    /// Shrink `node`'s neighbour list on `level` back to `max_degree`.
+    fn prune(
+        &mut self,
+        index: &VectorIndex,
+        node: u32,
+        level: usize,
+        max_degree: usize,
+    ) -> Result<()> {
+        let vector = index.vector_at_f32(&index.rows()[node as usize])?;
+        let mut candidates = Vec::with_capacity(self.neighbors[node as usize][level].len());
+        for &other in &self.neighbors[node as usize][level] {
+            candidates.push(Scored {
+                score: index.score_row(other as usize, &vector)?,
+                node: other,
+            });
+        }
+        candidates.sort_by(|a, b| b.cmp(a));
+        let selected = select_neighbors(index, &candidates, max_degree)?;
+        self.neighbors[node as usize][level] = selected.iter().map(|s| s.node).collect();
+        Ok(())
+    }
+
+    fn greedy_closest(
+        &self,
+        scorer: &RowScorer<'_>,
+        mut current: Scored,
+        level: usize,
+    ) -> Result<Scored> {
+        loop {
+            let mut improved = false;
+            for &other in self.layer(current.node, level) {
+                let score = scorer.score(other as usize)?;
+                if score > current.score {
+                    current = Scored { score, node: other };
+                    improved = true;
+                }
+            }
+            if !improved {
+                return Ok(current);
+            }
+        }
+    }
+
+    /// Beam search on one layer; returns up to `ef` matching nodes, best first.
+    fn search_layer(
+        &self,
+        index: &VectorIndex,
+        scorer: &RowScorer<'_>,
+        entry_points: &[Scored],
+        ef: usize,
+        level: usize,
+        filter: Option<&SemanticFilter>,
+    ) -> Result<Vec<Scored>> {
+        let rows = index.rows();
+        let accepts = |node: u32| filter.is_none_or(|f| f.matches(&rows[node as usize]));
+
+        let mut visited: HashSet<u32> = entry_points.iter().map(|s| s.node).collect();
+        let mut candidates: BinaryHeap<Scored> = entry_points.iter().copied().collect();
+        // Min-heap of the best `ef` accepted nodes so far.
+        let mut results: BinaryHeap<std::cmp::Reverse<Scored>> = entry_points
+            .iter()
+            .filter(|s| accepts(s.node))
+            .map(|s| std::cmp::Reverse(*s))
+            .collect();
+        while results.len() > ef {
+            results.pop();
+        }
+        let visit_budget = if filter.is_some() {
+            ef.saturating_mul(FILTER_VISIT_BUDGET)
+        } else {
+            usize::MAX
+        };
+
+        while let Some(current) = candidates.pop() {
+            if visited.len() > visit_budget {
+                break;
+            }
+            if results.len() >= ef
+                && let Some(worst) = results.peek()
+                && current.score < worst.0.score
+            {
+                break;
+            }
+            for &other in self.layer(current.node, level) {
+                if !visited.insert(other) {
+                    continue;
+                }
+                let scored = Scored {
+                    score: scorer.score(other as usize)?,
+                    node: other,
+                };
+                let worst = results.peek().map(|w| w.0.score);
+                if results.len() < ef || worst.is_some_and(|w| scored.score > w) {
+                    candidates.push(scored);
+                    if accepts(other) {
+                        results.push(std::cmp::Reverse(scored));
+                        if results.len() > ef {
+                            results.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut out: Vec<Scored> = results.into_iter().map(|r| r.0).collect();
+        out.sort_by(|a, b| b.cmp(a));
+        Ok(out)
+    }
+
+    fn layer(&self, node: u32, level: usize) -> &[u32] {
+        self.neighbors[node as usize]
+            .get(level)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&HNSW_MAGIC);
+        buf.extend_from_slice(&HNSW_VERSION.to_le_bytes());
+        buf.extend_from_slice(&(self.m as u16).to_le_bytes());
+        buf.extend_from_slice(&(self.ef_construction as u32).to_le_bytes());
+        buf.extend_from_slice(&(self.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&self.rows_crc.to_le_bytes());
+        buf.extend_from_slice(&self.entry_point.unwrap_or(u32::MAX).to_le_bytes());
+        buf.push(self.max_level as u8);
+        for levels in &self.neighbors {
+            buf.push((levels.len() - 1) as u8);
+            for list in levels {
+                buf.extend_from_slice(&(list.len() as u16).to_le_bytes());
+                for id in list {
+                    buf.extend_from_slice(&id.to_le_bytes());
+                }
+            }
+        }
+        let crc = crc32fast::hash(&buf);
+        buf.extend_from_slice(&crc.to_le_bytes());
+
+        let temp_path = path.with_extension("hnsw.tmp");
+        let mut file = File::create(&temp_path)
+            .with_context(|| format!("create temp HNSW file {temp_path:?}"))?;
+        file.write_all(&buf)?;
+        file.sync_all().context("fsync HNSW temp file")?;
+        std::fs::rename(&temp_path, path)
+            .with_context(|| format!("rename HNSW temp file {temp_path:?}"))?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path).with_context(|| format!("read HNSW file {path:?}"))?;
+        if bytes.len() < 4 {
+            bail!("HNSW file too short");
+        }
+        let (body, crc_bytes) = bytes.split_at(bytes.len() - 4);
+        let stored_crc = u32::from_le_bytes(crc_bytes.try_into()?);
+        if crc32fast::hash(body) != stored_crc {
+            bail!("HNSW checksum mismatch");
+        }
+
+        let mut reader = ByteReader {
+            bytes: body,
+            pos: 0,
+        };
+        if reader.take(4)? != HNSW_MAGIC {
+            bail!("not an HNSW file");
+        }
+        let version = reader.u16()?;
+        if version != HNSW_VERSION {
+            bail!("unsupported HNSW version {version}");
+        }
+        let m = reader.u16()? as usize;
+        let ef_construction = reader.u32()? as usize;
+        let count = reader.u32()? as usize;
+        let rows_crc = reader.u32()?;
+        let entry_point = match reader.u32()? {
+            u32::MAX => None,
+            id => Some(id),
+        };
+        let max_level = reader.u8()? as usize;
+
+        let mut neighbors = Vec::with_capacity(count);
+        for _ in 0..count {
+            let level = reader.u8()? as usize;
+            let mut levels = Vec::with_capacity(level + 1);
+            for _ in 0..=level {
+                let degree = reader.u16()? as usize;
+                let mut list = Vec::with_capacity(degree);
+                for _ in 0..degree {
+                    let id = reader.u32()?;
+                    if id as usize >= count {
+                        bail!("HNSW neighbour {id} out of range");
+                    }
+                    list.push(id);
+                }
+                levels.push(list);
+            }
+            neighbors.push(levels);
+        }
+        if reader.pos != body.len() {
+            bail!("trailing bytes in HNSW file");
+        }
+        if entry_point.is_some_and(|e| e as usize >= count) {
+            bail!("HNSW entry point out of range");
+        }
+
+        Ok(Self {
+            m,
+            ef_construction,
+            rows_crc,
+            entry_point,
+            max_level,
+            neighbors,
+        })
+    }
+}
+
+/// Build the HNSW sidecar for the CVVI file at `cvvi_path`.
+pub fn build_hnsw_for(cvvi_path: &Path) -> Result<PathBuf> {
+    let index = VectorIndex::load_mapped(cvvi_path)?;
+    let graph = HnswIndex::build(&index, HnswParams::default())?;
+    let path = hnsw_index_path(cvvi_path);
+    graph.save(&path)?;
+    Ok(path)
+}
+
+/// Load the HNSW sidecar for `cvvi_path` if it exists and fits `index`.
+pub fn load_hnsw_for(cvvi_path: &Path, index: &VectorIndex) -> Option<HnswIndex> {
+    let path = hnsw_index_path(cvvi_path);
+    if !path.is_file() {
+        return None;
+    }
+    match HnswIndex::load(&path) {
+        Ok(graph) if graph.matches(index) => Some(graph),
+        Ok(_) => {
+            tracing::info!(path = %path.display(), "HNSW graph is stale; using exact search");
+            None
+        }
+        Err(err) => {
+            tracing::warn!(path = %path.display(), error = %err, "failed to load HNSW graph");
+            None
+        }
+    }
+}
+
+/// Diversity heuristic from the HNSW paper: keep a candidate only if it is closer
+/// to the base node than to any neighbour already kept, then top up with the best
+/// of the rest. `candidates` must be sorted best first.
+fn select_neighbors(index: &VectorIndex, candidates: &[Scored], m: usize) -> Result<Vec<Scored>> {
+    if candidates.len() <= m {
+        return Ok(candidates.to_vec());
+    }
+    let mut selected: Vec<Scored> = Vec::with_capacity(m);
+    let mut skipped = Vec::new();
+    for candidate in candidates {
+        if selected.len() >= m {
+            break;
+        }
+        let vector = index.vector_at_f32(&index.rows()[candidate.node as usize])?;
+        let mut diverse = true;
+        for kept in &selected {
+            if index.score_row(kept.node as usize, &vector)? > candidate.score {
+                diverse = false;
+                break;
+            }
+        }
+        if diverse {
+            selected.push(*candidate);
+        } else {
+            skipped.push(*candidate);
+        }
+    }
+    for candidate in skipped {
+        if selected.len() >= m {
+            break;
+        }
+        selected.push(candidate);
+    }
+    Ok(selected)
+}
+
+/// Deterministic level draw so rebuilding the same index yields the same graph.
+fn random_level(node: u64, level_mult: f64) -> usize {
+    // splitmix64
+    let mut z = node.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    let unit = ((z >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+    ((-unit.ln() * level_mult) as usize).min(MAX_LEVEL)
+}
+
+fn rows_crc(rows: &[VectorRow]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    for row in rows {
+        hasher.update(&row.message_id.to_le_bytes());
+        hasher.update(&[row.chunk_idx]);
+    }
+    hasher.finalize()
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| anyhow!("HNSW file truncated"))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into()?))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::vector_index::{Quantization, VectorEntry};
+    use tempfile::tempdir;
+
+    struct TinyRng(u64);
+
+    impl TinyRng {
+        fn next_f32(&mut self) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+        }
+
+        fn unit_vector(&mut self, dim: usize) -> Vec<f32> {
+            let v: Vec<f32> = (0..dim).map(|_| self.next_f32()).collect();
+            let norm = v
+                .iter()
+                .map(|x| x * x)
+                .sum::<f32>()
+                .sqrt()
+                .max(f32::EPSILON);
+            v.into_iter().map(|x| x / norm).collect()
+        }
+    }
+
+    fn random_index(count: usize, dim: usize, seed: u64) -> VectorIndex {
+        let mut rng = TinyRng(seed);
+        let entries = (0..count).map(|i| VectorEntry {
+            message_id: i as u64 + 1,
+            created_at_ms: i as i64,
+            agent_id: (i % 4) as u32,
+            workspace_id: 0,
+            source_id: 0,
+            role: 0,
+            chunk_idx: 0,
+            content_hash: [0; 32],
+            vector: rng.unit_vector(dim),
+        });
+        VectorIndex::build("test", "rev", dim, Quantization::F32, entries).unwrap()
+    }
+
+    /// Recall harness: mean fraction of the exact top-`k` found by the graph.
+    fn recall_at_k(
+        index: &VectorIndex,
+        graph: &HnswIndex,
+        queries: &[Vec<f32>],
+        k: usize,
+        filter: Option<&SemanticFilter>,
+    ) -> f64 {
+        let mut total = 0.0;
+        for query in queries {
+            let exact: HashSet<u64> = index
+                .search_top_k(query, k, filter)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.message_id)
+                .collect();
+            let approx = graph
+                .search(index, query, k, DEFAULT_EF_SEARCH, filter)
+                .unwrap();
+            let found = approx
+                .iter()
+                .filter(|r| exact.contains(&r.message_id))
+                .count();
+            total += found as f64 / exact.len().max(1) as f64;
+        }
+        total / queries.len() as f64
+    }
+
+    #[test]
+    fn recall_matches_brute_force() {
+        let index = random_index(3000, 32, 7);
+        let graph = HnswIndex::build(&index, HnswParams::default()).unwrap();
+        let mut rng = TinyRng(99);
+        let queries: Vec<Vec<f32>> = (0..40).map(|_| rng.unit_vector(32)).collect();
+
+        let recall = recall_at_k(&index, &graph, &queries, 10, None);
+        assert!(recall >= 0.9, "recall@10 too low: {recall}");
+    }
+
+    #[test]
+    fn filtered_recall_matches_brute_force() {
+        let index = random_index(3000, 32, 11);
+        let graph = HnswIndex::build(&index, HnswParams::default()).unwrap();
+        let mut rng = TinyRng(5);
+        let queries: Vec<Vec<f32>> = (0..40).map(|_| rng.unit_vector(32)).collect();
+        let filter = SemanticFilter {
+            agents: Some(HashSet::from([1])),
+            ..SemanticFilter::default()
+        };
+
+        let recall = recall_at_k(&index, &graph, &queries, 10, Some(&filter));
+        assert!(recall >= 0.85, "filtered recall@10 too low: {recall}");
+        let hits = graph
+            .search(&index, &queries[0], 10, DEFAULT_EF_SEARCH, Some(&filter))
+            .unwrap();
+        assert!(hits.iter().all(|h| (h.message_id - 1) % 4 == 1));
+    }
+
+    #[test]
+    fn save_load_roundtrip_and_staleness() {
+        let index = random_index(200, 8, 3);
+        let graph = HnswIndex::build(&index, HnswParams::default()).unwrap();
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("index-test.hnsw");
+        graph.save(&path).unwrap();
+
+        let loaded = HnswIndex::load(&path).unwrap();
+        assert_eq!(loaded.len(), 200);
+        assert!(loaded.matches(&index));
+        let query = TinyRng(1).unit_vector(8);
+        let a = graph.search(&index, &query, 5, 32, None).unwrap();
+        let b = loaded.search(&index, &query, 5, 32, None).unwrap();
+        assert_eq!(
+            a.iter().map(|r| r.message_id).collect::<Vec<_>>(),
+            b.iter().map(|r| r.message_id).collect::<Vec<_>>()
+        );
+
+        let other = random_index(200, 8, 4);
+        let shifted = VectorIndex::build(
+            "test",
+            "rev",
+            8,
+            Quantization::F32,
+            other.rows().iter().map(|row| VectorEntry {
+                message_id: row.message_id + 1000,
+                created_at_ms: 0,
+                agent_id: 0,
+                workspace_id: 0,
+                source_id: 0,
+                role: 0,
+                chunk_idx: 0,
+                content_hash: [0; 32],
+                vector: other.vector_at_f32(row).unwrap(),
+            }),
+        )
+        .unwrap();
+        assert!(!loaded.matches(&shifted));
+
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes[20] ^= 0xFF;
+        std::fs::write(&path, bytes).unwrap();
+        assert!(HnswIndex::load(&path).is_err());
+    }
+
+    #[test]
+    fn rows_beyond_graph_are_scanned_exactly() {
+        let base = random_index(300, 16, 21);
+        let graph = HnswIndex::build(&base, HnswParams::default()).unwrap();
+        let query = TinyRng(8).unit_vector(16);
+
+        // Append a row that matches the query perfectly, as a merged segment would.
+        let mut entries: Vec<VectorEntry> = base
+            .rows()
+            .iter()
+            .map(|row| VectorEntry {
+                message_id: row.message_id,
+                created_at_ms: row.created_at_ms,
+                agent_id: row.agent_id,
+                workspace_id: row.workspace_id,
+                source_id: row.source_id,
+                role: row.role,
+                chunk_idx: row.chunk_idx,
+                content_hash: row.content_hash,
+                vector: base.vector_at_f32(row).unwrap(),
+            })
+            .collect();
+        entries.push(VectorEntry {
+            message_id: 9999,
+            created_at_ms: 0,
+            agent_id: 0,
+            workspace_id: 0,
+            source_id: 0,
+            role: 0,
+            chunk_idx: 0,
+            content_hash: [0; 32],
+            vector: query.clone(),
+        });
+        let extended = VectorIndex::build("test", "rev", 16, Quantization::F32, entries).unwrap();
+        assert!(graph.matches(&extended));
+
+        let hits = graph
+            .search_collapsed(&extended, &query, 3, DEFAULT_EF_SEARCH, None)
+            .unwrap();
+        assert_eq!(hits[0].message_id, 9999);
+    }
+}
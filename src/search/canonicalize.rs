@@ -14,6 +14,10 @@
 //! 5. **Low-signal filtering** - Remove "OK", "Done.", etc.
 //! 6. **Truncation** - Limit to MAX_EMBED_CHARS (2000)
 //!
+//! Long messages are indexed with [`chunk_for_embedding`] instead, which skips
+//! the truncation step and splits the canonical text into overlapping windows of
+//! at most MAX_EMBED_CHARS, each embedded as its own chunk.
+//!
 //! # Why This Matters
 //!
 //! Without proper canonicalization:
@@ -39,6 +43,12 @@ use unicode_normalization::UnicodeNormalization;
 /// Maximum characters to keep after canonicalization.
 pub const MAX_EMBED_CHARS: usize = 2000;
 
+/// Characters shared between consecutive chunks of a long message.
+pub const CHUNK_OVERLAP_CHARS: usize = 200;
+
+/// Maximum chunks embedded per message; text beyond the last chunk is dropped.
+pub const MAX_EMBED_CHUNKS: usize = 16;
+
 /// Maximum lines to keep from the beginning of a code block.
 pub const CODE_HEAD_LINES: usize = 20;
 
@@ -84,10 +94,59 @@ static STREAMING_CANONICALIZE_ENABLED: std::sync::LazyLock<bool> = std::sync::La
 ///
 /// Canonicalized text, suitable for embedding and hashing.
 pub fn canonicalize_for_embedding(text: &str) -> String {
+    canonicalize_with_limit(text, MAX_EMBED_CHARS)
+}
+
+/// Canonicalize text and split it into overlapping chunks for embedding.
+///
+/// Text that fits in MAX_EMBED_CHARS yields a single chunk identical to
+/// [`canonicalize_for_embedding`], so short messages hash the same either way.
+/// Longer text is cut into windows of at most MAX_EMBED_CHARS that overlap by
+/// CHUNK_OVERLAP_CHARS, breaking at whitespace where possible, up to
+/// MAX_EMBED_CHUNKS chunks. Low-signal or empty text yields no chunks.
+pub fn chunk_for_embedding(text: &str) -> Vec<String> {
+    let canonical = canonicalize_with_limit(text, usize::MAX);
+    if canonical.is_empty() {
+        return Vec::new();
+    }
+    let chars: Vec<char> = canonical.chars().collect();
+    if chars.len() <= MAX_EMBED_CHARS {
+        return vec![canonical];
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    loop {
+        let mut end = (start + MAX_EMBED_CHARS).min(chars.len());
+        if end < chars.len() {
+            // Prefer ending on a word boundary in the back half of the window.
+            let floor = start + MAX_EMBED_CHARS / 2;
+            if let Some(ws) = (floor..end).rev().find(|&i| chars[i].is_whitespace()) {
+                end = ws;
+            }
+        }
+        let chunk: String = chars[start..end].iter().collect();
+        let chunk = chunk.trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk.to_string());
+        }
+        if end >= chars.len() || chunks.len() >= MAX_EMBED_CHUNKS {
+            break;
+        }
+        // Step back for overlap, then forward to the next word start.
+        let overlap_start = end.saturating_sub(CHUNK_OVERLAP_CHARS).max(start + 1);
+        start = (overlap_start..end)
+            .find(|&i| chars[i].is_whitespace())
+            .map_or(overlap_start, |ws| ws + 1);
+    }
+    chunks
+}
+
+fn canonicalize_with_limit(text: &str, max_chars: usize) -> String {
     if *STREAMING_CANONICALIZE_ENABLED {
-        canonicalize_for_embedding_streaming(text)
+        canonicalize_streaming_with_limit(text, max_chars)
     } else {
-        canonicalize_for_embedding_legacy(text)
+        canonicalize_legacy_with_limit(text, max_chars)
     }
 }
 
@@ -95,6 +154,10 @@ pub fn canonicalize_for_embedding(text: &str) -> String {
 ///
 /// Exposed for benchmarks and regression comparisons.
 pub fn canonicalize_for_embedding_legacy(text: &str) -> String {
+    canonicalize_legacy_with_limit(text, MAX_EMBED_CHARS)
+}
+
+fn canonicalize_legacy_with_limit(text: &str, max_chars: usize) -> String {
     // Step 1: Unicode NFC normalization (CRITICAL for hash stability)
     let normalized: String = text.nfc().collect();
 
@@ -108,13 +171,17 @@ pub fn canonicalize_for_embedding_legacy(text: &str) -> String {
     let filtered = filter_low_signal(&whitespace_normalized);
 
     // Step 5: Truncate to max length
-    truncate_to_chars(&filtered, MAX_EMBED_CHARS)
+    truncate_to_chars(&filtered, max_chars)
 }
 
 /// Streaming canonicalization pipeline with reduced allocations.
 ///
 /// Exposed for benchmarks and regression comparisons.
 pub fn canonicalize_for_embedding_streaming(text: &str) -> String {
+    canonicalize_streaming_with_limit(text, MAX_EMBED_CHARS)
+}
+
+fn canonicalize_streaming_with_limit(text: &str, max_chars: usize) -> String {
     // Step 1: Unicode NFC normalization (CRITICAL for hash stability)
     let normalized: String = text.nfc().collect();
 
@@ -164,10 +231,10 @@ pub fn canonicalize_for_embedding_streaming(text: &str) -> String {
     }
 
     // Step 5: Truncate to max length
-    if output.chars().count() <= MAX_EMBED_CHARS {
+    if output.chars().count() <= max_chars {
         output
     } else {
-        output = output.chars().take(MAX_EMBED_CHARS).collect();
+        output = output.chars().take(max_chars).collect();
         output
     }
 }
@@ -631,6 +698,41 @@ mod tests {
         assert_eq!(canonical.len(), MAX_EMBED_CHARS);
     }
 
+    #[test]
+    fn test_chunking_short_text_matches_canonical() {
+        let text = "**Hello** world, a short message.";
+        assert_eq!(
+            chunk_for_embedding(text),
+            vec![canonicalize_for_embedding(text)]
+        );
+        assert!(chunk_for_embedding("OK").is_empty());
+    }
+
+    #[test]
+    fn test_chunking_long_text_overlaps() {
+        let words: Vec<String> = (0..1500).map(|i| format!("word{i}")).collect();
+        let text = words.join(" ");
+        let chunks = chunk_for_embedding(&text);
+
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.chars().count() <= MAX_EMBED_CHARS));
+        assert!(chunks[0].starts_with("word0 "));
+        assert!(chunks.last().unwrap().ends_with("word1499"));
+        // Consecutive chunks share whole words at the boundary.
+        let last_word = chunks[0].rsplit(' ').next().unwrap();
+        assert!(chunks[1].split(' ').any(|w| w == last_word));
+    }
+
+    #[test]
+    fn test_chunking_without_whitespace_and_cap() {
+        let chunks = chunk_for_embedding(&"a".repeat(5000));
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].len(), MAX_EMBED_CHARS);
+
+        let huge = chunk_for_embedding(&"a".repeat(MAX_EMBED_CHARS * (MAX_EMBED_CHUNKS + 4)));
+        assert_eq!(huge.len(), MAX_EMBED_CHUNKS);
+    }
+
     #[test]
     fn test_empty_input() {
         let canonical = canonicalize_for_embedding("");
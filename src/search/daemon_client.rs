@@ -5,9 +5,9 @@
 //! - Fallback wrappers for `Embedder` and `Reranker` with retry + jittered backoff.
 //! - Structured logging for daemon usage and fallback decisions.
 //!
-//! The concrete daemon transport is intentionally unspecified here until the
-//! xf daemon protocol/spec lands. This keeps the integration safe and testable
-//! without locking in a protocol prematurely.
+//! The concrete transport lives in [`crate::search::daemon_socket`] (Unix
+//! domain socket, used by `cass daemon`); this module stays transport-agnostic
+//! so the retry/fallback logic can be tested in isolation.
 
 use std::fmt;
 use std::sync::Arc;
@@ -107,7 +107,7 @@ impl fmt::Display for DaemonError {
 
 impl std::error::Error for DaemonError {}
 
-/// Abstract daemon client. The concrete transport is defined once the protocol is known.
+/// Abstract daemon client. See `daemon_socket::UnixSocketDaemonClient` for the real transport.
 pub trait DaemonClient: Send + Sync {
     fn id(&self) -> &str;
     fn is_available(&self) -> bool;
@@ -122,7 +122,7 @@ pub trait DaemonClient: Send + Sync {
     ) -> Result<Vec<f32>, DaemonError>;
 }
 
-/// No-op daemon client used when daemon config is missing.
+/// No-op daemon client used where no daemon transport is available (non-Unix targets).
 pub struct NoopDaemonClient {
     id: String,
 }
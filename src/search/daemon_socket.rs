@@ -0,0 +1,686 @@
+//! Unix domain socket transport for the semantic daemon (`cass daemon`).
+//!
+//! The daemon loads the embedder and reranker once and answers `embed`,
+//! `embed_batch` and `rerank` requests. [`UnixSocketDaemonClient`] is the
+//! matching [`DaemonClient`], so it plugs straight into
+//! `DaemonFallbackEmbedder` / `DaemonFallbackReranker`.
+//!
+//! Wire format: each message is a frame made of a 4-byte big-endian length
+//! followed by that many bytes of JSON ([`Request`] / [`Response`]). A
+//! connection may carry any number of request/response pairs.
+//!
+//! Overload and failures are reported in-band and mapped onto [`DaemonError`]:
+//! `overloaded` → `Overloaded` (with a retry hint), `error` → `InvalidInput`,
+//! `Unavailable` or `Failed`; connect failures → `Unavailable`; read/write
+//! timeouts → `Timeout`.
+
+use std::io::{self, Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::search::daemon_client::{DaemonClient, DaemonError};
+use crate::search::embedder::{Embedder, EmbedderError};
+use crate::search::reranker::{Reranker, RerankerError};
+
+/// Protocol version reported by `health`; bumped on incompatible changes.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Default number of requests served concurrently before replying `overloaded`.
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 8;
+
+/// Upper bound on a single frame, so a garbage length prefix cannot allocate gigabytes.
+const MAX_FRAME_BYTES: usize = 64 * 1024 * 1024;
+
+/// Retry hint sent with `overloaded` responses.
+const OVERLOAD_RETRY_AFTER: Duration = Duration::from_millis(50);
+
+/// Default client read/write timeout.
+const DEFAULT_CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often the accept loop checks for a shutdown request.
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// Socket path used by both `cass daemon` and `--daemon` searches:
+/// `CASS_DAEMON_SOCKET` if set, otherwise `<data_dir>/daemon.sock`.
+pub fn default_socket_path(data_dir: &Path) -> PathBuf {
+    dotenvy::var("CASS_DAEMON_SOCKET")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| data_dir.join("daemon.sock"))
+}
+
+// -------------------------------------------------------------------------
+// Protocol
+// -------------------------------------------------------------------------
+
+/// Client → daemon message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Request {
+    Health,
+    Embed {
+        request_id: String,
+        /// Embedder id the client expects; mismatches are rejected so vectors
+        /// never mix models.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        embedder: Option<String>,
+        text: String,
+    },
+    EmbedBatch {
+        request_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        embedder: Option<String>,
+        texts: Vec<String>,
+    },
+    Rerank {
+        request_id: String,
+        query: String,
+        documents: Vec<String>,
+    },
+    Shutdown,
+}
+
+/// Error classes carried in an `error` response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteErrorKind {
+    InvalidInput,
+    Unavailable,
+    Failed,
+}
+
+/// Daemon health snapshot, returned for `health` requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthInfo {
+    pub protocol_version: u32,
+    pub pid: u32,
+    pub embedder: Option<String>,
+    pub dimension: Option<usize>,
+    pub reranker: Option<String>,
+    pub in_flight: usize,
+    pub max_in_flight: usize,
+    pub requests_served: u64,
+    pub uptime_ms: u64,
+}
+
+/// Daemon → client message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Response {
+    Health(HealthInfo),
+    Vector {
+        vector: Vec<f32>,
+    },
+    Vectors {
+        vectors: Vec<Vec<f32>>,
+    },
+    Scores {
+        scores: Vec<f32>,
+    },
+    Overloaded {
+        retry_after_ms: Option<u64>,
+        message: String,
+    },
+    Error {
+        kind: RemoteErrorKind,
+        message: String,
+    },
+    ShuttingDown,
+}
+
+impl Response {
+    fn error(kind: RemoteErrorKind, message: impl Into<String>) -> Self {
+        Response::Error {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+/// Write one length-prefixed JSON frame.
+pub fn write_frame<T: Serialize>(stream: &mut impl Write, message: &T) -> io::Result<()> {
+    let body = serde_json::to_vec(message).map_err(io::Error::other)?;
+    if body.len() > MAX_FRAME_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {} bytes exceeds limit", body.len()),
+        ));
+    }
+    stream.write_all(&(body.len() as u32).to_be_bytes())?;
+    stream.write_all(&body)?;
+    stream.flush()
+}
+
+/// Read one length-prefixed JSON frame. Returns `Ok(None)` on a clean EOF
+/// between frames.
+pub fn read_frame<T: DeserializeOwned>(stream: &mut impl Read) -> io::Result<Option<T>> {
+    let mut len_buf = [0u8; 4];
+    match stream.read_exact(&mut len_buf) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+    let len = u32::from_be_bytes(len_buf) as usize;
+    if len > MAX_FRAME_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame of {len} bytes exceeds limit"),
+        ));
+    }
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// -------------------------------------------------------------------------
+// Server
+// -------------------------------------------------------------------------
+
+/// Warm model host behind `cass daemon`.
+pub struct DaemonServer {
+    embedder: Option<Arc<dyn Embedder>>,
+    reranker: Option<Arc<dyn Reranker>>,
+    max_in_flight: usize,
+    in_flight: AtomicUsize,
+    served: AtomicU64,
+    started: Instant,
+    shutdown: AtomicBool,
+}
+
+/// Releases an in-flight slot when dropped.
+struct InFlightSlot<'a>(&'a AtomicUsize);
+
+impl Drop for InFlightSlot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl DaemonServer {
+    pub fn new(
+        embedder: Option<Arc<dyn Embedder>>,
+        reranker: Option<Arc<dyn Reranker>>,
+        max_in_flight: usize,
+    ) -> Self {
+        Self {
+            embedder,
+            reranker,
+            max_in_flight,
+            in_flight: AtomicUsize::new(0),
+            served: AtomicU64::new(0),
+            started: Instant::now(),
+            shutdown: AtomicBool::new(false),
+        }
+    }
+
+    pub fn health(&self) -> HealthInfo {
+        HealthInfo {
+            protocol_version: PROTOCOL_VERSION,
+            pid: std::process::id(),
+            embedder: self.embedder.as_ref().map(|e| e.id().to_string()),
+            dimension: self.embedder.as_ref().map(|e| e.dimension()),
+            reranker: self.reranker.as_ref().map(|r| r.id().to_string()),
+            in_flight: self.in_flight.load(Ordering::Acquire),
+            max_in_flight: self.max_in_flight,
+            requests_served: self.served.load(Ordering::Relaxed),
+            uptime_ms: self.started.elapsed().as_millis() as u64,
+        }
+    }
+
+    /// Ask the accept loop to stop after the current poll interval.
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::Release);
+    }
+
+    fn try_acquire_slot(&self) -> Option<InFlightSlot<'_>> {
+        self.in_flight
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < self.max_in_flight).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| InFlightSlot(&self.in_flight))
+    }
+
+    fn embedder_for(&self, requested: Option<&str>) -> Result<&Arc<dyn Embedder>, Response> {
+        let Some(embedder) = &self.embedder else {
+            return Err(Response::error(
+                RemoteErrorKind::Unavailable,
+                "no embedder loaded",
+            ));
+        };
+        match requested {
+            Some(id) if id != embedder.id() => Err(Response::error(
+                RemoteErrorKind::Unavailable,
+                format!("daemon serves embedder {}, not {id}", embedder.id()),
+            )),
+            _ => Ok(embedder),
+        }
+    }
+
+    /// Answer a single request. Model calls are bounded by `max_in_flight`.
+    pub fn handle(&self, request: Request) -> Response {
+        match request {
+            Request::Health => return Response::Health(self.health()),
+            Request::Shutdown => {
+                self.shutdown();
+                return Response::ShuttingDown;
+            }
+            _ => {}
+        }
+
+        let Some(_slot) = self.try_acquire_slot() else {
+            return Response::Overloaded {
+                retry_after_ms: Some(OVERLOAD_RETRY_AFTER.as_millis() as u64),
+                message: format!("{} requests in flight", self.max_in_flight),
+            };
+        };
+        self.served.fetch_add(1, Ordering::Relaxed);
+
+        match request {
+            Request::Embed {
+                request_id,
+                embedder,
+                text,
+            } => {
+                debug!(request_id = %request_id, "daemon embed");
+                match self.embedder_for(embedder.as_deref()) {
+                    Ok(e) => match e.embed(&text) {
+                        Ok(vector) => Response::Vector { vector },
+                        Err(err) => embedder_error_response(err),
+                    },
+                    Err(resp) => resp,
+                }
+            }
+            Request::EmbedBatch {
+                request_id,
+                embedder,
+                texts,
+            } => {
+                debug!(request_id = %request_id, count = texts.len(), "daemon embed batch");
+                match self.embedder_for(embedder.as_deref()) {
+                    Ok(e) => {
+                        let refs: Vec<&str> = texts.iter().map(String::as_str).collect();
+                        match e.embed_batch(&refs) {
+                            Ok(vectors) => Response::Vectors { vectors },
+                            Err(err) => embedder_error_response(err),
+                        }
+                    }
+                    Err(resp) => resp,
+                }
+            }
+            Request::Rerank {
+                request_id,
+                query,
+                documents,
+            } => {
+                debug!(request_id = %request_id, count = documents.len(), "daemon rerank");
+                let Some(reranker) = &self.reranker else {
+                    return Response::error(RemoteErrorKind::Unavailable, "no reranker loaded");
+                };
+                let refs: Vec<&str> = documents.iter().map(String::as_str).collect();
+                match reranker.rerank(&query, &refs) {
+                    Ok(scores) => Response::Scores { scores },
+                    Err(err) => reranker_error_response(err),
+                }
+            }
+            Request::Health | Request::Shutdown => unreachable!("handled above"),
+        }
+    }
+
+    /// Bind `socket_path` and serve until a `shutdown` request arrives.
+    ///
+    /// A stale socket file left by a crashed daemon is replaced; a live one is
+    /// reported as `AddrInUse`. The socket is created owner-only (0600).
+    pub fn serve(self: Arc<Self>, socket_path: &Path) -> io::Result<()> {
+        let listener = bind_socket(socket_path)?;
+        listener.set_nonblocking(true)?;
+        info!(socket = %socket_path.display(), "daemon listening");
+
+        while !self.shutdown.load(Ordering::Acquire) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let server = Arc::clone(&self);
+                    std::thread::spawn(move || server.serve_connection(stream));
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+                    std::thread::sleep(ACCEPT_POLL_INTERVAL);
+                }
+                Err(e) => warn!(error = %e, "daemon accept failed"),
+            }
+        }
+
+        info!("daemon shutting down");
+        let _ = std::fs::remove_file(socket_path);
+        Ok(())
+    }
+
+    fn serve_connection(&self, mut stream: UnixStream) {
+        if let Err(e) = stream.set_nonblocking(false) {
+            warn!(error = %e, "daemon connection setup failed");
+            return;
+        }
+        loop {
+            let request = match read_frame::<Request>(&mut stream) {
+                Ok(Some(request)) => request,
+                Ok(None) => return,
+                Err(e) => {
+                    // Malformed frames get one error reply, then the connection is dropped.
+                    let reply =
+                        Response::error(RemoteErrorKind::InvalidInput, format!("bad request: {e}"));
+                    let _ = write_frame(&mut stream, &reply);
+                    return;
+                }
+            };
+            let response = self.handle(request);
+            if let Err(e) = write_frame(&mut stream, &response) {
+                debug!(error = %e, "daemon client went away");
+                return;
+            }
+        }
+    }
+}
+
+fn bind_socket(socket_path: &Path) -> io::Result<UnixListener> {
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("a daemon is already listening on {}", socket_path.display()),
+            ));
+        }
+        std::fs::remove_file(socket_path)?;
+    }
+    if let Some(parent) = socket_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(socket_path)?;
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+fn embedder_error_response(err: EmbedderError) -> Response {
+    let kind = match err {
+        EmbedderError::InvalidInput(_) => RemoteErrorKind::InvalidInput,
+        EmbedderError::Unavailable(_) => RemoteErrorKind::Unavailable,
+        EmbedderError::EmbeddingFailed(_) | EmbedderError::Internal(_) => RemoteErrorKind::Failed,
+    };
+    Response::error(kind, err.to_string())
+}
+
+fn reranker_error_response(err: RerankerError) -> Response {
+    let kind = match err {
+        RerankerError::InvalidInput(_) => RemoteErrorKind::InvalidInput,
+        RerankerError::Unavailable(_) => RemoteErrorKind::Unavailable,
+        RerankerError::RerankFailed(_) | RerankerError::Internal(_) => RemoteErrorKind::Failed,
+    };
+    Response::error(kind, err.to_string())
+}
+
+// -------------------------------------------------------------------------
+// Client
+// -------------------------------------------------------------------------
+
+/// [`DaemonClient`] that talks to `cass daemon` over its Unix socket.
+///
+/// Each request opens a fresh connection; connecting to a local socket is far
+/// cheaper than the model call it replaces and keeps the client stateless.
+pub struct UnixSocketDaemonClient {
+    id: String,
+    socket_path: PathBuf,
+    embedder_id: Option<String>,
+    timeout: Duration,
+}
+
+impl UnixSocketDaemonClient {
+    pub fn new(socket_path: impl Into<PathBuf>) -> Self {
+        let socket_path = socket_path.into();
+        Self {
+            id: format!("unix:{}", socket_path.display()),
+            socket_path,
+            embedder_id: None,
+            timeout: DEFAULT_CLIENT_TIMEOUT,
+        }
+    }
+
+    /// Only accept embeddings from this embedder (see [`Request::Embed`]).
+    pub fn with_embedder_id(mut self, embedder_id: impl Into<String>) -> Self {
+        self.embedder_id = Some(embedder_id.into());
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    pub fn health(&self) -> Result<HealthInfo, DaemonError> {
+        match self.call(&Request::Health)? {
+            Response::Health(info) => Ok(info),
+            other => Err(unexpected(&other)),
+        }
+    }
+
+    pub fn shutdown(&self) -> Result<(), DaemonError> {
+        match self.call(&Request::Shutdown)? {
+            Response::ShuttingDown => Ok(()),
+            other => Err(unexpected(&other)),
+        }
+    }
+
+    fn call(&self, request: &Request) -> Result<Response, DaemonError> {
+        let mut stream = UnixStream::connect(&self.socket_path).map_err(|e| {
+            DaemonError::Unavailable(format!("connect {}: {e}", self.socket_path.display()))
+        })?;
+        stream
+            .set_read_timeout(Some(self.timeout))
+            .and_then(|()| stream.set_write_timeout(Some(self.timeout)))
+            .map_err(io_error)?;
+        write_frame(&mut stream, request).map_err(io_error)?;
+        let response = read_frame::<Response>(&mut stream)
+            .map_err(io_error)?
+            .ok_or_else(|| DaemonError::Unavailable("daemon closed the connection".to_string()))?;
+
+        match response {
+            Response::Overloaded {
+                retry_after_ms,
+                message,
+            } => Err(DaemonError::Overloaded {
+                retry_after: retry_after_ms.map(Duration::from_millis),
+                message,
+            }),
+            Response::Error { kind, message } => Err(match kind {
+                RemoteErrorKind::InvalidInput => DaemonError::InvalidInput(message),
+                RemoteErrorKind::Unavailable => DaemonError::Unavailable(message),
+                RemoteErrorKind::Failed => DaemonError::Failed(message),
+            }),
+            ok => Ok(ok),
+        }
+    }
+}
+
+fn io_error(err: io::Error) -> DaemonError {
+    match err.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => {
+            DaemonError::Timeout(err.to_string())
+        }
+        io::ErrorKind::InvalidData => DaemonError::Failed(format!("protocol error: {err}")),
+        _ => DaemonError::Unavailable(err.to_string()),
+    }
+}
+
+fn unexpected(response: &Response) -> DaemonError {
+    DaemonError::Failed(format!("unexpected daemon response: {response:?}"))
+}
+
+impl DaemonClient for UnixSocketDaemonClient {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn is_available(&self) -> bool {
+        self.socket_path.exists()
+    }
+
+    fn embed(&self, text: &str, request_id: &str) -> Result<Vec<f32>, DaemonError> {
+        let request = Request::Embed {
+            request_id: request_id.to_string(),
+            embedder: self.embedder_id.clone(),
+            text: text.to_string(),
+        };
+        match self.call(&request)? {
+            Response::Vector { vector } => Ok(vector),
+            other => Err(unexpected(&other)),
+        }
+    }
+
+    fn embed_batch(&self, texts: &[&str], request_id: &str) -> Result<Vec<Vec<f32>>, DaemonError> {
+        let request = Request::EmbedBatch {
+            request_id: request_id.to_string(),
+            embedder: self.embedder_id.clone(),
+            texts: texts.iter().map(|t| t.to_string()).collect(),
+        };
+        match self.call(&request)? {
+            Response::Vectors { vectors } if vectors.len() == texts.len() => Ok(vectors),
+            other => Err(unexpected(&other)),
+        }
+    }
+
+    fn rerank(
+        &self,
+        query: &str,
+        documents: &[&str],
+        request_id: &str,
+    ) -> Result<Vec<f32>, DaemonError> {
+        let request = Request::Rerank {
+            request_id: request_id.to_string(),
+            query: query.to_string(),
+            documents: documents.iter().map(|d| d.to_string()).collect(),
+        };
+        match self.call(&request)? {
+            Response::Scores { scores } if scores.len() == documents.len() => Ok(scores),
+            other => Err(unexpected(&other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::hash_embedder::HashEmbedder;
+    use tempfile::TempDir;
+
+    fn spawn_server(dir: &TempDir, max_in_flight: usize) -> (Arc<DaemonServer>, PathBuf) {
+        let embedder: Arc<dyn Embedder> = Arc::new(HashEmbedder::new(64));
+        let server = Arc::new(DaemonServer::new(Some(embedder), None, max_in_flight));
+        let socket = dir.path().join("daemon.sock");
+        let serving = Arc::clone(&server);
+        let path = socket.clone();
+        std::thread::spawn(move || serving.serve(&path));
+        for _ in 0..200 {
+            if UnixStream::connect(&socket).is_ok() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        (server, socket)
+    }
+
+    #[test]
+    fn frame_roundtrip() {
+        let mut buf = Vec::new();
+        write_frame(&mut buf, &Request::Health).unwrap();
+        assert_eq!(&buf[..4], &((buf.len() - 4) as u32).to_be_bytes());
+        let decoded: Option<Request> = read_frame(&mut buf.as_slice()).unwrap();
+        assert!(matches!(decoded, Some(Request::Health)));
+
+        let empty: Option<Request> = read_frame(&mut io::empty()).unwrap();
+        assert!(empty.is_none());
+    }
+
+    #[test]
+    fn read_frame_rejects_oversized_length() {
+        let buf = u32::MAX.to_be_bytes();
+        let err = read_frame::<Request>(&mut buf.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn client_embeds_through_socket() {
+        let dir = TempDir::new().unwrap();
+        let (server, socket) = spawn_server(&dir, DEFAULT_MAX_IN_FLIGHT);
+        let local = HashEmbedder::new(64);
+        let client = UnixSocketDaemonClient::new(&socket).with_embedder_id(local.id());
+
+        assert!(client.is_available());
+        let remote = client.embed("warm model", "req-1").unwrap();
+        assert_eq!(remote, local.embed("warm model").unwrap());
+
+        let batch = client.embed_batch(&["a b", "c d"], "req-2").unwrap();
+        assert_eq!(batch.len(), 2);
+
+        let health = client.health().unwrap();
+        assert_eq!(health.protocol_version, PROTOCOL_VERSION);
+        assert_eq!(health.dimension, Some(64));
+        assert_eq!(health.requests_served, 2);
+
+        client.shutdown().unwrap();
+        assert!(server.shutdown.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn errors_map_to_daemon_error_variants() {
+        let dir = TempDir::new().unwrap();
+        let (_server, socket) = spawn_server(&dir, DEFAULT_MAX_IN_FLIGHT);
+
+        let client = UnixSocketDaemonClient::new(&socket);
+        assert!(matches!(
+            client.embed("", "req-empty"),
+            Err(DaemonError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            client.rerank("q", &["doc"], "req-rerank"),
+            Err(DaemonError::Unavailable(_))
+        ));
+
+        let wrong_model = UnixSocketDaemonClient::new(&socket).with_embedder_id("minilm-384");
+        assert!(matches!(
+            wrong_model.embed("text", "req-model"),
+            Err(DaemonError::Unavailable(_))
+        ));
+
+        let missing = UnixSocketDaemonClient::new(dir.path().join("nope.sock"));
+        assert!(!missing.is_available());
+        assert!(matches!(
+            missing.embed("text", "req-missing"),
+            Err(DaemonError::Unavailable(_))
+        ));
+    }
+
+    #[test]
+    fn saturated_daemon_reports_overloaded() {
+        let dir = TempDir::new().unwrap();
+        let (_server, socket) = spawn_server(&dir, 0);
+        let client = UnixSocketDaemonClient::new(&socket);
+        match client.embed("text", "req-busy") {
+            Err(DaemonError::Overloaded { retry_after, .. }) => {
+                assert_eq!(retry_after, Some(OVERLOAD_RETRY_AFTER));
+            }
+            other => panic!("expected overloaded, got {other:?}"),
+        }
+        // Health stays answerable while saturated.
+        assert_eq!(client.health().unwrap().max_in_flight, 0);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! This module provides the search infrastructure for cass, including:
 //!
+//! - **[`ann_index`]**: Optional HNSW approximate nearest-neighbour graph over the vector index.
 //! - **[`query`]**: Query parsing, execution, and caching for Tantivy-based full-text search.
 //! - **[`tantivy`]**: Tantivy index creation, schema management, and document indexing.
 //! - **[`embedder`]**: Embedder trait for semantic search (hash and ML implementations).
@@ -11,12 +12,16 @@
 //! - **[`reranker`]**: Reranker trait for cross-encoder reranking of search results.
 //! - **[`fastembed_reranker`]**: FastEmbed-backed cross-encoder reranker (ms-marco-MiniLM-L-6-v2).
 //! - **[`daemon_client`]**: Daemon client wrappers for warm embedder/reranker (bd-1lps).
+//! - **[`daemon_socket`]**: Unix socket server/client behind `cass daemon` (Unix only).
 //! - **[`model_manager`]**: Semantic model detection + context wiring (no downloads).
 //! - **[`model_download`]**: Model download system with consent, verification, and atomic install.
 //! - **[`canonicalize`]**: Text preprocessing for consistent embedding input.
 
+pub mod ann_index;
 pub mod canonicalize;
 pub mod daemon_client;
+#[cfg(unix)]
+pub mod daemon_socket;
 pub mod embedder;
 pub mod embedder_registry;
 pub mod fastembed_embedder;
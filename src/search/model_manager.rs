@@ -13,12 +13,14 @@ use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use crate::search::ann_index::{HnswIndex, hnsw_index_path, load_hnsw_for};
 use crate::search::embedder::Embedder;
 use crate::search::fastembed_embedder::FastEmbedder;
 use crate::search::hash_embedder::HashEmbedder;
 use crate::search::model_download::{ModelManifest, ModelState, check_version_mismatch};
 use crate::search::vector_index::{
     ROLE_ASSISTANT, ROLE_USER, SemanticFilterMaps, VectorIndex, vector_index_path,
+    vector_segment_paths,
 };
 use crate::storage::sqlite::SqliteStorage;
 
@@ -266,6 +268,8 @@ impl SemanticAvailability {
 pub struct SemanticContext {
     pub embedder: Arc<dyn Embedder>,
     pub index: VectorIndex,
+    /// HNSW graph for approximate search, when one was built and still fits `index`.
+    pub ann: Option<HnswIndex>,
     pub filter_maps: SemanticFilterMaps,
     pub roles: Option<HashSet<u8>>,
 }
@@ -319,7 +323,7 @@ pub fn load_hash_semantic_context(data_dir: &Path, db_path: &Path) -> SemanticSe
         }
     };
 
-    let index = match VectorIndex::load(&index_path) {
+    let index = match VectorIndex::load_with_segments(&index_path) {
         Ok(index) => index,
         Err(err) => {
             return SemanticSetup {
@@ -337,6 +341,7 @@ pub fn load_hash_semantic_context(data_dir: &Path, db_path: &Path) -> SemanticSe
     SemanticSetup {
         availability: SemanticAvailability::HashFallback,
         context: Some(SemanticContext {
+            ann: load_hnsw_for(&index_path, &index),
             embedder,
             index,
             filter_maps,
@@ -427,7 +432,7 @@ fn load_semantic_context_inner(
         }
     };
 
-    let index = match VectorIndex::load(&index_path) {
+    let index = match VectorIndex::load_with_segments(&index_path) {
         Ok(index) => index,
         Err(err) => {
             return SemanticSetup {
@@ -458,6 +463,7 @@ fn load_semantic_context_inner(
             embedder_id: embedder.id().to_string(),
         },
         context: Some(SemanticContext {
+            ann: load_hnsw_for(&index_path, &index),
             embedder,
             index,
             filter_maps,
@@ -496,7 +502,7 @@ pub fn needs_index_rebuild(data_dir: &Path) -> bool {
     }
 }
 
-/// Delete the vector index to force a rebuild.
+/// Delete the vector index (with its append segments and HNSW graph) to force a rebuild.
 ///
 /// Call this after a model upgrade when the user has consented to rebuilding
 /// the semantic index. The next index run will rebuild from scratch.
@@ -509,6 +515,13 @@ pub fn needs_index_rebuild(data_dir: &Path) -> bool {
 pub fn delete_vector_index_for_rebuild(data_dir: &Path) -> std::io::Result<bool> {
     let index_path = vector_index_path(data_dir, FastEmbedder::embedder_id_static());
 
+    for segment in vector_segment_paths(&index_path) {
+        std::fs::remove_file(&segment)?;
+    }
+    let ann_path = hnsw_index_path(&index_path);
+    if ann_path.is_file() {
+        std::fs::remove_file(&ann_path)?;
+    }
     if index_path.is_file() {
         std::fs::remove_file(&index_path)?;
         Ok(true)
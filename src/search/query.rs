@@ -14,6 +14,7 @@ use std::time::{Duration, Instant};
 use tantivy::collector::TopDocs;
 use tantivy::query::{
     AllQuery, BooleanQuery, Occur, PhraseQuery, Query, RangeQuery, RegexQuery, TermQuery,
+    TermSetQuery,
 };
 use tantivy::schema::{Field, IndexRecordOption, Term, Value};
 use tantivy::snippet::SnippetGenerator;
@@ -24,11 +25,14 @@ use tokio::task::JoinHandle;
 
 use rusqlite::Connection;
 
-use crate::search::canonicalize::canonicalize_for_embedding;
+use crate::connectors::claude_code::SessionThreads;
+use crate::search::ann_index::{DEFAULT_EF_SEARCH, HnswIndex};
+use crate::search::canonicalize::{canonicalize_for_embedding, chunk_for_embedding};
 use crate::search::embedder::Embedder;
 use crate::search::tantivy::fields_from_schema;
 use crate::search::vector_index::{
-    SemanticFilter, SemanticFilterMaps, VectorIndex, VectorSearchResult,
+    SemanticFilter, SemanticFilterMaps, VectorIndex, VectorSearchResult, canonical_role_name,
+    parse_role_codes,
 };
 
 use crate::sources::provenance::SourceFilter;
@@ -155,6 +159,21 @@ pub struct SearchFilters {
     /// Filter to specific session source paths (for chained searches)
     #[serde(skip_serializing_if = "HashSet::is_empty")]
     pub session_paths: HashSet<String>,
+    /// Filter to conversations carrying any of these user tags (`cass tag`).
+    /// Resolved to session paths via SQLite at search time.
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub tags: HashSet<String>,
+    /// Filter to messages with these roles (canonical names: user, assistant, tool, system)
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub roles: HashSet<String>,
+    /// Filter to sessions whose tool calls touched any of these files (`--file`).
+    /// Resolved to session paths via the SQLite snippets table at search time.
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub files: HashSet<String>,
+    /// Filter to conversations whose title contains any of these strings (`title:`).
+    /// Resolved to session paths via SQLite at search time.
+    #[serde(skip_serializing_if = "HashSet::is_empty")]
+    pub titles: HashSet<String>,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, clap::ValueEnum)]
@@ -179,7 +198,7 @@ impl SearchMode {
     }
 }
 
-const RRF_K: f32 = 60.0;
+pub(crate) const RRF_K: f32 = 60.0;
 const HYBRID_CANDIDATE_MULTIPLIER: usize = 3;
 
 // ============================================================================
@@ -254,6 +273,9 @@ pub struct ParsedQuery {
     pub operators: Vec<String>,
     /// Whether implicit AND is used between terms
     pub implicit_and: bool,
+    /// Inline `field:value` qualifiers lifted into filters
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldQualifier>,
 }
 
 /// Comprehensive query explanation for debugging and understanding search behavior
@@ -288,19 +310,39 @@ pub struct FiltersSummary {
     pub workspace_count: usize,
     /// Whether time range is applied
     pub has_time_filter: bool,
+    /// Conversation tags being filtered on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Message roles being filtered on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub roles: Vec<String>,
+    /// Touched files being filtered on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Title substrings being filtered on
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub titles: Vec<String>,
     /// Human-readable filter description
     pub description: Option<String>,
 }
 
 impl QueryExplanation {
     /// Build explanation from query string and filters
+    ///
+    /// `field:value` qualifiers in `query` are listed under `parsed.fields` and left out
+    /// of the text analysis; callers apply them to `filters` beforehand.
     pub fn analyze(query: &str, filters: &SearchFilters) -> Self {
-        let sanitized = sanitize_query(query);
+        let scoped = parse_field_qualifiers(query);
+        let text = scoped.text.as_str();
+        let sanitized = sanitize_query(text);
         // Parse original query to preserve quotes for phrases
-        let tokens = parse_boolean_query(query);
+        let tokens = parse_boolean_query(text);
 
         // Extract terms, phrases, and operators
-        let mut parsed = ParsedQuery::default();
+        let mut parsed = ParsedQuery {
+            fields: scoped.qualifiers,
+            ..ParsedQuery::default()
+        };
         let mut has_explicit_operator = false;
         let mut next_negated = false;
 
@@ -393,6 +435,7 @@ impl QueryExplanation {
             || !filters.workspaces.is_empty()
             || filters.created_from.is_some()
             || filters.created_to.is_some()
+            || !filters.roles.is_empty()
             || !filters.source_filter.is_all();
 
         if has_filters {
@@ -499,6 +542,26 @@ impl QueryExplanation {
         if has_time_filter {
             parts.push("time range".to_string());
         }
+        let mut tags: Vec<String> = filters.tags.iter().cloned().collect();
+        tags.sort();
+        if !tags.is_empty() {
+            parts.push(format!("tag {}", tags.join("|")));
+        }
+        let mut roles: Vec<String> = filters.roles.iter().cloned().collect();
+        roles.sort();
+        if !roles.is_empty() {
+            parts.push(format!("role {}", roles.join("|")));
+        }
+        let mut files: Vec<String> = filters.files.iter().cloned().collect();
+        files.sort();
+        if !files.is_empty() {
+            parts.push(format!("file {}", files.join("|")));
+        }
+        let mut titles: Vec<String> = filters.titles.iter().cloned().collect();
+        titles.sort();
+        if !titles.is_empty() {
+            parts.push(format!("title {}", titles.join("|")));
+        }
 
         let description = if parts.is_empty() {
             None
@@ -510,6 +573,10 @@ impl QueryExplanation {
             agent_count,
             workspace_count,
             has_time_filter,
+            tags,
+            roles,
+            files,
+            titles,
             description,
         }
     }
@@ -587,7 +654,7 @@ impl QueryExplanation {
 
 /// Indicates how a search result matched the query.
 /// Used for ranking: exact matches rank higher than wildcard matches.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MatchType {
     /// No wildcards - matched via exact term or edge n-gram prefix
@@ -758,12 +825,12 @@ impl FieldMask {
     }
 }
 
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SearchHit {
     pub title: String,
     pub snippet: String,
     pub content: String,
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     pub content_hash: u64,
     pub score: f32,
     pub source_path: String,
@@ -782,12 +849,22 @@ pub struct SearchHit {
     /// Source identifier (e.g., "local", "work-laptop")
     #[serde(default = "default_source_id")]
     pub source_id: String,
-    /// Origin kind ("local" or "ssh")
+    /// Origin kind ("local", "ssh", "path" or "bundle")
     #[serde(default = "default_origin_kind")]
     pub origin_kind: String,
     /// Origin host label for remote sources
     #[serde(skip_serializing_if = "Option::is_none")]
     pub origin_host: Option<String>,
+    /// Session that spawned the subagent transcript this hit comes from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<ParentSession>,
+}
+
+/// Parent of a subagent transcript (see `connectors::claude_code::ParentLink`).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ParentSession {
+    pub title: Option<String>,
+    pub source_path: String,
 }
 
 static LAZY_FIELDS_ENABLED: Lazy<bool> = Lazy::new(|| {
@@ -1106,6 +1183,7 @@ impl QueryCache {
 struct SemanticSearchState {
     embedder: Arc<dyn Embedder>,
     index: VectorIndex,
+    ann: Option<HnswIndex>,
     filter_maps: SemanticFilterMaps,
     roles: Option<HashSet<u8>>,
     query_cache: QueryCache,
@@ -1674,6 +1752,214 @@ fn parse_boolean_query(query: &str) -> QueryTokenList {
     tokens
 }
 
+/// A `field:value` qualifier written inline in a query string.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct FieldQualifier {
+    /// Canonical field name (see [`QUERY_FIELDS`])
+    pub field: &'static str,
+    /// Value with surrounding quotes removed
+    pub value: String,
+}
+
+/// A query string split into free text and its inline qualifiers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldScopedQuery {
+    /// The query with qualifiers removed, for the lexical/semantic engines
+    pub text: String,
+    pub qualifiers: Vec<FieldQualifier>,
+    /// Qualifiers under `NOT`, `-` or next to `OR`, left in the text; filters
+    /// cannot express them, so [`Self::apply_to`] rejects the query
+    pub unsupported: Vec<String>,
+}
+
+/// Field names accepted as `field:value` qualifiers, in completion order.
+pub const QUERY_FIELDS: &[&str] = &[
+    "agent",
+    "workspace",
+    "title",
+    "role",
+    "after",
+    "before",
+    "source",
+    "tag",
+    "file",
+];
+
+/// Map a qualifier name (or alias) to its canonical field.
+fn canonical_query_field(name: &str) -> Option<&'static str> {
+    match name.to_ascii_lowercase().as_str() {
+        "agent" => Some("agent"),
+        "workspace" | "ws" => Some("workspace"),
+        "title" => Some("title"),
+        "role" => Some("role"),
+        "after" | "since" => Some("after"),
+        "before" | "until" => Some("before"),
+        "source" => Some("source"),
+        "tag" => Some("tag"),
+        "file" => Some("file"),
+        _ => None,
+    }
+}
+
+/// Lift `field:value` qualifiers out of a query string.
+///
+/// Recognised fields are listed in [`QUERY_FIELDS`]; values may be quoted
+/// (`title:"db migration"`). Words with an unknown prefix (`std::io`, URLs) or an
+/// empty value (`agent:` while typing) stay in the text. Qualifiers only narrow
+/// the search: one that is negated (`NOT agent:x`, `-agent:x`) or next to `OR`
+/// stays in the text and is listed in `unsupported`, and an `AND` joining a
+/// lifted qualifier is dropped with it.
+pub fn parse_field_qualifiers(query: &str) -> FieldScopedQuery {
+    let is_op = |word: &str, ops: &[&str]| ops.iter().any(|op| word.eq_ignore_ascii_case(op));
+    let words = split_query_words(query);
+    let mut scoped = FieldScopedQuery::default();
+    let mut rest: Vec<&str> = Vec::new();
+    let mut drop_next_and = false;
+    for (idx, word) in words.iter().copied().enumerate() {
+        if drop_next_and {
+            drop_next_and = false;
+            if is_op(word, &["AND", "&&"]) {
+                continue;
+            }
+        }
+        if let Some(negated) = word.strip_prefix('-')
+            && parse_qualifier(negated).is_some()
+        {
+            scoped.unsupported.push(word.to_string());
+            rest.push(word);
+            continue;
+        }
+        let Some(qualifier) = parse_qualifier(word) else {
+            rest.push(word);
+            continue;
+        };
+        let prev = rest.last().copied();
+        let next = words.get(idx + 1).copied();
+        if let Some(op) = prev.filter(|w| is_op(w, &["NOT", "OR", "||"])) {
+            scoped.unsupported.push(format!("{op} {word}"));
+            rest.push(word);
+        } else if let Some(op) = next.filter(|w| is_op(w, &["OR", "||"])) {
+            scoped.unsupported.push(format!("{word} {op}"));
+            rest.push(word);
+        } else {
+            if prev.is_some_and(|w| is_op(w, &["AND", "&&"])) {
+                rest.pop();
+            } else {
+                drop_next_and = true;
+            }
+            scoped.qualifiers.push(qualifier);
+        }
+    }
+    scoped.text = rest.join(" ");
+    scoped
+}
+
+impl FieldScopedQuery {
+    /// Add the qualifiers to `filters`, on top of any filters set by flags or pills.
+    ///
+    /// Repeated qualifiers of one field widen the match like repeated flags do;
+    /// `after:`/`before:` narrow an existing time range.
+    pub fn apply_to(&self, filters: &mut SearchFilters) -> Result<()> {
+        if !self.unsupported.is_empty() {
+            bail!(
+                "field qualifiers cannot be negated or combined with OR: {}",
+                self.unsupported.join(", ")
+            );
+        }
+        for qualifier in &self.qualifiers {
+            let value = qualifier.value.as_str();
+            match qualifier.field {
+                "agent" => {
+                    filters.agents.insert(value.to_string());
+                }
+                "workspace" => {
+                    filters.workspaces.insert(expand_home(value));
+                }
+                "title" => {
+                    filters.titles.insert(value.to_string());
+                }
+                "role" => {
+                    let role = canonical_role_name(value).ok_or_else(|| {
+                        anyhow!("unknown role '{value}' (expected user, assistant, tool or system)")
+                    })?;
+                    filters.roles.insert(role.to_string());
+                }
+                "after" => {
+                    let ts = crate::ui::time_parser::parse_time_input(value)
+                        .ok_or_else(|| anyhow!("invalid date in after:{value}"))?;
+                    filters.created_from = Some(filters.created_from.map_or(ts, |t| t.max(ts)));
+                }
+                "before" => {
+                    let ts = crate::ui::time_parser::parse_time_input(value)
+                        .ok_or_else(|| anyhow!("invalid date in before:{value}"))?;
+                    filters.created_to = Some(filters.created_to.map_or(ts, |t| t.min(ts)));
+                }
+                "source" => {
+                    filters.source_filter = SourceFilter::parse(value);
+                }
+                "tag" => {
+                    filters.tags.insert(value.to_string());
+                }
+                "file" => {
+                    filters.files.insert(value.to_string());
+                }
+                other => bail!("unsupported query field '{other}'"),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Split on whitespace outside double quotes; quotes stay in the words.
+fn split_query_words(query: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut in_quotes = false;
+    for (idx, c) in query.char_indices() {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        if c.is_whitespace() && !in_quotes {
+            if let Some(begin) = start.take() {
+                words.push(&query[begin..idx]);
+            }
+        } else if start.is_none() {
+            start = Some(idx);
+        }
+    }
+    if let Some(begin) = start {
+        words.push(&query[begin..]);
+    }
+    words
+}
+
+fn parse_qualifier(word: &str) -> Option<FieldQualifier> {
+    let (name, value) = word.split_once(':')?;
+    let field = canonical_query_field(name)?;
+    let value = match value.strip_prefix('"') {
+        Some(quoted) => quoted.strip_suffix('"').unwrap_or(quoted),
+        None => value,
+    }
+    .trim();
+    if value.is_empty() {
+        return None;
+    }
+    Some(FieldQualifier {
+        field,
+        value: value.to_string(),
+    })
+}
+
+/// Expand a leading `~` to the home directory, as a shell would.
+fn expand_home(path: &str) -> String {
+    match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => dirs::home_dir()
+            .map(|home| format!("{}{rest}", home.display()))
+            .unwrap_or_else(|| path.to_string()),
+        _ => path.to_string(),
+    }
+}
+
 /// Normalize a term into tokenizer-aligned parts.
 /// Splits on punctuation to match SimpleTokenizer behavior, preserving `*` for wildcards.
 fn normalize_term_parts(raw: &str) -> Vec<String> {
@@ -2001,6 +2287,18 @@ fn snippet_from_content(content: &str) -> String {
     format!("{preview}...")
 }
 
+/// Snippet for a semantic hit: the start of the message, or of the matching chunk
+/// (marked with a leading ellipsis) when a later chunk of a long message scored best.
+fn snippet_for_chunk(content: &str, chunk_idx: u8) -> String {
+    if chunk_idx == 0 {
+        return snippet_from_content(content);
+    }
+    match chunk_for_embedding(content).get(chunk_idx as usize) {
+        Some(chunk) => format!("...{}", snippet_from_content(chunk)),
+        None => snippet_from_content(content),
+    }
+}
+
 /// Deduplicate search hits by (source_id, content), keeping only the highest-scored hit
 /// for each unique content within a source.
 ///
@@ -2133,6 +2431,105 @@ impl SearchClient {
         Ok(guard)
     }
 
+    /// Resolve `filters.tags`, `filters.files` and `filters.titles` into
+    /// `filters.session_paths` using the SQLite tag, snippet and conversation tables.
+    ///
+    /// None is in the Tantivy or vector indexes as a filterable field, so every search
+    /// mode applies them through the session-path filter. Returns `false` when no
+    /// session can match (the caller should return no hits rather than search
+    /// unfiltered).
+    fn resolve_session_filters(&self, filters: &mut SearchFilters) -> Result<bool> {
+        if filters.tags.is_empty() && filters.files.is_empty() && filters.titles.is_empty() {
+            return Ok(true);
+        }
+        let sqlite_guard = self.sqlite_guard()?;
+        let Some(conn) = sqlite_guard.as_ref() else {
+            return Ok(false);
+        };
+        let mut resolved = Vec::new();
+        if !filters.tags.is_empty() {
+            resolved.push(crate::storage::sqlite::tagged_session_paths(
+                conn,
+                &filters.tags,
+            )?);
+        }
+        if !filters.files.is_empty() {
+            resolved.push(crate::storage::sqlite::session_paths_touching_files(
+                conn,
+                &filters.files,
+            )?);
+        }
+        if !filters.titles.is_empty() {
+            resolved.push(crate::storage::sqlite::session_paths_with_title(
+                conn,
+                &filters.titles,
+            )?);
+        }
+        for paths in resolved {
+            filters.session_paths = if filters.session_paths.is_empty() {
+                paths
+            } else {
+                filters
+                    .session_paths
+                    .intersection(&paths)
+                    .cloned()
+                    .collect()
+            };
+            if filters.session_paths.is_empty() {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Fill in `parent` for hits from subagent transcripts, from the parent link
+    /// stored in their conversation's metadata.
+    fn attach_parent_sessions(&self, hits: &mut [SearchHit]) -> Result<()> {
+        let paths: HashSet<String> = hits
+            .iter()
+            .filter(|hit| hit.parent.is_none())
+            .map(|hit| hit.source_path.clone())
+            .collect();
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let sqlite_guard = self.sqlite_guard()?;
+        let Some(conn) = sqlite_guard.as_ref() else {
+            return Ok(());
+        };
+        let mut stmt = conn.prepare(
+            "SELECT metadata_json FROM conversations
+             WHERE source_path = ? AND metadata_json LIKE '%\"parent\"%'",
+        )?;
+        let mut parents: HashMap<String, ParentSession> = HashMap::new();
+        for path in paths {
+            let rows = stmt.query_map([&path], |row| row.get::<_, Option<String>>(0))?;
+            for metadata in rows {
+                let Some(metadata) =
+                    metadata?.and_then(|m| serde_json::from_str::<serde_json::Value>(&m).ok())
+                else {
+                    continue;
+                };
+                if let Some(link) = SessionThreads::from_metadata(&metadata).parent {
+                    parents.insert(
+                        path.clone(),
+                        ParentSession {
+                            title: link.title,
+                            source_path: link.source_path.to_string_lossy().into_owned(),
+                        },
+                    );
+                    break;
+                }
+            }
+        }
+        for hit in hits {
+            if let Some(parent) = parents.get(&hit.source_path) {
+                hit.parent = Some(parent.clone());
+            }
+        }
+        Ok(())
+    }
+
     pub fn search(
         &self,
         query: &str,
@@ -2141,6 +2538,22 @@ impl SearchClient {
         offset: usize,
         field_mask: FieldMask,
     ) -> Result<Vec<SearchHit>> {
+        let mut hits = self.search_lexical(query, filters, limit, offset, field_mask)?;
+        self.attach_parent_sessions(&mut hits)?;
+        Ok(hits)
+    }
+
+    fn search_lexical(
+        &self,
+        query: &str,
+        mut filters: SearchFilters,
+        limit: usize,
+        offset: usize,
+        field_mask: FieldMask,
+    ) -> Result<Vec<SearchHit>> {
+        if !self.resolve_session_filters(&mut filters)? {
+            return Ok(Vec::new());
+        }
         let sanitized = sanitize_query(query);
         let field_mask = effective_field_mask(field_mask);
         let can_use_cache = field_mask.allows_cache() && field_mask.needs_content();
@@ -2209,11 +2622,7 @@ impl SearchClient {
                 field_mask,
             )?;
             if !hits.is_empty() {
-                let mut deduped = deduplicate_hits(hits);
-                // Apply session_paths filter (post-search since source_path is not indexed)
-                if !filters.session_paths.is_empty() {
-                    deduped.retain(|h| filters.session_paths.contains(&h.source_path));
-                }
+                let deduped = deduplicate_hits(hits);
 
                 // Slice the page after deduplication
                 let paged_hits: Vec<SearchHit> =
@@ -2264,11 +2673,7 @@ impl SearchClient {
                 0, // Always fetch from 0 for global dedup
                 field_mask,
             )?;
-            let mut deduped = deduplicate_hits(hits);
-            // Apply session_paths filter (post-search since source_path is not indexed)
-            if !filters.session_paths.is_empty() {
-                deduped.retain(|h| filters.session_paths.contains(&h.source_path));
-            }
+            let deduped = deduplicate_hits(hits);
 
             let paged_hits: Vec<SearchHit> = deduped.into_iter().skip(offset).take(limit).collect();
 
@@ -2282,10 +2687,13 @@ impl SearchClient {
         Ok(Vec::new())
     }
 
+    /// Install the semantic search state. With `ann`, queries walk the HNSW graph
+    /// and fall back to the exact scan only when it yields too few results.
     pub fn set_semantic_context(
         &self,
         embedder: Arc<dyn Embedder>,
         index: VectorIndex,
+        ann: Option<HnswIndex>,
         filter_maps: SemanticFilterMaps,
         roles: Option<HashSet<u8>>,
     ) -> Result<()> {
@@ -2315,6 +2723,7 @@ impl SearchClient {
         *state_guard = Some(SemanticSearchState {
             embedder,
             index,
+            ann,
             filter_maps,
             roles,
             query_cache: QueryCache::new(embedder_id.as_str(), capacity),
@@ -2334,16 +2743,32 @@ impl SearchClient {
     pub fn search_semantic(
         &self,
         query: &str,
-        filters: SearchFilters,
+        mut filters: SearchFilters,
         limit: usize,
         offset: usize,
         field_mask: FieldMask,
     ) -> Result<Vec<SearchHit>> {
+        if !self.resolve_session_filters(&mut filters)? {
+            return Ok(Vec::new());
+        }
         let field_mask = effective_field_mask(field_mask);
         let canonical = canonicalize_for_embedding(query);
         if canonical.trim().is_empty() {
             return Ok(Vec::new());
         }
+        // Vector rows carry no session path; narrow to the sessions' message ids instead
+        let session_messages = if filters.session_paths.is_empty() {
+            None
+        } else {
+            let sqlite_guard = self.sqlite_guard()?;
+            let conn = sqlite_guard
+                .as_ref()
+                .ok_or_else(|| anyhow!("semantic search requires database connection"))?;
+            Some(crate::storage::sqlite::message_ids_for_session_paths(
+                conn,
+                &filters.session_paths,
+            )?)
+        };
         let mut guard = self
             .semantic
             .lock()
@@ -2357,19 +2782,43 @@ impl SearchClient {
             .get_or_embed(state.embedder.as_ref(), &canonical)?;
         let mut semantic_filter =
             SemanticFilter::from_search_filters(&filters, &state.filter_maps)?;
-        if let Some(roles) = state.roles.clone() {
+        // Per-query role filter narrows any roles configured on the semantic context.
+        let roles = if filters.roles.is_empty() {
+            state.roles.clone()
+        } else {
+            let requested = parse_role_codes(&filters.roles)?;
+            Some(match &state.roles {
+                Some(configured) => requested.intersection(configured).copied().collect(),
+                None => requested,
+            })
+        };
+        if let Some(roles) = roles {
             semantic_filter = semantic_filter.with_roles(Some(roles));
         }
+        semantic_filter.message_ids = session_messages;
 
         let fetch = limit.saturating_add(offset);
         if fetch == 0 {
             return Ok(Vec::new());
         }
 
-        let mut results =
-            state
+        let approx = match &state.ann {
+            Some(ann) => Some(ann.search_collapsed(
+                &state.index,
+                &embedding,
+                fetch,
+                DEFAULT_EF_SEARCH,
+                Some(&semantic_filter),
+            )?),
+            None => None,
+        };
+        // A selective filter can starve the graph walk; the exact scan is cheap then.
+        let mut results = match approx {
+            Some(results) if results.len() >= fetch => results,
+            _ => state
                 .index
-                .search_top_k_collapsed(&embedding, fetch, Some(&semantic_filter))?;
+                .search_top_k_collapsed(&embedding, fetch, Some(&semantic_filter))?,
+        };
         if offset > 0 {
             results = results.into_iter().skip(offset).collect();
         }
@@ -2379,11 +2828,8 @@ impl SearchClient {
         // Deduplicate semantic hits (filter noise and keep best version of same content)
         // This aligns behavior with lexical search
         let mut hits = deduplicate_hits(hits);
-
-        // Apply session_paths filter (not supported at SemanticFilter level)
-        if !filters.session_paths.is_empty() {
-            hits.retain(|h| filters.session_paths.contains(&h.source_path));
-        }
+        drop(guard);
+        self.attach_parent_sessions(&mut hits)?;
         Ok(hits)
     }
 
@@ -2412,7 +2858,13 @@ impl SearchClient {
             params.push(i64::try_from(result.message_id)?);
         }
 
-        let content_expr = if field_mask.needs_content() {
+        // Long messages match on their best chunk; the snippet comes from that chunk.
+        let chunk_by_id: HashMap<u64, u8> = results
+            .iter()
+            .map(|r| (r.message_id, r.chunk_idx))
+            .collect();
+
+        let content_expr = if field_mask.needs_content() || field_mask.wants_snippet() {
             "m.content"
         } else {
             "''"
@@ -2437,7 +2889,7 @@ impl SearchClient {
             rusqlite::params_from_iter(params.iter()),
             |row: &rusqlite::Row| -> rusqlite::Result<(u64, SearchHit)> {
                 let message_id: i64 = row.get(0)?;
-                let content: String = row.get(1)?;
+                let mut content: String = row.get(1)?;
                 let msg_created_at: Option<i64> = row.get(2)?;
                 let idx: Option<i64> = row.get(3)?;
                 let title: Option<String> = if field_mask.wants_title() {
@@ -2456,10 +2908,14 @@ impl SearchClient {
                 let created_at = msg_created_at.or(started_at);
                 let line_number = idx.map(|i| (i + 1) as usize);
                 let snippet = if field_mask.wants_snippet() {
-                    snippet_from_content(&content)
+                    let chunk_idx = chunk_by_id.get(&(message_id as u64)).copied();
+                    snippet_for_chunk(&content, chunk_idx.unwrap_or(0))
                 } else {
                     String::new()
                 };
+                if !field_mask.needs_content() {
+                    content.clear();
+                }
                 let content_hash = stable_hit_hash(&content, &source_path, line_number, created_at);
 
                 let hit = SearchHit {
@@ -2482,6 +2938,7 @@ impl SearchClient {
                     source_id: source_id.unwrap_or_else(default_source_id),
                     origin_kind,
                     origin_host,
+                    parent: None,
                 };
 
                 Ok((message_id as u64, hit))
@@ -2829,6 +3286,23 @@ impl SearchClient {
             clauses.push((Occur::Must, Box::new(BooleanQuery::new(terms))));
         }
 
+        if !filters.roles.is_empty() {
+            let terms = filters
+                .roles
+                .into_iter()
+                .map(|role| {
+                    (
+                        Occur::Should,
+                        Box::new(TermQuery::new(
+                            Term::from_field_text(fields.role, &role),
+                            IndexRecordOption::Basic,
+                        )) as Box<dyn Query>,
+                    )
+                })
+                .collect();
+            clauses.push((Occur::Must, Box::new(BooleanQuery::new(terms))));
+        }
+
         if filters.created_from.is_some() || filters.created_to.is_some() {
             use std::ops::Bound::{Included, Unbounded};
             let lower = filters.created_from.map_or(Unbounded, |v| {
@@ -2855,13 +3329,19 @@ impl SearchClient {
                 ));
             }
             SourceFilter::Remote => {
-                // Filter to remote sources only (origin_kind == "ssh")
-                // We use "ssh" since that's the only remote kind currently
-                let term = Term::from_field_text(fields.origin_kind, "ssh");
-                clauses.push((
-                    Occur::Must,
-                    Box::new(TermQuery::new(term, IndexRecordOption::Basic)),
-                ));
+                // Filter to remote sources only (origin_kind is "ssh", "path" or "bundle")
+                let kinds: Vec<(Occur, Box<dyn Query>)> = ["ssh", "path", "bundle"]
+                    .into_iter()
+                    .map(|kind| {
+                        let term = Term::from_field_text(fields.origin_kind, kind);
+                        (
+                            Occur::Should,
+                            Box::new(TermQuery::new(term, IndexRecordOption::Basic))
+                                as Box<dyn Query>,
+                        )
+                    })
+                    .collect();
+                clauses.push((Occur::Must, Box::new(BooleanQuery::new(kinds))));
             }
             SourceFilter::SourceId(source_id) => {
                 // Filter to specific source by ID
@@ -2873,8 +3353,13 @@ impl SearchClient {
             }
         }
 
-        // NOTE: session_paths filtering is applied post-search since source_path
-        // is STORED but not indexed. See apply_session_paths_filter().
+        if !filters.session_paths.is_empty() {
+            let terms = filters
+                .session_paths
+                .iter()
+                .map(|path| Term::from_field_text(fields.source_path, path));
+            clauses.push((Occur::Must, Box::new(TermSetQuery::new(terms))));
+        }
 
         let q: Box<dyn Query> = if clauses.is_empty() {
             Box::new(AllQuery)
@@ -2996,6 +3481,7 @@ impl SearchClient {
                 source_id,
                 origin_kind,
                 origin_host,
+                parent: None,
             });
         }
         Ok(hits)
@@ -3063,6 +3549,31 @@ impl SearchClient {
             }
         }
 
+        if !filters.roles.is_empty() {
+            // SQLite stores assistant turns as "agent" (MessageRole::Agent).
+            let db_roles: Vec<&str> = filters
+                .roles
+                .iter()
+                .flat_map(|r| match r.as_str() {
+                    "assistant" => vec!["agent", "assistant"],
+                    other => vec![other],
+                })
+                .collect();
+            let placeholders = sql_placeholders(db_roles.len());
+            sql.push_str(&format!(" AND m.role IN ({placeholders})"));
+            for r in db_roles {
+                params.push(Box::new(r.to_string()));
+            }
+        }
+
+        if !filters.session_paths.is_empty() {
+            let placeholders = sql_placeholders(filters.session_paths.len());
+            sql.push_str(&format!(" AND f.source_path IN ({placeholders})"));
+            for path in filters.session_paths {
+                params.push(Box::new(path));
+            }
+        }
+
         if let Some(created_from) = filters.created_from {
             sql.push_str(" AND f.created_at >= ?");
             params.push(Box::new(created_from));
@@ -3109,6 +3620,7 @@ impl SearchClient {
                     source_id: default_source_id(),
                     origin_kind: default_origin_kind(),
                     origin_host: None,
+                    parent: None,
                 })
             },
         )?;
@@ -3502,6 +4014,27 @@ fn filters_fingerprint(filters: &SearchFilters) -> String {
         v.sort();
         parts.push(format!("sp:{v:?}"));
     }
+    // Include conversation tags in cache key
+    if !filters.tags.is_empty() {
+        let mut v: Vec<_> = filters.tags.iter().cloned().collect();
+        v.sort();
+        parts.push(format!("tag:{v:?}"));
+    }
+    if !filters.roles.is_empty() {
+        let mut v: Vec<_> = filters.roles.iter().cloned().collect();
+        v.sort();
+        parts.push(format!("r:{v:?}"));
+    }
+    if !filters.files.is_empty() {
+        let mut v: Vec<_> = filters.files.iter().cloned().collect();
+        v.sort();
+        parts.push(format!("f:{v:?}"));
+    }
+    if !filters.titles.is_empty() {
+        let mut v: Vec<_> = filters.titles.iter().cloned().collect();
+        v.sort();
+        parts.push(format!("t:{v:?}"));
+    }
     parts.join("|")
 }
 
@@ -3830,6 +4363,7 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         };
         let cached = cached_hit_from(&hit);
 
@@ -3882,6 +4416,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
         }
     }
@@ -4091,6 +4626,7 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         };
 
         let cached = CachedHit {
@@ -4286,6 +4822,7 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         }];
 
         client.put_cache("こん", &SearchFilters::default(), &hits);
@@ -4315,6 +4852,7 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         };
         let cached = cached_hit_from(&hit);
         assert!(hit_matches_query_cached(&cached, "hello"));
@@ -4897,6 +5435,7 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         };
         let hits = vec![hit];
 
@@ -4954,6 +5493,7 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         };
         let hits = vec![hit.clone()];
 
@@ -5041,6 +5581,7 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         };
 
         // Put 3 entries - should trigger 1 eviction (cap is 2)
@@ -5107,6 +5648,7 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         };
 
         // Put 3 large entries - should trigger byte-based evictions
@@ -5403,6 +5945,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -5420,6 +5963,7 @@ mod tests {
                 source_id: "local".into(), // same source_id = will dedupe
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
         ];
 
@@ -5448,6 +5992,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -5465,6 +6010,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
         ];
 
@@ -5493,6 +6039,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -5510,6 +6057,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
         ];
 
@@ -5536,6 +6084,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -5553,6 +6102,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
         ];
 
@@ -5580,6 +6130,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
             SearchHit {
                 title: "title2".into(),
@@ -5597,6 +6148,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
             SearchHit {
                 title: "title3".into(),
@@ -5614,6 +6166,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
         ];
 
@@ -5642,6 +6195,7 @@ mod tests {
                 source_id: "local".into(),
                 origin_kind: "local".into(),
                 origin_host: None,
+                parent: None,
             },
             SearchHit {
                 title: "remote title".into(),
@@ -5659,6 +6213,7 @@ mod tests {
                 source_id: "work-laptop".into(), // different source = no dedupe
                 origin_kind: "ssh".into(),
                 origin_host: Some("work-laptop.local".into()),
+                parent: None,
             },
         ];
 
@@ -6105,6 +6660,101 @@ mod tests {
     }
 
     // Boolean query parsing tests
+    #[test]
+    fn parse_field_qualifiers_lifts_known_fields() {
+        let scoped = parse_field_qualifiers(
+            r#"agent:codex title:"db migration" retry "exact phrase" std::io ROLE:user"#,
+        );
+        assert_eq!(scoped.text, r#"retry "exact phrase" std::io"#);
+        let fields: Vec<(&str, &str)> = scoped
+            .qualifiers
+            .iter()
+            .map(|q| (q.field, q.value.as_str()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("agent", "codex"),
+                ("title", "db migration"),
+                ("role", "user")
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_field_qualifiers_keeps_incomplete_and_unknown_words() {
+        let scoped = parse_field_qualifiers("fix agent: https://example.com note:x");
+        assert!(scoped.qualifiers.is_empty());
+        assert_eq!(scoped.text, "fix agent: https://example.com note:x");
+    }
+
+    #[test]
+    fn parse_field_qualifiers_rejects_negated_and_or_qualifiers() {
+        for (query, rejected) in [
+            ("foo NOT agent:codex", "NOT agent:codex"),
+            ("foo -agent:codex", "-agent:codex"),
+            ("a OR agent:x", "OR agent:x"),
+            ("agent:x || b", "agent:x ||"),
+        ] {
+            let scoped = parse_field_qualifiers(query);
+            assert!(scoped.qualifiers.is_empty(), "{query}");
+            assert_eq!(scoped.text, query);
+            assert_eq!(scoped.unsupported, vec![rejected.to_string()]);
+            let err = scoped
+                .apply_to(&mut SearchFilters::default())
+                .expect_err(query);
+            assert!(err.to_string().contains(rejected), "{err}");
+        }
+    }
+
+    #[test]
+    fn parse_field_qualifiers_drops_the_and_joining_a_qualifier() {
+        let scoped = parse_field_qualifiers("retry AND agent:codex AND jitter NOT flaky");
+        assert_eq!(scoped.text, "retry AND jitter NOT flaky");
+        assert_eq!(scoped.qualifiers.len(), 1);
+        assert!(scoped.unsupported.is_empty());
+
+        let leading = parse_field_qualifiers("agent:codex AND retry");
+        assert_eq!(leading.text, "retry");
+    }
+
+    #[test]
+    fn field_qualifiers_apply_to_filters() -> Result<()> {
+        let scoped = parse_field_qualifiers(
+            "agent:codex agent:gemini ws:/proj role:agent after:2026-01-01 before:2026-02-01 \
+             source:laptop tag:ops file:src/lib.rs",
+        );
+        let mut filters = SearchFilters {
+            created_from: Some(0),
+            ..SearchFilters::default()
+        };
+        scoped.apply_to(&mut filters)?;
+        assert_eq!(filters.agents.len(), 2);
+        assert!(filters.workspaces.contains("/proj"));
+        assert!(filters.roles.contains("assistant"));
+        let from = filters.created_from.expect("after: sets created_from");
+        let to = filters.created_to.expect("before: sets created_to");
+        assert!(from > 0 && from < to);
+        assert_eq!(filters.source_filter, SourceFilter::parse("laptop"));
+        assert!(filters.tags.contains("ops"));
+        assert!(filters.files.contains("src/lib.rs"));
+
+        let bad_role = parse_field_qualifiers("role:robot");
+        assert!(bad_role.apply_to(&mut SearchFilters::default()).is_err());
+        let bad_date = parse_field_qualifiers("after:someday");
+        assert!(bad_date.apply_to(&mut SearchFilters::default()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn explanation_lists_field_qualifiers() {
+        let explanation = QueryExplanation::analyze("agent:codex retry", &SearchFilters::default());
+        assert_eq!(explanation.parsed.fields.len(), 1);
+        assert_eq!(explanation.parsed.fields[0].field, "agent");
+        assert_eq!(explanation.parsed.terms.len(), 1);
+        assert_eq!(explanation.parsed.terms[0].text, "retry");
+    }
+
     #[test]
     fn parse_boolean_query_simple_terms() {
         let tokens = parse_boolean_query("foo bar baz");
@@ -7853,6 +8503,278 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn search_session_paths_filter_finds_low_ranked_sessions() -> Result<()> {
+        // The filter is part of the query, so a session whose only hit ranks below
+        // many unfiltered hits is still found with a small limit.
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        let filler = "unrelated words ".repeat(50);
+        for i in 0..20 {
+            let content = if i == 19 {
+                format!("needle {filler}")
+            } else {
+                "needle".to_string()
+            };
+            let conv = NormalizedConversation {
+                agent_slug: "claude".into(),
+                external_id: None,
+                title: Some(format!("session-{i}")),
+                workspace: Some(std::path::PathBuf::from("/ws")),
+                source_path: dir.path().join(format!("session-{i}.jsonl")),
+                started_at: Some(100 + i),
+                ended_at: None,
+                metadata: serde_json::json!({}),
+                messages: vec![NormalizedMessage {
+                    idx: 0,
+                    role: "user".into(),
+                    author: None,
+                    created_at: Some(100 + i),
+                    content,
+                    extra: serde_json::json!({}),
+                    snippets: vec![],
+                }],
+            };
+            index.add_conversation(&conv)?;
+        }
+        index.commit()?;
+
+        let client = SearchClient::open(dir.path(), None)?.expect("index present");
+        let target = dir.path().join("session-19.jsonl");
+        let mut filters = SearchFilters::default();
+        filters
+            .session_paths
+            .insert(target.to_string_lossy().to_string());
+        let hits = client.search("needle", filters, 1, 0, FieldMask::FULL)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_path, target.to_string_lossy());
+        Ok(())
+    }
+
+    #[test]
+    fn search_hits_from_subagent_transcripts_name_their_parent() -> Result<()> {
+        use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
+        use crate::storage::sqlite::SqliteStorage;
+
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        let db_path = dir.path().join("agent_search.db");
+        let mut storage = SqliteStorage::open(&db_path)?;
+        let agent_id = storage.ensure_agent(&Agent {
+            id: None,
+            slug: "claude_code".into(),
+            name: "Claude Code".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        })?;
+
+        let session = dir.path().join("sess-1.jsonl");
+        let transcript = dir.path().join("agent-a1.jsonl");
+        for (path, metadata) in [
+            (&session, serde_json::json!({})),
+            (
+                &transcript,
+                serde_json::json!({
+                    "parent": { "source_path": session, "title": "Trace foo callers" }
+                }),
+            ),
+        ] {
+            index.add_conversation(&NormalizedConversation {
+                agent_slug: "claude_code".into(),
+                external_id: None,
+                title: None,
+                workspace: None,
+                source_path: path.clone(),
+                started_at: Some(100),
+                ended_at: None,
+                metadata: metadata.clone(),
+                messages: vec![NormalizedMessage {
+                    idx: 0,
+                    role: "assistant".into(),
+                    author: None,
+                    created_at: Some(100),
+                    content: "needle found in foo".into(),
+                    extra: serde_json::json!({}),
+                    snippets: vec![],
+                }],
+            })?;
+            storage.insert_conversation_tree(
+                agent_id,
+                None,
+                &Conversation {
+                    id: None,
+                    agent_slug: "claude_code".into(),
+                    workspace: None,
+                    external_id: Some(path.to_string_lossy().into_owned()),
+                    title: None,
+                    source_path: path.clone(),
+                    started_at: Some(100),
+                    ended_at: None,
+                    approx_tokens: None,
+                    metadata_json: metadata,
+                    messages: vec![Message {
+                        id: None,
+                        idx: 0,
+                        role: MessageRole::Agent,
+                        author: None,
+                        created_at: Some(100),
+                        content: "needle found in foo".into(),
+                        extra_json: serde_json::json!({}),
+                        snippets: vec![],
+                    }],
+                    source_id: "local".into(),
+                    origin_host: None,
+                },
+            )?;
+        }
+        index.commit()?;
+
+        let client =
+            SearchClient::open(dir.path(), Some(db_path.as_path()))?.expect("index present");
+        let hits = client.search("needle", SearchFilters::default(), 10, 0, FieldMask::FULL)?;
+        assert_eq!(hits.len(), 2);
+        for hit in &hits {
+            if hit.source_path == transcript.to_string_lossy() {
+                let parent = hit.parent.as_ref().expect("parent of the transcript");
+                assert_eq!(parent.title.as_deref(), Some("Trace foo callers"));
+                assert_eq!(parent.source_path, session.to_string_lossy());
+            } else {
+                assert!(hit.parent.is_none());
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn search_tags_and_files_filters_resolve_through_sqlite() -> Result<()> {
+        use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole, Snippet};
+        use crate::storage::sqlite::SqliteStorage;
+
+        let dir = TempDir::new()?;
+        let mut index = TantivyIndex::open_or_create(dir.path())?;
+        let db_path = dir.path().join("agent_search.db");
+        let mut storage = SqliteStorage::open(&db_path)?;
+        let agent_id = storage.ensure_agent(&Agent {
+            id: None,
+            slug: "claude".into(),
+            name: "Claude".into(),
+            version: None,
+            kind: AgentKind::Cli,
+        })?;
+
+        let paths = [
+            dir.path().join("session-a.jsonl"),
+            dir.path().join("session-b.jsonl"),
+        ];
+        for (i, path) in paths.iter().enumerate() {
+            index.add_conversation(&NormalizedConversation {
+                agent_slug: "claude".into(),
+                external_id: None,
+                title: Some(format!("session-{i}")),
+                workspace: None,
+                source_path: path.clone(),
+                started_at: Some(100),
+                ended_at: None,
+                metadata: serde_json::json!({}),
+                messages: vec![NormalizedMessage {
+                    idx: 0,
+                    role: "user".into(),
+                    author: None,
+                    created_at: Some(100),
+                    content: format!("needle content for session {i}"),
+                    extra: serde_json::json!({}),
+                    snippets: vec![],
+                }],
+            })?;
+            storage.insert_conversation_tree(
+                agent_id,
+                None,
+                &Conversation {
+                    id: None,
+                    agent_slug: "claude".into(),
+                    workspace: None,
+                    external_id: Some(format!("ext-{i}")),
+                    title: Some(format!("session-{i}")),
+                    source_path: path.clone(),
+                    started_at: Some(100),
+                    ended_at: None,
+                    approx_tokens: None,
+                    metadata_json: serde_json::json!({}),
+                    // Session a edited src/foo.rs through a tool call
+                    messages: vec![Message {
+                        id: None,
+                        idx: 0,
+                        role: MessageRole::Agent,
+                        author: None,
+                        created_at: Some(100),
+                        content: "[Tool: Edit - src/foo.rs]".into(),
+                        extra_json: serde_json::json!({}),
+                        snippets: if i == 0 {
+                            vec![Snippet {
+                                id: None,
+                                file_path: Some(PathBuf::from("/repo/src/foo.rs")),
+                                start_line: None,
+                                end_line: None,
+                                language: Some("rust".into()),
+                                snippet_text: Some("fn foo() {}".into()),
+                            }]
+                        } else {
+                            vec![]
+                        },
+                    }],
+                    source_id: "local".into(),
+                    origin_host: None,
+                },
+            )?;
+        }
+        index.commit()?;
+
+        let conv_b = storage.conversation_ids_for_path(&paths[1].to_string_lossy())?;
+        storage.add_conversation_tag(conv_b[0], "Postmortem")?;
+
+        let client =
+            SearchClient::open(dir.path(), Some(db_path.as_path()))?.expect("index present");
+
+        let mut filters = SearchFilters::default();
+        filters.tags.insert("postmortem".into());
+        let hits = client.search("needle", filters, 10, 0, FieldMask::FULL)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_path, paths[1].to_string_lossy());
+
+        // An unknown tag matches nothing rather than disabling the filter
+        let mut filters = SearchFilters::default();
+        filters.tags.insert("missing".into());
+        assert!(
+            client
+                .search("needle", filters, 10, 0, FieldMask::FULL)?
+                .is_empty()
+        );
+
+        let mut filters = SearchFilters::default();
+        filters.files.insert("src/foo.rs".into());
+        let hits = client.search("needle", filters.clone(), 10, 0, FieldMask::FULL)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_path, paths[0].to_string_lossy());
+
+        // Tag and file filters intersect
+        filters.tags.insert("postmortem".into());
+        assert!(
+            client
+                .search("needle", filters, 10, 0, FieldMask::FULL)?
+                .is_empty()
+        );
+
+        // `title:` qualifiers match title substrings case-insensitively
+        let scoped = parse_field_qualifiers("needle title:SESSION-1");
+        let mut filters = SearchFilters::default();
+        scoped.apply_to(&mut filters)?;
+        let hits = client.search(&scoped.text, filters, 10, 0, FieldMask::FULL)?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_path, paths[1].to_string_lossy());
+
+        Ok(())
+    }
+
     #[test]
     fn search_session_paths_empty_filter_returns_all() -> Result<()> {
         // Empty session_paths filter should not restrict results
@@ -7935,6 +8857,19 @@ mod tests {
         assert_eq!(result.chars().filter(|c| *c == ',').count(), 99);
     }
 
+    #[test]
+    fn snippet_for_chunk_uses_matching_chunk() {
+        let words: Vec<String> = (0..1000).map(|i| format!("w{i}")).collect();
+        let content = words.join(" ");
+
+        assert!(snippet_for_chunk(&content, 0).starts_with("w0 w1 "));
+        let later = snippet_for_chunk(&content, 1);
+        assert!(later.starts_with("..."));
+        assert!(!later.contains(" w0 "));
+        // Out-of-range chunks fall back to the message start.
+        assert!(snippet_for_chunk("short text", 3).starts_with("short text"));
+    }
+
     // =============================================================================
     // RRF (Reciprocal Rank Fusion) Tests
     // =============================================================================
@@ -7956,6 +8891,7 @@ mod tests {
             source_id: "local".to_string(),
             origin_kind: "local".to_string(),
             origin_host: None,
+            parent: None,
         }
     }
 
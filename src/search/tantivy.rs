@@ -8,7 +8,7 @@ use tantivy::schema::{
     FAST, Field, INDEXED, IndexRecordOption, STORED, STRING, Schema, TEXT, TextFieldIndexing,
     TextOptions,
 };
-use tantivy::{Index, IndexReader, IndexWriter, doc};
+use tantivy::{Index, IndexReader, IndexWriter, Term, doc};
 use tracing::{debug, info, warn};
 
 use crate::connectors::NormalizedConversation;
@@ -49,7 +49,7 @@ impl MergeStatus {
 }
 
 // Bump this when schema/tokenizer changes. Used to trigger rebuilds.
-pub const SCHEMA_HASH: &str = "tantivy-schema-v6-long-tokens";
+pub const SCHEMA_HASH: &str = "tantivy-schema-v6-long-tokens-role-path-term";
 
 /// Returns true if the given stored hash matches the current schema hash.
 pub fn schema_hash_matches(stored: &str) -> bool {
@@ -73,6 +73,8 @@ pub struct Fields {
     pub source_id: Field,
     pub origin_kind: Field,
     pub origin_host: Field,
+    /// Canonical message role (user/assistant/tool/system) for `--role` filtering
+    pub role: Field,
 }
 
 pub struct TantivyIndex {
@@ -147,6 +149,13 @@ impl TantivyIndex {
         self.add_messages(conv, &conv.messages)
     }
 
+    /// Delete every message document indexed from the session file `source_path`.
+    /// Takes effect on the next [`TantivyIndex::commit`].
+    pub fn delete_session(&mut self, source_path: &str) {
+        self.writer
+            .delete_term(Term::from_field_text(self.fields.source_path, source_path));
+    }
+
     pub fn delete_all(&mut self) -> Result<()> {
         self.writer.delete_all_documents()?;
         Ok(())
@@ -303,6 +312,7 @@ impl TantivyIndex {
                 self.fields.content => msg.content.clone(),
                 self.fields.source_id => source_id,
                 self.fields.origin_kind => origin_kind,
+                self.fields.role => index_role(&msg.role),
             };
             if let Some(host) = origin_host
                 && !host.is_empty()
@@ -405,7 +415,8 @@ pub fn build_schema() -> Schema {
     schema_builder.add_text_field("workspace", STRING | STORED);
     // workspace_original stores the pre-rewrite path for audit/display (P6.2)
     schema_builder.add_text_field("workspace_original", STORED);
-    schema_builder.add_text_field("source_path", STORED);
+    // STRING so every document of a session file can be deleted by term (`cass forget`).
+    schema_builder.add_text_field("source_path", STRING | STORED);
     schema_builder.add_u64_field("msg_idx", INDEXED | STORED);
     schema_builder.add_i64_field("created_at", INDEXED | STORED | FAST);
     schema_builder.add_text_field("title", text.clone());
@@ -417,6 +428,8 @@ pub fn build_schema() -> Schema {
     schema_builder.add_text_field("source_id", STRING | STORED);
     schema_builder.add_text_field("origin_kind", STRING | STORED);
     schema_builder.add_text_field("origin_host", STRING | STORED);
+    // Message role, normalized so "agent"/"assistant" share one term
+    schema_builder.add_text_field("role", STRING | STORED);
     schema_builder.build()
 }
 
@@ -441,9 +454,17 @@ pub fn fields_from_schema(schema: &Schema) -> Result<Fields> {
         source_id: get("source_id")?,
         origin_kind: get("origin_kind")?,
         origin_host: get("origin_host")?,
+        role: get("role")?,
     })
 }
 
+/// Role term stored in the index: the canonical name when known, else the raw role lowercased.
+fn index_role(role: &str) -> String {
+    crate::search::vector_index::canonical_role_name(role)
+        .map(str::to_string)
+        .unwrap_or_else(|| role.trim().to_lowercase())
+}
+
 fn build_preview(content: &str, max_chars: usize) -> String {
     let mut out = String::new();
     let mut chars = content.chars();
@@ -472,6 +493,17 @@ pub fn index_dir(base: &Path) -> Result<std::path::PathBuf> {
     Ok(dir)
 }
 
+/// Whether the index at `index_path` was written with the current schema.
+///
+/// Opening a stale index wipes it, so writers outside `cass index` check this first.
+pub fn index_schema_current(index_path: &Path) -> bool {
+    std::fs::read_to_string(index_path.join("schema_hash.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|json| json.get("schema_hash")?.as_str().map(schema_hash_matches))
+        .unwrap_or(false)
+}
+
 pub fn ensure_tokenizer(index: &mut Index) {
     use tantivy::tokenizer::{LowerCaser, RemoveLongFilter, SimpleTokenizer, TextAnalyzer};
     let analyzer = TextAnalyzer::builder(SimpleTokenizer::default())
@@ -750,6 +782,15 @@ mod tests {
         assert!(schema.get_field("source_id").is_ok());
         assert!(schema.get_field("origin_kind").is_ok());
         assert!(schema.get_field("origin_host").is_ok());
+        assert!(schema.get_field("role").is_ok());
+    }
+
+    #[test]
+    fn index_role_normalizes_agent_to_assistant() {
+        assert_eq!(index_role("agent"), "assistant");
+        assert_eq!(index_role("Assistant"), "assistant");
+        assert_eq!(index_role("user"), "user");
+        assert_eq!(index_role(" Other "), "other");
     }
 
     #[test]
@@ -773,6 +814,7 @@ mod tests {
         let _ = fields.source_id;
         let _ = fields.origin_kind;
         let _ = fields.origin_host;
+        let _ = fields.role;
     }
 
     #[test]
@@ -870,6 +912,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn delete_session_removes_only_that_source_path() {
+        use crate::connectors::NormalizedMessage;
+        use crate::search::query::{FieldMask, SearchClient, SearchFilters};
+
+        let dir = TempDir::new().unwrap();
+        let mut index = TantivyIndex::open_or_create(dir.path()).unwrap();
+        let conv = |path: &str| NormalizedConversation {
+            agent_slug: "codex".into(),
+            external_id: Some(path.into()),
+            title: None,
+            workspace: None,
+            source_path: path.into(),
+            started_at: Some(1_700_000_000_000),
+            ended_at: None,
+            metadata: serde_json::json!({}),
+            messages: vec![NormalizedMessage {
+                idx: 0,
+                role: "user".into(),
+                author: None,
+                created_at: Some(1_700_000_000_000),
+                content: "leaked token abc123".into(),
+                extra: serde_json::json!({}),
+                snippets: Vec::new(),
+            }],
+        };
+        index.add_conversation(&conv("/sessions/a.jsonl")).unwrap();
+        index.add_conversation(&conv("/sessions/b.jsonl")).unwrap();
+        index.commit().unwrap();
+
+        index.delete_session("/sessions/a.jsonl");
+        index.commit().unwrap();
+
+        let client = SearchClient::open(dir.path(), None).unwrap().unwrap();
+        let hits = client
+            .search("abc123", SearchFilters::default(), 10, 0, FieldMask::FULL)
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_path, "/sessions/b.jsonl");
+    }
+
     #[test]
     fn merge_status_should_merge_logic() {
         let status = MergeStatus {
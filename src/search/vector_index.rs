@@ -10,7 +10,7 @@
 //!   EmbedderRevision length: u16
 //!   EmbedderRevision: bytes
 //!   Dimension: u32
-//!   Quantization: u8 (0=f32, 1=f16)
+//!   Quantization: u8 (0=f32, 1=f16, 2=int8, 3=binary)
 //!   Count: u32
 //!   HeaderCRC32: u32 (CRC32 of header bytes before this field)
 //!
@@ -26,11 +26,22 @@
 //!   ContentHash: [u8; 32] (SHA256 of canonical content)
 //!
 //! Vector slab:
-//!   Count × Dimension × bytes_per_quant, contiguous, 32-byte aligned.
+//!   Count × vector_bytes(Dimension), contiguous, 32-byte aligned.
+//!   f32/f16: Dimension components.
+//!   int8: f32 scale followed by Dimension i8 codes (value ≈ code × scale).
+//!   binary: sign bits packed into ceil(Dimension/64) u64 words (set = non-negative),
+//!   followed by Dimension f16 components. Searches shortlist rows by Hamming
+//!   distance to the query's signs, then rerank the shortlist against the f16 copy.
+//!
+//! Append segments:
+//!   Incremental indexing writes new rows to `index-<embedder>.seg-<n>.cvvi` files
+//!   (ordinary CVVI files) next to the base file. Readers search base and segments
+//!   in place (later rows win per message/chunk); compaction folds segments into the
+//!   base. Writers serialize on `index-<embedder>.lock`, an exclusive file lock.
 
 use std::cmp::Ordering;
 use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::{Cursor, Read, Write};
 use std::path::{Path, PathBuf};
 
@@ -40,6 +51,7 @@ use memmap2::Mmap;
 use rayon::prelude::*;
 use rusqlite::Connection;
 
+use crate::search::ann_index::{HnswIndex, HnswParams, hnsw_index_path};
 use crate::search::query::SearchFilters;
 use crate::sources::provenance::{LOCAL_SOURCE_ID, SourceFilter, SourceKind};
 use crate::storage::sqlite::SqliteStorage;
@@ -72,10 +84,231 @@ pub fn vector_index_path(data_dir: &Path, embedder_id: &str) -> PathBuf {
         .join(format!("index-{embedder_id}.cvvi"))
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Compact once this many append segments have accumulated...
+pub const COMPACT_SEGMENT_COUNT: usize = 8;
+/// ...or once segments hold more than 1/`COMPACT_SEGMENT_RATIO` of the base's bytes.
+pub const COMPACT_SEGMENT_RATIO: u64 = 4;
+
+/// Take the exclusive lock serializing segment writes, compaction swaps and
+/// rewrites of the base index at `base` across processes. Released on drop.
+fn lock_segments(base: &Path) -> Result<File> {
+    let path = base.with_extension("lock");
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(&path)
+        .with_context(|| format!("open vector index lock {path:?}"))?;
+    file.lock()
+        .with_context(|| format!("lock vector index {path:?}"))?;
+    Ok(file)
+}
+
+/// Size and modification time of `path`, to notice files rewritten behind our back.
+fn file_stamp(path: &Path) -> Option<(u64, std::time::SystemTime)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()?))
+}
+
+/// Append segments belonging to the base index at `base`, oldest first.
+pub fn vector_segment_paths(base: &Path) -> Vec<PathBuf> {
+    let (Some(dir), Some(stem)) = (base.parent(), base.file_stem().and_then(|s| s.to_str())) else {
+        return Vec::new();
+    };
+    let prefix = format!("{stem}.seg-");
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut segments: Vec<(u64, PathBuf)> = read_dir
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let seq = name.strip_prefix(&prefix)?.strip_suffix(".cvvi")?;
+            Some((seq.parse().ok()?, entry.path()))
+        })
+        .collect();
+    segments.sort();
+    segments.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Write `index` as a new append segment of the base index at `base`.
+pub fn save_vector_segment(index: &VectorIndex, base: &Path) -> Result<PathBuf> {
+    let _lock = lock_segments(base)?;
+    let next = vector_segment_paths(base)
+        .last()
+        .and_then(|p| segment_seq(p))
+        .map_or(1, |seq| seq + 1);
+    let stem = base
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .ok_or_else(|| anyhow!("invalid vector index path {base:?}"))?;
+    let path = base.with_file_name(format!("{stem}.seg-{next:06}.cvvi"));
+    index.save(&path)?;
+    Ok(path)
+}
+
+/// Replace the base index at `base` with `index` and drop all of its segments
+/// (used after a full rebuild, which already covers every message).
+pub fn replace_vector_index(index: &VectorIndex, base: &Path) -> Result<()> {
+    let _lock = lock_segments(base)?;
+    index.save(base)?;
+    for segment in vector_segment_paths(base) {
+        std::fs::remove_file(&segment)
+            .with_context(|| format!("remove vector segment {segment:?}"))?;
+    }
+    Ok(())
+}
+
+/// Whether the segments of `base` are numerous or large enough to be worth compacting.
+pub fn segments_need_compaction(base: &Path) -> bool {
+    let segments = vector_segment_paths(base);
+    if segments.is_empty() {
+        return false;
+    }
+    if segments.len() >= COMPACT_SEGMENT_COUNT {
+        return true;
+    }
+    let size = |p: &Path| std::fs::metadata(p).map(|m| m.len()).unwrap_or(0);
+    let segment_bytes: u64 = segments.iter().map(|p| size(p)).sum();
+    segment_bytes.saturating_mul(COMPACT_SEGMENT_RATIO) >= size(base)
+}
+
+/// Fold the current segments of `base` into a new base file.
+///
+/// The merge runs without holding the segment lock, so appends can continue; only
+/// the final swap is serialized, and segments written after the snapshot are kept.
+/// If the base or a snapshotted segment changed in the meantime (another process
+/// compacted or rewrote the index), the merge is discarded and nothing is folded.
+/// An existing HNSW graph for the base is rebuilt afterwards.
+/// Returns the number of segments folded in.
+pub fn compact_vector_segments(base: &Path) -> Result<usize> {
+    let segments = vector_segment_paths(base);
+    if segments.is_empty() {
+        return Ok(0);
+    }
+    let snapshot: Vec<_> = std::iter::once(base)
+        .chain(segments.iter().map(PathBuf::as_path))
+        .map(file_stamp)
+        .collect();
+    let mut parts = vec![VectorIndex::load_mapped(base)?];
+    for segment in &segments {
+        parts.push(VectorIndex::load_mapped(segment)?);
+    }
+    let merged = VectorIndex::merge(&parts)?;
+    drop(parts);
+
+    let lock = lock_segments(base)?;
+    let unchanged = std::iter::once(base)
+        .chain(segments.iter().map(PathBuf::as_path))
+        .map(file_stamp)
+        .eq(snapshot.iter().copied());
+    if !unchanged {
+        tracing::debug!(
+            path = %base.display(),
+            "vector index changed during compaction; skipping"
+        );
+        return Ok(0);
+    }
+    merged.save(base)?;
+    for segment in &segments {
+        std::fs::remove_file(segment)
+            .with_context(|| format!("remove vector segment {segment:?}"))?;
+    }
+    drop(lock);
+
+    // Keep an existing HNSW graph in step with the rewritten base.
+    let ann_path = hnsw_index_path(base);
+    if ann_path.is_file() {
+        HnswIndex::build(&merged, HnswParams::default())?.save(&ann_path)?;
+    }
+    Ok(segments.len())
+}
+
+/// Base index files (one per embedder) under `data_dir`, without their segments.
+pub fn vector_index_bases(data_dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = std::fs::read_dir(data_dir.join(VECTOR_INDEX_DIR)) else {
+        return Vec::new();
+    };
+    let mut bases: Vec<PathBuf> = read_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+                n.starts_with("index-") && n.ends_with(".cvvi") && !n.contains(".seg-")
+            })
+        })
+        .collect();
+    bases.sort();
+    bases
+}
+
+/// Drop every row of the given messages from the base index at `base` and its
+/// segments. Segments are folded into the rewritten base and an existing HNSW
+/// graph is rebuilt; nothing is written when no row matches.
+/// Returns the number of rows removed.
+pub fn remove_vector_messages(base: &Path, message_ids: &HashSet<u64>) -> Result<usize> {
+    let _lock = lock_segments(base)?;
+    let segments = vector_segment_paths(base);
+    let mut parts = vec![VectorIndex::load_mapped(base)?];
+    for segment in &segments {
+        parts.push(VectorIndex::load_mapped(segment)?);
+    }
+    let stale = parts.iter().any(|part| {
+        part.rows()
+            .iter()
+            .any(|row| message_ids.contains(&row.message_id))
+    });
+    if !stale {
+        return Ok(0);
+    }
+
+    let header = parts[0].header().clone();
+    let entries = VectorIndex::merged_entries(&parts)?;
+    drop(parts);
+    let before = entries.len();
+    let kept: Vec<VectorEntry> = entries
+        .into_iter()
+        .filter(|entry| !message_ids.contains(&entry.message_id))
+        .collect();
+    let removed = before - kept.len();
+    let rewritten = VectorIndex::build(
+        header.embedder_id,
+        header.embedder_revision,
+        header.dimension as usize,
+        header.quantization,
+        kept,
+    )?;
+    rewritten.save(base)?;
+    for segment in &segments {
+        std::fs::remove_file(segment)
+            .with_context(|| format!("remove vector segment {segment:?}"))?;
+    }
+
+    let ann_path = hnsw_index_path(base);
+    if ann_path.is_file() {
+        HnswIndex::build(&rewritten, HnswParams::default())?.save(&ann_path)?;
+    }
+    Ok(removed)
+}
+
+fn segment_seq(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?;
+    let (_, seq) = name.strip_suffix(".cvvi")?.rsplit_once(".seg-")?;
+    seq.parse().ok()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, clap::ValueEnum)]
 pub enum Quantization {
+    /// Full-precision floats (largest, exact)
+    #[default]
     F32,
+    /// Half-precision floats (half the size, near-identical ranking)
     F16,
+    /// Scalar int8 codes with a per-vector scale (about a quarter of f32)
+    Int8,
+    /// 1-bit sign codes scanned by Hamming distance, reranked with f16 vectors
+    Binary,
 }
 
 impl Quantization {
@@ -83,6 +316,8 @@ impl Quantization {
         match self {
             Quantization::F32 => 0,
             Quantization::F16 => 1,
+            Quantization::Int8 => 2,
+            Quantization::Binary => 3,
         }
     }
 
@@ -90,18 +325,55 @@ impl Quantization {
         match value {
             0 => Ok(Quantization::F32),
             1 => Ok(Quantization::F16),
+            2 => Ok(Quantization::Int8),
+            3 => Ok(Quantization::Binary),
             other => bail!("unknown quantization value: {other}"),
         }
     }
 
-    pub fn bytes_per_component(self) -> usize {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Quantization::F32 => "f32",
+            Quantization::F16 => "f16",
+            Quantization::Int8 => "int8",
+            Quantization::Binary => "binary",
+        }
+    }
+
+    /// Bytes one stored vector of `dimension` components occupies in the slab.
+    pub fn vector_bytes(self, dimension: usize) -> Option<usize> {
+        match self {
+            Quantization::F32 => dimension.checked_mul(4),
+            Quantization::F16 => dimension.checked_mul(2),
+            Quantization::Int8 => dimension.checked_add(INT8_SCALE_BYTES),
+            Quantization::Binary => {
+                binary_code_bytes(dimension).checked_add(dimension.checked_mul(2)?)
+            }
+        }
+    }
+
+    /// Alignment every row's vector offset must satisfy.
+    fn offset_align(self) -> usize {
         match self {
             Quantization::F32 => 4,
-            Quantization::F16 => 2,
+            Quantization::F16 | Quantization::Binary => 2,
+            Quantization::Int8 => 1,
         }
     }
 }
 
+/// Size of the f32 scale stored ahead of each int8 vector.
+const INT8_SCALE_BYTES: usize = 4;
+
+/// Binary indexes rerank this many Hamming candidates per requested result
+/// (per requested message when collapsing chunks).
+const BINARY_RERANK_OVERSAMPLE: usize = 8;
+
+/// Bytes of packed sign bits for one binary vector (whole u64 words).
+fn binary_code_bytes(dimension: usize) -> usize {
+    dimension.div_ceil(64) * 8
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CvviHeader {
     pub version: u16,
@@ -352,6 +624,8 @@ pub struct SemanticFilter {
     pub roles: Option<HashSet<u8>>,
     pub created_from: Option<i64>,
     pub created_to: Option<i64>,
+    /// Messages of the sessions a search is restricted to (tags, files, titles, `--sessions-from`)
+    pub message_ids: Option<HashSet<u64>>,
 }
 
 impl SemanticFilter {
@@ -386,6 +660,11 @@ impl SemanticFilter {
         {
             return false;
         }
+        if let Some(ids) = &self.message_ids
+            && !ids.contains(&row.message_id)
+        {
+            return false;
+        }
         true
     }
 
@@ -401,6 +680,7 @@ impl SemanticFilter {
             roles: None,
             created_from: filters.created_from,
             created_to: filters.created_to,
+            message_ids: None,
         })
     }
 
@@ -425,6 +705,22 @@ pub fn role_code_from_str(role: &str) -> Option<u8> {
     }
 }
 
+/// Canonical role name for a role code; these are the names `--role` accepts.
+pub fn role_name_from_code(code: u8) -> Option<&'static str> {
+    match code {
+        ROLE_USER => Some("user"),
+        ROLE_ASSISTANT => Some("assistant"),
+        ROLE_SYSTEM => Some("system"),
+        ROLE_TOOL => Some("tool"),
+        _ => None,
+    }
+}
+
+/// Normalize a connector role string (e.g. "agent", "Assistant") to its canonical name.
+pub fn canonical_role_name(role: &str) -> Option<&'static str> {
+    role_code_from_str(role).and_then(role_name_from_code)
+}
+
 pub fn parse_role_codes<I, S>(roles: I) -> Result<HashSet<u8>>
 where
     I: IntoIterator<Item = S>,
@@ -530,11 +826,44 @@ pub struct VectorSearchResult {
     pub score: f32,
 }
 
+/// A query prepared for scoring rows of one index, from [`VectorIndex::row_scorer`].
+pub struct RowScorer<'a> {
+    index: &'a VectorIndex,
+    query: &'a [f32],
+    signature: Option<Vec<u64>>,
+}
+
+impl RowScorer<'_> {
+    /// Similarity of row `row_idx` to the query. For binary indexes this is the dot
+    /// product of the two ±1/√d sign vectors, computed from their Hamming distance.
+    pub fn score(&self, row_idx: usize) -> Result<f32> {
+        let Some(signature) = &self.signature else {
+            return self.index.score_row(row_idx, self.query);
+        };
+        let row = self
+            .index
+            .rows
+            .get(row_idx)
+            .ok_or_else(|| anyhow!("vector row {row_idx} out of bounds"))?;
+        let distance = hamming_distance(self.index.vector_bytes_at(row.vec_offset)?, signature);
+        Ok(1.0 - 2.0 * distance as f32 / self.query.len() as f32)
+    }
+
+    /// Whether [`Self::score`] approximates and results need [`VectorIndex::score_row`].
+    pub fn needs_rerank(&self) -> bool {
+        self.signature.is_some()
+    }
+}
+
 #[derive(Debug)]
 pub struct VectorIndex {
     header: CvviHeader,
     rows: Vec<VectorRow>,
     vectors: VectorStorage,
+    /// Append segments searched in place, each with the offset its slab starts at.
+    /// Rows taken from a segment carry `vec_offset`s shifted past the base slab and
+    /// the slabs of earlier segments (see [`VectorIndex::load_with_segments`]).
+    segments: Vec<(u64, VectorIndex)>,
 }
 
 #[derive(Debug)]
@@ -545,6 +874,8 @@ enum VectorStorage {
     /// The vec_offset values are still in F16 byte terms (2 bytes per component),
     /// so we use 2 as the divisor when computing element indices.
     PreconvertedF32(Vec<f32>),
+    /// Int8 and binary records, laid out exactly as on disk.
+    Bytes(Vec<u8>),
     Mmap {
         mmap: Mmap,
         offset: usize,
@@ -575,73 +906,55 @@ impl VectorIndex {
 
         let mut rows = Vec::with_capacity(entries.len());
         let mut offset_bytes: usize = 0;
-        let bytes_per = quantization.bytes_per_component();
-        let vector_bytes = dimension
-            .checked_mul(bytes_per)
+        let vector_bytes = quantization
+            .vector_bytes(dimension)
             .ok_or_else(|| anyhow!("vector size overflow"))?;
 
-        let vectors = match quantization {
-            Quantization::F32 => {
-                let mut slab = Vec::with_capacity(entries.len() * dimension);
-                for entry in &entries {
-                    if entry.vector.len() != dimension {
-                        bail!(
-                            "vector dimension mismatch: expected {}, got {}",
-                            dimension,
-                            entry.vector.len()
-                        );
-                    }
-                    let vec_offset = u64::try_from(offset_bytes)
-                        .map_err(|_| anyhow!("vector offset out of range"))?;
-                    rows.push(VectorRow {
-                        message_id: entry.message_id,
-                        created_at_ms: entry.created_at_ms,
-                        agent_id: entry.agent_id,
-                        workspace_id: entry.workspace_id,
-                        source_id: entry.source_id,
-                        role: entry.role,
-                        chunk_idx: entry.chunk_idx,
-                        vec_offset,
-                        content_hash: entry.content_hash,
-                    });
-                    slab.extend(entry.vector.iter().copied());
-                    offset_bytes = offset_bytes
-                        .checked_add(vector_bytes)
-                        .ok_or_else(|| anyhow!("vector slab size overflow"))?;
-                }
-                VectorStorage::F32(slab)
+        let mut vectors = match quantization {
+            Quantization::F32 => VectorStorage::F32(Vec::with_capacity(entries.len() * dimension)),
+            Quantization::F16 => VectorStorage::F16(Vec::with_capacity(entries.len() * dimension)),
+            Quantization::Int8 | Quantization::Binary => {
+                VectorStorage::Bytes(Vec::with_capacity(entries.len() * vector_bytes))
             }
-            Quantization::F16 => {
-                let mut slab = Vec::with_capacity(entries.len() * dimension);
-                for entry in &entries {
-                    if entry.vector.len() != dimension {
-                        bail!(
-                            "vector dimension mismatch: expected {}, got {}",
-                            dimension,
-                            entry.vector.len()
-                        );
-                    }
-                    let vec_offset = u64::try_from(offset_bytes)
-                        .map_err(|_| anyhow!("vector offset out of range"))?;
-                    rows.push(VectorRow {
-                        message_id: entry.message_id,
-                        created_at_ms: entry.created_at_ms,
-                        agent_id: entry.agent_id,
-                        workspace_id: entry.workspace_id,
-                        source_id: entry.source_id,
-                        role: entry.role,
-                        chunk_idx: entry.chunk_idx,
-                        vec_offset,
-                        content_hash: entry.content_hash,
-                    });
-                    slab.extend(entry.vector.iter().map(|v| f16::from_f32(*v)));
-                    offset_bytes = offset_bytes
-                        .checked_add(vector_bytes)
-                        .ok_or_else(|| anyhow!("vector slab size overflow"))?;
+        };
+        for entry in &entries {
+            if entry.vector.len() != dimension {
+                bail!(
+                    "vector dimension mismatch: expected {}, got {}",
+                    dimension,
+                    entry.vector.len()
+                );
+            }
+            let vec_offset =
+                u64::try_from(offset_bytes).map_err(|_| anyhow!("vector offset out of range"))?;
+            rows.push(VectorRow {
+                message_id: entry.message_id,
+                created_at_ms: entry.created_at_ms,
+                agent_id: entry.agent_id,
+                workspace_id: entry.workspace_id,
+                source_id: entry.source_id,
+                role: entry.role,
+                chunk_idx: entry.chunk_idx,
+                vec_offset,
+                content_hash: entry.content_hash,
+            });
+            match &mut vectors {
+                VectorStorage::F32(slab) => slab.extend(entry.vector.iter().copied()),
+                VectorStorage::F16(slab) => {
+                    slab.extend(entry.vector.iter().map(|v| f16::from_f32(*v)))
+                }
+                VectorStorage::Bytes(slab) if quantization == Quantization::Int8 => {
+                    encode_int8(&entry.vector, slab)
+                }
+                VectorStorage::Bytes(slab) => encode_binary(&entry.vector, slab),
+                VectorStorage::PreconvertedF32(_) | VectorStorage::Mmap { .. } => {
+                    unreachable!("build only creates owned slabs")
                 }
-                VectorStorage::F16(slab)
             }
-        };
+            offset_bytes = offset_bytes
+                .checked_add(vector_bytes)
+                .ok_or_else(|| anyhow!("vector slab size overflow"))?;
+        }
 
         let header = CvviHeader::new(
             embedder_id,
@@ -655,12 +968,167 @@ impl VectorIndex {
             header,
             rows,
             vectors,
+            segments: Vec::new(),
         };
         index.validate()?;
         Ok(index)
     }
 
     pub fn load(path: &Path) -> Result<Self> {
+        // P0 Opt 1: Pre-convert F16→F32 at load time to eliminate per-query conversion.
+        // Env var CASS_F16_PRECONVERT=0 disables this (keeps mmap + lazy conversion).
+        let f16_preconvert_enabled = dotenvy::var("CASS_F16_PRECONVERT")
+            .map(|v| v != "0" && v.to_lowercase() != "false")
+            .unwrap_or(true);
+        Self::load_inner(path, f16_preconvert_enabled)
+    }
+
+    /// Load keeping the vector slab memory-mapped (no F16 pre-conversion).
+    ///
+    /// Used by the indexer, which reads a few vectors for reuse and merging
+    /// rather than scoring every row.
+    pub fn load_mapped(path: &Path) -> Result<Self> {
+        Self::load_inner(path, false)
+    }
+
+    /// Load the base index at `path` together with any append segments next to it.
+    ///
+    /// Segment vectors stay in their own slabs and are scored in place; a segment row
+    /// replaces the row with the same (message_id, chunk_idx) from the base or an
+    /// earlier segment, keeping its position. Segments written with another
+    /// quantization than the base are re-encoded through [`VectorIndex::merge`].
+    ///
+    /// Segments that disappear while loading (compacted concurrently) are skipped;
+    /// their rows are already in the rewritten base.
+    pub fn load_with_segments(path: &Path) -> Result<Self> {
+        let base = Self::load(path)?;
+        let segments = vector_segment_paths(path);
+        if segments.is_empty() {
+            return Ok(base);
+        }
+        let mut parts = vec![base];
+        for segment in segments {
+            match Self::load(&segment) {
+                Ok(index) => parts.push(index),
+                Err(err) if !segment.exists() => {
+                    tracing::debug!(
+                        path = %segment.display(),
+                        error = %err,
+                        "vector segment vanished"
+                    );
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        let base_header = parts[0].header();
+        if parts.iter().any(|part| {
+            let header = part.header();
+            header.embedder_id != base_header.embedder_id
+                || header.dimension != base_header.dimension
+                || header.quantization != base_header.quantization
+        }) {
+            return Self::merge(&parts);
+        }
+
+        let mut parts = parts.into_iter();
+        let mut index = parts.next().expect("base part");
+        let mut slots: HashMap<(u64, u8), usize> = index
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(row_idx, row)| ((row.message_id, row.chunk_idx), row_idx))
+            .collect();
+        let mut next_start = index.vectors.len_bytes(index.header.quantization)? as u64;
+        for segment in parts {
+            let start = next_start;
+            next_start += segment.vectors.len_bytes(segment.header.quantization)? as u64;
+            for row in &segment.rows {
+                let mut row = row.clone();
+                row.vec_offset += start;
+                match slots.get(&(row.message_id, row.chunk_idx)) {
+                    Some(&row_idx) => index.rows[row_idx] = row,
+                    None => {
+                        slots.insert((row.message_id, row.chunk_idx), index.rows.len());
+                        index.rows.push(row);
+                    }
+                }
+            }
+            index.segments.push((start, segment));
+        }
+        index.header.count =
+            u32::try_from(index.rows.len()).map_err(|_| anyhow!("row count out of range"))?;
+        Ok(index)
+    }
+
+    /// Merge indexes built with the same embedder into one in-memory index.
+    ///
+    /// Rows are keyed by (message_id, chunk_idx); a row in a later part replaces the
+    /// same key from an earlier part. The first part's header decides quantization.
+    pub fn merge(parts: &[VectorIndex]) -> Result<Self> {
+        let first = parts
+            .first()
+            .ok_or_else(|| anyhow!("no vector indexes to merge"))?;
+        let header = first.header();
+        Self::build(
+            header.embedder_id.clone(),
+            header.embedder_revision.clone(),
+            header.dimension as usize,
+            header.quantization,
+            Self::merged_entries(parts)?,
+        )
+    }
+
+    /// The deduplicated rows of `parts` (see [`VectorIndex::merge`]) as entries with
+    /// their vectors decoded to f32, e.g. to re-encode them with another quantization.
+    pub fn merged_entries(parts: &[VectorIndex]) -> Result<Vec<VectorEntry>> {
+        let first = parts
+            .first()
+            .ok_or_else(|| anyhow!("no vector indexes to merge"))?;
+        let header = first.header();
+        let mut slots: HashMap<(u64, u8), (usize, usize)> = HashMap::new();
+        let mut order: Vec<(u64, u8)> = Vec::new();
+        for (part_idx, part) in parts.iter().enumerate() {
+            let part_header = part.header();
+            if part_header.embedder_id != header.embedder_id
+                || part_header.dimension != header.dimension
+            {
+                bail!(
+                    "cannot merge vector index for {} ({}d) into {} ({}d)",
+                    part_header.embedder_id,
+                    part_header.dimension,
+                    header.embedder_id,
+                    header.dimension
+                );
+            }
+            for (row_idx, row) in part.rows().iter().enumerate() {
+                let key = (row.message_id, row.chunk_idx);
+                if slots.insert(key, (part_idx, row_idx)).is_none() {
+                    order.push(key);
+                }
+            }
+        }
+
+        let mut entries = Vec::with_capacity(order.len());
+        for key in order {
+            let (part_idx, row_idx) = slots[&key];
+            let part = &parts[part_idx];
+            let row = &part.rows()[row_idx];
+            entries.push(VectorEntry {
+                message_id: row.message_id,
+                created_at_ms: row.created_at_ms,
+                agent_id: row.agent_id,
+                workspace_id: row.workspace_id,
+                source_id: row.source_id,
+                role: row.role,
+                chunk_idx: row.chunk_idx,
+                content_hash: row.content_hash,
+                vector: part.vector_at_f32(row)?,
+            });
+        }
+        Ok(entries)
+    }
+
+    fn load_inner(path: &Path, f16_preconvert_enabled: bool) -> Result<Self> {
         if cfg!(target_endian = "big") {
             bail!("CVVI load is only supported on little-endian targets");
         }
@@ -718,12 +1186,6 @@ impl VectorIndex {
             slab_size,
         )?;
 
-        // P0 Opt 1: Pre-convert F16→F32 at load time to eliminate per-query conversion.
-        // Env var CASS_F16_PRECONVERT=0 disables this (keeps mmap + lazy conversion).
-        let f16_preconvert_enabled = dotenvy::var("CASS_F16_PRECONVERT")
-            .map(|v| v != "0" && v.to_lowercase() != "false")
-            .unwrap_or(true);
-
         let vectors = if f16_preconvert_enabled && header.quantization == Quantization::F16 {
             // Pre-convert entire F16 slab to F32 for faster dot products.
             // Trade-off: 2x memory usage, but eliminates 19.2M conversions/query for 50k vectors.
@@ -750,12 +1212,14 @@ impl VectorIndex {
             header,
             rows,
             vectors,
+            segments: Vec::new(),
         };
         index.validate()?;
         Ok(index)
     }
 
     pub fn save(&self, path: &Path) -> Result<()> {
+        self.ensure_unsegmented()?;
         let parent = path
             .parent()
             .filter(|p| !p.as_os_str().is_empty())
@@ -773,6 +1237,7 @@ impl VectorIndex {
     }
 
     pub fn write_to<W: Write>(&self, mut writer: W) -> Result<()> {
+        self.ensure_unsegmented()?;
         self.validate()?;
         let header_len = self.header.header_len_bytes()?;
         let written = self.header.write_to(&mut writer)?;
@@ -814,6 +1279,10 @@ impl VectorIndex {
             return Ok(Vec::new());
         }
 
+        if self.header.quantization == Quantization::Binary {
+            return self.search_top_k_binary(query_vec, k, filter);
+        }
+
         // P1 Opt 3: Dispatch to parallel search for large indices.
         // Skip parallelism for small indices where Rayon overhead exceeds benefit.
         if *PARALLEL_SEARCH_ENABLED && self.rows.len() >= PARALLEL_THRESHOLD {
@@ -864,6 +1333,74 @@ impl VectorIndex {
         Ok(results)
     }
 
+    /// Binary indexes: shortlist rows by Hamming distance between sign codes, then
+    /// rerank the shortlist by dot product against the stored f16 vectors.
+    fn search_top_k_binary(
+        &self,
+        query_vec: &[f32],
+        k: usize,
+        filter: Option<&SemanticFilter>,
+    ) -> Result<Vec<VectorSearchResult>> {
+        let shortlist = self.binary_shortlist(
+            query_vec,
+            k.saturating_mul(BINARY_RERANK_OVERSAMPLE),
+            filter,
+        )?;
+        let mut results = Vec::with_capacity(shortlist.len());
+        for row_idx in shortlist {
+            let row = &self.rows[row_idx];
+            results.push(VectorSearchResult {
+                message_id: row.message_id,
+                chunk_idx: row.chunk_idx,
+                score: self.dot_product_at(row.vec_offset, query_vec)?,
+            });
+        }
+        results.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.message_id.cmp(&b.message_id))
+        });
+        results.truncate(k);
+        Ok(results)
+    }
+
+    /// Indexes of the `len` rows of a binary index (matching `filter`) whose sign
+    /// codes are closest to the query's by Hamming distance.
+    fn binary_shortlist(
+        &self,
+        query_vec: &[f32],
+        len: usize,
+        filter: Option<&SemanticFilter>,
+    ) -> Result<Vec<usize>> {
+        let signature = binary_signature(query_vec);
+        // Max-heap on distance: popping drops the worst candidate.
+        let mut shortlist: BinaryHeap<(u32, usize)> = BinaryHeap::with_capacity(len + 1);
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            if let Some(filter) = filter
+                && !filter.matches(row)
+            {
+                continue;
+            }
+            let distance = hamming_distance(self.vector_bytes_at(row.vec_offset)?, &signature);
+            shortlist.push((distance, row_idx));
+            if shortlist.len() > len {
+                shortlist.pop();
+            }
+        }
+        Ok(shortlist.into_iter().map(|(_, row_idx)| row_idx).collect())
+    }
+
+    /// Scorer for walking rows one at a time (the HNSW graph). Binary indexes score
+    /// by Hamming distance to the query's signs; callers rerank with [`Self::score_row`].
+    pub fn row_scorer<'a>(&'a self, query: &'a [f32]) -> RowScorer<'a> {
+        RowScorer {
+            index: self,
+            query,
+            signature: (self.header.quantization == Quantization::Binary)
+                .then(|| binary_signature(query)),
+        }
+    }
+
     /// P1 Opt 3: Parallel search using Rayon for large indices.
     /// Uses par_chunks with thread-local heaps, then merges results.
     fn search_top_k_parallel(
@@ -950,13 +1487,27 @@ impl VectorIndex {
             return Ok(Vec::new());
         }
 
+        // Binary indexes only rerank their Hamming shortlist
+        let shortlist = if self.header.quantization == Quantization::Binary {
+            Some(self.binary_shortlist(
+                query_vec,
+                k.saturating_mul(BINARY_RERANK_OVERSAMPLE),
+                filter,
+            )?)
+        } else {
+            None
+        };
+        let candidates: Box<dyn Iterator<Item = &VectorRow>> = match &shortlist {
+            Some(rows) => Box::new(rows.iter().map(|&row_idx| &self.rows[row_idx])),
+            None => Box::new(
+                self.rows
+                    .iter()
+                    .filter(|row| filter.is_none_or(|f| f.matches(row))),
+            ),
+        };
+
         let mut best_by_message: HashMap<u64, VectorSearchResult> = HashMap::new();
-        for row in &self.rows {
-            if let Some(filter) = filter
-                && !filter.matches(row)
-            {
-                continue;
-            }
+        for row in candidates {
             let score = self.dot_product_at(row.vec_offset, query_vec)?;
             best_by_message
                 .entry(row.message_id)
@@ -985,11 +1536,39 @@ impl VectorIndex {
         Ok(results)
     }
 
+    /// Dot product between `query` and the stored vector of row `row_idx`.
+    pub fn score_row(&self, row_idx: usize, query: &[f32]) -> Result<f32> {
+        let row = self
+            .rows
+            .get(row_idx)
+            .ok_or_else(|| anyhow!("vector row {row_idx} out of bounds"))?;
+        self.dot_product_at(row.vec_offset, query)
+    }
+
     pub fn vector_at_f32(&self, row: &VectorRow) -> Result<Vec<f32>> {
+        let (part, offset) = self.locate(row.vec_offset);
+        part.slab_vector_f32(offset)
+    }
+
+    /// The index whose slab holds the vector at `vec_offset` (this one or one of its
+    /// segments), and the offset within that slab.
+    fn locate(&self, vec_offset: u64) -> (&VectorIndex, u64) {
+        match self
+            .segments
+            .iter()
+            .rev()
+            .find(|(start, _)| vec_offset >= *start)
+        {
+            Some((start, segment)) => (segment, vec_offset - start),
+            None => (self, vec_offset),
+        }
+    }
+
+    fn slab_vector_f32(&self, vec_offset: u64) -> Result<Vec<f32>> {
         let dimension = self.header.dimension as usize;
         match &self.vectors {
             VectorStorage::F32(values) => {
-                let start = vector_offset_to_index(row.vec_offset, 4)?;
+                let start = vector_offset_to_index(vec_offset, 4)?;
                 let end = start
                     .checked_add(dimension)
                     .ok_or_else(|| anyhow!("vector slice overflow"))?;
@@ -999,7 +1578,7 @@ impl VectorIndex {
                 Ok(slice.to_vec())
             }
             VectorStorage::F16(values) => {
-                let start = vector_offset_to_index(row.vec_offset, 2)?;
+                let start = vector_offset_to_index(vec_offset, 2)?;
                 let end = start
                     .checked_add(dimension)
                     .ok_or_else(|| anyhow!("vector slice overflow"))?;
@@ -1010,7 +1589,7 @@ impl VectorIndex {
             }
             VectorStorage::PreconvertedF32(values) => {
                 // P0 Opt 1: Pre-converted from F16, vec_offset is in F16 byte terms.
-                let start = vector_offset_to_index(row.vec_offset, 2)?;
+                let start = vector_offset_to_index(vec_offset, 2)?;
                 let end = start
                     .checked_add(dimension)
                     .ok_or_else(|| anyhow!("vector slice overflow"))?;
@@ -1019,37 +1598,49 @@ impl VectorIndex {
                     .ok_or_else(|| anyhow!("vector slice out of bounds"))?;
                 Ok(slice.to_vec())
             }
-            VectorStorage::Mmap { mmap, offset, .. } => {
-                let bytes_per = self.header.quantization.bytes_per_component();
-                let base = offset
-                    .checked_add(
-                        usize::try_from(row.vec_offset)
-                            .map_err(|_| anyhow!("vector offset out of range"))?,
-                    )
-                    .ok_or_else(|| anyhow!("vector slice overflow"))?;
-                let byte_len = dimension
-                    .checked_mul(bytes_per)
-                    .ok_or_else(|| anyhow!("vector slice overflow"))?;
-                let end = base
-                    .checked_add(byte_len)
-                    .ok_or_else(|| anyhow!("vector slice overflow"))?;
-                let bytes = mmap
-                    .get(base..end)
-                    .ok_or_else(|| anyhow!("vector slice out of bounds"))?;
-                match self.header.quantization {
-                    Quantization::F32 => {
-                        let slice = bytes_as_f32(bytes)?;
-                        Ok(slice.to_vec())
-                    }
-                    Quantization::F16 => {
-                        let slice = bytes_as_f16(bytes)?;
-                        Ok(slice.iter().map(|v| f32::from(*v)).collect())
-                    }
-                }
+            VectorStorage::Bytes(_) | VectorStorage::Mmap { .. } => {
+                let bytes = self.slab_vector_bytes(vec_offset)?;
+                decode_vector(self.header.quantization, bytes, dimension)
             }
         }
     }
 
+    /// Raw slab bytes of the vector at `vec_offset` for byte-addressed storage.
+    fn vector_bytes_at(&self, vec_offset: u64) -> Result<&[u8]> {
+        let (part, offset) = self.locate(vec_offset);
+        part.slab_vector_bytes(offset)
+    }
+
+    fn slab_vector_bytes(&self, vec_offset: u64) -> Result<&[u8]> {
+        let slab = match &self.vectors {
+            VectorStorage::Bytes(bytes) => bytes.as_slice(),
+            VectorStorage::Mmap { mmap, offset, len } => mmap
+                .get(*offset..offset + len)
+                .ok_or_else(|| anyhow!("vector slab out of bounds"))?,
+            _ => bail!("vector storage is not byte-addressed"),
+        };
+        let start =
+            usize::try_from(vec_offset).map_err(|_| anyhow!("vector offset out of range"))?;
+        let byte_len = self
+            .header
+            .quantization
+            .vector_bytes(self.header.dimension as usize)
+            .ok_or_else(|| anyhow!("vector slice overflow"))?;
+        let end = start
+            .checked_add(byte_len)
+            .ok_or_else(|| anyhow!("vector slice overflow"))?;
+        slab.get(start..end)
+            .ok_or_else(|| anyhow!("vector slice out of bounds"))
+    }
+
+    /// Indexes loaded with their segments span several slabs and cannot be written.
+    fn ensure_unsegmented(&self) -> Result<()> {
+        if !self.segments.is_empty() {
+            bail!("cannot write a vector index loaded with its segments; merge it first");
+        }
+        Ok(())
+    }
+
     pub fn header(&self) -> &CvviHeader {
         &self.header
     }
@@ -1110,6 +1701,9 @@ impl VectorIndex {
                 let bytes = f16_as_bytes(&f16_slab);
                 writer.write_all(bytes)?;
             }
+            VectorStorage::Bytes(bytes) => {
+                writer.write_all(bytes)?;
+            }
             VectorStorage::Mmap { mmap, offset, len } => {
                 let bytes = mmap
                     .get(*offset..offset + len)
@@ -1121,6 +1715,11 @@ impl VectorIndex {
     }
 
     fn dot_product_at(&self, vec_offset: u64, query: &[f32]) -> Result<f32> {
+        let (part, offset) = self.locate(vec_offset);
+        part.slab_dot_product(offset, query)
+    }
+
+    fn slab_dot_product(&self, vec_offset: u64, query: &[f32]) -> Result<f32> {
         match &self.vectors {
             VectorStorage::F32(values) => {
                 let start = vector_offset_to_index(vec_offset, 4)?;
@@ -1154,37 +1753,9 @@ impl VectorIndex {
                     .ok_or_else(|| anyhow!("vector slice out of bounds"))?;
                 Ok(dot_product(slice, query))
             }
-            VectorStorage::Mmap { mmap, offset, len } => {
-                let bytes_per = self.header.quantization.bytes_per_component();
-                let base = offset
-                    .checked_add(
-                        usize::try_from(vec_offset)
-                            .map_err(|_| anyhow!("vector offset out of range"))?,
-                    )
-                    .ok_or_else(|| anyhow!("vector slice overflow"))?;
-                let byte_len = query
-                    .len()
-                    .checked_mul(bytes_per)
-                    .ok_or_else(|| anyhow!("vector slice overflow"))?;
-                let end = base
-                    .checked_add(byte_len)
-                    .ok_or_else(|| anyhow!("vector slice overflow"))?;
-                let bytes = mmap
-                    .get(base..end)
-                    .ok_or_else(|| anyhow!("vector slice out of bounds"))?;
-                if base + byte_len > offset + len {
-                    bail!("vector slice out of bounds");
-                }
-                match self.header.quantization {
-                    Quantization::F32 => {
-                        let slice = bytes_as_f32(bytes)?;
-                        Ok(dot_product(slice, query))
-                    }
-                    Quantization::F16 => {
-                        let slice = bytes_as_f16(bytes)?;
-                        Ok(dot_product_f16(slice, query))
-                    }
-                }
+            VectorStorage::Bytes(_) | VectorStorage::Mmap { .. } => {
+                let bytes = self.slab_vector_bytes(vec_offset)?;
+                score_vector(self.header.quantization, bytes, query)
             }
         }
     }
@@ -1209,11 +1780,11 @@ pub fn vector_slab_size_bytes(
     dimension: u32,
     quantization: Quantization,
 ) -> Result<usize> {
-    let components = (count as usize)
-        .checked_mul(dimension as usize)
+    let vector_bytes = quantization
+        .vector_bytes(dimension as usize)
         .ok_or_else(|| anyhow!("vector slab size overflow"))?;
-    components
-        .checked_mul(quantization.bytes_per_component())
+    (count as usize)
+        .checked_mul(vector_bytes)
         .ok_or_else(|| anyhow!("vector slab size overflow"))
 }
 
@@ -1312,6 +1883,12 @@ impl VectorStorage {
                     .checked_mul(2) // Each F32 element represents one F16 value
                     .ok_or_else(|| anyhow!("vector slab size overflow"))
             }
+            VectorStorage::Bytes(bytes) => {
+                if !matches!(quantization, Quantization::Int8 | Quantization::Binary) {
+                    bail!("vector storage quantization mismatch (expected int8 or binary)");
+                }
+                Ok(bytes.len())
+            }
             VectorStorage::Mmap { len, .. } => Ok(*len),
         }
     }
@@ -1323,14 +1900,14 @@ fn validate_row_offsets(
     quantization: Quantization,
     slab_size: usize,
 ) -> Result<()> {
-    let bytes_per = quantization.bytes_per_component();
-    let vector_bytes = dimension
-        .checked_mul(bytes_per)
+    let align = quantization.offset_align();
+    let vector_bytes = quantization
+        .vector_bytes(dimension)
         .ok_or_else(|| anyhow!("vector size overflow"))?;
     for (idx, row) in rows.iter().enumerate() {
         let offset = usize::try_from(row.vec_offset)
             .map_err(|_| anyhow!("row {idx} vector offset out of range"))?;
-        if offset % bytes_per != 0 {
+        if offset % align != 0 {
             bail!("row {idx} vector offset not aligned");
         }
         let end = offset
@@ -1534,6 +2111,213 @@ fn dot_product_f16(a: &[f16], b: &[f32]) -> f32 {
     }
 }
 
+/// Append an int8 record: the f32 scale, then one code per component.
+///
+/// The scale maps the largest magnitude to 127 so each vector keeps its own range.
+fn encode_int8(vector: &[f32], out: &mut Vec<u8>) {
+    let max_abs = vector.iter().fold(0.0f32, |acc, v| acc.max(v.abs()));
+    let scale = if max_abs > 0.0 { max_abs / 127.0 } else { 0.0 };
+    out.extend_from_slice(&scale.to_le_bytes());
+    out.extend(vector.iter().map(|v| {
+        let code = if scale > 0.0 {
+            (v / scale).round().clamp(-127.0, 127.0) as i8
+        } else {
+            0
+        };
+        code as u8
+    }));
+}
+
+/// Scalar sign packing (fallback when SIMD is disabled).
+fn binary_signature_scalar(vector: &[f32]) -> Vec<u64> {
+    let mut words = vec![0u64; vector.len().div_ceil(64)];
+    for (idx, value) in vector.iter().enumerate() {
+        if *value >= 0.0 {
+            words[idx / 64] |= 1 << (idx % 64);
+        }
+    }
+    words
+}
+
+/// SIMD sign packing using wide crate: one sign mask per 8 components. Adding
+/// zero turns -0.0 into +0.0, so only negative components set the mask bit.
+fn binary_signature_simd(vector: &[f32]) -> Vec<u64> {
+    use wide::f32x8;
+
+    let mut words = vec![0u64; vector.len().div_ceil(64)];
+    let chunks = vector.chunks_exact(8);
+    let remainder = chunks.remainder();
+    for (chunk_idx, chunk) in chunks.enumerate() {
+        // SAFETY: chunks_exact guarantees exactly 8 elements.
+        let arr: [f32; 8] = chunk.try_into().unwrap();
+        let negative = (f32x8::from(arr) + f32x8::ZERO).to_bitmask();
+        let signs = u64::from(!negative & 0xFF);
+        words[chunk_idx / 8] |= signs << ((chunk_idx % 8) * 8);
+    }
+    let base = vector.len() - remainder.len();
+    for (offset, value) in remainder.iter().enumerate() {
+        if *value >= 0.0 {
+            let idx = base + offset;
+            words[idx / 64] |= 1 << (idx % 64);
+        }
+    }
+    words
+}
+
+/// Pack the sign of each component into u64 words (bit set = non-negative).
+/// Dispatches to SIMD or scalar packing based on CASS_SIMD_DOT env var.
+fn binary_signature(vector: &[f32]) -> Vec<u64> {
+    if *SIMD_DOT_ENABLED {
+        binary_signature_simd(vector)
+    } else {
+        binary_signature_scalar(vector)
+    }
+}
+
+/// Append a binary record: packed sign bits, then the f16 rerank vector.
+fn encode_binary(vector: &[f32], out: &mut Vec<u8>) {
+    for word in binary_signature(vector) {
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+    for value in vector {
+        out.extend_from_slice(&f16::from_f32(*value).to_le_bytes());
+    }
+}
+
+/// Decode one stored vector back to f32 (int8 is dequantized, binary uses its f16 copy).
+fn decode_vector(quantization: Quantization, bytes: &[u8], dimension: usize) -> Result<Vec<f32>> {
+    match quantization {
+        Quantization::F32 => Ok(bytes_as_f32(bytes)?.to_vec()),
+        Quantization::F16 => Ok(bytes_as_f16(bytes)?.iter().map(|v| f32::from(*v)).collect()),
+        Quantization::Int8 => {
+            let (scale, codes) = split_int8(bytes)?;
+            Ok(codes.iter().map(|c| f32::from(*c) * scale).collect())
+        }
+        Quantization::Binary => {
+            let rerank = bytes_as_f16(&bytes[binary_code_bytes(dimension)..])?;
+            Ok(rerank.iter().map(|v| f32::from(*v)).collect())
+        }
+    }
+}
+
+/// Dot product between `query` and one stored vector in its on-disk encoding.
+fn score_vector(quantization: Quantization, bytes: &[u8], query: &[f32]) -> Result<f32> {
+    match quantization {
+        Quantization::F32 => Ok(dot_product(bytes_as_f32(bytes)?, query)),
+        Quantization::F16 => Ok(dot_product_f16(bytes_as_f16(bytes)?, query)),
+        Quantization::Int8 => {
+            let (scale, codes) = split_int8(bytes)?;
+            Ok(dot_product_i8(codes, query) * scale)
+        }
+        Quantization::Binary => {
+            let rerank = bytes_as_f16(&bytes[binary_code_bytes(query.len())..])?;
+            Ok(dot_product_f16(rerank, query))
+        }
+    }
+}
+
+fn split_int8(bytes: &[u8]) -> Result<(f32, &[i8])> {
+    let (scale, codes) = bytes
+        .split_first_chunk::<INT8_SCALE_BYTES>()
+        .ok_or_else(|| anyhow!("int8 vector record is truncated"))?;
+    // SAFETY: i8 and u8 have identical size and alignment.
+    let codes = unsafe { std::slice::from_raw_parts(codes.as_ptr() as *const i8, codes.len()) };
+    Ok((f32::from_le_bytes(*scale), codes))
+}
+
+/// Scalar int8 dot product (fallback when SIMD is disabled).
+#[inline]
+fn dot_product_i8_scalar(a: &[i8], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| f32::from(*x) * y).sum()
+}
+
+/// SIMD int8 dot product using wide crate, mirroring the f16 kernel: widen 8 codes
+/// to f32 per iteration and accumulate with 8-wide multiplies. The caller applies
+/// the per-vector scale once to the sum.
+#[inline]
+fn dot_product_i8_simd(a: &[i8], b: &[f32]) -> f32 {
+    use wide::f32x8;
+
+    let chunks_a = a.chunks_exact(8);
+    let chunks_b = b.chunks_exact(8);
+    let remainder_a = chunks_a.remainder();
+    let remainder_b = chunks_b.remainder();
+
+    let mut sum = f32x8::ZERO;
+    for (ca, cb) in chunks_a.zip(chunks_b) {
+        let a_f32: [f32; 8] = std::array::from_fn(|i| f32::from(ca[i]));
+        // SAFETY: chunks_exact guarantees exactly 8 elements.
+        let b_f32: [f32; 8] = cb.try_into().unwrap();
+        sum += f32x8::from(a_f32) * f32x8::from(b_f32);
+    }
+
+    let mut scalar_sum = sum.reduce_add();
+    for (a, b) in remainder_a.iter().zip(remainder_b) {
+        scalar_sum += f32::from(*a) * b;
+    }
+    scalar_sum
+}
+
+/// Dispatches to SIMD or scalar int8 dot product based on CASS_SIMD_DOT env var.
+#[inline]
+fn dot_product_i8(a: &[i8], b: &[f32]) -> f32 {
+    if *SIMD_DOT_ENABLED {
+        dot_product_i8_simd(a, b)
+    } else {
+        dot_product_i8_scalar(a, b)
+    }
+}
+
+/// Scalar Hamming distance (fallback when SIMD is disabled), one popcount per u64 word.
+#[inline]
+fn hamming_distance_scalar(code: &[u8], signature: &[u64]) -> u32 {
+    code.chunks_exact(8)
+        .zip(signature)
+        .map(|(chunk, word)| {
+            // SAFETY: chunks_exact guarantees exactly 8 bytes.
+            let stored = u64::from_le_bytes(chunk.try_into().unwrap());
+            (stored ^ word).count_ones()
+        })
+        .sum()
+}
+
+/// SIMD Hamming distance using wide crate: XOR four u64 words per iteration and
+/// popcount the lanes.
+#[inline]
+fn hamming_distance_simd(code: &[u8], signature: &[u64]) -> u32 {
+    use wide::u64x4;
+
+    let code = &code[..signature.len() * 8];
+    let chunks_code = code.chunks_exact(32);
+    let chunks_sig = signature.chunks_exact(4);
+    let remainder_code = chunks_code.remainder();
+    let remainder_sig = chunks_sig.remainder();
+
+    let mut distance = 0;
+    for (cc, cs) in chunks_code.zip(chunks_sig) {
+        let stored: [u64; 4] = std::array::from_fn(|i| {
+            // SAFETY: chunks_exact guarantees exactly 32 bytes.
+            u64::from_le_bytes(cc[i * 8..i * 8 + 8].try_into().unwrap())
+        });
+        // SAFETY: chunks_exact guarantees exactly 4 words.
+        let sig: [u64; 4] = cs.try_into().unwrap();
+        let diff = u64x4::from(stored) ^ u64x4::from(sig);
+        distance += diff.to_array().iter().map(|w| w.count_ones()).sum::<u32>();
+    }
+    distance + hamming_distance_scalar(remainder_code, remainder_sig)
+}
+
+/// Hamming distance between the packed sign code at the start of a binary record
+/// and a query signature. Dispatches to SIMD or scalar based on CASS_SIMD_DOT env var.
+#[inline]
+fn hamming_distance(code: &[u8], signature: &[u64]) -> u32 {
+    if *SIMD_DOT_ENABLED {
+        hamming_distance_simd(code, signature)
+    } else {
+        hamming_distance_scalar(code, signature)
+    }
+}
+
 fn sync_dir(path: &Path) -> Result<()> {
     let dir = File::open(path)?;
     dir.sync_all()?;
@@ -1666,6 +2450,86 @@ mod tests {
         assert_sync::<VectorIndex>();
     }
 
+    #[test]
+    fn segments_are_searched_in_place_and_compact_into_base() -> Result<()> {
+        let dir = tempdir()?;
+        let base = dir.path().join("index-test.cvvi");
+        let entries = sample_entries();
+        VectorIndex::build("test", "rev", 3, Quantization::F32, entries[..2].to_vec())?
+            .save(&base)?;
+
+        let mut updated = entries[1].clone();
+        updated.vector = vec![0.5, 0.5, 0.0];
+        let segment = VectorIndex::build(
+            "test",
+            "rev",
+            3,
+            Quantization::F32,
+            vec![updated, entries[2].clone()],
+        )?;
+        let seg_path = save_vector_segment(&segment, &base)?;
+        assert!(
+            seg_path
+                .to_string_lossy()
+                .ends_with("index-test.seg-000001.cvvi")
+        );
+        assert_eq!(vector_segment_paths(&base), vec![seg_path.clone()]);
+        assert!(segments_need_compaction(&base));
+
+        let merged = VectorIndex::load_with_segments(&base)?;
+        assert_eq!(merged.rows().len(), 3);
+        let row = merged
+            .rows()
+            .iter()
+            .find(|r| r.message_id == 2)
+            .expect("message 2");
+        assert_eq!(merged.vector_at_f32(row)?, vec![0.5, 0.5, 0.0]);
+        let top = merged.search_top_k(&[0.0, 0.0, 1.0], 1, None)?;
+        assert_eq!(top[0].message_id, 3);
+        assert!((top[0].score - 1.0).abs() < 1e-6);
+        assert!(merged.save(&dir.path().join("copy.cvvi")).is_err());
+
+        assert_eq!(compact_vector_segments(&base)?, 1);
+        assert!(vector_segment_paths(&base).is_empty());
+        assert!(!segments_need_compaction(&base));
+        let compacted = VectorIndex::load(&base)?;
+        assert_eq!(compacted.rows().len(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn remove_vector_messages_rewrites_base_and_segments() -> Result<()> {
+        let dir = tempdir()?;
+        std::fs::create_dir_all(dir.path().join(VECTOR_INDEX_DIR))?;
+        let base = vector_index_path(dir.path(), "test");
+        let entries = sample_entries();
+        VectorIndex::build("test", "rev", 3, Quantization::Int8, entries[..2].to_vec())?
+            .save(&base)?;
+        let segment =
+            VectorIndex::build("test", "rev", 3, Quantization::Int8, entries[2..].to_vec())?;
+        save_vector_segment(&segment, &base)?;
+        assert_eq!(vector_index_bases(dir.path()), vec![base.clone()]);
+
+        assert_eq!(remove_vector_messages(&base, &HashSet::from([9]))?, 0);
+        assert_eq!(vector_segment_paths(&base).len(), 1);
+
+        assert_eq!(remove_vector_messages(&base, &HashSet::from([1, 3]))?, 2);
+        assert!(vector_segment_paths(&base).is_empty());
+        let index = VectorIndex::load(&base)?;
+        assert_eq!(index.header().quantization, Quantization::Int8);
+        let ids: Vec<u64> = index.rows().iter().map(|r| r.message_id).collect();
+        assert_eq!(ids, vec![2]);
+        Ok(())
+    }
+
+    #[test]
+    fn merge_rejects_mismatched_embedders() -> Result<()> {
+        let a = VectorIndex::build("a", "rev", 3, Quantization::F32, sample_entries())?;
+        let b = VectorIndex::build("b", "rev", 3, Quantization::F32, sample_entries())?;
+        assert!(VectorIndex::merge(&[a, b]).is_err());
+        Ok(())
+    }
+
     #[test]
     fn header_roundtrip_and_crc() -> Result<()> {
         let header = CvviHeader::new("minilm-384", "e4ce9877", 384, Quantization::F16, 42)?;
@@ -1753,6 +2617,14 @@ mod tests {
         let results = index.search_top_k(&[0.0, 0.0, 1.0], 5, Some(&filter))?;
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].message_id, 3);
+
+        let filter = SemanticFilter {
+            message_ids: Some(HashSet::from([1])),
+            ..Default::default()
+        };
+        let results = index.search_top_k(&[0.0, 0.0, 1.0], 5, Some(&filter))?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message_id, 1);
         Ok(())
     }
 
@@ -1770,6 +2642,179 @@ mod tests {
         Ok(())
     }
 
+    fn random_entries(count: usize, dimension: usize, seed: u32) -> Vec<VectorEntry> {
+        let mut rng = TinyRng::new(seed);
+        (0..count)
+            .map(|i| VectorEntry {
+                message_id: i as u64,
+                created_at_ms: i as i64,
+                agent_id: 1,
+                workspace_id: 1,
+                source_id: 1,
+                role: 0,
+                chunk_idx: 0,
+                content_hash: [i as u8; 32],
+                vector: (0..dimension).map(|_| rng.next_f32()).collect(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn int8_and_binary_roundtrip_save_load() -> Result<()> {
+        // 45 components: exercises the SIMD remainder and a partial sign word.
+        let entries = random_entries(20, 45, 7);
+        let dir = tempdir()?;
+        for quantization in [Quantization::Int8, Quantization::Binary] {
+            let index = VectorIndex::build("test", "rev", 45, quantization, entries.clone())?;
+            let path = dir.path().join(format!("{}.cvvi", quantization.as_str()));
+            index.save(&path)?;
+
+            let loaded = VectorIndex::load(&path)?;
+            assert_eq!(loaded.header().quantization, quantization);
+            assert_eq!(loaded.rows().len(), entries.len());
+            for (row, entry) in loaded.rows().iter().zip(&entries) {
+                let decoded = loaded.vector_at_f32(row)?;
+                for (got, want) in decoded.iter().zip(&entry.vector) {
+                    assert!(
+                        (got - want).abs() < 0.01,
+                        "{quantization:?}: {got} vs {want}"
+                    );
+                }
+            }
+            let query = &entries[3].vector;
+            assert_eq!(
+                loaded.search_top_k(query, 1, None)?[0].score,
+                index.search_top_k(query, 1, None)?[0].score
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn quantized_slab_sizes() -> Result<()> {
+        let slab = |q| vector_slab_size_bytes(1000, 384, q);
+        assert_eq!(slab(Quantization::F16)?, 1000 * 384 * 2);
+        assert_eq!(slab(Quantization::Int8)?, 1000 * (4 + 384));
+        assert_eq!(slab(Quantization::Binary)?, 1000 * (48 + 384 * 2));
+        Ok(())
+    }
+
+    #[test]
+    fn quantized_rankings_track_f32() -> Result<()> {
+        let entries = random_entries(300, 64, 42);
+        let exact = VectorIndex::build("test", "rev", 64, Quantization::F32, entries.clone())?;
+        let int8 = VectorIndex::build("test", "rev", 64, Quantization::Int8, entries.clone())?;
+        let binary = VectorIndex::build("test", "rev", 64, Quantization::Binary, entries.clone())?;
+        let mut rng = TinyRng::new(9);
+        for target in [0usize, 17, 150, 299] {
+            let query: Vec<f32> = entries[target]
+                .vector
+                .iter()
+                .map(|v| v + 0.1 * rng.next_f32())
+                .collect();
+            let ids = |index: &VectorIndex| -> Result<Vec<u64>> {
+                Ok(index
+                    .search_top_k(&query, 10, None)?
+                    .iter()
+                    .map(|r| r.message_id)
+                    .collect())
+            };
+            let want = ids(&exact)?;
+            let got_int8 = ids(&int8)?;
+            let got_binary = ids(&binary)?;
+            assert_eq!(got_int8[0], target as u64);
+            assert_eq!(got_binary[0], target as u64);
+            let overlap = |got: &[u64]| got.iter().filter(|id| want.contains(id)).count();
+            assert!(
+                overlap(&got_int8) >= 8,
+                "int8 top-10 {got_int8:?} vs {want:?}"
+            );
+            assert!(
+                overlap(&got_binary) >= 6,
+                "binary top-10 {got_binary:?} vs {want:?}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn binary_search_respects_filter() -> Result<()> {
+        let index = VectorIndex::build("hash-3", "rev", 3, Quantization::Binary, sample_entries())?;
+        let filter = SemanticFilter {
+            agents: Some(HashSet::from([2])),
+            ..Default::default()
+        };
+        let results = index.search_top_k(&[1.0, 0.0, 0.0], 3, Some(&filter))?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].message_id, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn int8_simd_dot_product_matches_scalar() {
+        let mut rng = TinyRng::new(3);
+        for len in [0, 1, 7, 8, 9, 384, 385] {
+            let codes: Vec<i8> = (0..len).map(|_| (rng.next_u32() % 255) as i8).collect();
+            let query: Vec<f32> = (0..len).map(|_| rng.next_f32()).collect();
+            let scalar = dot_product_i8_scalar(&codes, &query);
+            let simd = dot_product_i8_simd(&codes, &query);
+            assert!(
+                (scalar - simd).abs() <= 1e-3 * scalar.abs().max(1.0),
+                "len {len}: scalar {scalar} vs simd {simd}"
+            );
+        }
+    }
+
+    #[test]
+    fn hamming_distance_counts_sign_flips() {
+        let a = [0.5, -0.5, 0.25, -1.0, 0.0];
+        let b = [0.5, 0.5, -0.25, -1.0, 0.0];
+        let mut code = Vec::new();
+        encode_binary(&a, &mut code);
+        assert_eq!(code.len(), 8 + 5 * 2);
+        assert_eq!(hamming_distance(&code, &binary_signature(&a)), 0);
+        assert_eq!(hamming_distance(&code, &binary_signature(&b)), 2);
+    }
+
+    #[test]
+    fn binary_simd_kernels_match_scalar() {
+        let entries = random_entries(2, 300, 7);
+        let (a, mut b) = (entries[0].vector.clone(), entries[1].vector.clone());
+        b[0] = -0.0;
+        b[1] = 0.0;
+        for vector in [&a, &b] {
+            assert_eq!(
+                binary_signature_simd(vector),
+                binary_signature_scalar(vector)
+            );
+        }
+        let mut code = Vec::new();
+        encode_binary(&a, &mut code);
+        let signature = binary_signature_scalar(&b);
+        assert_eq!(
+            hamming_distance_simd(&code, &signature),
+            hamming_distance_scalar(&code, &signature)
+        );
+    }
+
+    #[test]
+    fn binary_collapsed_search_reranks_the_hamming_shortlist() -> Result<()> {
+        let entries = random_entries(300, 64, 42);
+        let binary = VectorIndex::build("test", "rev", 64, Quantization::Binary, entries.clone())?;
+        let query = &entries[150].vector;
+        let plain = binary.search_top_k(query, 5, None)?;
+        let collapsed = binary.search_top_k_collapsed(query, 5, None)?;
+        assert_eq!(collapsed[0].message_id, 150);
+        assert_eq!(
+            plain.iter().map(|r| r.message_id).collect::<Vec<_>>(),
+            collapsed.iter().map(|r| r.message_id).collect::<Vec<_>>()
+        );
+        let scorer = binary.row_scorer(query);
+        assert!(scorer.needs_rerank());
+        assert_eq!(scorer.score(150)?, 1.0);
+        Ok(())
+    }
+
     #[test]
     fn semantic_filter_from_search_filters_maps_ids() -> Result<()> {
         let conn = Connection::open_in_memory()?;
@@ -0,0 +1,807 @@
+//! Local HTTP/JSON API (`cass serve`).
+//!
+//! Routes (all `GET`):
+//! - `/search`, `/expand`, `/timeline`, `/stats`: query parameters are the
+//!   command's flags (`/search?query=retry&limit=5&fields=minimal`); the body
+//!   is exactly what `cass <command> --json` prints, cursors, field masks and
+//!   `_meta` (`robot_meta=true`) included
+//! - `/sessions`: indexed sessions, newest first (`limit`, `offset`)
+//! - `/sessions/{id}`: one session with its messages
+//! - `/health`: the `cass health --json` payload; 503 while unhealthy
+//!
+//! Requests go through the same dispatcher as `cass mcp`, so the Tantivy
+//! reader and result cache stay warm between calls and the reader reloads when
+//! the index is updated. Each connection is read on its own thread, so a slow
+//! or idle client does not hold up others; commands take turns on the shared
+//! dispatcher. Each request is answered and the connection closed. With a token configured, every request
+//! needs `Authorization: Bearer <token>`.
+//!
+//! On TCP, requests must name the server by a loopback host or its bound
+//! address in `Host`, so a web page that rebinds its own DNS name to 127.0.0.1
+//! cannot read the API. `/expand` only reads session files that are indexed.
+
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::{Map, Value, json};
+use tracing::{debug, info, warn};
+
+use crate::mcp::{
+    McpServer, error_payload, exposed_args, property_name, session_json, session_summary,
+};
+use crate::storage::sqlite::SqliteStorage;
+use crate::{CliError, CliResult};
+
+/// Commands served at `/<name>`
+pub const COMMAND_ROUTES: &[&str] = &["search", "expand", "timeline", "stats"];
+
+/// Environment variable read when `--token` is not given
+pub const TOKEN_ENV: &str = "CASS_SERVE_TOKEN";
+
+/// Upper bound on the request line plus headers
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// A client that stalls longer than this is dropped, so idle connections do not pile up.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default and maximum page size for `/sessions`
+const SESSION_PAGE_SIZE: i64 = 100;
+const MAX_SESSION_PAGE_SIZE: i64 = 1000;
+
+/// Staleness threshold for `/health`, as for `cass health`
+const DEFAULT_STALE_THRESHOLD_SECS: u64 = 300;
+
+/// `Host` names always accepted on TCP
+const LOOPBACK_HOSTS: &[&str] = &["localhost", "127.0.0.1", "[::1]"];
+
+/// Where `cass serve` listens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListenAddr {
+    Tcp(SocketAddr),
+    /// `unix:/path/to/socket`
+    Unix(PathBuf),
+}
+
+impl ListenAddr {
+    pub fn parse(spec: &str) -> CliResult<Self> {
+        if let Some(path) = spec.strip_prefix("unix:") {
+            if path.is_empty() {
+                return Err(CliError::usage(
+                    "--listen unix: needs a socket path",
+                    Some("Use --listen unix:/path/to/cass.sock".to_string()),
+                ));
+            }
+            return Ok(Self::Unix(PathBuf::from(path)));
+        }
+        spec.parse().map(Self::Tcp).map_err(|_| {
+            CliError::usage(
+                format!("invalid --listen address '{spec}'"),
+                Some("Use HOST:PORT (e.g. 127.0.0.1:8765) or unix:/path/to/cass.sock".to_string()),
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for ListenAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// Serve the API on `listen` until the process is stopped.
+pub fn run(
+    listen: ListenAddr,
+    token: Option<String>,
+    data_dir: PathBuf,
+    db_override: Option<PathBuf>,
+) -> CliResult<()> {
+    let bind_error = |e: io::Error| CliError {
+        code: 9,
+        kind: "bind",
+        message: format!("Cannot listen on {listen}: {e}"),
+        hint: None,
+        retryable: true,
+    };
+    let mut server = ApiServer::new(data_dir, db_override, token);
+    server.warm();
+
+    match &listen {
+        ListenAddr::Tcp(addr) => {
+            if !addr.ip().is_loopback() && server.token.is_none() {
+                warn!(%addr, "serving on a non-loopback address without a bearer token");
+            }
+            let listener = TcpListener::bind(addr).map_err(bind_error)?;
+            let local = listener.local_addr().map_err(bind_error)?;
+            server.bound = Some(local);
+            eprintln!("cass serve listening on http://{local}");
+            let server = Arc::new(server);
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let server = Arc::clone(&server);
+                        std::thread::spawn(move || server.serve_connection(stream));
+                    }
+                    Err(e) => warn!(error = %e, "serve accept failed"),
+                }
+            }
+        }
+        #[cfg(unix)]
+        ListenAddr::Unix(path) => {
+            let listener = bind_unix_socket(path).map_err(bind_error)?;
+            eprintln!("cass serve listening on unix:{}", path.display());
+            let server = Arc::new(server);
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let server = Arc::clone(&server);
+                        std::thread::spawn(move || server.serve_connection(stream));
+                    }
+                    Err(e) => warn!(error = %e, "serve accept failed"),
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        ListenAddr::Unix(_) => {
+            return Err(CliError {
+                code: 2,
+                kind: "unsupported",
+                message: "Unix sockets are not available on this platform".to_string(),
+                hint: Some("Use --listen HOST:PORT instead.".to_string()),
+                retryable: false,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// A stale socket left by a crashed server is replaced; the new one is owner-only (0600).
+#[cfg(unix)]
+fn bind_unix_socket(path: &std::path::Path) -> io::Result<std::os::unix::net::UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("a server is already listening on {}", path.display()),
+            ));
+        }
+        std::fs::remove_file(path)?;
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    Ok(listener)
+}
+
+/// A parsed `GET` request.
+#[derive(Debug, Clone, Default)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    /// Decoded query parameters, in order
+    pub query: Vec<(String, String)>,
+    pub authorization: Option<String>,
+    pub host: Option<String>,
+}
+
+impl Request {
+    /// Last value of query parameter `name` (snake_case; kebab-case also matches)
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .rev()
+            .find(|(key, _)| key.replace('-', "_") == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, err: &CliError) -> Self {
+        Self {
+            status,
+            body: error_payload(err),
+        }
+    }
+
+    /// Status for a failed command, keyed off the robot-mode exit code
+    fn from_cli_error(err: &CliError) -> Self {
+        let status = match (err.kind, err.code) {
+            ("file-not-found", _) => 404,
+            (_, 2) => 400,
+            (_, 3) => 503,
+            (_, 10) => 504,
+            _ => 500,
+        };
+        Self::error(status, err)
+    }
+}
+
+pub struct ApiServer {
+    /// Shared by the connection threads; held only while a command runs
+    dispatcher: Mutex<McpServer>,
+    data_dir: PathBuf,
+    db_override: Option<PathBuf>,
+    token: Option<String>,
+    /// TCP address being served; `None` on a Unix socket, which browsers cannot reach
+    bound: Option<SocketAddr>,
+}
+
+impl ApiServer {
+    pub fn new(data_dir: PathBuf, db_override: Option<PathBuf>, token: Option<String>) -> Self {
+        Self {
+            dispatcher: Mutex::new(McpServer::new(data_dir.clone(), db_override.clone())),
+            data_dir,
+            db_override,
+            token: token.filter(|t| !t.is_empty()),
+            bound: None,
+        }
+    }
+
+    /// Open the search client up front so the first `/search` is not a cold start.
+    fn warm(&self) {
+        match self.dispatcher().ensure_search_client() {
+            Ok(()) => info!("search client ready"),
+            Err(e) => {
+                warn!(error = %e.message, "search client not opened yet; retrying on first search")
+            }
+        }
+    }
+
+    fn dispatcher(&self) -> std::sync::MutexGuard<'_, McpServer> {
+        self.dispatcher.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn serve_connection(&self, mut stream: impl ClientStream) {
+        if let Err(e) = stream.set_timeouts(CLIENT_TIMEOUT) {
+            debug!(error = %e, "serve connection setup failed");
+            return;
+        }
+        let response = match read_request(&mut stream) {
+            Ok(Some(request)) => {
+                let started = Instant::now();
+                let response = self.handle(&request);
+                debug!(
+                    path = %request.path,
+                    status = response.status,
+                    elapsed_ms = started.elapsed().as_millis() as u64,
+                    "served request"
+                );
+                response
+            }
+            Ok(None) => return,
+            Err(e) => Response::error(400, &CliError::usage(format!("bad request: {e}"), None)),
+        };
+        if let Err(e) = write_response(&mut stream, &response) {
+            debug!(error = %e, "serve client went away");
+        }
+    }
+
+    /// Answer one request.
+    pub fn handle(&self, request: &Request) -> Response {
+        if !self.host_allowed(request.host.as_deref()) {
+            return Response::error(
+                403,
+                &CliError {
+                    code: 6,
+                    kind: "host",
+                    message: format!(
+                        "requests must be addressed to localhost, not {}",
+                        request.host.as_deref().unwrap_or("(no Host header)")
+                    ),
+                    hint: Some(
+                        "Use http://127.0.0.1:<port>/ or http://localhost:<port>/.".to_string(),
+                    ),
+                    retryable: false,
+                },
+            );
+        }
+        if let Some(token) = &self.token {
+            let presented = request
+                .authorization
+                .as_deref()
+                .and_then(|value| value.strip_prefix("Bearer "));
+            if !presented.is_some_and(|p| constant_time_eq(p.trim(), token)) {
+                return Response::error(
+                    401,
+                    &CliError {
+                        code: 6,
+                        kind: "auth",
+                        message: "missing or invalid bearer token".to_string(),
+                        hint: Some("Send 'Authorization: Bearer <token>'.".to_string()),
+                        retryable: false,
+                    },
+                );
+            }
+        }
+        if request.method != "GET" {
+            return Response::error(
+                405,
+                &CliError::usage(format!("method {} not allowed", request.method), None),
+            );
+        }
+
+        let route = request.path.trim_end_matches('/');
+        if route == "/health" {
+            return self.health(request);
+        }
+        if route == "/sessions" {
+            return self.sessions(request);
+        }
+        if let Some(id) = route.strip_prefix("/sessions/") {
+            return self.session(id);
+        }
+        match route
+            .strip_prefix('/')
+            .filter(|name| COMMAND_ROUTES.contains(name))
+        {
+            Some(name) => self.command(name, &request.query),
+            None => Response::error(
+                404,
+                &CliError {
+                    code: 2,
+                    kind: "not-found",
+                    message: format!("no route for {}", request.path),
+                    hint: Some(
+                        "Routes: /search, /sessions, /sessions/{id}, /expand, /timeline, /stats, /health"
+                            .to_string(),
+                    ),
+                    retryable: false,
+                },
+            ),
+        }
+    }
+
+    /// Whether `host` (the `Host` header) names this server.
+    ///
+    /// Loopback names and the bound address are accepted. A server bound to
+    /// every interface with a token also accepts any name, since a page that
+    /// rebinds DNS cannot know the token.
+    fn host_allowed(&self, host: Option<&str>) -> bool {
+        let Some(bound) = self.bound else {
+            return true;
+        };
+        if bound.ip().is_unspecified() && self.token.is_some() {
+            return true;
+        }
+        let Some(host) = host else {
+            return false;
+        };
+        let name = strip_port(host.trim()).to_ascii_lowercase();
+        let bound_name = match bound.ip() {
+            std::net::IpAddr::V4(ip) => ip.to_string(),
+            std::net::IpAddr::V6(ip) => format!("[{ip}]"),
+        };
+        LOOPBACK_HOSTS.contains(&name.as_str()) || name == bound_name
+    }
+
+    fn command(&self, name: &str, query: &[(String, String)]) -> Response {
+        let arguments = match self.dispatcher().tool(name) {
+            Some(schema) => query_arguments(schema, query),
+            None => return Response::error(404, &CliError::unknown("route has no command")),
+        };
+        if name == "expand"
+            && let Some(path) = arguments.get("path").and_then(Value::as_str)
+            && let Err(response) = self.ensure_indexed(path)
+        {
+            return response;
+        }
+        match self.dispatcher().run_command(name, &arguments) {
+            Ok(payload) => Response::ok(payload),
+            Err(err) => Response::from_cli_error(&err),
+        }
+    }
+
+    fn sessions(&self, request: &Request) -> Response {
+        let number = |name: &str, default: i64| match request.param(name) {
+            None => Ok(default),
+            Some(value) => value
+                .parse::<i64>()
+                .ok()
+                .filter(|n| *n >= 0)
+                .ok_or_else(|| {
+                    Response::error(
+                        400,
+                        &CliError::usage(format!("'{name}' must be a non-negative integer"), None),
+                    )
+                }),
+        };
+        let (limit, offset) = match (number("limit", SESSION_PAGE_SIZE), number("offset", 0)) {
+            (Ok(limit), Ok(offset)) => (limit.min(MAX_SESSION_PAGE_SIZE), offset),
+            (Err(response), _) | (_, Err(response)) => return response,
+        };
+        let storage = match self.open_storage() {
+            Ok(storage) => storage,
+            Err(response) => return response,
+        };
+        match storage.list_conversations(limit, offset) {
+            Ok(conversations) => {
+                let next_offset = (conversations.len() as i64 == limit).then(|| offset + limit);
+                Response::ok(json!({
+                    "sessions": conversations.iter().filter_map(session_summary).collect::<Vec<_>>(),
+                    "offset": offset,
+                    "limit": limit,
+                    "next_offset": next_offset,
+                }))
+            }
+            Err(e) => Response::from_cli_error(&CliError::unknown(format!("{e:#}"))),
+        }
+    }
+
+    fn session(&self, id: &str) -> Response {
+        let not_found = || {
+            Response::error(
+                404,
+                &CliError {
+                    code: 3,
+                    kind: "session-not-found",
+                    message: format!("no session with id {id}"),
+                    hint: Some("List session ids with GET /sessions.".to_string()),
+                    retryable: false,
+                },
+            )
+        };
+        let Ok(id) = id.parse::<i64>() else {
+            return not_found();
+        };
+        let storage = match self.open_storage() {
+            Ok(storage) => storage,
+            Err(response) => return response,
+        };
+        match session_json(&storage, id) {
+            Ok(Some(session)) => Response::ok(session),
+            Ok(None) => not_found(),
+            Err(e) => Response::from_cli_error(&CliError::unknown(format!("{e:#}"))),
+        }
+    }
+
+    /// `/expand` reads files from disk, so only session files in the index are allowed.
+    fn ensure_indexed(&self, path: &str) -> Result<(), Response> {
+        let storage = self.open_storage()?;
+        let canonical = std::fs::canonicalize(path)
+            .ok()
+            .map(|p| p.to_string_lossy().into_owned());
+        for candidate in std::iter::once(path.to_string()).chain(canonical) {
+            match storage.conversation_ids_for_path(&candidate) {
+                Ok(ids) if !ids.is_empty() => return Ok(()),
+                Ok(_) => {}
+                Err(e) => {
+                    return Err(Response::from_cli_error(&CliError::unknown(format!(
+                        "{e:#}"
+                    ))));
+                }
+            }
+        }
+        Err(Response::error(
+            403,
+            &CliError {
+                code: 6,
+                kind: "not-indexed",
+                message: format!("{path} is not an indexed session file"),
+                hint: Some("Pass a source_path from /search or /sessions results.".to_string()),
+                retryable: false,
+            },
+        ))
+    }
+
+    fn open_storage(&self) -> Result<SqliteStorage, Response> {
+        let db_path = self.db_path();
+        if !db_path.exists() {
+            return Err(Response::from_cli_error(&CliError {
+                code: 3,
+                kind: "missing-db",
+                message: format!("Database not found at {}", db_path.display()),
+                hint: Some("Run 'cass index --full' first.".to_string()),
+                retryable: true,
+            }));
+        }
+        SqliteStorage::open_readonly(&db_path)
+            .map_err(|e| Response::from_cli_error(&CliError::unknown(format!("{e:#}"))))
+    }
+
+    fn health(&self, request: &Request) -> Response {
+        let started = Instant::now();
+        let stale_threshold = request
+            .param("stale_threshold")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_STALE_THRESHOLD_SECS);
+        let state = crate::state_meta_json(&self.data_dir, &self.db_path(), stale_threshold);
+        let exists = |section: &str| state[section]["exists"].as_bool().unwrap_or(false);
+        let locked = state["vault"]["locked"].as_bool().unwrap_or(false);
+        let healthy = !locked && exists("database") && exists("index");
+        Response {
+            status: if healthy { 200 } else { 503 },
+            body: json!({
+                "healthy": healthy,
+                "latency_ms": started.elapsed().as_millis() as u64,
+                "state": state,
+            }),
+        }
+    }
+
+    fn db_path(&self) -> PathBuf {
+        self.db_override
+            .clone()
+            .unwrap_or_else(|| self.data_dir.join("agent_search.db"))
+    }
+}
+
+/// Query parameters as tool arguments: flags become booleans and repeated
+/// parameters of repeatable options become arrays. `max-tokens` and
+/// `max_tokens` are the same parameter. Unknown names are passed through so
+/// the dispatcher can reject them.
+fn query_arguments(
+    schema: &crate::CommandSchema,
+    query: &[(String, String)],
+) -> Map<String, Value> {
+    let mut arguments = Map::new();
+    for (key, value) in query {
+        let key = key.replace('-', "_");
+        let Some(arg) = exposed_args(schema).find(|arg| property_name(arg) == key) else {
+            arguments.insert(key, json!(value));
+            continue;
+        };
+        if arg.arg_type == "flag" {
+            let flag = match value.as_str() {
+                "" | "true" | "1" | "yes" => json!(true),
+                "false" | "0" | "no" => json!(false),
+                other => json!(other),
+            };
+            arguments.insert(key, flag);
+        } else if arg.repeatable == Some(true) {
+            let items = arguments.entry(key).or_insert_with(|| json!([]));
+            if let Some(items) = items.as_array_mut() {
+                items.push(json!(value));
+            }
+        } else {
+            arguments.insert(key, json!(value));
+        }
+    }
+    arguments
+}
+
+/// Compare without short-circuiting, so response timing does not leak the token.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+trait ClientStream: Read + Write {
+    fn set_timeouts(&self, timeout: Duration) -> io::Result<()>;
+}
+
+impl ClientStream for std::net::TcpStream {
+    fn set_timeouts(&self, timeout: Duration) -> io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+}
+
+#[cfg(unix)]
+impl ClientStream for std::os::unix::net::UnixStream {
+    fn set_timeouts(&self, timeout: Duration) -> io::Result<()> {
+        self.set_read_timeout(Some(timeout))?;
+        self.set_write_timeout(Some(timeout))
+    }
+}
+
+/// Read the request line and headers. Request bodies are not used and not read.
+fn read_request(stream: &mut impl Read) -> io::Result<Option<Request>> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            if head.is_empty() {
+                return Ok(None);
+            }
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+        if head.len() > MAX_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request headers too large",
+            ));
+        }
+    }
+    parse_request(&String::from_utf8_lossy(&head)).map(Some)
+}
+
+fn parse_request(head: &str) -> io::Result<Request> {
+    let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+    let mut lines = head.split("\r\n");
+    let mut parts = lines.next().unwrap_or("").split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(invalid("malformed request line"));
+    };
+    let target = target.split('#').next().unwrap_or(target);
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut request = Request {
+        method: method.to_string(),
+        path: decode_component(path),
+        query: query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+                (decode_component(key), decode_component(value))
+            })
+            .collect(),
+        authorization: None,
+        host: None,
+    };
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = name.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            request.authorization = Some(value.trim().to_string());
+        } else if name.eq_ignore_ascii_case("host") {
+            request.host = Some(value.trim().to_string());
+        }
+    }
+    Ok(request)
+}
+
+/// `Host` header without its port (`[::1]:8765` -> `[::1]`).
+fn strip_port(host: &str) -> &str {
+    if host.starts_with('[') {
+        return host.find(']').map_or(host, |end| &host[..=end]);
+    }
+    host.rsplit_once(':').map_or(host, |(name, _)| name)
+}
+
+/// Percent-decode a path or query component (`+` is a space).
+fn decode_component(raw: &str) -> String {
+    let spaced = raw.replace('+', " ");
+    urlencoding::decode(&spaced)
+        .map(|decoded| decoded.into_owned())
+        .unwrap_or(spaced)
+}
+
+fn write_response(stream: &mut impl Write, response: &Response) -> io::Result<()> {
+    let status_text = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "Internal Server Error",
+    };
+    let body = serde_json::to_vec_pretty(&response.body).unwrap_or_default();
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json; charset=utf-8\r\n\
+         Content-Length: {}\r\n\
+         Cache-Control: no-store\r\n\
+         Connection: close\r\n",
+        response.status,
+        status_text,
+        body.len()
+    );
+    if response.status == 401 {
+        head.push_str("WWW-Authenticate: Bearer\r\n");
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(&body)?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(target: &str) -> Request {
+        parse_request(&format!("GET {target} HTTP/1.1\r\nHost: localhost\r\n\r\n")).unwrap()
+    }
+
+    #[test]
+    fn listen_accepts_tcp_and_unix_addresses() {
+        assert_eq!(
+            ListenAddr::parse("127.0.0.1:8765").unwrap(),
+            ListenAddr::Tcp("127.0.0.1:8765".parse().unwrap())
+        );
+        assert_eq!(
+            ListenAddr::parse("unix:/tmp/cass.sock").unwrap(),
+            ListenAddr::Unix(PathBuf::from("/tmp/cass.sock"))
+        );
+        assert_eq!(ListenAddr::parse("localhost").unwrap_err().code, 2);
+        assert!(ListenAddr::parse("unix:").is_err());
+    }
+
+    #[test]
+    fn query_parameters_become_tool_arguments() {
+        let request = get(
+            "/search?query=retry+logic%21&agent=codex&agent=claude_code&max-tokens=200&robot_meta&today=false",
+        );
+        assert_eq!(request.path, "/search");
+        let server = ApiServer::new(PathBuf::from("/nonexistent/cass"), None, None);
+        let dispatcher = server.dispatcher();
+        let schema = dispatcher.tool("search").unwrap();
+        let arguments = query_arguments(schema, &request.query);
+        assert_eq!(arguments["query"], "retry logic!");
+        assert_eq!(arguments["agent"], json!(["codex", "claude_code"]));
+        assert_eq!(arguments["max_tokens"], "200");
+        assert_eq!(arguments["robot_meta"], true);
+        assert_eq!(arguments["today"], false);
+    }
+
+    #[test]
+    fn token_is_required_when_configured() {
+        let server = ApiServer::new(
+            PathBuf::from("/nonexistent/cass"),
+            None,
+            Some("s3cret".to_string()),
+        );
+        let mut request = get("/stats");
+        assert_eq!(server.handle(&request).status, 401);
+        request.authorization = Some("Bearer wrong!".to_string());
+        assert_eq!(server.handle(&request).status, 401);
+
+        request.authorization = Some("Bearer s3cret".to_string());
+        request.path = "/nope".to_string();
+        let response = server.handle(&request);
+        assert_eq!(response.status, 404);
+        assert_eq!(response.body["error"]["kind"], "not-found");
+    }
+
+    #[test]
+    fn tcp_requests_must_name_a_local_host() {
+        let mut server = ApiServer::new(PathBuf::from("/nonexistent/cass"), None, None);
+        server.bound = Some("127.0.0.1:8765".parse().unwrap());
+        let mut request = get("/nope");
+        for host in [
+            "localhost:8765",
+            "127.0.0.1:8765",
+            "[::1]:8765",
+            "LOCALHOST",
+        ] {
+            request.host = Some(host.to_string());
+            assert_eq!(server.handle(&request).status, 404, "{host}");
+        }
+        for host in [
+            Some("attacker.example:8765"),
+            Some("localhost.attacker.example"),
+            None,
+        ] {
+            request.host = host.map(str::to_string);
+            let response = server.handle(&request);
+            assert_eq!(response.status, 403, "{host:?}");
+            assert_eq!(response.body["error"]["kind"], "host");
+        }
+
+        // Served on a LAN address, that address is accepted too
+        server.bound = Some("192.168.1.20:8765".parse().unwrap());
+        request.host = Some("192.168.1.20:8765".to_string());
+        assert_eq!(server.handle(&request).status, 404);
+    }
+}
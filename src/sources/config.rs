@@ -21,6 +21,15 @@
 //! paths = ["~/.claude/projects"]
 //! sync_schedule = "daily"
 //!
+//! # A directory laid out like another machine's home (NFS mount, external
+//! # drive, Syncthing folder). Paths starting with ~ resolve under `root`.
+//! [[sources]]
+//! name = "old-laptop"
+//! type = "path"
+//! root = "/mnt/backup/home/me"
+//! paths = ["~/.claude/projects"]
+//! platform = "macos"
+//!
 //! # Path mappings rewrite remote paths to local equivalents
 //! [[sources.path_mappings]]
 //! from = "/home/user/projects"
@@ -145,16 +154,28 @@ pub struct SourceDefinition {
     /// This becomes the `source_id` used throughout the system.
     pub name: String,
 
-    /// Connection type (local, ssh, etc.).
-    #[serde(rename = "type", default)]
+    /// Connection type (local, ssh, path).
+    #[serde(rename = "type", alias = "kind", default)]
     pub source_type: SourceKind,
 
     /// Remote host for SSH connections (e.g., "user@laptop.local").
     #[serde(default)]
     pub host: Option<String>,
 
+    /// Directory standing in for the other machine's home, for path sources
+    /// (e.g., "/mnt/nas/laptop-home"). Supports ~ expansion.
+    #[serde(default)]
+    pub root: Option<String>,
+
+    /// Copy changed files into the local mirror on `cass sources sync`
+    /// instead of indexing `root` in place. Useful for drives that are not
+    /// always mounted.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub mirror: bool,
+
     /// Paths to sync from this source.
-    /// For SSH sources, these are remote paths.
+    /// For SSH sources, these are remote paths; for path sources, ~ and
+    /// relative paths resolve under `root`.
     /// Supports ~ expansion.
     #[serde(default)]
     pub paths: Vec<String>,
@@ -194,11 +215,66 @@ impl SourceDefinition {
         }
     }
 
+    /// Create a new path source definition rooted at `root`.
+    pub fn path(name: impl Into<String>, root: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            source_type: SourceKind::Path,
+            root: Some(root.into()),
+            ..Default::default()
+        }
+    }
+
     /// Check if this source requires SSH connectivity.
     pub fn is_remote(&self) -> bool {
         matches!(self.source_type, SourceKind::Ssh)
     }
 
+    /// Check if this source reads a directory on this machine.
+    pub fn is_path(&self) -> bool {
+        matches!(self.source_type, SourceKind::Path)
+    }
+
+    /// Check if `cass sources sync` copies this source into the local mirror.
+    pub fn is_synced(&self) -> bool {
+        self.is_remote() || (self.is_path() && self.mirror)
+    }
+
+    /// The root directory of a path source, with ~ expanded.
+    pub fn root_dir(&self) -> Option<PathBuf> {
+        if !self.is_path() {
+            return None;
+        }
+        let root = self.root.as_deref()?;
+        let expanded = match root.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+                dirs::home_dir()?.join(rest.trim_start_matches('/'))
+            }
+            _ => PathBuf::from(root),
+        };
+        Some(expanded)
+    }
+
+    /// Resolve one of `paths` to a directory on this machine (path sources).
+    ///
+    /// `~/x` and relative paths resolve under `root`; absolute paths are
+    /// used as given.
+    pub fn resolve_path(&self, path: &str) -> Option<PathBuf> {
+        let root = self.root_dir()?;
+        if path == "~" {
+            return Some(root);
+        }
+        if let Some(rest) = path.strip_prefix("~/") {
+            return Some(root.join(rest));
+        }
+        let path = Path::new(path);
+        Some(if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            root.join(path)
+        })
+    }
+
     /// Validate the source definition.
     pub fn validate(&self) -> Result<(), ConfigError> {
         if self.name.is_empty() {
@@ -229,6 +305,12 @@ impl SourceDefinition {
             validate_ssh_host(host)?;
         }
 
+        if self.is_path() && self.root.as_deref().is_none_or(|r| r.trim().is_empty()) {
+            return Err(ConfigError::Validation(
+                "Path sources require a root directory".into(),
+            ));
+        }
+
         Ok(())
     }
 
@@ -302,6 +384,17 @@ pub enum SyncSchedule {
     Daily,
 }
 
+impl SyncSchedule {
+    /// Time between scheduled syncs, or `None` for manual sources.
+    pub fn interval(self) -> Option<std::time::Duration> {
+        match self {
+            Self::Manual => None,
+            Self::Hourly => Some(std::time::Duration::from_secs(60 * 60)),
+            Self::Daily => Some(std::time::Duration::from_secs(24 * 60 * 60)),
+        }
+    }
+}
+
 impl std::fmt::Display for SyncSchedule {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -461,6 +554,11 @@ impl SourcesConfig {
     pub fn remote_sources(&self) -> impl Iterator<Item = &SourceDefinition> {
         self.sources.iter().filter(|s| s.is_remote())
     }
+
+    /// Get all sources that `cass sources sync` copies into the local mirror.
+    pub fn synced_sources(&self) -> impl Iterator<Item = &SourceDefinition> {
+        self.sources.iter().filter(|s| s.is_synced())
+    }
 }
 
 /// Get preset paths for a given platform.
@@ -784,6 +882,7 @@ impl SourceConfigGenerator {
             sync_schedule: SyncSchedule::Manual,
             path_mappings,
             platform,
+            ..Default::default()
         }
     }
 
@@ -1047,6 +1146,48 @@ mod tests {
         assert!(source.validate().is_err());
     }
 
+    #[test]
+    fn test_path_source_parses_and_resolves_under_root() {
+        let config: SourcesConfig = toml::from_str(
+            r#"
+[[sources]]
+name = "nas-laptop"
+kind = "path"
+root = "/mnt/nas/laptop"
+paths = ["~/.claude/projects", ".codex/sessions", "/srv/shared/sessions"]
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        let source = &config.sources[0];
+        assert_eq!(source.source_type, SourceKind::Path);
+        assert!(source.is_path() && !source.is_remote() && !source.is_synced());
+        assert_eq!(
+            source.resolve_path("~/.claude/projects"),
+            Some(PathBuf::from("/mnt/nas/laptop/.claude/projects"))
+        );
+        assert_eq!(
+            source.resolve_path(".codex/sessions"),
+            Some(PathBuf::from("/mnt/nas/laptop/.codex/sessions"))
+        );
+        assert_eq!(
+            source.resolve_path("/srv/shared/sessions"),
+            Some(PathBuf::from("/srv/shared/sessions"))
+        );
+        assert_eq!(config.remote_sources().count(), 0);
+
+        let mut mirrored = source.clone();
+        mirrored.mirror = true;
+        assert!(mirrored.is_synced());
+
+        let mut rootless = SourceDefinition::path("usb", "");
+        assert!(rootless.validate().is_err());
+        rootless.root = None;
+        assert!(rootless.validate().is_err());
+        assert_eq!(SourceDefinition::ssh("a", "b").resolve_path("~/x"), None);
+    }
+
     #[test]
     fn test_source_validation_ssh_host_hardening() {
         let source = SourceDefinition::ssh("test", "-oProxyCommand=evil");
@@ -1215,6 +1356,7 @@ mod tests {
             sync_schedule: SyncSchedule::Daily,
             path_mappings: vec![PathMapping::new("/home/user", "/Users/me")],
             platform: Some(Platform::Linux),
+            ..Default::default()
         });
 
         let serialized = toml::to_string_pretty(&config).unwrap();
@@ -1242,6 +1384,7 @@ mod tests {
                 PathMapping::with_agents("/opt/work", "/Volumes/Work", vec!["claude-code".into()]),
             ],
             platform: None,
+            ..Default::default()
         });
 
         let serialized = toml::to_string_pretty(&config).unwrap();
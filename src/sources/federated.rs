@@ -0,0 +1,436 @@
+//! Federated search across remote cass installations.
+//!
+//! Instead of mirroring a remote's sessions with `sources sync`, a federated
+//! search runs the query on each configured SSH source that has cass
+//! installed (`cass search --robot-format jsonl`) and merges the returned
+//! hits with the local results using Reciprocal Rank Fusion. Scores from
+//! different indexes are not comparable, so only ranks are fused.
+//!
+//! Every remote runs in parallel with its own deadline. A remote that times
+//! out, fails or prints something unexpected is reported in its
+//! [`RemoteOutcome`] and contributes no hits; the search itself still
+//! succeeds.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use coding_agent_search::sources::federated::{RemoteSearch, search_remotes};
+//!
+//! let search = RemoteSearch {
+//!     query: "retry backoff".into(),
+//!     limit: 10,
+//!     filters: vec!["--agent".into(), "codex".into()],
+//!     timeout: Duration::from_secs(15),
+//! };
+//! for outcome in search_remotes(&sources, &search) {
+//!     println!("{}: {:?} ({} hits)", outcome.source, outcome.status, outcome.hits.len());
+//! }
+//! ```
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use super::config::SourceDefinition;
+use crate::search::query::{RRF_K, SearchHit, stable_content_hash};
+
+/// Default per-remote deadline in milliseconds when `--timeout` is not set.
+pub const DEFAULT_REMOTE_TIMEOUT_MS: u64 = 15_000;
+
+/// How often a running remote search is checked against its deadline.
+const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+/// A query to run on every remote.
+#[derive(Debug, Clone)]
+pub struct RemoteSearch {
+    pub query: String,
+    /// Hits to request from each remote (already includes any offset)
+    pub limit: usize,
+    /// Extra `cass search` flags forwarded verbatim (agent, time filters, ...)
+    pub filters: Vec<String>,
+    /// Deadline for each remote, including the SSH handshake
+    pub timeout: Duration,
+}
+
+impl RemoteSearch {
+    /// Shell command run on the remote host.
+    ///
+    /// Non-interactive SSH sessions often lack the directories cass is
+    /// installed into, so they are prepended to `PATH` first.
+    pub fn remote_command(&self) -> String {
+        let mut argv = vec![
+            "cass".to_string(),
+            "search".to_string(),
+            "--robot-format".to_string(),
+            "jsonl".to_string(),
+            "--limit".to_string(),
+            self.limit.to_string(),
+        ];
+        argv.extend(self.filters.iter().cloned());
+        argv.push("--".to_string());
+        argv.push(self.query.clone());
+        format!(
+            "export PATH=\"$HOME/.local/bin:$HOME/.cargo/bin:$PATH\"; {}",
+            shell_words::join(&argv)
+        )
+    }
+}
+
+/// How a remote search ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteStatus {
+    Ok,
+    Timeout,
+    Error,
+}
+
+/// Result of searching one remote.
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteOutcome {
+    /// Source name from `sources.toml`, used as the hits' `source_id`
+    pub source: String,
+    pub host: String,
+    pub status: RemoteStatus,
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Hits with provenance filled in, in the remote's rank order
+    #[serde(skip)]
+    pub hits: Vec<SearchHit>,
+}
+
+impl RemoteOutcome {
+    fn failed(
+        source: &SourceDefinition,
+        status: RemoteStatus,
+        elapsed: Duration,
+        error: String,
+    ) -> Self {
+        Self {
+            source: source.name.clone(),
+            host: source_host(source).to_string(),
+            status,
+            elapsed_ms: elapsed.as_millis() as u64,
+            error: Some(error),
+            hits: Vec::new(),
+        }
+    }
+
+    /// Summary for the `_meta.federation.sources` array.
+    pub fn to_meta(&self) -> serde_json::Value {
+        let mut meta = serde_json::to_value(self).unwrap_or_default();
+        if let serde_json::Value::Object(ref mut map) = meta {
+            map.insert("hits".to_string(), serde_json::json!(self.hits.len()));
+        }
+        meta
+    }
+}
+
+fn source_host(source: &SourceDefinition) -> &str {
+    source.host.as_deref().unwrap_or(&source.name)
+}
+
+/// Run `search` on `source` over SSH, killing it at the deadline.
+pub fn search_remote(source: &SourceDefinition, search: &RemoteSearch) -> RemoteOutcome {
+    let start = Instant::now();
+    let host = source_host(source);
+    let connect_secs = search.timeout.as_secs().max(1);
+
+    let mut child = match Command::new("ssh")
+        .args(["-o", "BatchMode=yes"])
+        .args(["-o", &format!("ConnectTimeout={connect_secs}")])
+        .args(["-o", "StrictHostKeyChecking=accept-new"])
+        .arg("--")
+        .arg(host)
+        .arg(search.remote_command())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            return RemoteOutcome::failed(
+                source,
+                RemoteStatus::Error,
+                start.elapsed(),
+                format!("failed to execute ssh: {e}"),
+            );
+        }
+    };
+
+    // Drain both pipes off-thread so a chatty remote cannot block on a full pipe
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() >= search.timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return RemoteOutcome::failed(
+                    source,
+                    RemoteStatus::Timeout,
+                    start.elapsed(),
+                    format!("no answer within {}ms", search.timeout.as_millis()),
+                );
+            }
+            Ok(None) => std::thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                let _ = child.kill();
+                return RemoteOutcome::failed(
+                    source,
+                    RemoteStatus::Error,
+                    start.elapsed(),
+                    format!("waiting for ssh failed: {e}"),
+                );
+            }
+        }
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if !status.success() {
+        return RemoteOutcome::failed(
+            source,
+            RemoteStatus::Error,
+            start.elapsed(),
+            remote_error(status.code(), &stderr),
+        );
+    }
+
+    match parse_remote_hits(&stdout, source) {
+        Ok(hits) => RemoteOutcome {
+            source: source.name.clone(),
+            host: host.to_string(),
+            status: RemoteStatus::Ok,
+            elapsed_ms: start.elapsed().as_millis() as u64,
+            error: None,
+            hits,
+        },
+        Err(e) => RemoteOutcome::failed(source, RemoteStatus::Error, start.elapsed(), e),
+    }
+}
+
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> std::thread::JoinHandle<String> {
+    std::thread::spawn(move || {
+        let mut out = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut out);
+        }
+        out
+    })
+}
+
+/// Short description of a failed remote run.
+fn remote_error(code: Option<i32>, stderr: &str) -> String {
+    // ssh exits 255 on its own failures; 127 is the remote shell's "not found"
+    match code {
+        Some(127) => return "cass is not installed on the remote".to_string(),
+        Some(255) if stderr.contains("Permission denied") => {
+            return "Permission denied (key not loaded in ssh-agent?)".to_string();
+        }
+        _ => {}
+    }
+    // cass reports robot-mode errors as a JSON object on stderr
+    let detail = stderr
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| {
+            serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .and_then(|v| {
+                    v.pointer("/error/message")
+                        .or_else(|| v.get("message"))
+                        .and_then(|m| m.as_str())
+                        .map(str::to_string)
+                })
+                .unwrap_or_else(|| line.to_string())
+        });
+    match (code, detail) {
+        (Some(code), Some(detail)) => format!("remote exited with {code}: {detail}"),
+        (Some(code), None) => format!("remote exited with {code}"),
+        (None, _) => "remote was killed by a signal".to_string(),
+    }
+}
+
+/// Parse `cass search --robot-format jsonl` output into hits from `source`.
+///
+/// The optional `_meta` header line is skipped. Provenance is rewritten to
+/// the source as configured here, and workspaces go through its
+/// `path_mappings` like synced sessions do.
+pub fn parse_remote_hits(
+    output: &str,
+    source: &SourceDefinition,
+) -> Result<Vec<SearchHit>, String> {
+    let mut hits = Vec::new();
+    for line in output.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let value: serde_json::Value = serde_json::from_str(line)
+            .map_err(|e| format!("unexpected output from remote cass: {e}"))?;
+        if value.get("_meta").is_some() || value.get("source_path").is_none() {
+            continue;
+        }
+        let mut hit: SearchHit = serde_json::from_value(value)
+            .map_err(|e| format!("unexpected hit from remote cass: {e}"))?;
+        hit.content_hash = stable_content_hash(&hit.content);
+        hit.source_id = source.name.clone();
+        hit.origin_kind = "ssh".to_string();
+        hit.origin_host = Some(source_host(source).to_string());
+        let mapped = source.rewrite_path_for_agent(&hit.workspace, Some(&hit.agent));
+        if mapped != hit.workspace {
+            hit.workspace_original = Some(std::mem::replace(&mut hit.workspace, mapped));
+        }
+        hits.push(hit);
+    }
+    Ok(hits)
+}
+
+/// Search every source in parallel.
+pub fn search_remotes(sources: &[SourceDefinition], search: &RemoteSearch) -> Vec<RemoteOutcome> {
+    use rayon::prelude::*;
+
+    sources
+        .par_iter()
+        .map(|source| search_remote(source, search))
+        .collect()
+}
+
+/// Fuse ranked hit lists with Reciprocal Rank Fusion.
+///
+/// Each hit scores `1 / (k + rank)` per list it appears in; the fused score
+/// replaces `score`, as in hybrid search. Ties keep list order, so the local
+/// list should come first.
+pub fn rrf_merge(lists: Vec<Vec<SearchHit>>) -> Vec<SearchHit> {
+    let mut fused: Vec<(f32, usize, SearchHit)> = Vec::new();
+    let mut positions: HashMap<(String, String, Option<usize>), usize> = HashMap::new();
+
+    for (list_idx, list) in lists.into_iter().enumerate() {
+        for (rank, hit) in list.into_iter().enumerate() {
+            let rrf = 1.0 / (RRF_K + rank as f32 + 1.0);
+            let key = (
+                hit.source_id.clone(),
+                hit.source_path.clone(),
+                hit.line_number,
+            );
+            match positions.get(&key) {
+                Some(&pos) => fused[pos].0 += rrf,
+                None => {
+                    positions.insert(key, fused.len());
+                    fused.push((rrf, list_idx, hit));
+                }
+            }
+        }
+    }
+
+    // Stable sort: equal scores stay in insertion (list) order
+    fused.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+    fused
+        .into_iter()
+        .map(|(rrf, _, mut hit)| {
+            hit.score = rrf;
+            hit
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::config::PathMapping;
+
+    fn hit(source_id: &str, path: &str) -> SearchHit {
+        serde_json::from_value(serde_json::json!({
+            "title": path,
+            "snippet": "",
+            "content": path,
+            "score": 1.0,
+            "source_path": path,
+            "agent": "codex",
+            "workspace": "/home/dev/app",
+            "created_at": null,
+            "line_number": 1,
+            "source_id": source_id,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_remote_jsonl_and_fills_provenance() {
+        let mut source = SourceDefinition::ssh("build-box", "dev@build.internal");
+        source
+            .path_mappings
+            .push(PathMapping::new("/home/dev", "/Users/me"));
+        let output = [
+            r#"{"_meta":{"query":"retry","count":1}}"#.to_string(),
+            serde_json::to_string(&hit("local", "/home/dev/.codex/a.jsonl")).unwrap(),
+            String::new(),
+        ]
+        .join("\n");
+
+        let hits = parse_remote_hits(&output, &source).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].source_id, "build-box");
+        assert_eq!(hits[0].origin_kind, "ssh");
+        assert_eq!(hits[0].origin_host.as_deref(), Some("dev@build.internal"));
+        assert_eq!(hits[0].workspace, "/Users/me/app");
+        assert_eq!(hits[0].workspace_original.as_deref(), Some("/home/dev/app"));
+        assert_ne!(hits[0].content_hash, 0);
+
+        assert!(parse_remote_hits("cass: command not found", &source).is_err());
+    }
+
+    #[test]
+    fn rrf_merge_interleaves_lists_by_rank() {
+        let local = vec![hit("local", "/l1"), hit("local", "/l2")];
+        let remote = vec![hit("box", "/r1"), hit("box", "/r2"), hit("box", "/r3")];
+
+        let merged = rrf_merge(vec![local, remote]);
+        let paths: Vec<&str> = merged.iter().map(|h| h.source_path.as_str()).collect();
+        assert_eq!(paths, vec!["/l1", "/r1", "/l2", "/r2", "/r3"]);
+        assert!((merged[0].score - 1.0 / (RRF_K + 1.0)).abs() < 1e-6);
+
+        // The same hit from two lists is fused, not duplicated
+        let merged = rrf_merge(vec![
+            vec![hit("box", "/r2")],
+            vec![hit("box", "/r1"), hit("box", "/r2")],
+        ]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].source_path, "/r2");
+    }
+
+    #[test]
+    fn remote_command_quotes_the_query() {
+        let search = RemoteSearch {
+            query: "it's -broken".to_string(),
+            limit: 20,
+            filters: vec!["--agent".to_string(), "claude_code".to_string()],
+            timeout: Duration::from_secs(5),
+        };
+        let command = search.remote_command();
+        assert!(command.ends_with(
+            "cass search --robot-format jsonl --limit 20 --agent claude_code -- 'it'\\''s -broken'"
+        ));
+    }
+
+    #[test]
+    fn remote_errors_are_summarized() {
+        assert_eq!(
+            remote_error(Some(127), "bash: cass: command not found"),
+            "cass is not installed on the remote"
+        );
+        assert_eq!(
+            remote_error(
+                Some(3),
+                r#"{"error":{"code":3,"kind":"missing-index","message":"Index not found"}}"#
+            ),
+            "remote exited with 3: Index not found"
+        );
+    }
+}
@@ -69,11 +69,13 @@
 //! ```
 
 pub mod config;
+pub mod federated;
 pub mod index;
 pub mod install;
 pub mod interactive;
 pub mod probe;
 pub mod provenance;
+pub mod scheduler;
 pub mod setup;
 pub mod sync;
 
@@ -99,6 +101,9 @@ pub use probe::{
     probe_hosts_parallel,
 };
 
+// Re-export commonly used federated search types
+pub use federated::{RemoteOutcome, RemoteSearch, RemoteStatus, search_remotes};
+
 // Re-export commonly used install types
 pub use install::{
     InstallError, InstallMethod, InstallProgress, InstallResult, InstallStage, RemoteInstaller,
@@ -114,5 +119,8 @@ pub use interactive::{
     run_host_selection,
 };
 
+// Re-export commonly used scheduler types
+pub use scheduler::{SCHEDULER_TICK, SyncScheduler, changed_mirror_paths};
+
 // Re-export commonly used setup types
 pub use setup::{SetupError, SetupOptions, SetupResult, SetupState, run_setup};
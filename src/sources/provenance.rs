@@ -39,6 +39,13 @@ pub enum SourceKind {
     Local,
     /// Remote machine via SSH.
     Ssh,
+    /// Account data export brought in with `cass import`.
+    Import,
+    /// Directory laid out like another machine's home (NFS mount, external
+    /// drive, Syncthing folder).
+    Path,
+    /// Session bundle brought in with `cass bundle import`.
+    Bundle,
     // Future extensions:
     // S3,
     // Git,
@@ -47,8 +54,12 @@ pub enum SourceKind {
 
 impl SourceKind {
     /// Returns true if this is a remote source kind.
+    ///
+    /// Path sources and bundles hold another machine's sessions, so they count
+    /// as remote. Imports are neither local nor remote: they match only their
+    /// own source id.
     pub fn is_remote(&self) -> bool {
-        !matches!(self, Self::Local)
+        matches!(self, Self::Ssh | Self::Path | Self::Bundle)
     }
 
     /// Get the string representation.
@@ -56,6 +67,9 @@ impl SourceKind {
         match self {
             Self::Local => "local",
             Self::Ssh => "ssh",
+            Self::Import => "import",
+            Self::Path => "path",
+            Self::Bundle => "bundle",
         }
     }
 
@@ -64,6 +78,9 @@ impl SourceKind {
         match s.to_lowercase().as_str() {
             "local" => Some(Self::Local),
             "ssh" => Some(Self::Ssh),
+            "import" => Some(Self::Import),
+            "path" => Some(Self::Path),
+            "bundle" => Some(Self::Bundle),
             _ => None,
         }
     }
@@ -229,6 +246,34 @@ impl Origin {
         }
     }
 
+    /// Create an origin for conversations read from a path source.
+    pub fn path(source_id: impl Into<String>) -> Self {
+        Self {
+            source_id: source_id.into(),
+            kind: SourceKind::Path,
+            host: None,
+        }
+    }
+
+    /// Create an origin for conversations read from a session bundle, with the
+    /// name of the machine that exported it.
+    pub fn bundle(source_id: impl Into<String>, host: impl Into<String>) -> Self {
+        Self {
+            source_id: source_id.into(),
+            kind: SourceKind::Bundle,
+            host: Some(host.into()),
+        }
+    }
+
+    /// Create an origin for conversations read from an account export.
+    pub fn import(source_id: impl Into<String>) -> Self {
+        Self {
+            source_id: source_id.into(),
+            kind: SourceKind::Import,
+            host: None,
+        }
+    }
+
     /// Check if this origin is from a remote source.
     pub fn is_remote(&self) -> bool {
         self.kind.is_remote()
@@ -248,6 +293,9 @@ impl Origin {
             (Some(host), SourceKind::Local) => host.clone(),
             (None, SourceKind::Local) => "local".to_string(),
             (None, SourceKind::Ssh) => format!("{} (remote)", self.source_id),
+            (_, SourceKind::Import) => format!("{} (import)", self.source_id),
+            (_, SourceKind::Path) => format!("{} (path)", self.source_id),
+            (_, SourceKind::Bundle) => format!("{} (bundle)", self.source_id),
         }
     }
 
@@ -274,7 +322,7 @@ pub enum SourceFilter {
     All,
     /// Match only local sources.
     Local,
-    /// Match only remote sources (any SSH source).
+    /// Match only remote sources (SSH, path and bundle sources).
     Remote,
     /// Match a specific source by ID.
     SourceId(String),
@@ -349,6 +397,9 @@ mod tests {
     fn test_source_kind_is_remote() {
         assert!(!SourceKind::Local.is_remote());
         assert!(SourceKind::Ssh.is_remote());
+        assert!(!SourceKind::Import.is_remote());
+        assert!(SourceKind::Path.is_remote());
+        assert!(SourceKind::Bundle.is_remote());
     }
 
     #[test]
@@ -363,6 +414,9 @@ mod tests {
         assert_eq!(SourceKind::parse("LOCAL"), Some(SourceKind::Local));
         assert_eq!(SourceKind::parse("ssh"), Some(SourceKind::Ssh));
         assert_eq!(SourceKind::parse("SSH"), Some(SourceKind::Ssh));
+        assert_eq!(SourceKind::parse("import"), Some(SourceKind::Import));
+        assert_eq!(SourceKind::parse("path"), Some(SourceKind::Path));
+        assert_eq!(SourceKind::parse("bundle"), Some(SourceKind::Bundle));
         assert_eq!(SourceKind::parse("unknown"), None);
     }
 
@@ -459,6 +513,19 @@ mod tests {
             remote_with_host.display_label(),
             "user@laptop.local (remote)"
         );
+
+        let import = Origin::import("chatgpt-export");
+        assert_eq!(import.display_label(), "chatgpt-export (import)");
+
+        let path = Origin::path("nas-laptop");
+        assert_eq!(path.display_label(), "nas-laptop (path)");
+        assert!(path.is_remote());
+        assert!(!import.is_remote() && !import.is_local());
+
+        let bundle = Origin::bundle("ci-runner", "runner-7");
+        assert_eq!(bundle.display_label(), "ci-runner (bundle)");
+        assert_eq!(bundle.short_label(), "runner-7");
+        assert!(bundle.is_remote());
     }
 
     #[test]
@@ -0,0 +1,188 @@
+//! Scheduled syncing of remote sources.
+//!
+//! Sources with `sync_schedule = "hourly"` or `"daily"` are synced by
+//! `cass index --watch` and `cass sources sync --daemon`, which call
+//! [`SyncScheduler::run_due`] once per [`SCHEDULER_TICK`]. Bookkeeping lives
+//! in [`SyncStatus`] (`sync_status.json`), shared with manual
+//! `cass sources sync` runs, so a manual sync also resets the schedule.
+//!
+//! A failed sync is retried after [`retry_delay`], which doubles with each
+//! consecutive failure and never exceeds the source's own interval.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::config::{SourceDefinition, SyncSchedule};
+use super::sync::{SourceSyncInfo, SyncEngine, SyncReport, SyncStatus};
+
+/// How often the scheduler checks for due sources.
+pub const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+/// Delay before retrying after the first failure.
+const RETRY_BASE: Duration = Duration::from_secs(5 * 60);
+
+/// Backoff before the next attempt after `failures` consecutive failures.
+pub fn retry_delay(failures: u32) -> Duration {
+    RETRY_BASE.saturating_mul(1u32 << failures.saturating_sub(1).min(16))
+}
+
+/// When a source is next due, in epoch milliseconds.
+///
+/// `None` for manual sources. Sources that never synced are due immediately.
+pub fn next_run(schedule: SyncSchedule, info: Option<&SourceSyncInfo>, now_ms: i64) -> Option<i64> {
+    let interval = schedule.interval()?;
+    let Some(info) = info else {
+        return Some(now_ms);
+    };
+    let Some(last) = info.last_sync else {
+        return Some(now_ms);
+    };
+    let wait = if info.consecutive_failures > 0 {
+        retry_delay(info.consecutive_failures).min(interval)
+    } else {
+        interval
+    };
+    Some(last.saturating_add(wait.as_millis() as i64))
+}
+
+/// Mirror directories that received files in `reports`.
+pub fn changed_mirror_paths(reports: &[SyncReport]) -> Vec<PathBuf> {
+    reports
+        .iter()
+        .flat_map(|r| &r.path_results)
+        .filter(|r| r.success && r.files_transferred > 0)
+        .map(|r| r.local_path.clone())
+        .collect()
+}
+
+/// Runs due syncs against one data directory.
+pub struct SyncScheduler {
+    data_dir: PathBuf,
+    engine: SyncEngine,
+}
+
+impl SyncScheduler {
+    pub fn new(data_dir: &Path) -> Self {
+        Self {
+            data_dir: data_dir.to_path_buf(),
+            engine: SyncEngine::new(data_dir),
+        }
+    }
+
+    /// Sync every synced source in `sources` (SSH, or path with `mirror`)
+    /// that is due at `now_ms`.
+    ///
+    /// Failures are recorded in the sync status rather than returned, so one
+    /// unreachable host does not stop the others.
+    pub fn run_due(&self, sources: &[SourceDefinition], now_ms: i64) -> Vec<SyncReport> {
+        let mut status = SyncStatus::load(&self.data_dir).unwrap_or_default();
+        let mut reports = Vec::new();
+
+        for source in sources.iter().filter(|s| s.is_synced()) {
+            let due = next_run(source.sync_schedule, status.get(&source.name), now_ms);
+            if !due.is_some_and(|at| at <= now_ms) {
+                continue;
+            }
+
+            let report = self
+                .engine
+                .sync_source(source)
+                .unwrap_or_else(|e| SyncReport::failed(&source.name, e));
+            if report.all_succeeded {
+                tracing::info!(
+                    source = %source.name,
+                    files = report.total_files(),
+                    "scheduled sync complete"
+                );
+            } else {
+                tracing::warn!(
+                    source = %source.name,
+                    failed_paths = report.failed_paths(),
+                    "scheduled sync failed"
+                );
+            }
+            status.update(&source.name, &report);
+            reports.push(report);
+        }
+
+        if !reports.is_empty()
+            && let Err(e) = status.save(&self.data_dir)
+        {
+            tracing::warn!("failed to save sync status: {e}");
+        }
+        reports
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::sync::{PathSyncResult, SyncMethod, SyncResult};
+
+    const HOUR_MS: i64 = 60 * 60 * 1000;
+
+    fn synced_at(last_sync: i64, consecutive_failures: u32) -> SourceSyncInfo {
+        SourceSyncInfo {
+            last_sync: Some(last_sync),
+            last_result: if consecutive_failures == 0 {
+                SyncResult::Success
+            } else {
+                SyncResult::Failed("unreachable".into())
+            },
+            consecutive_failures,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn next_run_follows_schedule_and_backs_off() {
+        let now = 100 * HOUR_MS;
+        assert_eq!(next_run(SyncSchedule::Manual, None, now), None);
+        assert_eq!(next_run(SyncSchedule::Hourly, None, now), Some(now));
+        assert_eq!(
+            next_run(SyncSchedule::Hourly, Some(&synced_at(now, 0)), now),
+            Some(now + HOUR_MS)
+        );
+        assert_eq!(
+            next_run(SyncSchedule::Daily, Some(&synced_at(now, 0)), now),
+            Some(now + 24 * HOUR_MS)
+        );
+
+        // 5, 10, 20 minutes... capped at the schedule's interval
+        let minutes = |failures| {
+            let at = next_run(SyncSchedule::Daily, Some(&synced_at(now, failures)), now);
+            (at.unwrap() - now) / 60_000
+        };
+        assert_eq!(minutes(1), 5);
+        assert_eq!(minutes(2), 10);
+        assert_eq!(minutes(3), 20);
+        assert_eq!(minutes(20), 24 * 60);
+        assert_eq!(
+            next_run(SyncSchedule::Hourly, Some(&synced_at(now, 5)), now),
+            Some(now + HOUR_MS)
+        );
+    }
+
+    #[test]
+    fn manual_sources_are_never_synced() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let scheduler = SyncScheduler::new(dir.path());
+        let sources = vec![SourceDefinition::ssh("laptop", "me@laptop")];
+        assert!(scheduler.run_due(&sources, 0).is_empty());
+        assert!(!dir.path().join("sync_status.json").exists());
+    }
+
+    #[test]
+    fn changed_paths_skip_failed_and_unchanged_mirrors() {
+        let mut report = SyncReport::new("laptop", SyncMethod::Rsync);
+        for (path, files, success) in [("a", 3, true), ("b", 0, true), ("c", 2, false)] {
+            report.add_path_result(PathSyncResult {
+                local_path: PathBuf::from(path),
+                files_transferred: files,
+                success,
+                ..Default::default()
+            });
+        }
+        assert_eq!(changed_mirror_paths(&[report]), vec![PathBuf::from("a")]);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! This module provides the core sync functionality using rsync over SSH
 //! for efficient delta transfers, with progress reporting and error recovery.
+//! Path sources with `mirror = true` are copied file by file instead, skipping
+//! files whose size and modification time already match the mirror.
 //!
 //! # Safety
 //!
@@ -44,6 +46,9 @@ pub enum SyncError {
     #[error("Source has no paths configured")]
     NoPaths,
 
+    #[error("Source root is not available: {0}")]
+    RootUnavailable(String),
+
     #[error("rsync command failed: {0}")]
     RsyncFailed(String),
 
@@ -67,6 +72,8 @@ pub enum SyncMethod {
     Rsync,
     /// SFTP fallback when rsync is unavailable
     Sftp,
+    /// Local file copy for path sources
+    Copy,
 }
 
 impl std::fmt::Display for SyncMethod {
@@ -74,6 +81,7 @@ impl std::fmt::Display for SyncMethod {
         match self {
             Self::Rsync => write!(f, "rsync"),
             Self::Sftp => write!(f, "sftp"),
+            Self::Copy => write!(f, "copy"),
         }
     }
 }
@@ -296,6 +304,10 @@ impl SyncEngine {
     /// Syncs all configured paths from the source to the local mirror directory.
     /// Individual path failures don't abort the entire sync.
     pub fn sync_source(&self, source: &SourceDefinition) -> Result<SyncReport, SyncError> {
+        if source.is_path() {
+            return self.sync_source_copy(source);
+        }
+
         if !source.is_remote() {
             return Err(SyncError::NoHost);
         }
@@ -343,6 +355,62 @@ impl SyncEngine {
         Ok(report)
     }
 
+    /// Copy a path source into the local mirror.
+    ///
+    /// Mirror directories are named exactly as for SSH sources, so the indexer
+    /// finds them the same way. Like rsync here, nothing is ever deleted.
+    fn sync_source_copy(&self, source: &SourceDefinition) -> Result<SyncReport, SyncError> {
+        let root = source
+            .root_dir()
+            .ok_or_else(|| SyncError::RootUnavailable("no root configured".into()))?;
+        if source.paths.is_empty() {
+            return Err(SyncError::NoPaths);
+        }
+        if !root.is_dir() {
+            return Err(SyncError::RootUnavailable(format!(
+                "{} is not a directory (is it mounted?)",
+                root.display()
+            )));
+        }
+
+        let mut report = SyncReport::new(&source.name, SyncMethod::Copy);
+        let overall_start = Instant::now();
+        let mirror_dir = self.mirror_dir(&source.name);
+        std::fs::create_dir_all(&mirror_dir)?;
+
+        for path in &source.paths {
+            let start = Instant::now();
+            let local_path = mirror_dir.join(path_to_safe_dirname(path));
+            let mut result = PathSyncResult {
+                remote_path: path.clone(),
+                local_path: local_path.clone(),
+                ..Default::default()
+            };
+
+            let copied = match source.resolve_path(path) {
+                Some(from) if from.exists() => copy_changed(&from, &local_path),
+                Some(from) => Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("Path not found: {}", from.display()),
+                )),
+                None => Err(std::io::Error::other("Path could not be resolved")),
+            };
+            match copied {
+                Ok((files, bytes)) => {
+                    result.files_transferred = files;
+                    result.bytes_transferred = bytes;
+                    result.success = true;
+                }
+                Err(e) => result.error = Some(e.to_string()),
+            }
+            result.duration_ms = start.elapsed().as_millis() as u64;
+            report.add_path_result(result);
+        }
+
+        report.total_duration_ms = overall_start.elapsed().as_millis() as u64;
+        Ok(report)
+    }
+
     /// Sync all remote sources from a config.
     ///
     /// Continues even if individual sources fail.
@@ -907,6 +975,57 @@ fn parse_ssh_host(host: &str) -> (Option<&str>, &str) {
     }
 }
 
+/// Copy files from `from` into the directory `to`, skipping files whose size
+/// and modification time already match. A single file is copied into `to`.
+///
+/// Returns the number of files and bytes copied. Modification times are
+/// carried over so later runs can tell the files apart.
+fn copy_changed(from: &Path, to: &Path) -> std::io::Result<(u64, u64)> {
+    std::fs::create_dir_all(to)?;
+    let mut files = 0;
+    let mut bytes = 0;
+
+    let mut copy_file = |src: &Path, dest: &Path| -> std::io::Result<()> {
+        let meta = std::fs::metadata(src)?;
+        let modified = meta.modified()?;
+        if let Ok(existing) = std::fs::metadata(dest)
+            && existing.len() == meta.len()
+            && existing.modified().ok() == Some(modified)
+        {
+            return Ok(());
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(src, dest)?;
+        std::fs::File::options()
+            .write(true)
+            .open(dest)?
+            .set_modified(modified)?;
+        files += 1;
+        bytes += meta.len();
+        Ok(())
+    };
+
+    if from.is_file() {
+        let name = from.file_name().unwrap_or_default();
+        copy_file(from, &to.join(name))?;
+        return Ok((files, bytes));
+    }
+
+    for entry in walkdir::WalkDir::new(from).follow_links(true) {
+        let entry = entry.map_err(std::io::Error::other)?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(rel) = entry.path().strip_prefix(from) else {
+            continue;
+        };
+        copy_file(entry.path(), &to.join(rel))?;
+    }
+    Ok((files, bytes))
+}
+
 /// Expand tilde in local paths.
 fn expand_tilde_local(path: &str) -> String {
     if let Some(stripped) = path.strip_prefix("~/")
@@ -1038,6 +1157,9 @@ pub struct SourceSyncInfo {
     pub bytes_transferred: u64,
     /// Duration of last sync in milliseconds.
     pub duration_ms: u64,
+    /// Failed syncs since the last success (drives scheduler backoff).
+    #[serde(default)]
+    pub consecutive_failures: u32,
 }
 
 /// Persistent sync status for all sources.
@@ -1095,6 +1217,14 @@ impl SyncStatus {
             SyncResult::Failed(errors.join("; "))
         };
 
+        let consecutive_failures = match result {
+            SyncResult::Success => 0,
+            _ => self
+                .get(source_name)
+                .map_or(0, |info| info.consecutive_failures)
+                .saturating_add(1),
+        };
+
         self.sources.insert(
             source_name.to_string(),
             SourceSyncInfo {
@@ -1103,6 +1233,7 @@ impl SyncStatus {
                 files_synced: report.total_files(),
                 bytes_transferred: report.total_bytes(),
                 duration_ms: report.total_duration_ms,
+                consecutive_failures,
             },
         );
     }
@@ -1339,10 +1470,19 @@ Total transferred file size: 1,234 bytes
             ..Default::default()
         });
 
+        status.update("dead-host", &report);
         status.update("dead-host", &report);
 
         let info = status.get("dead-host").unwrap();
         assert!(matches!(info.last_result, SyncResult::Failed(_)));
+        assert_eq!(info.consecutive_failures, 2);
+
+        // A success resets the failure streak
+        status.update(
+            "dead-host",
+            &SyncReport::new("dead-host", SyncMethod::Rsync),
+        );
+        assert_eq!(status.get("dead-host").unwrap().consecutive_failures, 0);
     }
 
     #[test]
@@ -1372,6 +1512,53 @@ Total transferred file size: 1,234 bytes
         assert_eq!(SyncError::Cancelled.to_string(), "Sync cancelled");
     }
 
+    #[test]
+    fn test_sync_path_source_copies_only_changed_files() {
+        let root = tempfile::TempDir::new().unwrap();
+        let data = tempfile::TempDir::new().unwrap();
+        let sessions = root.path().join(".codex/sessions/2025");
+        std::fs::create_dir_all(&sessions).unwrap();
+        std::fs::write(sessions.join("a.jsonl"), "one").unwrap();
+        std::fs::write(root.path().join(".aider.chat.history.md"), "# chat").unwrap();
+
+        let mut source = SourceDefinition::path("usb", root.path().to_str().unwrap());
+        source.mirror = true;
+        source.paths = vec![
+            "~/.codex/sessions".into(),
+            "~/.aider.chat.history.md".into(),
+        ];
+        let engine = SyncEngine::new(data.path());
+        let mirror = engine.mirror_dir("usb");
+
+        let report = engine.sync_source(&source).unwrap();
+        assert!(report.all_succeeded);
+        assert_eq!(report.method, SyncMethod::Copy);
+        assert_eq!(report.total_files(), 2);
+        let copied = mirror
+            .join(path_to_safe_dirname("~/.codex/sessions"))
+            .join("2025/a.jsonl");
+        assert_eq!(std::fs::read_to_string(&copied).unwrap(), "one");
+        assert!(
+            mirror
+                .join(path_to_safe_dirname("~/.aider.chat.history.md"))
+                .join(".aider.chat.history.md")
+                .is_file()
+        );
+
+        // Unchanged files are skipped, new ones copied
+        std::fs::write(sessions.join("b.jsonl"), "two").unwrap();
+        let report = engine.sync_source(&source).unwrap();
+        assert_eq!(report.total_files(), 1);
+
+        // An unmounted root fails without touching the mirror
+        source.root = Some(root.path().join("missing").display().to_string());
+        assert!(matches!(
+            engine.sync_source(&source),
+            Err(SyncError::RootUnavailable(_))
+        ));
+        assert!(copied.exists());
+    }
+
     // =========================================================================
     // SFTP helper function tests
     // =========================================================================
@@ -1,9 +1,13 @@
 //! `SQLite` backend: schema, pragmas, and migrations.
 
-use crate::model::types::{Agent, AgentKind, Conversation, Message, MessageRole, Snippet};
+use crate::model::types::{
+    Agent, AgentKind, Conversation, ConversationSelector, Message, MessageRole,
+    SelectedConversation, Snippet, Tag, TokenUsage, TouchedFile,
+};
 use crate::sources::provenance::{LOCAL_SOURCE_ID, Source, SourceKind};
 use anyhow::{Context, Result, anyhow};
 use rusqlite::{Connection, OpenFlags, OptionalExtension, Transaction, params};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
 use std::path::Path;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -57,6 +61,37 @@ fn read_metadata_compat(
     serde_json::Value::Object(serde_json::Map::new())
 }
 
+/// Column list read by [`conversation_from_row`].
+const CONVERSATION_SELECT: &str = r"SELECT c.id, a.slug, w.path, c.external_id, c.title, c.source_path,
+                       c.started_at, c.ended_at, c.approx_tokens, c.metadata_json,
+                       c.source_id, c.origin_host, c.metadata_bin
+                FROM conversations c
+                JOIN agents a ON c.agent_id = a.id
+                LEFT JOIN workspaces w ON c.workspace_id = w.id";
+
+fn conversation_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Conversation> {
+    Ok(Conversation {
+        id: Some(row.get(0)?),
+        agent_slug: row.get(1)?,
+        workspace: row
+            .get::<_, Option<String>>(2)?
+            .map(|p| Path::new(&p).to_path_buf()),
+        external_id: row.get(3)?,
+        title: row.get(4)?,
+        source_path: Path::new(&row.get::<_, String>(5)?).to_path_buf(),
+        started_at: row.get(6)?,
+        ended_at: row.get(7)?,
+        approx_tokens: row.get(8)?,
+        // Read from binary column first (idx 12), fallback to JSON (idx 9)
+        metadata_json: read_metadata_compat(row, 9, 12),
+        messages: Vec::new(),
+        source_id: row
+            .get::<_, String>(10)
+            .unwrap_or_else(|_| "local".to_string()),
+        origin_host: row.get(11)?,
+    })
+}
+
 // -------------------------------------------------------------------------
 // Migration Error Types (P1.5)
 // -------------------------------------------------------------------------
@@ -221,7 +256,7 @@ pub fn cleanup_old_backups(db_path: &Path, keep_count: usize) -> Result<(), std:
 }
 
 /// Public schema version constant for external checks.
-pub const CURRENT_SCHEMA_VERSION: i64 = 8;
+pub const CURRENT_SCHEMA_VERSION: i64 = 10;
 
 /// Result of checking schema compatibility.
 #[derive(Debug, Clone)]
@@ -296,7 +331,7 @@ fn check_schema_compatibility(path: &Path) -> std::result::Result<SchemaCheck, r
     }
 }
 
-const SCHEMA_VERSION: i64 = 8;
+const SCHEMA_VERSION: i64 = 10;
 
 const MIGRATION_V1: &str = r"
 PRAGMA foreign_keys = ON;
@@ -518,6 +553,33 @@ CREATE INDEX IF NOT EXISTS idx_daily_stats_agent ON daily_stats(agent_slug, day_
 CREATE INDEX IF NOT EXISTS idx_daily_stats_source ON daily_stats(source_id, day_id);
 ";
 
+const MIGRATION_V9: &str = r"
+-- Secrets replaced at ingest time (cass index --redact-secrets), per conversation and kind
+CREATE TABLE IF NOT EXISTS conversation_redactions (
+    conversation_id INTEGER NOT NULL REFERENCES conversations(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (conversation_id, kind)
+);
+";
+
+const MIGRATION_V10: &str = r"
+-- Tokens reported by the agent per message (cass usage)
+CREATE TABLE IF NOT EXISTS message_usage (
+    message_id INTEGER PRIMARY KEY REFERENCES messages(id) ON DELETE CASCADE,
+    model TEXT,
+    input_tokens INTEGER NOT NULL DEFAULT 0,
+    output_tokens INTEGER NOT NULL DEFAULT 0,
+    cache_read_tokens INTEGER NOT NULL DEFAULT 0,
+    cache_write_tokens INTEGER NOT NULL DEFAULT 0
+);
+
+ALTER TABLE daily_stats ADD COLUMN input_tokens INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE daily_stats ADD COLUMN output_tokens INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE daily_stats ADD COLUMN cache_read_tokens INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE daily_stats ADD COLUMN cache_write_tokens INTEGER NOT NULL DEFAULT 0;
+";
+
 pub struct SqliteStorage {
     conn: Connection,
 }
@@ -527,6 +589,22 @@ pub struct InsertOutcome {
     pub inserted_indices: Vec<i64>,
 }
 
+/// Totals returned by [`SqliteStorage::redaction_summary`].
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct RedactionSummary {
+    /// Conversations with at least one redaction
+    pub conversations: i64,
+    pub total: i64,
+    pub by_kind: BTreeMap<String, i64>,
+}
+
+/// Rows removed by [`SqliteStorage::delete_conversations`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeletedConversations {
+    pub conversations: usize,
+    pub messages: usize,
+}
+
 /// Message data needed for semantic embedding generation.
 pub struct MessageForEmbedding {
     pub message_id: i64,
@@ -973,14 +1051,17 @@ impl SqliteStorage {
         let conv_id = insert_conversation(&tx, agent_id, workspace_id, conv)?;
         let mut fts_entries = Vec::with_capacity(conv.messages.len());
         let mut total_chars: i64 = 0;
+        let mut usage = TokenUsage::default();
         for msg in &conv.messages {
             let msg_id = insert_message(&tx, conv_id, msg)?;
             insert_snippets(&tx, msg_id, &msg.snippets)?;
+            insert_message_usage(&tx, msg_id, msg, &mut usage)?;
             fts_entries.push(FtsEntry::from_message(msg_id, msg, conv));
             total_chars += msg.content.len() as i64;
         }
         // Batch insert FTS entries
         batch_insert_fts_messages(&tx, &fts_entries)?;
+        update_daily_tokens_in_tx(&tx, conv, &usage, 1)?;
 
         // Update daily stats (+1 session, +N messages)
         update_daily_stats_in_tx(
@@ -1017,12 +1098,23 @@ impl SqliteStorage {
         let mut inserted_indices = Vec::new();
         let mut fts_entries = Vec::new();
         let mut new_chars: i64 = 0;
+        let mut usage = TokenUsage::default();
+        let mut retracted = TokenUsage::default();
+        refresh_stored_usage(
+            &tx,
+            conversation_id,
+            conv,
+            cutoff,
+            &mut usage,
+            &mut retracted,
+        )?;
         for msg in &conv.messages {
             if msg.idx <= cutoff {
                 continue;
             }
             let msg_id = insert_message(&tx, conversation_id, msg)?;
             insert_snippets(&tx, msg_id, &msg.snippets)?;
+            insert_message_usage(&tx, msg_id, msg, &mut usage)?;
             fts_entries.push(FtsEntry::from_message(msg_id, msg, conv));
             inserted_indices.push(msg.idx);
             new_chars += msg.content.len() as i64;
@@ -1030,6 +1122,8 @@ impl SqliteStorage {
 
         // Batch insert FTS entries
         batch_insert_fts_messages(&tx, &fts_entries)?;
+        update_daily_tokens_in_tx(&tx, conv, &retracted, -1)?;
+        update_daily_tokens_in_tx(&tx, conv, &usage, 1)?;
 
         if let Some(last_ts) = conv.messages.iter().filter_map(|m| m.created_at).max() {
             // Use IFNULL to handle NULL ended_at values correctly.
@@ -1181,39 +1275,38 @@ impl SqliteStorage {
     }
 
     pub fn list_conversations(&self, limit: i64, offset: i64) -> Result<Vec<Conversation>> {
-        let mut stmt = self.conn.prepare(
-            r"SELECT c.id, a.slug, w.path, c.external_id, c.title, c.source_path,
-                       c.started_at, c.ended_at, c.approx_tokens, c.metadata_json,
-                       c.source_id, c.origin_host, c.metadata_bin
-                FROM conversations c
-                JOIN agents a ON c.agent_id = a.id
-                LEFT JOIN workspaces w ON c.workspace_id = w.id
+        let mut stmt = self.conn.prepare(&format!(
+            "{CONVERSATION_SELECT}
                 ORDER BY c.started_at IS NULL, c.started_at DESC, c.id DESC
-                LIMIT ? OFFSET ?",
-        )?;
+                LIMIT ? OFFSET ?"
+        ))?;
 
-        let rows = stmt.query_map(params![limit, offset], |row| {
-            Ok(Conversation {
-                id: Some(row.get(0)?),
-                agent_slug: row.get(1)?,
-                workspace: row
-                    .get::<_, Option<String>>(2)?
-                    .map(|p| Path::new(&p).to_path_buf()),
-                external_id: row.get(3)?,
-                title: row.get(4)?,
-                source_path: Path::new(&row.get::<_, String>(5)?).to_path_buf(),
-                started_at: row.get(6)?,
-                ended_at: row.get(7)?,
-                approx_tokens: row.get(8)?,
-                // Read from binary column first (idx 12), fallback to JSON (idx 9)
-                metadata_json: read_metadata_compat(row, 9, 12),
-                messages: Vec::new(),
-                source_id: row
-                    .get::<_, String>(10)
-                    .unwrap_or_else(|_| "local".to_string()),
-                origin_host: row.get(11)?,
-            })
-        })?;
+        let rows = stmt.query_map(params![limit, offset], conversation_from_row)?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// One conversation by id, without its messages.
+    pub fn get_conversation(&self, conversation_id: i64) -> Result<Option<Conversation>> {
+        self.conn
+            .query_row(
+                &format!("{CONVERSATION_SELECT} WHERE c.id = ?"),
+                params![conversation_id],
+                conversation_from_row,
+            )
+            .optional()
+            .map_err(Into::into)
+    }
+
+    /// All conversations stored for a session file, without their messages.
+    pub fn conversations_for_path(&self, source_path: &str) -> Result<Vec<Conversation>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "{CONVERSATION_SELECT} WHERE c.source_path = ? ORDER BY c.id"
+        ))?;
+        let rows = stmt.query_map(params![source_path], conversation_from_row)?;
         let mut out = Vec::new();
         for r in rows {
             out.push(r?);
@@ -1256,14 +1349,24 @@ impl SqliteStorage {
     ///
     /// Returns MessageForEmbedding records with all metadata needed for vector indexing.
     pub fn fetch_messages_for_embedding(&self) -> Result<Vec<MessageForEmbedding>> {
+        self.fetch_messages_for_embedding_after(0)
+    }
+
+    /// Like [`Self::fetch_messages_for_embedding`], limited to messages with `id > after_id`
+    /// (incremental semantic indexing).
+    pub fn fetch_messages_for_embedding_after(
+        &self,
+        after_id: i64,
+    ) -> Result<Vec<MessageForEmbedding>> {
         let mut stmt = self.conn.prepare(
             r"SELECT m.id, m.created_at, c.agent_id, c.workspace_id, c.source_id, m.role, m.content
               FROM messages m
               JOIN conversations c ON m.conversation_id = c.id
+              WHERE m.id > ?1
               ORDER BY m.id",
         )?;
 
-        let rows = stmt.query_map([], |row| {
+        let rows = stmt.query_map([after_id], |row| {
             let source_id_str: String = row
                 .get::<_, Option<String>>(4)?
                 .unwrap_or_else(|| "local".to_string());
@@ -1339,6 +1442,75 @@ impl SqliteStorage {
         Ok(())
     }
 
+    /// Whether ingest-time secret redaction was turned on for this database.
+    pub fn get_redact_secrets(&self) -> Result<bool> {
+        let value: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT value FROM meta WHERE key = 'redact_secrets'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value.as_deref() == Some("1"))
+    }
+
+    /// Remember the ingest-time secret redaction setting for later index runs.
+    pub fn set_redact_secrets(&mut self, enabled: bool) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO meta(key, value) VALUES('redact_secrets', ?)",
+            params![if enabled { "1" } else { "0" }],
+        )?;
+        Ok(())
+    }
+
+    /// Add ingest-time redaction counts, keyed by conversation id and secret kind.
+    pub fn add_conversation_redactions(
+        &mut self,
+        redactions: &[(i64, BTreeMap<String, i64>)],
+    ) -> Result<()> {
+        if redactions.is_empty() {
+            return Ok(());
+        }
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO conversation_redactions(conversation_id, kind, count)
+                 VALUES(?1, ?2, ?3)
+                 ON CONFLICT(conversation_id, kind) DO UPDATE SET count = count + excluded.count",
+            )?;
+            for (conversation_id, counts) in redactions {
+                for (kind, count) in counts {
+                    stmt.execute(params![conversation_id, kind, count])?;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Totals of the ingest-time redactions recorded so far.
+    pub fn redaction_summary(&self) -> Result<RedactionSummary> {
+        let mut summary = RedactionSummary::default();
+        let mut stmt = self.conn.prepare(
+            "SELECT kind, SUM(count) FROM conversation_redactions GROUP BY kind ORDER BY kind",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })?;
+        for row in rows {
+            let (kind, count) = row?;
+            summary.total += count;
+            summary.by_kind.insert(kind, count);
+        }
+        summary.conversations = self.conn.query_row(
+            "SELECT COUNT(DISTINCT conversation_id) FROM conversation_redactions",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(summary)
+    }
+
     /// Get current time as milliseconds since epoch.
     pub fn now_millis() -> i64 {
         SystemTime::now()
@@ -1455,22 +1627,286 @@ impl SqliteStorage {
 
     /// Delete a source by ID.
     ///
-    /// If `cascade` is true, also deletes all conversations from this source.
-    /// Note: Currently conversations don't have a source_id column, so cascade
-    /// is a no-op until P1.3 is implemented.
-    pub fn delete_source(&self, id: &str, _cascade: bool) -> Result<bool> {
+    /// If `cascade` is true, also deletes all conversations from this source
+    /// (see [`SqliteStorage::delete_conversations`]); otherwise a source that
+    /// still owns conversations cannot be deleted.
+    pub fn delete_source(&self, id: &str, cascade: bool) -> Result<bool> {
         // Prevent deletion of the local source
         if id == LOCAL_SOURCE_ID {
             return Err(anyhow!("cannot delete the local source"));
         }
 
-        let rows_affected = self
-            .conn
-            .execute("DELETE FROM sources WHERE id = ?", params![id])?;
+        let tx = self.conn.unchecked_transaction()?;
+        if cascade {
+            let ids: Vec<i64> = {
+                let mut stmt = tx.prepare("SELECT id FROM conversations WHERE source_id = ?")?;
+                stmt.query_map(params![id], |row| row.get(0))?
+                    .collect::<rusqlite::Result<_>>()?
+            };
+            delete_conversations_in_tx(&tx, &ids)?;
+        }
+        let rows_affected = tx.execute("DELETE FROM sources WHERE id = ?", params![id])?;
+        tx.commit()?;
 
         Ok(rows_affected > 0)
     }
 
+    // -------------------------------------------------------------------------
+    // Targeted removal (`cass forget`)
+    // -------------------------------------------------------------------------
+
+    /// Conversations matching `selector`, oldest first.
+    pub fn select_conversations(
+        &self,
+        selector: &ConversationSelector,
+    ) -> Result<Vec<SelectedConversation>> {
+        let mut clauses: Vec<String> = Vec::new();
+        let mut values: Vec<rusqlite::types::Value> = Vec::new();
+        let mut any_of = |expr: &str, items: &[String]| {
+            if items.is_empty() {
+                return;
+            }
+            let ors = vec![expr; items.len()].join(" OR ");
+            clauses.push(format!("({ors})"));
+            values.extend(items.iter().map(|v| v.clone().into()));
+        };
+        any_of("c.source_id = ?", &selector.source_ids);
+        any_of("a.slug = ?", &selector.agents);
+        any_of("w.path = ?", &selector.workspaces);
+        any_of("c.source_path GLOB ?", &selector.path_globs);
+        let tags: Vec<String> = selector
+            .tags
+            .iter()
+            .filter_map(|tag| normalize_tag_name(tag).ok())
+            .collect();
+        any_of(
+            "c.id IN (SELECT ct.conversation_id FROM conversation_tags ct \
+             JOIN tags t ON t.id = ct.tag_id WHERE t.name = ?)",
+            &tags,
+        );
+        if let Some(from) = selector.started_from {
+            clauses.push("COALESCE(c.started_at, c.ended_at) >= ?".into());
+            values.push(from.into());
+        }
+        if let Some(to) = selector.started_to {
+            clauses.push("COALESCE(c.started_at, c.ended_at) <= ?".into());
+            values.push(to.into());
+        }
+        if let Some(query) = &selector.query {
+            let fts_query = fts_phrase_query(query)
+                .ok_or_else(|| anyhow!("the query must contain at least one word"))?;
+            clauses.push(
+                "c.id IN (SELECT m.conversation_id FROM messages m WHERE m.id IN \
+                 (SELECT message_id FROM fts_messages WHERE fts_messages MATCH ?))"
+                    .into(),
+            );
+            values.push(fts_query.into());
+        }
+        let where_sql = if clauses.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", clauses.join(" AND "))
+        };
+
+        let mut stmt = self.conn.prepare(&format!(
+            r"SELECT c.id, a.slug, w.path, c.source_id, c.source_path, c.title, c.started_at,
+                     (SELECT COUNT(*) FROM messages m WHERE m.conversation_id = c.id)
+              FROM conversations c
+              JOIN agents a ON c.agent_id = a.id
+              LEFT JOIN workspaces w ON c.workspace_id = w.id
+              {where_sql}
+              ORDER BY c.started_at IS NULL, c.started_at, c.id"
+        ))?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(values), |row| {
+            Ok(SelectedConversation {
+                conversation_id: row.get(0)?,
+                agent: row.get(1)?,
+                workspace: row.get(2)?,
+                source_id: row.get(3)?,
+                source_path: row.get(4)?,
+                title: row.get(5)?,
+                started_at: row.get(6)?,
+                message_count: row.get(7)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Ids of every message in the given conversations.
+    pub fn message_ids_for_conversations(&self, conversation_ids: &[i64]) -> Result<Vec<i64>> {
+        let mut out = Vec::new();
+        for chunk in conversation_ids.chunks(DELETE_CHUNK) {
+            let mut stmt = self.conn.prepare(&format!(
+                "SELECT id FROM messages WHERE conversation_id IN ({}) ORDER BY id",
+                sql_placeholders(chunk.len())
+            ))?;
+            let rows = stmt.query_map(rusqlite::params_from_iter(chunk), |row| row.get(0))?;
+            for r in rows {
+                out.push(r?);
+            }
+        }
+        Ok(out)
+    }
+
+    /// Delete conversations with their messages, snippets, tags and FTS rows in
+    /// one transaction, subtracting them from `daily_stats`.
+    pub fn delete_conversations(
+        &mut self,
+        conversation_ids: &[i64],
+    ) -> Result<DeletedConversations> {
+        let tx = self.conn.transaction()?;
+        let deleted = delete_conversations_in_tx(&tx, conversation_ids)?;
+        tx.commit()?;
+        Ok(deleted)
+    }
+
+    // -------------------------------------------------------------------------
+    // Conversation Tags - user labels on whole conversations
+    // -------------------------------------------------------------------------
+
+    /// Ids of all conversations stored for a session file.
+    pub fn conversation_ids_for_path(&self, source_path: &str) -> Result<Vec<i64>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT id FROM conversations WHERE source_path = ? ORDER BY id")?;
+        let rows = stmt.query_map(params![source_path], |row| row.get(0))?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Get the id of a tag, creating it if needed. Names are normalized
+    /// (see [`normalize_tag_name`]).
+    pub fn ensure_tag(&self, name: &str) -> Result<i64> {
+        let name = normalize_tag_name(name)?;
+        self.conn
+            .execute("INSERT OR IGNORE INTO tags(name) VALUES(?)", params![name])?;
+        self.conn
+            .query_row("SELECT id FROM tags WHERE name = ?", params![name], |row| {
+                row.get(0)
+            })
+            .with_context(|| format!("fetching tag id for '{name}'"))
+    }
+
+    /// Attach a tag to a conversation. Returns false if it was already tagged.
+    pub fn add_conversation_tag(&self, conversation_id: i64, name: &str) -> Result<bool> {
+        let tag_id = self.ensure_tag(name)?;
+        let rows = self.conn.execute(
+            "INSERT OR IGNORE INTO conversation_tags(conversation_id, tag_id) VALUES(?, ?)",
+            params![conversation_id, tag_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Detach a tag from a conversation. Tags left without conversations are dropped.
+    pub fn remove_conversation_tag(&self, conversation_id: i64, name: &str) -> Result<bool> {
+        let name = normalize_tag_name(name)?;
+        let rows = self.conn.execute(
+            "DELETE FROM conversation_tags
+             WHERE conversation_id = ? AND tag_id = (SELECT id FROM tags WHERE name = ?)",
+            params![conversation_id, name],
+        )?;
+        self.conn.execute(
+            "DELETE FROM tags WHERE id NOT IN (SELECT DISTINCT tag_id FROM conversation_tags)",
+            [],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Tags attached to a conversation, ordered by name.
+    pub fn conversation_tags(&self, conversation_id: i64) -> Result<Vec<Tag>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.id, t.name FROM tags t
+             JOIN conversation_tags ct ON ct.tag_id = t.id
+             WHERE ct.conversation_id = ?
+             ORDER BY t.name",
+        )?;
+        let rows = stmt.query_map(params![conversation_id], |row| {
+            Ok(Tag {
+                id: Some(row.get(0)?),
+                name: row.get(1)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// All tags with the number of conversations carrying each, ordered by name.
+    pub fn list_tags(&self) -> Result<Vec<(Tag, i64)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.id, t.name, COUNT(ct.conversation_id) FROM tags t
+             LEFT JOIN conversation_tags ct ON ct.tag_id = t.id
+             GROUP BY t.id
+             ORDER BY t.name",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                Tag {
+                    id: Some(row.get(0)?),
+                    name: row.get(1)?,
+                },
+                row.get(2)?,
+            ))
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Session paths of conversations carrying any of `tags`.
+    pub fn tagged_session_paths(&self, tags: &HashSet<String>) -> Result<HashSet<String>> {
+        tagged_session_paths(&self.conn, tags)
+    }
+
+    /// Files touched by the tool calls of every conversation stored for `source_path`,
+    /// most recently touched first.
+    pub fn session_files(&self, source_path: &str) -> Result<Vec<TouchedFile>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.file_path, MAX(s.language),
+                    SUM(CASE WHEN s.snippet_text IS NOT NULL THEN 1 ELSE 0 END),
+                    SUM(CASE WHEN s.snippet_text IS NULL THEN 1 ELSE 0 END),
+                    MIN(s.start_line), MAX(s.end_line), MAX(m.created_at)
+             FROM snippets s
+             JOIN messages m ON m.id = s.message_id
+             JOIN conversations c ON c.id = m.conversation_id
+             WHERE c.source_path = ? AND s.file_path IS NOT NULL
+             GROUP BY s.file_path
+             ORDER BY MAX(m.created_at) DESC, s.file_path",
+        )?;
+        let rows = stmt.query_map(params![source_path], |row| {
+            Ok(TouchedFile {
+                file_path: row.get(0)?,
+                language: row.get(1)?,
+                edits: row.get(2)?,
+                reads: row.get(3)?,
+                first_line: row.get(4)?,
+                last_line: row.get(5)?,
+                last_touched_at: row.get(6)?,
+            })
+        })?;
+        let mut out = Vec::new();
+        for r in rows {
+            out.push(r?);
+        }
+        Ok(out)
+    }
+
+    /// Session paths whose tool calls touched any of `files`.
+    pub fn session_paths_touching_files(&self, files: &HashSet<String>) -> Result<HashSet<String>> {
+        session_paths_touching_files(&self.conn, files)
+    }
+
     // -------------------------------------------------------------------------
     // Daily Stats (Opt 3.2) - Materialized Aggregates for O(1) Range Queries
     // -------------------------------------------------------------------------
@@ -1730,6 +2166,11 @@ impl SqliteStorage {
             params![now],
         )?;
 
+        // Token columns come from message_usage
+        for (agent, source_id, started_at, usage) in conversation_token_totals(&tx, "1", [])? {
+            add_daily_tokens_in_tx(&tx, &agent, &source_id, started_at, &usage, 1)?;
+        }
+
         let rows_created: i64 =
             tx.query_row("SELECT COUNT(*) FROM daily_stats", [], |r| r.get(0))?;
         let total_sessions: i64 = tx.query_row(
@@ -2035,6 +2476,8 @@ fn migrate(conn: &mut Connection) -> Result<()> {
             tx.execute_batch(MIGRATION_V6)?;
             tx.execute_batch(MIGRATION_V7)?;
             tx.execute_batch(MIGRATION_V8)?;
+            tx.execute_batch(MIGRATION_V9)?;
+            tx.execute_batch(MIGRATION_V10)?;
         }
         1 => {
             tx.execute_batch(MIGRATION_V2)?;
@@ -2044,6 +2487,8 @@ fn migrate(conn: &mut Connection) -> Result<()> {
             tx.execute_batch(MIGRATION_V6)?;
             tx.execute_batch(MIGRATION_V7)?;
             tx.execute_batch(MIGRATION_V8)?;
+            tx.execute_batch(MIGRATION_V9)?;
+            tx.execute_batch(MIGRATION_V10)?;
         }
         2 => {
             tx.execute_batch(MIGRATION_V3)?;
@@ -2052,6 +2497,8 @@ fn migrate(conn: &mut Connection) -> Result<()> {
             tx.execute_batch(MIGRATION_V6)?;
             tx.execute_batch(MIGRATION_V7)?;
             tx.execute_batch(MIGRATION_V8)?;
+            tx.execute_batch(MIGRATION_V9)?;
+            tx.execute_batch(MIGRATION_V10)?;
         }
         3 => {
             tx.execute_batch(MIGRATION_V4)?;
@@ -2059,24 +2506,41 @@ fn migrate(conn: &mut Connection) -> Result<()> {
             tx.execute_batch(MIGRATION_V6)?;
             tx.execute_batch(MIGRATION_V7)?;
             tx.execute_batch(MIGRATION_V8)?;
+            tx.execute_batch(MIGRATION_V9)?;
+            tx.execute_batch(MIGRATION_V10)?;
         }
         4 => {
             tx.execute_batch(MIGRATION_V5)?;
             tx.execute_batch(MIGRATION_V6)?;
             tx.execute_batch(MIGRATION_V7)?;
             tx.execute_batch(MIGRATION_V8)?;
+            tx.execute_batch(MIGRATION_V9)?;
+            tx.execute_batch(MIGRATION_V10)?;
         }
         5 => {
             tx.execute_batch(MIGRATION_V6)?;
             tx.execute_batch(MIGRATION_V7)?;
             tx.execute_batch(MIGRATION_V8)?;
+            tx.execute_batch(MIGRATION_V9)?;
+            tx.execute_batch(MIGRATION_V10)?;
         }
         6 => {
             tx.execute_batch(MIGRATION_V7)?;
             tx.execute_batch(MIGRATION_V8)?;
+            tx.execute_batch(MIGRATION_V9)?;
+            tx.execute_batch(MIGRATION_V10)?;
         }
         7 => {
             tx.execute_batch(MIGRATION_V8)?;
+            tx.execute_batch(MIGRATION_V9)?;
+            tx.execute_batch(MIGRATION_V10)?;
+        }
+        8 => {
+            tx.execute_batch(MIGRATION_V9)?;
+            tx.execute_batch(MIGRATION_V10)?;
+        }
+        9 => {
+            tx.execute_batch(MIGRATION_V10)?;
         }
         v => return Err(anyhow!("unsupported schema version {v}")),
     }
@@ -2147,6 +2611,191 @@ fn insert_message(tx: &Transaction<'_>, conversation_id: i64, msg: &Message) ->
     Ok(tx.last_insert_rowid())
 }
 
+/// Store the tokens a connector reported for `msg` (if any) and add them to
+/// `total`, which callers flush with `update_daily_tokens_in_tx`.
+fn insert_message_usage(
+    tx: &Transaction<'_>,
+    message_id: i64,
+    msg: &Message,
+    total: &mut TokenUsage,
+) -> Result<()> {
+    let Some(usage) = TokenUsage::from_extra(&msg.extra_json) else {
+        return Ok(());
+    };
+    tx.execute(
+        "INSERT OR REPLACE INTO message_usage(message_id, model, input_tokens, output_tokens, cache_read_tokens, cache_write_tokens)
+         VALUES(?,?,?,?,?,?)",
+        params![
+            message_id,
+            usage.model,
+            usage.input_tokens,
+            usage.output_tokens,
+            usage.cache_read_tokens,
+            usage.cache_write_tokens,
+        ],
+    )?;
+    total.add(&usage);
+    Ok(())
+}
+
+/// Update `message_usage` for messages already stored (`idx <= cutoff`) whose
+/// reported usage changed since they were indexed; Codex, for one, credits a
+/// turn's later requests to its last agent message. New usage goes to `total`
+/// and the replaced rows to `retracted`, for the caller to flush.
+fn refresh_stored_usage(
+    tx: &Transaction<'_>,
+    conversation_id: i64,
+    conv: &Conversation,
+    cutoff: i64,
+    total: &mut TokenUsage,
+    retracted: &mut TokenUsage,
+) -> Result<()> {
+    let reported: BTreeMap<i64, &Message> = conv
+        .messages
+        .iter()
+        .filter(|msg| msg.idx <= cutoff && TokenUsage::from_extra(&msg.extra_json).is_some())
+        .map(|msg| (msg.idx, msg))
+        .collect();
+    if reported.is_empty() {
+        return Ok(());
+    }
+    let mut stmt = tx.prepare(
+        "SELECT m.idx, m.id, u.model, u.input_tokens, u.output_tokens, u.cache_read_tokens, u.cache_write_tokens
+         FROM messages m LEFT JOIN message_usage u ON u.message_id = m.id
+         WHERE m.conversation_id = ?",
+    )?;
+    let stored = stmt
+        .query_map(params![conversation_id], |row| {
+            let usage = match row.get::<_, Option<i64>>(3)? {
+                Some(input_tokens) => Some(TokenUsage {
+                    model: row.get(2)?,
+                    input_tokens,
+                    output_tokens: row.get(4)?,
+                    cache_read_tokens: row.get(5)?,
+                    cache_write_tokens: row.get(6)?,
+                }),
+                None => None,
+            };
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, usage))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (idx, message_id, old) in stored {
+        let Some(msg) = reported.get(&idx) else {
+            continue;
+        };
+        if old == TokenUsage::from_extra(&msg.extra_json) {
+            continue;
+        }
+        if let Some(old) = &old {
+            retracted.add(old);
+        }
+        insert_message_usage(tx, message_id, msg, total)?;
+    }
+    Ok(())
+}
+
+/// Add (`sign = 1`) or subtract (`sign = -1`) token totals in daily_stats for
+/// the same four agent/source permutations as `update_daily_stats_in_tx`.
+fn update_daily_tokens_in_tx(
+    tx: &Transaction<'_>,
+    conv: &Conversation,
+    usage: &TokenUsage,
+    sign: i64,
+) -> Result<()> {
+    add_daily_tokens_in_tx(
+        tx,
+        &conv.agent_slug,
+        &conv.source_id,
+        conv.started_at,
+        usage,
+        sign,
+    )
+}
+
+fn add_daily_tokens_in_tx(
+    tx: &Transaction<'_>,
+    agent_slug: &str,
+    source_id: &str,
+    started_at_ms: Option<i64>,
+    usage: &TokenUsage,
+    sign: i64,
+) -> Result<()> {
+    if usage.is_empty() {
+        return Ok(());
+    }
+    let day_id = started_at_ms
+        .map(SqliteStorage::day_id_from_millis)
+        .unwrap_or(0);
+    let now = SqliteStorage::now_millis();
+    let mut keys = vec![
+        (agent_slug, source_id),
+        ("all", source_id),
+        (agent_slug, "all"),
+        ("all", "all"),
+    ];
+    keys.sort_unstable();
+    keys.dedup();
+    for (agent, source) in keys {
+        tx.execute(
+            "INSERT INTO daily_stats (day_id, agent_slug, source_id, session_count, message_count, total_chars, last_updated,
+                                      input_tokens, output_tokens, cache_read_tokens, cache_write_tokens)
+             VALUES (?, ?, ?, 0, 0, 0, ?, ?, ?, ?, ?)
+             ON CONFLICT(day_id, agent_slug, source_id) DO UPDATE SET
+                 input_tokens = input_tokens + excluded.input_tokens,
+                 output_tokens = output_tokens + excluded.output_tokens,
+                 cache_read_tokens = cache_read_tokens + excluded.cache_read_tokens,
+                 cache_write_tokens = cache_write_tokens + excluded.cache_write_tokens,
+                 last_updated = excluded.last_updated",
+            params![
+                day_id,
+                agent,
+                source,
+                now,
+                sign * usage.input_tokens,
+                sign * usage.output_tokens,
+                sign * usage.cache_read_tokens,
+                sign * usage.cache_write_tokens,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// Per-conversation token totals as (agent slug, source id, started_at, usage),
+/// restricted by `filter` (a WHERE clause over `c`).
+fn conversation_token_totals<P: rusqlite::Params>(
+    tx: &Transaction<'_>,
+    filter: &str,
+    params: P,
+) -> Result<Vec<(String, String, Option<i64>, TokenUsage)>> {
+    let mut stmt = tx.prepare(&format!(
+        r"SELECT a.slug, c.source_id, c.started_at,
+                 SUM(u.input_tokens), SUM(u.output_tokens),
+                 SUM(u.cache_read_tokens), SUM(u.cache_write_tokens)
+          FROM message_usage u
+          JOIN messages m ON m.id = u.message_id
+          JOIN conversations c ON c.id = m.conversation_id
+          JOIN agents a ON a.id = c.agent_id
+          WHERE {filter}
+          GROUP BY c.id"
+    ))?;
+    let rows = stmt.query_map(params, |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<i64>>(2)?,
+            TokenUsage {
+                model: None,
+                input_tokens: row.get(3)?,
+                output_tokens: row.get(4)?,
+                cache_read_tokens: row.get(5)?,
+                cache_write_tokens: row.get(6)?,
+            },
+        ))
+    })?;
+    rows.collect::<rusqlite::Result<_>>().map_err(Into::into)
+}
+
 fn insert_snippets(tx: &Transaction<'_>, message_id: i64, snippets: &[Snippet]) -> Result<()> {
     for snip in snippets {
         tx.execute(
@@ -2325,17 +2974,30 @@ fn insert_conversation_in_tx_batched(
 
             let mut inserted_indices = Vec::new();
             let mut new_chars: i64 = 0;
+            let mut usage = TokenUsage::default();
+            let mut retracted = TokenUsage::default();
+            refresh_stored_usage(
+                tx,
+                conversation_id,
+                conv,
+                cutoff,
+                &mut usage,
+                &mut retracted,
+            )?;
             for msg in &conv.messages {
                 if msg.idx <= cutoff {
                     continue;
                 }
                 let msg_id = insert_message(tx, conversation_id, msg)?;
                 insert_snippets(tx, msg_id, &msg.snippets)?;
+                insert_message_usage(tx, msg_id, msg, &mut usage)?;
                 // Collect FTS entry instead of inserting immediately
                 fts_entries.push(FtsEntry::from_message(msg_id, msg, conv));
                 inserted_indices.push(msg.idx);
                 new_chars += msg.content.len() as i64;
             }
+            update_daily_tokens_in_tx(tx, conv, &retracted, -1)?;
+            update_daily_tokens_in_tx(tx, conv, &usage, 1)?;
 
             // Update metadata fields and ended_at
             if !inserted_indices.is_empty() {
@@ -2391,13 +3053,16 @@ fn insert_conversation_in_tx_batched(
     // Insert new conversation
     let conv_id = insert_conversation(tx, agent_id, workspace_id, conv)?;
     let mut total_chars: i64 = 0;
+    let mut usage = TokenUsage::default();
     for msg in &conv.messages {
         let msg_id = insert_message(tx, conv_id, msg)?;
         insert_snippets(tx, msg_id, &msg.snippets)?;
+        insert_message_usage(tx, msg_id, msg, &mut usage)?;
         // Collect FTS entry instead of inserting immediately
         fts_entries.push(FtsEntry::from_message(msg_id, msg, conv));
         total_chars += msg.content.len() as i64;
     }
+    update_daily_tokens_in_tx(tx, conv, &usage, 1)?;
 
     // Note: Daily stats update skipped here to prevent double counting.
     // The caller (ingest_batch) handles stats aggregation efficiently.
@@ -2490,6 +3155,245 @@ fn agent_kind_str(kind: AgentKind) -> String {
     }
 }
 
+/// Normalize a conversation tag name: trimmed, lowercase, non-empty.
+pub fn normalize_tag_name(name: &str) -> Result<String> {
+    let normalized = name.trim().to_lowercase();
+    if normalized.is_empty() {
+        return Err(anyhow!("tag name cannot be empty"));
+    }
+    if normalized.contains(char::is_whitespace) || normalized.contains(',') {
+        return Err(anyhow!(
+            "tag name '{normalized}' cannot contain whitespace or commas"
+        ));
+    }
+    Ok(normalized)
+}
+
+/// Session paths of conversations carrying any of `tags` (matched after normalization).
+///
+/// Takes a raw connection so the search client can resolve `SearchFilters::tags`
+/// without going through `SqliteStorage`.
+pub fn tagged_session_paths(conn: &Connection, tags: &HashSet<String>) -> Result<HashSet<String>> {
+    let mut out = HashSet::new();
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT c.source_path FROM conversations c
+         JOIN conversation_tags ct ON ct.conversation_id = c.id
+         JOIN tags t ON t.id = ct.tag_id
+         WHERE t.name = ?",
+    )?;
+    for tag in tags {
+        let Ok(name) = normalize_tag_name(tag) else {
+            continue;
+        };
+        let rows = stmt.query_map(params![name], |row| row.get::<_, String>(0))?;
+        for r in rows {
+            out.insert(r?);
+        }
+    }
+    Ok(out)
+}
+
+/// Whether a snippet's stored `file_path` refers to the file a user asked about.
+///
+/// Agents record paths both absolute and workspace-relative, so a query matches when
+/// either path is a whole-component suffix of the other: `src/foo.rs` matches
+/// `/home/me/proj/src/foo.rs`, and `/home/me/proj/src/foo.rs` matches a stored
+/// `src/foo.rs`. `foo.rs` never matches `barfoo.rs`.
+pub fn file_path_matches(stored: &str, wanted: &str) -> bool {
+    fn normalize(p: &str) -> String {
+        let p = p.trim().replace('\\', "/");
+        p.strip_prefix("./").unwrap_or(&p).to_string()
+    }
+    let stored = normalize(stored);
+    let wanted = normalize(wanted);
+    if stored.is_empty() || wanted.is_empty() {
+        return false;
+    }
+    let is_suffix = |long: &str, short: &str| {
+        long == short
+            || long
+                .strip_suffix(short)
+                .is_some_and(|head| head.ends_with('/'))
+    };
+    is_suffix(&stored, &wanted) || is_suffix(&wanted, &stored)
+}
+
+/// Session paths of conversations whose tool call snippets touched any of `files`
+/// (see [`file_path_matches`]).
+///
+/// Takes a raw connection so the search client can resolve `SearchFilters::files`
+/// without going through `SqliteStorage`.
+pub fn session_paths_touching_files(
+    conn: &Connection,
+    files: &HashSet<String>,
+) -> Result<HashSet<String>> {
+    let mut out = HashSet::new();
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT c.source_path, s.file_path FROM snippets s
+         JOIN messages m ON m.id = s.message_id
+         JOIN conversations c ON c.id = m.conversation_id
+         WHERE instr(s.file_path, ?) > 0",
+    )?;
+    for wanted in files {
+        // Prefilter on the file name; file_path_matches decides the rest.
+        let name = wanted.trim().rsplit(['/', '\\']).next().unwrap_or_default();
+        if name.is_empty() {
+            continue;
+        }
+        let rows = stmt.query_map(params![name], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        for r in rows {
+            let (source_path, file_path) = r?;
+            if file_path_matches(&file_path, wanted) {
+                out.insert(source_path);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Ids per statement when deleting conversations, well under SQLite's variable limit.
+const DELETE_CHUNK: usize = 500;
+
+fn sql_placeholders(count: usize) -> String {
+    vec!["?"; count].join(", ")
+}
+
+/// Quote each word of `query` as an FTS5 phrase so user text (paths, secrets,
+/// punctuation) is matched literally; all words must appear.
+fn fts_phrase_query(query: &str) -> Option<String> {
+    let phrases: Vec<String> = query
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+    (!phrases.is_empty()).then(|| phrases.join(" "))
+}
+
+fn delete_conversations_in_tx(
+    tx: &Transaction<'_>,
+    conversation_ids: &[i64],
+) -> Result<DeletedConversations> {
+    let mut deleted = DeletedConversations::default();
+    for chunk in conversation_ids.chunks(DELETE_CHUNK) {
+        let ids = sql_placeholders(chunk.len());
+        let chunk_params = || rusqlite::params_from_iter(chunk);
+
+        // Totals to subtract from daily_stats (chars are bytes, as at insert time)
+        let mut stats = Vec::new();
+        {
+            let mut stmt = tx.prepare(&format!(
+                r"SELECT a.slug, c.source_id, c.started_at, COUNT(m.id),
+                         COALESCE(SUM(LENGTH(CAST(m.content AS BLOB))), 0)
+                  FROM conversations c
+                  JOIN agents a ON c.agent_id = a.id
+                  LEFT JOIN messages m ON m.conversation_id = c.id
+                  WHERE c.id IN ({ids})
+                  GROUP BY c.id"
+            ))?;
+            let rows = stmt.query_map(chunk_params(), |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<i64>>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, i64>(4)?,
+                ))
+            })?;
+            for r in rows {
+                stats.push(r?);
+            }
+        }
+
+        let tokens = conversation_token_totals(tx, &format!("c.id IN ({ids})"), chunk_params())?;
+
+        let messages_of = format!("SELECT id FROM messages WHERE conversation_id IN ({ids})");
+        tx.execute(
+            &format!("DELETE FROM fts_messages WHERE message_id IN ({messages_of})"),
+            chunk_params(),
+        )?;
+        tx.execute(
+            &format!("DELETE FROM message_usage WHERE message_id IN ({messages_of})"),
+            chunk_params(),
+        )?;
+        tx.execute(
+            &format!("DELETE FROM snippets WHERE message_id IN ({messages_of})"),
+            chunk_params(),
+        )?;
+        deleted.messages += tx.execute(
+            &format!("DELETE FROM messages WHERE conversation_id IN ({ids})"),
+            chunk_params(),
+        )?;
+        tx.execute(
+            &format!("DELETE FROM conversation_tags WHERE conversation_id IN ({ids})"),
+            chunk_params(),
+        )?;
+        tx.execute(
+            &format!("DELETE FROM conversation_redactions WHERE conversation_id IN ({ids})"),
+            chunk_params(),
+        )?;
+        deleted.conversations += tx.execute(
+            &format!("DELETE FROM conversations WHERE id IN ({ids})"),
+            chunk_params(),
+        )?;
+
+        for (agent, source_id, started_at, messages, chars) in stats {
+            update_daily_stats_in_tx(tx, &agent, &source_id, started_at, -1, -messages, -chars)?;
+        }
+        for (agent, source_id, started_at, usage) in tokens {
+            add_daily_tokens_in_tx(tx, &agent, &source_id, started_at, &usage, -1)?;
+        }
+    }
+    tx.execute(
+        "DELETE FROM daily_stats WHERE session_count <= 0 AND message_count <= 0",
+        [],
+    )?;
+    Ok(deleted)
+}
+
+/// Session paths of conversations whose title contains any of `titles`
+/// (case-insensitive).
+pub fn session_paths_with_title(
+    conn: &Connection,
+    titles: &HashSet<String>,
+) -> Result<HashSet<String>> {
+    let mut out = HashSet::new();
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT source_path FROM conversations
+         WHERE instr(lower(title), lower(?)) > 0",
+    )?;
+    for title in titles {
+        let title = title.trim();
+        if title.is_empty() {
+            continue;
+        }
+        let rows = stmt.query_map(params![title], |row| row.get::<_, String>(0))?;
+        for r in rows {
+            out.insert(r?);
+        }
+    }
+    Ok(out)
+}
+
+/// Ids of the messages in the sessions at `paths`, for filters the vector index cannot apply.
+pub fn message_ids_for_session_paths(
+    conn: &Connection,
+    paths: &HashSet<String>,
+) -> Result<HashSet<u64>> {
+    let mut out = HashSet::new();
+    let mut stmt = conn.prepare(
+        "SELECT m.id FROM messages m JOIN conversations c ON c.id = m.conversation_id
+         WHERE c.source_path = ?",
+    )?;
+    for path in paths {
+        let rows = stmt.query_map(params![path], |row| row.get::<_, i64>(0))?;
+        for r in rows {
+            out.insert(r? as u64);
+        }
+    }
+    Ok(out)
+}
+
 // =============================================================================
 // Tests (bead yln.4)
 // =============================================================================
@@ -2499,6 +3403,37 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    // =========================================================================
+    // Tag name normalization
+    // =========================================================================
+
+    #[test]
+    fn file_path_matches_on_component_suffix() {
+        assert!(file_path_matches("/home/me/proj/src/foo.rs", "src/foo.rs"));
+        assert!(file_path_matches("src/foo.rs", "/home/me/proj/src/foo.rs"));
+        assert!(file_path_matches("./src/foo.rs", "src/foo.rs"));
+        assert!(file_path_matches("C:\\proj\\src\\foo.rs", "src/foo.rs"));
+        assert!(!file_path_matches("/proj/src/barfoo.rs", "foo.rs"));
+        assert!(!file_path_matches("/proj/src/foo.rs", "lib/foo.rs"));
+        assert!(!file_path_matches("/proj/src/foo.rs", ""));
+    }
+
+    #[test]
+    fn normalize_tag_name_trims_and_lowercases() {
+        assert_eq!(normalize_tag_name("  PostMortem ").unwrap(), "postmortem");
+        assert_eq!(
+            normalize_tag_name("migration-2026").unwrap(),
+            "migration-2026"
+        );
+    }
+
+    #[test]
+    fn normalize_tag_name_rejects_empty_and_whitespace() {
+        assert!(normalize_tag_name("   ").is_err());
+        assert!(normalize_tag_name("two words").is_err());
+        assert!(normalize_tag_name("a,b").is_err());
+    }
+
     // =========================================================================
     // User data file protection tests (bead yln.4)
     // =========================================================================
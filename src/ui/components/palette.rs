@@ -20,6 +20,7 @@ pub enum PaletteAction {
     OpenUpdateBanner,
     FilterAgent,
     FilterWorkspace,
+    FilterRole,
     FilterToday,
     FilterWeek,
     FilterCustomDate,
@@ -28,6 +29,9 @@ pub enum PaletteAction {
     LoadViewSlot(u8),
     OpenBulkActions,
     ReloadIndex,
+    ToggleBookmark,
+    OpenBookmarks,
+    TagConversation,
 }
 
 /// Render-ready descriptor for an action.
@@ -188,6 +192,11 @@ pub fn default_actions() -> Vec<PaletteItem> {
             "Filter: workspace",
             "Set workspace filter",
         ),
+        item(
+            PaletteAction::FilterRole,
+            "Filter: role",
+            "Cycle user/assistant/tool/system (Alt+R)",
+        ),
         item(
             PaletteAction::FilterToday,
             "Filter: today",
@@ -218,6 +227,21 @@ pub fn default_actions() -> Vec<PaletteItem> {
             "Saved views",
             "List saved slots",
         ),
+        item(
+            PaletteAction::ToggleBookmark,
+            "Bookmark result",
+            "Add/remove bookmark (Alt+B)",
+        ),
+        item(
+            PaletteAction::OpenBookmarks,
+            "Bookmarks",
+            "Browse bookmarks (Alt+M)",
+        ),
+        item(
+            PaletteAction::TagConversation,
+            "Tag conversation",
+            "Add/remove tags (Alt+T)",
+        ),
     ];
     // Slots 1-9
     for slot in 1..=9 {
@@ -576,7 +600,11 @@ mod tests {
         assert!(labels.contains(&"Toggle theme"));
         assert!(labels.contains(&"Toggle density"));
         assert!(labels.contains(&"Filter: agent"));
+        assert!(labels.contains(&"Filter: role"));
         assert!(labels.contains(&"Reload index/view"));
+        assert!(labels.contains(&"Bookmark result"));
+        assert!(labels.contains(&"Bookmarks"));
+        assert!(labels.contains(&"Tag conversation"));
     }
 
     #[test]
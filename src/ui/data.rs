@@ -1,3 +1,4 @@
+use crate::connectors::claude_code::{SessionThreads, SubagentThread};
 use crate::model::types::{Conversation, Message, MessageRole, Workspace};
 use crate::storage::sqlite::SqliteStorage;
 use crate::ui::components::theme::ThemePalette;
@@ -5,6 +6,7 @@ use anyhow::Result;
 use lru::LruCache;
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
+use rusqlite::OptionalExtension;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -23,6 +25,8 @@ pub enum InputMode {
     PaneFilter,
     /// Inline find within the detail pane (local, non-indexed)
     DetailFind,
+    /// Tag prompt for the active or bulk-selected conversations
+    Tag,
 }
 
 // -------------------------------------------------------------------------
@@ -34,6 +38,42 @@ pub struct ConversationView {
     pub convo: Conversation,
     pub messages: Vec<Message>,
     pub workspace: Option<Workspace>,
+    /// Subagent transcripts spawned by this conversation (Claude Code)
+    pub subagents: Vec<SubagentView>,
+}
+
+/// A subagent thread whose messages live in a separate transcript.
+#[derive(Clone, Debug)]
+pub struct SubagentView {
+    pub thread: SubagentThread,
+    pub messages: Vec<Message>,
+}
+
+/// Indexed messages of the subagent transcripts listed in `metadata`.
+fn load_subagents(
+    storage: &SqliteStorage,
+    metadata: &serde_json::Value,
+) -> Result<Vec<SubagentView>> {
+    let mut views = Vec::new();
+    for thread in SessionThreads::from_metadata(metadata).subagents {
+        let Some(transcript) = &thread.transcript else {
+            continue;
+        };
+        let id: Option<i64> = storage
+            .raw()
+            .query_row(
+                "SELECT id FROM conversations WHERE source_path = ?1
+                 ORDER BY started_at DESC LIMIT 1",
+                [transcript.to_string_lossy()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(id) = id {
+            let messages = storage.fetch_messages(id)?;
+            views.push(SubagentView { thread, messages });
+        }
+    }
+    Ok(views)
 }
 
 // -------------------------------------------------------------------------
@@ -237,10 +277,12 @@ fn load_conversation_uncached(
             display_name: row.get(4).ok().flatten(),
         });
         let messages = storage.fetch_messages(convo_id)?;
+        let subagents = load_subagents(storage, &convo.metadata_json)?;
         return Ok(Some(ConversationView {
             convo,
             messages,
             workspace,
+            subagents,
         }));
     }
     Ok(None)
@@ -377,6 +419,7 @@ mod tests {
                 path: PathBuf::from("/test/workspace"),
                 display_name: None,
             }),
+            subagents: Vec::new(),
         }
     }
 
@@ -26,12 +26,16 @@ pub const HISTORY_CYCLE: &str = "Ctrl+R";
 pub const SCOPE_AGENT: &str = "Shift+F3";
 pub const SCOPE_WORKSPACE: &str = "Shift+F4";
 pub const CYCLE_TIME_PRESETS: &str = "Shift+F5";
+pub const CYCLE_ROLE: &str = "Alt+R";
 
 // Actions
 pub const COPY: &str = "y";
 pub const BULK_MENU: &str = "A";
 pub const TOGGLE_SELECT: &str = "Ctrl+X";
 pub const PANE_FILTER: &str = "/";
+pub const BOOKMARK: &str = "Alt+B";
+pub const BOOKMARKS_PANE: &str = "Alt+M";
+pub const TAG_CONVERSATION: &str = "Alt+T";
 
 // Navigation
 pub const TAB_FOCUS: &str = "Tab";
@@ -117,6 +121,7 @@ mod tests {
         assert!(!SCOPE_AGENT.is_empty());
         assert!(!SCOPE_WORKSPACE.is_empty());
         assert!(!CYCLE_TIME_PRESETS.is_empty());
+        assert!(!CYCLE_ROLE.is_empty());
     }
 
     #[test]
@@ -124,6 +129,7 @@ mod tests {
         assert_eq!(SCOPE_AGENT, "Shift+F3");
         assert_eq!(SCOPE_WORKSPACE, "Shift+F4");
         assert_eq!(CYCLE_TIME_PRESETS, "Shift+F5");
+        assert_eq!(CYCLE_ROLE, "Alt+R");
     }
 
     // =========================================================================
@@ -135,6 +141,9 @@ mod tests {
         assert!(!COPY.is_empty());
         assert!(!BULK_MENU.is_empty());
         assert!(!PANE_FILTER.is_empty());
+        assert!(!BOOKMARK.is_empty());
+        assert!(!BOOKMARKS_PANE.is_empty());
+        assert!(!TAG_CONVERSATION.is_empty());
     }
 
     #[test]
@@ -142,6 +151,9 @@ mod tests {
         assert_eq!(COPY, "y");
         assert_eq!(BULK_MENU, "A");
         assert_eq!(PANE_FILTER, "/");
+        assert_eq!(BOOKMARK, "Alt+B");
+        assert_eq!(BOOKMARKS_PANE, "Alt+M");
+        assert_eq!(TAG_CONVERSATION, "Alt+T");
     }
 
     // =========================================================================
@@ -306,6 +318,10 @@ mod tests {
             HISTORY_PREV,
             HISTORY_CYCLE,
             TOGGLE_SELECT,
+            BOOKMARK,
+            BOOKMARKS_PANE,
+            TAG_CONVERSATION,
+            CYCLE_ROLE,
         ];
 
         for key in &modifier_keys {
@@ -28,15 +28,17 @@ use syntect::easy::HighlightLines;
 use syntect::highlighting::{Theme, ThemeSet};
 use syntect::parsing::SyntaxSet;
 
+use crate::bookmarks::{Bookmark, BookmarkStore};
+use crate::connectors::claude_code::SessionThreads;
 use crate::default_data_dir;
 use crate::html_export::{HtmlExporter, Message as HtmlMessage, TemplateMetadata};
-use crate::model::types::MessageRole;
+use crate::model::types::{Message, MessageRole};
 use crate::search::model_download::{DownloadProgress, ModelDownloader, ModelManifest};
 use crate::search::model_manager::{
     SemanticAvailability, default_model_dir, load_hash_semantic_context, load_semantic_context,
 };
 use crate::search::query::{
-    CacheStats, QuerySuggestion, SearchClient, SearchFilters, SearchHit, SearchMode,
+    CacheStats, QUERY_FIELDS, QuerySuggestion, SearchClient, SearchFilters, SearchHit, SearchMode,
 };
 use crate::search::tantivy::index_dir;
 use crate::ui::components::export_modal::{
@@ -72,7 +74,7 @@ enum ExportTaskEvent {
     Failed(String),
 }
 
-fn html_role_slug(role: &MessageRole) -> String {
+pub(crate) fn html_role_slug(role: &MessageRole) -> String {
     match role {
         MessageRole::User => "user".to_string(),
         MessageRole::Agent => "assistant".to_string(),
@@ -918,6 +920,8 @@ pub fn help_lines(palette: ThemePalette) -> Vec<Line<'static>> {
                 shortcuts::FILTER_AGENT, shortcuts::FILTER_WORKSPACE, shortcuts::FILTER_DATE_FROM, shortcuts::FILTER_DATE_TO, shortcuts::CLEAR_FILTERS),
             format!("{} scope to active agent | {} clear scope | {} cycle time presets (24h/7d/30d/all)",
                 shortcuts::SCOPE_AGENT, shortcuts::SCOPE_WORKSPACE, shortcuts::CYCLE_TIME_PRESETS),
+            format!("{} cycle role filter: all → user → assistant → tool → system (click the role pill too)",
+                shortcuts::CYCLE_ROLE),
             "Chips in search bar; Backspace removes last; Enter (query empty) edits last chip".to_string(),
         ],
     ));
@@ -998,7 +1002,7 @@ pub fn help_lines(palette: ThemePalette) -> Vec<Line<'static>> {
         "Actions",
         &[
             format!(
-                "{} opens detail modal (o=open, c=copy, p=path, s=snip, n=nano, Esc=close)",
+                "{} opens detail modal (o=open, c=copy, p=path, s=snip, n=nano, t=subagents, Esc=close)",
                 shortcuts::DETAIL_OPEN
             ),
             format!(
@@ -1010,6 +1014,15 @@ pub fn help_lines(palette: ThemePalette) -> Vec<Line<'static>> {
                 "{} detail-find within messages; n/N cycle matches",
                 shortcuts::PANE_FILTER
             ),
+            format!(
+                "{} bookmark/unbookmark selected hit; {} browse bookmarks (Enter scopes search)",
+                shortcuts::BOOKMARK,
+                shortcuts::BOOKMARKS_PANE
+            ),
+            format!(
+                "{} tag the selected conversation (\"postmortem, -wip\" adds/removes); bulk menu tags all selected",
+                shortcuts::TAG_CONVERSATION
+            ),
             format!(
                 "{}/? toggle this help; {} quit (or back from detail)",
                 shortcuts::HELP,
@@ -1117,14 +1130,98 @@ fn centered_rect_fixed(width: u16, height: u16, r: Rect) -> Rect {
     horizontal[1]
 }
 
+/// Bookmarks pane (Alt+M): list of saved bookmarks with the current selection highlighted.
+fn render_bookmarks_modal(
+    frame: &mut Frame,
+    bookmarks: &[Bookmark],
+    selection: usize,
+    palette: ThemePalette,
+) {
+    let area = centered_rect(60, 60, frame.area());
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" Bookmarks ({}) ", bookmarks.len()),
+            Style::default()
+                .fg(palette.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .title_bottom(Span::styled(
+            " Enter scope · a all · d delete · Esc close ",
+            Style::default().fg(palette.hint),
+        ))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(palette.accent))
+        .style(Style::default().bg(palette.surface));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+
+    if bookmarks.is_empty() {
+        let empty = Paragraph::new(vec![
+            Line::from(""),
+            Line::from(Span::styled(
+                "No bookmarks yet.",
+                Style::default().fg(palette.fg),
+            )),
+            Line::from(Span::styled(
+                format!("Press {} on a result to bookmark it.", shortcuts::BOOKMARK),
+                Style::default().fg(palette.hint),
+            )),
+        ])
+        .block(block)
+        .alignment(Alignment::Center);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let items: Vec<ListItem> = bookmarks
+        .iter()
+        .enumerate()
+        .map(|(i, b)| {
+            let selected = i == selection;
+            let style = if selected {
+                Style::default()
+                    .bg(palette.accent)
+                    .fg(palette.bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(palette.fg)
+            };
+            let mut spans = vec![
+                Span::styled(if selected { "→ " } else { "  " }, style),
+                Span::styled(b.title.clone(), style),
+            ];
+            if !b.agent.is_empty() {
+                spans.push(Span::styled(
+                    format!("  {}", b.agent),
+                    Style::default().fg(palette.hint),
+                ));
+            }
+            if !b.tags.is_empty() {
+                spans.push(Span::styled(
+                    format!("  #{}", b.tag_list().join(" #")),
+                    Style::default().fg(palette.accent_alt),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(selection.min(bookmarks.len() - 1)));
+    frame.render_stateful_widget(List::new(items).block(block), area, &mut state);
+}
+
 /// Render parsed content lines from a conversation for the detail modal.
 /// Parses tool use, code blocks, and formats beautifully for human reading.
 fn render_parsed_content(
     detail: &ConversationView,
     query: &str,
     palette: ThemePalette,
+    collapse_subagents: bool,
 ) -> Vec<Line<'static>> {
     let mut lines: Vec<Line<'static>> = Vec::new();
+    let threads = SessionThreads::from_metadata(&detail.convo.metadata_json);
 
     // Header with conversation info
     if let Some(title) = &detail.convo.title {
@@ -1140,6 +1237,21 @@ fn render_parsed_content(
         lines.push(Line::from(""));
     }
 
+    // Parent session of a subagent transcript
+    if let Some(parent) = &threads.parent {
+        lines.push(Line::from(vec![
+            Span::styled("↳ Subagent of: ", Style::default().fg(palette.hint)),
+            Span::styled(
+                parent
+                    .title
+                    .clone()
+                    .unwrap_or_else(|| parent.source_path.display().to_string()),
+                Style::default().fg(palette.fg),
+            ),
+        ]));
+        lines.push(Line::from(""));
+    }
+
     // Workspace info
     if let Some(ws) = &detail.workspace {
         lines.push(Line::from(vec![
@@ -1172,48 +1284,109 @@ fn render_parsed_content(
     )));
     lines.push(Line::from(""));
 
-    // Render messages with beautiful formatting
+    // Render messages with beautiful formatting; subagent threads follow the
+    // message that spawned them instead of their place in the file
     for msg in &detail.messages {
-        let (role_icon, role_label, role_color) = match &msg.role {
-            MessageRole::User => ("👤", "You", palette.user),
-            MessageRole::Agent => ("🤖", "Assistant", palette.agent),
-            MessageRole::Tool => ("🔧", "Tool", palette.tool),
-            MessageRole::System => ("⚙️", "System", palette.system),
-            MessageRole::Other(r) => ("📝", r.as_str(), palette.hint),
-        };
-
-        // Role header with timestamp
-        let ts_text = msg
-            .created_at
-            .map(|t| format!(" · {}", format_absolute_time(t)))
-            .unwrap_or_default();
-        lines.push(Line::from(vec![
-            Span::styled(format!("{role_icon} "), Style::default()),
-            Span::styled(
-                role_label.to_string(),
-                Style::default().fg(role_color).add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(ts_text, Style::default().fg(palette.hint)),
-        ]));
-        lines.push(Line::from(""));
+        if threads.inline_thread_of(msg.idx).is_some() {
+            continue;
+        }
+        lines.extend(render_message_lines(msg, query, palette));
 
-        // Parse and render content
-        let content = &msg.content;
-        let parsed_lines = parse_message_content(content, query, palette);
-        lines.extend(parsed_lines);
-        lines.push(Line::from(""));
-        lines.push(Line::from(Span::styled(
-            "─".repeat(60),
-            Style::default()
-                .fg(palette.hint)
-                .add_modifier(Modifier::DIM),
-        )));
-        lines.push(Line::from(""));
+        for thread in threads
+            .subagents
+            .iter()
+            .filter(|t| t.spawned_by == Some(msg.idx))
+        {
+            let transcript = detail
+                .subagents
+                .iter()
+                .find(|s| s.thread.transcript.is_some() && s.thread.transcript == thread.transcript)
+                .map(|s| s.messages.as_slice())
+                .unwrap_or_default();
+            let inline: Vec<&Message> = detail
+                .messages
+                .iter()
+                .filter(|m| thread.messages.contains(&m.idx))
+                .collect();
+            let count = inline.len() + transcript.len();
+            let label = thread
+                .description
+                .clone()
+                .or_else(|| thread.agent_id.clone())
+                .unwrap_or_else(|| "subagent".to_string());
+            let state = if collapse_subagents {
+                format!(" · {count} msgs · t to expand")
+            } else {
+                format!(" · {count} msgs")
+            };
+            lines.push(Line::from(vec![
+                Span::styled("  ↳ Subagent: ", Style::default().fg(palette.hint)),
+                Span::styled(
+                    label,
+                    Style::default()
+                        .fg(palette.accent_alt)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(state, Style::default().fg(palette.hint)),
+            ]));
+            lines.push(Line::from(""));
+            if collapse_subagents {
+                continue;
+            }
+            for sub_msg in inline.into_iter().chain(transcript) {
+                for line in render_message_lines(sub_msg, query, palette) {
+                    let mut spans = vec![Span::styled("  │ ", Style::default().fg(palette.hint))];
+                    spans.extend(line.spans);
+                    lines.push(Line::from(spans));
+                }
+            }
+        }
     }
 
     lines
 }
 
+/// Role header, parsed content and separator of one message in the detail view.
+fn render_message_lines(msg: &Message, query: &str, palette: ThemePalette) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let (role_icon, role_label, role_color) = match &msg.role {
+        MessageRole::User => ("👤", "You", palette.user),
+        MessageRole::Agent => ("🤖", "Assistant", palette.agent),
+        MessageRole::Tool => ("🔧", "Tool", palette.tool),
+        MessageRole::System => ("⚙️", "System", palette.system),
+        MessageRole::Other(r) => ("📝", r.as_str(), palette.hint),
+    };
+
+    // Role header with timestamp
+    let ts_text = msg
+        .created_at
+        .map(|t| format!(" · {}", format_absolute_time(t)))
+        .unwrap_or_default();
+    lines.push(Line::from(vec![
+        Span::styled(format!("{role_icon} "), Style::default()),
+        Span::styled(
+            role_label.to_string(),
+            Style::default().fg(role_color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(ts_text, Style::default().fg(palette.hint)),
+    ]));
+    lines.push(Line::from(""));
+
+    // Parse and render content
+    let content = &msg.content;
+    let parsed_lines = parse_message_content(content, query, palette);
+    lines.extend(parsed_lines);
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "─".repeat(60),
+        Style::default()
+            .fg(palette.hint)
+            .add_modifier(Modifier::DIM),
+    )));
+    lines.push(Line::from(""));
+    lines
+}
+
 /// Parse message content and render with beautiful formatting.
 /// Handles code blocks, tool calls, JSON, and highlights search terms.
 fn parse_message_content(content: &str, query: &str, palette: ThemePalette) -> Vec<Line<'static>> {
@@ -1379,19 +1552,20 @@ fn render_detail_modal(
     query: &str,
     palette: ThemePalette,
     scroll: u16,
+    collapse_subagents: bool,
 ) {
     let area = frame.area();
     // Use near-full-screen for maximum readability
     let popup_area = centered_rect(90, 90, area);
 
-    let lines = render_parsed_content(detail, query, palette);
+    let lines = render_parsed_content(detail, query, palette, collapse_subagents);
     let total_lines = lines.len();
     // Clamp scroll for display (actual scroll handled by Paragraph)
     let display_line = (scroll as usize).min(total_lines.saturating_sub(1)) + 1;
 
     // Build title with scroll position and hints
     let title_text = format!(
-        " {} · line {}/{} · Esc · o open · c copy · p path · s snip · n nano ",
+        " {} · line {}/{} · Esc · o open · c copy · p path · s snip · n nano · t subagents ",
         hit.title, display_line, total_lines
     );
 
@@ -1583,6 +1757,87 @@ fn workspace_suggestions(input: &str, known_workspaces: &[String]) -> Vec<String
         .collect()
 }
 
+/// Candidate completions for the trailing word of the search query when it is
+/// (the start of) a `field:value` qualifier such as `agent:cl` or `work`.
+fn query_field_suggestions(word: &str, known_workspaces: &[String]) -> Vec<String> {
+    let Some((field, value)) = word.split_once(':') else {
+        if word.len() < 2 {
+            return Vec::new();
+        }
+        let word_lower = word.to_lowercase();
+        return QUERY_FIELDS
+            .iter()
+            .filter(|field| field.starts_with(&word_lower))
+            .map(|field| format!("{field}:"))
+            .collect();
+    };
+    let value_lower = value.trim_matches('"').to_lowercase();
+    let values: Vec<String> = match field.to_lowercase().as_str() {
+        "agent" => agent_suggestions(&value_lower)
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        "workspace" | "ws" => workspace_suggestions(&value_lower, known_workspaces),
+        "role" => ROLE_CYCLE
+            .iter()
+            .filter(|role| role.starts_with(&value_lower))
+            .map(|role| role.to_string())
+            .collect(),
+        "source" => ["local", "remote"]
+            .iter()
+            .filter(|source| source.starts_with(&value_lower))
+            .map(|source| source.to_string())
+            .collect(),
+        "after" | "before" | "since" | "until" => ["today", "yesterday", "7d", "30d"]
+            .iter()
+            .filter(|preset| preset.starts_with(&value_lower))
+            .map(|preset| preset.to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+    values
+        .into_iter()
+        .map(|value| {
+            if value.contains(char::is_whitespace) {
+                format!("{field}:\"{value}\"")
+            } else {
+                format!("{field}:{value}")
+            }
+        })
+        .collect()
+}
+
+/// Tab completion for inline qualifiers: replaces a trailing `field:` word with its
+/// first suggestion. Returns `None` when there is nothing new to complete, including
+/// for plain words, so Tab keeps toggling focus while typing ordinary queries.
+fn complete_query_field(query: &str, known_workspaces: &[String]) -> Option<String> {
+    let word = query.split_whitespace().last()?;
+    if query.ends_with(char::is_whitespace) || !word.contains(':') {
+        return None;
+    }
+    let completed = query_field_suggestions(word, known_workspaces)
+        .into_iter()
+        .next()?;
+    if completed == word {
+        return None;
+    }
+    Some(format!("{}{completed}", &query[..query.len() - word.len()]))
+}
+
+/// Status-line hint listing qualifier completions while a `field:` word is typed.
+fn query_field_hint(query: &str, known_workspaces: &[String]) -> Option<String> {
+    let word = query.split_whitespace().last()?;
+    if query.ends_with(char::is_whitespace) || !word.contains(':') {
+        return None;
+    }
+    let suggestions = query_field_suggestions(word, known_workspaces);
+    if suggestions.is_empty() {
+        return None;
+    }
+    let shown: Vec<&str> = suggestions.iter().take(5).map(String::as_str).collect();
+    Some(format!("Tab to complete: {}", shown.join(", ")))
+}
+
 /// Suggests a correction for a query based on history.
 /// Uses Levenshtein distance to find close matches (max edit distance 2).
 /// Only suggests if the history item is different from the query.
@@ -1988,6 +2243,7 @@ fn initialize_semantic_context(
         if let Err(err) = client.set_semantic_context(
             context.embedder,
             context.index,
+            context.ann,
             context.filter_maps,
             context.roles,
         ) {
@@ -2069,6 +2325,24 @@ fn chips_for_filters(filters: &SearchFilters, palette: ThemePalette) -> Vec<Span
             spans.push(Span::raw(" ".to_string()));
         }
     }
+    if !filters.roles.is_empty() {
+        let mut roles: Vec<_> = filters.roles.iter().cloned().collect();
+        roles.sort();
+        spans.push(Span::styled(
+            format!("[role:{}]", roles.join("|")),
+            Style::default()
+                .fg(palette.accent_alt)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw(" ".to_string()));
+    }
+    if !filters.session_paths.is_empty() {
+        spans.push(Span::styled(
+            format!("[sessions:{}]", filters.session_paths.len()),
+            Style::default().fg(palette.accent_alt),
+        ));
+        spans.push(Span::raw(" ".to_string()));
+    }
     if filters.created_from.is_some() || filters.created_to.is_some() {
         let chip_text = format_time_chip(filters.created_from, filters.created_to);
         if !chip_text.is_empty() {
@@ -2101,6 +2375,7 @@ fn contextual_shortcuts(
             ("j/k".into(), "Scroll".into()),
             ("Home/End".into(), "Top/Bottom".into()),
             ("c".into(), "Copy".into()),
+            ("t".into(), "Subagents".into()),
         ];
     }
     match input_mode {
@@ -2129,6 +2404,11 @@ fn contextual_shortcuts(
             (shortcuts::DETAIL_OPEN.into(), "Apply".into()),
             (shortcuts::DETAIL_CLOSE.into(), "Cancel".into()),
         ],
+        InputMode::Tag => vec![
+            ("type".into(), "Tags (-name removes)".into()),
+            (shortcuts::DETAIL_OPEN.into(), "Apply".into()),
+            (shortcuts::DETAIL_CLOSE.into(), "Cancel".into()),
+        ],
         InputMode::Query => match focus_region {
             FocusRegion::Results => vec![
                 ("Ctrl+P".into(), "Palette".into()),
@@ -2161,6 +2441,110 @@ fn contextual_shortcuts(
     }
 }
 
+/// Role filter cycle for the TUI pill: all → user → assistant → tool → system → all.
+const ROLE_CYCLE: [&str; 4] = ["user", "assistant", "tool", "system"];
+
+/// Advance the role filter one step through [`ROLE_CYCLE`]; returns the status line.
+fn cycle_role_filter(filters: &mut SearchFilters) -> String {
+    let next = match filters.roles.iter().next() {
+        Some(current) if filters.roles.len() == 1 => ROLE_CYCLE
+            .iter()
+            .position(|r| r == current)
+            .and_then(|i| ROLE_CYCLE.get(i + 1)),
+        Some(_) => None,
+        None => ROLE_CYCLE.first(),
+    };
+    filters.roles.clear();
+    match next {
+        Some(role) => {
+            filters.roles.insert((*role).to_string());
+            format!("Role filter: {role} ({} to cycle)", shortcuts::CYCLE_ROLE)
+        }
+        None => "Role filter cleared".to_string(),
+    }
+}
+
+/// Add a bookmark for `hit`, or remove it if that location is already bookmarked.
+/// Returns the status line to show.
+fn toggle_bookmark(store: &BookmarkStore, hit: &SearchHit) -> Result<String> {
+    if let Some(existing) = store.find(&hit.source_path, hit.line_number)? {
+        store.remove(existing.id)?;
+        return Ok(format!("Removed bookmark: {}", existing.title));
+    }
+
+    let title = if hit.title.trim().is_empty() {
+        hit.source_path.clone()
+    } else {
+        hit.title.clone()
+    };
+    let snippet: String = hit.snippet.chars().take(200).collect();
+    let mut bookmark = Bookmark::new(
+        title.clone(),
+        hit.source_path.clone(),
+        hit.agent.clone(),
+        hit.workspace.clone(),
+    )
+    .with_snippet(snippet);
+    if let Some(line) = hit.line_number {
+        bookmark = bookmark.with_line(line);
+    }
+    store.add(&bookmark)?;
+    Ok(format!(
+        "Bookmarked: {title} ({} to browse)",
+        shortcuts::BOOKMARKS_PANE
+    ))
+}
+
+/// Apply a tag prompt such as `postmortem, -wip` to every conversation stored for
+/// `paths`: plain names are added, names prefixed with `-` are removed.
+/// Returns the status line to show.
+fn apply_conversation_tags(db_path: &Path, paths: &[String], input: &str) -> Result<String> {
+    use crate::storage::sqlite::{SqliteStorage, normalize_tag_name};
+
+    let mut add = Vec::new();
+    let mut remove = Vec::new();
+    for raw in input.split([',', ' ']).filter(|s| !s.trim().is_empty()) {
+        match raw.trim().strip_prefix('-') {
+            Some(name) => remove.push(normalize_tag_name(name)?),
+            None => add.push(normalize_tag_name(raw)?),
+        }
+    }
+    if add.is_empty() && remove.is_empty() {
+        return Ok("No tags entered".to_string());
+    }
+
+    let storage = SqliteStorage::open(db_path)?;
+    let mut sessions = 0usize;
+    for path in paths {
+        let ids = storage.conversation_ids_for_path(path)?;
+        if ids.is_empty() {
+            continue;
+        }
+        sessions += 1;
+        for id in ids {
+            for name in &add {
+                storage.add_conversation_tag(id, name)?;
+            }
+            for name in &remove {
+                storage.remove_conversation_tag(id, name)?;
+            }
+        }
+    }
+    if sessions == 0 {
+        return Ok("No indexed conversation found to tag".to_string());
+    }
+
+    let changes: Vec<String> = add
+        .iter()
+        .map(|n| format!("+{n}"))
+        .chain(remove.iter().map(|n| format!("-{n}")))
+        .collect();
+    Ok(format!(
+        "Tagged {sessions} session(s): {} (search with --tag)",
+        changes.join(" ")
+    ))
+}
+
 fn save_view_slot(
     slot: u8,
     filters: &SearchFilters,
@@ -2746,6 +3130,8 @@ pub fn run_tui(
     // Full-screen modal for viewing parsed content
     let mut show_detail_modal = false;
     let mut modal_scroll: u16 = 0;
+    // Subagent threads under their spawning message: expanded or one-line summaries
+    let mut collapse_subagents = false;
     // Bulk action modal state
     let mut show_bulk_modal = false;
     let mut bulk_action_idx: usize = 0;
@@ -2857,6 +3243,14 @@ pub fn run_tui(
     // Available source IDs discovered from index (populated on menu open)
     let mut available_source_ids: Vec<String> = Vec::new();
 
+    // Bookmarks pane state (Alt+M); bookmarks live in their own db in the data dir
+    let bookmarks_path = crate::bookmarks::bookmarks_path_for(&data_dir);
+    let mut bookmarks_pane_open = false;
+    let mut bookmarks_pane_selection: usize = 0;
+    let mut bookmarks_pane_items: Vec<Bookmark> = Vec::new();
+    // Source paths the tag prompt (Alt+T / bulk "Tag selected") applies to
+    let mut tag_targets: Vec<String> = Vec::new();
+
     // Keep a short history of indexer percentages for sparkline rendering
     let mut progress_history: std::collections::VecDeque<u8> =
         std::collections::VecDeque::with_capacity(24);
@@ -3095,6 +3489,7 @@ pub fn run_tui(
                     InputMode::CreatedTo => format!("[to] {input_buffer}"),
                     InputMode::PaneFilter => format!("[pane] {input_buffer}"),
                     InputMode::DetailFind => format!("[detail find] {input_buffer}"),
+                    InputMode::Tag => format!("[tag] {input_buffer}"),
                 };
                 let mode_label = match match_mode {
                     MatchMode::Standard => "standard",
@@ -3138,6 +3533,16 @@ pub fn run_tui(
                         editable: true,
                     });
                 }
+                if !filters.roles.is_empty() {
+                    let mut roles: Vec<_> = filters.roles.iter().cloned().collect();
+                    roles.sort();
+                    pill_vec.push(Pill {
+                        label: "role".into(),
+                        value: roles.join("|"),
+                        active: true,
+                        editable: true,
+                    });
+                }
                 if let Some(filter) = pane_filter.as_ref().filter(|s| !s.is_empty()) {
                     pill_vec.push(Pill {
                         label: "pane".into(),
@@ -3460,13 +3865,27 @@ pub fn run_tui(
                                 // Location line (separate from snippet for clarity)
                                 let truncated_source = truncate_path(&hit.source_path, 50);
                                 let truncated_ws = truncate_path(&hit.workspace, 30);
-                                let mut location_spans: Vec<Span> = vec![
-                                    Span::styled("[file] ", Style::default().fg(palette.hint)),
-                                    Span::styled(
-                                        truncated_source,
+                                let mut location_spans: Vec<Span> = Vec::new();
+                                // Subagent transcripts lead with the session that spawned them
+                                if let Some(parent) = &hit.parent {
+                                    let parent_label = parent
+                                        .title
+                                        .clone()
+                                        .filter(|t| !t.trim().is_empty())
+                                        .unwrap_or_else(|| truncate_path(&parent.source_path, 40));
+                                    location_spans.push(Span::styled(
+                                        format!("[parent] {parent_label} › "),
                                         Style::default().fg(palette.hint),
-                                    ),
-                                ];
+                                    ));
+                                }
+                                location_spans.push(Span::styled(
+                                    "[file] ",
+                                    Style::default().fg(palette.hint),
+                                ));
+                                location_spans.push(Span::styled(
+                                    truncated_source,
+                                    Style::default().fg(palette.hint),
+                                ));
                                 if !hit.workspace.is_empty() {
                                     location_spans.push(Span::raw("  "));
                                     location_spans.push(Span::styled(
@@ -3850,7 +4269,12 @@ pub fn run_tui(
                     let content_lines: Vec<Line> = match detail_tab {
                         DetailTab::Messages => {
                             if let Some(full) = detail {
-                                let lines = render_parsed_content(&full, highlight_term, palette);
+                                let lines = render_parsed_content(
+                                    &full,
+                                    highlight_term,
+                                    palette,
+                                    collapse_subagents,
+                                );
                                 detail_match_lines = match_line_indices(&lines, highlight_term);
                                 if lines.is_empty() {
                                     vec![Line::from(Span::styled(
@@ -4395,7 +4819,15 @@ pub fn run_tui(
                     } else {
                         last_query.as_str()
                     };
-                    render_detail_modal(f, detail, hit, modal_highlight, palette, modal_scroll);
+                    render_detail_modal(
+                        f,
+                        detail,
+                        hit,
+                        modal_highlight,
+                        palette,
+                        modal_scroll,
+                        collapse_subagents,
+                    );
                 }
 
                 // Bulk action modal
@@ -4413,11 +4845,12 @@ pub fn run_tui(
                         .border_style(Style::default().fg(palette.accent))
                         .style(Style::default().bg(palette.surface));
 
-                    const BULK_ACTIONS: [&str; 4] = [
+                    const BULK_ACTIONS: [&str; 5] = [
                         "Open all in editor",
                         "Copy all paths",
                         "Export as JSON",
                         "Clear selection",
+                        "Tag selected…",
                     ];
                     let items: Vec<ListItem> = BULK_ACTIONS
                         .iter()
@@ -4597,6 +5030,16 @@ pub fn run_tui(
                     f.render_widget(list, area);
                 }
 
+                // Bookmarks pane (Alt+M)
+                if bookmarks_pane_open {
+                    render_bookmarks_modal(
+                        f,
+                        &bookmarks_pane_items,
+                        bookmarks_pane_selection,
+                        palette,
+                    );
+                }
+
                 if palette_state.open {
                     let area = centered_rect(70, 60, f.area());
                     palette::draw_palette(f, area, &palette_state, palette);
@@ -4623,7 +5066,12 @@ pub fn run_tui(
             // Handle mouse events (skip when modal is open)
             if let Event::Mouse(mouse) = event {
                 // Ignore mouse events when help, detail, bulk, or source filter modal is open
-                if show_help || show_detail_modal || show_bulk_modal || source_filter_menu_open {
+                if show_help
+                    || show_detail_modal
+                    || show_bulk_modal
+                    || source_filter_menu_open
+                    || bookmarks_pane_open
+                {
                     continue;
                 }
                 needs_draw = true;
@@ -4653,6 +5101,11 @@ pub fn run_tui(
                                         status = "Edit workspace filter".to_string();
                                         dirty_since = None;
                                     }
+                                    "role" => {
+                                        status = cycle_role_filter(&mut filters);
+                                        page = 0;
+                                        dirty_since = Some(Instant::now());
+                                    }
                                     "time" => {
                                         input_mode = InputMode::CreatedFrom;
                                         input_buffer.clear();
@@ -4898,6 +5351,11 @@ pub fn run_tui(
                                     input_mode = InputMode::Workspace;
                                     input_buffer.clear();
                                 }
+                                PaletteAction::FilterRole => {
+                                    status = cycle_role_filter(&mut filters);
+                                    page = 0;
+                                    dirty_since = Some(Instant::now());
+                                }
                                 PaletteAction::FilterToday => {
                                     if let Some((start, _)) = quick_date_range_today() {
                                         filters.created_from = Some(start);
@@ -4918,11 +5376,45 @@ pub fn run_tui(
                                     status = "Enter start date (YYYY-MM-DD)".to_string();
                                 }
                                 PaletteAction::OpenBulkActions => {
-                                    status = "Bulk actions: select with m, open with A".to_string();
+                                    if selected.is_empty() {
+                                        status =
+                                            "Bulk actions: select with m, open with A".to_string();
+                                    } else {
+                                        show_bulk_modal = true;
+                                        bulk_action_idx = 0;
+                                        status = "Bulk actions: ↑↓ navigate · Enter execute · Esc cancel"
+                                            .to_string();
+                                    }
+                                }
+                                PaletteAction::TagConversation => {
+                                    if let Some(hit) = active_hit(&panes, active_pane) {
+                                        tag_targets = vec![hit.source_path.clone()];
+                                        input_mode = InputMode::Tag;
+                                        input_buffer.clear();
+                                        status = "Tags to add (comma-separated, -name removes)"
+                                            .to_string();
+                                    } else {
+                                        status = "No result selected to tag".to_string();
+                                    }
                                 }
                                 PaletteAction::ReloadIndex => {
                                     dirty_since = Some(Instant::now());
                                 }
+                                PaletteAction::ToggleBookmark => {
+                                    status = match active_hit(&panes, active_pane) {
+                                        Some(hit) => BookmarkStore::open(&bookmarks_path)
+                                            .and_then(|store| toggle_bookmark(&store, hit))
+                                            .unwrap_or_else(|e| format!("Bookmark failed: {e}")),
+                                        None => "No result selected to bookmark".to_string(),
+                                    };
+                                }
+                                PaletteAction::OpenBookmarks => {
+                                    bookmarks_pane_items = BookmarkStore::open(&bookmarks_path)
+                                        .and_then(|store| store.list(None))
+                                        .unwrap_or_default();
+                                    bookmarks_pane_selection = 0;
+                                    bookmarks_pane_open = true;
+                                }
                                 PaletteAction::OpenSavedViews => {
                                     status =
                                         "Saved views: Ctrl+<n> save, Shift+<n> load".to_string();
@@ -5046,6 +5538,7 @@ pub fn run_tui(
                                 if let Err(err) = client.set_semantic_context(
                                     context.embedder,
                                     context.index,
+                                    context.ann,
                                     context.filter_maps,
                                     context.roles,
                                 ) {
@@ -5106,11 +5599,12 @@ pub fn run_tui(
 
             // Bulk action modal: handle keys when open
             if show_bulk_modal {
-                const BULK_ACTIONS: [&str; 4] = [
+                const BULK_ACTIONS: [&str; 5] = [
                     "Open all in editor",
                     "Copy all paths",
                     "Export as JSON",
                     "Clear selection",
+                    "Tag selected…",
                 ];
                 match key.code {
                     KeyCode::Esc => {
@@ -5287,6 +5781,22 @@ pub fn run_tui(
                                 open_confirm_armed = false;
                                 status = format!("Cleared {count} selections");
                             }
+                            4 => {
+                                // Tag selected conversations
+                                let mut paths: Vec<String> = selected_hits
+                                    .iter()
+                                    .map(|h| h.source_path.clone())
+                                    .collect();
+                                paths.sort();
+                                paths.dedup();
+                                status = format!(
+                                    "Tags for {} session(s) (comma-separated, -name removes)",
+                                    paths.len()
+                                );
+                                tag_targets = paths;
+                                input_mode = InputMode::Tag;
+                                input_buffer.clear();
+                            }
                             _ => {}
                         }
                     }
@@ -5346,6 +5856,66 @@ pub fn run_tui(
                 continue;
             }
 
+            // Bookmarks pane: handle keys when open
+            if bookmarks_pane_open {
+                match key.code {
+                    KeyCode::Esc => {
+                        bookmarks_pane_open = false;
+                        status = "Bookmarks closed".to_string();
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        bookmarks_pane_selection = bookmarks_pane_selection.saturating_sub(1);
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        bookmarks_pane_selection = (bookmarks_pane_selection + 1)
+                            .min(bookmarks_pane_items.len().saturating_sub(1));
+                    }
+                    KeyCode::Enter => {
+                        if let Some(b) = bookmarks_pane_items.get(bookmarks_pane_selection) {
+                            filters.session_paths = HashSet::from([b.source_path.clone()]);
+                            status = format!(
+                                "Scoped to bookmarked session: {} ({} clears)",
+                                b.title,
+                                shortcuts::CLEAR_FILTERS
+                            );
+                            dirty_since = Some(Instant::now());
+                            bookmarks_pane_open = false;
+                        }
+                    }
+                    KeyCode::Char('a') if !bookmarks_pane_items.is_empty() => {
+                        filters.session_paths = bookmarks_pane_items
+                            .iter()
+                            .map(|b| b.source_path.clone())
+                            .collect();
+                        status = format!(
+                            "Scoped to {} bookmarked session(s) ({} clears)",
+                            filters.session_paths.len(),
+                            shortcuts::CLEAR_FILTERS
+                        );
+                        dirty_since = Some(Instant::now());
+                        bookmarks_pane_open = false;
+                    }
+                    KeyCode::Char('d') | KeyCode::Delete => {
+                        if let Some(b) = bookmarks_pane_items.get(bookmarks_pane_selection) {
+                            let removed = BookmarkStore::open(&bookmarks_path)
+                                .and_then(|store| store.remove(b.id))
+                                .unwrap_or(false);
+                            if removed {
+                                status = format!("Removed bookmark: {}", b.title);
+                                bookmarks_pane_items.remove(bookmarks_pane_selection);
+                                bookmarks_pane_selection = bookmarks_pane_selection
+                                    .min(bookmarks_pane_items.len().saturating_sub(1));
+                            } else {
+                                status = "Failed to remove bookmark".to_string();
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                needs_draw = true;
+                continue;
+            }
+
             // While help is open, keys scroll the help modal and do not affect panes.
             if show_help {
                 match key.code {
@@ -5393,6 +5963,14 @@ pub fn run_tui(
                     }
                     KeyCode::Home | KeyCode::Char('g') => modal_scroll = 0,
                     KeyCode::End | KeyCode::Char('G') => modal_scroll = u16::MAX,
+                    KeyCode::Char('t') => {
+                        collapse_subagents = !collapse_subagents;
+                        status = if collapse_subagents {
+                            "Subagent threads collapsed".to_string()
+                        } else {
+                            "Subagent threads expanded".to_string()
+                        };
+                    }
                     KeyCode::Char('c') => {
                         // Copy rendered content to clipboard using xclip/xsel/pbcopy
                         if let Some((_, ref detail)) = cached_detail {
@@ -5884,6 +6462,7 @@ pub fn run_tui(
                                         message_count: messages.len(),
                                         duration,
                                         project,
+                                        nav: Vec::new(),
                                     };
 
                                     if encrypted {
@@ -5997,6 +6576,61 @@ pub fn run_tui(
                 continue;
             }
 
+            // Toggle bookmark on the selected hit (Alt+B)
+            if matches!(key.code, KeyCode::Char('b' | 'B'))
+                && key.modifiers.contains(KeyModifiers::ALT)
+            {
+                status = match active_hit(&panes, active_pane) {
+                    Some(hit) => BookmarkStore::open(&bookmarks_path)
+                        .and_then(|store| toggle_bookmark(&store, hit))
+                        .unwrap_or_else(|e| format!("Bookmark failed: {e}")),
+                    None => "No result selected to bookmark".to_string(),
+                };
+                needs_draw = true;
+                continue;
+            }
+
+            // Cycle role filter (Alt+R)
+            if matches!(key.code, KeyCode::Char('r' | 'R'))
+                && key.modifiers.contains(KeyModifiers::ALT)
+            {
+                status = cycle_role_filter(&mut filters);
+                page = 0;
+                dirty_since = Some(Instant::now());
+                needs_draw = true;
+                continue;
+            }
+
+            // Tag the selected conversation (Alt+T)
+            if matches!(key.code, KeyCode::Char('t' | 'T'))
+                && key.modifiers.contains(KeyModifiers::ALT)
+            {
+                if let Some(hit) = active_hit(&panes, active_pane) {
+                    tag_targets = vec![hit.source_path.clone()];
+                    input_mode = InputMode::Tag;
+                    input_buffer.clear();
+                    status = "Tags to add (comma-separated, -name removes)".to_string();
+                } else {
+                    status = "No result selected to tag".to_string();
+                }
+                needs_draw = true;
+                continue;
+            }
+
+            // Open bookmarks pane (Alt+M)
+            if matches!(key.code, KeyCode::Char('m' | 'M'))
+                && key.modifiers.contains(KeyModifiers::ALT)
+            {
+                bookmarks_pane_items = BookmarkStore::open(&bookmarks_path)
+                    .and_then(|store| store.list(None))
+                    .unwrap_or_default();
+                bookmarks_pane_selection = 0;
+                bookmarks_pane_open = true;
+                status = "Bookmarks (↑/↓ select, Enter scope, a all, d delete, Esc close)".into();
+                needs_draw = true;
+                continue;
+            }
+
             match input_mode {
                 InputMode::Query => {
                     if key.modifiers.contains(KeyModifiers::CONTROL) {
@@ -6673,6 +7307,15 @@ pub fn run_tui(
                             dirty_since = Some(Instant::now());
                         }
                         KeyCode::Tab => {
+                            // Complete a trailing `field:value` qualifier first
+                            if let Some(completed) = complete_query_field(&query, &known_workspaces)
+                            {
+                                query = completed;
+                                page = 0;
+                                dirty_since = Some(Instant::now());
+                                status = format!("Query: {query}");
+                                continue;
+                            }
                             // Toggle focus
                             focus_region = match focus_region {
                                 FocusRegion::Results => FocusRegion::Detail,
@@ -6983,6 +7626,9 @@ pub fn run_tui(
                             }
                             // All other characters pass through to query input
                             query.push(c);
+                            if let Some(hint) = query_field_hint(&query, &known_workspaces) {
+                                status = hint;
+                            }
                             page = 0;
                             history_cursor = None;
                             suggestion_idx = None;
@@ -7423,6 +8069,28 @@ pub fn run_tui(
                     }
                     _ => {}
                 },
+                InputMode::Tag => match key.code {
+                    KeyCode::Esc => {
+                        tag_targets.clear();
+                        input_buffer.clear();
+                        input_mode = InputMode::Query;
+                        status = "Tagging cancelled".to_string();
+                    }
+                    KeyCode::Enter => {
+                        status = apply_conversation_tags(&db_path, &tag_targets, &input_buffer)
+                            .unwrap_or_else(|e| format!("Tagging failed: {e}"));
+                        tag_targets.clear();
+                        input_buffer.clear();
+                        input_mode = InputMode::Query;
+                    }
+                    KeyCode::Backspace => {
+                        input_buffer.pop();
+                    }
+                    KeyCode::Char(c) => {
+                        input_buffer.push(c);
+                    }
+                    _ => {}
+                },
             }
         }
 
@@ -7444,8 +8112,14 @@ pub fn run_tui(
                         .map(|h| h.agent.clone())
                         .or_else(|| panes.get(active_pane).map(|p| p.agent.clone()));
                     let prev_path = active_hit(&panes, active_pane).map(|h| h.source_path.clone());
-                    let lexical_query = apply_match_mode(&query, match_mode);
-                    let semantic_query = query.clone();
+                    // Lift inline `field:value` qualifiers out of the query into filters
+                    let scoped = crate::search::query::parse_field_qualifiers(&query);
+                    let mut search_filters = filters.clone();
+                    if let Err(err) = scoped.apply_to(&mut search_filters) {
+                        status = err.to_string();
+                    }
+                    let lexical_query = apply_match_mode(&scoped.text, match_mode);
+                    let semantic_query = scoped.text.clone();
                     // Use search_with_fallback for implicit wildcard expansion on sparse results
                     const SPARSE_THRESHOLD: usize = 3;
                     let search_started = Instant::now();
@@ -7465,7 +8139,7 @@ pub fn run_tui(
                             match client.search_hybrid(
                                 &lexical_query,
                                 &semantic_query,
-                                filters.clone(),
+                                search_filters.clone(),
                                 page_size,
                                 page * page_size,
                                 SPARSE_THRESHOLD,
@@ -7486,7 +8160,7 @@ pub fn run_tui(
                                     ));
                                     client.search_with_fallback(
                                         &lexical_query,
-                                        filters.clone(),
+                                        search_filters.clone(),
                                         page_size,
                                         page * page_size,
                                         SPARSE_THRESHOLD,
@@ -7498,7 +8172,7 @@ pub fn run_tui(
                         SearchMode::Semantic if use_semantic => {
                             match client.search_semantic(
                                 &semantic_query,
-                                filters.clone(),
+                                search_filters.clone(),
                                 page_size,
                                 page * page_size,
                                 crate::search::query::FieldMask::FULL,
@@ -7524,7 +8198,7 @@ pub fn run_tui(
                                     ));
                                     client.search_with_fallback(
                                         &lexical_query,
-                                        filters.clone(),
+                                        search_filters.clone(),
                                         page_size,
                                         page * page_size,
                                         SPARSE_THRESHOLD,
@@ -7535,7 +8209,7 @@ pub fn run_tui(
                         }
                         _ => client.search_with_fallback(
                             &lexical_query,
-                            filters.clone(),
+                            search_filters.clone(),
                             page_size,
                             page * page_size,
                             SPARSE_THRESHOLD,
@@ -8271,9 +8945,10 @@ mod tests {
             convo,
             messages: vec![message],
             workspace: None,
+            subagents: Vec::new(),
         };
 
-        let lines = render_parsed_content(&detail, "", palette);
+        let lines = render_parsed_content(&detail, "", palette, false);
         let joined = lines
             .iter()
             .map(line_to_string)
@@ -8288,6 +8963,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detail_threads_subagents_under_spawning_message() {
+        let palette = ThemePalette::dark();
+        let message = |idx: i64, role: MessageRole, content: &str| Message {
+            id: Some(idx + 1),
+            idx,
+            role,
+            author: None,
+            created_at: None,
+            content: content.into(),
+            extra_json: json!({}),
+            snippets: vec![],
+        };
+        let detail = ConversationView {
+            convo: Conversation {
+                id: Some(1),
+                agent_slug: "claude_code".into(),
+                workspace: None,
+                external_id: None,
+                title: Some("Trace foo callers".into()),
+                source_path: PathBuf::from("/tmp/sess-1.jsonl"),
+                started_at: None,
+                ended_at: None,
+                approx_tokens: None,
+                metadata_json: json!({
+                    "subagents": [{
+                        "description": "Find callers",
+                        "spawned_by": 0,
+                        "messages": [1]
+                    }]
+                }),
+                messages: Vec::new(),
+                source_id: "local".to_string(),
+                origin_host: None,
+            },
+            messages: vec![
+                message(0, MessageRole::Agent, "Delegating the search"),
+                message(1, MessageRole::Agent, "Found 3 callers"),
+                message(2, MessageRole::Agent, "foo has 3 callers"),
+            ],
+            workspace: None,
+            subagents: Vec::new(),
+        };
+        let render = |collapse: bool| {
+            render_parsed_content(&detail, "", palette, collapse)
+                .iter()
+                .map(line_to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let expanded = render(false);
+        let header = expanded
+            .iter()
+            .position(|l| l.contains("Subagent: Find callers"))
+            .unwrap();
+        let nested = expanded
+            .iter()
+            .position(|l| l.contains("│") && l.contains("Found 3 callers"))
+            .unwrap();
+        let after = expanded
+            .iter()
+            .position(|l| l.contains("foo has 3 callers"))
+            .unwrap();
+        assert!(header < nested && nested < after);
+
+        let collapsed = render(true).join("\n");
+        assert!(collapsed.contains("1 msgs · t to expand"));
+        assert!(!collapsed.contains("Found 3 callers"));
+    }
+
     // ==========================================================================
     // Navigation State Tests (tst.ui.nav)
     // Tests for TUI navigation state machine behavior
@@ -8375,6 +9120,7 @@ mod tests {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         }
     }
 
@@ -8588,6 +9334,37 @@ mod tests {
         assert_eq!(suggestions.len(), 3);
     }
 
+    #[test]
+    fn complete_query_field_completes_names_and_values() {
+        let known = vec!["/home/me/my project".to_string()];
+        assert_eq!(
+            complete_query_field("error agent:cl", &known).as_deref(),
+            Some("error agent:claude_code")
+        );
+        assert_eq!(
+            complete_query_field("role:as", &known).as_deref(),
+            Some("role:assistant")
+        );
+        assert_eq!(
+            complete_query_field("ws:proj", &known).as_deref(),
+            Some("ws:\"/home/me/my project\"")
+        );
+        // Already complete, plain words (even field-name prefixes), and trailing
+        // spaces fall through to focus toggle
+        assert_eq!(complete_query_field("role:user", &known), None);
+        assert_eq!(complete_query_field("error", &known), None);
+        assert_eq!(complete_query_field("error work", &known), None);
+        assert_eq!(complete_query_field("agent:cl ", &known), None);
+    }
+
+    #[test]
+    fn query_field_hint_lists_value_suggestions() {
+        let hint = query_field_hint("agent:c", &[]).expect("hint");
+        assert!(hint.contains("agent:claude_code"));
+        assert!(hint.contains("agent:codex"));
+        assert_eq!(query_field_hint("plain text", &[]), None);
+    }
+
     // ==========================================================================
     // UI State Persistence Tests (tst.ui.pers)
     // Tests for saving and restoring UI state across sessions
@@ -9716,4 +10493,109 @@ mod tests {
         assert_eq!(items_per_sec, 0);
         let _ = items_delta; // suppress unused warning
     }
+
+    #[test]
+    fn toggle_bookmark_adds_then_removes() {
+        let dir = TempDir::new().unwrap();
+        let store = BookmarkStore::open(&dir.path().join("bookmarks.db")).unwrap();
+        let mut hit = make_hit("codex", "/sessions/a.jsonl", 1.0, "fix the auth bug");
+        hit.line_number = Some(7);
+
+        let msg = toggle_bookmark(&store, &hit).unwrap();
+        assert!(msg.starts_with("Bookmarked"));
+        let saved = store.find("/sessions/a.jsonl", Some(7)).unwrap().unwrap();
+        assert_eq!(saved.agent, "codex");
+        assert_eq!(saved.snippet, "fix the auth bug");
+
+        let msg = toggle_bookmark(&store, &hit).unwrap();
+        assert!(msg.starts_with("Removed bookmark"));
+        assert_eq!(store.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn chips_show_session_scope() {
+        let mut filters = SearchFilters::default();
+        filters.session_paths.insert("/sessions/a.jsonl".into());
+        let text: String = chips_for_filters(&filters, ThemePalette::dark())
+            .iter()
+            .map(|s| s.content.to_string())
+            .collect();
+        assert!(text.contains("[sessions:1]"));
+    }
+
+    #[test]
+    fn cycle_role_filter_walks_roles_then_clears() {
+        let mut filters = SearchFilters::default();
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            cycle_role_filter(&mut filters);
+            seen.push(filters.roles.iter().next().cloned().unwrap());
+        }
+        assert_eq!(seen, ROLE_CYCLE);
+        assert_eq!(cycle_role_filter(&mut filters), "Role filter cleared");
+        assert!(filters.roles.is_empty());
+
+        filters.roles.insert("user".into());
+        let text: String = chips_for_filters(&filters, ThemePalette::dark())
+            .iter()
+            .map(|s| s.content.to_string())
+            .collect();
+        assert!(text.contains("[role:user]"));
+    }
+
+    #[test]
+    fn apply_conversation_tags_adds_and_removes() {
+        use crate::model::types::{Agent, AgentKind, Conversation};
+        use crate::storage::sqlite::SqliteStorage;
+
+        let dir = TempDir::new().unwrap();
+        let db_path = dir.path().join("agent_search.db");
+        let mut storage = SqliteStorage::open(&db_path).unwrap();
+        let agent_id = storage
+            .ensure_agent(&Agent {
+                id: None,
+                slug: "codex".into(),
+                name: "Codex".into(),
+                version: None,
+                kind: AgentKind::Cli,
+            })
+            .unwrap();
+        let conv = Conversation {
+            id: None,
+            agent_slug: "codex".into(),
+            workspace: None,
+            external_id: Some("c1".into()),
+            title: Some("demo".into()),
+            source_path: PathBuf::from("/sessions/a.jsonl"),
+            started_at: Some(1),
+            ended_at: None,
+            approx_tokens: None,
+            metadata_json: serde_json::json!({}),
+            messages: Vec::new(),
+            source_id: "local".into(),
+            origin_host: None,
+        };
+        let conv_id = storage
+            .insert_conversation_tree(agent_id, None, &conv)
+            .unwrap()
+            .conversation_id;
+        drop(storage);
+
+        let paths = vec!["/sessions/a.jsonl".to_string()];
+        let msg = apply_conversation_tags(&db_path, &paths, "postmortem, wip").unwrap();
+        assert!(msg.contains("+postmortem +wip"), "{msg}");
+        apply_conversation_tags(&db_path, &paths, "-wip").unwrap();
+
+        let storage = SqliteStorage::open(&db_path).unwrap();
+        let names: Vec<String> = storage
+            .conversation_tags(conv_id)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, vec!["postmortem"]);
+
+        let msg = apply_conversation_tags(&db_path, &["/nope.jsonl".into()], "x").unwrap();
+        assert!(msg.starts_with("No indexed conversation"));
+    }
 }
@@ -0,0 +1,363 @@
+//! Token usage and cost reporting (`cass usage`).
+//!
+//! Connectors record the tokens each agent message consumed in
+//! `message_usage`; this module groups them by day, agent, workspace or model
+//! and prices them with a user-maintained table. Prices live in
+//! `pricing.toml` next to `sources.toml` (or `CASS_PRICING_FILE`), in USD per
+//! million tokens:
+//!
+//! ```toml
+//! [models."claude-sonnet-4"]
+//! input = 3.0
+//! output = 15.0
+//! cache_read = 0.3
+//! cache_write = 3.75
+//!
+//! [models."gpt-5"]
+//! input = 1.25
+//! output = 10.0
+//! ```
+//!
+//! Model keys match as prefixes (the longest wins), so `claude-sonnet-4`
+//! also prices `claude-sonnet-4-20250514`. Cache prices default to the input
+//! price. cass ships no prices of its own; models without one are reported
+//! as unpriced instead of costing zero.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::model::types::TokenUsage;
+use crate::sources::provenance::SourceFilter;
+
+/// Key used for usage recorded without a model name.
+pub const UNKNOWN_MODEL: &str = "unknown";
+
+/// Price of one model in USD per million tokens.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub output: f64,
+    /// Defaults to `input`
+    #[serde(default)]
+    pub cache_read: Option<f64>,
+    /// Defaults to `input`
+    #[serde(default)]
+    pub cache_write: Option<f64>,
+}
+
+impl ModelPrice {
+    /// Cost of `usage` in USD.
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        let per_token = |tokens: i64, price: f64| tokens as f64 * price / 1_000_000.0;
+        per_token(usage.input_tokens, self.input)
+            + per_token(usage.output_tokens, self.output)
+            + per_token(
+                usage.cache_read_tokens,
+                self.cache_read.unwrap_or(self.input),
+            )
+            + per_token(
+                usage.cache_write_tokens,
+                self.cache_write.unwrap_or(self.input),
+            )
+    }
+}
+
+/// Per-model prices from `pricing.toml`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct PricingTable {
+    #[serde(default)]
+    pub models: BTreeMap<String, ModelPrice>,
+}
+
+impl PricingTable {
+    /// `CASS_PRICING_FILE`, else `pricing.toml` next to `sources.toml`
+    pub fn path() -> Option<PathBuf> {
+        if let Ok(path) = dotenvy::var("CASS_PRICING_FILE") {
+            return Some(PathBuf::from(path));
+        }
+        crate::sources::config::SourcesConfig::config_path()
+            .ok()
+            .and_then(|p| p.parent().map(|dir| dir.join("pricing.toml")))
+    }
+
+    /// Load the table at `path`; a missing file is an empty table.
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                toml::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    /// Price of `model`: the longest key that is a prefix of it.
+    pub fn price_for(&self, model: &str) -> Option<&ModelPrice> {
+        self.models
+            .iter()
+            .filter(|(key, _)| model.starts_with(key.as_str()))
+            .max_by_key(|(key, _)| key.len())
+            .map(|(_, price)| price)
+    }
+}
+
+/// Dimension `cass usage` groups by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageGrouping {
+    Day,
+    Agent,
+    Workspace,
+    Model,
+}
+
+impl UsageGrouping {
+    fn key_sql(self) -> &'static str {
+        match self {
+            Self::Day => {
+                "date(COALESCE(m.created_at, c.started_at, 0) / 1000, 'unixepoch', 'localtime')"
+            }
+            Self::Agent => "a.slug",
+            Self::Workspace => "COALESCE(w.path, '(none)')",
+            // Matches UNKNOWN_MODEL
+            Self::Model => "COALESCE(u.model, 'unknown')",
+        }
+    }
+}
+
+/// Which usage to report. Times are epoch milliseconds and apply to the
+/// message timestamp (the session start when a message has none).
+#[derive(Debug, Clone, Default)]
+pub struct UsageFilter {
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    pub agents: Vec<String>,
+    /// Substring of the workspace path
+    pub workspace: Option<String>,
+    pub source: Option<SourceFilter>,
+}
+
+/// One group of a usage report.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageRow {
+    pub key: String,
+    pub sessions: usize,
+    pub messages: i64,
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cache_write_tokens: i64,
+    pub total_tokens: i64,
+    /// Cost of the priced part of this group's usage
+    pub cost_usd: f64,
+    /// Tokens from models without a price
+    pub unpriced_tokens: i64,
+}
+
+impl UsageRow {
+    fn add(&mut self, messages: i64, usage: &TokenUsage, cost: Option<f64>) {
+        self.messages += messages;
+        self.input_tokens += usage.input_tokens;
+        self.output_tokens += usage.output_tokens;
+        self.cache_read_tokens += usage.cache_read_tokens;
+        self.cache_write_tokens += usage.cache_write_tokens;
+        self.total_tokens += usage.total();
+        match cost {
+            Some(cost) => self.cost_usd += cost,
+            None => self.unpriced_tokens += usage.total(),
+        }
+    }
+}
+
+/// Result of [`report`], serialized as-is for `cass usage --json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageReport {
+    pub group_by: UsageGrouping,
+    pub rows: Vec<UsageRow>,
+    pub totals: UsageRow,
+    /// Models that had usage but no entry in the pricing table
+    pub unpriced_models: Vec<String>,
+    pub pricing_file: Option<PathBuf>,
+}
+
+/// Group the recorded token usage matching `filter` by `group_by` and price it.
+pub fn report(
+    conn: &Connection,
+    group_by: UsageGrouping,
+    filter: &UsageFilter,
+    pricing: &PricingTable,
+    pricing_file: Option<PathBuf>,
+) -> Result<UsageReport> {
+    let mut sql = format!(
+        "SELECT {key}, c.id, COALESCE(u.model, '{UNKNOWN_MODEL}'), COUNT(*),
+                SUM(u.input_tokens), SUM(u.output_tokens),
+                SUM(u.cache_read_tokens), SUM(u.cache_write_tokens)
+         FROM message_usage u
+         JOIN messages m ON m.id = u.message_id
+         JOIN conversations c ON c.id = m.conversation_id
+         JOIN agents a ON a.id = c.agent_id
+         LEFT JOIN workspaces w ON w.id = c.workspace_id
+         WHERE 1 = 1",
+        key = group_by.key_sql()
+    );
+    let mut params: Vec<rusqlite::types::Value> = Vec::new();
+    let at = "COALESCE(m.created_at, c.started_at)";
+    if let Some(since) = filter.since {
+        sql.push_str(&format!(" AND {at} >= ?"));
+        params.push(since.into());
+    }
+    if let Some(until) = filter.until {
+        sql.push_str(&format!(" AND {at} <= ?"));
+        params.push(until.into());
+    }
+    if !filter.agents.is_empty() {
+        let marks = vec!["?"; filter.agents.len()].join(", ");
+        sql.push_str(&format!(" AND a.slug IN ({marks})"));
+        params.extend(filter.agents.iter().map(|a| a.clone().into()));
+    }
+    if let Some(workspace) = &filter.workspace {
+        sql.push_str(" AND instr(w.path, ?) > 0");
+        params.push(workspace.clone().into());
+    }
+    match &filter.source {
+        None | Some(SourceFilter::All) => {}
+        Some(SourceFilter::Local) => sql.push_str(" AND c.source_id = 'local'"),
+        Some(SourceFilter::Remote) => {
+            sql.push_str(" AND c.source_id != 'local' AND ");
+            sql.push_str(crate::NOT_IMPORTED_SQL);
+        }
+        Some(SourceFilter::SourceId(id)) => {
+            sql.push_str(" AND c.source_id = ?");
+            params.push(id.clone().into());
+        }
+    }
+    sql.push_str(" GROUP BY 1, 2, 3");
+
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(rusqlite::params_from_iter(params), |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, i64>(3)?,
+            TokenUsage {
+                model: None,
+                input_tokens: row.get(4)?,
+                output_tokens: row.get(5)?,
+                cache_read_tokens: row.get(6)?,
+                cache_write_tokens: row.get(7)?,
+            },
+        ))
+    })?;
+
+    let mut groups: BTreeMap<String, (UsageRow, HashSet<i64>)> = BTreeMap::new();
+    let mut totals = UsageRow {
+        key: "total".to_string(),
+        ..UsageRow::default()
+    };
+    let mut sessions = HashSet::new();
+    let mut unpriced = BTreeSet::new();
+    for row in rows {
+        let (key, conversation_id, model, messages, usage) = row?;
+        let cost = pricing.price_for(&model).map(|p| p.cost(&usage));
+        if cost.is_none() {
+            unpriced.insert(model);
+        }
+
+        let (group, group_sessions) = groups.entry(key.clone()).or_insert_with(|| {
+            (
+                UsageRow {
+                    key,
+                    ..UsageRow::default()
+                },
+                HashSet::new(),
+            )
+        });
+        group.add(messages, &usage, cost);
+        group_sessions.insert(conversation_id);
+        totals.add(messages, &usage, cost);
+        sessions.insert(conversation_id);
+    }
+    totals.sessions = sessions.len();
+
+    let mut rows: Vec<UsageRow> = groups
+        .into_values()
+        .map(|(mut row, group_sessions)| {
+            row.sessions = group_sessions.len();
+            row
+        })
+        .collect();
+    // Days read best in order; everything else by spend, then volume
+    if group_by != UsageGrouping::Day {
+        rows.sort_by(|a, b| {
+            b.cost_usd
+                .total_cmp(&a.cost_usd)
+                .then(b.total_tokens.cmp(&a.total_tokens))
+                .then(a.key.cmp(&b.key))
+        });
+    }
+
+    Ok(UsageReport {
+        group_by,
+        rows,
+        totals,
+        unpriced_models: unpriced.into_iter().collect(),
+        pricing_file,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(toml: &str) -> PricingTable {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn longest_prefix_prices_a_model() {
+        let pricing = table(
+            r#"
+            [models."claude"]
+            input = 1.0
+            output = 2.0
+
+            [models."claude-opus"]
+            input = 15.0
+            output = 75.0
+            cache_read = 1.5
+            "#,
+        );
+        assert_eq!(
+            pricing.price_for("claude-opus-4-1").map(|p| p.input),
+            Some(15.0)
+        );
+        assert_eq!(
+            pricing.price_for("claude-haiku").map(|p| p.input),
+            Some(1.0)
+        );
+        assert!(pricing.price_for("gpt-5").is_none());
+
+        let usage = TokenUsage {
+            model: None,
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            cache_read_tokens: 2_000_000,
+            cache_write_tokens: 1_000_000,
+        };
+        let cost = pricing.price_for("claude-opus-4").unwrap().cost(&usage);
+        // 15 + 7.5 + 3 (cache read) + 15 (cache write at the input price)
+        assert!((cost - 40.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn missing_pricing_file_is_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let pricing = PricingTable::load(&dir.path().join("pricing.toml")).unwrap();
+        assert!(pricing.models.is_empty());
+    }
+}
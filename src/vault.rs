@@ -0,0 +1,816 @@
+//! Optional at-rest encryption of the data directory (`cass vault`).
+//!
+//! Locking packs the sensitive parts of the data dir (SQLite database, Tantivy
+//! index, vector indexes, synced remote sessions, imported bundles and account
+//! exports, bookmarks) into one stream, encrypts it under `vault/sealed/` with
+//! the pages envelope engine (Argon2id password slots, HKDF key-file slots,
+//! chunked AES-256-GCM) and deletes the plaintext. Unlocking restores the files
+//! and starts a session; once the session has timed out, the next cass command
+//! locks the data dir again.
+//!
+//! While a session is open its data key sits in `vault/session.key` (mode
+//! 0600), next to the plaintext it protects, so that locking needs no password.
+//! Locking removes it.
+//!
+//! Long-running commands (TUI, `index --watch`, `serve`, `mcp`, the daemon)
+//! hold a shared lock on `.in-use.lock` in the data dir for as long as they
+//! run. Sealing takes it exclusively, so a lock never deletes files another
+//! process still has open; an expired session stays open until they exit.
+
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use base64::prelude::*;
+use rand::{RngCore, rngs::OsRng};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::bundle::BUNDLES_DIR;
+use crate::import::IMPORTS_DIR;
+use crate::pages::encrypt::{
+    DEFAULT_CHUNK_SIZE, DecryptionEngine, EncryptionEngine, SecretKey, SlotType, load_config,
+};
+use crate::search::vector_index::VECTOR_INDEX_DIR;
+
+/// Vault directory inside the data dir
+pub const VAULT_DIR: &str = "vault";
+const SEALED_DIR: &str = "sealed";
+const STATE_FILE: &str = "vault.json";
+const SESSION_KEY_FILE: &str = "session.key";
+const IN_USE_FILE: &str = ".in-use.lock";
+const ARCHIVE_MAGIC: &[u8; 8] = b"CASSVLT1";
+
+/// Session length used when `cass vault init` is not given `--timeout`
+pub const DEFAULT_SESSION_TIMEOUT_SECS: u64 = 60 * 60;
+
+/// Data-dir entries sealed by a lock (files or directories)
+pub const SEALED_ITEMS: &[&str] = &[
+    "agent_search.db",
+    "agent_search.db-wal",
+    "agent_search.db-shm",
+    "index",
+    VECTOR_INDEX_DIR,
+    "remotes",
+    BUNDLES_DIR,
+    IMPORTS_DIR,
+    "bookmarks.db",
+];
+
+#[derive(Debug, Error)]
+pub enum VaultError {
+    #[error("the data directory is locked")]
+    Locked,
+    #[error("no vault is set up for this data directory")]
+    NotEnabled,
+    #[error("a vault is already set up for this data directory")]
+    AlreadyEnabled,
+    #[error("wrong password or key file")]
+    WrongSecret,
+    #[error("no unlock session key found; unlock again before locking")]
+    NoSession,
+    #[error("unlocking would overwrite existing files: {}", .0.join(", "))]
+    Conflict(Vec<String>),
+    #[error("the data directory is in use by another cass process")]
+    InUse,
+}
+
+/// How to open the vault's key slots
+#[derive(Clone, Copy)]
+pub enum VaultSecret<'a> {
+    Password(&'a str),
+    /// Contents of a key file created by `cass vault init --key-file`
+    KeyFile(&'a [u8]),
+}
+
+/// Persisted in `vault/vault.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultState {
+    version: u8,
+    locked: bool,
+    session_timeout_secs: u64,
+    #[serde(default)]
+    locked_at: Option<i64>,
+    #[serde(default)]
+    unlocked_at: Option<i64>,
+    #[serde(default)]
+    expires_at: Option<i64>,
+}
+
+/// Lock state reported by `cass vault status`, `cass status` and `cass health`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct VaultStatus {
+    pub enabled: bool,
+    pub locked: bool,
+    pub session_timeout_secs: Option<u64>,
+    /// When the current session ends (ms since epoch); `None` while locked
+    pub expires_at: Option<i64>,
+    pub password_slots: usize,
+    pub key_file_slots: usize,
+}
+
+/// Current vault state of `data_dir` (not enabled if there is no vault).
+pub fn status(data_dir: &Path) -> Result<VaultStatus> {
+    let Some(state) = read_state(data_dir)? else {
+        return Ok(VaultStatus::default());
+    };
+    let config = load_config(sealed_dir(data_dir)).context("reading vault key slots")?;
+    let slots = |kind| {
+        config
+            .key_slots
+            .iter()
+            .filter(|s| s.slot_type == kind)
+            .count()
+    };
+    Ok(VaultStatus {
+        enabled: true,
+        locked: state.locked,
+        session_timeout_secs: Some(state.session_timeout_secs),
+        expires_at: if state.locked { None } else { state.expires_at },
+        password_slots: slots(SlotType::Password),
+        key_file_slots: slots(SlotType::Recovery),
+    })
+}
+
+/// Set up a vault with a password (and optionally a key file) and lock right away.
+pub fn init(
+    data_dir: &Path,
+    password: &str,
+    key_file: Option<&[u8]>,
+    session_timeout_secs: u64,
+) -> Result<VaultStatus> {
+    if read_state(data_dir)?.is_some() {
+        return Err(VaultError::AlreadyEnabled.into());
+    }
+    let mut engine = EncryptionEngine::new(DEFAULT_CHUNK_SIZE);
+    engine.add_password_slot(password)?;
+    if let Some(secret) = key_file {
+        engine.add_recovery_slot(secret)?;
+    }
+    fs::create_dir_all(vault_dir(data_dir))?;
+    let mut state = VaultState {
+        version: 1,
+        locked: false,
+        session_timeout_secs,
+        locked_at: None,
+        unlocked_at: None,
+        expires_at: None,
+    };
+    seal(data_dir, &engine, &mut state)?;
+    status(data_dir)
+}
+
+/// Encrypt the data dir with the key of the open session. A no-op when already locked.
+pub fn lock(data_dir: &Path) -> Result<VaultStatus> {
+    let mut state = read_state(data_dir)?.ok_or(VaultError::NotEnabled)?;
+    if state.locked {
+        return status(data_dir);
+    }
+    let key = read_session_key(data_dir)?;
+    let config = load_config(sealed_dir(data_dir))?;
+    let export_id: [u8; 16] = BASE64_STANDARD
+        .decode(&config.export_id)?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("invalid export id in vault config"))?;
+    let engine = EncryptionEngine::with_key(
+        key,
+        export_id,
+        config.key_slots.clone(),
+        config.payload.chunk_size,
+    );
+    seal(data_dir, &engine, &mut state)?;
+    status(data_dir)
+}
+
+/// Decrypt the data dir and open a session of `timeout_secs` (default: the
+/// vault's timeout). Unlocking an open vault checks the secret and extends it.
+pub fn unlock(
+    data_dir: &Path,
+    secret: VaultSecret<'_>,
+    timeout_secs: Option<u64>,
+) -> Result<VaultStatus> {
+    let mut state = read_state(data_dir)?.ok_or(VaultError::NotEnabled)?;
+    let sealed = sealed_dir(data_dir);
+    let config = load_config(&sealed)?;
+    let decryptor = match secret {
+        VaultSecret::Password(password) => DecryptionEngine::unlock_with_password(config, password),
+        VaultSecret::KeyFile(bytes) => DecryptionEngine::unlock_with_recovery(config, bytes),
+    }
+    .map_err(|_| VaultError::WrongSecret)?;
+
+    if state.locked {
+        let conflicts: Vec<String> = SEALED_ITEMS
+            .iter()
+            .filter(|item| data_dir.join(item).exists())
+            .map(|item| (*item).to_string())
+            .collect();
+        if !conflicts.is_empty() {
+            return Err(VaultError::Conflict(conflicts).into());
+        }
+        let mut writer = ArchiveWriter::new(data_dir);
+        let restored = decryptor
+            .decrypt_to_writer(&sealed, &mut writer, |_, _| {})
+            .and_then(|()| writer.finish());
+        if let Err(e) = restored {
+            remove_items(data_dir)?;
+            return Err(e.context("restoring the data directory"));
+        }
+    }
+
+    write_session_key(data_dir, decryptor.key())?;
+    let now = now_millis();
+    let timeout = timeout_secs.unwrap_or(state.session_timeout_secs);
+    state.locked = false;
+    state.unlocked_at = Some(now);
+    state.expires_at = Some(now.saturating_add((timeout as i64).saturating_mul(1000)));
+    write_state(data_dir, &state)?;
+    status(data_dir)
+}
+
+/// Re-lock the data dir if its session has expired. Returns the resulting status.
+///
+/// An expired session stays open while a long-running command holds the data dir.
+pub fn enforce_session_timeout(data_dir: &Path) -> Result<VaultStatus> {
+    let Some(state) = read_state(data_dir)? else {
+        return Ok(VaultStatus::default());
+    };
+    if !state.locked && state.expires_at.is_some_and(|at| at <= now_millis()) {
+        tracing::info!(data_dir = %data_dir.display(), "vault session expired; locking");
+        return match lock(data_dir) {
+            Err(e) if matches!(e.downcast_ref::<VaultError>(), Some(VaultError::InUse)) => {
+                tracing::info!("data dir in use; keeping the expired session open");
+                status(data_dir)
+            }
+            result => result,
+        };
+    }
+    status(data_dir)
+}
+
+/// Fail with [`VaultError::Locked`] unless the data dir is usable.
+pub fn ensure_unlocked(data_dir: &Path) -> Result<()> {
+    if enforce_session_timeout(data_dir)?.locked {
+        return Err(VaultError::Locked.into());
+    }
+    Ok(())
+}
+
+/// Shared hold on the data dir; the vault cannot seal it until this is dropped.
+#[derive(Debug)]
+pub struct InUseGuard {
+    _file: File,
+}
+
+/// Keep the vault from sealing `data_dir` while a long-running command runs.
+///
+/// Fails with [`VaultError::Locked`] if the data dir was sealed before the hold
+/// was taken.
+pub fn hold_in_use(data_dir: &Path) -> Result<InUseGuard> {
+    fs::create_dir_all(data_dir)?;
+    let file = open_in_use_file(data_dir)?;
+    file.lock_shared()
+        .context("taking the data directory in-use lock")?;
+    if read_state(data_dir)?.is_some_and(|state| state.locked) {
+        return Err(VaultError::Locked.into());
+    }
+    Ok(InUseGuard { _file: file })
+}
+
+/// Remove the vault of an unlocked data dir, leaving the plaintext in place.
+pub fn disable(data_dir: &Path) -> Result<()> {
+    let state = read_state(data_dir)?.ok_or(VaultError::NotEnabled)?;
+    if state.locked {
+        return Err(VaultError::Locked.into());
+    }
+    fs::remove_dir_all(vault_dir(data_dir))?;
+    Ok(())
+}
+
+/// Create a key file with a random secret; refuses to overwrite an existing file.
+pub fn create_key_file(path: &Path) -> Result<Vec<u8>> {
+    let mut secret = [0u8; 32];
+    OsRng.fill_bytes(&mut secret);
+    let encoded = BASE64_STANDARD.encode(secret);
+    let mut file = private_file(path, true)
+        .with_context(|| format!("creating key file {}", path.display()))?;
+    writeln!(file, "{encoded}")?;
+    Ok(encoded.into_bytes())
+}
+
+/// Read a key file, ignoring surrounding whitespace.
+pub fn read_key_file(path: &Path) -> Result<Vec<u8>> {
+    let contents =
+        fs::read(path).with_context(|| format!("reading key file {}", path.display()))?;
+    let secret = contents.trim_ascii().to_vec();
+    if secret.is_empty() {
+        bail!("key file {} is empty", path.display());
+    }
+    Ok(secret)
+}
+
+fn vault_dir(data_dir: &Path) -> PathBuf {
+    data_dir.join(VAULT_DIR)
+}
+
+fn sealed_dir(data_dir: &Path) -> PathBuf {
+    vault_dir(data_dir).join(SEALED_DIR)
+}
+
+fn read_state(data_dir: &Path) -> Result<Option<VaultState>> {
+    let path = vault_dir(data_dir).join(STATE_FILE);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)?;
+    Ok(Some(
+        serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))?,
+    ))
+}
+
+fn write_state(data_dir: &Path, state: &VaultState) -> Result<()> {
+    let path = vault_dir(data_dir).join(STATE_FILE);
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(state)?)?;
+    fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+fn read_session_key(data_dir: &Path) -> Result<SecretKey> {
+    let path = vault_dir(data_dir).join(SESSION_KEY_FILE);
+    let encoded = fs::read_to_string(&path).map_err(|_| VaultError::NoSession)?;
+    let bytes: [u8; 32] = BASE64_STANDARD
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(VaultError::NoSession)?;
+    Ok(SecretKey::from_bytes(bytes))
+}
+
+fn write_session_key(data_dir: &Path, key: &SecretKey) -> Result<()> {
+    let path = vault_dir(data_dir).join(SESSION_KEY_FILE);
+    let mut file = private_file(&path, false)?;
+    file.write_all(BASE64_STANDARD.encode(key.as_bytes()).as_bytes())?;
+    Ok(())
+}
+
+fn private_file(path: &Path, create_new: bool) -> io::Result<File> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true);
+    if create_new {
+        options.create_new(true);
+    } else {
+        options.create(true).truncate(true);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+fn open_in_use_file(data_dir: &Path) -> io::Result<File> {
+    fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(data_dir.join(IN_USE_FILE))
+}
+
+/// Encrypt the sealed items into `vault/sealed`, then delete the plaintext.
+///
+/// Refuses with [`VaultError::InUse`] while a long-running command holds the data dir.
+fn seal(data_dir: &Path, engine: &EncryptionEngine, state: &mut VaultState) -> Result<()> {
+    let in_use = open_in_use_file(data_dir)?;
+    match in_use.try_lock() {
+        Ok(()) => {}
+        Err(fs::TryLockError::WouldBlock) => return Err(VaultError::InUse.into()),
+        Err(fs::TryLockError::Error(e)) => {
+            return Err(anyhow::Error::new(e).context("taking the data directory in-use lock"));
+        }
+    }
+    let vault = vault_dir(data_dir);
+    let entries = collect_entries(data_dir)?;
+    let archive_len = ARCHIVE_MAGIC.len() as u64
+        + entries
+            .iter()
+            .map(|e| 4 + e.rel.len() as u64 + 8 + e.size)
+            .sum::<u64>()
+        + 4;
+
+    let staging = vault.join("sealed.tmp");
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    engine
+        .encrypt_reader(
+            ArchiveReader::new(entries),
+            archive_len,
+            &staging,
+            |_, _| {},
+        )
+        .context("encrypting the data directory")?;
+
+    // Swap in the new container before any plaintext is removed
+    let sealed = sealed_dir(data_dir);
+    let previous = vault.join("sealed.old");
+    if sealed.exists() {
+        if previous.exists() {
+            fs::remove_dir_all(&previous)?;
+        }
+        fs::rename(&sealed, &previous)?;
+    }
+    fs::rename(&staging, &sealed)?;
+    if previous.exists() {
+        fs::remove_dir_all(&previous)?;
+    }
+
+    remove_items(data_dir)?;
+    let session_key = vault.join(SESSION_KEY_FILE);
+    if session_key.exists() {
+        fs::remove_file(session_key)?;
+    }
+    state.locked = true;
+    state.locked_at = Some(now_millis());
+    state.unlocked_at = None;
+    state.expires_at = None;
+    write_state(data_dir, state)
+}
+
+fn remove_items(data_dir: &Path) -> Result<()> {
+    for item in SEALED_ITEMS {
+        let path = data_dir.join(item);
+        if path.is_dir() {
+            fs::remove_dir_all(&path)?;
+        } else if path.exists() {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+fn now_millis() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+// -----------------------------------------------------------------------------
+// Archive stream: MAGIC, then per file `u32 path len | path | u64 size | bytes`,
+// terminated by a zero path length. Paths are relative with `/` separators.
+// -----------------------------------------------------------------------------
+
+struct ArchiveEntry {
+    rel: String,
+    path: PathBuf,
+    size: u64,
+}
+
+fn collect_entries(data_dir: &Path) -> Result<Vec<ArchiveEntry>> {
+    let mut entries = Vec::new();
+    for item in SEALED_ITEMS {
+        let root = data_dir.join(item);
+        if !root.exists() {
+            continue;
+        }
+        for entry in walkdir::WalkDir::new(&root).sort_by_file_name() {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let rel = entry
+                .path()
+                .strip_prefix(data_dir)?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            entries.push(ArchiveEntry {
+                rel,
+                path: entry.path().to_path_buf(),
+                size: entry.metadata()?.len(),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+struct ArchiveReader {
+    entries: VecDeque<ArchiveEntry>,
+    pending: io::Cursor<Vec<u8>>,
+    file: Option<(File, u64)>,
+    finished: bool,
+}
+
+impl ArchiveReader {
+    fn new(entries: Vec<ArchiveEntry>) -> Self {
+        Self {
+            entries: entries.into(),
+            pending: io::Cursor::new(ARCHIVE_MAGIC.to_vec()),
+            file: None,
+            finished: false,
+        }
+    }
+}
+
+impl Read for ArchiveReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            let n = self.pending.read(buf)?;
+            if n > 0 {
+                return Ok(n);
+            }
+            if let Some((file, remaining)) = &mut self.file {
+                if *remaining == 0 {
+                    self.file = None;
+                    continue;
+                }
+                let limit = buf.len().min(*remaining as usize);
+                let n = file.read(&mut buf[..limit])?;
+                if n == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "a file shrank while the data directory was being locked",
+                    ));
+                }
+                *remaining -= n as u64;
+                return Ok(n);
+            }
+            let header = if let Some(entry) = self.entries.pop_front() {
+                let mut header = Vec::with_capacity(12 + entry.rel.len());
+                header.extend_from_slice(&(entry.rel.len() as u32).to_le_bytes());
+                header.extend_from_slice(entry.rel.as_bytes());
+                header.extend_from_slice(&entry.size.to_le_bytes());
+                self.file = Some((File::open(&entry.path)?, entry.size));
+                header
+            } else if !self.finished {
+                self.finished = true;
+                0u32.to_le_bytes().to_vec()
+            } else {
+                return Ok(0);
+            };
+            self.pending = io::Cursor::new(header);
+        }
+    }
+}
+
+enum ArchiveState {
+    Magic,
+    PathLen,
+    Path(usize),
+    Size(PathBuf),
+    Data(File, u64),
+    Done,
+}
+
+/// Unpacks the archive stream into the data dir as it is decrypted.
+struct ArchiveWriter {
+    root: PathBuf,
+    buf: Vec<u8>,
+    state: ArchiveState,
+}
+
+impl ArchiveWriter {
+    fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            buf: Vec::new(),
+            state: ArchiveState::Magic,
+        }
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        if !matches!(self.state, ArchiveState::Done) {
+            bail!("vault archive is truncated");
+        }
+        Ok(())
+    }
+
+    /// Buffer header bytes until `len` are available.
+    fn take_header(&mut self, data: &mut &[u8], len: usize) -> Option<Vec<u8>> {
+        let needed = len - self.buf.len();
+        let take = needed.min(data.len());
+        self.buf.extend_from_slice(&data[..take]);
+        *data = &data[take..];
+        (self.buf.len() == len).then(|| std::mem::take(&mut self.buf))
+    }
+
+    fn target(&self, rel: &str) -> io::Result<PathBuf> {
+        let rel_path = Path::new(rel);
+        let top_level = rel.split('/').next().unwrap_or_default();
+        let safe = SEALED_ITEMS.contains(&top_level)
+            && rel_path
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)));
+        if !safe {
+            return Err(invalid(format!("unexpected path in vault archive: {rel}")));
+        }
+        Ok(self.root.join(rel_path))
+    }
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+impl Write for ArchiveWriter {
+    fn write(&mut self, mut data: &[u8]) -> io::Result<usize> {
+        let written = data.len();
+        while !data.is_empty() {
+            match std::mem::replace(&mut self.state, ArchiveState::Done) {
+                ArchiveState::Magic => match self.take_header(&mut data, ARCHIVE_MAGIC.len()) {
+                    Some(magic) if magic == ARCHIVE_MAGIC => self.state = ArchiveState::PathLen,
+                    Some(_) => return Err(invalid("not a cass vault archive".into())),
+                    None => self.state = ArchiveState::Magic,
+                },
+                ArchiveState::PathLen => match self.take_header(&mut data, 4) {
+                    Some(bytes) => {
+                        let len = u32::from_le_bytes(bytes.try_into().expect("4 bytes")) as usize;
+                        self.state = if len == 0 {
+                            ArchiveState::Done
+                        } else {
+                            ArchiveState::Path(len)
+                        };
+                    }
+                    None => self.state = ArchiveState::PathLen,
+                },
+                ArchiveState::Path(len) => match self.take_header(&mut data, len) {
+                    Some(bytes) => {
+                        let rel = String::from_utf8(bytes)
+                            .map_err(|_| invalid("non UTF-8 path in vault archive".into()))?;
+                        self.state = ArchiveState::Size(self.target(&rel)?);
+                    }
+                    None => self.state = ArchiveState::Path(len),
+                },
+                ArchiveState::Size(path) => match self.take_header(&mut data, 8) {
+                    Some(bytes) => {
+                        let size = u64::from_le_bytes(bytes.try_into().expect("8 bytes"));
+                        if let Some(parent) = path.parent() {
+                            fs::create_dir_all(parent)?;
+                        }
+                        let file = File::create(&path)?;
+                        self.state = if size == 0 {
+                            ArchiveState::PathLen
+                        } else {
+                            ArchiveState::Data(file, size)
+                        };
+                    }
+                    None => self.state = ArchiveState::Size(path),
+                },
+                ArchiveState::Data(mut file, remaining) => {
+                    let take = data.len().min(remaining as usize);
+                    file.write_all(&data[..take])?;
+                    data = &data[take..];
+                    self.state = if remaining == take as u64 {
+                        ArchiveState::PathLen
+                    } else {
+                        ArchiveState::Data(file, remaining - take as u64)
+                    };
+                }
+                ArchiveState::Done => {
+                    return Err(invalid("trailing data after vault archive".into()));
+                }
+            }
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if let ArchiveState::Data(file, _) = &mut self.state {
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn populate(data_dir: &Path) {
+        fs::write(data_dir.join("agent_search.db"), b"sqlite bytes").unwrap();
+        fs::create_dir_all(data_dir.join("index/v4")).unwrap();
+        fs::write(data_dir.join("index/v4/meta.json"), b"{}").unwrap();
+        fs::write(data_dir.join("index/v4/empty.seg"), b"").unwrap();
+        fs::create_dir_all(data_dir.join(VECTOR_INDEX_DIR)).unwrap();
+        fs::write(
+            data_dir.join(VECTOR_INDEX_DIR).join("index-hash.cvvi"),
+            vec![42u8; 5000],
+        )
+        .unwrap();
+        fs::create_dir_all(data_dir.join("models")).unwrap();
+        fs::write(data_dir.join("models/model.onnx"), b"weights").unwrap();
+    }
+
+    #[test]
+    fn lock_and_unlock_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let data_dir = dir.path();
+        populate(data_dir);
+
+        let status = init(data_dir, "correct horse", None, 600).unwrap();
+        assert!(status.enabled && status.locked);
+        assert_eq!(status.password_slots, 1);
+        assert!(!data_dir.join("agent_search.db").exists());
+        assert!(!data_dir.join("index").exists());
+        // Models are not sensitive and stay in place
+        assert!(data_dir.join("models/model.onnx").exists());
+        assert!(ensure_unlocked(data_dir).is_err());
+
+        let err = unlock(data_dir, VaultSecret::Password("wrong"), None).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VaultError>(),
+            Some(VaultError::WrongSecret)
+        ));
+
+        let status = unlock(data_dir, VaultSecret::Password("correct horse"), None).unwrap();
+        assert!(!status.locked);
+        assert!(status.expires_at.is_some());
+        ensure_unlocked(data_dir).unwrap();
+        assert_eq!(
+            fs::read(data_dir.join("agent_search.db")).unwrap(),
+            b"sqlite bytes"
+        );
+        assert!(data_dir.join("index/v4/empty.seg").exists());
+        assert_eq!(
+            fs::read(data_dir.join(VECTOR_INDEX_DIR).join("index-hash.cvvi")).unwrap(),
+            vec![42u8; 5000]
+        );
+
+        // Changes made while unlocked are sealed by the next lock
+        fs::write(data_dir.join("bookmarks.db"), b"bookmarks").unwrap();
+        assert!(lock(data_dir).unwrap().locked);
+        assert!(!vault_dir(data_dir).join(SESSION_KEY_FILE).exists());
+        unlock(data_dir, VaultSecret::Password("correct horse"), None).unwrap();
+        assert_eq!(
+            fs::read(data_dir.join("bookmarks.db")).unwrap(),
+            b"bookmarks"
+        );
+    }
+
+    #[test]
+    fn key_file_unlocks_and_expired_sessions_relock() {
+        let dir = TempDir::new().unwrap();
+        let data_dir = dir.path();
+        populate(data_dir);
+        let key_path = dir.path().join("cass.key");
+        let secret = create_key_file(&key_path).unwrap();
+        assert!(create_key_file(&key_path).is_err(), "must not overwrite");
+        assert_eq!(read_key_file(&key_path).unwrap(), secret);
+
+        init(data_dir, "correct horse", Some(&secret), 600).unwrap();
+        let status = unlock(data_dir, VaultSecret::KeyFile(&secret), Some(0)).unwrap();
+        assert_eq!(status.key_file_slots, 1);
+        assert!(data_dir.join("agent_search.db").exists());
+
+        // A zero-length session is already over
+        assert!(enforce_session_timeout(data_dir).unwrap().locked);
+        assert!(!data_dir.join("agent_search.db").exists());
+    }
+
+    #[test]
+    fn long_running_commands_keep_the_data_dir_from_sealing() {
+        let dir = TempDir::new().unwrap();
+        let data_dir = dir.path();
+        populate(data_dir);
+        init(data_dir, "correct horse", None, 600).unwrap();
+        assert!(matches!(
+            hold_in_use(data_dir)
+                .unwrap_err()
+                .downcast_ref::<VaultError>(),
+            Some(VaultError::Locked)
+        ));
+        unlock(data_dir, VaultSecret::Password("correct horse"), Some(0)).unwrap();
+
+        let guard = hold_in_use(data_dir).unwrap();
+        let err = lock(data_dir).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VaultError>(),
+            Some(VaultError::InUse)
+        ));
+        // The expired session is left open rather than pulled out from under the holder
+        assert!(!enforce_session_timeout(data_dir).unwrap().locked);
+        assert!(data_dir.join("agent_search.db").exists());
+
+        drop(guard);
+        assert!(enforce_session_timeout(data_dir).unwrap().locked);
+    }
+
+    #[test]
+    fn unlock_refuses_to_overwrite_new_files() {
+        let dir = TempDir::new().unwrap();
+        let data_dir = dir.path();
+        populate(data_dir);
+        init(data_dir, "correct horse", None, 600).unwrap();
+
+        fs::write(data_dir.join("agent_search.db"), b"created while locked").unwrap();
+        let err = unlock(data_dir, VaultSecret::Password("correct horse"), None).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<VaultError>(),
+            Some(VaultError::Conflict(items)) if items == &["agent_search.db".to_string()]
+        ));
+        assert!(status(data_dir).unwrap().locked);
+    }
+}
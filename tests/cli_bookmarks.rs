@@ -0,0 +1,118 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use rusqlite::Connection;
+use serde_json::Value;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn bookmarks_json(data_dir: &Path, args: &[&str]) -> Value {
+    let out = cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .arg("bookmarks")
+        .args(args)
+        .arg("--data-dir")
+        .arg(data_dir)
+        .arg("--json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&out).expect("valid bookmarks json")
+}
+
+#[test]
+fn bookmarks_add_list_edit_remove_roundtrip() {
+    let tmp = TempDir::new().expect("tempdir");
+    let data_dir = tmp.path();
+
+    let added = bookmarks_json(
+        data_dir,
+        &[
+            "add",
+            "/sessions/a.jsonl",
+            "--line",
+            "3",
+            "--title",
+            "JWT refresh",
+            "--tags",
+            "auth, good",
+        ],
+    );
+    let id = added["bookmark"]["id"].as_i64().expect("bookmark id");
+    assert_eq!(added["bookmark"]["line_number"], 3);
+
+    bookmarks_json(data_dir, &["add", "/sessions/b.jsonl", "--tags", "misc"]);
+
+    let listed = bookmarks_json(data_dir, &["list", "--tag", "auth"]);
+    assert_eq!(listed["count"], 1);
+    assert_eq!(listed["bookmarks"][0]["title"], "JWT refresh");
+
+    let edited = bookmarks_json(data_dir, &["edit", &id.to_string(), "--note", "keep"]);
+    assert_eq!(edited["bookmark"]["note"], "keep");
+
+    let removed = bookmarks_json(data_dir, &["remove", &id.to_string()]);
+    assert_eq!(removed["removed"], true);
+    assert_eq!(bookmarks_json(data_dir, &["list"])["count"], 1);
+}
+
+#[test]
+fn bookmarks_add_fills_metadata_from_index() {
+    let tmp = TempDir::new().expect("tempdir");
+    let data_dir = tmp.path();
+
+    // Minimal schema required by the `bookmarks add` metadata lookup.
+    let conn = Connection::open(data_dir.join("agent_search.db")).expect("open db");
+    conn.execute_batch(
+        r#"
+        CREATE TABLE agents (id INTEGER PRIMARY KEY, slug TEXT NOT NULL);
+        CREATE TABLE workspaces (id INTEGER PRIMARY KEY, path TEXT NOT NULL);
+        CREATE TABLE conversations (
+            id INTEGER PRIMARY KEY,
+            agent_id INTEGER NOT NULL,
+            workspace_id INTEGER,
+            title TEXT,
+            source_path TEXT NOT NULL
+        );
+        CREATE TABLE messages (
+            id INTEGER PRIMARY KEY,
+            conversation_id INTEGER NOT NULL,
+            idx INTEGER NOT NULL,
+            content TEXT NOT NULL
+        );
+        INSERT INTO agents (id, slug) VALUES (1, 'codex');
+        INSERT INTO workspaces (id, path) VALUES (1, '/tmp/ws');
+        INSERT INTO conversations (id, agent_id, workspace_id, title, source_path)
+            VALUES (1, 1, 1, 'Auth refactor', '/sessions/a.jsonl');
+        INSERT INTO messages (id, conversation_id, idx, content)
+            VALUES (1, 1, 0, 'first'), (2, 1, 1, 'second message');
+        "#,
+    )
+    .expect("create schema");
+
+    let added = bookmarks_json(data_dir, &["add", "/sessions/a.jsonl", "--line", "2"]);
+    let bookmark = &added["bookmark"];
+    assert_eq!(bookmark["title"], "Auth refactor");
+    assert_eq!(bookmark["agent"], "codex");
+    assert_eq!(bookmark["workspace"], "/tmp/ws");
+    assert_eq!(bookmark["snippet"], "second message");
+}
+
+#[test]
+fn bookmarks_add_rejects_duplicate_location() {
+    let tmp = TempDir::new().expect("tempdir");
+    let data_dir = tmp.path();
+
+    bookmarks_json(data_dir, &["add", "/sessions/a.jsonl"]);
+    cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .args([
+            "bookmarks",
+            "add",
+            "/sessions/a.jsonl",
+            "--json",
+            "--data-dir",
+        ])
+        .arg(data_dir)
+        .assert()
+        .code(2);
+}
@@ -0,0 +1,158 @@
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn base_cmd(home: &Path) -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cass"));
+    cmd.env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1");
+    // Isolate connectors so only the fixture sessions are indexed
+    cmd.env("HOME", home);
+    cmd.env("XDG_DATA_HOME", home.join(".local/share"));
+    cmd.env("XDG_CONFIG_HOME", home.join(".config"));
+    cmd.env("CODEX_HOME", home.join(".codex"));
+    cmd.env("CASS_DATA_DIR", home.join("data"));
+    cmd
+}
+
+fn parse_json(stdout: &[u8]) -> Value {
+    serde_json::Deserializer::from_slice(stdout)
+        .into_iter::<Value>()
+        .next()
+        .expect("json output")
+        .unwrap()
+}
+
+fn run_json(home: &Path, args: &[&str]) -> Value {
+    let output = base_cmd(home).args(args).output().expect("run cass");
+    assert!(
+        output.status.success(),
+        "cass {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    parse_json(&output.stdout)
+}
+
+/// A machine with one indexed Codex session.
+fn ci_runner(text: &str) -> TempDir {
+    let home = TempDir::new().unwrap();
+    let sessions = home.path().join(".codex/sessions/2025/12/01");
+    fs::create_dir_all(&sessions).unwrap();
+    fs::write(
+        sessions.join("rollout-1.jsonl"),
+        [
+            r#"{"type":"turn_context","timestamp":"2025-12-01T10:00:00Z","payload":{"model":"gpt-5-codex"}}"#.to_string(),
+            format!(
+                r#"{{"type":"response_item","timestamp":"2025-12-01T10:00:01Z","payload":{{"role":"user","content":"{text}"}}}}"#
+            ),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    base_cmd(home.path())
+        .args(["index", "--full"])
+        .assert()
+        .success();
+    home
+}
+
+#[test]
+fn bundle_import_is_searchable_and_idempotent() {
+    let ci = ci_runner("rotate the signing keys");
+    let bundle = ci.path().join("runner-7.cassbundle");
+    let bundle_arg = bundle.to_str().unwrap();
+    let exported = run_json(
+        ci.path(),
+        &[
+            "bundle", "export", "-o", bundle_arg, "--name", "runner-7", "--json",
+        ],
+    );
+    assert_eq!(exported["conversations"], 1);
+    assert_eq!(exported["encrypted"], false);
+
+    let hub = TempDir::new().unwrap();
+    base_cmd(hub.path())
+        .args(["index", "--full"])
+        .assert()
+        .success();
+    let imported = run_json(hub.path(), &["bundle", "import", bundle_arg, "--json"]);
+    assert_eq!(imported["source_id"], "runner-7");
+    assert_eq!(imported["lexical_index"], "updated");
+    assert_eq!(imported["new_messages"], imported["messages"]);
+
+    let again = run_json(hub.path(), &["bundle", "import", bundle_arg, "--json"]);
+    assert_eq!(again["new_messages"], 0);
+
+    let result = run_json(hub.path(), &["search", "signing", "--json"]);
+    let hits = result["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["source_id"], "runner-7");
+    assert_eq!(hits[0]["origin_kind"], "bundle");
+
+    // A rebuild re-reads the stored copy
+    base_cmd(hub.path())
+        .args(["index", "--full"])
+        .assert()
+        .success();
+    let result = run_json(
+        hub.path(),
+        &["search", "signing", "--source", "remote", "--json"],
+    );
+    assert_eq!(result["hits"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn encrypted_bundle_needs_its_password() {
+    let ci = ci_runner("bump the cache ttl");
+    let bundle = ci.path().join("secret.cassbundle");
+    let bundle_arg = bundle.to_str().unwrap();
+    let output = base_cmd(ci.path())
+        .args([
+            "bundle",
+            "export",
+            "-o",
+            bundle_arg,
+            "--encrypt",
+            "--password-stdin",
+            "--json",
+        ])
+        .write_stdin("correct horse\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(parse_json(&output.stdout)["encrypted"], true);
+    let raw = fs::read(&bundle).unwrap();
+    assert!(!String::from_utf8_lossy(&raw).contains("cache ttl"));
+
+    let hub = TempDir::new().unwrap();
+    base_cmd(hub.path())
+        .args(["bundle", "import", bundle_arg, "--json"])
+        .assert()
+        .failure();
+    base_cmd(hub.path())
+        .args(["bundle", "import", bundle_arg, "--password-stdin", "--json"])
+        .write_stdin("wrong horse\n")
+        .assert()
+        .failure();
+
+    let output = base_cmd(hub.path())
+        .args([
+            "bundle",
+            "import",
+            bundle_arg,
+            "--source-id",
+            "ci",
+            "--password-stdin",
+            "--dry-run",
+            "--json",
+        ])
+        .write_stdin("correct horse\n")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report = parse_json(&output.stdout);
+    assert_eq!(report["source_id"], "ci");
+    assert_eq!(report["conversations"], 1);
+    assert_eq!(report["lexical_index"], "untouched");
+}
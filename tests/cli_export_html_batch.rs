@@ -0,0 +1,171 @@
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn base_cmd(home: &Path) -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cass"));
+    cmd.env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1");
+    // Isolate connectors so only the fixture sessions are indexed
+    cmd.env("HOME", home);
+    cmd.env("XDG_DATA_HOME", home.join(".local/share"));
+    cmd.env("XDG_CONFIG_HOME", home.join(".config"));
+    cmd.env("CODEX_HOME", home.join(".codex"));
+    cmd.env("CASS_DATA_DIR", home.join("data"));
+    cmd
+}
+
+fn run_json(home: &Path, args: &[&str]) -> Value {
+    let output = base_cmd(home).args(args).output().expect("run cass");
+    assert!(
+        output.status.success(),
+        "cass {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("json output")
+}
+
+fn write_session(home: &Path, day: &str, text: &str) -> PathBuf {
+    let sessions = home.join(format!(".codex/sessions/2025/12/{day}"));
+    fs::create_dir_all(&sessions).unwrap();
+    let path = sessions.join("rollout-1.jsonl");
+    fs::write(
+        &path,
+        [
+            format!(
+                r#"{{"type":"turn_context","timestamp":"2025-12-{day}T10:00:00Z","payload":{{"model":"gpt-5-codex"}}}}"#
+            ),
+            format!(
+                r#"{{"type":"response_item","timestamp":"2025-12-{day}T10:00:01Z","payload":{{"role":"user","content":"{text}"}}}}"#
+            ),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    path
+}
+
+/// A machine with three indexed Codex sessions, the first two tagged `review`.
+fn sprint() -> TempDir {
+    let home = TempDir::new().unwrap();
+    let tagged = [
+        write_session(home.path(), "01", "rotate the signing keys"),
+        write_session(home.path(), "02", "bump the cache ttl"),
+    ];
+    write_session(home.path(), "03", "why is ci red");
+    base_cmd(home.path())
+        .args(["index", "--full"])
+        .assert()
+        .success();
+    for path in &tagged {
+        base_cmd(home.path())
+            .args(["tag", "add", path.to_str().unwrap(), "review"])
+            .assert()
+            .success();
+    }
+    home
+}
+
+#[test]
+fn export_html_by_tag_writes_linked_pages_and_index() {
+    let home = sprint();
+    let out = home.path().join("exports");
+    let out_arg = out.to_str().unwrap();
+
+    let result = run_json(
+        home.path(),
+        &[
+            "export-html",
+            "--tag",
+            "review",
+            "--output-dir",
+            out_arg,
+            "--no-cdns",
+            "--json",
+        ],
+    );
+    let exported = &result["exported"];
+    assert_eq!(exported["conversations"], 2);
+    assert_eq!(exported["encrypted"], false);
+
+    // Newest first
+    let files = exported["files"].as_array().unwrap();
+    let names: Vec<&str> = files
+        .iter()
+        .map(|f| f["filename"].as_str().unwrap())
+        .collect();
+    assert!(files[0]["title"].as_str().unwrap().contains("cache ttl"));
+
+    let index = fs::read_to_string(out.join("index.html")).unwrap();
+    assert!(index.contains(r#"id="index-table""#));
+    assert!(index.contains("2 of 2"));
+    assert!(!index.contains("why is ci red"));
+    for name in &names {
+        assert!(index.contains(&format!(r#"href="{name}""#)));
+    }
+
+    let newest = fs::read_to_string(out.join(names[0])).unwrap();
+    assert!(newest.contains("bump the cache ttl"));
+    assert!(newest.contains(r#"href="index.html""#));
+    assert!(newest.contains(&format!(r#"<a href="{}" rel="next">"#, names[1])));
+    assert!(!newest.contains("cdn.jsdelivr.net"));
+
+    let preview = run_json(
+        home.path(),
+        &["export-html", "--query", "ci", "--dry-run", "--json"],
+    );
+    assert_eq!(preview["conversations"], 1);
+}
+
+#[test]
+fn export_html_encrypts_whole_set_or_sessions_only() {
+    let home = sprint();
+    for (scope, index_encrypted) in [("all", true), ("sessions", false)] {
+        let out = home.path().join(scope);
+        let output = base_cmd(home.path())
+            .args([
+                "export-html",
+                "--since",
+                "2025-12-01",
+                "--output-dir",
+                out.to_str().unwrap(),
+                "--encrypt",
+                "--encrypt-scope",
+                scope,
+                "--password-stdin",
+                "--json",
+            ])
+            .write_stdin("correct horse\n")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(result["exported"]["conversations"], 3);
+        assert_eq!(result["exported"]["encrypt_scope"], scope);
+
+        for file in result["exported"]["files"].as_array().unwrap() {
+            let page = fs::read_to_string(out.join(file["filename"].as_str().unwrap())).unwrap();
+            assert!(page.contains("encrypted-content"));
+        }
+        let index = fs::read_to_string(out.join("index.html")).unwrap();
+        assert_eq!(index.contains("encrypted-content"), index_encrypted);
+    }
+}
+
+#[test]
+fn export_html_needs_a_session_or_selectors() {
+    let home = sprint();
+    base_cmd(home.path())
+        .args(["export-html", "--json"])
+        .assert()
+        .failure();
+    base_cmd(home.path())
+        .args(["export-html", "--tag", "review", "--filename", "x.html"])
+        .assert()
+        .failure();
+    base_cmd(home.path())
+        .args(["export-html", "--tag", "nothing-here", "--json"])
+        .assert()
+        .failure();
+}
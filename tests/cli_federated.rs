@@ -0,0 +1,170 @@
+#![cfg(unix)]
+
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn base_cmd(home: &Path) -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cass"));
+    cmd.env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1");
+    // Isolate connectors so only the fixture sessions are indexed
+    cmd.env("HOME", home);
+    cmd.env("XDG_DATA_HOME", home.join(".local/share"));
+    cmd.env("XDG_CONFIG_HOME", home.join(".config"));
+    cmd.env("CODEX_HOME", home.join(".codex"));
+    cmd.env("CASS_DATA_DIR", home.join("data"));
+    cmd
+}
+
+fn write_session(home: &Path, text: &str) {
+    let sessions = home.join(".codex/sessions/2025/12/01");
+    fs::create_dir_all(&sessions).unwrap();
+    fs::write(
+        sessions.join("rollout-1.jsonl"),
+        [
+            r#"{"type":"turn_context","timestamp":"2025-12-01T10:00:00Z","payload":{"model":"gpt-5-codex"}}"#.to_string(),
+            format!(
+                r#"{{"type":"response_item","timestamp":"2025-12-01T10:00:01Z","payload":{{"role":"user","content":"{text}"}}}}"#
+            ),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    base_cmd(home).args(["index", "--full"]).assert().success();
+}
+
+/// Stand-in for ssh: `dev@build` runs the command as the remote home,
+/// anything else refuses the connection.
+fn fake_ssh(bin: &Path, remote_home: &Path) {
+    let cass_dir = assert_cmd::cargo::cargo_bin!("cass")
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let script = format!(
+        r#"#!/bin/sh
+while [ "$1" != "--" ]; do shift; done
+host="$2"
+if [ "$host" != "dev@build" ]; then
+  echo "ssh: connect to host $host port 22: Connection refused" >&2
+  exit 255
+fi
+export HOME="{home}" CODEX_HOME="{home}/.codex" CASS_DATA_DIR="{home}/data"
+export XDG_CONFIG_HOME="{home}/.config" XDG_DATA_HOME="{home}/.local/share"
+export PATH="{cass}:$PATH"
+exec sh -c "$3"
+"#,
+        home = remote_home.display(),
+        cass = cass_dir.display()
+    );
+    fs::create_dir_all(bin).unwrap();
+    let ssh = bin.join("ssh");
+    fs::write(&ssh, script).unwrap();
+    fs::set_permissions(&ssh, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[test]
+fn federated_search_merges_remote_hits_and_reports_failures() {
+    let local = TempDir::new().unwrap();
+    let remote = TempDir::new().unwrap();
+    write_session(local.path(), "retry the upload locally");
+    write_session(remote.path(), "retry the upload on the build box");
+
+    let config = local.path().join(".config/cass");
+    fs::create_dir_all(&config).unwrap();
+    fs::write(
+        config.join("sources.toml"),
+        r#"
+[[sources]]
+name = "buildbox"
+type = "ssh"
+host = "dev@build"
+
+[[sources]]
+name = "offline"
+type = "ssh"
+host = "dev@offline"
+"#,
+    )
+    .unwrap();
+    let bin = local.path().join("bin");
+    fake_ssh(&bin, remote.path());
+    let path = format!(
+        "{}:{}",
+        bin.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    let output = base_cmd(local.path())
+        .env("PATH", &path)
+        .args(["search", "upload", "--federated", "--json"])
+        .output()
+        .expect("run cass");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let result: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let hits = result["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 2);
+    let remote_hit = hits
+        .iter()
+        .find(|h| h["source_id"] == "buildbox")
+        .expect("remote hit");
+    assert_eq!(remote_hit["origin_kind"], "ssh");
+    assert_eq!(remote_hit["origin_host"], "dev@build");
+    assert!(
+        remote_hit["content"]
+            .as_str()
+            .unwrap()
+            .contains("build box")
+    );
+    assert!(hits.iter().any(|h| h["source_id"] == "local"));
+
+    let federation = &result["_meta"]["federation"];
+    assert_eq!(federation["merge"], "rrf");
+    assert_eq!(federation["partial"], true);
+    let sources = federation["sources"].as_array().unwrap();
+    let status = |name: &str| {
+        sources
+            .iter()
+            .find(|s| s["source"] == name)
+            .unwrap_or_else(|| panic!("no status for {name}"))
+            .clone()
+    };
+    assert_eq!(status("buildbox")["status"], "ok");
+    assert_eq!(status("buildbox")["hits"], 1);
+    assert_eq!(status("offline")["status"], "error");
+    assert!(
+        status("offline")["error"]
+            .as_str()
+            .unwrap()
+            .contains("Connection refused")
+    );
+
+    // --source narrows the remotes that are asked
+    let output = base_cmd(local.path())
+        .env("PATH", &path)
+        .args([
+            "search",
+            "upload",
+            "--federated",
+            "--source",
+            "buildbox",
+            "--robot-format",
+            "jsonl",
+        ])
+        .output()
+        .expect("run cass");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let header: Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+    assert_eq!(header["_meta"]["federation"]["partial"], false);
+    let hits: Vec<Value> = lines.map(|l| serde_json::from_str(l).unwrap()).collect();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["source_id"], "buildbox");
+}
@@ -0,0 +1,71 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod util;
+
+use util::{SnippetSpec, fixture_claude_code, seed_fixture};
+
+fn seed_session(data_dir: &Path) {
+    seed_fixture(
+        data_dir,
+        fixture_claude_code()
+            .title("Fix parser")
+            .workspace("/repo")
+            .external_id("files-1")
+            .source_path("/sessions/files.jsonl")
+            .with_content(1, "[Tool: Edit - /repo/src/parser.rs]")
+            .with_snippet(
+                SnippetSpec::new(1)
+                    .file("/repo/src/parser.rs")
+                    .language("rust")
+                    .text("fn parse() {}"),
+            )
+            .with_snippet(
+                SnippetSpec::new(1)
+                    .file("/repo/src/lib.rs")
+                    .language("rust"),
+            ),
+    );
+}
+
+#[test]
+fn files_lists_touched_files_as_json() {
+    let tmp = TempDir::new().expect("tempdir");
+    seed_session(tmp.path());
+
+    let out = cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .args(["files", "/sessions/files.jsonl", "--json", "--data-dir"])
+        .arg(tmp.path())
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&out).expect("valid files json");
+
+    assert_eq!(json["count"], 2);
+    let parser = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|f| f["file_path"] == "/repo/src/parser.rs")
+        .expect("parser.rs listed");
+    assert_eq!(parser["edits"], 1);
+    assert_eq!(parser["reads"], 0);
+}
+
+#[test]
+fn files_unknown_session_fails() {
+    let tmp = TempDir::new().expect("tempdir");
+    seed_session(tmp.path());
+
+    cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .args(["files", "/sessions/missing.jsonl", "--json", "--data-dir"])
+        .arg(tmp.path())
+        .assert()
+        .code(2);
+}
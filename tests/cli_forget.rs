@@ -0,0 +1,192 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use coding_agent_search::model::types::ConversationSelector;
+use coding_agent_search::storage::sqlite::SqliteStorage;
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod util;
+
+use util::{fixture_codex, seed_fixture};
+
+fn seed_sessions(data_dir: &Path) {
+    for (external_id, content) in [("leak", "token sk-live-4f9a"), ("keep", "refactor notes")] {
+        seed_fixture(
+            data_dir,
+            fixture_codex()
+                .title(external_id)
+                .workspace("/repo")
+                .external_id(external_id)
+                .source_path(format!("/sessions/{external_id}.jsonl"))
+                .with_content(0, content),
+        );
+    }
+}
+
+fn forget_json(data_dir: &Path, extra: &[&str]) -> assert_cmd::assert::Assert {
+    cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .args(["forget", "--query", "sk-live-4f9a", "--json"])
+        .args(extra)
+        .arg("--data-dir")
+        .arg(data_dir)
+        .assert()
+}
+
+/// `cass` with connectors pointed into `home`, so only imports are indexed.
+fn isolated_cmd(home: &Path) -> assert_cmd::Command {
+    let mut cmd = cargo_bin_cmd!("cass");
+    cmd.env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .env("HOME", home)
+        .env("XDG_DATA_HOME", home.join(".local/share"))
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("CODEX_HOME", home.join(".codex"));
+    cmd
+}
+
+fn chatgpt_conversation(id: &str, text: &str) -> Value {
+    json!({
+        "id": id,
+        "title": id,
+        "current_node": "u1",
+        "mapping": {
+            "root": { "id": "root", "parent": null, "children": ["u1"], "message": null },
+            "u1": {
+                "id": "u1",
+                "parent": "root",
+                "children": [],
+                "message": {
+                    "author": { "role": "user" },
+                    "create_time": 1.7e9,
+                    "content": { "content_type": "text", "parts": [text] }
+                }
+            }
+        }
+    })
+}
+
+fn remaining_titles(data_dir: &Path) -> Vec<String> {
+    let storage = SqliteStorage::open(&data_dir.join("agent_search.db")).expect("open db");
+    let selector = ConversationSelector {
+        agents: vec!["codex".into()],
+        ..Default::default()
+    };
+    storage
+        .select_conversations(&selector)
+        .expect("select")
+        .into_iter()
+        .filter_map(|c| c.title)
+        .collect()
+}
+
+#[test]
+fn forget_dry_run_reports_without_deleting() {
+    let tmp = TempDir::new().expect("tempdir");
+    seed_sessions(tmp.path());
+
+    let out = forget_json(tmp.path(), &["--dry-run"])
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&out).expect("valid forget json");
+
+    assert_eq!(json["dry_run"], true);
+    assert_eq!(json["conversation_count"], 1);
+    assert_eq!(json["conversations"][0]["title"], "leak");
+    assert_eq!(remaining_titles(tmp.path()).len(), 2);
+}
+
+#[test]
+fn forget_requires_yes_in_robot_mode() {
+    let tmp = TempDir::new().expect("tempdir");
+    seed_sessions(tmp.path());
+
+    forget_json(tmp.path(), &[]).code(2);
+    assert_eq!(remaining_titles(tmp.path()).len(), 2);
+}
+
+#[test]
+fn forget_with_yes_deletes_the_selection() {
+    let tmp = TempDir::new().expect("tempdir");
+    seed_sessions(tmp.path());
+
+    let out = forget_json(tmp.path(), &["--yes"])
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&out).expect("valid forget json");
+
+    assert_eq!(json["dry_run"], false);
+    assert_eq!(json["lexical_index"], "missing");
+    assert_eq!(remaining_titles(tmp.path()), vec!["keep".to_string()]);
+}
+
+#[test]
+fn forgotten_imports_stay_gone_after_a_full_reindex() {
+    let home = TempDir::new().expect("tempdir");
+    let data = home.path().join("data");
+    let export = home.path().join("conversations.json");
+    fs::write(
+        &export,
+        json!([
+            chatgpt_conversation("leak", "token sk-live-4f9a"),
+            chatgpt_conversation("keep", "refactor notes"),
+        ])
+        .to_string(),
+    )
+    .expect("write export");
+    isolated_cmd(home.path())
+        .arg("import")
+        .arg(&export)
+        .arg("--data-dir")
+        .arg(&data)
+        .assert()
+        .success();
+    let reindex = || {
+        isolated_cmd(home.path())
+            .args(["index", "--full", "--data-dir"])
+            .arg(&data)
+            .assert()
+            .success();
+    };
+    reindex();
+
+    let out = forget_json(&data, &["--yes"])
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let json: Value = serde_json::from_slice(&out).expect("valid forget json");
+    assert_eq!(json["conversation_count"], 1);
+    assert_eq!(json["stored_copies"].as_array().map(Vec::len), Some(1));
+    let stored =
+        fs::read_to_string(data.join("imports/chatgpt-export/export.json")).expect("stored export");
+    assert!(!stored.contains("sk-live-4f9a"));
+    assert!(stored.contains("refactor notes"));
+
+    reindex();
+    let hits = |query: &str| {
+        let out = isolated_cmd(home.path())
+            .args([
+                "search",
+                query,
+                "--json",
+                "--source",
+                "chatgpt-export",
+                "--data-dir",
+            ])
+            .arg(&data)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let json: Value = serde_json::from_slice(&out).expect("valid search json");
+        json["hits"].as_array().map_or(0, Vec::len)
+    };
+    assert_eq!(hits("sk-live-4f9a"), 0);
+    assert_eq!(hits("refactor"), 1);
+}
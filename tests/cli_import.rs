@@ -0,0 +1,138 @@
+use assert_cmd::Command;
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn base_cmd(temp_home: &Path) -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cass"));
+    cmd.env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1");
+    // Isolate connectors so only the import is indexed
+    cmd.env("HOME", temp_home);
+    cmd.env("XDG_DATA_HOME", temp_home.join(".local/share"));
+    cmd.env("XDG_CONFIG_HOME", temp_home.join(".config"));
+    cmd.env("CODEX_HOME", temp_home.join(".codex"));
+    cmd
+}
+
+fn run_json(home: &Path, args: &[&str]) -> Value {
+    let output = base_cmd(home).args(args).output().expect("run cass");
+    assert!(
+        output.status.success(),
+        "cass {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("json output")
+}
+
+fn node(id: &str, parent: &str, children: &[&str], role: &str, text: &str, at: f64) -> Value {
+    json!({
+        "id": id,
+        "parent": parent,
+        "children": children,
+        "message": {
+            "author": { "role": role },
+            "create_time": at,
+            "content": { "content_type": "text", "parts": [text] }
+        }
+    })
+}
+
+#[test]
+fn imported_exports_are_searchable_and_survive_rebuilds() {
+    let home = TempDir::new().unwrap();
+    let data = home.path().join("data");
+    let data_dir = data.to_str().unwrap();
+    let export = home.path().join("conversations.json");
+    fs::write(
+        &export,
+        json!([{
+            "id": "conv-1",
+            "title": "Retry policy",
+            "current_node": "a2",
+            "mapping": {
+                "root": { "id": "root", "parent": null, "children": ["u1"], "message": null },
+                "u1": node("u1", "root", &["a1", "a2"], "user", "How should the uploader retry?", 1.7e9),
+                "a1": node("a1", "u1", &[], "assistant", "Retry forever on a fixed timer.", 1.7e9 + 5.0),
+                "a2": node("a2", "u1", &[], "assistant", "Exponential backoff with jitter.", 1.7e9 + 9.0),
+            }
+        }])
+        .to_string(),
+    )
+    .unwrap();
+    let export_path = export.to_str().unwrap();
+
+    let dry = run_json(
+        home.path(),
+        &[
+            "import",
+            export_path,
+            "--dry-run",
+            "--json",
+            "--data-dir",
+            data_dir,
+        ],
+    );
+    assert_eq!(dry["format"], "chatgpt");
+    assert_eq!(dry["source_id"], "chatgpt-export");
+    assert_eq!(dry["lexical_index"], "untouched");
+    assert!(!data.join("imports").exists());
+
+    // Without an index the import is stored and left to `cass index`
+    let report = run_json(
+        home.path(),
+        &["import", export_path, "--json", "--data-dir", data_dir],
+    );
+    assert_eq!(report["conversations"], 1);
+    assert_eq!(report["messages"], 2);
+    assert_eq!(report["alternate_branches"], 1);
+    assert_eq!(report["lexical_index"], "pending");
+    assert!(data.join("imports/chatgpt-export/export.json").is_file());
+
+    base_cmd(home.path())
+        .args(["index", "--full", "--data-dir", data_dir])
+        .assert()
+        .success();
+    let search = |query: &str, source: &str| {
+        run_json(
+            home.path(),
+            &[
+                "search",
+                query,
+                "--json",
+                "--source",
+                source,
+                "--data-dir",
+                data_dir,
+            ],
+        )
+    };
+    let hits = search("jitter", "chatgpt-export");
+    assert_eq!(hits["hits"][0]["source_id"], "chatgpt-export");
+    assert_eq!(hits["hits"][0]["agent"], "chatgpt");
+    // Only the active branch is indexed, and imports are neither local nor remote
+    assert_eq!(
+        search("timer", "all")["hits"].as_array().map(Vec::len),
+        Some(0)
+    );
+    assert_eq!(
+        search("jitter", "local")["hits"].as_array().map(Vec::len),
+        Some(0)
+    );
+    assert_eq!(
+        search("jitter", "remote")["hits"].as_array().map(Vec::len),
+        Some(0)
+    );
+
+    base_cmd(home.path())
+        .args([
+            "import",
+            export_path,
+            "--source-id",
+            "local",
+            "--data-dir",
+            data_dir,
+        ])
+        .assert()
+        .failure();
+}
@@ -0,0 +1,158 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::{Value, json};
+use std::path::Path;
+use tempfile::TempDir;
+
+mod util;
+
+use util::{fixture_codex, seed_fixture};
+
+fn seed_session(data_dir: &Path) {
+    seed_fixture(
+        data_dir,
+        fixture_codex()
+            .title("retry logic")
+            .workspace("/repo")
+            .external_id("retry")
+            .source_path("/sessions/retry.jsonl")
+            .with_content(0, "add exponential backoff"),
+    );
+}
+
+/// Send `requests` to `cass mcp` and return its responses in order.
+fn mcp_session(data_dir: &Path, requests: &[Value]) -> Vec<Value> {
+    let input: String = requests.iter().map(|r| format!("{r}\n")).collect();
+    let output = cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .args(["mcp", "--data-dir"])
+        .arg(data_dir)
+        .write_stdin(input)
+        .output()
+        .expect("run cass mcp");
+    assert!(output.status.success(), "cass mcp failed: {output:?}");
+    String::from_utf8(output.stdout)
+        .expect("utf-8 stdout")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("one JSON-RPC message per line"))
+        .collect()
+}
+
+fn request(id: i64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+#[test]
+fn mcp_serves_tools_and_session_resources() {
+    let tmp = TempDir::new().expect("tempdir");
+    seed_session(tmp.path());
+
+    let responses = mcp_session(
+        tmp.path(),
+        &[
+            request(1, "initialize", json!({ "protocolVersion": "2025-06-18" })),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            request(2, "tools/list", json!({})),
+            request(
+                3,
+                "tools/call",
+                json!({ "name": "stats", "arguments": { "by_source": true } }),
+            ),
+            request(4, "resources/list", json!({})),
+            request(
+                5,
+                "tools/call",
+                json!({ "name": "search", "arguments": { "query": "backoff" } }),
+            ),
+        ],
+    );
+    assert_eq!(responses.len(), 5, "the notification gets no reply");
+    assert_eq!(responses[0]["result"]["serverInfo"]["name"], "cass");
+    assert_eq!(
+        responses[1]["result"]["tools"].as_array().map(Vec::len),
+        Some(6)
+    );
+
+    let stats = &responses[2]["result"];
+    assert_eq!(stats["isError"], false);
+    assert_eq!(stats["structuredContent"]["conversations"], 1);
+    assert_eq!(
+        stats["structuredContent"]["by_source"][0]["source_id"],
+        "local"
+    );
+
+    let resources = responses[3]["result"]["resources"].as_array().unwrap();
+    assert_eq!(resources.len(), 1);
+    assert_eq!(resources[0]["name"], "retry logic");
+
+    // No lexical index yet, so this is served from the SQLite FTS fallback
+    let search = &responses[4]["result"];
+    assert_eq!(search["isError"], false);
+    assert_eq!(
+        search["structuredContent"]["hits"][0]["source_path"],
+        "/sessions/retry.jsonl"
+    );
+
+    let uri = resources[0]["uri"].as_str().unwrap();
+    let read = mcp_session(
+        tmp.path(),
+        &[
+            request(1, "resources/read", json!({ "uri": uri })),
+            request(
+                2,
+                "tools/call",
+                json!({ "name": "view", "arguments": { "path": "/sessions/retry.jsonl" } }),
+            ),
+        ],
+    );
+    let text = read[0]["result"]["contents"][0]["text"].as_str().unwrap();
+    let session: Value = serde_json::from_str(text).unwrap();
+    assert_eq!(session["agent"], "codex");
+    assert_eq!(session["messages"][0]["content"], "add exponential backoff");
+
+    // The session file itself is gone: a tool error, not a protocol error
+    assert_eq!(read[1]["result"]["isError"], true);
+    assert_eq!(
+        read[1]["result"]["structuredContent"]["error"]["kind"],
+        "file-not-found"
+    );
+}
+
+#[test]
+fn mcp_search_keeps_every_output_format_off_stdout() {
+    let tmp = TempDir::new().expect("tempdir");
+    seed_session(tmp.path());
+
+    let formats = ["json", "jsonl", "compact", "toon", "sessions"];
+    let requests: Vec<Value> = (1..)
+        .zip(formats)
+        .map(|(id, format)| {
+            request(
+                id,
+                "tools/call",
+                json!({
+                    "name": "search",
+                    "arguments": { "query": "backoff", "robot_format": format, "robot_meta": true }
+                }),
+            )
+        })
+        .collect();
+    // mcp_session already fails on any stdout line that is not JSON
+    let responses = mcp_session(tmp.path(), &requests);
+    assert_eq!(responses.len(), formats.len());
+    for ((id, format), response) in (1..).zip(formats).zip(&responses) {
+        assert_eq!(response["jsonrpc"], "2.0", "{format}");
+        assert_eq!(response["id"], id, "{format}");
+        assert_eq!(response["result"]["isError"], false, "{format}");
+    }
+    assert_eq!(
+        responses[0]["result"]["structuredContent"]["hits"][0]["source_path"],
+        "/sessions/retry.jsonl"
+    );
+    let sessions: Value = serde_json::from_str(
+        responses[4]["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(sessions, json!(["/sessions/retry.jsonl"]));
+}
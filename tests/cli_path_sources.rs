@@ -0,0 +1,146 @@
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn base_cmd(home: &Path) -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cass"));
+    cmd.env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1");
+    // Isolate connectors so only the fixture sessions are indexed
+    cmd.env("HOME", home);
+    cmd.env("XDG_DATA_HOME", home.join(".local/share"));
+    cmd.env("XDG_CONFIG_HOME", home.join(".config"));
+    cmd.env("CODEX_HOME", home.join(".codex"));
+    cmd.env("CASS_DATA_DIR", home.join("data"));
+    cmd
+}
+
+fn run_json(home: &Path, args: &[&str]) -> Value {
+    let output = base_cmd(home).args(args).output().expect("run cass");
+    assert!(
+        output.status.success(),
+        "cass {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter::<Value>()
+        .next()
+        .expect("json output")
+        .unwrap()
+}
+
+/// A directory laid out like another machine's home, holding one Codex session.
+fn other_home(text: &str) -> TempDir {
+    let home = TempDir::new().unwrap();
+    let sessions = home.path().join(".codex/sessions/2025/12/01");
+    fs::create_dir_all(&sessions).unwrap();
+    fs::write(
+        sessions.join("rollout-1.jsonl"),
+        [
+            r#"{"type":"turn_context","timestamp":"2025-12-01T10:00:00Z","payload":{"model":"gpt-5-codex"}}"#.to_string(),
+            format!(
+                r#"{{"type":"response_item","timestamp":"2025-12-01T10:00:01Z","payload":{{"role":"user","content":"{text}"}}}}"#
+            ),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    home
+}
+
+#[test]
+fn path_source_is_indexed_in_place_with_its_own_source_id() {
+    let local = TempDir::new().unwrap();
+    let nas = other_home("rotate the signing keys");
+    let root = nas.path().to_str().unwrap();
+
+    base_cmd(local.path())
+        .args([
+            "sources",
+            "add",
+            root,
+            "--name",
+            "nas",
+            "--path",
+            "~/.codex/sessions",
+        ])
+        .assert()
+        .success();
+
+    let listed = run_json(local.path(), &["sources", "list", "--json"]);
+    assert_eq!(listed["sources"][0]["type"], "path");
+    assert_eq!(listed["sources"][0]["root"], root);
+    assert_eq!(listed["sources"][0]["next_run"], Value::Null);
+
+    let doctor = run_json(local.path(), &["sources", "doctor", "--json"]);
+    assert_eq!(doctor[0]["source_id"], "nas");
+    assert_eq!(doctor[0]["failed"], 0);
+    assert_eq!(doctor[0]["checks"][0]["name"], "Root Directory");
+
+    base_cmd(local.path())
+        .args(["index", "--full"])
+        .assert()
+        .success();
+    let result = run_json(local.path(), &["search", "signing", "--json"]);
+    let hits = result["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["source_id"], "nas");
+    assert_eq!(hits[0]["origin_kind"], "path");
+
+    let remote = run_json(
+        local.path(),
+        &["search", "signing", "--source", "remote", "--json"],
+    );
+    assert_eq!(remote["hits"].as_array().unwrap().len(), 1);
+
+    // Nothing is copied for an in-place source
+    assert!(!local.path().join("data/remotes/nas/mirror").exists());
+}
+
+#[test]
+fn mirrored_path_source_is_copied_by_sync() {
+    let local = TempDir::new().unwrap();
+    let usb = other_home("bump the cache ttl");
+    let root = usb.path().to_str().unwrap();
+
+    base_cmd(local.path())
+        .args([
+            "sources",
+            "add",
+            root,
+            "--name",
+            "usb",
+            "--path",
+            "~/.codex/sessions",
+            "--mirror",
+        ])
+        .assert()
+        .success();
+
+    let synced = run_json(local.path(), &["sources", "sync", "--json"]);
+    assert_eq!(synced["sources"][0]["method"], "copy");
+    assert_eq!(synced["total_files"], 1);
+
+    // The copy stays searchable once the drive is gone
+    drop(usb);
+    let result = run_json(local.path(), &["search", "ttl", "--json"]);
+    let hits = result["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0]["source_id"], "usb");
+    assert_eq!(hits[0]["origin_kind"], "path");
+
+    let doctor = base_cmd(local.path())
+        .args(["sources", "doctor", "--json"])
+        .output()
+        .unwrap();
+    assert!(!doctor.status.success());
+    let doctor: Value = serde_json::from_slice(&doctor.stdout).unwrap();
+    assert_eq!(doctor[0]["checks"][0]["status"], "fail");
+
+    // --mirror is meaningless for SSH sources
+    base_cmd(local.path())
+        .args(["sources", "add", "me@host", "--path", "~/x", "--mirror"])
+        .assert()
+        .failure();
+}
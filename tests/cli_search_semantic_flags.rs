@@ -74,6 +74,19 @@ fn search_parses_no_daemon_flag() {
     }
 }
 
+#[test]
+fn search_parses_exact_flag() {
+    let cli = Cli::try_parse_from(["cass", "search", "query", "--mode", "semantic", "--exact"])
+        .expect("parse search flags");
+
+    match cli.command {
+        Some(Commands::Search { exact, .. }) => {
+            assert!(exact, "exact flag should be true");
+        }
+        other => panic!("expected search command, got {other:?}"),
+    }
+}
+
 #[test]
 fn search_default_flags_are_false() {
     let cli = Cli::try_parse_from(["cass", "search", "query"]).expect("parse search flags");
@@ -0,0 +1,161 @@
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+mod util;
+
+use util::{fixture_codex, seed_fixture};
+
+const TOKEN: &str = "dashboard-token";
+
+fn seed_session(data_dir: &Path) {
+    seed_fixture(
+        data_dir,
+        fixture_codex()
+            .title("retry logic")
+            .workspace("/repo")
+            .external_id("retry")
+            .source_path("/sessions/retry.jsonl")
+            .with_content(0, "add exponential backoff"),
+    );
+}
+
+/// A running `cass serve`, killed on drop.
+struct Server {
+    child: Child,
+    addr: String,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn start_server(data_dir: &Path) -> Server {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cass"))
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .args(["serve", "--listen", "127.0.0.1:0", "--token", TOKEN])
+        .arg("--data-dir")
+        .arg(data_dir)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn cass serve");
+
+    // Keep draining stderr so logging never blocks the server
+    let stderr = child.stderr.take().expect("stderr");
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            if let Some(addr) = line.split("listening on http://").nth(1) {
+                let _ = tx.send(addr.trim().to_string());
+            }
+        }
+    });
+    let addr = rx
+        .recv_timeout(Duration::from_secs(30))
+        .expect("server reports its address");
+    Server { child, addr }
+}
+
+/// GET `target` and return the status code and JSON body.
+fn get(server: &Server, target: &str, token: Option<&str>) -> (u16, Value) {
+    let mut stream = TcpStream::connect(&server.addr).expect("connect");
+    let mut request = format!("GET {target} HTTP/1.1\r\nHost: localhost\r\n");
+    if let Some(token) = token {
+        request.push_str(&format!("Authorization: Bearer {token}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).expect("send request");
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("read response");
+    let (head, body) = response.split_once("\r\n\r\n").expect("http response");
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .expect("status code");
+    (status, serde_json::from_str(body).expect("json body"))
+}
+
+#[test]
+fn serve_answers_with_robot_mode_payloads() {
+    let tmp = TempDir::new().expect("tempdir");
+    seed_session(tmp.path());
+    let server = start_server(tmp.path());
+
+    let (status, body) = get(&server, "/stats", None);
+    assert_eq!(status, 401);
+    assert_eq!(body["error"]["kind"], "auth");
+
+    let (status, stats) = get(&server, "/stats?by_source=true", Some(TOKEN));
+    assert_eq!(status, 200);
+    assert_eq!(stats["conversations"], 1);
+    assert_eq!(stats["by_source"][0]["source_id"], "local");
+
+    // No lexical index yet, so this is served from the SQLite FTS fallback
+    let (status, search) = get(
+        &server,
+        "/search?query=exponential+backoff&fields=minimal&robot-meta",
+        Some(TOKEN),
+    );
+    assert_eq!(status, 200);
+    assert_eq!(search["hits"][0]["source_path"], "/sessions/retry.jsonl");
+    assert!(search["hits"][0].get("content").is_none());
+    assert!(search["_meta"]["elapsed_ms"].is_number());
+
+    let (status, sessions) = get(&server, "/sessions", Some(TOKEN));
+    assert_eq!(status, 200);
+    assert_eq!(sessions["sessions"].as_array().map(Vec::len), Some(1));
+    assert!(sessions["next_offset"].is_null());
+    let id = &sessions["sessions"][0]["id"];
+    let (status, session) = get(&server, &format!("/sessions/{id}"), Some(TOKEN));
+    assert_eq!(status, 200);
+    assert_eq!(session["title"], "retry logic");
+    assert_eq!(session["messages"][0]["content"], "add exponential backoff");
+    assert_eq!(get(&server, "/sessions/999", Some(TOKEN)).0, 404);
+
+    // Only indexed session files can be expanded
+    let (status, denied) = get(&server, "/expand?path=/etc/passwd&line=1", Some(TOKEN));
+    assert_eq!(status, 403);
+    assert_eq!(denied["error"]["kind"], "not-indexed");
+
+    let (status, bad) = get(&server, "/search?query=x&data_dir=/etc", Some(TOKEN));
+    assert_eq!(status, 400);
+    assert_eq!(bad["error"]["kind"], "usage");
+
+    let (status, health) = get(&server, "/health", Some(TOKEN));
+    assert_eq!(status, 503, "no index has been built");
+    assert_eq!(health["healthy"], false);
+    assert_eq!(health["state"]["database"]["exists"], true);
+}
+
+#[test]
+fn stalled_client_does_not_block_other_requests() {
+    let tmp = TempDir::new().expect("tempdir");
+    seed_session(tmp.path());
+    let server = start_server(tmp.path());
+
+    // Connects but never sends a request
+    let stalled = TcpStream::connect(&server.addr).expect("connect");
+    std::thread::sleep(Duration::from_millis(200));
+
+    let started = std::time::Instant::now();
+    let (status, stats) = get(&server, "/stats", Some(TOKEN));
+    assert_eq!(status, 200);
+    assert_eq!(stats["conversations"], 1);
+    assert!(
+        started.elapsed() < Duration::from_secs(5),
+        "request waited {:?} behind the stalled client",
+        started.elapsed()
+    );
+    drop(stalled);
+}
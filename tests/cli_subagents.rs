@@ -0,0 +1,137 @@
+use assert_cmd::Command;
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn run_json(home: &Path, args: &[&str]) -> Value {
+    let output = Command::new(assert_cmd::cargo::cargo_bin!("cass"))
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .env("HOME", home)
+        .args(args)
+        .output()
+        .expect("run cass");
+    assert!(
+        output.status.success(),
+        "cass {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("json output")
+}
+
+fn entry(kind: &str, uuid: &str, parent: Option<&str>, content: Value) -> Value {
+    json!({
+        "type": kind,
+        "uuid": uuid,
+        "parentUuid": parent,
+        "sessionId": "sess-1",
+        "message": { "role": kind, "content": content }
+    })
+}
+
+fn sidechain(uuid: &str, parent: Option<&str>, kind: &str, text: &str) -> Value {
+    let mut line = entry(kind, uuid, parent, json!(text));
+    line["isSidechain"] = json!(true);
+    line
+}
+
+fn lines_of(messages: &Value) -> Vec<u64> {
+    messages
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["line"].as_u64().unwrap())
+        .collect()
+}
+
+#[test]
+fn expand_shows_or_collapses_inline_subagent_threads() {
+    let home = TempDir::new().unwrap();
+    let session = home.path().join("sess-1.jsonl");
+    let lines = [
+        entry("user", "u1", None, json!("Trace foo callers")),
+        entry(
+            "assistant",
+            "a1",
+            Some("u1"),
+            json!([
+                { "type": "text", "text": "Delegating the search" },
+                {
+                    "type": "tool_use",
+                    "id": "toolu_1",
+                    "name": "Task",
+                    "input": { "description": "Find callers", "prompt": "Find all callers of foo" }
+                }
+            ]),
+        ),
+        sidechain("s1", None, "user", "Find all callers of foo"),
+        entry("user", "r1", Some("a1"), json!("Subagent finished")),
+        sidechain("s2", Some("s1"), "assistant", "Found 3 callers"),
+        entry("assistant", "a2", Some("r1"), json!("foo has 3 callers")),
+    ];
+    let content: Vec<String> = lines.iter().map(Value::to_string).collect();
+    fs::write(&session, content.join("\n")).unwrap();
+    let path = session.to_str().unwrap();
+
+    // Without the flag the file order is kept
+    let plain = run_json(
+        home.path(),
+        &["expand", path, "-n", "1", "-C", "10", "--json"],
+    );
+    assert_eq!(lines_of(&plain), vec![1, 2, 3, 4, 5, 6]);
+    assert!(plain[2].get("subagent").is_none());
+
+    let shown = run_json(
+        home.path(),
+        &[
+            "expand",
+            path,
+            "-n",
+            "1",
+            "-C",
+            "10",
+            "--subagents",
+            "show",
+            "--json",
+        ],
+    );
+    assert_eq!(lines_of(&shown), vec![1, 2, 3, 5, 4, 6]);
+    assert_eq!(shown[1]["spawns"][0]["description"], "Find callers");
+    assert_eq!(shown[1]["spawns"][0]["collapsed"], false);
+    assert_eq!(shown[3]["subagent"], "Find callers");
+
+    let collapsed = run_json(
+        home.path(),
+        &[
+            "expand",
+            path,
+            "-n",
+            "6",
+            "-C",
+            "10",
+            "--subagents",
+            "collapse",
+            "--json",
+        ],
+    );
+    assert_eq!(lines_of(&collapsed), vec![1, 2, 4, 6]);
+    assert_eq!(collapsed[1]["spawns"][0]["collapsed"], true);
+
+    // A target inside a thread keeps that thread open
+    let inside = run_json(
+        home.path(),
+        &[
+            "expand",
+            path,
+            "-n",
+            "5",
+            "-C",
+            "10",
+            "--subagents",
+            "collapse",
+            "--json",
+        ],
+    );
+    assert_eq!(lines_of(&inside), vec![1, 2, 3, 5, 4, 6]);
+    assert_eq!(inside[3]["is_target"], true);
+}
@@ -0,0 +1,117 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::Value;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod util;
+
+use util::{fixture_codex, seed_fixture};
+
+fn seed_conversation(data_dir: &Path, source_path: &str) {
+    seed_fixture(
+        data_dir,
+        fixture_codex()
+            .title("Incident review")
+            .external_id(source_path)
+            .source_path(source_path),
+    );
+}
+
+fn tag_json(data_dir: &Path, args: &[&str]) -> Value {
+    let out = cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .arg("tag")
+        .args(args)
+        .arg("--data-dir")
+        .arg(data_dir)
+        .arg("--json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&out).expect("valid tag json")
+}
+
+#[test]
+fn tag_add_list_remove_roundtrip() {
+    let tmp = TempDir::new().expect("tempdir");
+    let data_dir = tmp.path();
+    seed_conversation(data_dir, "/sessions/a.jsonl");
+    seed_conversation(data_dir, "/sessions/b.jsonl");
+
+    let added = tag_json(
+        data_dir,
+        &["add", "/sessions/a.jsonl", "Postmortem", "migration-2026"],
+    );
+    assert_eq!(
+        added["tags"],
+        serde_json::json!(["migration-2026", "postmortem"])
+    );
+    tag_json(data_dir, &["add", "/sessions/b.jsonl", "postmortem"]);
+
+    let all = tag_json(data_dir, &["list"]);
+    assert_eq!(all["count"], 2);
+    assert_eq!(all["tags"][1]["name"], "postmortem");
+    assert_eq!(all["tags"][1]["conversations"], 2);
+
+    let removed = tag_json(data_dir, &["remove", "/sessions/a.jsonl", "postmortem"]);
+    assert_eq!(removed["changed"], 1);
+    let one = tag_json(data_dir, &["list", "/sessions/a.jsonl"]);
+    assert_eq!(one["tags"], serde_json::json!(["migration-2026"]));
+}
+
+#[test]
+fn tag_add_unknown_session_fails() {
+    let tmp = TempDir::new().expect("tempdir");
+    let data_dir = tmp.path();
+    seed_conversation(data_dir, "/sessions/a.jsonl");
+
+    cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .args([
+            "tag",
+            "add",
+            "/sessions/missing.jsonl",
+            "x",
+            "--json",
+            "--data-dir",
+        ])
+        .arg(data_dir)
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn search_tag_filters_conversation_tags_not_bookmarks() {
+    let tmp = TempDir::new().expect("tempdir");
+    let data_dir = tmp.path();
+    seed_conversation(data_dir, "/sessions/a.jsonl");
+    seed_conversation(data_dir, "/sessions/b.jsonl");
+    tag_json(data_dir, &["add", "/sessions/a.jsonl", "postmortem"]);
+
+    let hit_paths = |flag: &str| -> Vec<String> {
+        let out = cargo_bin_cmd!("cass")
+            .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+            .args(["search", "msg", flag, "postmortem", "--json", "--data-dir"])
+            .arg(data_dir)
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let json: Value = serde_json::from_slice(&out).expect("valid search json");
+        let mut paths: Vec<String> = json["hits"]
+            .as_array()
+            .expect("hits")
+            .iter()
+            .filter_map(|hit| hit["source_path"].as_str().map(String::from))
+            .collect();
+        paths.dedup();
+        paths
+    };
+    assert_eq!(hit_paths("--tag"), vec!["/sessions/a.jsonl"]);
+    assert_eq!(hit_paths("--session-tag"), vec!["/sessions/a.jsonl"]);
+    // No session is bookmarked with that tag
+    assert!(hit_paths("--bookmark-tag").is_empty());
+}
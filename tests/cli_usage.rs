@@ -0,0 +1,99 @@
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn base_cmd(temp_home: &Path) -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("cass"));
+    cmd.env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1");
+    // Isolate connectors so only the fixture session is indexed
+    cmd.env("HOME", temp_home);
+    cmd.env("XDG_DATA_HOME", temp_home.join(".local/share"));
+    cmd.env("XDG_CONFIG_HOME", temp_home.join(".config"));
+    cmd.env("CODEX_HOME", temp_home.join(".codex"));
+    cmd.env("CASS_PRICING_FILE", temp_home.join("pricing.toml"));
+    cmd
+}
+
+fn run_json(home: &Path, args: &[&str]) -> Value {
+    let output = base_cmd(home).args(args).output().expect("run cass");
+    assert!(
+        output.status.success(),
+        "cass {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("json output")
+}
+
+fn token_count(input: i64, cached: i64, output: i64) -> String {
+    let usage = format!(
+        r#"{{"input_tokens":{input},"cached_input_tokens":{cached},"output_tokens":{output},"total_tokens":{}}}"#,
+        input + output
+    );
+    format!(
+        r#"{{"type":"event_msg","timestamp":"2025-12-01T10:00:09Z","payload":{{"type":"token_count","info":{{"total_token_usage":{usage},"last_token_usage":{usage}}}}}}}"#
+    )
+}
+
+#[test]
+fn usage_reports_tokens_and_cost_per_model() {
+    let home = TempDir::new().unwrap();
+    let data = home.path().join("data");
+    let data_dir = data.to_str().unwrap();
+    let sessions = home.path().join(".codex/sessions/2025/12/01");
+    fs::create_dir_all(&sessions).unwrap();
+    fs::write(
+        sessions.join("rollout-usage.jsonl"),
+        [
+            r#"{"type":"turn_context","timestamp":"2025-12-01T10:00:00Z","payload":{"model":"gpt-5-codex"}}"#.to_string(),
+            r#"{"type":"response_item","timestamp":"2025-12-01T10:00:01Z","payload":{"role":"user","content":"Fix the flaky test"}}"#.to_string(),
+            r#"{"type":"response_item","timestamp":"2025-12-01T10:00:02Z","payload":{"role":"assistant","content":"Pinned the seed"}}"#.to_string(),
+            token_count(1_200_000, 1_000_000, 100_000),
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    fs::write(
+        home.path().join("pricing.toml"),
+        "[models.\"gpt-5\"]\ninput = 1.25\noutput = 10.0\ncache_read = 0.125\n",
+    )
+    .unwrap();
+
+    base_cmd(home.path())
+        .args(["index", "--full", "--data-dir", data_dir])
+        .assert()
+        .success();
+
+    let report = run_json(
+        home.path(),
+        &["usage", "--by", "model", "--json", "--data-dir", data_dir],
+    );
+    assert_eq!(report["group_by"], "model");
+    let row = &report["rows"][0];
+    assert_eq!(row["key"], "gpt-5-codex");
+    assert_eq!(row["sessions"], 1);
+    assert_eq!(row["input_tokens"], 200_000);
+    assert_eq!(row["cache_read_tokens"], 1_000_000);
+    assert_eq!(row["output_tokens"], 100_000);
+    // 0.25 input + 0.125 cache read + 1.0 output
+    let cost = row["cost_usd"].as_f64().unwrap();
+    assert!((cost - 1.375).abs() < 1e-9, "cost {cost}");
+    assert_eq!(report["unpriced_models"].as_array().map(Vec::len), Some(0));
+
+    let by_agent = run_json(
+        home.path(),
+        &[
+            "usage",
+            "--by",
+            "agent",
+            "--since",
+            "2026-01-01",
+            "--json",
+            "--data-dir",
+            data_dir,
+        ],
+    );
+    assert_eq!(by_agent["rows"].as_array().map(Vec::len), Some(0));
+    assert_eq!(by_agent["totals"]["total_tokens"], 0);
+}
@@ -0,0 +1,195 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::str::contains;
+use serde_json::Value;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod util;
+
+use util::{fixture_codex, seed_fixture};
+
+const PASSWORD: &str = "correct horse battery";
+
+fn seed_session(data_dir: &Path) {
+    seed_fixture(
+        data_dir,
+        fixture_codex()
+            .title("private")
+            .workspace("/repo")
+            .external_id("private")
+            .source_path("/sessions/private.jsonl")
+            .with_content(0, "quarterly numbers"),
+    );
+}
+
+fn cass(data_dir: &Path, args: &[&str], stdin: &str) -> assert_cmd::assert::Assert {
+    cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .args(args)
+        .arg("--data-dir")
+        .arg(data_dir)
+        .write_stdin(stdin)
+        .assert()
+}
+
+fn json_of(assert: assert_cmd::assert::Assert) -> Value {
+    serde_json::from_slice(&assert.get_output().stdout).expect("valid json")
+}
+
+/// Whether `needle` appears in any file under `dir`.
+fn contains_bytes(dir: &Path, needle: &[u8]) -> bool {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .any(|entry| {
+            std::fs::read(entry.path())
+                .expect("read file")
+                .windows(needle.len())
+                .any(|window| window == needle)
+        })
+}
+
+#[test]
+fn vault_locks_and_unlocks_the_data_dir() {
+    let tmp = TempDir::new().expect("tempdir");
+    let data_dir = tmp.path();
+    seed_session(data_dir);
+    let bundle = data_dir.join("bundles/laptop/bundle-1.cassbundle");
+    std::fs::create_dir_all(bundle.parent().unwrap()).expect("bundles dir");
+    std::fs::write(&bundle, "bundled session: quarterly numbers").expect("write bundle");
+    let export = data_dir.join("imports/chatgpt/export.json");
+    std::fs::create_dir_all(export.parent().unwrap()).expect("imports dir");
+    std::fs::write(&export, "exported chat: quarterly numbers").expect("write export");
+    let password_line = format!("{PASSWORD}\n");
+
+    let init = json_of(
+        cass(
+            data_dir,
+            &["vault", "init", "--password-stdin", "--json"],
+            &password_line,
+        )
+        .success(),
+    );
+    assert_eq!(init["vault"]["locked"], true);
+    assert_eq!(init["vault"]["password_slots"], 1);
+    assert!(!data_dir.join("agent_search.db").exists());
+    assert!(!bundle.exists());
+    assert!(!contains_bytes(data_dir, b"bundled session"));
+    assert!(!export.exists());
+    assert!(!contains_bytes(data_dir, b"exported chat"));
+
+    cass(data_dir, &["stats", "--json"], "")
+        .code(3)
+        .stderr(contains("vault_locked"));
+    let status = json_of(cass(data_dir, &["status", "--json"], ""));
+    assert_eq!(status["vault"]["locked"], true);
+    assert_eq!(status["healthy"], false);
+
+    cass(
+        data_dir,
+        &["vault", "unlock", "--password-stdin", "--json"],
+        "wrong password\n",
+    )
+    .code(6);
+
+    let unlocked = json_of(
+        cass(
+            data_dir,
+            &["vault", "unlock", "--password-stdin", "--json"],
+            &password_line,
+        )
+        .success(),
+    );
+    assert_eq!(unlocked["vault"]["locked"], false);
+    assert!(unlocked["vault"]["expires_at"].is_string());
+    assert!(bundle.exists());
+    assert!(export.exists());
+    let stats = json_of(cass(data_dir, &["stats", "--json"], "").success());
+    assert_eq!(stats["conversations"], 1);
+
+    let locked = json_of(cass(data_dir, &["vault", "lock", "--json"], "").success());
+    assert_eq!(locked["vault"]["locked"], true);
+    assert!(!data_dir.join("agent_search.db").exists());
+}
+
+#[test]
+fn reporting_commands_do_not_lock_an_expired_session() {
+    let tmp = TempDir::new().expect("tempdir");
+    let data_dir = tmp.path();
+    seed_session(data_dir);
+    let password_line = format!("{PASSWORD}\n");
+    cass(
+        data_dir,
+        &["vault", "init", "--password-stdin", "--json"],
+        &password_line,
+    )
+    .success();
+    cass(
+        data_dir,
+        &[
+            "vault",
+            "unlock",
+            "--timeout",
+            "0",
+            "--password-stdin",
+            "--json",
+        ],
+        &password_line,
+    )
+    .success();
+
+    for args in [
+        &["status", "--json"][..],
+        &["health", "--json"][..],
+        &["vault", "status", "--json"][..],
+    ] {
+        let report = json_of(cass(data_dir, args, ""));
+        assert_eq!(report["vault"]["locked"], false, "{args:?}");
+        assert!(data_dir.join("agent_search.db").exists(), "{args:?}");
+    }
+
+    // The next command that uses the data dir locks it
+    cass(data_dir, &["stats", "--json"], "")
+        .code(3)
+        .stderr(contains("vault_locked"));
+}
+
+#[test]
+fn vault_unlocks_with_a_key_file() {
+    let tmp = TempDir::new().expect("tempdir");
+    let data_dir = tmp.path().join("data");
+    std::fs::create_dir_all(&data_dir).expect("data dir");
+    seed_session(&data_dir);
+    let key_file = tmp.path().join("cass.key");
+    let key_file_arg = key_file.to_str().expect("utf-8 path");
+
+    let init = json_of(
+        cass(
+            &data_dir,
+            &[
+                "vault",
+                "init",
+                "--key-file",
+                key_file_arg,
+                "--password-stdin",
+                "--json",
+            ],
+            &format!("{PASSWORD}\n"),
+        )
+        .success(),
+    );
+    assert_eq!(init["key_file_created"], key_file_arg);
+    assert_eq!(init["vault"]["key_file_slots"], 1);
+
+    cass(
+        &data_dir,
+        &["vault", "unlock", "--key-file", key_file_arg, "--json"],
+        "",
+    )
+    .success();
+    assert!(data_dir.join("agent_search.db").exists());
+
+    cass(&data_dir, &["vault", "disable", "--json"], "").success();
+    cass(&data_dir, &["stats", "--json"], "").success();
+}
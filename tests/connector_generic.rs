@@ -0,0 +1,97 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+use coding_agent_search::connectors::{
+    Connector, ScanContext, ScanRoot, generic::GenericConnector,
+};
+use serial_test::serial;
+
+/// Write a definition for `acme` sessions under `sessions` and point cass at it.
+fn install_definition(dir: &TempDir, sessions: &std::path::Path) {
+    let defs = dir.path().join("connectors");
+    fs::create_dir_all(&defs).unwrap();
+    fs::write(
+        defs.join("acme.toml"),
+        format!(
+            r#"
+slug = "acme"
+format = "jsonl"
+roots = ["{}/**/*.jsonl"]
+
+[mapping]
+role = "/msg/author"
+content = "/msg/body"
+timestamp = "/ts"
+workspace = "/cwd"
+session_id = "/session"
+
+[roles]
+human = "user"
+"#,
+            sessions.display()
+        ),
+    )
+    .unwrap();
+    // Broken definitions are skipped, not fatal
+    fs::write(defs.join("broken.toml"), "slug = \"codex\"\n").unwrap();
+    unsafe {
+        std::env::set_var("CASS_CONNECTORS_DIR", &defs);
+    }
+}
+
+#[test]
+#[serial]
+fn generic_connector_reads_declared_jsonl_sessions() {
+    let dir = TempDir::new().unwrap();
+    let sessions = dir.path().join("acme/sessions");
+    fs::create_dir_all(sessions.join("2024-06")).unwrap();
+    fs::write(
+        sessions.join("2024-06/s1.jsonl"),
+        r#"{"session":"s1","cwd":"/work/api"}
+{"msg":{"author":"human","body":"why does the retry loop spin?"},"ts":"2024-06-01T10:00:00Z"}
+not json
+{"msg":{"author":"assistant","body":[{"type":"text","text":"The backoff is never reset."}]},"ts":"2024-06-01T10:00:05Z"}
+"#,
+    )
+    .unwrap();
+    install_definition(&dir, &sessions);
+
+    let connector = GenericConnector::new();
+    assert_eq!(connector.definitions().len(), 1);
+    let detection = connector.detect();
+    assert!(detection.detected);
+    assert_eq!(detection.root_paths, vec![sessions.clone()]);
+
+    let convs = connector
+        .scan(&ScanContext::local_default(dir.path().to_path_buf(), None))
+        .unwrap();
+    assert_eq!(convs.len(), 1);
+    let c = &convs[0];
+    assert_eq!(c.agent_slug, "acme");
+    assert_eq!(c.external_id.as_deref(), Some("s1"));
+    assert_eq!(c.workspace, Some(PathBuf::from("/work/api")));
+    assert_eq!(c.title.as_deref(), Some("why does the retry loop spin?"));
+    assert_eq!(c.messages.len(), 2);
+    assert_eq!(c.messages[0].role, "user");
+    assert_eq!(c.messages[1].content, "The backoff is never reset.");
+    assert!(c.started_at.unwrap() < c.ended_at.unwrap());
+
+    // Watch mode rescans only the root that changed
+    let watch = ScanContext::with_roots(
+        sessions.join("2024-06"),
+        vec![ScanRoot::local(sessions.join("2024-06"))],
+        None,
+    );
+    assert_eq!(connector.scan(&watch).unwrap().len(), 1);
+    let elsewhere = ScanContext::with_roots(
+        dir.path().join("other"),
+        vec![ScanRoot::local(dir.path().join("other"))],
+        None,
+    );
+    assert!(connector.scan(&elsewhere).unwrap().is_empty());
+
+    unsafe {
+        std::env::remove_var("CASS_CONNECTORS_DIR");
+    }
+}
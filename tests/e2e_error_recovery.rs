@@ -19,6 +19,7 @@ use coding_agent_search::indexer::{self, IndexOptions};
 use coding_agent_search::model::types::{Agent, AgentKind};
 use coding_agent_search::pages::encrypt::{DecryptionEngine, EncryptionEngine, load_config};
 use coding_agent_search::pages::export::{ExportEngine, ExportFilter, PathMode};
+use coding_agent_search::search::vector_index::Quantization;
 use coding_agent_search::storage::sqlite::SqliteStorage;
 use std::fs;
 use std::path::Path;
@@ -324,6 +325,9 @@ fn test_corrupted_index_triggers_rebuild() {
         data_dir: data_dir.clone(),
         semantic: false,
         embedder: "fastembed".to_string(),
+        ann: false,
+        quantization: Quantization::F32,
+        redact_secrets: false,
         progress: None,
     };
     let result = indexer::run_index(opts, None);
@@ -352,6 +356,9 @@ fn test_corrupted_index_triggers_rebuild() {
             data_dir: data_dir.clone(),
             semantic: false,
             embedder: "fastembed".to_string(),
+            ann: false,
+            quantization: Quantization::F32,
+            redact_secrets: false,
             progress: None,
         };
         // force_rebuild should handle corrupted index gracefully
@@ -231,6 +231,87 @@ fn test_pages_bundle_key_add_revoke_cycle() {
     assert!(DecryptionEngine::unlock_with_password(config, TEST_PASSWORD_2).is_ok());
 }
 
+fn pages_key_json(action: &str, archive: &Path, args: &[&str], stdin: &str) -> Value {
+    let out = cargo_bin_cmd!("cass")
+        .env("CODING_AGENT_SEARCH_NO_UPDATE_PROMPT", "1")
+        .args(["pages", "key", action])
+        .arg(archive)
+        .args(args)
+        .args(["--password-stdin", "--json"])
+        .write_stdin(stdin)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&out).expect("valid pages key json")
+}
+
+#[test]
+fn test_pages_key_cli_revoke_then_rotate_keeps_bundle_valid() {
+    let temp_dir = TempDir::new().unwrap();
+    let artifacts = build_pipeline(&temp_dir);
+    let site_dir = &artifacts.bundle.site_dir;
+    // The bundle root resolves to its site/ directory
+    let bundle_root = site_dir.parent().unwrap();
+
+    let added = pages_key_json(
+        "add",
+        bundle_root,
+        &[],
+        &format!("{TEST_PASSWORD}\n{TEST_PASSWORD_2}\n"),
+    );
+    assert_eq!(added["slot_id"], 2);
+    assert_eq!(added["slot_type"], "password");
+
+    let out = cargo_bin_cmd!("cass")
+        .args(["pages", "key", "list", "--json"])
+        .arg(bundle_root)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let list: Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(list["active_slots"], 3);
+
+    let revoked = pages_key_json("revoke", site_dir, &["0"], &format!("{TEST_PASSWORD_2}\n"));
+    assert_eq!(revoked["revoked_slot_id"], 0);
+    assert_eq!(revoked["remaining_slots"], 2);
+    assert_eq!(verify_bundle(site_dir, false).unwrap().status, "valid");
+
+    let rotated = pages_key_json(
+        "rotate",
+        site_dir,
+        &["--recovery"],
+        &format!("{TEST_PASSWORD_2}\nthird-password\n"),
+    );
+    assert_eq!(rotated["slot_count"], 2);
+    assert_eq!(rotated["verify"]["status"], "valid");
+    assert!(rotated["recovery_secret"].is_string());
+
+    let config = load_config(site_dir).unwrap();
+    assert!(DecryptionEngine::unlock_with_password(config, TEST_PASSWORD_2).is_err());
+    let config = load_config(site_dir).unwrap();
+    assert!(DecryptionEngine::unlock_with_password(config, "third-password").is_ok());
+}
+
+#[test]
+fn test_pages_key_cli_requires_password_stdin_in_robot_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let artifacts = build_pipeline(&temp_dir);
+
+    cargo_bin_cmd!("cass")
+        .args(["pages", "key", "add", "--json"])
+        .arg(&artifacts.bundle.site_dir)
+        .assert()
+        .code(6);
+    assert_eq!(
+        key_list(&artifacts.bundle.site_dir).unwrap().active_slots,
+        2
+    );
+}
+
 #[test]
 fn test_pages_bundle_verify_detects_corruption() {
     let temp_dir = TempDir::new().unwrap();
@@ -77,6 +77,7 @@ fn make_hit(id: &str, score: f32) -> SearchHit {
         source_id: "local".to_string(),
         origin_kind: "local".to_string(),
         origin_host: None,
+        parent: None,
     }
 }
 
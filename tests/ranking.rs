@@ -31,6 +31,7 @@ fn exact_hits_rank_above_wildcards_at_equal_recency_and_score() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        parent: None,
     };
 
     let prefix = SearchHit {
@@ -84,6 +85,7 @@ fn recency_boost_can_outweigh_quality_when_far_newer() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        parent: None,
     };
 
     let newer_suffix = SearchHit {
@@ -102,6 +104,7 @@ fn recency_boost_can_outweigh_quality_when_far_newer() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        parent: None,
     };
 
     let max_created = newer_suffix.created_at.unwrap();
@@ -136,6 +139,7 @@ fn relevance_heavy_mode_prefers_quality_over_recency() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        parent: None,
     };
 
     let newer_substring = SearchHit {
@@ -154,6 +158,7 @@ fn relevance_heavy_mode_prefers_quality_over_recency() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        parent: None,
     };
 
     let older_score = blended_score(&older_exact, max_created, alpha);
@@ -189,6 +194,7 @@ fn match_quality_heavy_mode_balances_quality_and_recency() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        parent: None,
     };
 
     let implicit = SearchHit {
@@ -236,6 +242,7 @@ fn ranking_handles_missing_created_at() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        parent: None,
     };
 
     let hit_without_date = SearchHit {
@@ -254,6 +261,7 @@ fn ranking_handles_missing_created_at() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        parent: None,
     };
 
     let with_date_score = blended_score(&hit_with_date, max_created, alpha);
@@ -290,6 +298,7 @@ fn ranking_handles_zero_max_created() {
         source_id: "local".into(),
         origin_kind: "local".into(),
         origin_host: None,
+        parent: None,
     };
 
     let score = blended_score(&hit, max_created, alpha);
@@ -324,6 +333,7 @@ fn all_ranking_modes_maintain_quality_ordering_at_equal_inputs() {
             source_id: "local".into(),
             origin_kind: "local".into(),
             origin_host: None,
+            parent: None,
         };
 
         let exact_score = blended_score(&base, max_created, alpha);
@@ -88,6 +88,42 @@ fn workspace_filter_limits_results() {
     assert_eq!(hits[0].workspace, expected_ws);
 }
 
+/// Role filter should keep only messages from the requested speaker.
+#[test]
+fn role_filter_limits_results() {
+    let dir = TempDir::new().unwrap();
+    let mut index = TantivyIndex::open_or_create(dir.path()).unwrap();
+
+    // Fixture alternates roles: even indices are user turns, odd are assistant turns.
+    let conv = util::ConversationFixtureBuilder::new("codex")
+        .source_path(dir.path().join("roles.jsonl"))
+        .with_content(0, "role_term how do I rotate keys")
+        .with_content(1, "role_term rotate them with the CLI")
+        .build_normalized();
+    index.add_conversation(&conv).unwrap();
+    index.commit().unwrap();
+
+    let client = SearchClient::open(dir.path(), None)
+        .unwrap()
+        .expect("client");
+
+    let mut filters = SearchFilters::default();
+    filters.roles.insert("user".into());
+    let hits = client
+        .search("role_term", filters, 10, 0, FieldMask::FULL)
+        .expect("search");
+    assert_eq!(hits.len(), 1);
+    assert!(hits[0].content.contains("how do I"));
+
+    let mut filters = SearchFilters::default();
+    filters.roles.insert("assistant".into());
+    let hits = client
+        .search("role_term", filters, 10, 0, FieldMask::FULL)
+        .expect("search");
+    assert_eq!(hits.len(), 1);
+    assert!(hits[0].content.contains("with the CLI"));
+}
+
 /// Time filters should exclude content outside the window.
 #[test]
 fn time_filter_respects_since_until() {
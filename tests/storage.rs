@@ -1,6 +1,8 @@
 use std::path::PathBuf;
 
-use coding_agent_search::model::types::{Agent, AgentKind, Conversation, Message, MessageRole};
+use coding_agent_search::model::types::{
+    Agent, AgentKind, Conversation, ConversationSelector, Message, MessageRole, Snippet, TokenUsage,
+};
 use coding_agent_search::sources::provenance::{LOCAL_SOURCE_ID, Source, SourceKind};
 use coding_agent_search::storage::sqlite::SqliteStorage;
 
@@ -51,7 +53,7 @@ fn schema_version_created_on_open() {
     let db_path = tmp.path().join("store.db");
     let storage = SqliteStorage::open(&db_path).expect("open");
 
-    assert_eq!(storage.schema_version().unwrap(), 8);
+    assert_eq!(storage.schema_version().unwrap(), 10);
 
     // If meta row is removed, the getter surfaces an error.
     storage.raw().execute("DELETE FROM meta", []).unwrap();
@@ -252,6 +254,434 @@ fn last_scan_ts_overwrite() {
     assert_eq!(storage.get_last_scan_ts().unwrap(), Some(20));
 }
 
+#[test]
+fn redaction_counts_accumulate_and_follow_deletes() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let mut storage = SqliteStorage::open(&tmp.path().join("redact.db")).expect("open");
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+    let first = storage
+        .insert_conversation_tree(agent_id, None, &sample_conv(Some("a"), vec![msg(0, 1)]))
+        .unwrap()
+        .conversation_id;
+    let second = storage
+        .insert_conversation_tree(agent_id, None, &sample_conv(Some("b"), vec![msg(0, 1)]))
+        .unwrap()
+        .conversation_id;
+
+    assert!(!storage.get_redact_secrets().unwrap());
+    storage.set_redact_secrets(true).unwrap();
+    assert!(storage.get_redact_secrets().unwrap());
+
+    let counts = |pairs: &[(&str, i64)]| -> std::collections::BTreeMap<String, i64> {
+        pairs.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    };
+    storage
+        .add_conversation_redactions(&[
+            (first, counts(&[("openai_key", 2)])),
+            (second, counts(&[("private_key", 1)])),
+        ])
+        .unwrap();
+    // An append to the first conversation adds to its existing counts
+    storage
+        .add_conversation_redactions(&[(first, counts(&[("openai_key", 1), ("jwt", 1)]))])
+        .unwrap();
+
+    let summary = storage.redaction_summary().unwrap();
+    assert_eq!(summary.conversations, 2);
+    assert_eq!(summary.total, 5);
+    assert_eq!(
+        summary.by_kind,
+        counts(&[("jwt", 1), ("openai_key", 3), ("private_key", 1)])
+    );
+
+    storage.delete_conversations(&[first]).unwrap();
+    let summary = storage.redaction_summary().unwrap();
+    assert_eq!(summary.conversations, 1);
+    assert_eq!(summary.by_kind, counts(&[("private_key", 1)]));
+}
+
+#[test]
+fn conversation_tags_add_list_remove() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("tags.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+    let conv = sample_conv(Some("ext-tag"), vec![msg(0, 10)]);
+    let conv_id = storage
+        .insert_conversation_tree(agent_id, None, &conv)
+        .unwrap()
+        .conversation_id;
+
+    assert_eq!(
+        storage
+            .conversation_ids_for_path("/logs/demo.jsonl")
+            .unwrap(),
+        vec![conv_id]
+    );
+    assert!(
+        storage
+            .add_conversation_tag(conv_id, " PostMortem ")
+            .unwrap()
+    );
+    assert!(!storage.add_conversation_tag(conv_id, "postmortem").unwrap());
+    assert!(
+        storage
+            .add_conversation_tag(conv_id, "migration-2026")
+            .unwrap()
+    );
+    assert!(storage.add_conversation_tag(conv_id, "bad tag").is_err());
+
+    let names: Vec<String> = storage
+        .conversation_tags(conv_id)
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, vec!["migration-2026", "postmortem"]);
+
+    let wanted = ["postmortem".to_string()].into_iter().collect();
+    let paths = storage.tagged_session_paths(&wanted).unwrap();
+    assert!(paths.contains("/logs/demo.jsonl"));
+
+    assert!(
+        storage
+            .remove_conversation_tag(conv_id, "postmortem")
+            .unwrap()
+    );
+    assert!(
+        !storage
+            .remove_conversation_tag(conv_id, "postmortem")
+            .unwrap()
+    );
+    assert!(storage.tagged_session_paths(&wanted).unwrap().is_empty());
+
+    // Orphaned tags are dropped so `cass tag list` stays meaningful.
+    let listed: Vec<(String, i64)> = storage
+        .list_tags()
+        .unwrap()
+        .into_iter()
+        .map(|(t, n)| (t.name, n))
+        .collect();
+    assert_eq!(listed, vec![("migration-2026".to_string(), 1)]);
+}
+
+#[test]
+fn session_files_from_tool_snippets() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("files.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+
+    let snippet = |path: &str, lines: Option<(i64, i64)>, text: Option<&str>| Snippet {
+        id: None,
+        file_path: Some(PathBuf::from(path)),
+        start_line: lines.map(|l| l.0),
+        end_line: lines.map(|l| l.1),
+        language: Some("rust".into()),
+        snippet_text: text.map(str::to_string),
+    };
+    let mut read = msg(0, 10);
+    read.snippets = vec![snippet("/workspace/demo/src/foo.rs", Some((1, 40)), None)];
+    let mut edit = msg(1, 20);
+    edit.snippets = vec![
+        snippet(
+            "/workspace/demo/src/foo.rs",
+            Some((12, 14)),
+            Some("fn fixed() {}"),
+        ),
+        snippet("/workspace/demo/src/bar.rs", None, Some("// bar")),
+    ];
+
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+    storage
+        .insert_conversation_tree(
+            agent_id,
+            None,
+            &sample_conv(Some("ext-files"), vec![read, edit]),
+        )
+        .unwrap();
+
+    let files = storage.session_files("/logs/demo.jsonl").unwrap();
+    assert_eq!(files.len(), 2);
+    let foo = files
+        .iter()
+        .find(|f| f.file_path.ends_with("foo.rs"))
+        .expect("foo.rs listed");
+    assert_eq!((foo.edits, foo.reads), (1, 1));
+    assert_eq!((foo.first_line, foo.last_line), (Some(1), Some(40)));
+    assert_eq!(foo.last_touched_at, Some(20));
+    assert!(
+        storage
+            .session_files("/logs/other.jsonl")
+            .unwrap()
+            .is_empty()
+    );
+
+    let wanted = ["src/foo.rs".to_string()].into_iter().collect();
+    let paths = storage.session_paths_touching_files(&wanted).unwrap();
+    assert!(paths.contains("/logs/demo.jsonl"));
+    let wanted = ["oo.rs".to_string()].into_iter().collect();
+    assert!(
+        storage
+            .session_paths_touching_files(&wanted)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn select_and_delete_conversations_keeps_stats_in_sync() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("forget.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+
+    let mut leaked = msg(1, 20);
+    leaked.content = "export API_KEY=sk-live-4f9a".into();
+    leaked.snippets = vec![Snippet {
+        id: None,
+        file_path: Some(PathBuf::from("/workspace/demo/.env")),
+        start_line: None,
+        end_line: None,
+        language: None,
+        snippet_text: Some("API_KEY=sk-live-4f9a".into()),
+    }];
+    let secret = storage
+        .insert_conversation_tree(
+            agent_id,
+            None,
+            &sample_conv(Some("ext-secret"), vec![msg(0, 10), leaked]),
+        )
+        .unwrap()
+        .conversation_id;
+    let mut other = sample_conv(Some("ext-other"), vec![msg(0, 30)]);
+    other.source_path = PathBuf::from("/logs/other.jsonl");
+    storage
+        .insert_conversation_tree(agent_id, None, &other)
+        .unwrap();
+    storage.add_conversation_tag(secret, "leak").unwrap();
+
+    let by_query = ConversationSelector {
+        query: Some("sk-live-4f9a".into()),
+        ..Default::default()
+    };
+    let selected = storage.select_conversations(&by_query).unwrap();
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].conversation_id, secret);
+    assert_eq!(selected[0].message_count, 2);
+
+    let by_glob = ConversationSelector {
+        path_globs: vec!["/logs/*.jsonl".into()],
+        agents: vec!["tester".into()],
+        ..Default::default()
+    };
+    assert_eq!(storage.select_conversations(&by_glob).unwrap().len(), 2);
+
+    let by_tag = ConversationSelector {
+        tags: vec!["Leak".into()],
+        ..Default::default()
+    };
+    let selected = storage.select_conversations(&by_tag).unwrap();
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].conversation_id, secret);
+
+    let deleted = storage.delete_conversations(&[secret]).unwrap();
+    assert_eq!((deleted.conversations, deleted.messages), (1, 2));
+    assert!(storage.select_conversations(&by_query).unwrap().is_empty());
+
+    let count = |sql: &str| -> i64 { storage.raw().query_row(sql, [], |r| r.get(0)).unwrap() };
+    assert_eq!(count("SELECT COUNT(*) FROM messages"), 1);
+    assert_eq!(count("SELECT COUNT(*) FROM snippets"), 0);
+    assert_eq!(count("SELECT COUNT(*) FROM conversation_tags"), 0);
+    assert_eq!(
+        count("SELECT COUNT(*) FROM fts_messages WHERE fts_messages MATCH '\"4f9a\"'"),
+        0
+    );
+    assert_eq!(
+        count(
+            "SELECT SUM(message_count) FROM daily_stats WHERE agent_slug = 'all' AND source_id = 'all'"
+        ),
+        1
+    );
+
+    // Stats match a rebuild from the remaining rows
+    let totals_sql =
+        "SELECT SUM(session_count), SUM(message_count), SUM(total_chars) FROM daily_stats";
+    let totals = |storage: &SqliteStorage| -> (i64, i64, i64) {
+        storage
+            .raw()
+            .query_row(totals_sql, [], |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)))
+            .unwrap()
+    };
+    let incremental = totals(&storage);
+    storage.rebuild_daily_stats().unwrap();
+    assert_eq!(totals(&storage), incremental);
+}
+
+#[test]
+fn token_usage_is_stored_per_message_and_in_daily_stats() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("usage.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+
+    let with_usage = |idx: i64, model: &str, input: i64, output: i64| {
+        let mut m = msg(idx, 10 + idx);
+        m.role = MessageRole::Agent;
+        TokenUsage {
+            model: Some(model.into()),
+            input_tokens: input,
+            output_tokens: output,
+            cache_read_tokens: 100,
+            cache_write_tokens: 0,
+        }
+        .add_to_extra(&mut m.extra_json);
+        m
+    };
+    let first = storage
+        .insert_conversation_tree(
+            agent_id,
+            None,
+            &sample_conv(
+                Some("ext-a"),
+                vec![msg(0, 10), with_usage(1, "gpt-5", 1_000, 200)],
+            ),
+        )
+        .unwrap()
+        .conversation_id;
+    // Appended messages record their usage too
+    storage
+        .insert_conversation_tree(
+            agent_id,
+            None,
+            &sample_conv(
+                Some("ext-a"),
+                vec![
+                    msg(0, 10),
+                    with_usage(1, "gpt-5", 1_000, 200),
+                    with_usage(2, "gpt-5-codex", 500, 50),
+                ],
+            ),
+        )
+        .unwrap();
+    let mut other = sample_conv(Some("ext-b"), vec![with_usage(0, "gpt-5", 10, 1)]);
+    other.source_path = PathBuf::from("/logs/other.jsonl");
+    storage
+        .insert_conversation_tree(agent_id, None, &other)
+        .unwrap();
+
+    let count = |storage: &SqliteStorage, sql: &str| -> i64 {
+        storage.raw().query_row(sql, [], |r| r.get(0)).unwrap()
+    };
+    assert_eq!(count(&storage, "SELECT COUNT(*) FROM message_usage"), 3);
+    let daily = |storage: &SqliteStorage| -> (i64, i64, i64) {
+        storage
+            .raw()
+            .query_row(
+                "SELECT SUM(input_tokens), SUM(output_tokens), SUM(cache_read_tokens)
+                 FROM daily_stats WHERE agent_slug = 'all' AND source_id = 'all'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?, r.get(2)?)),
+            )
+            .unwrap()
+    };
+    assert_eq!(daily(&storage), (1_510, 251, 300));
+
+    storage.delete_conversations(&[first]).unwrap();
+    assert_eq!(count(&storage, "SELECT COUNT(*) FROM message_usage"), 1);
+    assert_eq!(daily(&storage), (10, 1, 100));
+    storage.rebuild_daily_stats().unwrap();
+    assert_eq!(daily(&storage), (10, 1, 100));
+}
+
+#[test]
+fn appending_updates_usage_reported_later_for_stored_messages() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("usage-append.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+
+    let with_usage = |idx: i64, input: i64| {
+        let mut m = msg(idx, 10 + idx);
+        m.role = MessageRole::Agent;
+        TokenUsage {
+            model: Some("gpt-5".into()),
+            input_tokens: input,
+            output_tokens: 10,
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
+        }
+        .add_to_extra(&mut m.extra_json);
+        m
+    };
+    storage
+        .insert_conversation_tree(
+            agent_id,
+            None,
+            &sample_conv(Some("ext-a"), vec![msg(0, 10), with_usage(1, 100)]),
+        )
+        .unwrap();
+    // A re-scan credits another request to the stored message and adds a new one
+    storage
+        .insert_conversation_tree(
+            agent_id,
+            None,
+            &sample_conv(
+                Some("ext-a"),
+                vec![msg(0, 10), with_usage(1, 150), with_usage(2, 40)],
+            ),
+        )
+        .unwrap();
+
+    let stored: i64 = storage
+        .raw()
+        .query_row(
+            "SELECT u.input_tokens FROM message_usage u JOIN messages m ON m.id = u.message_id
+             WHERE m.idx = 1",
+            [],
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert_eq!(stored, 150);
+    let daily = |storage: &SqliteStorage| -> (i64, i64) {
+        storage
+            .raw()
+            .query_row(
+                "SELECT SUM(input_tokens), SUM(output_tokens)
+                 FROM daily_stats WHERE agent_slug = 'all' AND source_id = 'all'",
+                [],
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap()
+    };
+    assert_eq!(daily(&storage), (190, 20));
+    storage.rebuild_daily_stats().unwrap();
+    assert_eq!(daily(&storage), (190, 20));
+}
+
+#[test]
+fn delete_source_cascade_removes_its_conversations() {
+    let tmp = tempfile::TempDir::new().unwrap();
+    let db_path = tmp.path().join("cascade.db");
+    let mut storage = SqliteStorage::open(&db_path).expect("open");
+    storage
+        .upsert_source(&Source::remote("laptop", "laptop.local"))
+        .unwrap();
+    let agent_id = storage.ensure_agent(&sample_agent()).unwrap();
+    let mut remote = sample_conv(Some("ext-remote"), vec![msg(0, 10)]);
+    remote.source_id = "laptop".into();
+    storage
+        .insert_conversation_tree(agent_id, None, &remote)
+        .unwrap();
+
+    assert!(storage.delete_source("laptop", true).expect("delete"));
+    let selector = ConversationSelector {
+        source_ids: vec!["laptop".into()],
+        ..Default::default()
+    };
+    assert!(storage.select_conversations(&selector).unwrap().is_empty());
+}
+
 #[test]
 fn unsupported_schema_version_errors() {
     let tmp = tempfile::TempDir::new().unwrap();
@@ -562,7 +992,11 @@ fn migration_from_v1_applies_v2_and_v3() {
     let storage = SqliteStorage::open(&db_path).expect("open v1 db");
 
     // Verify migration completed
-    assert_eq!(storage.schema_version().unwrap(), 8, "should migrate to v8");
+    assert_eq!(
+        storage.schema_version().unwrap(),
+        10,
+        "should migrate to v10"
+    );
 
     // Verify FTS5 table was created
     let tables: Vec<String> = storage
@@ -678,7 +1112,11 @@ fn migration_from_v2_applies_v3() {
     let storage = SqliteStorage::open(&db_path).expect("open v2 db");
 
     // Verify migration completed
-    assert_eq!(storage.schema_version().unwrap(), 8, "should migrate to v8");
+    assert_eq!(
+        storage.schema_version().unwrap(),
+        10,
+        "should migrate to v10"
+    );
 }
 
 #[test]
@@ -1039,7 +1477,11 @@ fn migration_from_v3_creates_sources_table() {
     let storage = SqliteStorage::open(&db_path).expect("open v3 db");
 
     // Verify migration completed
-    assert_eq!(storage.schema_version().unwrap(), 8, "should migrate to v8");
+    assert_eq!(
+        storage.schema_version().unwrap(),
+        10,
+        "should migrate to v10"
+    );
 
     // Verify sources table was created with local source
     let sources = storage.list_sources().expect("list_sources");
@@ -133,3 +133,20 @@ fn source_filter_display_for_status_messages() {
         "laptop"
     );
 }
+
+#[test]
+fn bookmark_hotkeys_documented_in_help() {
+    let lines = coding_agent_search::ui::tui::help_lines(
+        coding_agent_search::ui::components::theme::ThemePalette::dark(),
+    );
+    let text: String = lines.iter().map(|l| l.to_string()).collect();
+
+    assert!(
+        text.contains("Alt+B") && text.contains("bookmark"),
+        "help should mention Alt+B for bookmarking the selected hit"
+    );
+    assert!(
+        text.contains("Alt+M"),
+        "help should mention Alt+M for the bookmarks pane"
+    );
+}
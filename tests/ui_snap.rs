@@ -663,6 +663,7 @@ fn input_mode_covers_all_filter_types() {
         InputMode::CreatedTo,
         InputMode::PaneFilter,
         InputMode::DetailFind,
+        InputMode::Tag,
     ];
 
     // All should be distinct
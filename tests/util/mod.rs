@@ -3,13 +3,16 @@ pub mod e2e_log;
 use coding_agent_search::connectors::{
     NormalizedConversation, NormalizedMessage, NormalizedSnippet,
 };
-use coding_agent_search::model::types::{Conversation, Message, MessageRole, Snippet};
+use coding_agent_search::model::types::{
+    Agent, AgentKind, Conversation, Message, MessageRole, Snippet,
+};
 use coding_agent_search::search::query::{MatchType, SearchHit};
 use coding_agent_search::sources::probe::HostProbeResult;
+use coding_agent_search::storage::sqlite::SqliteStorage;
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
 use serde_json::json;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tempfile::TempDir;
 
@@ -584,6 +587,25 @@ pub fn fixture_amp() -> ConversationFixtureBuilder {
         .external_id("amp-1")
 }
 
+/// Insert the conversation built by `builder` into the database under `data_dir`,
+/// registering its agent first.
+#[allow(dead_code)]
+pub fn seed_fixture(data_dir: &Path, builder: ConversationFixtureBuilder) {
+    let mut storage = SqliteStorage::open(&data_dir.join("agent_search.db")).expect("open db");
+    let agent_id = storage
+        .ensure_agent(&Agent {
+            id: None,
+            slug: builder.agent_slug.clone(),
+            name: builder.agent_slug.clone(),
+            version: None,
+            kind: AgentKind::Cli,
+        })
+        .expect("agent");
+    storage
+        .insert_conversation_tree(agent_id, None, &builder.build_conversation())
+        .expect("insert conversation");
+}
+
 // =============================================================================
 // Multi-Source Fixture Helpers (P7.6)
 // =============================================================================